@@ -90,6 +90,24 @@ export const TITAN_SUBJECTS = {
        * Topic: titan.cmd.operator.disarm.v1
        */
       DISARM: 'titan.cmd.operator.disarm.v1',
+
+      /**
+       * Lift an arming block (e.g. after a failed startup reconciliation)
+       * Topic: titan.cmd.operator.approve_arming.v1
+       */
+      APPROVE_ARMING: 'titan.cmd.operator.approve_arming.v1',
+
+      /**
+       * Freeze new opens while exits keep being managed
+       * Topic: titan.cmd.operator.maintenance.enter.v1
+       */
+      MAINTENANCE_ENTER: 'titan.cmd.operator.maintenance.enter.v1',
+
+      /**
+       * Lift maintenance mode
+       * Topic: titan.cmd.operator.maintenance.exit.v1
+       */
+      MAINTENANCE_EXIT: 'titan.cmd.operator.maintenance.exit.v1',
       ALL: 'titan.cmd.operator.v1.>',
     },
 
@@ -359,6 +377,7 @@ export const TITAN_SUBJECTS = {
       GET_BALANCES: (venue: string) => `titan.rpc.execution.get_balances.v1.${venue}`,
       GET_BALANCES_PREFIX: 'titan.rpc.execution.get_balances.v1',
       GET_BALANCES_ALL: 'titan.rpc.execution.get_balances.v1.>',
+      GET_HEALTH: 'titan.rpc.execution.get_health.v1',
       POLICY_HASH: 'titan.req.exec.policy_hash.v1',
     },
   },
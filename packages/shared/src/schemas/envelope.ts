@@ -21,6 +21,7 @@ export interface Envelope<T> {
 
   // Security (Jan 2026)
   sig?: string;
+  sig_alg?: string; // 'hmac-sha256' (default) | 'ed25519'
   key_id?: string;
   nonce?: string;
 }
@@ -40,6 +41,7 @@ export const EnvelopeSchema = z.object({
   partition_key: z.string().optional(),
   idempotency_key: z.string().optional(),
   sig: z.string().optional(),
+  sig_alg: z.string().optional(),
   key_id: z.string().optional(),
   nonce: z.string().optional(),
   payload: z.record(z.any()), // Generic wrapper validation
@@ -64,6 +66,7 @@ export function createEnvelope<T>(
     partition_key: meta.partition_key,
     idempotency_key: meta.idempotency_key,
     sig: meta.sig,
+    sig_alg: meta.sig_alg,
     key_id: meta.key_id,
     nonce: meta.nonce,
     payload,
@@ -0,0 +1,103 @@
+//! Per-order REST latency with and without connection reuse.
+//!
+//! Run with `cargo bench --bench http_pool`. A local HTTP/1.1 server stands in
+//! for the venue and charges every new connection a simulated 1 ms handshake
+//! (TCP + TLS to a remote host), so the gap between `no_reuse` and the pooled
+//! clients is the connection churn the pool settings remove. Pooled clients
+//! should sit close to the loopback round trip; `no_reuse` pays the handshake
+//! on every order.
+
+use std::time::Duration;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use titan_execution_rs::config::HttpPoolConfig;
+use titan_execution_rs::exchange::adapter::pooled_client_builder;
+
+const HANDSHAKE: Duration = Duration::from_millis(1);
+const RESPONSE: &[u8] =
+    b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 2\r\n\r\n{}";
+
+/// Answer every request on the connection with an empty JSON body.
+async fn serve(mut socket: TcpStream) {
+    tokio::time::sleep(HANDSHAKE).await;
+    let mut buf = Vec::with_capacity(4096);
+    let mut chunk = [0u8; 4096];
+    loop {
+        let Ok(n) = socket.read(&mut chunk).await else {
+            return;
+        };
+        if n == 0 {
+            return;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        // Requests carry no body, so the header terminator ends each one
+        while let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            buf.drain(..end + 4);
+            if socket.write_all(RESPONSE).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+fn bench_order_post(c: &mut Criterion) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let addr = rt.block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                tokio::spawn(serve(socket));
+            }
+        });
+        addr
+    });
+    let url = format!(
+        "http://{}/fapi/v1/order?symbol=BTCUSDT&side=BUY&type=MARKET&quantity=0.001",
+        addr
+    );
+
+    let clients = [
+        (
+            "no_reuse",
+            HttpPoolConfig {
+                max_idle_per_host: Some(0),
+                ..Default::default()
+            },
+        ),
+        ("default", HttpPoolConfig::default()),
+        (
+            "tuned",
+            HttpPoolConfig {
+                max_idle_per_host: Some(32),
+                idle_timeout_ms: 300_000,
+                tcp_keepalive_ms: Some(30_000),
+                ..Default::default()
+            },
+        ),
+    ];
+
+    let mut group = c.benchmark_group("http_pool/order_post");
+    for (name, pool) in clients {
+        let client = pooled_client_builder(&pool).build().unwrap();
+        group.bench_function(name, |b| {
+            b.to_async(&rt).iter(|| async {
+                client
+                    .post(&url)
+                    .send()
+                    .await
+                    .unwrap()
+                    .bytes()
+                    .await
+                    .unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_order_post);
+criterion_main!(benches);
@@ -0,0 +1,242 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use tokio::sync::{Notify, Semaphore};
+
+/// Default number of intents buffered between the JetStream pull and the pipeline.
+/// Kept small so buffered messages are processed well within the consumer ack_wait.
+pub const DEFAULT_INTAKE_CAPACITY: usize = 32;
+
+/// Admission priority. Lower discriminant is served first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    /// Risk commands
+    Risk = 0,
+    /// Reduce-only exits, closes and ForceSync
+    Exit = 1,
+    /// New opens
+    Normal = 2,
+}
+
+const PRIORITY_CLASSES: usize = 3;
+
+impl Priority {
+    /// Classify a raw command by subject and (enveloped or raw) intent payload.
+    /// Unparseable payloads are Normal so they still flow to validation / DLQ.
+    pub fn classify(subject: &str, payload: &[u8]) -> Self {
+        if subject.starts_with("titan.cmd.risk.") {
+            return Priority::Risk;
+        }
+        let Ok(value) = serde_json::from_slice::<serde_json::Value>(payload) else {
+            return Priority::Normal;
+        };
+        let intent = value.get("payload").unwrap_or(&value);
+
+        let is_exit_type = matches!(
+            intent.get("type").and_then(|t| t.as_str()),
+            Some("CLOSE" | "CLOSE_LONG" | "CLOSE_SHORT" | "FORCE_SYNC")
+        );
+        let is_reduce_only = intent
+            .get("reduce_only")
+            .or_else(|| intent.get("metadata").and_then(|m| m.get("reduce_only")))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        if is_exit_type || is_reduce_only {
+            Priority::Exit
+        } else {
+            Priority::Normal
+        }
+    }
+}
+
+struct Shared<T> {
+    queues: Mutex<[VecDeque<T>; PRIORITY_CLASSES]>,
+    items: Notify,
+    capacity: Semaphore,
+    senders: AtomicUsize,
+}
+
+/// Sending half of a bounded priority channel. Blocks when the channel is full.
+pub struct PrioritySender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// Receiving half: always yields the oldest item of the highest non-empty class.
+pub struct PriorityReceiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// Bounded multi-class channel. FIFO within a class, strict priority across classes.
+pub fn priority_channel<T>(capacity: usize) -> (PrioritySender<T>, PriorityReceiver<T>) {
+    let shared = Arc::new(Shared {
+        queues: Mutex::new(std::array::from_fn(|_| VecDeque::new())),
+        items: Notify::new(),
+        capacity: Semaphore::new(capacity.max(1)),
+        senders: AtomicUsize::new(1),
+    });
+    (
+        PrioritySender {
+            shared: shared.clone(),
+        },
+        PriorityReceiver { shared },
+    )
+}
+
+impl<T> PrioritySender<T> {
+    pub async fn send(&self, priority: Priority, item: T) {
+        if let Ok(permit) = self.shared.capacity.acquire().await {
+            permit.forget();
+        }
+        self.shared.queues.lock()[priority as usize].push_back(item);
+        self.shared.items.notify_one();
+    }
+}
+
+impl<T> Clone for PrioritySender<T> {
+    fn clone(&self) -> Self {
+        self.shared.senders.fetch_add(1, Ordering::SeqCst);
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Drop for PrioritySender<T> {
+    fn drop(&mut self) {
+        if self.shared.senders.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.shared.items.notify_one();
+        }
+    }
+}
+
+impl<T> PriorityReceiver<T> {
+    pub fn try_recv(&self) -> Option<T> {
+        let item = self
+            .shared
+            .queues
+            .lock()
+            .iter_mut()
+            .find_map(|queue| queue.pop_front());
+        if item.is_some() {
+            self.shared.capacity.add_permits(1);
+        }
+        item
+    }
+
+    /// Wait for the next item. Returns `None` once all senders are gone and the
+    /// channel is drained.
+    pub async fn recv(&mut self) -> Option<T> {
+        loop {
+            let notified = self.shared.items.notified();
+            if let Some(item) = self.try_recv() {
+                return Some(item);
+            }
+            if self.shared.senders.load(Ordering::SeqCst) == 0 {
+                return None;
+            }
+            notified.await;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.shared.queues.lock().iter().map(|q| q.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intent_envelope(signal_id: &str, intent_type: &str) -> Vec<u8> {
+        serde_json::to_vec(&serde_json::json!({
+            "type": "titan.cmd.execution.place.v1",
+            "payload": { "signal_id": signal_id, "type": intent_type }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_classify() {
+        let subject = "titan.cmd.execution.place.v1.BTCUSDT";
+        assert_eq!(
+            Priority::classify(subject, &intent_envelope("a", "BUY_SETUP")),
+            Priority::Normal
+        );
+        assert_eq!(
+            Priority::classify(subject, &intent_envelope("b", "CLOSE_LONG")),
+            Priority::Exit
+        );
+        assert_eq!(
+            Priority::classify(subject, &intent_envelope("c", "FORCE_SYNC")),
+            Priority::Exit
+        );
+        assert_eq!(
+            Priority::classify(
+                subject,
+                br#"{"type":"BUY_SETUP","metadata":{"reduce_only":true}}"#
+            ),
+            Priority::Exit
+        );
+        assert_eq!(
+            Priority::classify("titan.cmd.risk.flatten.v1", b"{}"),
+            Priority::Risk
+        );
+        assert_eq!(Priority::classify(subject, b"not json"), Priority::Normal);
+    }
+
+    #[tokio::test]
+    async fn test_reduce_only_jumps_queued_opens() {
+        let (tx, mut rx) = priority_channel(DEFAULT_INTAKE_CAPACITY);
+        let subject = "titan.cmd.execution.place.v1.BTCUSDT";
+
+        for (id, kind) in [
+            ("open-1", "BUY_SETUP"),
+            ("open-2", "SELL_SETUP"),
+            ("open-3", "BUY_SETUP"),
+            ("exit-1", "CLOSE"),
+        ] {
+            let payload = intent_envelope(id, kind);
+            tx.send(Priority::classify(subject, &payload), id).await;
+        }
+        tx.send(
+            Priority::classify("titan.cmd.risk.control.v1", b"{}"),
+            "risk-1",
+        )
+        .await;
+        drop(tx);
+
+        let mut order = Vec::new();
+        while let Some(id) = rx.recv().await {
+            order.push(id);
+        }
+        // Risk first, then the exit, then opens in arrival order
+        assert_eq!(
+            order,
+            vec!["risk-1", "exit-1", "open-1", "open-2", "open-3"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bounded_send_waits_for_capacity() {
+        let (tx, mut rx) = priority_channel(1);
+        tx.send(Priority::Normal, 1).await;
+
+        let sender = tokio::spawn(async move {
+            tx.send(Priority::Exit, 2).await;
+        });
+        tokio::task::yield_now().await;
+        assert_eq!(rx.len(), 1);
+
+        assert_eq!(rx.recv().await, Some(1));
+        sender.await.unwrap();
+        assert_eq!(rx.recv().await, Some(2));
+        assert_eq!(rx.recv().await, None);
+    }
+}
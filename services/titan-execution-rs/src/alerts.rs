@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tracing::{error, warn};
+
+use crate::config::AlertsConfig;
+
+pub const KIND_HALT_ENGAGED: &str = "halt_engaged";
+pub const KIND_POSITION_DIVERGENCE: &str = "position_divergence";
+pub const KIND_BREAKER_TRIPPED: &str = "breaker_tripped";
+pub const KIND_OVERFILL: &str = "overfill";
+pub const KIND_POSITION_TRANSFER: &str = "position_transfer";
+pub const KIND_HEARTBEAT_LOST: &str = "heartbeat_lost";
+pub const KIND_REJECTION_RATE: &str = "rejection_rate";
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, Default,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertSeverity {
+    Info,
+    #[default]
+    Warning,
+    Critical,
+}
+
+impl AlertSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertSeverity::Info => "INFO",
+            AlertSeverity::Warning => "WARNING",
+            AlertSeverity::Critical => "CRITICAL",
+        }
+    }
+}
+
+/// One operator-facing event. `kind` is a stable key, also used for rate limiting.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Alert {
+    pub severity: AlertSeverity,
+    pub kind: String,
+    pub message: String,
+    pub ts: i64,
+}
+
+impl Alert {
+    pub fn new(severity: AlertSeverity, kind: &str, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            kind: kind.to_string(),
+            message: message.into(),
+            ts: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    pub fn critical(kind: &str, message: impl Into<String>) -> Self {
+        Self::new(AlertSeverity::Critical, kind, message)
+    }
+}
+
+/// Destination for critical events, on top of logs and NATS. Called from hot
+/// and synchronous paths, so implementations must not block.
+pub trait AlertSink: Send + Sync {
+    fn send(&self, alert: Alert);
+}
+
+/// Posts alerts as JSON to a webhook (Slack incoming webhook, PagerDuty relay, ...).
+/// Alerts below `min_severity` are dropped, and each kind is sent at most once
+/// per `rate_limit_ms` and severity so a flapping condition cannot flood the
+/// channel, while an escalation (a warning turning critical) still goes out.
+pub struct WebhookAlertSink {
+    client: Client,
+    url: String,
+    min_severity: AlertSeverity,
+    rate_limit_ms: i64,
+    last_sent: Mutex<HashMap<(String, AlertSeverity), i64>>,
+}
+
+impl WebhookAlertSink {
+    pub fn new(url: String, min_severity: AlertSeverity, rate_limit_ms: u64) -> Self {
+        Self {
+            client: Client::builder()
+                .timeout(Duration::from_secs(5))
+                .build()
+                .unwrap_or_default(),
+            url,
+            min_severity,
+            rate_limit_ms: rate_limit_ms as i64,
+            last_sent: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sink for the configured webhook, if any
+    pub fn from_config(config: &AlertsConfig) -> Option<Self> {
+        let url = config.webhook_url.clone().filter(|u| !u.is_empty())?;
+        Some(Self::new(url, config.min_severity, config.rate_limit_ms))
+    }
+
+    fn admit(&self, alert: &Alert) -> bool {
+        if alert.severity < self.min_severity {
+            return false;
+        }
+        let key = (alert.kind.clone(), alert.severity);
+        let mut last_sent = self.last_sent.lock();
+        match last_sent.get(&key) {
+            Some(last) if alert.ts - last < self.rate_limit_ms => false,
+            _ => {
+                last_sent.insert(key, alert.ts);
+                true
+            }
+        }
+    }
+
+    fn payload(alert: &Alert) -> serde_json::Value {
+        serde_json::json!({
+            "text": format!("[{}] {}: {}", alert.severity.as_str(), alert.kind, alert.message),
+            "service": "titan-execution-rs",
+            "severity": alert.severity,
+            "kind": alert.kind,
+            "message": alert.message,
+            "ts": alert.ts,
+        })
+    }
+}
+
+impl AlertSink for WebhookAlertSink {
+    fn send(&self, alert: Alert) {
+        if !self.admit(&alert) {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            warn!(kind = %alert.kind, "No runtime to deliver alert: {}", alert.message);
+            return;
+        };
+        let request = self.client.post(&self.url).json(&Self::payload(&alert));
+        runtime.spawn(async move {
+            match request.send().await {
+                Ok(resp) if !resp.status().is_success() => {
+                    error!(kind = %alert.kind, "Alert webhook returned {}", resp.status());
+                }
+                Err(e) => error!(kind = %alert.kind, "Alert webhook failed: {}", e),
+                Ok(_) => {}
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit_breaker::GlobalHalt;
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_rate_limit_and_min_severity() {
+        let sink = WebhookAlertSink::new(
+            "http://127.0.0.1:9".to_string(),
+            AlertSeverity::Warning,
+            60_000,
+        );
+        let info = Alert::new(AlertSeverity::Info, "heartbeat", "ok");
+        assert!(!sink.admit(&info));
+
+        let first = Alert::critical(KIND_HALT_ENGAGED, "drift");
+        assert!(sink.admit(&first));
+        let flood = Alert::critical(KIND_HALT_ENGAGED, "drift again");
+        assert!(!sink.admit(&flood));
+        // Other kinds have their own budget
+        assert!(sink.admit(&Alert::critical(KIND_BREAKER_TRIPPED, "slippage")));
+
+        // A warning does not hold back the critical that follows it
+        let warning = Alert::new(AlertSeverity::Warning, KIND_REJECTION_RATE, "rising");
+        assert!(sink.admit(&warning));
+        assert!(!sink.admit(&Alert::new(
+            AlertSeverity::Warning,
+            KIND_REJECTION_RATE,
+            "still rising"
+        )));
+        assert!(sink.admit(&Alert::critical(KIND_REJECTION_RATE, "breaker tripped")));
+
+        let later = Alert {
+            ts: first.ts + 60_000,
+            ..first
+        };
+        assert!(sink.admit(&later));
+    }
+
+    #[tokio::test]
+    async fn test_halt_engaged_posts_to_webhook() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hooks/titan", listener.local_addr().unwrap());
+        let sink = Arc::new(WebhookAlertSink::new(url, AlertSeverity::Warning, 60_000));
+        let halt_path = std::env::temp_dir().join(format!("titan_halt_{}", uuid::Uuid::new_v4()));
+        let halt = GlobalHalt::with_file(&halt_path).with_alert_sink(sink);
+
+        halt.set_halt(true, "Reconciliation drift detected");
+
+        let (mut socket, _) = tokio::time::timeout(Duration::from_secs(5), listener.accept())
+            .await
+            .unwrap()
+            .unwrap();
+        let mut raw = Vec::new();
+        let mut buf = [0u8; 4096];
+        let (head, body) = loop {
+            let n = socket.read(&mut buf).await.unwrap();
+            raw.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&raw).to_string();
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let len = head
+                    .lines()
+                    .find_map(|l| {
+                        l.to_ascii_lowercase()
+                            .strip_prefix("content-length:")
+                            .map(|v| v.trim().parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                if body.len() >= len {
+                    break (head.to_string(), body.to_string());
+                }
+            }
+        };
+        socket
+            .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+            .await
+            .unwrap();
+
+        assert!(head.starts_with("POST /hooks/titan "));
+        let payload: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(payload["severity"], "critical");
+        assert_eq!(payload["kind"], KIND_HALT_ENGAGED);
+        assert_eq!(
+            payload["message"],
+            "HARD_HALT: Reconciliation drift detected"
+        );
+        assert_eq!(
+            payload["text"],
+            "[CRITICAL] halt_engaged: HARD_HALT: Reconciliation drift detected"
+        );
+
+        std::fs::remove_file(halt_path).unwrap_or(());
+    }
+}
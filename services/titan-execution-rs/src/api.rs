@@ -1,11 +1,23 @@
+use crate::armed_state::ArmedState;
+use crate::circuit_breaker::{GlobalHalt, HaltLevel};
+use crate::context::TimeProvider;
+use crate::event_log::{EventLog, TYPE_OPERATOR_CONTROL};
+use crate::execution_report::ExecutionReportStore;
+use crate::heartbeat::HeartbeatStatus;
+use crate::intent_trace::IntentTracer;
+use crate::maintenance_mode::MaintenanceMode;
+use crate::persistence::store::PersistenceStore;
+use crate::rebalance::{PositionTransfer, TransferError};
 use crate::risk_guard::RiskGuard;
 use crate::risk_policy::RiskState;
 use crate::shadow_state::ShadowState;
+use crate::shutdown::{Flattener, ShutdownReport};
 use actix_web::{web, HttpResponse, Responder};
 use async_nats::Client as NatsClient;
 use parking_lot::RwLock;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use tracing::{error, info};
 
 #[derive(Serialize)]
 pub struct HealthResponse {
@@ -25,6 +37,7 @@ pub struct StatusResponse {
     reasons: Vec<String>,
     actions: Vec<String>,
     unsafe_actions: Vec<String>,
+    heartbeat: HeartbeatStatus,
 }
 
 pub async fn health_check(nats: web::Data<NatsClient>) -> impl Responder {
@@ -49,8 +62,22 @@ pub async fn health_check(nats: web::Data<NatsClient>) -> impl Responder {
 
 pub async fn system_status(risk_guard: web::Data<Arc<RiskGuard>>) -> impl Responder {
     let policy = risk_guard.get_policy();
+    let heartbeat = risk_guard.heartbeat_status();
 
-    let (mode, actions, unsafe_actions) = match policy.current_state {
+    // A lost Brain heartbeat trades as DEFENSIVE whatever the policy state
+    let state = match policy.current_state {
+        RiskState::Normal | RiskState::Cautious if heartbeat.lost => RiskState::Defensive,
+        state => state,
+    };
+    let mut reasons = vec![format!("Risk State: {:?}", policy.current_state)];
+    if heartbeat.lost {
+        reasons.push(format!(
+            "Brain heartbeat lost ({}ms since last)",
+            heartbeat.gap_ms
+        ));
+    }
+
+    let (mode, actions, unsafe_actions) = match state {
         RiskState::Normal => ("NORMAL", vec!["Monitor Logs"], vec![]),
         RiskState::Cautious => (
             "CAUTIOUS",
@@ -74,9 +101,10 @@ pub async fn system_status(risk_guard: web::Data<Arc<RiskGuard>>) -> impl Respon
 
     HttpResponse::Ok().json(StatusResponse {
         mode: mode.to_string(),
-        reasons: vec![format!("Risk State: {:?}", policy.current_state)],
+        reasons,
         actions: actions.into_iter().map(String::from).collect(),
         unsafe_actions: unsafe_actions.into_iter().map(String::from).collect(),
+        heartbeat,
     })
 }
 
@@ -87,9 +115,882 @@ pub async fn get_positions(data: web::Data<Arc<RwLock<ShadowState>>>) -> impl Re
     }))
 }
 
+pub async fn get_execution_report(
+    path: web::Path<String>,
+    reports: web::Data<Arc<ExecutionReportStore>>,
+) -> impl Responder {
+    let correlation_id = path.into_inner();
+    match reports.get(&correlation_id) {
+        Some(report) => HttpResponse::Ok().json(report),
+        None => HttpResponse::NotFound().json(serde_json::json!({
+            "error": format!("No execution report for correlation id {}", correlation_id)
+        })),
+    }
+}
+
+pub async fn get_intent_trace(
+    path: web::Path<String>,
+    tracer: web::Data<Arc<IntentTracer>>,
+) -> impl Responder {
+    let correlation_id = path.into_inner();
+    match tracer.get(&correlation_id) {
+        Some(trace) => HttpResponse::Ok().json(trace),
+        None => HttpResponse::NotFound().json(serde_json::json!({
+            "error": format!("No trace for correlation id {}", correlation_id)
+        })),
+    }
+}
+
+/// What the last flatten-on-shutdown did, for after the restart.
+pub async fn get_shutdown_report(persistence: web::Data<Arc<PersistenceStore>>) -> impl Responder {
+    match ShutdownReport::load_last(&persistence) {
+        Ok(Some(report)) => HttpResponse::Ok().json(report),
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "No shutdown report recorded"
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Failed to load shutdown report: {}", e)
+        })),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FundingQuery {
+    symbol: Option<String>,
+    /// Earliest funding time (ms) to include
+    since: Option<i64>,
+}
+
+/// Funding payments applied to positions, oldest first.
+pub async fn get_funding_history(
+    query: web::Query<FundingQuery>,
+    persistence: web::Data<Arc<PersistenceStore>>,
+) -> impl Responder {
+    match persistence.load_funding_history(query.symbol.as_deref(), query.since) {
+        Ok(funding) => HttpResponse::Ok().json(serde_json::json!({ "funding": funding })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Failed to load funding history: {}", e)
+        })),
+    }
+}
+
+#[derive(Serialize)]
+pub struct WhitelistResponse {
+    symbols: Vec<String>,
+    policy_hash: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+pub struct WhitelistUpdate {
+    add: Vec<String>,
+    remove: Vec<String>,
+}
+
+fn whitelist_response(risk_guard: &RiskGuard) -> WhitelistResponse {
+    let mut symbols: Vec<String> = risk_guard
+        .get_policy()
+        .symbol_whitelist
+        .into_iter()
+        .collect();
+    symbols.sort();
+    WhitelistResponse {
+        symbols,
+        policy_hash: risk_guard.get_current_policy_hash(),
+    }
+}
+
+pub async fn get_whitelist(risk_guard: web::Data<Arc<RiskGuard>>) -> impl Responder {
+    HttpResponse::Ok().json(whitelist_response(&risk_guard))
+}
+
+/// Edit the symbol whitelist without a full policy push. The reply carries the
+/// new policy hash for Brain to resync against.
+pub async fn update_whitelist(
+    body: web::Json<WhitelistUpdate>,
+    risk_guard: web::Data<Arc<RiskGuard>>,
+) -> impl Responder {
+    if body
+        .add
+        .iter()
+        .chain(&body.remove)
+        .any(|s| s.trim().is_empty())
+    {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "symbols must not be empty",
+        }));
+    }
+    risk_guard.update_symbol_whitelist(&body.add, &body.remove);
+    HttpResponse::Ok().json(whitelist_response(&risk_guard))
+}
+
+#[derive(Serialize)]
+pub struct ControlStatus {
+    armed: bool,
+    /// Why ARM is refused until an operator approves it
+    arm_block: Option<String>,
+    halt: &'static str,
+    /// New opens frozen while exits keep being managed
+    maintenance: bool,
+}
+
+/// Operator command over HTTP, mirroring the NATS ARM/DISARM/halt commands.
+#[derive(Debug, Deserialize)]
+pub struct ControlCommand {
+    actor: String,
+    reason: String,
+    /// `SOFT_HALT` or `HARD_HALT` (default) for `/control/halt`
+    #[serde(default)]
+    level: Option<String>,
+}
+
+fn control_status(
+    armed: &ArmedState,
+    halt: &GlobalHalt,
+    maintenance: &MaintenanceMode,
+) -> ControlStatus {
+    ControlStatus {
+        armed: armed.is_armed(),
+        arm_block: armed.arm_block(),
+        halt: halt.level().as_str(),
+        maintenance: maintenance.is_active(),
+    }
+}
+
+/// Validate the command and record who issued it on the event log before it
+/// is applied.
+fn audit_control(
+    event_log: &EventLog,
+    action: &str,
+    command: &ControlCommand,
+    now: i64,
+) -> Result<(), HttpResponse> {
+    audit_operator(event_log, action, None, command, now)
+}
+
+fn audit_operator(
+    event_log: &EventLog,
+    action: &str,
+    symbol: Option<&str>,
+    command: &ControlCommand,
+    now: i64,
+) -> Result<(), HttpResponse> {
+    if command.actor.trim().is_empty() || command.reason.trim().is_empty() {
+        return Err(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "actor and reason are required",
+        })));
+    }
+    info!(
+        "🎛️ Operator {} via HTTP: {} ({})",
+        command.actor, action, command.reason
+    );
+    let mut payload = serde_json::json!({
+        "action": action,
+        "actor": command.actor,
+        "reason": command.reason,
+        "channel": "http",
+    });
+    if let Some(symbol) = symbol {
+        payload["symbol"] = serde_json::json!(symbol);
+    }
+    if let Err(e) = event_log.append(TYPE_OPERATOR_CONTROL, None, payload, now) {
+        // Unaudited control changes are refused
+        error!("Failed to audit operator {}: {}", action, e);
+        return Err(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Failed to record audit event: {}", e),
+        })));
+    }
+    Ok(())
+}
+
+pub async fn get_control_status(
+    armed: web::Data<Arc<ArmedState>>,
+    halt: web::Data<Arc<GlobalHalt>>,
+    maintenance: web::Data<Arc<MaintenanceMode>>,
+) -> impl Responder {
+    HttpResponse::Ok().json(control_status(&armed, &halt, &maintenance))
+}
+
+pub async fn control_arm(
+    body: web::Json<ControlCommand>,
+    armed: web::Data<Arc<ArmedState>>,
+    halt: web::Data<Arc<GlobalHalt>>,
+    maintenance: web::Data<Arc<MaintenanceMode>>,
+    event_log: web::Data<Arc<EventLog>>,
+    time: web::Data<Arc<dyn TimeProvider>>,
+) -> HttpResponse {
+    if let Some(block) = armed.arm_block() {
+        return HttpResponse::Conflict().json(serde_json::json!({
+            "error": format!("Arming blocked until operator approval: {}", block),
+        }));
+    }
+    if let Err(resp) = audit_control(&event_log, "arm", &body, time.now_millis()) {
+        return resp;
+    }
+    armed.set_armed(true, &format!("{}: {}", body.actor, body.reason));
+    HttpResponse::Ok().json(control_status(&armed, &halt, &maintenance))
+}
+
+pub async fn control_disarm(
+    body: web::Json<ControlCommand>,
+    armed: web::Data<Arc<ArmedState>>,
+    halt: web::Data<Arc<GlobalHalt>>,
+    maintenance: web::Data<Arc<MaintenanceMode>>,
+    event_log: web::Data<Arc<EventLog>>,
+    time: web::Data<Arc<dyn TimeProvider>>,
+) -> HttpResponse {
+    if let Err(resp) = audit_control(&event_log, "disarm", &body, time.now_millis()) {
+        return resp;
+    }
+    armed.set_armed(false, &format!("{}: {}", body.actor, body.reason));
+    HttpResponse::Ok().json(control_status(&armed, &halt, &maintenance))
+}
+
+pub async fn control_halt(
+    body: web::Json<ControlCommand>,
+    armed: web::Data<Arc<ArmedState>>,
+    halt: web::Data<Arc<GlobalHalt>>,
+    maintenance: web::Data<Arc<MaintenanceMode>>,
+    event_log: web::Data<Arc<EventLog>>,
+    time: web::Data<Arc<dyn TimeProvider>>,
+) -> HttpResponse {
+    let level = match body.level.as_deref() {
+        None | Some("HARD_HALT") => HaltLevel::Hard,
+        Some("SOFT_HALT") => HaltLevel::Soft,
+        Some(other) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": format!("unknown halt level {}", other),
+            }))
+        }
+    };
+    if let Err(resp) = audit_control(&event_log, level.as_str(), &body, time.now_millis()) {
+        return resp;
+    }
+    halt.set_level(level, &format!("{}: {}", body.actor, body.reason));
+    HttpResponse::Ok().json(control_status(&armed, &halt, &maintenance))
+}
+
+pub async fn control_resume(
+    body: web::Json<ControlCommand>,
+    armed: web::Data<Arc<ArmedState>>,
+    halt: web::Data<Arc<GlobalHalt>>,
+    maintenance: web::Data<Arc<MaintenanceMode>>,
+    event_log: web::Data<Arc<EventLog>>,
+    time: web::Data<Arc<dyn TimeProvider>>,
+) -> HttpResponse {
+    if let Err(resp) = audit_control(&event_log, "resume", &body, time.now_millis()) {
+        return resp;
+    }
+    halt.set_level(HaltLevel::Open, &format!("{}: {}", body.actor, body.reason));
+    HttpResponse::Ok().json(control_status(&armed, &halt, &maintenance))
+}
+
+pub async fn control_maintenance_enter(
+    body: web::Json<ControlCommand>,
+    armed: web::Data<Arc<ArmedState>>,
+    halt: web::Data<Arc<GlobalHalt>>,
+    maintenance: web::Data<Arc<MaintenanceMode>>,
+    event_log: web::Data<Arc<EventLog>>,
+    time: web::Data<Arc<dyn TimeProvider>>,
+) -> HttpResponse {
+    if let Err(resp) = audit_control(&event_log, "maintenance_enter", &body, time.now_millis()) {
+        return resp;
+    }
+    maintenance.set_active(true, &format!("{}: {}", body.actor, body.reason));
+    HttpResponse::Ok().json(control_status(&armed, &halt, &maintenance))
+}
+
+pub async fn control_maintenance_exit(
+    body: web::Json<ControlCommand>,
+    armed: web::Data<Arc<ArmedState>>,
+    halt: web::Data<Arc<GlobalHalt>>,
+    maintenance: web::Data<Arc<MaintenanceMode>>,
+    event_log: web::Data<Arc<EventLog>>,
+    time: web::Data<Arc<dyn TimeProvider>>,
+) -> HttpResponse {
+    if let Err(resp) = audit_control(&event_log, "maintenance_exit", &body, time.now_millis()) {
+        return resp;
+    }
+    maintenance.set_active(false, &format!("{}: {}", body.actor, body.reason));
+    HttpResponse::Ok().json(control_status(&armed, &halt, &maintenance))
+}
+
+/// Close a symbol outside the whitelist and pre-trade risk checks, for a
+/// holding the policy no longer admits (delisted, excluded). Requires the
+/// system to be armed and an audited operator command.
+pub async fn emergency_exit(
+    path: web::Path<String>,
+    body: web::Json<ControlCommand>,
+    armed: web::Data<Arc<ArmedState>>,
+    event_log: web::Data<Arc<EventLog>>,
+    time: web::Data<Arc<dyn TimeProvider>>,
+    flattener: web::Data<Arc<Flattener>>,
+) -> HttpResponse {
+    let symbol = path.into_inner();
+    if !armed.is_armed() {
+        return HttpResponse::Conflict().json(serde_json::json!({
+            "error": "Emergency exit requires the system to be armed",
+        }));
+    }
+    if let Err(resp) = audit_operator(
+        &event_log,
+        "emergency_exit",
+        Some(&symbol),
+        &body,
+        time.now_millis(),
+    ) {
+        return resp;
+    }
+    match flattener.flatten_symbol(&symbol).await {
+        Some(report) => HttpResponse::Ok().json(report),
+        None => HttpResponse::NotFound().json(serde_json::json!({
+            "error": format!("No position held in {}", symbol),
+        })),
+    }
+}
+
+/// Move a symbol's position onto another venue: closed on the venue holding
+/// it, then reopened on `venue`. Requires the system to be armed and an
+/// audited operator command.
+pub async fn transfer_position(
+    path: web::Path<(String, String)>,
+    body: web::Json<ControlCommand>,
+    armed: web::Data<Arc<ArmedState>>,
+    event_log: web::Data<Arc<EventLog>>,
+    time: web::Data<Arc<dyn TimeProvider>>,
+    transfer: web::Data<Arc<PositionTransfer>>,
+) -> HttpResponse {
+    let (symbol, venue) = path.into_inner();
+    if !armed.is_armed() {
+        return HttpResponse::Conflict().json(serde_json::json!({
+            "error": "Position transfer requires the system to be armed",
+        }));
+    }
+    if let Err(resp) = audit_operator(
+        &event_log,
+        "transfer",
+        Some(&symbol),
+        &body,
+        time.now_millis(),
+    ) {
+        return resp;
+    }
+    match transfer.transfer(&symbol, &venue).await {
+        Ok(report) => HttpResponse::Ok().json(report),
+        Err(e @ TransferError::NoPosition(_)) => {
+            HttpResponse::NotFound().json(serde_json::json!({ "error": e.to_string() }))
+        }
+        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e.to_string() })),
+    }
+}
+
 // Define scope configuration
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(web::resource("/health").route(web::get().to(health_check)))
         .service(web::resource("/status").route(web::get().to(system_status)))
-        .service(web::resource("/positions").route(web::get().to(get_positions)));
+        .service(web::resource("/positions").route(web::get().to(get_positions)))
+        .service(
+            web::resource("/executions/{correlation_id}")
+                .route(web::get().to(get_execution_report)),
+        )
+        .service(web::resource("/trace/{correlation_id}").route(web::get().to(get_intent_trace)))
+        .service(web::resource("/shutdown/report").route(web::get().to(get_shutdown_report)))
+        .service(web::resource("/funding").route(web::get().to(get_funding_history)))
+        .service(
+            web::resource("/risk/whitelist")
+                .route(web::get().to(get_whitelist))
+                .route(web::post().to(update_whitelist)),
+        )
+        .service(
+            web::resource("/risk/emergency_exit/{symbol}").route(web::post().to(emergency_exit)),
+        )
+        .service(
+            web::resource("/risk/transfer/{symbol}/{venue}")
+                .route(web::post().to(transfer_position)),
+        )
+        .service(web::resource("/control/status").route(web::get().to(get_control_status)))
+        .service(web::resource("/control/arm").route(web::post().to(control_arm)))
+        .service(web::resource("/control/disarm").route(web::post().to(control_disarm)))
+        .service(web::resource("/control/halt").route(web::post().to(control_halt)))
+        .service(web::resource("/control/resume").route(web::post().to(control_resume)))
+        .service(
+            web::resource("/control/maintenance/enter")
+                .route(web::post().to(control_maintenance_enter)),
+        )
+        .service(
+            web::resource("/control/maintenance/exit")
+                .route(web::post().to(control_maintenance_exit)),
+        );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::{ExecutionContext, MockClock};
+    use crate::model::Intent;
+    use crate::persistence::redb_store::RedbStore;
+    use crate::persistence::store::PersistenceStore;
+    use crate::persistence::wal::WalManager;
+    use crate::risk_guard::RiskRejectionReason;
+    use crate::risk_policy::RiskPolicy;
+    use actix_web::{test, App};
+
+    #[actix_web::test]
+    async fn test_whitelist_add_admits_symbol_and_changes_hash() {
+        let path = format!("/tmp/test_api_whitelist_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let persistence = Arc::new(PersistenceStore::new(redb, wal));
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let state = Arc::new(RwLock::new(ShadowState::new(
+            persistence,
+            ctx,
+            Some(10000.0),
+        )));
+        let guard = Arc::new(RiskGuard::new(RiskPolicy::default(), state));
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(guard.clone()))
+                .configure(config),
+        )
+        .await;
+
+        let intent: Intent = serde_json::from_value(serde_json::json!({
+            "signal_id": "sig-doge",
+            "symbol": "DOGE/USDT",
+            "direction": 1,
+            "type": "BUY_SETUP",
+            "entry_zone": [0.1],
+            "size": 100.0,
+            "status": "PENDING",
+            "t_signal": chrono::Utc::now().timestamp_millis(),
+        }))
+        .unwrap();
+        assert_eq!(
+            guard.check_pre_trade(&intent),
+            Err(RiskRejectionReason::SymbolNotWhitelisted(
+                "DOGE/USDT".to_string()
+            ))
+        );
+        let old_hash = guard.get_current_policy_hash();
+
+        let req = test::TestRequest::post()
+            .uri("/risk/whitelist")
+            .set_json(serde_json::json!({ "add": ["DOGE/USDT"] }))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        let new_hash = resp["policy_hash"].as_str().unwrap().to_string();
+        assert_ne!(new_hash, old_hash);
+        assert_eq!(new_hash, guard.get_current_policy_hash());
+        assert!(guard.check_pre_trade(&intent).is_ok());
+
+        let req = test::TestRequest::get().uri("/risk/whitelist").to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(
+            resp["symbols"],
+            serde_json::json!(["BTC/USDT", "DOGE/USDT", "ETH/USDT", "SOL/USDT"])
+        );
+
+        // Removing it again restores the canonical hash
+        let req = test::TestRequest::post()
+            .uri("/risk/whitelist")
+            .set_json(serde_json::json!({ "remove": ["DOGE/USDT"] }))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["policy_hash"], RiskPolicy::get_hash());
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[actix_web::test]
+    async fn test_control_endpoints_toggle_shared_arm_and_halt_state() {
+        let path = format!("/tmp/test_api_control_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let event_log = Arc::new(EventLog::new(Arc::new(PersistenceStore::new(redb, wal))));
+        let clock = Arc::new(MockClock::new(1_700_000_000_000));
+        let tmp = std::env::temp_dir();
+        let armed = Arc::new(ArmedState::with_file(
+            tmp.join(format!("titan_armed_{}", uuid::Uuid::new_v4())),
+        ));
+        let halt = Arc::new(GlobalHalt::with_file(
+            tmp.join(format!("titan_halt_{}", uuid::Uuid::new_v4())),
+        ));
+        let maintenance = Arc::new(MaintenanceMode::with_file(
+            tmp.join(format!("titan_maint_{}", uuid::Uuid::new_v4())),
+        ));
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(armed.clone()))
+                .app_data(web::Data::new(halt.clone()))
+                .app_data(web::Data::new(maintenance.clone()))
+                .app_data(web::Data::new(event_log.clone()))
+                .app_data(web::Data::new(clock.clone() as Arc<dyn TimeProvider>))
+                .configure(config),
+        )
+        .await;
+        let command = |uri: &str, body: serde_json::Value| {
+            test::TestRequest::post()
+                .uri(uri)
+                .set_json(body)
+                .to_request()
+        };
+
+        let req = test::TestRequest::get().uri("/control/status").to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["armed"], false);
+        assert_eq!(resp["halt"], "OPEN");
+        assert_eq!(resp["maintenance"], false);
+
+        let req = command(
+            "/control/arm",
+            serde_json::json!({ "actor": "alice", "reason": "open session" }),
+        );
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["armed"], true);
+        assert!(armed.is_armed());
+
+        let req = command(
+            "/control/halt",
+            serde_json::json!({ "actor": "bob", "reason": "venue outage", "level": "SOFT_HALT" }),
+        );
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["halt"], "SOFT_HALT");
+        assert_eq!(halt.level(), HaltLevel::Soft);
+
+        let req = command(
+            "/control/resume",
+            serde_json::json!({ "actor": "bob", "reason": "venue back" }),
+        );
+        test::call_service(&app, req).await;
+        assert!(!halt.is_halted());
+
+        // Maintenance freezes opens without engaging the halt
+        let req = command(
+            "/control/maintenance/enter",
+            serde_json::json!({ "actor": "carol", "reason": "venue upgrade" }),
+        );
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["maintenance"], true);
+        assert_eq!(resp["halt"], "OPEN");
+        assert!(maintenance.is_active());
+        assert!(!maintenance.allows(false));
+
+        let req = command(
+            "/control/maintenance/exit",
+            serde_json::json!({ "actor": "carol", "reason": "upgrade done" }),
+        );
+        test::call_service(&app, req).await;
+        assert!(!maintenance.is_active());
+
+        let req = command(
+            "/control/disarm",
+            serde_json::json!({ "actor": "alice", "reason": "close session" }),
+        );
+        test::call_service(&app, req).await;
+        assert!(!armed.is_armed());
+
+        // Commands without an actor are refused and leave state alone
+        let req = command(
+            "/control/halt",
+            serde_json::json!({ "actor": "", "reason": "no name" }),
+        );
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+        assert!(!halt.is_halted());
+
+        // A blocked arm is reported, not silently ignored
+        armed.block_arming("state diverges from venues");
+        let req = command(
+            "/control/arm",
+            serde_json::json!({ "actor": "alice", "reason": "retry" }),
+        );
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::CONFLICT);
+        assert!(!armed.is_armed());
+
+        // Each applied command is on the event log with its actor
+        let events = event_log.replay_from(1, 10).unwrap();
+        let audit: Vec<(String, String)> = events
+            .iter()
+            .map(|e| {
+                assert_eq!(e.event_type, TYPE_OPERATOR_CONTROL);
+                assert_eq!(e.ts, 1_700_000_000_000);
+                (
+                    e.payload["action"].as_str().unwrap().to_string(),
+                    e.payload["actor"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            audit,
+            vec![
+                ("arm".to_string(), "alice".to_string()),
+                ("SOFT_HALT".to_string(), "bob".to_string()),
+                ("resume".to_string(), "bob".to_string()),
+                ("maintenance_enter".to_string(), "carol".to_string()),
+                ("maintenance_exit".to_string(), "carol".to_string()),
+                ("disarm".to_string(), "alice".to_string()),
+            ]
+        );
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[actix_web::test]
+    async fn test_funding_history_filtered_by_symbol_and_time() {
+        use crate::model::Position;
+        use rust_decimal_macros::dec;
+
+        let path = format!("/tmp/test_api_funding_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let persistence = Arc::new(PersistenceStore::new(redb, wal));
+        for symbol in ["BTC/USDT", "ETH/USDT"] {
+            let position: Position = serde_json::from_value(serde_json::json!({
+                "symbol": symbol,
+                "side": "LONG",
+                "size": "1",
+                "entry_price": "100",
+                "stop_loss": "0",
+                "take_profits": [],
+                "signal_id": format!("sig-{}", symbol),
+                "opened_at": chrono::Utc::now(),
+                "regime_state": null,
+                "phase": null,
+                "metadata": null,
+            }))
+            .unwrap();
+            persistence.save_position(&position).unwrap();
+        }
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let mut state = ShadowState::new(persistence.clone(), ctx, Some(10_000.0));
+
+        const EPOCH: i64 = 1_700_000_000_000;
+        const EIGHT_HOURS: i64 = 28_800_000;
+        for (i, amount) in [dec!(1.5), dec!(-0.5), dec!(2)].into_iter().enumerate() {
+            let t = EPOCH + i as i64 * EIGHT_HOURS;
+            assert!(state
+                .apply_funding(
+                    "bybit",
+                    "BTC/USDT",
+                    amount,
+                    Some(dec!(0.0001)),
+                    "USDT".to_string(),
+                    t
+                )
+                .is_some());
+            assert!(state
+                .apply_funding("bybit", "ETH/USDT", dec!(0.3), None, "USDT".to_string(), t)
+                .is_some());
+        }
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(persistence))
+                .configure(config),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!(
+                "/funding?symbol=BTC%2FUSDT&since={}",
+                EPOCH + EIGHT_HOURS
+            ))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        let funding = resp["funding"].as_array().unwrap();
+        assert_eq!(funding.len(), 2);
+        assert_eq!(funding[0]["amount"], "-0.5");
+        assert_eq!(funding[0]["funding_time"], EPOCH + EIGHT_HOURS);
+        assert_eq!(funding[1]["amount"], "2");
+        assert_eq!(funding[1]["rate"], "0.0001");
+        assert_eq!(funding[1]["position_signal_id"], "sig-BTC/USDT");
+
+        let req = test::TestRequest::get().uri("/funding").to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["funding"].as_array().unwrap().len(), 6);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[actix_web::test]
+    async fn test_emergency_exit_closes_non_whitelisted_position() {
+        use crate::exchange::router::ExecutionRouter;
+        use crate::model::{Position, Side};
+        use crate::test_support::MockAdapter;
+        use rust_decimal_macros::dec;
+
+        let path = format!("/tmp/test_api_emergency_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let persistence = Arc::new(PersistenceStore::new(redb, wal));
+        let position: Position = serde_json::from_value(serde_json::json!({
+            "symbol": "DOGE/USDT",
+            "side": "LONG",
+            "size": "1000",
+            "entry_price": "0.1",
+            "stop_loss": "0",
+            "take_profits": [],
+            "signal_id": "sig-doge",
+            "opened_at": chrono::Utc::now(),
+            "regime_state": null,
+            "phase": null,
+            "metadata": null,
+            "exchange": "binance",
+        }))
+        .unwrap();
+        persistence.save_position(&position).unwrap();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let state = Arc::new(RwLock::new(ShadowState::new(
+            persistence.clone(),
+            ctx.clone(),
+            Some(10_000.0),
+        )));
+        let guard = RiskGuard::new(RiskPolicy::default(), state.clone());
+        assert!(!guard.is_whitelisted("DOGE/USDT"));
+
+        let adapter = Arc::new(MockAdapter::new("binance").with_fill_price(dec!(0.1)));
+        // Wired as in production, where the router filters routes by the whitelist
+        let router = Arc::new(ExecutionRouter::new().with_risk_guard(Arc::new(guard)));
+        router.register("binance", adapter.clone());
+        let clock = ctx.time.clone();
+        let flattener = Arc::new(Flattener::new(
+            router,
+            state.clone(),
+            persistence.clone(),
+            ctx,
+        ));
+        let event_log = Arc::new(EventLog::new(persistence));
+        let armed = Arc::new(ArmedState::with_file(
+            std::env::temp_dir().join(format!("titan_armed_{}", uuid::Uuid::new_v4())),
+        ));
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(armed.clone()))
+                .app_data(web::Data::new(event_log.clone()))
+                .app_data(web::Data::new(clock.clone() as Arc<dyn TimeProvider>))
+                .app_data(web::Data::new(flattener))
+                .configure(config),
+        )
+        .await;
+        let exit = || {
+            test::TestRequest::post()
+                .uri("/risk/emergency_exit/DOGE_USDT")
+                .set_json(serde_json::json!({ "actor": "alice", "reason": "delisted" }))
+                .to_request()
+        };
+
+        // Refused while disarmed, with nothing sent or recorded
+        let resp = test::call_service(&app, exit()).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::CONFLICT);
+        assert!(adapter.placed.lock().is_empty());
+
+        armed.set_armed(true, "test");
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, exit()).await;
+        assert_eq!(resp["symbol"], "DOGE/USDT");
+        assert_eq!(
+            resp["positionClosed"]["venues"],
+            serde_json::json!(["binance"])
+        );
+
+        let placed = adapter.placed.lock().clone();
+        assert_eq!(placed.len(), 1);
+        assert_eq!(placed[0].symbol, "DOGEUSDT");
+        assert_eq!(placed[0].side, Side::Sell);
+        assert_eq!(placed[0].quantity, dec!(1000));
+        assert!(placed[0].reduce_only);
+        // The fill is applied: the position is gone from the shadow book
+        assert!(!state.read().has_position("DOGE/USDT"));
+
+        let events = event_log.replay_from(1, 10).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, TYPE_OPERATOR_CONTROL);
+        assert_eq!(events[0].payload["action"], "emergency_exit");
+        assert_eq!(events[0].payload["symbol"], "DOGE_USDT");
+        assert_eq!(events[0].payload["actor"], "alice");
+
+        // Unknown symbols are reported rather than silently accepted
+        let req = test::TestRequest::post()
+            .uri("/risk/emergency_exit/XRPUSDT")
+            .set_json(serde_json::json!({ "actor": "alice", "reason": "typo" }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[actix_web::test]
+    async fn test_transfer_endpoint_moves_position_between_venues() {
+        use crate::exchange::router::ExecutionRouter;
+        use crate::model::Position;
+        use crate::test_support::MockAdapter;
+        use rust_decimal_macros::dec;
+
+        let path = format!("/tmp/test_api_transfer_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let persistence = Arc::new(PersistenceStore::new(redb, wal));
+        let position: Position = serde_json::from_value(serde_json::json!({
+            "symbol": "BTC/USDT",
+            "side": "LONG",
+            "size": "0.5",
+            "entry_price": "50000",
+            "stop_loss": "0",
+            "take_profits": [],
+            "signal_id": "sig-btc",
+            "opened_at": chrono::Utc::now(),
+            "regime_state": null,
+            "phase": null,
+            "metadata": null,
+            "exchange": "binance",
+        }))
+        .unwrap();
+        persistence.save_position(&position).unwrap();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let state = Arc::new(RwLock::new(ShadowState::new(
+            persistence.clone(),
+            ctx.clone(),
+            Some(10_000.0),
+        )));
+        let source = Arc::new(MockAdapter::new("binance"));
+        let target = Arc::new(MockAdapter::new("bybit"));
+        let router = Arc::new(ExecutionRouter::new());
+        router.register("binance", source.clone());
+        router.register("bybit", target.clone());
+        let clock = ctx.time.clone();
+        let transfer = Arc::new(PositionTransfer::new(router, state.clone(), ctx));
+        let event_log = Arc::new(EventLog::new(persistence));
+        let armed = Arc::new(ArmedState::with_file(
+            std::env::temp_dir().join(format!("titan_armed_{}", uuid::Uuid::new_v4())),
+        ));
+        armed.set_armed(true, "test");
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(armed.clone()))
+                .app_data(web::Data::new(event_log.clone()))
+                .app_data(web::Data::new(clock.clone() as Arc<dyn TimeProvider>))
+                .app_data(web::Data::new(transfer))
+                .configure(config),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/risk/transfer/BTC_USDT/bybit")
+            .set_json(serde_json::json!({ "actor": "alice", "reason": "binance degraded" }))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["outcome"], "MOVED");
+        assert_eq!(source.placed().len(), 1);
+        assert_eq!(target.placed().len(), 1);
+        let pos = state.read().get_position("BTC/USDT").cloned().unwrap();
+        assert_eq!(pos.exchange.as_deref(), Some("bybit"));
+
+        let events = event_log.replay_from(1, 10).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].payload["action"], "transfer");
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
 }
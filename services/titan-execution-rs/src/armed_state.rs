@@ -1,3 +1,4 @@
+use parking_lot::RwLock;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tracing::{info, warn};
@@ -16,6 +17,9 @@ use tracing::{info, warn};
 pub struct ArmedState {
     is_armed: Arc<AtomicBool>,
     file_path: std::path::PathBuf,
+    /// Why arming is refused until an operator approves it (e.g. startup
+    /// reconciliation found hydrated state diverging from the venues)
+    arm_block: Arc<RwLock<Option<String>>>,
 }
 
 impl Default for ArmedState {
@@ -26,7 +30,12 @@ impl Default for ArmedState {
 
 impl ArmedState {
     pub fn new() -> Self {
-        let file_path = std::path::PathBuf::from("execution.armed");
+        Self::with_file("execution.armed")
+    }
+
+    /// Armed state persisted to the lockfile at `file_path`.
+    pub fn with_file(file_path: impl Into<std::path::PathBuf>) -> Self {
+        let file_path = file_path.into();
         let exists = file_path.exists();
 
         if exists {
@@ -38,6 +47,7 @@ impl ArmedState {
         Self {
             is_armed: Arc::new(AtomicBool::new(exists)),
             file_path,
+            arm_block: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -48,6 +58,16 @@ impl ArmedState {
 
     /// Set the armed state. Only operators can arm the system.
     pub fn set_armed(&self, armed: bool, reason: &str) {
+        if armed {
+            if let Some(block) = self.arm_block.read().as_deref() {
+                warn!(
+                    "⛔ ARM refused ({}): {} - operator approval required",
+                    reason, block
+                );
+                return;
+            }
+        }
+
         let prev = self.is_armed.swap(armed, Ordering::SeqCst);
 
         // Sync to disk for persistence across restarts
@@ -72,6 +92,24 @@ impl ArmedState {
             }
         }
     }
+
+    /// Disarm and refuse ARM commands until `approve_arming` is called.
+    pub fn block_arming(&self, reason: &str) {
+        *self.arm_block.write() = Some(reason.to_string());
+        self.set_armed(false, reason);
+    }
+
+    /// Operator sign-off lifting an arming block. Does not arm by itself.
+    pub fn approve_arming(&self, reason: &str) {
+        if let Some(block) = self.arm_block.write().take() {
+            info!("✅ Arming block lifted by operator ({}): {}", reason, block);
+        }
+    }
+
+    /// Reason arming is currently refused, if any.
+    pub fn arm_block(&self) -> Option<String> {
+        self.arm_block.read().clone()
+    }
 }
 
 #[cfg(test)]
@@ -96,4 +134,26 @@ mod tests {
         state.set_armed(false, "Test DISARM");
         assert!(!state.is_armed(), "Should be disarmed");
     }
+
+    #[test]
+    fn test_arm_block_requires_approval() {
+        let path = std::env::temp_dir().join(format!("titan_armed_{}", uuid::Uuid::new_v4()));
+        let state = ArmedState::with_file(&path);
+        state.set_armed(true, "Test ARM");
+
+        state.block_arming("state diverges from venues");
+        assert!(!state.is_armed());
+        assert!(!path.exists());
+
+        state.set_armed(true, "Test ARM");
+        assert!(!state.is_armed(), "ARM must be refused while blocked");
+
+        state.approve_arming("operator checked positions");
+        assert!(state.arm_block().is_none());
+        assert!(!state.is_armed(), "Approval alone does not arm");
+        state.set_armed(true, "Test ARM");
+        assert!(state.is_armed());
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
 }
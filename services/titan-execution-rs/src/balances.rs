@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::market_data::engine::MarketDataEngine;
+
+/// Assets valued at par when converting balances to USD.
+const USD_STABLES: &[&str] = &["USD", "USDT", "USDC", "DAI", "FDUSD", "BUSD"];
+
+/// Balance of one settlement asset held on one venue.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VenueBalance {
+    pub venue: String,
+    pub total: Decimal,
+    /// None when no price is available for the asset
+    pub usd_value: Option<Decimal>,
+}
+
+/// One asset summed across every venue holding it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetBalance {
+    pub currency: String,
+    pub total: Decimal,
+    pub usd_value: Option<Decimal>,
+    pub venues: Vec<VenueBalance>,
+}
+
+/// A venue/asset balance query that failed; the rest of the report is still valid.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceError {
+    pub venue: String,
+    pub currency: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceReport {
+    pub balances: Vec<AssetBalance>,
+    /// Sum of all priced balances; unpriced assets are left out
+    pub total_usd: Decimal,
+    pub errors: Vec<BalanceError>,
+    pub update_time: i64,
+}
+
+fn mid(market_data: &MarketDataEngine, venue: &str, symbol: &str) -> Option<Decimal> {
+    let ticker = market_data
+        .get_venue_ticker(venue, symbol)
+        .or_else(|| market_data.get_ticker(symbol))?;
+    let mid = (ticker.best_bid + ticker.best_ask) / Decimal::from(2);
+    (mid > Decimal::ZERO).then_some(mid)
+}
+
+/// USD price of an asset: stables at par, otherwise the asset's USDT book
+/// (holding venue first, then the aggregated feed).
+pub fn usd_price(
+    market_data: Option<&MarketDataEngine>,
+    venue: &str,
+    asset: &str,
+) -> Option<Decimal> {
+    let asset = asset.to_uppercase();
+    if USD_STABLES.contains(&asset.as_str()) {
+        return Some(Decimal::ONE);
+    }
+    let market_data = market_data?;
+    let symbol = format!("{}USDT", asset);
+    mid(market_data, venue, &symbol).or_else(|| market_data.get_price(&symbol))
+}
+
+/// Group raw (venue, asset, total) rows into a per-asset, per-venue report.
+pub fn aggregate(
+    rows: Vec<(String, String, Decimal)>,
+    errors: Vec<BalanceError>,
+    market_data: Option<&MarketDataEngine>,
+    update_time: i64,
+) -> BalanceReport {
+    let mut by_asset: BTreeMap<String, Vec<VenueBalance>> = BTreeMap::new();
+    for (venue, asset, total) in rows {
+        let asset = asset.to_uppercase();
+        let usd_value = usd_price(market_data, &venue, &asset).map(|px| px * total);
+        by_asset.entry(asset).or_default().push(VenueBalance {
+            venue,
+            total,
+            usd_value,
+        });
+    }
+
+    let balances: Vec<AssetBalance> = by_asset
+        .into_iter()
+        .map(|(currency, mut venues)| {
+            venues.sort_by(|a, b| a.venue.cmp(&b.venue));
+            let total = venues.iter().map(|v| v.total).sum();
+            let usd_value = venues.iter().map(|v| v.usd_value).sum::<Option<Decimal>>();
+            AssetBalance {
+                currency,
+                total,
+                usd_value,
+                venues,
+            }
+        })
+        .collect();
+
+    let total_usd = balances
+        .iter()
+        .flat_map(|b| b.venues.iter().filter_map(|v| v.usd_value))
+        .sum();
+
+    BalanceReport {
+        balances,
+        total_usd,
+        errors,
+        update_time,
+    }
+}
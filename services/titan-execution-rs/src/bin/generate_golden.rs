@@ -59,7 +59,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     write_event(
         &mut file,
         ReplayEvent::RiskPolicy {
-            policy: policy.clone(),
+            policy: Box::new(policy.clone()),
             ts: timestamp,
         },
     )?;
@@ -133,7 +133,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             write_event(
                 &mut file,
                 ReplayEvent::RiskPolicy {
-                    policy: policy.clone(),
+                    policy: Box::new(policy.clone()),
                     ts: timestamp + 10,
                 },
             )?;
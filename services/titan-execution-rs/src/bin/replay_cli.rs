@@ -15,7 +15,7 @@ use titan_execution_rs::context::{
 };
 use titan_execution_rs::drift_detector::DriftDetector;
 use titan_execution_rs::exchange::adapter::{
-    ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse,
+    ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, OrderStatus,
 };
 use titan_execution_rs::exchange::router::ExecutionRouter;
 use titan_execution_rs::market_data::engine::MarketDataEngine;
@@ -45,7 +45,8 @@ impl ExchangeAdapter for MockAdapter {
             order_id: format!("mock-{}", order.client_order_id),
             client_order_id: order.client_order_id,
             symbol: order.symbol,
-            status: "NEW".to_string(),
+            status: OrderStatus::New,
+            raw_status: "NEW".to_string(),
             avg_price: None, // Will fill at limit or mock logic elsewhere
             executed_qty: order.quantity,
             t_exchange: None,
@@ -102,10 +103,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let id_provider = Arc::new(DeterministicIdProvider::new());
 
     // We construct ExecutionContext (struct) but store it in Arc for components that need Arc<ExecutionContext>
-    let ctx_struct = ExecutionContext {
-        time: time_provider.clone(),
-        id: id_provider,
-    };
+    let ctx_struct = ExecutionContext::from_providers(time_provider.clone(), id_provider);
     let ctx = Arc::new(ctx_struct.clone());
 
     // 2. Persistence (Redb Temp)
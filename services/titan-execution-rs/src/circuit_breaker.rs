@@ -1,13 +1,55 @@
-use std::sync::atomic::{AtomicBool, Ordering};
+use crate::alerts::{Alert, AlertSink, KIND_HALT_ENGAGED};
+use rust_decimal::Decimal;
+use std::sync::atomic::{AtomicU8, Ordering};
 use std::sync::Arc;
-use tracing::{info, warn};
+use tracing::{error, info, warn};
+
+/// Severity of the global halt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum HaltLevel {
+    /// Normal operation
+    Open = 0,
+    /// Reduce-only: new opens are rejected, closes / reduce-only intents still flow
+    Soft = 1,
+    /// Emergency stop: everything is rejected
+    Hard = 2,
+}
+
+impl HaltLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HaltLevel::Open => "OPEN",
+            HaltLevel::Soft => "SOFT_HALT",
+            HaltLevel::Hard => "HARD_HALT",
+        }
+    }
+
+    fn from_u8(v: u8) -> Self {
+        match v {
+            0 => HaltLevel::Open,
+            1 => HaltLevel::Soft,
+            _ => HaltLevel::Hard,
+        }
+    }
+}
 
 /// Global system halt state.
-/// When true, the system is in HALT mode and should reject orders.
-#[derive(Clone, Debug)]
+/// SOFT_HALT rejects new opens only; HARD_HALT rejects all orders.
+#[derive(Clone)]
 pub struct GlobalHalt {
-    is_halted: Arc<AtomicBool>,
+    level: Arc<AtomicU8>,
     file_path: std::path::PathBuf,
+    alert_sink: Option<Arc<dyn AlertSink>>,
+}
+
+impl std::fmt::Debug for GlobalHalt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GlobalHalt")
+            .field("level", &self.level())
+            .field("file_path", &self.file_path)
+            .finish()
+    }
 }
 
 impl Default for GlobalHalt {
@@ -18,31 +60,76 @@ impl Default for GlobalHalt {
 
 impl GlobalHalt {
     pub fn new() -> Self {
-        let file_path = std::path::PathBuf::from("system.halt");
-        let exists = file_path.exists();
+        Self::with_file("system.halt")
+    }
+
+    /// Halt state persisted to `file_path`. The lockfile holds the level followed by
+    /// the reason; a lockfile without a recognised level restores as HARD_HALT.
+    pub fn with_file(file_path: impl Into<std::path::PathBuf>) -> Self {
+        let file_path = file_path.into();
+        let level = match std::fs::read_to_string(&file_path) {
+            Ok(contents) if contents.starts_with(HaltLevel::Soft.as_str()) => HaltLevel::Soft,
+            Ok(_) => HaltLevel::Hard,
+            Err(_) if file_path.exists() => HaltLevel::Hard,
+            Err(_) => HaltLevel::Open,
+        };
 
-        if exists {
-            warn!("⚠️ System initialized in HALTED state (system.halt file found)");
+        if level != HaltLevel::Open {
+            warn!(
+                "⚠️ System initialized in {} state (system.halt file found)",
+                level.as_str()
+            );
         }
 
         Self {
-            is_halted: Arc::new(AtomicBool::new(exists)),
+            level: Arc::new(AtomicU8::new(level as u8)),
             file_path,
+            alert_sink: None,
         }
     }
 
-    /// Check if the system is currently halted.
+    /// Raise a critical alert whenever a halt is engaged
+    pub fn with_alert_sink(mut self, sink: Arc<dyn AlertSink>) -> Self {
+        self.alert_sink = Some(sink);
+        self
+    }
+
+    pub fn level(&self) -> HaltLevel {
+        HaltLevel::from_u8(self.level.load(Ordering::SeqCst))
+    }
+
+    /// Check if the system is currently halted (soft or hard).
     pub fn is_halted(&self) -> bool {
-        self.is_halted.load(Ordering::SeqCst)
+        self.level() != HaltLevel::Open
     }
 
-    /// Set the halt state.
+    /// Whether an order may proceed under the current halt level.
+    /// `reduces_risk` marks closes / reduce-only intents.
+    pub fn allows(&self, reduces_risk: bool) -> bool {
+        match self.level() {
+            HaltLevel::Open => true,
+            HaltLevel::Soft => reduces_risk,
+            HaltLevel::Hard => false,
+        }
+    }
+
+    /// Set the halt state. `true` is a HARD_HALT.
     pub fn set_halt(&self, active: bool, reason: &str) {
-        let prev = self.is_halted.swap(active, Ordering::SeqCst);
+        let level = if active {
+            HaltLevel::Hard
+        } else {
+            HaltLevel::Open
+        };
+        self.set_level(level, reason);
+    }
+
+    pub fn set_level(&self, level: HaltLevel, reason: &str) {
+        let prev = HaltLevel::from_u8(self.level.swap(level as u8, Ordering::SeqCst));
 
         // Sync to disk
-        if active {
-            if let Err(e) = std::fs::write(&self.file_path, reason) {
+        if level != HaltLevel::Open {
+            let contents = format!("{}: {}", level.as_str(), reason);
+            if let Err(e) = std::fs::write(&self.file_path, contents) {
                 warn!("Failed to persist halt lockfile: {}", e);
             }
         } else if self.file_path.exists() {
@@ -51,20 +138,68 @@ impl GlobalHalt {
             }
         }
 
-        if prev != active {
-            if active {
-                warn!("🚨 SYSTEM HALT ACTIVATED: {}", reason);
-            } else {
-                info!("✅ SYSTEM HALT LIFTED: {}", reason);
+        if prev != level {
+            match level {
+                HaltLevel::Open => info!("✅ SYSTEM HALT LIFTED: {}", reason),
+                HaltLevel::Soft => warn!("🟡 SYSTEM SOFT HALT (reduce-only): {}", reason),
+                HaltLevel::Hard => warn!("🚨 SYSTEM HALT ACTIVATED: {}", reason),
+            }
+            if let (HaltLevel::Soft | HaltLevel::Hard, Some(sink)) = (level, &self.alert_sink) {
+                sink.send(Alert::critical(
+                    KIND_HALT_ENGAGED,
+                    format!("{}: {}", level.as_str(), reason),
+                ));
             }
         }
     }
 }
 
+/// Trailing drawdown halt: engages HARD_HALT once equity falls more than
+/// `max_drawdown_pct` below its high-water mark. Catches give-back after a
+/// profitable run that a fixed daily loss limit misses.
+pub struct DrawdownBreaker {
+    max_drawdown_pct: Decimal,
+    halt: Arc<GlobalHalt>,
+}
+
+impl DrawdownBreaker {
+    pub fn new(max_drawdown_pct: Decimal, halt: Arc<GlobalHalt>) -> Self {
+        Self {
+            max_drawdown_pct,
+            halt,
+        }
+    }
+
+    /// Returns true if the drawdown limit is breached (and the halt engaged).
+    pub fn check(&self, equity: Decimal, hwm: Decimal) -> bool {
+        if hwm <= Decimal::ZERO {
+            return false;
+        }
+        let drawdown_pct = (hwm - equity) / hwm * Decimal::from(100);
+        if drawdown_pct <= self.max_drawdown_pct {
+            return false;
+        }
+        if self.halt.level() != HaltLevel::Hard {
+            let reason = format!(
+                "Trailing drawdown {:.2}% from equity HWM {} exceeds {}%",
+                drawdown_pct, hwm, self.max_drawdown_pct
+            );
+            error!("📉 {}", reason);
+            self.halt.set_halt(true, &reason);
+        }
+        true
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn temp_halt() -> (GlobalHalt, std::path::PathBuf) {
+        let path = std::env::temp_dir().join(format!("titan_halt_{}", uuid::Uuid::new_v4()));
+        (GlobalHalt::with_file(&path), path)
+    }
+
     #[test]
     fn test_halt_logic() {
         let _ = std::fs::remove_file("system.halt");
@@ -77,4 +212,31 @@ mod tests {
         breaker.set_halt(false, "Test Resume");
         assert!(!breaker.is_halted(), "Should be resumed");
     }
+
+    #[test]
+    fn test_soft_halt_blocks_opens_allows_closes() {
+        let (halt, path) = temp_halt();
+        halt.set_level(HaltLevel::Soft, "De-risk");
+        assert!(halt.is_halted());
+        assert!(!halt.allows(false), "Opens blocked in SOFT_HALT");
+        assert!(halt.allows(true), "Closes allowed in SOFT_HALT");
+
+        // Level survives restart
+        assert_eq!(GlobalHalt::with_file(&path).level(), HaltLevel::Soft);
+
+        halt.set_level(HaltLevel::Open, "Resume");
+        assert!(halt.allows(false));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_hard_halt_blocks_everything() {
+        let (halt, path) = temp_halt();
+        halt.set_level(HaltLevel::Hard, "Emergency");
+        assert!(!halt.allows(false));
+        assert!(!halt.allows(true));
+        assert_eq!(GlobalHalt::with_file(&path).level(), HaltLevel::Hard);
+
+        halt.set_halt(false, "Resume");
+    }
 }
@@ -0,0 +1,257 @@
+use crate::context::{IdProvider, TimeProvider};
+use crate::exchange::adapter::ExchangeError;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::error;
+
+/// Default window during which an issued client_order_id must not be reused.
+pub const DEFAULT_COLLISION_WINDOW_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Fallback length limit for venues without a documented limit.
+pub const DEFAULT_MAX_LEN: usize = 36;
+
+/// Characters of the correlation id embedded in a client_order_id tag.
+pub const CORRELATION_TAG_LEN: usize = 8;
+
+/// Build an id tag carrying a truncated correlation id, e.g. `tx` + `3f2a9c1e`.
+/// `clamp` always keeps the tag, so the correlation survives venue length limits.
+pub fn correlation_prefix(prefix: &str, correlation_id: &str) -> String {
+    let tag: String = correlation_id
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .take(CORRELATION_TAG_LEN)
+        .collect();
+    format!("{}{}", prefix, tag)
+}
+
+/// Maximum retries when a freshly generated id collides with an issued one.
+const MAX_GENERATION_ATTEMPTS: usize = 8;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ClientOrderIdError {
+    #[error("client_order_id '{0}' already issued within the collision window")]
+    Collision(String),
+    #[error("client_order_id '{id}' exceeds {exchange} limit of {limit} chars")]
+    TooLong {
+        exchange: String,
+        id: String,
+        limit: usize,
+    },
+    #[error("client_order_id cannot be empty")]
+    Empty,
+}
+
+/// Maximum client order id length accepted by each venue.
+/// Keys match the names used by `ExecutionRouter::register`.
+pub fn max_len_for(exchange: &str) -> usize {
+    match exchange.to_lowercase().as_str() {
+        "binance" => 36,     // newClientOrderId
+        "bybit" => 36,       // orderLinkId
+        "okx" => 32,         // clOrdId
+        "mexc" => 32,        // externalOid
+        "kucoin" => 40,      // clientOid
+        "gateio" => 28,      // text (including "t-" prefix)
+        "cryptocom" => 36,   // client_oid
+        "coinbase" => 128,   // client_order_id
+        "kraken" => 36,      // cl_ord_id
+        "dydx" => 36,        // client id (hashed to u32 on submit)
+        "hyperliquid" => 34, // cloid (0x + 16 bytes hex)
+        _ => DEFAULT_MAX_LEN,
+    }
+}
+
+/// Clamp an id to `max_len` while keeping its prefix tag and unique tail.
+///
+/// Ids have the form `<tag>-<unique>[-<suffix>]`. When too long we drop characters
+/// from the middle: the tag stays for attribution and the tail carries the entropy
+/// (and any child suffix).
+pub fn clamp(id: &str, max_len: usize) -> String {
+    if id.len() <= max_len {
+        return id.to_string();
+    }
+    match id.split_once('-') {
+        Some((tag, rest)) if tag.len() + 1 < max_len => {
+            let keep = max_len - tag.len() - 1;
+            format!("{}-{}", tag, &rest[rest.len() - keep..])
+        }
+        _ => id[id.len() - max_len..].to_string(),
+    }
+}
+
+/// Validate an outgoing client_order_id against the venue limit.
+/// Adapters call this before submitting so an over-long id is rejected locally
+/// instead of being truncated (and potentially deduped) by the exchange.
+pub fn validate_for_exchange(exchange: &str, id: &str) -> Result<(), ExchangeError> {
+    if id.is_empty() {
+        return Ok(()); // Adapters fall back to their own id when none is supplied
+    }
+    let limit = max_len_for(exchange);
+    if id.len() > limit {
+        return Err(ExchangeError::OrderRejected(
+            ClientOrderIdError::TooLong {
+                exchange: exchange.to_string(),
+                id: id.to_string(),
+                limit,
+            }
+            .to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Centralized client_order_id issuance.
+///
+/// Produces prefix-tagged ids from the context `IdProvider` (deterministic under replay)
+/// and remembers every issued id for `window_ms` so accidental reuse is caught before
+/// an exchange silently dedupes two distinct orders.
+pub struct ClientOrderIdGenerator {
+    id: Arc<dyn IdProvider>,
+    time: Arc<dyn TimeProvider>,
+    window_ms: i64,
+    issued: Mutex<HashMap<String, i64>>,
+}
+
+impl ClientOrderIdGenerator {
+    pub fn new(id: Arc<dyn IdProvider>, time: Arc<dyn TimeProvider>, window_ms: i64) -> Self {
+        Self {
+            id,
+            time,
+            window_ms,
+            issued: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Issue a new id `<prefix>-<unique>` clamped to `max_len`. Fails rather
+    /// than hand out an id that is already in use when every retry collides.
+    pub fn generate(&self, prefix: &str, max_len: usize) -> Result<String, ClientOrderIdError> {
+        let mut candidate = String::new();
+        for _ in 0..MAX_GENERATION_ATTEMPTS {
+            let unique = self.id.new_id().replace('-', "");
+            candidate = clamp(&format!("{}-{}", prefix, unique), max_len);
+            if self.record(&candidate).is_ok() {
+                return Ok(candidate);
+            }
+            error!(client_order_id = %candidate, "client_order_id collision on generate, retrying");
+        }
+        Err(ClientOrderIdError::Collision(candidate))
+    }
+
+    /// Derive the per-venue child id for a fan-out leg, clamped to that venue's limit.
+    pub fn child(
+        &self,
+        parent: &str,
+        exchange: &str,
+        idx: usize,
+    ) -> Result<String, ClientOrderIdError> {
+        let id = clamp(&format!("{}-{}", parent, idx), max_len_for(exchange));
+        self.record(&id)?;
+        Ok(id)
+    }
+
+    /// Record an externally built id. Fails if it was already issued within the window.
+    pub fn record(&self, id: &str) -> Result<(), ClientOrderIdError> {
+        if id.is_empty() {
+            return Err(ClientOrderIdError::Empty);
+        }
+        let now = self.time.now_millis();
+        let mut issued = self.issued.lock();
+        issued.retain(|_, ts| now - *ts <= self.window_ms);
+        if issued.contains_key(id) {
+            return Err(ClientOrderIdError::Collision(id.to_string()));
+        }
+        issued.insert(id.to_string(), now);
+        Ok(())
+    }
+
+    pub fn issued_count(&self) -> usize {
+        self.issued.lock().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::{DeterministicIdProvider, SimulatedTimeProvider};
+    use std::collections::HashSet;
+
+    struct FixedIdProvider;
+
+    impl IdProvider for FixedIdProvider {
+        fn new_id(&self) -> String {
+            "same-id".to_string()
+        }
+    }
+
+    fn generator(window_ms: i64) -> (ClientOrderIdGenerator, Arc<SimulatedTimeProvider>) {
+        let time = Arc::new(SimulatedTimeProvider::new(1_000));
+        let gen = ClientOrderIdGenerator::new(
+            Arc::new(DeterministicIdProvider::new()),
+            time.clone(),
+            window_ms,
+        );
+        (gen, time)
+    }
+
+    #[test]
+    fn test_generated_ids_are_unique() {
+        let (gen, _) = generator(DEFAULT_COLLISION_WINDOW_MS);
+        let ids: HashSet<String> = (0..500).map(|_| gen.generate("tx", 32).unwrap()).collect();
+        assert_eq!(ids.len(), 500);
+        assert!(ids.iter().all(|id| id.starts_with("tx-") && id.len() <= 32));
+    }
+
+    #[test]
+    fn test_length_clamped_per_exchange() {
+        let (gen, _) = generator(DEFAULT_COLLISION_WINDOW_MS);
+        let parent = gen.generate("tx", 64).unwrap();
+
+        for exchange in ["binance", "okx", "gateio", "hyperliquid", "coinbase"] {
+            let child = gen.child(&parent, exchange, 1).unwrap();
+            assert!(
+                child.len() <= max_len_for(exchange),
+                "{} too long",
+                exchange
+            );
+            assert!(child.starts_with("tx-"));
+            assert!(child.ends_with("-1"));
+            assert!(validate_for_exchange(exchange, &child).is_ok());
+        }
+
+        let too_long = "x".repeat(40);
+        assert!(matches!(
+            validate_for_exchange("binance", &too_long),
+            Err(ExchangeError::OrderRejected(_))
+        ));
+    }
+
+    #[test]
+    fn test_collision_detected_within_window() {
+        let (gen, time) = generator(1_000);
+        assert!(gen.record("tx-abc").is_ok());
+        assert_eq!(
+            gen.record("tx-abc"),
+            Err(ClientOrderIdError::Collision("tx-abc".to_string()))
+        );
+
+        // Outside the window the id may be reused
+        time.advance(1_001);
+        assert!(gen.record("tx-abc").is_ok());
+    }
+
+    #[test]
+    fn test_generate_retries_on_collision() {
+        let time = Arc::new(SimulatedTimeProvider::new(0));
+        let gen = ClientOrderIdGenerator::new(Arc::new(FixedIdProvider), time, 60_000);
+        let first = gen.generate("tx", 36).unwrap();
+        assert_eq!(first, "tx-sameid");
+        // Provider keeps returning the same id: every retry collides and
+        // nothing is handed out
+        assert_eq!(
+            gen.generate("tx", 36),
+            Err(ClientOrderIdError::Collision(first.clone()))
+        );
+        assert_eq!(gen.issued_count(), 1);
+    }
+}
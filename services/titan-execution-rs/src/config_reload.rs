@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use config::ConfigError;
+use parking_lot::{Mutex, RwLock};
+use thiserror::Error;
+use tracing::{error, info, warn};
+
+use crate::config::{ConfigValidationError, ExecutionConfig, Settings};
+use crate::exchange::router::ExecutionRouter;
+use crate::order_manager::OrderManagerConfig;
+
+/// Freshness threshold used when the config leaves it unset
+pub const DEFAULT_FRESHNESS_THRESHOLD_MS: u64 = 5000;
+
+#[derive(Debug, Error)]
+pub enum ReloadError {
+    #[error("Failed to load configuration: {0}")]
+    Load(#[from] ConfigError),
+    #[error("Invalid configuration: {0}")]
+    Invalid(#[from] ConfigValidationError),
+    #[error("{0} changed; restart required")]
+    RequiresRestart(String),
+}
+
+/// Applies a reloaded `Settings` to the running engine. Swapped live: routing
+/// (weights, fan-out, dispersion tolerance, symbol rate limits), the intent
+/// freshness threshold, and the order manager's order type schedule and tick
+/// sizes. Per-exchange rate limits live in each adapter's token bucket and
+/// maker/taker fees are not configurable, so neither changes without a
+/// restart. A reload that changes anything else is refused as a whole,
+/// leaving the running config untouched.
+pub struct ConfigReloader {
+    current: Mutex<Settings>,
+    router: Arc<ExecutionRouter>,
+    freshness_threshold_ms: Arc<AtomicU64>,
+    order_manager: Option<Arc<RwLock<OrderManagerConfig>>>,
+}
+
+impl ConfigReloader {
+    pub fn new(
+        settings: Settings,
+        router: Arc<ExecutionRouter>,
+        freshness_threshold_ms: Arc<AtomicU64>,
+    ) -> Self {
+        Self {
+            current: Mutex::new(settings),
+            router,
+            freshness_threshold_ms,
+            order_manager: None,
+        }
+    }
+
+    /// Swap the order manager's config (see `OrderManager::shared_config`)
+    pub fn with_order_manager_config(mut self, config: Arc<RwLock<OrderManagerConfig>>) -> Self {
+        self.order_manager = Some(config);
+        self
+    }
+
+    /// Re-read the config sources and apply them.
+    pub fn reload(&self) -> Result<(), ReloadError> {
+        self.apply(Settings::new()?)
+    }
+
+    pub fn apply(&self, new: Settings) -> Result<(), ReloadError> {
+        new.validate()?;
+        let mut current = self.current.lock();
+        check_restart_only(&current, &new)?;
+
+        let exec = new.execution.clone().unwrap_or_default();
+        self.router.update_routing(exec.routing.unwrap_or_default());
+        let freshness = exec
+            .freshness_threshold_ms
+            .unwrap_or(DEFAULT_FRESHNESS_THRESHOLD_MS);
+        self.freshness_threshold_ms
+            .store(freshness, Ordering::Relaxed);
+        if let Some(order_manager) = &self.order_manager {
+            *order_manager.write() = OrderManagerConfig::from_execution(&exec);
+        }
+
+        *current = new;
+        info!(
+            "♻️ Configuration reloaded (routing, order types, freshness {}ms)",
+            freshness
+        );
+        Ok(())
+    }
+
+    /// Reload on every SIGHUP.
+    pub fn listen_for_sighup(self: Arc<Self>) -> std::io::Result<tokio::task::JoinHandle<()>> {
+        use tokio::signal::unix::{signal, SignalKind};
+        let mut hangups = signal(SignalKind::hangup())?;
+        Ok(tokio::spawn(async move {
+            while hangups.recv().await.is_some() {
+                info!("♻️ SIGHUP received, reloading configuration");
+                if let Err(e) = self.reload() {
+                    error!("❌ Configuration reload rejected: {}", e);
+                }
+            }
+        }))
+    }
+}
+
+/// Refuse changes outside the hot-swappable subset: exchanges (adapters and
+/// their rate limiters are built at startup), routing dedup and maintenance
+/// settings (baked into adapters and trackers), lot sizes (held by the risk
+/// guard), and every other execution section.
+fn check_restart_only(current: &Settings, new: &Settings) -> Result<(), ReloadError> {
+    if current.exchanges != new.exchanges {
+        let current_exchanges = current.exchanges.clone().unwrap_or_default();
+        let new_exchanges = new.exchanges.clone().unwrap_or_default();
+        let current_limits: HashMap<&str, _> = current_exchanges
+            .iter()
+            .map(|(name, c)| (name, c.rate_limit))
+            .collect();
+        let rate_limited = new_exchanges.iter().find(|(name, c)| {
+            current_limits
+                .get(name)
+                .is_some_and(|limit| *limit != c.rate_limit)
+        });
+        return Err(ReloadError::RequiresRestart(match rate_limited {
+            Some((name, _)) => format!("exchanges.{}.rate_limit", name),
+            None => "exchanges".to_string(),
+        }));
+    }
+
+    let current_exec = current.execution.clone().unwrap_or_default();
+    let new_exec = new.execution.clone().unwrap_or_default();
+    let current_routing = current_exec.routing.clone().unwrap_or_default();
+    let new_routing = new_exec.routing.clone().unwrap_or_default();
+    if current_routing.order_dedup_ttl_ms != new_routing.order_dedup_ttl_ms {
+        return Err(ReloadError::RequiresRestart(
+            "execution.routing.order_dedup_ttl_ms".to_string(),
+        ));
+    }
+    if current_routing.maintenance_cooldown_ms != new_routing.maintenance_cooldown_ms {
+        return Err(ReloadError::RequiresRestart(
+            "execution.routing.maintenance_cooldown_ms".to_string(),
+        ));
+    }
+
+    if lot_sizes(&current_exec) != lot_sizes(&new_exec) {
+        return Err(ReloadError::RequiresRestart(
+            "execution.instruments.*.lot_size".to_string(),
+        ));
+    }
+
+    let mut rest = new_exec;
+    rest.routing = current_exec.routing.clone();
+    rest.freshness_threshold_ms = current_exec.freshness_threshold_ms;
+    rest.order_type = current_exec.order_type.clone();
+    rest.instruments = current_exec.instruments.clone();
+    if rest != current_exec {
+        warn!("Reload touches execution settings outside the hot-swappable subset");
+        return Err(ReloadError::RequiresRestart(
+            "execution (other than routing, order_type, instrument tick sizes and freshness_threshold_ms)"
+                .to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn lot_sizes(exec: &ExecutionConfig) -> HashMap<&str, f64> {
+    exec.instruments
+        .iter()
+        .filter_map(|(symbol, instrument)| Some((symbol.as_str(), instrument.lot_size?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{
+        ExchangeConfig, Exchanges, ExecutionConfig, HttpPoolConfig, InstrumentConfig, MarketType,
+        OrderTypeMode, RiskGuardConfig, RoutingConfig,
+    };
+    use crate::exchange::routing::ExplicitOnly;
+    use rust_decimal_macros::dec;
+
+    fn exchange() -> ExchangeConfig {
+        ExchangeConfig {
+            api_key: Some("key".to_string()),
+            secret_key: Some("secret".to_string()),
+            api_key_alt: None,
+            secret_key_alt: None,
+            enabled: true,
+            testnet: true,
+            execute_on: true,
+            rate_limit: None,
+            proxy_url: None,
+            http_pool: HttpPoolConfig::default(),
+            market_type: MarketType::default(),
+            leverage: None,
+            min_leverage: None,
+            max_leverage: None,
+        }
+    }
+
+    fn settings(binance: f64, bybit: f64, freshness_ms: u64) -> Settings {
+        Settings {
+            exchanges: Some(Exchanges {
+                binance: Some(exchange()),
+                bybit: Some(exchange()),
+                ..Default::default()
+            }),
+            execution: Some(ExecutionConfig {
+                nats_url: Some("nats://localhost:4222".to_string()),
+                initial_balance: Some(10_000.0),
+                freshness_threshold_ms: Some(freshness_ms),
+                routing: Some(RoutingConfig {
+                    fanout: Some(true),
+                    weights: Some(HashMap::from([
+                        ("binance".to_string(), binance),
+                        ("bybit".to_string(), bybit),
+                    ])),
+                    ..Default::default()
+                }),
+                risk_guard: RiskGuardConfig {
+                    max_leverage: 5.0,
+                    daily_loss_limit: 500.0,
+                    symbol_whitelist: vec!["BTCUSDT".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn setup(initial: &Settings) -> (ConfigReloader, Arc<ExecutionRouter>, Arc<AtomicU64>) {
+        let exec = initial.execution.clone().unwrap();
+        let router = Arc::new(ExecutionRouter::with_routing(exec.routing.unwrap()));
+        let freshness = Arc::new(AtomicU64::new(exec.freshness_threshold_ms.unwrap()));
+        let reloader = ConfigReloader::new(initial.clone(), router.clone(), freshness.clone());
+        (reloader, router, freshness)
+    }
+
+    fn binance_weight(router: &ExecutionRouter) -> f64 {
+        router.routing().weights.unwrap()["binance"]
+    }
+
+    #[test]
+    fn test_reload_applies_routing_and_freshness() {
+        let (reloader, router, freshness) = setup(&settings(0.7, 0.3, 5000));
+
+        reloader.apply(settings(0.2, 0.8, 2000)).unwrap();
+
+        assert_eq!(binance_weight(&router), 0.2);
+        assert_eq!(freshness.load(Ordering::Relaxed), 2000);
+    }
+
+    #[test]
+    fn test_reload_rejects_restart_only_changes() {
+        let (reloader, router, freshness) = setup(&settings(0.7, 0.3, 5000));
+
+        // Enabling a new exchange needs its adapter built at startup
+        let mut new = settings(0.5, 0.5, 1000);
+        new.exchanges.as_mut().unwrap().okx = Some(exchange());
+        assert!(matches!(
+            reloader.apply(new),
+            Err(ReloadError::RequiresRestart(what)) if what == "exchanges"
+        ));
+
+        let mut new = settings(0.5, 0.5, 1000);
+        new.execution.as_mut().unwrap().active_standby = true;
+        assert!(matches!(
+            reloader.apply(new),
+            Err(ReloadError::RequiresRestart(_))
+        ));
+
+        let mut new = settings(0.5, 0.5, 1000);
+        new.execution
+            .as_mut()
+            .unwrap()
+            .routing
+            .as_mut()
+            .unwrap()
+            .order_dedup_ttl_ms = Some(60_000);
+        assert!(matches!(
+            reloader.apply(new),
+            Err(ReloadError::RequiresRestart(_))
+        ));
+
+        // Venue rate limits are held by the adapters
+        let mut new = settings(0.5, 0.5, 1000);
+        new.exchanges
+            .as_mut()
+            .unwrap()
+            .binance
+            .as_mut()
+            .unwrap()
+            .rate_limit = Some(5);
+        assert!(matches!(
+            reloader.apply(new),
+            Err(ReloadError::RequiresRestart(what)) if what == "exchanges.binance.rate_limit"
+        ));
+
+        // Lot sizes are held by the risk guard
+        let mut new = settings(0.5, 0.5, 1000);
+        new.execution.as_mut().unwrap().instruments = HashMap::from([(
+            "BTC/USDT".to_string(),
+            InstrumentConfig {
+                tick_size: None,
+                lot_size: Some(0.001),
+            },
+        )]);
+        assert!(matches!(
+            reloader.apply(new),
+            Err(ReloadError::RequiresRestart(what)) if what == "execution.instruments.*.lot_size"
+        ));
+
+        // Nothing from the refused reloads was applied
+        assert_eq!(binance_weight(&router), 0.7);
+        assert_eq!(freshness.load(Ordering::Relaxed), 5000);
+    }
+
+    #[test]
+    fn test_reload_swaps_order_manager_config_and_keeps_custom_strategy() {
+        let initial = settings(0.7, 0.3, 5000);
+        let exec = initial.execution.clone().unwrap();
+        let router = Arc::new(
+            ExecutionRouter::with_routing(exec.routing.unwrap())
+                .with_routing_strategy(Arc::new(ExplicitOnly)),
+        );
+        let order_manager = Arc::new(RwLock::new(OrderManagerConfig::default()));
+        let reloader = ConfigReloader::new(initial, router.clone(), Arc::new(AtomicU64::new(5000)))
+            .with_order_manager_config(order_manager.clone());
+
+        let mut new = settings(0.2, 0.8, 5000);
+        let new_exec = new.execution.as_mut().unwrap();
+        new_exec.order_type.mode = OrderTypeMode::Urgency;
+        new_exec.instruments = HashMap::from([(
+            "BTC/USDT".to_string(),
+            InstrumentConfig {
+                tick_size: Some(0.5),
+                lot_size: None,
+            },
+        )]);
+        reloader.apply(new).unwrap();
+
+        let config = order_manager.read();
+        assert_eq!(config.order_type.mode, OrderTypeMode::Urgency);
+        assert_eq!(config.tick_sizes["BTC/USDT"], dec!(0.5));
+        drop(config);
+        assert_eq!(binance_weight(&router), 0.2);
+        assert_eq!(router.strategy_name(), "explicit_only");
+    }
+}
@@ -1,3 +1,4 @@
+use crate::client_order_id::{ClientOrderIdGenerator, DEFAULT_COLLISION_WINDOW_MS};
 use chrono::{DateTime, TimeZone, Utc};
 use std::sync::atomic::{AtomicI64, Ordering};
 use std::sync::{Arc, Mutex};
@@ -22,20 +23,37 @@ pub trait IdProvider: Send + Sync {
 pub struct ExecutionContext {
     pub time: Arc<dyn TimeProvider>,
     pub id: Arc<dyn IdProvider>,
+    pub client_order_ids: Arc<ClientOrderIdGenerator>,
 }
 
 impl ExecutionContext {
     pub fn new_system() -> Self {
-        Self {
-            time: Arc::new(SystemTimeProvider),
-            id: Arc::new(RandomIdProvider),
-        }
+        Self::from_providers(Arc::new(SystemTimeProvider), Arc::new(RandomIdProvider))
     }
 
     pub fn new_simulated(start_time_ms: i64) -> Self {
+        Self::from_providers(
+            Arc::new(SimulatedTimeProvider::new(start_time_ms)),
+            Arc::new(DeterministicIdProvider::new()),
+        )
+    }
+
+    /// Context for tests: time only moves when the test advances `clock`.
+    pub fn new_test(clock: Arc<MockClock>) -> Self {
+        Self::from_providers(clock, Arc::new(DeterministicIdProvider::new()))
+    }
+
+    /// Build a context from explicit providers; the client order id generator shares them.
+    pub fn from_providers(time: Arc<dyn TimeProvider>, id: Arc<dyn IdProvider>) -> Self {
+        let client_order_ids = Arc::new(ClientOrderIdGenerator::new(
+            id.clone(),
+            time.clone(),
+            DEFAULT_COLLISION_WINDOW_MS,
+        ));
         Self {
-            time: Arc::new(SimulatedTimeProvider::new(start_time_ms)),
-            id: Arc::new(DeterministicIdProvider::new()),
+            time,
+            id,
+            client_order_ids,
         }
     }
 }
@@ -96,6 +114,10 @@ impl TimeProvider for SimulatedTimeProvider {
     }
 }
 
+/// Manually advanced clock for tests; keep the `Arc` to move time forward
+/// after handing it to `ExecutionContext::new_test`.
+pub type MockClock = SimulatedTimeProvider;
+
 pub struct DeterministicIdProvider {
     counter: Mutex<u64>,
 }
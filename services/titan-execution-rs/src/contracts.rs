@@ -41,6 +41,8 @@ pub struct Envelope {
 
     pub sig: Option<String>,
 
+    pub sig_alg: Option<String>,
+
     pub ts: Option<i64>,
 
     #[serde(rename = "type")]
@@ -106,6 +108,8 @@ pub struct IntentEnvelope {
 
     pub sig: Option<String>,
 
+    pub sig_alg: Option<String>,
+
     pub ts: Option<i64>,
 
     #[serde(rename = "type")]
@@ -0,0 +1,138 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::config::DeadMansSwitchConfig;
+use crate::exchange::adapter::{ExchangeAdapter, ExchangeError};
+
+type Venue = (String, Arc<dyn ExchangeAdapter + Send + Sync>);
+
+/// Keeps venue cancel-on-disconnect timers armed while the process is alive.
+/// If we crash or lose connectivity the keepalives stop and the venues cancel
+/// our resting orders; a clean shutdown disarms the timers first.
+pub struct DeadMansSwitch {
+    venues: Vec<Venue>,
+    window_ms: u64,
+    keepalive: Duration,
+    stop_tx: watch::Sender<bool>,
+    handle: Mutex<Option<JoinHandle<Vec<Venue>>>>,
+}
+
+impl DeadMansSwitch {
+    pub fn new(venues: Vec<Venue>, config: &DeadMansSwitchConfig) -> Self {
+        let keepalive_ms = config.keepalive_ms.unwrap_or(config.window_ms / 4).max(1);
+        let (stop_tx, _) = watch::channel(false);
+        Self {
+            venues,
+            window_ms: config.window_ms,
+            keepalive: Duration::from_millis(keepalive_ms),
+            stop_tx,
+            handle: Mutex::new(None),
+        }
+    }
+
+    /// Spawns the keepalive loop. The first tick arms every venue; venues
+    /// without cancel-on-disconnect support are dropped after that.
+    pub fn start(&self) {
+        let mut venues = self.venues.clone();
+        let window_ms = self.window_ms;
+        let mut interval = tokio::time::interval(self.keepalive);
+        let mut stop_rx = self.stop_tx.subscribe();
+
+        let handle = tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = stop_rx.changed() => break,
+                }
+                let mut armed = Vec::with_capacity(venues.len());
+                for (name, adapter) in venues {
+                    match adapter.set_dead_mans_switch(window_ms).await {
+                        Ok(()) => armed.push((name, adapter)),
+                        Err(ExchangeError::NotImplemented(msg)) => {
+                            info!("Dead man's switch skipped for {}: {}", name, msg);
+                        }
+                        Err(e) => {
+                            // Keep trying; the venue timer may still be running
+                            warn!("Dead man's switch keepalive failed for {}: {}", name, e);
+                            armed.push((name, adapter));
+                        }
+                    }
+                }
+                venues = armed;
+            }
+            venues
+        });
+        *self.handle.lock() = Some(handle);
+    }
+
+    /// Stops the keepalive loop and disarms the venues it was refreshing.
+    pub async fn shutdown(&self) {
+        let _ = self.stop_tx.send(true);
+        let handle = self.handle.lock().take();
+        let Some(handle) = handle else {
+            return;
+        };
+        let venues = match handle.await {
+            Ok(venues) => venues,
+            Err(e) => {
+                warn!("Dead man's switch task ended abnormally: {}", e);
+                return;
+            }
+        };
+        for (name, adapter) in venues {
+            match adapter.set_dead_mans_switch(0).await {
+                Ok(()) => info!("Dead man's switch disarmed for {}", name),
+                Err(e) => warn!("Failed to disarm dead man's switch for {}: {}", name, e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::MockAdapter;
+
+    #[tokio::test]
+    async fn test_keepalive_interval_and_disarm_on_shutdown() {
+        let supported = Arc::new(MockAdapter::new("bybit").with_dead_mans_switch());
+        let unsupported = Arc::new(MockAdapter::new("binance"));
+        let config = DeadMansSwitchConfig {
+            enabled: true,
+            window_ms: 5_000,
+            keepalive_ms: Some(100),
+        };
+        let venues: Vec<Venue> = vec![
+            ("bybit".to_string(), supported.clone() as Arc<_>),
+            ("binance".to_string(), unsupported.clone() as Arc<_>),
+        ];
+        let switch = DeadMansSwitch::new(venues, &config);
+
+        switch.start();
+        // Ticks at 0, 100, 200 and 300ms
+        tokio::time::sleep(Duration::from_millis(350)).await;
+        switch.shutdown().await;
+
+        let calls = supported.dead_mans_switch_calls.lock().clone();
+        let (last, keepalives) = calls.split_last().unwrap();
+        assert!(
+            (3..=5).contains(&keepalives.len()),
+            "unexpected keepalive count: {:?}",
+            calls
+        );
+        assert!(keepalives.iter().all(|w| *w == 5_000));
+        assert_eq!(*last, 0);
+
+        // Unsupported venues are probed once and never disarmed
+        assert_eq!(*unsupported.dead_mans_switch_calls.lock(), vec![5_000]);
+
+        // No keepalives after shutdown
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(supported.dead_mans_switch_calls.lock().len(), calls.len());
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+
+use parking_lot::RwLock;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use tracing::warn;
+
+use crate::config::DepthGateConfig;
+use crate::market_data::model::OrderBookL2;
+use crate::market_data::orderbook_manager::OrderBookManager;
+use crate::model::Side;
+use crate::risk_guard::RiskRejectionReason;
+
+/// Blocks market orders into thin books. The depth resting within `band_bps`
+/// of the touch on the side the order consumes must cover the order size times
+/// `safety_factor`; otherwise the order should go out as a limit instead.
+/// Symbols without a recorded book are not gated.
+pub struct DepthGate {
+    config: DepthGateConfig,
+    /// Venue -> local L2 books, keyed by normalized symbol
+    books: RwLock<HashMap<String, OrderBookManager>>,
+}
+
+impl DepthGate {
+    pub fn new(config: DepthGateConfig) -> Self {
+        Self {
+            config,
+            books: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn key(symbol: &str) -> String {
+        symbol.replace("/", "").replace("_", "").to_uppercase()
+    }
+
+    /// Apply an L2 snapshot or delta from the book feed. `venue` is the router
+    /// adapter name, not the connector's own exchange label.
+    pub fn record(&self, venue: &str, book: &OrderBookL2) {
+        let mut event = book.clone();
+        event.symbol = Self::key(&book.symbol);
+        self.books
+            .write()
+            .entry(venue.to_lowercase())
+            .or_default()
+            .apply_event(&event);
+    }
+
+    /// Size resting within the band of the touch on the side a market order
+    /// on `side` would take, or None if `venue` has no book for the symbol.
+    pub fn depth_within_band(&self, venue: &str, symbol: &str, side: &Side) -> Option<Decimal> {
+        let books = self.books.read();
+        let book = books
+            .get(&venue.to_lowercase())?
+            .get_snapshot(&Self::key(symbol), usize::MAX)?;
+        let band = Decimal::from_f64(self.config.band_bps / 10_000.0).unwrap_or(Decimal::ZERO);
+
+        let depth = match side {
+            Side::Buy | Side::Long => match book.asks.first() {
+                Some(best) => {
+                    let limit = best.price * (Decimal::ONE + band);
+                    book.asks
+                        .iter()
+                        .take_while(|l| l.price <= limit)
+                        .map(|l| l.quantity)
+                        .sum()
+                }
+                None => Decimal::ZERO,
+            },
+            Side::Sell | Side::Short => match book.bids.first() {
+                Some(best) => {
+                    let limit = best.price * (Decimal::ONE - band);
+                    book.bids
+                        .iter()
+                        .take_while(|l| l.price >= limit)
+                        .map(|l| l.quantity)
+                        .sum()
+                }
+                None => Decimal::ZERO,
+            },
+        };
+        Some(depth)
+    }
+
+    /// Check a market order before it is sent. With no target venue, every
+    /// venue holding a book for the symbol must be deep enough.
+    pub fn check(
+        &self,
+        venue: Option<&str>,
+        symbol: &str,
+        side: &Side,
+        quantity: Decimal,
+        reduce_only: bool,
+    ) -> Result<(), RiskRejectionReason> {
+        if !self.config.enabled || reduce_only {
+            return Ok(());
+        }
+
+        let venues: Vec<String> = match venue {
+            Some(v) => vec![v.to_lowercase()],
+            None => self.books.read().keys().cloned().collect(),
+        };
+        let factor = Decimal::from_f64(self.config.safety_factor).unwrap_or(Decimal::ONE);
+        let required = quantity * factor;
+
+        for venue in venues {
+            let Some(available) = self.depth_within_band(&venue, symbol, side) else {
+                continue;
+            };
+            if available < required {
+                warn!(
+                    symbol = %symbol,
+                    venue = %venue,
+                    %available,
+                    %required,
+                    "Risk Reject: book too thin for market order"
+                );
+                return Err(RiskRejectionReason::InsufficientBookDepth {
+                    symbol: symbol.to_string(),
+                    required,
+                    available,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market_data::model::OrderBookLevel;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn level(price: Decimal, quantity: Decimal) -> OrderBookLevel {
+        OrderBookLevel { price, quantity }
+    }
+
+    fn book(asks: Vec<OrderBookLevel>) -> OrderBookL2 {
+        OrderBookL2 {
+            symbol: "BTCUSDT".to_string(),
+            bids: vec![level(dec!(49990), dec!(5))],
+            asks,
+            timestamp: Utc::now(),
+            update_id: 1,
+            is_snapshot: true,
+            exchange: "BINANCE_FUTURES".to_string(),
+        }
+    }
+
+    fn gate() -> DepthGate {
+        DepthGate::new(DepthGateConfig {
+            enabled: true,
+            safety_factor: 3.0,
+            band_bps: 10.0,
+        })
+    }
+
+    #[test]
+    fn test_deep_book_passes() {
+        let gate = gate();
+        gate.record(
+            "binance",
+            &book(vec![
+                level(dec!(50000), dec!(1)),
+                level(dec!(50020), dec!(1)),
+                level(dec!(50040), dec!(2)),
+            ]),
+        );
+
+        // 4 BTC within 10bps of 50000 covers 1 BTC x 3
+        assert_eq!(
+            gate.depth_within_band("binance", "BTC/USDT", &Side::Buy),
+            Some(dec!(4))
+        );
+        assert!(gate
+            .check(Some("binance"), "BTC/USDT", &Side::Buy, dec!(1), false)
+            .is_ok());
+        assert!(gate
+            .check(None, "BTC/USDT", &Side::Buy, dec!(1), false)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_thin_book_rejects_same_order() {
+        let gate = gate();
+        // Plenty of size, but mostly outside the band
+        gate.record(
+            "binance",
+            &book(vec![
+                level(dec!(50000), dec!(0.5)),
+                level(dec!(50040), dec!(1)),
+                level(dec!(50200), dec!(10)),
+            ]),
+        );
+
+        let result = gate.check(Some("binance"), "BTC/USDT", &Side::Buy, dec!(1), false);
+        match result {
+            Err(RiskRejectionReason::InsufficientBookDepth {
+                required,
+                available,
+                ..
+            }) => {
+                assert_eq!(required, dec!(3));
+                assert_eq!(available, dec!(1.5));
+            }
+            other => panic!("Expected InsufficientBookDepth, got {:?}", other),
+        }
+
+        // Exits are never blocked, and unknown venues are not gated
+        assert!(gate
+            .check(Some("binance"), "BTC/USDT", &Side::Buy, dec!(1), true)
+            .is_ok());
+        assert!(gate
+            .check(Some("bybit"), "BTC/USDT", &Side::Buy, dec!(1), false)
+            .is_ok());
+    }
+}
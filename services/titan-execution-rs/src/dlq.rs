@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::exchange::rejection::VenueRejection;
+
+/// Why a message was dead-lettered. Published as `reason_code` next to the
+/// free-text `reason` so dashboards can group DLQ traffic without parsing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DlqReasonCode {
+    /// Refused by the pre-trade risk guard
+    RiskRejection,
+    /// Payload could not be parsed or failed schema validation
+    ValidationFailure,
+    /// Envelope signature did not verify
+    HmacMismatch,
+    /// Envelope producer is not on the intent source allowlist
+    ProducerNotAllowed,
+    /// Intent was sized against a different risk policy
+    PolicyHashMismatch,
+    /// Venue refused the order or reported an implausible fill
+    AdapterError,
+    /// Part of a fill past its intent size, held back from shadow state
+    Overfill,
+    /// Intent arrived after its freshness window
+    Timeout,
+    /// Processing the intent panicked
+    Panic,
+}
+
+impl DlqReasonCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DlqReasonCode::RiskRejection => "risk_rejection",
+            DlqReasonCode::ValidationFailure => "validation_failure",
+            DlqReasonCode::HmacMismatch => "hmac_mismatch",
+            DlqReasonCode::ProducerNotAllowed => "producer_not_allowed",
+            DlqReasonCode::PolicyHashMismatch => "policy_hash_mismatch",
+            DlqReasonCode::AdapterError => "adapter_error",
+            DlqReasonCode::Overfill => "overfill",
+            DlqReasonCode::Timeout => "timeout",
+            DlqReasonCode::Panic => "panic",
+        }
+    }
+}
+
+impl std::fmt::Display for DlqReasonCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// DLQ record for a rejected message. The original payload is embedded as
+/// JSON when it parses, otherwise as a lossy string.
+pub fn dlq_payload(code: DlqReasonCode, reason: &str, payload: &[u8], t_ingress: i64) -> Value {
+    let parsed_payload = serde_json::from_slice::<Value>(payload)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(payload).to_string()));
+
+    serde_json::json!({
+        "reason_code": code,
+        "reason": reason,
+        "payload": parsed_payload,
+        "t_ingress": t_ingress,
+    })
+}
+
+/// DLQ record for an intent the venues refused, with each venue's native code
+/// and whether resubmitting could succeed (any refusal being retryable).
+pub fn dlq_payload_with_rejections(
+    code: DlqReasonCode,
+    reason: &str,
+    payload: &[u8],
+    t_ingress: i64,
+    rejections: &[VenueRejection],
+) -> Value {
+    let mut record = dlq_payload(code, reason, payload, t_ingress);
+    if !rejections.is_empty() {
+        record["venue_rejections"] = serde_json::json!(rejections);
+        record["retryable"] = Value::Bool(rejections.iter().any(|r| r.retryable));
+    }
+    record
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dlq_payload_carries_code_and_reason() {
+        let record = dlq_payload(
+            DlqReasonCode::PolicyHashMismatch,
+            "Policy Hash mismatch: exp a got b",
+            br#"{"signal_id":"sig-1"}"#,
+            42,
+        );
+        assert_eq!(record["reason_code"], "policy_hash_mismatch");
+        assert_eq!(record["reason"], "Policy Hash mismatch: exp a got b");
+        assert_eq!(record["payload"]["signal_id"], "sig-1");
+        assert_eq!(record["t_ingress"], 42);
+
+        let raw = dlq_payload(
+            DlqReasonCode::ValidationFailure,
+            "Invalid JSON",
+            b"not json",
+            1,
+        );
+        assert_eq!(raw["payload"], "not json");
+    }
+
+    #[test]
+    fn test_bybit_ret_codes_carried_with_retry_decision() {
+        use crate::exchange::adapter::ExchangeError;
+        let rejection = |code: &str, message: &str| {
+            VenueRejection::from_error(
+                "bybit",
+                &ExchangeError::VenueRejected {
+                    code: code.to_string(),
+                    message: message.to_string(),
+                },
+            )
+            .unwrap()
+        };
+
+        let record = dlq_payload_with_rejections(
+            DlqReasonCode::AdapterError,
+            "EXECUTION FAILED",
+            br#"{"signal_id":"sig-1"}"#,
+            7,
+            &[rejection("110007", "ab not enough for new order")],
+        );
+        assert_eq!(record["reason_code"], "adapter_error");
+        let venue = &record["venue_rejections"][0];
+        assert_eq!(venue["exchange"], "bybit");
+        assert_eq!(venue["code"], "110007");
+        assert_eq!(venue["message"], "ab not enough for new order");
+        assert_eq!(venue["class"], "insufficient_balance");
+        assert_eq!(venue["retryable"], false);
+        assert_eq!(record["retryable"], false);
+
+        let record = dlq_payload_with_rejections(
+            DlqReasonCode::AdapterError,
+            "EXECUTION FAILED",
+            b"{}",
+            7,
+            &[
+                rejection("110007", "ab not enough for new order"),
+                rejection("10006", "Too many visits"),
+            ],
+        );
+        assert_eq!(record["venue_rejections"][1]["class"], "rate_limited");
+        assert_eq!(record["venue_rejections"][1]["retryable"], true);
+        assert_eq!(record["retryable"], true);
+
+        // Non-venue drops keep the plain shape
+        let plain = dlq_payload_with_rejections(DlqReasonCode::Timeout, "stale", b"{}", 7, &[]);
+        assert!(plain.get("venue_rejections").is_none());
+        assert!(plain.get("retryable").is_none());
+    }
+
+    #[test]
+    fn test_reason_codes_serialize_as_their_str() {
+        for code in [
+            DlqReasonCode::RiskRejection,
+            DlqReasonCode::ValidationFailure,
+            DlqReasonCode::HmacMismatch,
+            DlqReasonCode::ProducerNotAllowed,
+            DlqReasonCode::PolicyHashMismatch,
+            DlqReasonCode::AdapterError,
+            DlqReasonCode::Overfill,
+            DlqReasonCode::Timeout,
+            DlqReasonCode::Panic,
+        ] {
+            assert_eq!(serde_json::to_value(code).unwrap(), code.as_str());
+        }
+    }
+}
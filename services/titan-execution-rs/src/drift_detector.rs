@@ -172,6 +172,7 @@ mod tests {
             closed_at: Utc::now(),
             close_reason: "".to_string(),
             metadata: None,
+            lots: Vec::new(),
         };
 
         let reports = detector.analyze(&intent, &trade);
@@ -210,6 +211,7 @@ mod tests {
             closed_at: Utc::now(),
             close_reason: "".to_string(),
             metadata: None,
+            lots: Vec::new(),
         };
 
         let reports = detector.analyze(&intent, &trade);
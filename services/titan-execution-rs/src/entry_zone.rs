@@ -0,0 +1,135 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::RwLock;
+use rust_decimal::Decimal;
+use tracing::{error, info};
+
+use crate::config::EntryZoneConfig;
+use crate::exchange::router::ExecutionRouter;
+use crate::model::{Intent, Side};
+use crate::shadow_state::ShadowState;
+use crate::tp_ladder::ladder_sizes;
+
+/// Precision of the interpolated zone prices.
+const PRICE_DP: u32 = 8;
+
+/// `levels` evenly spaced prices from one edge of the zone to the other,
+/// nearest the market first: buys start at the top, sells at the bottom.
+pub fn zone_prices(low: Decimal, high: Decimal, levels: usize, side: &Side) -> Vec<Decimal> {
+    if levels == 0 {
+        return Vec::new();
+    }
+    if levels == 1 {
+        return vec![high];
+    }
+    let step = (high - low) / Decimal::from(levels as u64 - 1);
+    let ascending = (0..levels).map(|i| {
+        if i + 1 == levels {
+            high
+        } else {
+            (low + step * Decimal::from(i as u64)).round_dp(PRICE_DP)
+        }
+    });
+    match side {
+        Side::Buy | Side::Long => ascending.rev().collect(),
+        Side::Sell | Side::Short => ascending.collect(),
+    }
+}
+
+/// Splits an entry across the intent's `entry_zone` as scaled limit orders,
+/// and cancels whatever is still resting when the intent's TTL runs out.
+pub struct EntryZoneExecutor {
+    shadow_state: Arc<RwLock<ShadowState>>,
+    router: Arc<ExecutionRouter>,
+    config: EntryZoneConfig,
+}
+
+impl EntryZoneExecutor {
+    pub fn new(
+        shadow_state: Arc<RwLock<ShadowState>>,
+        router: Arc<ExecutionRouter>,
+        config: EntryZoneConfig,
+    ) -> Self {
+        Self {
+            shadow_state,
+            router,
+            config,
+        }
+    }
+
+    /// (price, quantity) per zone order, or None when the intent should go
+    /// out as a single order (disabled, or the zone is a single price).
+    pub fn plan(&self, intent: &Intent, side: &Side) -> Option<Vec<(Decimal, Decimal)>> {
+        if !self.config.enabled || self.config.levels < 2 {
+            return None;
+        }
+        let low = intent.entry_zone.iter().copied().min()?;
+        let high = intent.entry_zone.iter().copied().max()?;
+        if low <= Decimal::ZERO || low == high {
+            return None;
+        }
+
+        let prices = zone_prices(low, high, self.config.levels, side);
+        let sizes = ladder_sizes(intent.size, prices.len(), self.config.distribution);
+        Some(
+            prices
+                .into_iter()
+                .zip(sizes)
+                .filter(|(_, qty)| *qty > Decimal::ZERO)
+                .collect(),
+        )
+    }
+
+    pub fn ttl(&self, intent: &Intent) -> Duration {
+        let ttl_ms = intent.ttl_ms.unwrap_or(self.config.ttl_ms).max(0);
+        Duration::from_millis(ttl_ms as u64)
+    }
+
+    /// Wait out the TTL, then expire the intent.
+    pub async fn expire_after(&self, signal_id: &str, symbol: &str, ttl: Duration) {
+        tokio::time::sleep(ttl).await;
+        self.expire(signal_id, symbol).await;
+    }
+
+    /// Cancel the intent's zone orders that can still fill and expire the
+    /// intent, releasing the cash held for them. Returns the cancel count.
+    pub async fn expire(&self, signal_id: &str, symbol: &str) -> usize {
+        let children = self
+            .shadow_state
+            .read()
+            .get_child_orders(signal_id)
+            .cloned()
+            .unwrap_or_default();
+
+        let mut cancelled = 0;
+        for child in children.iter().filter(|c| !c.status.is_terminal()) {
+            let Some(adapter) = self.router.get_adapter(&child.exchange) else {
+                continue;
+            };
+            match adapter
+                .cancel_order(&symbol.replace("/", ""), &child.execution_order_id)
+                .await
+            {
+                Ok(_) => cancelled += 1,
+                Err(e) => error!(
+                    "❌ Failed to cancel zone order {} on {}: {}",
+                    child.execution_order_id, child.exchange, e
+                ),
+            }
+        }
+
+        if self
+            .shadow_state
+            .write()
+            .expire_intent(signal_id, "Entry zone TTL elapsed".to_string())
+            .is_some()
+        {
+            info!(
+                "⏱️ Entry zone for {} expired: {} orders cancelled",
+                signal_id, cancelled
+            );
+        }
+        cancelled
+    }
+}
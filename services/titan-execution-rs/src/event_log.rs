@@ -0,0 +1,295 @@
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+
+use crate::persistence::redb_store::StoreError;
+use crate::persistence::store::PersistenceStore;
+use crate::shadow_state::ExecutionEvent;
+
+/// Most records returned by one replay request; consumers page with `fromSeq`
+pub const MAX_REPLAY_LIMIT: usize = 1000;
+
+pub const TYPE_POSITION_OPENED: &str = "position.opened";
+pub const TYPE_POSITION_UPDATED: &str = "position.updated";
+pub const TYPE_TRADE_CLOSED: &str = "trade.closed";
+pub const TYPE_FUNDING_PAID: &str = "funding.paid";
+pub const TYPE_BALANCE_UPDATED: &str = "balance.updated";
+pub const TYPE_FILL_ANOMALY: &str = "fill.anomaly";
+pub const TYPE_FILL_OVERFILL: &str = "fill.overfill";
+pub const TYPE_FILL: &str = "fill";
+pub const TYPE_POSITION_SYNCED: &str = "position.synced";
+pub const TYPE_INTENT_DEAD_LETTERED: &str = "intent.dead_lettered";
+pub const TYPE_INTENT_TRANSITION: &str = "intent.transition";
+pub const TYPE_OPERATOR_CONTROL: &str = "operator.control";
+
+/// One state change on the CDC feed. `seq` is gap-free and strictly increasing
+/// across restarts, so a consumer that sees a jump knows to replay.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LoggedEvent {
+    pub seq: u64,
+    pub ts: i64,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub correlation_id: Option<String>,
+    pub payload: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayRequest {
+    pub from_seq: u64,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayResponse {
+    pub events: Vec<LoggedEvent>,
+    /// Head of the log; more remain when the last returned seq is below it
+    pub last_seq: u64,
+}
+
+/// Persisted, sequenced log of everything published about execution state.
+/// Sequence assignment and the write happen under one lock, so the order of
+/// sequence numbers is the order records hit disk.
+pub struct EventLog {
+    persistence: Arc<PersistenceStore>,
+    last_seq: Mutex<u64>,
+}
+
+impl EventLog {
+    pub fn new(persistence: Arc<PersistenceStore>) -> Self {
+        let last_seq = match persistence.last_event_seq() {
+            Ok(seq) => seq.unwrap_or(0),
+            Err(e) => {
+                error!("Failed to read event log head, starting at 0: {}", e);
+                0
+            }
+        };
+        info!("Event log resumed at seq {}", last_seq);
+        Self {
+            persistence,
+            last_seq: Mutex::new(last_seq),
+        }
+    }
+
+    pub fn last_seq(&self) -> u64 {
+        *self.last_seq.lock()
+    }
+
+    pub fn append(
+        &self,
+        event_type: &str,
+        correlation_id: Option<&str>,
+        payload: serde_json::Value,
+        ts: i64,
+    ) -> Result<LoggedEvent, StoreError> {
+        let mut last_seq = self.last_seq.lock();
+        let event = LoggedEvent {
+            seq: *last_seq + 1,
+            ts,
+            event_type: event_type.to_string(),
+            correlation_id: correlation_id.map(str::to_string),
+            payload,
+        };
+        self.persistence
+            .append_event(event.seq, &serde_json::to_vec(&event)?)?;
+        *last_seq = event.seq;
+        Ok(event)
+    }
+
+    pub fn append_execution_event(
+        &self,
+        event: &ExecutionEvent,
+        correlation_id: Option<&str>,
+        ts: i64,
+    ) -> Result<LoggedEvent, StoreError> {
+        let (event_type, payload) = match event {
+            ExecutionEvent::Opened(pos) => (TYPE_POSITION_OPENED, serde_json::to_value(pos)?),
+            ExecutionEvent::Updated(pos) => (TYPE_POSITION_UPDATED, serde_json::to_value(pos)?),
+            ExecutionEvent::Closed(trade) => (TYPE_TRADE_CLOSED, serde_json::to_value(trade)?),
+            ExecutionEvent::FundingPaid(symbol, amount, asset) => (
+                TYPE_FUNDING_PAID,
+                serde_json::json!({ "symbol": symbol, "amount": amount, "asset": asset }),
+            ),
+            ExecutionEvent::BalanceUpdated(equity, cash) => (
+                TYPE_BALANCE_UPDATED,
+                serde_json::json!({ "equity": equity, "availableCash": cash }),
+            ),
+            ExecutionEvent::FillAnomaly(anomaly) => {
+                (TYPE_FILL_ANOMALY, serde_json::to_value(anomaly)?)
+            }
+            ExecutionEvent::Overfill(overfill) => {
+                (TYPE_FILL_OVERFILL, serde_json::to_value(overfill)?)
+            }
+        };
+        self.append(event_type, correlation_id, payload, ts)
+    }
+
+    /// Records with seq >= `from_seq`, oldest first, capped at MAX_REPLAY_LIMIT.
+    pub fn replay_from(&self, from_seq: u64, limit: usize) -> Result<Vec<LoggedEvent>, StoreError> {
+        self.persistence
+            .load_events_from(from_seq, limit.min(MAX_REPLAY_LIMIT))?
+            .into_iter()
+            .map(|(_, data)| serde_json::from_slice(&data).map_err(StoreError::from))
+            .collect()
+    }
+
+    pub fn handle_replay(&self, request: &ReplayRequest) -> Result<ReplayResponse, StoreError> {
+        let events =
+            self.replay_from(request.from_seq, request.limit.unwrap_or(MAX_REPLAY_LIMIT))?;
+        Ok(ReplayResponse {
+            events,
+            last_seq: self.last_seq(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Position, Side, TradeRecord};
+    use crate::persistence::redb_store::RedbStore;
+    use crate::persistence::wal::WalManager;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn test_persistence() -> (Arc<PersistenceStore>, String) {
+        let path = format!("/tmp/test_event_log_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        (Arc::new(PersistenceStore::new(redb, wal)), path)
+    }
+
+    fn position() -> Position {
+        Position {
+            symbol: "BTC/USDT".to_string(),
+            side: Side::Long,
+            size: dec!(0.1),
+            entry_price: dec!(50000),
+            stop_loss: dec!(49000),
+            take_profits: vec![],
+            signal_id: "sig-cdc".to_string(),
+            opened_at: Utc::now(),
+            regime_state: None,
+            phase: None,
+            metadata: None,
+            exchange: Some("binance".to_string()),
+            position_mode: None,
+            realized_pnl: dec!(0),
+            unrealized_pnl: dec!(0),
+            fees_paid: dec!(0),
+            funding_paid: dec!(0),
+            last_mark_price: None,
+            last_update_ts: 0,
+            entry_lots: Vec::new(),
+        }
+    }
+
+    fn trade() -> TradeRecord {
+        TradeRecord {
+            signal_id: "sig-cdc".to_string(),
+            symbol: "BTC/USDT".to_string(),
+            side: Side::Long,
+            entry_price: dec!(50000),
+            exit_price: dec!(51000),
+            size: dec!(0.1),
+            pnl: dec!(100),
+            pnl_pct: dec!(2),
+            fee: dec!(0),
+            fee_asset: "USDT".to_string(),
+            opened_at: Utc::now(),
+            closed_at: Utc::now(),
+            close_reason: "TP".to_string(),
+            metadata: None,
+            lots: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_sequence_monotonic_across_mixed_events_and_restart() {
+        let (persistence, path) = test_persistence();
+        let log = EventLog::new(persistence.clone());
+
+        let events = vec![
+            ExecutionEvent::Opened(position()),
+            ExecutionEvent::Updated(position()),
+            ExecutionEvent::FundingPaid("BTC/USDT".to_string(), dec!(1.5), "USDT".to_string()),
+            ExecutionEvent::Closed(trade()),
+            ExecutionEvent::BalanceUpdated(dec!(10100), dec!(10100)),
+        ];
+        let mut seqs = Vec::new();
+        for (i, event) in events.iter().enumerate() {
+            seqs.push(
+                log.append_execution_event(event, Some("corr-cdc"), i as i64)
+                    .unwrap()
+                    .seq,
+            );
+        }
+        seqs.push(
+            log.append(TYPE_FILL, None, serde_json::json!({ "qty": "0.1" }), 9)
+                .unwrap()
+                .seq,
+        );
+        assert_eq!(seqs, vec![1, 2, 3, 4, 5, 6]);
+
+        // A restart resumes after the persisted head instead of reusing seqs
+        let log = EventLog::new(persistence);
+        assert_eq!(log.last_seq(), 6);
+        let next = log
+            .append_execution_event(&ExecutionEvent::Opened(position()), None, 10)
+            .unwrap();
+        assert_eq!(next.seq, 7);
+        assert_eq!(next.event_type, TYPE_POSITION_OPENED);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[test]
+    fn test_replay_from_seq_returns_suffix() {
+        let (persistence, path) = test_persistence();
+        let log = EventLog::new(persistence);
+
+        // Nothing logged yet
+        let empty = log
+            .handle_replay(&ReplayRequest {
+                from_seq: 1,
+                limit: None,
+            })
+            .unwrap();
+        assert!(empty.events.is_empty());
+        assert_eq!(empty.last_seq, 0);
+
+        for i in 0..10 {
+            log.append(TYPE_FILL, None, serde_json::json!({ "n": i }), i)
+                .unwrap();
+        }
+
+        let suffix = log.replay_from(7, MAX_REPLAY_LIMIT).unwrap();
+        assert_eq!(
+            suffix.iter().map(|e| e.seq).collect::<Vec<_>>(),
+            vec![7, 8, 9, 10]
+        );
+        assert_eq!(suffix[0].payload["n"], 6);
+
+        let page = log
+            .handle_replay(&ReplayRequest {
+                from_seq: 3,
+                limit: Some(2),
+            })
+            .unwrap();
+        assert_eq!(
+            page.events.iter().map(|e| e.seq).collect::<Vec<_>>(),
+            vec![3, 4]
+        );
+        assert_eq!(page.last_seq, 10);
+
+        assert!(log.replay_from(11, MAX_REPLAY_LIMIT).unwrap().is_empty());
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+}
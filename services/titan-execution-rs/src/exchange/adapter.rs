@@ -1,14 +1,21 @@
 pub use crate::model::{OrderType, Position, Side};
 use async_trait::async_trait;
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
 use thiserror::Error;
 
+use crate::config::{ExchangeConfig, HttpPoolConfig};
+
 #[derive(Error, Debug)]
 pub enum ExchangeError {
     #[error("Network error: {0}")]
     Network(String),
     #[error("API error: {0}")]
     Api(String),
+    /// Venue refused the request with its own error code (Bybit retCode, Binance code)
+    #[error("API error {code}: {message}")]
+    VenueRejected { code: String, message: String },
     #[error("Signing error: {0}")]
     Signing(String),
     #[error("Configuration error: {0}")]
@@ -19,6 +26,26 @@ pub enum ExchangeError {
     NotImplemented(String),
     #[error("Parse error: {0}")]
     Parse(String),
+    /// DEX swap reverted because the output fell below (or the input rose above) its limit
+    #[error("Slippage exceeded: {0}")]
+    SlippageExceeded(String),
+    /// DEX pool or vault could not cover the trade
+    #[error("Insufficient liquidity: {0}")]
+    InsufficientLiquidity(String),
+    /// DEX transaction was mined after its deadline
+    #[error("Deadline passed: {0}")]
+    DeadlinePassed(String),
+}
+
+impl ExchangeError {
+    /// Whether re-quoting and resubmitting (fresh quote and deadline) could succeed;
+    /// the router does so once. Liquidity reverts need a smaller size or another venue.
+    pub fn is_requote_retryable(&self) -> bool {
+        matches!(
+            self,
+            ExchangeError::SlippageExceeded(_) | ExchangeError::DeadlinePassed(_)
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -31,6 +58,137 @@ pub struct OrderRequest {
     pub stop_price: Option<Decimal>,
     pub client_order_id: String,
     pub reduce_only: bool,
+    /// Pipeline correlation id of the originating intent, for exchange-side log matching
+    pub correlation_id: Option<String>,
+    /// Which side of a swap `quantity` fixes. Only DEX adapters honour ExactOut.
+    pub swap_mode: SwapMode,
+    /// Take-profit/stop-loss attached to the entry. Only sent to venues with
+    /// native bracket support; elsewhere exits are left to the TP ladder.
+    pub bracket: Option<Bracket>,
+}
+
+/// Exit levels attached to an entry order and managed by the venue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Bracket {
+    pub take_profit: Option<Decimal>,
+    pub stop_loss: Option<Decimal>,
+}
+
+impl Bracket {
+    /// Bracket for an entry with these exit levels. None when there is nothing
+    /// to attach, or more than one take-profit (a ladder, which venues can't
+    /// carry on the entry).
+    pub fn from_levels(stop_loss: Decimal, take_profits: &[Decimal]) -> Option<Self> {
+        if take_profits.len() > 1 {
+            return None;
+        }
+        let bracket = Self {
+            take_profit: take_profits.first().copied().filter(|p| *p > Decimal::ZERO),
+            stop_loss: Some(stop_loss).filter(|p| *p > Decimal::ZERO),
+        };
+        (bracket != Self::default()).then_some(bracket)
+    }
+}
+
+/// Swap quoting mode: fix the amount paid in, or the amount received out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SwapMode {
+    /// `quantity` is the input amount; the output is solved for
+    #[default]
+    ExactIn,
+    /// `quantity` is the desired output amount; the input is solved for
+    ExactOut,
+}
+
+impl SwapMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SwapMode::ExactIn => "ExactIn",
+            SwapMode::ExactOut => "ExactOut",
+        }
+    }
+}
+
+/// Venue-independent order status. Adapters keep the venue's own wording in
+/// `OrderResponse::raw_status`; everything downstream branches on this.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrderStatus {
+    /// Resting on the book
+    New,
+    /// Submitted but not yet acknowledged or settled (DEX tx in flight, keeper pending)
+    #[default]
+    Pending,
+    PartiallyFilled,
+    Filled,
+    #[serde(alias = "CANCELED")]
+    Cancelled,
+    Rejected,
+    Unknown,
+}
+
+impl OrderStatus {
+    /// Map a venue status string onto the canonical status. Case, `_`, `-` and
+    /// spaces are ignored so "PartiallyFilled", "PARTIALLY_FILLED" and
+    /// "partially-filled" all agree.
+    pub fn normalize(raw: &str) -> Self {
+        let key: String = raw
+            .chars()
+            .take_while(|c| *c != ':')
+            .filter(|c| !matches!(c, '_' | '-' | ' '))
+            .collect::<String>()
+            .to_uppercase();
+        match key.as_str() {
+            "NEW" | "OPEN" | "LIVE" | "ACCEPTED" | "RESTING" | "UNTRIGGERED" | "ACTIVE"
+            | "PENDINGCANCEL" => OrderStatus::New,
+            "PENDING" | "PENDINGNEW" | "PENDINGKEEPER" | "SUBMITTED" | "CREATED"
+            | "BESTEFFORTOPENED" => OrderStatus::Pending,
+            "PARTIALLYFILLED" | "PARTIALFILL" | "PARTIAL" => OrderStatus::PartiallyFilled,
+            // Gate.io / Kraken report a fully executed order as "closed"
+            "FILLED" | "CONFIRMED" | "CLOSED" | "EXECUTED" => OrderStatus::Filled,
+            "CANCELED"
+            | "CANCELLED"
+            | "EXPIRED"
+            | "EXPIREDINMATCH"
+            | "BESTEFFORTCANCELED"
+            | "PARTIALLYCANCELED"
+            | "PARTIALLYFILLEDCANCELED"
+            | "DEACTIVATED" => OrderStatus::Cancelled,
+            "REJECTED" | "FAILED" | "ERROR" => OrderStatus::Rejected,
+            _ => OrderStatus::Unknown,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderStatus::New => "NEW",
+            OrderStatus::Pending => "PENDING",
+            OrderStatus::PartiallyFilled => "PARTIALLY_FILLED",
+            OrderStatus::Filled => "FILLED",
+            OrderStatus::Cancelled => "CANCELLED",
+            OrderStatus::Rejected => "REJECTED",
+            OrderStatus::Unknown => "UNKNOWN",
+        }
+    }
+
+    /// The venue will not fill any more of this order
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Rejected
+        )
+    }
+
+    /// Ended without (further) execution
+    pub fn is_dead(&self) -> bool {
+        matches!(self, OrderStatus::Cancelled | OrderStatus::Rejected)
+    }
+}
+
+impl std::fmt::Display for OrderStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -38,7 +196,9 @@ pub struct OrderResponse {
     pub order_id: String,
     pub client_order_id: String,
     pub symbol: String,
-    pub status: String,
+    pub status: OrderStatus,
+    /// Status exactly as the venue reported it
+    pub raw_status: String,
     pub avg_price: Option<Decimal>,
     pub executed_qty: Decimal,
     pub t_exchange: Option<i64>,
@@ -47,6 +207,62 @@ pub struct OrderResponse {
     pub fee_asset: Option<String>,
 }
 
+/// One funding settlement the venue booked on our position in `symbol`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FundingPayment {
+    /// Venue symbol, e.g. BTCUSDT
+    pub symbol: String,
+    /// Paid by us; negative when funding was received
+    pub amount: Decimal,
+    pub rate: Option<Decimal>,
+    pub asset: String,
+    /// Settlement time (ms)
+    pub funding_time: i64,
+}
+
+/// Client builder for a venue's REST API, routed through the venue's
+/// `proxy_url` (http, https or socks5) when one is configured and pooled per
+/// its `http_pool`. Adapters build one client and reuse it for every request.
+pub fn http_client_builder(
+    config: Option<&ExchangeConfig>,
+) -> Result<reqwest::ClientBuilder, ExchangeError> {
+    let pool = config.map(|c| c.http_pool.clone()).unwrap_or_default();
+    let builder = pooled_client_builder(&pool);
+    let Some(url) = config
+        .and_then(|c| c.proxy_url.as_deref())
+        .filter(|u| !u.trim().is_empty())
+    else {
+        return Ok(builder);
+    };
+    // The URL may carry proxy credentials, so it is kept out of the error
+    let proxy = reqwest::Proxy::all(url).map_err(|e| {
+        ExchangeError::Configuration(format!("Invalid proxy_url: {}", e.without_url()))
+    })?;
+    Ok(builder.proxy(proxy))
+}
+
+/// Client builder with `pool`'s connection reuse and keep-alive settings.
+pub fn pooled_client_builder(pool: &HttpPoolConfig) -> reqwest::ClientBuilder {
+    let mut builder = reqwest::Client::builder()
+        .pool_idle_timeout(Duration::from_millis(pool.idle_timeout_ms))
+        .tcp_keepalive(pool.tcp_keepalive_ms.map(Duration::from_millis));
+    if let Some(max_idle) = pool.max_idle_per_host {
+        builder = builder.pool_max_idle_per_host(max_idle);
+    }
+    if pool.http2_prior_knowledge {
+        builder = builder.http2_prior_knowledge();
+    }
+    if pool.http2_adaptive_window {
+        builder = builder.http2_adaptive_window(true);
+    }
+    if let Some(interval_ms) = pool.http2_keep_alive_interval_ms {
+        builder = builder
+            .http2_keep_alive_interval(Duration::from_millis(interval_ms))
+            .http2_keep_alive_while_idle(true);
+    }
+    builder
+}
+
 #[async_trait]
 pub trait ExchangeAdapter: Send + Sync {
     /// Initialize the connection (e.g., perform handshake or get listen key)
@@ -62,12 +278,264 @@ pub trait ExchangeAdapter: Send + Sync {
         order_id: &str,
     ) -> Result<OrderResponse, ExchangeError>;
 
+    /// Amend the price of a resting order in place
+    async fn amend_order(
+        &self,
+        _symbol: &str,
+        _order_id: &str,
+        _price: Decimal,
+    ) -> Result<OrderResponse, ExchangeError> {
+        Err(ExchangeError::NotImplemented(format!(
+            "amend_order not supported by {}",
+            self.name()
+        )))
+    }
+
+    /// Look up an order by the client order id it was placed with. Ok(None)
+    /// means the venue has no such order.
+    async fn get_order(
+        &self,
+        _symbol: &str,
+        _client_order_id: &str,
+    ) -> Result<Option<OrderResponse>, ExchangeError> {
+        Err(ExchangeError::NotImplemented(format!(
+            "get_order not supported by {}",
+            self.name()
+        )))
+    }
+
+    /// All orders currently resting on the venue, across symbols
+    async fn get_open_orders(&self) -> Result<Vec<OrderResponse>, ExchangeError> {
+        Err(ExchangeError::NotImplemented(format!(
+            "get_open_orders not supported by {}",
+            self.name()
+        )))
+    }
+
+    /// Arm (or re-arm) the venue's cancel-on-disconnect timer: if it is not
+    /// refreshed within `window_ms`, the venue cancels all our open orders.
+    /// A zero window disarms it.
+    async fn set_dead_mans_switch(&self, _window_ms: u64) -> Result<(), ExchangeError> {
+        Err(ExchangeError::NotImplemented(format!(
+            "cancel-on-disconnect not supported by {}",
+            self.name()
+        )))
+    }
+
+    /// Set the leverage the venue applies to new positions in `symbol`
+    async fn set_leverage(&self, _symbol: &str, _leverage: u32) -> Result<(), ExchangeError> {
+        Err(ExchangeError::NotImplemented(format!(
+            "set_leverage not supported by {}",
+            self.name()
+        )))
+    }
+
+    /// Funding settled on our positions since `since` (ms), oldest first
+    async fn get_funding_payments(
+        &self,
+        _since: i64,
+    ) -> Result<Vec<FundingPayment>, ExchangeError> {
+        Err(ExchangeError::NotImplemented(format!(
+            "funding history not supported by {}",
+            self.name()
+        )))
+    }
+
     /// Get current wallet balance for a specific asset
     async fn get_balance(&self, asset: &str) -> Result<Decimal, ExchangeError>;
 
+    /// Whether `SwapMode::ExactOut` orders can be placed here
+    fn supports_exact_out(&self) -> bool {
+        false
+    }
+
+    /// Whether an order's `bracket` is placed on the venue with the entry
+    fn supports_native_brackets(&self) -> bool {
+        false
+    }
+
+    /// Assets this venue settles in, queried when aggregating balances
+    fn settlement_assets(&self) -> Vec<String> {
+        vec!["USDT".to_string()]
+    }
+
     /// Get exchange name (e.g., "Binance Futures")
     fn name(&self) -> &str;
 
     /// Get current open positions
     async fn get_positions(&self) -> Result<Vec<Position>, ExchangeError>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_venue_status_strings_normalize() {
+        use OrderStatus::*;
+        let cases: &[(&str, &[(&str, OrderStatus)])] = &[
+            (
+                "binance",
+                &[
+                    ("NEW", New),
+                    ("PARTIALLY_FILLED", PartiallyFilled),
+                    ("FILLED", Filled),
+                    ("CANCELED", Cancelled),
+                    ("PENDING_CANCEL", New),
+                    ("REJECTED", Rejected),
+                    ("EXPIRED", Cancelled),
+                    ("EXPIRED_IN_MATCH", Cancelled),
+                ],
+            ),
+            (
+                "bybit",
+                &[
+                    ("New", New),
+                    ("PartiallyFilled", PartiallyFilled),
+                    ("Filled", Filled),
+                    ("Cancelled", Cancelled),
+                    ("PartiallyFilledCanceled", Cancelled),
+                    ("Rejected", Rejected),
+                    ("Untriggered", New),
+                    ("Deactivated", Cancelled),
+                ],
+            ),
+            (
+                "okx",
+                &[
+                    ("live", New),
+                    ("partially_filled", PartiallyFilled),
+                    ("filled", Filled),
+                    ("canceled", Cancelled),
+                ],
+            ),
+            (
+                "dydx",
+                &[
+                    ("OPEN", New),
+                    ("BEST_EFFORT_OPENED", Pending),
+                    ("FILLED", Filled),
+                    ("CANCELED", Cancelled),
+                    ("BEST_EFFORT_CANCELED", Cancelled),
+                    ("UNTRIGGERED", New),
+                ],
+            ),
+            (
+                "gateio",
+                &[("open", New), ("closed", Filled), ("cancelled", Cancelled)],
+            ),
+            (
+                "kraken",
+                &[
+                    ("pending", Pending),
+                    ("open", New),
+                    ("closed", Filled),
+                    ("canceled", Cancelled),
+                    ("expired", Cancelled),
+                ],
+            ),
+            (
+                "coinbase",
+                &[
+                    ("PENDING", Pending),
+                    ("OPEN", New),
+                    ("FILLED", Filled),
+                    ("CANCELLED", Cancelled),
+                    ("FAILED", Rejected),
+                    ("UNKNOWN", Unknown),
+                ],
+            ),
+            (
+                "cryptocom",
+                &[("ACTIVE", New), ("FILLED", Filled), ("REJECTED", Rejected)],
+            ),
+            ("mexc", &[("NEW", New), ("PARTIALLY_CANCELED", Cancelled)]),
+            ("hyperliquid", &[("ERROR: insufficient margin", Rejected)]),
+            ("gmx", &[("PENDING_KEEPER", Pending)]),
+            ("jupiter", &[("CONFIRMED", Filled)]),
+            ("uniswap", &[("PENDING", Pending)]),
+        ];
+
+        for (venue, statuses) in cases {
+            for (raw, expected) in *statuses {
+                assert_eq!(
+                    OrderStatus::normalize(raw),
+                    *expected,
+                    "{} status {:?}",
+                    venue,
+                    raw
+                );
+            }
+        }
+
+        assert_eq!(OrderStatus::normalize("something new"), Unknown);
+    }
+
+    #[test]
+    fn test_order_status_round_trips_as_canonical_string() {
+        for status in [
+            OrderStatus::New,
+            OrderStatus::Pending,
+            OrderStatus::PartiallyFilled,
+            OrderStatus::Filled,
+            OrderStatus::Cancelled,
+            OrderStatus::Rejected,
+            OrderStatus::Unknown,
+        ] {
+            let json = serde_json::to_value(status).unwrap();
+            assert_eq!(json, status.as_str());
+            assert_eq!(OrderStatus::normalize(status.as_str()), status);
+        }
+        let legacy: OrderStatus = serde_json::from_str("\"CANCELED\"").unwrap();
+        assert_eq!(legacy, OrderStatus::Cancelled);
+    }
+
+    #[test]
+    fn test_bracket_from_levels() {
+        use rust_decimal_macros::dec;
+
+        assert_eq!(
+            Bracket::from_levels(dec!(49000), &[dec!(52000)]),
+            Some(Bracket {
+                take_profit: Some(dec!(52000)),
+                stop_loss: Some(dec!(49000)),
+            })
+        );
+        assert_eq!(
+            Bracket::from_levels(Decimal::ZERO, &[dec!(52000)]),
+            Some(Bracket {
+                take_profit: Some(dec!(52000)),
+                stop_loss: None,
+            })
+        );
+        // Nothing to attach
+        assert_eq!(Bracket::from_levels(Decimal::ZERO, &[]), None);
+        // A multi-level ladder stays with the TP ladder executor
+        assert_eq!(
+            Bracket::from_levels(dec!(49000), &[dec!(51000), dec!(52000)]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_http_pool_settings_parse_and_build() {
+        let config: ExchangeConfig = serde_json::from_value(serde_json::json!({
+            "enabled": true,
+            "httpPool": {
+                "max_idle_per_host": 16,
+                "tcp_keepalive_ms": 30000,
+                "http2_adaptive_window": true,
+                "http2_keep_alive_interval_ms": 15000,
+            },
+        }))
+        .unwrap();
+        assert_eq!(config.http_pool.max_idle_per_host, Some(16));
+        assert_eq!(config.http_pool.tcp_keepalive_ms, Some(30_000));
+        // Unset fields keep the defaults
+        assert_eq!(config.http_pool.idle_timeout_ms, 90_000);
+        assert!(!config.http_pool.http2_prior_knowledge);
+
+        assert!(http_client_builder(Some(&config)).unwrap().build().is_ok());
+        assert!(http_client_builder(None).unwrap().build().is_ok());
+    }
+}
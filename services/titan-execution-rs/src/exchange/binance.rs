@@ -1,4 +1,8 @@
-use crate::exchange::adapter::{ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse};
+use crate::exchange::adapter::{
+    http_client_builder, ExchangeAdapter, ExchangeError, FundingPayment, OrderRequest,
+    OrderResponse, OrderStatus,
+};
+use crate::exchange::telemetry;
 use crate::model::{Position, Side};
 use async_trait::async_trait;
 use chrono::Utc;
@@ -7,6 +11,7 @@ use hmac::{Hmac, Mac};
 use reqwest::Client;
 use rust_decimal::Decimal;
 use sha2::Sha256;
+use std::collections::HashSet;
 use std::env;
 
 use crate::rate_limiter::TokenBucket;
@@ -15,12 +20,71 @@ pub struct BinanceAdapter {
     api_key: String,
     secret_key: String,
     base_url: String,
+    market_type: MarketType,
+    endpoints: Endpoints,
     client: Client,
     http_limiter: TokenBucket,
     _ws_limiter: TokenBucket,
 }
 
-use crate::config::ExchangeConfig;
+use crate::config::{ExchangeConfig, MarketType};
+
+/// REST paths of one Binance product family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Endpoints {
+    pub ping: &'static str,
+    pub order: &'static str,
+    pub open_orders: &'static str,
+    pub balance: &'static str,
+    /// Spot has no positions to query
+    pub positions: Option<&'static str>,
+    /// Spot has no leverage to set
+    pub leverage: Option<&'static str>,
+    /// Income history (funding fees); spot pays no funding
+    pub income: Option<&'static str>,
+}
+
+pub(crate) fn endpoints(market_type: MarketType) -> Endpoints {
+    match market_type {
+        MarketType::Spot => Endpoints {
+            ping: "/api/v3/ping",
+            order: "/api/v3/order",
+            open_orders: "/api/v3/openOrders",
+            balance: "/api/v3/account",
+            positions: None,
+            leverage: None,
+            income: None,
+        },
+        MarketType::Usdm => Endpoints {
+            ping: "/fapi/v1/ping",
+            order: "/fapi/v1/order",
+            open_orders: "/fapi/v1/openOrders",
+            balance: "/fapi/v2/balance",
+            positions: Some("/fapi/v2/positionRisk"),
+            leverage: Some("/fapi/v1/leverage"),
+            income: Some("/fapi/v1/income"),
+        },
+        MarketType::Coinm => Endpoints {
+            ping: "/dapi/v1/ping",
+            order: "/dapi/v1/order",
+            open_orders: "/dapi/v1/openOrders",
+            balance: "/dapi/v1/balance",
+            positions: Some("/dapi/v1/positionRisk"),
+            leverage: Some("/dapi/v1/leverage"),
+            income: Some("/dapi/v1/income"),
+        },
+    }
+}
+
+fn default_base_url(market_type: MarketType, testnet: bool) -> &'static str {
+    match (market_type, testnet) {
+        (MarketType::Spot, true) => "https://testnet.binance.vision",
+        (MarketType::Spot, false) => "https://api.binance.com",
+        (MarketType::Usdm | MarketType::Coinm, true) => "https://testnet.binancefuture.com",
+        (MarketType::Usdm, false) => "https://fapi.binance.com",
+        (MarketType::Coinm, false) => "https://dapi.binance.com",
+    }
+}
 
 impl BinanceAdapter {
     pub fn new(config: Option<&ExchangeConfig>) -> Result<Self, ExchangeError> {
@@ -42,12 +106,10 @@ impl BinanceAdapter {
                 )
             })?;
 
+        let market_type = config.map(|c| c.market_type).unwrap_or_default();
         let base_url = env::var("BINANCE_BASE_URL").unwrap_or_else(|_| {
-            if config.map(|c| c.testnet).unwrap_or(true) {
-                "https://testnet.binancefuture.com".to_string()
-            } else {
-                "https://fapi.binance.com".to_string()
-            }
+            let testnet = config.map(|c| c.testnet).unwrap_or(true);
+            default_base_url(market_type, testnet).to_string()
         });
 
         // HTTP Limit: ~2400 req/min => 40 req/sec. Burst 50.
@@ -62,12 +124,66 @@ impl BinanceAdapter {
             api_key,
             secret_key,
             base_url,
-            client: Client::new(),
+            market_type,
+            endpoints: endpoints(market_type),
+            client: http_client_builder(config)?
+                .build()
+                .map_err(|e| ExchangeError::Network(e.to_string()))?,
             http_limiter,
             _ws_limiter: ws_limiter,
         })
     }
 
+    /// One page of funding-fee income rows starting at `start_time`.
+    async fn fetch_income_page(
+        &self,
+        endpoint: &str,
+        start_time: i64,
+    ) -> Result<Vec<serde_json::Value>, ExchangeError> {
+        self.http_limiter.acquire(1).await;
+
+        let params = format!(
+            "incomeType=FUNDING_FEE&startTime={}&limit={}&timestamp={}&recvWindow=5000",
+            start_time,
+            INCOME_PAGE_LIMIT,
+            Utc::now().timestamp_millis()
+        );
+        let signature = self.sign(&params);
+        let url = format!(
+            "{}{}?{}&signature={}",
+            self.base_url, endpoint, params, signature
+        );
+
+        let resp = telemetry::send(
+            "binance",
+            self.client.get(&url).header("X-MBX-APIKEY", &self.api_key),
+        )
+        .await
+        .map_err(|e| ExchangeError::Network(e.to_string()))?;
+
+        if !resp.status().is_success() {
+            let text = resp.text().await.unwrap_or_default();
+            return Err(ExchangeError::Api(format!(
+                "Binance income failed: {}",
+                text
+            )));
+        }
+
+        let text = resp
+            .text()
+            .await
+            .map_err(|e| ExchangeError::Network(e.to_string()))?;
+        let json: serde_json::Value = serde_json::from_str(&text)
+            .map_err(|e| ExchangeError::Api(format!("Parse error: {}", e)))?;
+        match json {
+            serde_json::Value::Array(page) => Ok(page),
+            other => Err(ExchangeError::Api(format!(
+                "Binance income returned a non-array: {}",
+                other
+            ))),
+        }
+    }
+
     fn sign(&self, query: &str) -> String {
         let mut mac = Hmac::<Sha256>::new_from_slice(self.secret_key.as_bytes())
             .expect("HMAC can take key of any size");
@@ -90,14 +206,206 @@ impl BinanceAdapter {
         }
         value.to_string().trim_matches('"').to_string()
     }
+
+    /// Order response from an order endpoint body (place and query share the shape).
+    fn order_response(
+        json: &serde_json::Value,
+        client_order_id: String,
+        symbol: String,
+    ) -> OrderResponse {
+        let raw_status = json["status"].as_str().unwrap_or("UNKNOWN").to_string();
+        OrderResponse {
+            order_id: Self::normalize_order_id(&json["orderId"]),
+            client_order_id,
+            symbol,
+            status: OrderStatus::normalize(&raw_status),
+            raw_status,
+            avg_price: json["avgPrice"]
+                .as_str()
+                .and_then(|s| rust_decimal::Decimal::from_str_exact(s).ok()),
+            executed_qty: json["executedQty"]
+                .as_str()
+                .and_then(|s| rust_decimal::Decimal::from_str_exact(s).ok())
+                .unwrap_or_default(),
+            t_ack: Utc::now().timestamp_millis(),
+            t_exchange: None,
+            fee: None,
+            fee_asset: None,
+        }
+    }
+}
+
+/// Error for a refused order. Binance bodies carry `{"code": -2019, "msg": ...}`;
+/// anything else keeps the raw status and body.
+pub(crate) fn parse_order_error(status: reqwest::StatusCode, body: &str) -> ExchangeError {
+    let json: Option<serde_json::Value> = serde_json::from_str(body).ok();
+    match json
+        .as_ref()
+        .and_then(|j| Some((j["code"].as_i64()?, j["msg"].as_str()?)))
+    {
+        Some((code, msg)) => ExchangeError::VenueRejected {
+            code: code.to_string(),
+            message: msg.to_string(),
+        },
+        None => ExchangeError::Api(format!("Order failed {}: {}", status, body)),
+    }
+}
+
+/// Funding fees of a futures `income?incomeType=FUNDING_FEE` response, oldest
+/// first. Binance reports income, so a fee we paid is negative there.
+/// Row cap of one `/income` request; a full page means more may follow.
+pub(crate) const INCOME_PAGE_LIMIT: usize = 1000;
+
+/// `startTime` of the income page after `page`, `None` once a short page
+/// shows the history is exhausted. The next page restarts at the newest
+/// row's millisecond so rows sharing it aren't skipped.
+pub(crate) fn next_income_start(
+    page: &[serde_json::Value],
+    start_time: i64,
+    limit: usize,
+) -> Option<i64> {
+    if page.len() < limit {
+        return None;
+    }
+    let newest = page.iter().filter_map(|item| item["time"].as_i64()).max()?;
+    // A full page inside one millisecond would otherwise be refetched forever.
+    Some(if newest > start_time {
+        newest
+    } else {
+        start_time + 1
+    })
+}
+
+pub(crate) fn parse_funding_income(json: &serde_json::Value) -> Vec<FundingPayment> {
+    let mut payments: Vec<FundingPayment> = json
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|item| item["incomeType"].as_str() == Some("FUNDING_FEE"))
+        .filter_map(|item| {
+            let income = item["income"]
+                .as_str()
+                .and_then(|s| Decimal::from_str_exact(s).ok())?;
+            Some(FundingPayment {
+                symbol: item["symbol"].as_str()?.to_string(),
+                amount: -income,
+                rate: None,
+                asset: item["asset"].as_str().unwrap_or("USDT").to_string(),
+                funding_time: item["time"].as_i64()?,
+            })
+        })
+        .collect();
+    payments.sort_by_key(|p| p.funding_time);
+    payments
+}
+
+/// Normalize a futures `positionRisk` response into positions, skipping flat entries.
+/// Leverage has no field on `Position` and is carried in `metadata.leverage`.
+pub(crate) fn parse_position_risk(json: &serde_json::Value) -> Vec<Position> {
+    let decimal = |item: &serde_json::Value, key: &str| {
+        item[key]
+            .as_str()
+            .and_then(|s| Decimal::from_str_exact(s).ok())
+    };
+
+    let Some(list) = json.as_array() else {
+        return Vec::new();
+    };
+
+    let mut positions = Vec::new();
+    for item in list {
+        let amt = decimal(item, "positionAmt").unwrap_or(Decimal::ZERO);
+        if amt.is_zero() {
+            continue; // Skip closed positions
+        }
+
+        // Binance "positionSide" indicates "LONG", "SHORT" (Hedge Mode) or "BOTH" (One-Way)
+        let pos_side_str = item["positionSide"].as_str().unwrap_or("BOTH");
+        let side = match pos_side_str {
+            "SHORT" => Side::Short,
+            "LONG" => Side::Long,
+            // One-Way Mode: sign of positionAmt
+            _ if amt.is_sign_negative() => Side::Short,
+            _ => Side::Long,
+        };
+
+        let metadata = decimal(item, "leverage").map(|leverage| {
+            serde_json::json!({
+                "leverage": leverage,
+                "margin_type": item["marginType"].as_str(),
+                "liquidation_price": decimal(item, "liquidationPrice"),
+            })
+        });
+
+        positions.push(Position {
+            symbol: item["symbol"].as_str().unwrap_or("").to_string(),
+            side,
+            size: amt.abs(),
+            entry_price: decimal(item, "entryPrice").unwrap_or(Decimal::ZERO),
+            stop_loss: Decimal::ZERO, // Exchange doesn't give SL/TP easily in this endpoint usually
+            take_profits: vec![],
+            signal_id: "EXCHANGE_FETCHED".to_string(),
+            opened_at: Utc::now(), // Unknown
+            regime_state: None,
+            phase: None,
+            metadata,
+            exchange: Some("BINANCE".to_string()),
+            position_mode: Some(pos_side_str.to_string()),
+            realized_pnl: Decimal::ZERO,
+            unrealized_pnl: decimal(item, "unRealizedProfit").unwrap_or(Decimal::ZERO),
+            fees_paid: Decimal::ZERO,
+            funding_paid: Decimal::ZERO,
+            last_mark_price: decimal(item, "markPrice").filter(|p| !p.is_zero()),
+            last_update_ts: item["updateTime"]
+                .as_i64()
+                .filter(|ts| *ts > 0)
+                .unwrap_or_else(|| Utc::now().timestamp_millis()),
+            entry_lots: Vec::new(),
+        });
+    }
+    positions
+}
+
+/// Available balance of `asset`: `free` from a spot `/api/v3/account` body,
+/// `availableBalance` (else `balance`) from a futures balance list.
+pub(crate) fn parse_balance(
+    market_type: MarketType,
+    json: &serde_json::Value,
+    asset: &str,
+) -> Result<Decimal, ExchangeError> {
+    let (list, fields) = match market_type {
+        MarketType::Spot => (&json["balances"], &["free"][..]),
+        MarketType::Usdm | MarketType::Coinm => (json, &["availableBalance", "balance"][..]),
+    };
+    let balances = list
+        .as_array()
+        .ok_or_else(|| ExchangeError::Api("Unexpected balance response".into()))?;
+
+    let value = balances
+        .iter()
+        .find(|entry| entry.get("asset").and_then(|v| v.as_str()) == Some(asset))
+        .and_then(|entry| {
+            fields.iter().find_map(|field| {
+                entry
+                    .get(*field)
+                    .and_then(|v| v.as_str())
+                    .and_then(|v| Decimal::from_str_exact(v).ok())
+            })
+        });
+    Ok(value.unwrap_or(Decimal::ZERO))
 }
 
-pub(crate) fn build_order_params(order: &OrderRequest, timestamp: i64) -> String {
+/// Signed order query. Spot has no reduce-only flag, so it is left off there.
+pub(crate) fn build_order_params(
+    order: &OrderRequest,
+    timestamp: i64,
+    market_type: MarketType,
+) -> String {
     let side_str = match order.side {
         Side::Buy | Side::Long => "BUY",
         Side::Sell | Side::Short => "SELL",
     };
-    let reduce_only = if order.reduce_only {
+    let reduce_only = if order.reduce_only && market_type != MarketType::Spot {
         "&reduceOnly=true"
     } else {
         ""
@@ -125,15 +433,22 @@ pub(crate) fn build_order_params(order: &OrderRequest, timestamp: i64) -> String
     }
 }
 
+/// Signed query for the futures leverage endpoint
+pub(crate) fn build_leverage_params(symbol: &str, leverage: u32, timestamp: i64) -> String {
+    format!(
+        "symbol={}&leverage={}&timestamp={}",
+        symbol.replace("/", ""),
+        leverage,
+        timestamp
+    )
+}
+
 #[async_trait]
 impl ExchangeAdapter for BinanceAdapter {
     async fn init(&self) -> Result<(), ExchangeError> {
         // Minimal health check or ping
-        let url = format!("{}/fapi/v1/ping", self.base_url);
-        let resp = self
-            .client
-            .get(&url)
-            .send()
+        let url = format!("{}{}", self.base_url, self.endpoints.ping);
+        let resp = telemetry::send("binance", self.client.get(&url))
             .await
             .map_err(|e| ExchangeError::Network(e.to_string()))?;
 
@@ -150,25 +465,76 @@ impl ExchangeAdapter for BinanceAdapter {
         // Enforce Rate Limit (HTTP)
         self.http_limiter.acquire(1).await;
 
-        let endpoint = "/fapi/v1/order";
+        let endpoint = self.endpoints.order;
         let timestamp = Utc::now().timestamp_millis();
-        let params = build_order_params(&order, timestamp);
+        let params = build_order_params(&order, timestamp, self.market_type);
 
         let signature = self.sign(&params);
         let full_query = format!("{}&signature={}", params, signature);
         let url = format!("{}{}", self.base_url, endpoint);
 
-        let resp = self
-            .client
-            .post(&url)
-            .header("X-MBX-APIKEY", &self.api_key)
-            .body(full_query) // Usually GET query params for Binance signed requests? No, POST body or query.
-            // For Binance Futures, signed endpoints send params in query string or body.
-            // Query string is easier for debugging.
-            .send()
+        // Signed endpoints take params in the query string or body.
+        // Query string is easier for debugging.
+        let resp = telemetry::send(
+            "binance",
+            self.client
+                .post(&url)
+                .header("X-MBX-APIKEY", &self.api_key)
+                .body(full_query), // Usually GET query params for Binance signed requests? No, POST body or query.
+        )
+        .await
+        .map_err(|e| ExchangeError::Network(e.to_string()))?;
+
+        let status = resp.status();
+        let text = resp
+            .text()
             .await
             .map_err(|e| ExchangeError::Network(e.to_string()))?;
 
+        if !status.is_success() {
+            return Err(parse_order_error(status, &text));
+        }
+
+        // Parse response (simplified)
+        let json: serde_json::Value = serde_json::from_str(&text)
+            .map_err(|e| ExchangeError::Api(format!("Parse error: {}", e)))?;
+
+        Ok(Self::order_response(
+            &json,
+            order.client_order_id,
+            order.symbol,
+        ))
+    }
+
+    async fn get_order(
+        &self,
+        symbol: &str,
+        client_order_id: &str,
+    ) -> Result<Option<OrderResponse>, ExchangeError> {
+        self.http_limiter.acquire(1).await;
+
+        let endpoint = self.endpoints.order;
+        let timestamp = Utc::now().timestamp_millis();
+        let params = format!(
+            "symbol={}&origClientOrderId={}&timestamp={}",
+            symbol.replace("/", ""),
+            client_order_id,
+            timestamp
+        );
+
+        let signature = self.sign(&params);
+        let url = format!(
+            "{}{}?{}&signature={}",
+            self.base_url, endpoint, params, signature
+        );
+
+        let resp = telemetry::send(
+            "binance",
+            self.client.get(&url).header("X-MBX-APIKEY", &self.api_key),
+        )
+        .await
+        .map_err(|e| ExchangeError::Network(e.to_string()))?;
+
         let status = resp.status();
         let text = resp
             .text()
@@ -176,35 +542,76 @@ impl ExchangeAdapter for BinanceAdapter {
             .map_err(|e| ExchangeError::Network(e.to_string()))?;
 
         if !status.is_success() {
+            // -2013: Order does not exist
+            if text.contains("-2013") {
+                return Ok(None);
+            }
             return Err(ExchangeError::Api(format!(
-                "Order failed {}: {}",
+                "Order query failed {}: {}",
                 status, text
             )));
         }
 
-        // Parse response (simplified)
         let json: serde_json::Value = serde_json::from_str(&text)
             .map_err(|e| ExchangeError::Api(format!("Parse error: {}", e)))?;
 
-        let order_id = Self::normalize_order_id(&json["orderId"]);
+        Ok(Some(Self::order_response(
+            &json,
+            client_order_id.to_string(),
+            symbol.replace("/", ""),
+        )))
+    }
 
-        Ok(OrderResponse {
-            order_id,
-            client_order_id: order.client_order_id,
-            symbol: order.symbol,
-            status: json["status"].as_str().unwrap_or("UNKNOWN").to_string(),
-            avg_price: json["avgPrice"]
-                .as_str()
-                .and_then(|s| rust_decimal::Decimal::from_str_exact(s).ok()),
-            executed_qty: json["executedQty"]
-                .as_str()
-                .and_then(|s| rust_decimal::Decimal::from_str_exact(s).ok())
-                .unwrap_or_default(),
-            t_ack: Utc::now().timestamp_millis(),
-            t_exchange: None,
-            fee: None,
-            fee_asset: None,
-        })
+    async fn get_open_orders(&self) -> Result<Vec<OrderResponse>, ExchangeError> {
+        // Without a symbol the query weighs 40
+        self.http_limiter.acquire(40).await;
+
+        let timestamp = Utc::now().timestamp_millis();
+        let params = format!("timestamp={}", timestamp);
+        let signature = self.sign(&params);
+        let url = format!(
+            "{}{}?{}&signature={}",
+            self.base_url, self.endpoints.open_orders, params, signature
+        );
+
+        let resp = telemetry::send(
+            "binance",
+            self.client.get(&url).header("X-MBX-APIKEY", &self.api_key),
+        )
+        .await
+        .map_err(|e| ExchangeError::Network(e.to_string()))?;
+
+        let status = resp.status();
+        let text = resp
+            .text()
+            .await
+            .map_err(|e| ExchangeError::Network(e.to_string()))?;
+
+        if !status.is_success() {
+            return Err(ExchangeError::Api(format!(
+                "Open orders query failed {}: {}",
+                status, text
+            )));
+        }
+
+        let json: serde_json::Value = serde_json::from_str(&text)
+            .map_err(|e| ExchangeError::Api(format!("Parse error: {}", e)))?;
+
+        Ok(json
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|order| {
+                Self::order_response(
+                    order,
+                    order["clientOrderId"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                    order["symbol"].as_str().unwrap_or_default().to_string(),
+                )
+            })
+            .collect())
     }
 
     async fn cancel_order(
@@ -215,7 +622,7 @@ impl ExchangeAdapter for BinanceAdapter {
         // Enforce Rate Limit (HTTP)
         self.http_limiter.acquire(1).await;
 
-        let endpoint = "/fapi/v1/order";
+        let endpoint = self.endpoints.order;
         let timestamp = Utc::now().timestamp_millis();
 
         let params = format!(
@@ -229,13 +636,14 @@ impl ExchangeAdapter for BinanceAdapter {
         let full_query = format!("{}&signature={}", params, signature);
         let url = format!("{}{}?{}", self.base_url, endpoint, full_query);
 
-        let resp = self
-            .client
-            .delete(&url)
-            .header("X-MBX-APIKEY", &self.api_key)
-            .send()
-            .await
-            .map_err(|e| ExchangeError::Network(e.to_string()))?;
+        let resp = telemetry::send(
+            "binance",
+            self.client
+                .delete(&url)
+                .header("X-MBX-APIKEY", &self.api_key),
+        )
+        .await
+        .map_err(|e| ExchangeError::Network(e.to_string()))?;
 
         if !resp.status().is_success() {
             let text = resp.text().await.unwrap_or_default();
@@ -247,7 +655,8 @@ impl ExchangeAdapter for BinanceAdapter {
             order_id: order_id.to_string(),
             client_order_id: "".to_string(),
             symbol: symbol.to_string(),
-            status: "CANCELED".to_string(),
+            status: OrderStatus::Cancelled,
+            raw_status: "CANCELED".to_string(),
             avg_price: None,
             executed_qty: Decimal::ZERO,
             t_ack: Utc::now().timestamp_millis(),
@@ -257,10 +666,43 @@ impl ExchangeAdapter for BinanceAdapter {
         })
     }
 
+    async fn set_leverage(&self, symbol: &str, leverage: u32) -> Result<(), ExchangeError> {
+        let Some(endpoint) = self.endpoints.leverage else {
+            return Err(ExchangeError::NotImplemented(
+                "Binance spot has no leverage".to_string(),
+            ));
+        };
+        self.http_limiter.acquire(1).await;
+
+        let params = build_leverage_params(symbol, leverage, Utc::now().timestamp_millis());
+        let signature = self.sign(&params);
+        let url = format!("{}{}", self.base_url, endpoint);
+
+        let resp = telemetry::send(
+            "binance",
+            self.client
+                .post(&url)
+                .header("X-MBX-APIKEY", &self.api_key)
+                .body(format!("{}&signature={}", params, signature)),
+        )
+        .await
+        .map_err(|e| ExchangeError::Network(e.to_string()))?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            return Err(ExchangeError::Api(format!(
+                "Set leverage failed {}: {}",
+                status, text
+            )));
+        }
+        Ok(())
+    }
+
     async fn get_balance(&self, asset: &str) -> Result<Decimal, ExchangeError> {
         self.http_limiter.acquire(1).await;
 
-        let endpoint = "/fapi/v2/balance";
+        let endpoint = self.endpoints.balance;
         let timestamp = Utc::now().timestamp_millis();
         let params = format!("timestamp={}&recvWindow=5000", timestamp);
         let signature = self.sign(&params);
@@ -269,13 +711,12 @@ impl ExchangeAdapter for BinanceAdapter {
             self.base_url, endpoint, params, signature
         );
 
-        let resp = self
-            .client
-            .get(&url)
-            .header("X-MBX-APIKEY", &self.api_key)
-            .send()
-            .await
-            .map_err(|e| ExchangeError::Network(e.to_string()))?;
+        let resp = telemetry::send(
+            "binance",
+            self.client.get(&url).header("X-MBX-APIKEY", &self.api_key),
+        )
+        .await
+        .map_err(|e| ExchangeError::Network(e.to_string()))?;
 
         let status = resp.status();
         let text = resp
@@ -293,37 +734,32 @@ impl ExchangeAdapter for BinanceAdapter {
         let json: serde_json::Value = serde_json::from_str(&text)
             .map_err(|e| ExchangeError::Api(format!("Parse error: {}", e)))?;
 
-        let balances = json
-            .as_array()
-            .ok_or_else(|| ExchangeError::Api("Unexpected balance response".into()))?;
-
-        for entry in balances {
-            if entry.get("asset").and_then(|v| v.as_str()) == Some(asset) {
-                if let Some(available) = entry.get("availableBalance").and_then(|v| v.as_str()) {
-                    if let Ok(value) = Decimal::from_str_exact(available) {
-                        return Ok(value);
-                    }
-                }
-                if let Some(balance) = entry.get("balance").and_then(|v| v.as_str()) {
-                    if let Ok(value) = Decimal::from_str_exact(balance) {
-                        return Ok(value);
-                    }
-                }
-            }
-        }
+        parse_balance(self.market_type, &json, asset)
+    }
 
-        Ok(Decimal::ZERO)
+    fn settlement_assets(&self) -> Vec<String> {
+        match self.market_type {
+            MarketType::Spot | MarketType::Usdm => vec!["USDT".to_string(), "USDC".to_string()],
+            // Coin-margined contracts settle in the base coin
+            MarketType::Coinm => vec!["BTC".to_string(), "ETH".to_string()],
+        }
     }
 
     fn name(&self) -> &str {
-        "Binance Futures"
+        match self.market_type {
+            MarketType::Spot => "Binance Spot",
+            MarketType::Usdm => "Binance Futures",
+            MarketType::Coinm => "Binance COIN-M",
+        }
     }
 
     async fn get_positions(&self) -> Result<Vec<Position>, ExchangeError> {
-        // /fapi/v2/positionRisk
+        // Spot holds balances, not positions
+        let Some(endpoint) = self.endpoints.positions else {
+            return Ok(Vec::new());
+        };
         self.http_limiter.acquire(1).await;
 
-        let endpoint = "/fapi/v2/positionRisk";
         let timestamp = Utc::now().timestamp_millis();
         let params = format!("timestamp={}&recvWindow=5000", timestamp);
         let signature = self.sign(&params);
@@ -333,13 +769,12 @@ impl ExchangeAdapter for BinanceAdapter {
             self.base_url, endpoint, params, signature
         );
 
-        let resp = self
-            .client
-            .get(&url)
-            .header("X-MBX-APIKEY", &self.api_key)
-            .send()
-            .await
-            .map_err(|e| ExchangeError::Network(e.to_string()))?;
+        let resp = telemetry::send(
+            "binance",
+            self.client.get(&url).header("X-MBX-APIKEY", &self.api_key),
+        )
+        .await
+        .map_err(|e| ExchangeError::Network(e.to_string()))?;
 
         if !resp.status().is_success() {
             let text = resp.text().await.unwrap_or_default();
@@ -356,70 +791,28 @@ impl ExchangeAdapter for BinanceAdapter {
         let json: serde_json::Value = serde_json::from_str(&text)
             .map_err(|e| ExchangeError::Api(format!("Parse error: {}", e)))?;
 
-        let mut positions = Vec::new();
-
-        if let Some(list) = json.as_array() {
-            for item in list {
-                let symbol = item["symbol"].as_str().unwrap_or("").to_string();
-                let amt_str = item["positionAmt"].as_str().unwrap_or("0");
-                let amt = rust_decimal::Decimal::from_str_exact(amt_str).unwrap_or(Decimal::ZERO);
-
-                if amt.is_zero() {
-                    continue; // Skip closed positions
-                }
-
-                let entry_str = item["entryPrice"].as_str().unwrap_or("0");
-                let entry_price =
-                    rust_decimal::Decimal::from_str_exact(entry_str).unwrap_or(Decimal::ZERO);
-
-                // Determine Side
-                // Logic: if amt > 0 -> Long, if amt < 0 -> Short
-                // Binance "positionSide" indicates "LONG", "SHORT" (Hedge Mode) or "BOTH" (One-Way)
-                let pos_side_str = item["positionSide"].as_str().unwrap_or("BOTH");
-
-                let side = if pos_side_str == "SHORT" {
-                    Side::Short
-                } else if pos_side_str == "LONG" {
-                    Side::Long
-                } else {
-                    // One-Way Mode
-                    if amt.is_sign_negative() {
-                        Side::Short
-                    } else {
-                        Side::Long
-                    }
-                };
-
-                // Abs size
-                let size = amt.abs();
-
-                positions.push(Position {
-                    symbol,
-                    side,
-                    size,
-                    entry_price,
-                    stop_loss: Decimal::ZERO, // Exchange doesn't give SL/TP easily in this endpoint usually
-                    take_profits: vec![],
-                    signal_id: "EXCHANGE_FETCHED".to_string(),
-                    opened_at: Utc::now(), // Unknown
-                    regime_state: None,
-                    phase: None,
-                    metadata: None,
-                    exchange: Some("BINANCE".to_string()),
-                    position_mode: Some(pos_side_str.to_string()),
-                    realized_pnl: Decimal::ZERO,
-                    unrealized_pnl: item["unRealizedProfit"]
-                        .as_str()
-                        .and_then(|s| Decimal::from_str_exact(s).ok())
-                        .unwrap_or(Decimal::ZERO),
-                    fees_paid: Decimal::ZERO,
-                    funding_paid: Decimal::ZERO,
-                    last_mark_price: None,
-                    last_update_ts: Utc::now().timestamp_millis(),
-                });
-            }
-        }
+        Ok(parse_position_risk(&json))
+    }
 
-        Ok(positions)
+    async fn get_funding_payments(&self, since: i64) -> Result<Vec<FundingPayment>, ExchangeError> {
+        let Some(endpoint) = self.endpoints.income else {
+            return Err(ExchangeError::NotImplemented(
+                "Binance spot pays no funding".to_string(),
+            ));
+        };
+
+        let mut items = Vec::new();
+        let mut seen = HashSet::new();
+        let mut start = Some(since);
+        while let Some(start_time) = start {
+            let page = self.fetch_income_page(endpoint, start_time).await?;
+            start = next_income_start(&page, start_time, INCOME_PAGE_LIMIT);
+            // Pages overlap on the boundary millisecond, so drop rows already taken.
+            items.extend(
+                page.into_iter()
+                    .filter(|item| item["tranId"].as_i64().is_none_or(|id| seen.insert(id))),
+            );
+        }
+        Ok(parse_funding_income(&serde_json::Value::Array(items)))
     }
 }
@@ -1,4 +1,9 @@
-use crate::exchange::adapter::{ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse};
+use crate::client_order_id::validate_for_exchange;
+use crate::exchange::adapter::{
+    http_client_builder, ExchangeAdapter, ExchangeError, FundingPayment, OrderRequest,
+    OrderResponse, OrderStatus,
+};
+use crate::exchange::telemetry;
 use crate::model::{OrderType, Position, Side};
 use async_trait::async_trait;
 use hex;
@@ -62,7 +67,9 @@ impl BybitAdapter {
         });
 
         Ok(Self {
-            client: Client::new(),
+            client: http_client_builder(config)?
+                .build()
+                .map_err(|e| ExchangeError::Network(e.to_string()))?,
             api_key,
             api_secret,
             base_url,
@@ -142,8 +149,7 @@ impl BybitAdapter {
             request = request.body(body_str);
         }
 
-        let response = request
-            .send()
+        let response = telemetry::send("bybit", request)
             .await
             .map_err(|e| ExchangeError::Network(e.to_string()))?;
         let status = response.status();
@@ -165,16 +171,26 @@ impl BybitAdapter {
         })?;
 
         if base_resp.ret_code != 0 {
-            return Err(ExchangeError::Api(format!(
-                "Bybit API Error {}: {}",
-                base_resp.ret_code, base_resp.ret_msg
-            )));
+            return Err(ExchangeError::VenueRejected {
+                code: base_resp.ret_code.to_string(),
+                message: base_resp.ret_msg,
+            });
         }
 
         Ok(base_resp.result)
     }
 }
 
+/// Body for `/v5/position/set-leverage`: one-way mode, both sides alike
+pub(crate) fn build_leverage_payload(symbol: &str, leverage: u32) -> serde_json::Value {
+    serde_json::json!({
+        "category": "linear",
+        "symbol": symbol.replace("/", ""),
+        "buyLeverage": leverage.to_string(),
+        "sellLeverage": leverage.to_string(),
+    })
+}
+
 pub(crate) fn build_order_payload(order: &OrderRequest) -> serde_json::Value {
     let side = match order.side {
         Side::Buy | Side::Long => "Buy",
@@ -204,9 +220,90 @@ pub(crate) fn build_order_payload(order: &OrderRequest) -> serde_json::Value {
         }
     }
 
+    // Native TP/SL on the entry, closing the whole position when triggered
+    if let (Some(bracket), Some(obj)) = (order.bracket, payload.as_object_mut()) {
+        if let Some(tp) = bracket.take_profit {
+            obj.insert("takeProfit".to_string(), serde_json::json!(tp.to_string()));
+        }
+        if let Some(sl) = bracket.stop_loss {
+            obj.insert("stopLoss".to_string(), serde_json::json!(sl.to_string()));
+        }
+        obj.insert("tpslMode".to_string(), serde_json::json!("Full"));
+    }
+
     payload
 }
 
+/// First order of a `/v5/order/realtime` result, None when the list is empty.
+pub(crate) fn parse_order_query(result: &serde_json::Value) -> Option<OrderResponse> {
+    parse_open_orders(result).into_iter().next()
+}
+
+/// Every order of a `/v5/order/realtime` result
+pub(crate) fn parse_open_orders(result: &serde_json::Value) -> Vec<OrderResponse> {
+    result["list"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(parse_order_item)
+        .collect()
+}
+
+/// Funding settlements of a `/v5/account/transaction-log?type=SETTLEMENT`
+/// result, oldest first. Bybit's `funding` is positive for an expense.
+pub(crate) fn parse_funding_settlements(result: &serde_json::Value) -> Vec<FundingPayment> {
+    let decimal = |item: &serde_json::Value, key: &str| {
+        item[key]
+            .as_str()
+            .and_then(|s| Decimal::from_str_exact(s).ok())
+    };
+    let mut payments: Vec<FundingPayment> = result["list"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|item| {
+            Some(FundingPayment {
+                symbol: item["symbol"].as_str()?.to_string(),
+                amount: decimal(item, "funding")?,
+                rate: decimal(item, "feeRate"),
+                asset: item["currency"].as_str().unwrap_or("USDT").to_string(),
+                funding_time: item["transactionTime"].as_str()?.parse().ok()?,
+            })
+        })
+        .collect();
+    payments.sort_by_key(|p| p.funding_time);
+    payments
+}
+
+/// Cursor of the next page of a paginated v5 result, `None` on the last page.
+pub(crate) fn next_page_cursor(result: &serde_json::Value) -> Option<&str> {
+    result["nextPageCursor"].as_str().filter(|c| !c.is_empty())
+}
+
+fn parse_order_item(item: &serde_json::Value) -> OrderResponse {
+    let text = |key: &str| item[key].as_str().unwrap_or_default().to_string();
+    let decimal = |key: &str| {
+        item[key]
+            .as_str()
+            .and_then(|s| Decimal::from_str_exact(s).ok())
+            .filter(|d| !d.is_zero())
+    };
+    let raw_status = text("orderStatus");
+    OrderResponse {
+        order_id: text("orderId"),
+        client_order_id: text("orderLinkId"),
+        symbol: text("symbol"),
+        status: OrderStatus::normalize(&raw_status),
+        raw_status,
+        avg_price: decimal("avgPrice"),
+        executed_qty: decimal("cumExecQty").unwrap_or(Decimal::ZERO),
+        t_ack: chrono::Utc::now().timestamp_millis(),
+        t_exchange: item["updatedTime"].as_str().and_then(|s| s.parse().ok()),
+        fee: decimal("cumExecFee"),
+        fee_asset: None,
+    }
+}
+
 #[async_trait]
 impl ExchangeAdapter for BybitAdapter {
     async fn init(&self) -> Result<(), ExchangeError> {
@@ -226,6 +323,8 @@ impl ExchangeAdapter for BybitAdapter {
     }
 
     async fn place_order(&self, order: OrderRequest) -> Result<OrderResponse, ExchangeError> {
+        validate_for_exchange("bybit", &order.client_order_id)?;
+
         let payload = build_order_payload(&order);
         if payload.get("error").is_some() {
             return Err(ExchangeError::Configuration(
@@ -241,7 +340,8 @@ impl ExchangeAdapter for BybitAdapter {
             order_id: resp.order_id,
             client_order_id: resp.order_link_id,
             symbol: resp.symbol,
-            status: resp.order_status,
+            status: OrderStatus::normalize(&resp.order_status),
+            raw_status: resp.order_status,
             avg_price: None, // Bybit Async response doesn't give fill price immediately usually
             executed_qty: Decimal::ZERO, // Need to fetch or wait for ws
             t_ack: chrono::Utc::now().timestamp_millis(),
@@ -270,7 +370,8 @@ impl ExchangeAdapter for BybitAdapter {
             order_id: resp.order_id,
             client_order_id: resp.order_link_id,
             symbol: resp.symbol,
-            status: "CANCELLED".to_string(),
+            status: OrderStatus::Cancelled,
+            raw_status: "CANCELLED".to_string(),
             avg_price: None,
             executed_qty: Decimal::ZERO,
             t_ack: chrono::Utc::now().timestamp_millis(),
@@ -280,6 +381,96 @@ impl ExchangeAdapter for BybitAdapter {
         })
     }
 
+    async fn get_order(
+        &self,
+        symbol: &str,
+        client_order_id: &str,
+    ) -> Result<Option<OrderResponse>, ExchangeError> {
+        let endpoint = format!(
+            "/v5/order/realtime?category=linear&symbol={}&orderLinkId={}",
+            symbol.replace("/", ""),
+            client_order_id
+        );
+        let result: serde_json::Value = self.request(Method::GET, &endpoint, None).await?;
+        Ok(parse_order_query(&result))
+    }
+
+    async fn get_open_orders(&self) -> Result<Vec<OrderResponse>, ExchangeError> {
+        // openOnly=0 lists active orders; settleCoin stands in for a symbol
+        let result: serde_json::Value = self
+            .request(
+                Method::GET,
+                "/v5/order/realtime?category=linear&settleCoin=USDT&openOnly=0&limit=50",
+                None,
+            )
+            .await?;
+        Ok(parse_open_orders(&result))
+    }
+
+    async fn set_dead_mans_switch(&self, window_ms: u64) -> Result<(), ExchangeError> {
+        // Disconnect-cancel protection is switched off from the account
+        // settings page only; the REST endpoint just sets the window.
+        if window_ms == 0 {
+            return Err(ExchangeError::NotImplemented(
+                "Bybit cancel-on-disconnect cannot be disarmed over REST".to_string(),
+            ));
+        }
+        // Bybit accepts a window of 3..=300 seconds
+        let payload = serde_json::json!({
+            "product": "DERIVATIVES",
+            "timeWindow": (window_ms / 1000).clamp(3, 300)
+        });
+
+        let _: serde_json::Value = self
+            .request(
+                Method::POST,
+                "/v5/order/disconnected-cancel-all",
+                Some(payload),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn set_leverage(&self, symbol: &str, leverage: u32) -> Result<(), ExchangeError> {
+        let result: Result<serde_json::Value, _> = self
+            .request(
+                Method::POST,
+                "/v5/position/set-leverage",
+                Some(build_leverage_payload(symbol, leverage)),
+            )
+            .await;
+        match result {
+            Ok(_) => Ok(()),
+            // 110043: leverage not modified, already at the target
+            Err(ExchangeError::VenueRejected { code, .. }) if code == "110043" => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn get_funding_payments(&self, since: i64) -> Result<Vec<FundingPayment>, ExchangeError> {
+        let mut payments = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut endpoint = format!(
+                "/v5/account/transaction-log?accountType=UNIFIED&category=linear&type=SETTLEMENT&startTime={}&limit=50",
+                since
+            );
+            if let Some(cursor) = &cursor {
+                endpoint.push_str(&format!("&cursor={}", cursor));
+            }
+            let result: serde_json::Value = self.request(Method::GET, &endpoint, None).await?;
+            payments.extend(parse_funding_settlements(&result));
+
+            // Stop on a repeated cursor too, so a misbehaving venue can't spin us forever.
+            match next_page_cursor(&result) {
+                Some(next) if cursor.as_deref() != Some(next) => cursor = Some(next.to_string()),
+                _ => break,
+            }
+        }
+        payments.sort_by_key(|p| p.funding_time);
+        Ok(payments)
+    }
+
     async fn get_balance(&self, asset: &str) -> Result<Decimal, ExchangeError> {
         // /v5/account/wallet-balance?accountType=UNIFIED
         // This is a GET request which requires query string signing logic which is annoying.
@@ -305,16 +496,17 @@ impl ExchangeAdapter for BybitAdapter {
             "{}{}?{}",
             self.base_url, "/v5/account/wallet-balance", query
         );
-        let resp = self
-            .client
-            .get(&url)
-            .header("X-BAPI-API-KEY", &self.api_key)
-            .header("X-BAPI-TIMESTAMP", &timestamp)
-            .header("X-BAPI-SIGN", signature)
-            .header("X-BAPI-RECV-WINDOW", RECV_WINDOW)
-            .send()
-            .await
-            .map_err(|e| ExchangeError::Network(e.to_string()))?;
+        let resp = telemetry::send(
+            "bybit",
+            self.client
+                .get(&url)
+                .header("X-BAPI-API-KEY", &self.api_key)
+                .header("X-BAPI-TIMESTAMP", &timestamp)
+                .header("X-BAPI-SIGN", signature)
+                .header("X-BAPI-RECV-WINDOW", RECV_WINDOW),
+        )
+        .await
+        .map_err(|e| ExchangeError::Network(e.to_string()))?;
 
         let status = resp.status();
         let text = resp
@@ -335,10 +527,10 @@ impl ExchangeAdapter for BybitAdapter {
             })?;
 
         if base_resp.ret_code != 0 {
-            return Err(ExchangeError::Api(format!(
-                "Bybit API Error {}: {}",
-                base_resp.ret_code, base_resp.ret_msg
-            )));
+            return Err(ExchangeError::VenueRejected {
+                code: base_resp.ret_code.to_string(),
+                message: base_resp.ret_msg,
+            });
         }
 
         let asset_upper = asset.to_uppercase();
@@ -357,6 +549,14 @@ impl ExchangeAdapter for BybitAdapter {
         )))
     }
 
+    fn supports_native_brackets(&self) -> bool {
+        true
+    }
+
+    fn settlement_assets(&self) -> Vec<String> {
+        vec!["USDT".to_string(), "USDC".to_string()]
+    }
+
     fn name(&self) -> &str {
         "Bybit V5"
     }
@@ -427,6 +627,7 @@ impl ExchangeAdapter for BybitAdapter {
                         .unwrap_or(Decimal::ZERO), // Approximate mapping
                     last_mark_price: None,
                     last_update_ts: chrono::Utc::now().timestamp_millis(),
+                    entry_lots: Vec::new(),
                 });
             }
         }
@@ -1,4 +1,8 @@
-use crate::exchange::adapter::{ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse};
+use crate::client_order_id::validate_for_exchange;
+use crate::exchange::adapter::{
+    http_client_builder, ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, OrderStatus,
+};
+use crate::exchange::telemetry;
 use crate::model::{Position, Side};
 use async_trait::async_trait;
 use chrono::Utc;
@@ -57,7 +61,9 @@ impl CoinbaseAdapter {
             api_key,
             secret_key,
             base_url,
-            client: Client::new(),
+            client: http_client_builder(config)?
+                .build()
+                .map_err(|e| ExchangeError::Network(e.to_string()))?,
             http_limiter,
         })
     }
@@ -101,8 +107,7 @@ impl CoinbaseAdapter {
             request = request.body(body_str);
         }
 
-        let resp = request
-            .send()
+        let resp = telemetry::send("coinbase", request)
             .await
             .map_err(|e| ExchangeError::Network(e.to_string()))?;
         let status = resp.status();
@@ -132,6 +137,8 @@ impl ExchangeAdapter for CoinbaseAdapter {
     }
 
     async fn place_order(&self, order: OrderRequest) -> Result<OrderResponse, ExchangeError> {
+        validate_for_exchange("coinbase", &order.client_order_id)?;
+
         // POST /api/v3/brokerage/orders
         let path = "/api/v3/brokerage/orders";
 
@@ -205,7 +212,8 @@ impl ExchangeAdapter for CoinbaseAdapter {
             order_id,
             client_order_id,
             symbol: order.symbol,
-            status: "UNKNOWN".to_string(), // Need to query status or assume PENDING
+            status: OrderStatus::Unknown, // Need to query status or assume PENDING
+            raw_status: "UNKNOWN".to_string(),
             avg_price: None,
             executed_qty: Decimal::ZERO,
             t_ack: Utc::now().timestamp_millis(),
@@ -235,7 +243,8 @@ impl ExchangeAdapter for CoinbaseAdapter {
             order_id: order_id.to_string(),
             client_order_id: "".to_string(),
             symbol: symbol.to_string(),
-            status: "CANCELED".to_string(),
+            status: OrderStatus::Cancelled,
+            raw_status: "CANCELED".to_string(),
             avg_price: None,
             executed_qty: Decimal::ZERO,
             t_ack: Utc::now().timestamp_millis(),
@@ -267,6 +276,10 @@ impl ExchangeAdapter for CoinbaseAdapter {
         Ok(Decimal::ZERO)
     }
 
+    fn settlement_assets(&self) -> Vec<String> {
+        vec!["USD".to_string(), "USDC".to_string()]
+    }
+
     fn name(&self) -> &str {
         "Coinbase Advanced"
     }
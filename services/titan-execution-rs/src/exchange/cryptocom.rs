@@ -1,7 +1,10 @@
+use crate::client_order_id::validate_for_exchange;
 use crate::config::ExchangeConfig;
 use crate::exchange::adapter::{
-    ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, Position,
+    http_client_builder, ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, OrderStatus,
+    Position,
 };
+use crate::exchange::telemetry;
 use async_trait::async_trait;
 use chrono::Utc;
 use hex;
@@ -50,7 +53,7 @@ impl CryptoComAdapter {
             api_key,
             secret_key,
             base_url,
-            client: Client::builder()
+            client: http_client_builder(Some(config))?
                 .timeout(Duration::from_secs(10))
                 .build()
                 .map_err(|e| ExchangeError::Network(e.to_string()))?,
@@ -151,14 +154,15 @@ impl CryptoComAdapter {
 
         request_body.insert("sig".to_string(), Value::String(signature));
 
-        let response = self
-            .client
-            .post(&url)
-            .header(CONTENT_TYPE, "application/json")
-            .json(&Value::Object(request_body))
-            .send()
-            .await
-            .map_err(|e| ExchangeError::Network(e.to_string()))?;
+        let response = telemetry::send(
+            "cryptocom",
+            self.client
+                .post(&url)
+                .header(CONTENT_TYPE, "application/json")
+                .json(&Value::Object(request_body)),
+        )
+        .await
+        .map_err(|e| ExchangeError::Network(e.to_string()))?;
 
         let status = response.status();
         let text = response
@@ -210,6 +214,8 @@ impl ExchangeAdapter for CryptoComAdapter {
     }
 
     async fn place_order(&self, order: OrderRequest) -> Result<OrderResponse, ExchangeError> {
+        validate_for_exchange("cryptocom", &order.client_order_id)?;
+
         // private/create-order
         let endpoint = "private/create-order";
 
@@ -287,7 +293,8 @@ impl ExchangeAdapter for CryptoComAdapter {
             order_id,
             client_order_id: order.client_order_id,
             symbol: order.symbol,
-            status: "NEW".to_string(),
+            status: OrderStatus::New,
+            raw_status: "NEW".to_string(),
             executed_qty: Decimal::zero(),
             avg_price: None,
             t_exchange: None,
@@ -317,7 +324,8 @@ impl ExchangeAdapter for CryptoComAdapter {
             order_id: order_id.to_string(),
             client_order_id: "".to_string(),
             symbol: symbol.to_string(),
-            status: "CANCELED".to_string(),
+            status: OrderStatus::Cancelled,
+            raw_status: "CANCELED".to_string(),
             executed_qty: Decimal::zero(),
             avg_price: None,
             t_exchange: None,
@@ -436,6 +444,7 @@ impl ExchangeAdapter for CryptoComAdapter {
                     funding_paid: Decimal::ZERO,
                     last_mark_price: None,
                     last_update_ts: Utc::now().timestamp_millis(),
+                    entry_lots: Vec::new(),
                 });
             }
         }
@@ -1,6 +1,6 @@
 use crate::config::ExchangeConfig;
 use crate::exchange::adapter::{
-    ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, Position,
+    ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, OrderStatus, Position,
 };
 use crate::exchange::dex_utils;
 use async_trait::async_trait;
@@ -57,6 +57,8 @@ pub struct CurveAdapter {
     pool_address: Address,
     pool_name: String,
     slippage_bps: u64,
+    amount_rounding: dex_utils::AmountRounding,
+    gas_guard: dex_utils::GasGuard,
 }
 
 impl CurveAdapter {
@@ -112,6 +114,8 @@ impl CurveAdapter {
             pool_address,
             pool_name,
             slippage_bps,
+            amount_rounding: dex_utils::resolve_amount_rounding("CURVE"),
+            gas_guard: dex_utils::GasGuard::from_env("CURVE")?,
         })
     }
 
@@ -208,10 +212,7 @@ impl ExchangeAdapter for CurveAdapter {
         let j = Self::token_index(&self.pool_name, token_out)?;
 
         let decimals = Self::token_decimals(token_in);
-        let amount_in_raw = (order.quantity * Decimal::from(10u64.pow(decimals)))
-            .to_u64()
-            .unwrap_or(0);
-        let amount_in = U256::from(amount_in_raw);
+        let amount_in = dex_utils::to_base_units(order.quantity, decimals, self.amount_rounding)?;
 
         // ERC-20 approval (skip for native ETH)
         if token_in != "ETH" {
@@ -254,6 +255,14 @@ impl ExchangeAdapter for CurveAdapter {
 
         let tx = contract.exchange(i, j, amount_in, min_dy);
 
+        self.gas_guard
+            .enforce(
+                self.client.clone(),
+                &tx.tx,
+                dex_utils::swap_notional_usd(token_in, order.quantity, order.price),
+            )
+            .await?;
+
         let pending_tx = tx
             .send()
             .await
@@ -265,7 +274,8 @@ impl ExchangeAdapter for CurveAdapter {
             order_id: tx_hash,
             client_order_id: order.client_order_id,
             symbol: order.symbol,
-            status: "PENDING".to_string(),
+            status: OrderStatus::Pending,
+            raw_status: "PENDING".to_string(),
             executed_qty: Decimal::zero(),
             avg_price: None,
             t_exchange: None,
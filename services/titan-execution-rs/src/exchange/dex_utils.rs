@@ -1,11 +1,15 @@
 use ethers::prelude::*;
 use rust_decimal::prelude::*;
 use std::sync::Arc;
+use thiserror::Error;
 
-// Shared EVM DEX utilities — slippage, token approval, gas estimation
+use crate::exchange::adapter::{ExchangeError, SwapMode};
+
+// Shared DEX utilities — slippage, token approval, gas estimation, amount precision
 //
 // Every DEX adapter must call `ensure_approval()` before swapping tokens,
-// and use `calc_min_output()` for slippage protection.
+// use `calc_min_output()` for slippage protection, and convert order quantities
+// with `to_base_units()` (never `Decimal -> u64` casts, which truncate or overflow).
 
 // Standard ERC-20 ABI — approve + allowance
 abigen!(
@@ -62,6 +66,113 @@ pub fn calc_min_output(amount_in: U256, slippage_bps: u64) -> U256 {
     amount_in * factor / U256::from(10000u64)
 }
 
+/// Calculate maximum input amount with slippage protection for exact-out swaps:
+/// `quoted_in * (10000 + slippage_bps) / 10000`.
+pub fn calc_max_input(quoted_in: U256, slippage_bps: u64) -> U256 {
+    let factor = U256::from(10000u64 + slippage_bps);
+    quoted_in * factor / U256::from(10000u64)
+}
+
+/// How to treat order quantities with more precision than the token supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AmountRounding {
+    /// Reject amounts that cannot be represented exactly
+    #[default]
+    Strict,
+    /// Truncate toward zero (never send more than requested)
+    Down,
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum AmountError {
+    #[error("Amount {0} is negative")]
+    Negative(Decimal),
+    #[error("Amount {amount} has more precision than the token's {decimals} decimals")]
+    PrecisionLoss { amount: Decimal, decimals: u32 },
+    #[error("Amount {amount} at {decimals} decimals overflows a {bits}-bit integer")]
+    Overflow {
+        amount: Decimal,
+        decimals: u32,
+        bits: u32,
+    },
+}
+
+impl From<AmountError> for ExchangeError {
+    fn from(e: AmountError) -> Self {
+        ExchangeError::OrderRejected(e.to_string())
+    }
+}
+
+/// Convert a token amount to integer base units (`amount * 10^decimals`) exactly,
+/// without going through f64 or a fixed-width intermediate.
+pub fn to_base_units(
+    amount: Decimal,
+    decimals: u32,
+    rounding: AmountRounding,
+) -> Result<U256, AmountError> {
+    if amount.is_sign_negative() && !amount.is_zero() {
+        return Err(AmountError::Negative(amount));
+    }
+
+    let mut units = amount.normalize();
+    if units.scale() > decimals {
+        if rounding == AmountRounding::Strict {
+            return Err(AmountError::PrecisionLoss { amount, decimals });
+        }
+        units = units
+            .round_dp_with_strategy(decimals, RoundingStrategy::ToZero)
+            .normalize();
+    }
+
+    let overflow = AmountError::Overflow {
+        amount,
+        decimals,
+        bits: 256,
+    };
+    // 10^77 is the largest power of ten that fits in a U256
+    let exponent = (decimals - units.scale()) as usize;
+    if exponent > 77 {
+        return Err(overflow);
+    }
+    U256::from(units.mantissa().unsigned_abs())
+        .checked_mul(U256::exp10(exponent))
+        .ok_or(overflow)
+}
+
+/// `to_base_units` for APIs that take a u64 amount (e.g. Solana SPL tokens).
+pub fn to_base_units_u64(
+    amount: Decimal,
+    decimals: u32,
+    rounding: AmountRounding,
+) -> Result<u64, AmountError> {
+    let units = to_base_units(amount, decimals, rounding)?;
+    if units > U256::from(u64::MAX) {
+        return Err(AmountError::Overflow {
+            amount,
+            decimals,
+            bits: 64,
+        });
+    }
+    Ok(units.as_u64())
+}
+
+/// `to_base_units` for APIs that take a u128 amount.
+pub fn to_base_units_u128(
+    amount: Decimal,
+    decimals: u32,
+    rounding: AmountRounding,
+) -> Result<u128, AmountError> {
+    let units = to_base_units(amount, decimals, rounding)?;
+    if units > U256::from(u128::MAX) {
+        return Err(AmountError::Overflow {
+            amount,
+            decimals,
+            bits: 128,
+        });
+    }
+    Ok(units.as_u128())
+}
+
 /// Ensure the router has sufficient ERC-20 token allowance.
 ///
 /// If current allowance < required amount, sends an `approve(MAX)` transaction.
@@ -170,3 +281,599 @@ pub fn resolve_slippage(prefix: &str) -> u64 {
         .and_then(|s| s.parse().ok())
         .unwrap_or(DEFAULT_SLIPPAGE_BPS)
 }
+
+/// Slippage tolerance that widens with trade size: small swaps get tight
+/// limits, leaving sandwich bots little room, while large ones keep the room
+/// they need to fill. Every tier is clamped to `max_bps`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlippageCurve {
+    /// (USD notional upper bound, bps), ascending by notional
+    tiers: Vec<(Decimal, u64)>,
+    /// Applies above the last tier and when the notional is unknown
+    max_bps: u64,
+}
+
+impl SlippageCurve {
+    pub fn flat(bps: u64) -> Self {
+        Self {
+            tiers: Vec::new(),
+            max_bps: bps,
+        }
+    }
+
+    /// Parse `notional:bps` tiers, e.g. `1000:10,10000:25,100000:50`.
+    pub fn parse(spec: &str, max_bps: u64) -> Result<Self, String> {
+        let mut tiers: Vec<(Decimal, u64)> = Vec::new();
+        for tier in spec.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            let (notional, bps) = tier
+                .split_once(':')
+                .ok_or_else(|| format!("tier '{}' is not notional:bps", tier))?;
+            let notional = Decimal::from_str(notional.trim())
+                .map_err(|_| format!("tier '{}' has an invalid notional", tier))?;
+            let bps: u64 = bps
+                .trim()
+                .parse()
+                .map_err(|_| format!("tier '{}' has invalid bps", tier))?;
+            if bps >= 10_000 {
+                return Err(format!("tier '{}' allows 100% slippage or more", tier));
+            }
+            if tiers.last().is_some_and(|(prev, _)| notional <= *prev) {
+                return Err(format!("tier '{}' is not above the previous one", tier));
+            }
+            tiers.push((notional, bps));
+        }
+        Ok(Self { tiers, max_bps })
+    }
+
+    /// Reads `{PREFIX}_SLIPPAGE_BPS` as the cap and `{PREFIX}_SLIPPAGE_CURVE`
+    /// as tiers. A curve that does not parse is ignored (flat cap).
+    pub fn from_env(prefix: &str) -> Self {
+        let max_bps = resolve_slippage(prefix);
+        let Ok(spec) = std::env::var(format!("{}_SLIPPAGE_CURVE", prefix)) else {
+            return Self::flat(max_bps);
+        };
+        Self::parse(&spec, max_bps).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring {}_SLIPPAGE_CURVE: {}", prefix, e);
+            Self::flat(max_bps)
+        })
+    }
+
+    /// Slippage (bps) for a swap of `notional_usd`.
+    pub fn bps_for(&self, notional_usd: Option<Decimal>) -> u64 {
+        notional_usd
+            .and_then(|n| self.tiers.iter().find(|(limit, _)| n <= *limit))
+            .map_or(self.max_bps, |(_, bps)| (*bps).min(self.max_bps))
+    }
+}
+
+/// Default cap on a swap's gas cost as a share of its notional (100 bps = 1%)
+pub const DEFAULT_MAX_GAS_BPS: u64 = 100;
+
+/// Gas units assumed for a swap when `eth_estimateGas` fails (e.g. approval still pending)
+pub const FALLBACK_SWAP_GAS_UNITS: u64 = 250_000;
+
+/// Rejects swaps whose gas cost would eat too much of the trade, so a gas spike
+/// cannot turn a small swap into a loss. Every EVM adapter enforces it right
+/// before sending. Pricing gas in USD needs the native gas token's price, so an
+/// enabled guard refuses to build without one; `max_gas_bps` 0 turns it off.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GasGuard {
+    pub max_gas_bps: u64,
+    pub native_usd: Option<Decimal>,
+}
+
+impl GasGuard {
+    /// Reads `{PREFIX}_MAX_GAS_BPS` and `{PREFIX}_NATIVE_USD` (ETH/AVAX/BNB price).
+    pub fn from_env(prefix: &str) -> Result<Self, ExchangeError> {
+        Self::parse(
+            prefix,
+            std::env::var(format!("{}_MAX_GAS_BPS", prefix))
+                .ok()
+                .as_deref(),
+            std::env::var(format!("{}_NATIVE_USD", prefix))
+                .ok()
+                .as_deref(),
+        )
+    }
+
+    fn parse(
+        prefix: &str,
+        max_gas_bps: Option<&str>,
+        native_usd: Option<&str>,
+    ) -> Result<Self, ExchangeError> {
+        let max_gas_bps = match max_gas_bps {
+            Some(raw) => raw.trim().parse().map_err(|_| {
+                ExchangeError::Configuration(format!("Invalid {}_MAX_GAS_BPS: {}", prefix, raw))
+            })?,
+            None => DEFAULT_MAX_GAS_BPS,
+        };
+        let native_usd = native_usd
+            .and_then(|s| Decimal::from_str(s.trim()).ok())
+            .filter(|px| *px > Decimal::ZERO);
+        if max_gas_bps > 0 && native_usd.is_none() {
+            return Err(ExchangeError::Configuration(format!(
+                "Gas guard needs a positive {0}_NATIVE_USD (set {0}_MAX_GAS_BPS=0 to disable it)",
+                prefix
+            )));
+        }
+        Ok(Self {
+            max_gas_bps,
+            native_usd,
+        })
+    }
+
+    /// Check `gas_cost_wei` (gas units * gas price) against the trade's USD notional.
+    /// Returns the estimated gas cost in USD when it could be evaluated.
+    pub fn check(
+        &self,
+        gas_cost_wei: U256,
+        notional_usd: Option<Decimal>,
+    ) -> Result<Option<Decimal>, ExchangeError> {
+        if self.max_gas_bps == 0 {
+            return Ok(None);
+        }
+        let (Some(native_usd), Some(notional_usd)) = (self.native_usd, notional_usd) else {
+            return Ok(None);
+        };
+        let cost_native = Decimal::from_str(&gas_cost_wei.to_string())
+            .map_err(|_| ExchangeError::OrderRejected("gas too high: cost overflow".into()))?
+            / Decimal::from(10u64.pow(18));
+        let cost_usd = cost_native * native_usd;
+        let budget_usd = notional_usd * Decimal::from(self.max_gas_bps) / Decimal::from(10_000);
+        if cost_usd > budget_usd {
+            return Err(ExchangeError::OrderRejected(format!(
+                "gas too high: est. ${} exceeds {}bps of ${} notional (${})",
+                cost_usd.round_dp(2),
+                self.max_gas_bps,
+                notional_usd.round_dp(2),
+                budget_usd.round_dp(2)
+            )));
+        }
+        Ok(Some(cost_usd))
+    }
+}
+
+impl GasGuard {
+    /// Estimate the gas cost of `tx` and enforce the budget. Skips the RPC round
+    /// trips entirely when disabled or there is nothing to compare against.
+    pub async fn enforce<M: Middleware + 'static>(
+        &self,
+        client: Arc<M>,
+        tx: &ethers::types::transaction::eip2718::TypedTransaction,
+        notional_usd: Option<Decimal>,
+    ) -> Result<(), ExchangeError> {
+        if self.max_gas_bps == 0 || self.native_usd.is_none() || notional_usd.is_none() {
+            return Ok(());
+        }
+        let gas_cost_wei = estimate_gas_cost(client, tx).await?;
+        self.check(gas_cost_wei, notional_usd).map(|_| ())
+    }
+}
+
+/// Gas cost (wei) of sending `tx`: `eth_gasPrice` * `eth_estimateGas`.
+pub async fn estimate_gas_cost<M: Middleware + 'static>(
+    client: Arc<M>,
+    tx: &ethers::types::transaction::eip2718::TypedTransaction,
+) -> Result<U256, ExchangeError> {
+    let gas_price = client
+        .get_gas_price()
+        .await
+        .map_err(|e| ExchangeError::Network(format!("Gas price fetch failed: {}", e)))?;
+    let gas_units = client
+        .estimate_gas(tx, None)
+        .await
+        .unwrap_or_else(|_| U256::from(FALLBACK_SWAP_GAS_UNITS));
+    Ok(gas_price * gas_units)
+}
+
+/// USD notional of a swap of `quantity` `token_in`: stables at par, otherwise the
+/// order's limit price when it has one.
+pub fn swap_notional_usd(
+    token_in: &str,
+    quantity: Decimal,
+    price: Option<Decimal>,
+) -> Option<Decimal> {
+    let symbol = token_in.to_uppercase();
+    if ["USDC", "USDT", "DAI", "USDC.E", "BUSD"].contains(&symbol.as_str()) {
+        return Some(quantity);
+    }
+    // Address-form tokens: match the well-known stables
+    match token_in.to_lowercase().as_str() {
+        "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"
+        | "0xdac17f958d2ee523a2206206994597c13d831ec7"
+        | "0x6b175474e89094c44da98b954eedeac495271d0f" => Some(quantity),
+        _ => price.map(|px| quantity * px),
+    }
+}
+
+/// USD notional of an order swapping out of `token_in`: ExactIn spends
+/// `quantity` of it, ExactOut buys `quantity` at `price` (token_in per unit).
+pub fn order_notional_usd(
+    token_in: &str,
+    mode: SwapMode,
+    quantity: Decimal,
+    price: Option<Decimal>,
+) -> Option<Decimal> {
+    match mode {
+        SwapMode::ExactIn => swap_notional_usd(token_in, quantity, price),
+        SwapMode::ExactOut => price.and_then(|px| swap_notional_usd(token_in, quantity * px, None)),
+    }
+}
+
+/// Selector of Solidity's `Error(string)`, emitted by `require(cond, "reason")`
+const ERROR_STRING_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// Why a DEX transaction reverted, as far as the pipeline needs to know.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevertKind {
+    SlippageExceeded,
+    InsufficientLiquidity,
+    DeadlinePassed,
+}
+
+/// Custom errors (Uniswap Universal Router, GMX V2) by signature
+const CUSTOM_REVERTS: &[(&str, RevertKind)] = &[
+    ("V3TooLittleReceived()", RevertKind::SlippageExceeded),
+    ("V3TooMuchRequested()", RevertKind::SlippageExceeded),
+    ("V2TooLittleReceived()", RevertKind::SlippageExceeded),
+    ("V2TooMuchRequested()", RevertKind::SlippageExceeded),
+    ("TransactionDeadlinePassed()", RevertKind::DeadlinePassed),
+    (
+        "OrderNotFulfillableAtAcceptablePrice(uint256,uint256)",
+        RevertKind::SlippageExceeded,
+    ),
+    (
+        "InsufficientSwapOutputAmount(uint256,uint256)",
+        RevertKind::SlippageExceeded,
+    ),
+    (
+        "InsufficientOutputAmount(uint256,uint256)",
+        RevertKind::SlippageExceeded,
+    ),
+    (
+        "InsufficientReserve(uint256,uint256)",
+        RevertKind::InsufficientLiquidity,
+    ),
+    (
+        "InsufficientPoolAmount(uint256,uint256)",
+        RevertKind::InsufficientLiquidity,
+    ),
+];
+
+/// Classify a `require` reason string from Uniswap V2/V3 routers and pools.
+pub fn classify_revert_reason(reason: &str) -> Option<RevertKind> {
+    let upper = reason.to_uppercase();
+    if upper.contains("TOO LITTLE RECEIVED")
+        || upper.contains("TOO MUCH REQUESTED")
+        || upper.contains("INSUFFICIENT_OUTPUT_AMOUNT")
+        || upper.contains("EXCESSIVE_INPUT_AMOUNT")
+    {
+        Some(RevertKind::SlippageExceeded)
+    } else if upper.contains("TRANSACTION TOO OLD") || upper.contains("EXPIRED") {
+        Some(RevertKind::DeadlinePassed)
+    } else if upper.contains("INSUFFICIENT_LIQUIDITY") || upper.contains("INSUFFICIENT LIQUIDITY") {
+        Some(RevertKind::InsufficientLiquidity)
+    } else {
+        None
+    }
+}
+
+/// Decode raw revert data into a readable reason and, when recognised, its kind.
+/// Returns None for empty data.
+pub fn decode_revert_data(data: &[u8]) -> Option<(String, Option<RevertKind>)> {
+    let selector: [u8; 4] = data.get(..4)?.try_into().ok()?;
+    if selector == ERROR_STRING_SELECTOR {
+        let reason = ethers::abi::decode(&[ethers::abi::ParamType::String], &data[4..])
+            .ok()
+            .and_then(|tokens| tokens.into_iter().next()?.into_string())?;
+        let kind = classify_revert_reason(&reason);
+        return Some((reason, kind));
+    }
+    CUSTOM_REVERTS
+        .iter()
+        .find(|(signature, _)| ethers::utils::id(signature) == selector)
+        .map(|(signature, kind)| (signature.to_string(), Some(*kind)))
+        .or_else(|| Some((format!("0x{}", hex::encode(data)), None)))
+}
+
+/// Map a failed DEX contract call to an actionable `ExchangeError`: decodes the
+/// revert data when the node returned it, otherwise classifies the RPC message
+/// (e.g. `execution reverted: Too little received`). Unrecognised reverts are
+/// order rejections; anything else stays a network error.
+pub fn contract_error<M: Middleware>(context: &str, e: ContractError<M>) -> ExchangeError {
+    let message = e.to_string();
+    let reverted = e.is_revert() || message.to_lowercase().contains("revert");
+    let (reason, kind) = match e
+        .as_revert()
+        .and_then(|data| decode_revert_data(data.as_ref()))
+    {
+        Some(decoded) => decoded,
+        None if reverted => (message.clone(), classify_revert_reason(&message)),
+        None => (message.clone(), None),
+    };
+    let detail = format!("{}: {}", context, reason);
+    match kind {
+        Some(RevertKind::SlippageExceeded) => ExchangeError::SlippageExceeded(detail),
+        Some(RevertKind::InsufficientLiquidity) => ExchangeError::InsufficientLiquidity(detail),
+        Some(RevertKind::DeadlinePassed) => ExchangeError::DeadlinePassed(detail),
+        None if reverted => ExchangeError::OrderRejected(detail),
+        None => ExchangeError::Network(detail),
+    }
+}
+
+/// Resolve amount rounding from environment variable (`strict` or `down`).
+/// Reads `{PREFIX}_AMOUNT_ROUNDING` env var.
+pub fn resolve_amount_rounding(prefix: &str) -> AmountRounding {
+    match std::env::var(format!("{}_AMOUNT_ROUNDING", prefix))
+        .map(|s| s.to_lowercase())
+        .as_deref()
+    {
+        Ok("down") => AmountRounding::Down,
+        _ => AmountRounding::Strict,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_slippage_scales_with_notional() {
+        let curve = SlippageCurve::parse("1000:10, 10000:25, 100000:80", 50).unwrap();
+        assert_eq!(curve.bps_for(Some(dec!(250))), 10);
+        assert_eq!(curve.bps_for(Some(dec!(1000))), 10);
+        assert_eq!(curve.bps_for(Some(dec!(5000))), 25);
+        // Tier above the cap is clamped to it
+        assert_eq!(curve.bps_for(Some(dec!(50000))), 50);
+        assert_eq!(curve.bps_for(Some(dec!(1000000))), 50);
+        // Unknown notional: the cap
+        assert_eq!(curve.bps_for(None), 50);
+        assert_eq!(SlippageCurve::flat(30).bps_for(Some(dec!(10))), 30);
+
+        assert!(SlippageCurve::parse("1000:10,500:20", 50).is_err());
+        assert!(SlippageCurve::parse("1000", 50).is_err());
+        assert!(SlippageCurve::parse("1000:10000", 50).is_err());
+    }
+
+    #[test]
+    fn test_base_units_exact_at_precision_boundary() {
+        // Exactly 6 decimals fits USDC
+        assert_eq!(
+            to_base_units(dec!(1.123456), 6, AmountRounding::Strict),
+            Ok(U256::from(1_123_456u64))
+        );
+        // Trailing zeros beyond the token decimals are not a loss
+        assert_eq!(
+            to_base_units(dec!(2.500000000), 6, AmountRounding::Strict),
+            Ok(U256::from(2_500_000u64))
+        );
+        // One digit too many
+        assert_eq!(
+            to_base_units(dec!(1.1234567), 6, AmountRounding::Strict),
+            Err(AmountError::PrecisionLoss {
+                amount: dec!(1.1234567),
+                decimals: 6
+            })
+        );
+        assert_eq!(
+            to_base_units(dec!(1.1234567), 6, AmountRounding::Down),
+            Ok(U256::from(1_123_456u64))
+        );
+        // Smallest representable 18-decimal amount
+        assert_eq!(
+            to_base_units(dec!(0.000000000000000001), 18, AmountRounding::Strict),
+            Ok(U256::one())
+        );
+        assert!(matches!(
+            to_base_units(dec!(-1), 18, AmountRounding::Strict),
+            Err(AmountError::Negative(_))
+        ));
+    }
+
+    fn eth_guard() -> GasGuard {
+        GasGuard {
+            max_gas_bps: 100,
+            native_usd: Some(dec!(2000)),
+        }
+    }
+
+    #[test]
+    fn test_gas_guard_accepts_cheap_gas() {
+        // 150k gas @ 20 gwei = 0.003 ETH = $6, under 1% of $1000
+        let cost = U256::from(150_000u64) * U256::from(20_000_000_000u64);
+        assert_eq!(
+            eth_guard().check(cost, Some(dec!(1000))).unwrap(),
+            Some(dec!(6))
+        );
+    }
+
+    #[test]
+    fn test_gas_guard_rejects_gas_spike() {
+        // 150k gas @ 50 gwei = 0.0075 ETH = $15, over the $10 budget
+        let cost = U256::from(150_000u64) * U256::from(50_000_000_000u64);
+        match eth_guard().check(cost, Some(dec!(1000))) {
+            Err(ExchangeError::OrderRejected(msg)) => assert!(msg.starts_with("gas too high")),
+            other => panic!("expected gas rejection, got {:?}", other),
+        }
+        // Same spike is fine on a notional large enough to absorb it
+        assert!(eth_guard().check(cost, Some(dec!(5000))).is_ok());
+        // Unknown notional or native price: nothing to compare, allowed
+        assert_eq!(eth_guard().check(cost, None).unwrap(), None);
+        let unpriced = GasGuard {
+            native_usd: None,
+            ..eth_guard()
+        };
+        assert_eq!(unpriced.check(cost, Some(dec!(1000))).unwrap(), None);
+        let disabled = GasGuard {
+            max_gas_bps: 0,
+            ..eth_guard()
+        };
+        assert_eq!(disabled.check(cost, Some(dec!(1000))).unwrap(), None);
+    }
+
+    #[test]
+    fn test_gas_guard_requires_native_price_unless_disabled() {
+        assert_eq!(
+            GasGuard::parse("UNISWAP", None, Some("2000")).unwrap(),
+            GasGuard {
+                max_gas_bps: DEFAULT_MAX_GAS_BPS,
+                native_usd: Some(dec!(2000)),
+            }
+        );
+        for native_usd in [None, Some("0"), Some("abc")] {
+            match GasGuard::parse("UNISWAP", None, native_usd) {
+                Err(ExchangeError::Configuration(msg)) => {
+                    assert!(msg.contains("UNISWAP_NATIVE_USD"), "{}", msg)
+                }
+                other => panic!("expected configuration error, got {:?}", other),
+            }
+        }
+        assert!(GasGuard::parse("UNISWAP", Some("lots"), Some("2000")).is_err());
+        // Explicitly off: no price needed
+        assert_eq!(
+            GasGuard::parse("UNISWAP", Some("0"), None)
+                .unwrap()
+                .max_gas_bps,
+            0
+        );
+    }
+
+    #[test]
+    fn test_swap_notional_usd() {
+        assert_eq!(swap_notional_usd("USDC", dec!(500), None), Some(dec!(500)));
+        assert_eq!(
+            swap_notional_usd(
+                "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+                dec!(500),
+                None
+            ),
+            Some(dec!(500))
+        );
+        assert_eq!(
+            swap_notional_usd("WETH", dec!(2), Some(dec!(2500))),
+            Some(dec!(5000))
+        );
+        assert_eq!(swap_notional_usd("WETH", dec!(2), None), None);
+    }
+
+    fn error_string(reason: &str) -> Vec<u8> {
+        let mut data = ERROR_STRING_SELECTOR.to_vec();
+        data.extend(ethers::abi::encode(&[ethers::abi::Token::String(
+            reason.to_string(),
+        )]));
+        data
+    }
+
+    fn custom_error(signature: &str, args: &[u64]) -> Vec<u8> {
+        let mut data = ethers::utils::id(signature).to_vec();
+        let tokens: Vec<_> = args
+            .iter()
+            .map(|a| ethers::abi::Token::Uint(U256::from(*a)))
+            .collect();
+        data.extend(ethers::abi::encode(&tokens));
+        data
+    }
+
+    #[test]
+    fn test_revert_reason_strings_map_to_kinds() {
+        let cases = [
+            ("Too little received", Some(RevertKind::SlippageExceeded)),
+            ("Too much requested", Some(RevertKind::SlippageExceeded)),
+            (
+                "UniswapV2Router: INSUFFICIENT_OUTPUT_AMOUNT",
+                Some(RevertKind::SlippageExceeded),
+            ),
+            ("Transaction too old", Some(RevertKind::DeadlinePassed)),
+            ("UniswapV2Router: EXPIRED", Some(RevertKind::DeadlinePassed)),
+            (
+                "UniswapV2: INSUFFICIENT_LIQUIDITY",
+                Some(RevertKind::InsufficientLiquidity),
+            ),
+            ("STF", None),
+        ];
+        for (reason, kind) in cases {
+            assert_eq!(
+                decode_revert_data(&error_string(reason)),
+                Some((reason.to_string(), kind)),
+                "{}",
+                reason
+            );
+        }
+    }
+
+    #[test]
+    fn test_custom_error_selectors_map_to_kinds() {
+        assert_eq!(
+            decode_revert_data(&custom_error("V3TooLittleReceived()", &[])),
+            Some((
+                "V3TooLittleReceived()".to_string(),
+                Some(RevertKind::SlippageExceeded)
+            ))
+        );
+        assert_eq!(
+            decode_revert_data(&custom_error("TransactionDeadlinePassed()", &[])).and_then(|d| d.1),
+            Some(RevertKind::DeadlinePassed)
+        );
+        assert_eq!(
+            decode_revert_data(&custom_error(
+                "OrderNotFulfillableAtAcceptablePrice(uint256,uint256)",
+                &[1_900, 2_000]
+            ))
+            .and_then(|d| d.1),
+            Some(RevertKind::SlippageExceeded)
+        );
+        assert_eq!(
+            decode_revert_data(&custom_error(
+                "InsufficientPoolAmount(uint256,uint256)",
+                &[10, 20]
+            ))
+            .and_then(|d| d.1),
+            Some(RevertKind::InsufficientLiquidity)
+        );
+        // Unknown selector: surfaced as hex, no kind
+        assert_eq!(
+            decode_revert_data(&[0xde, 0xad, 0xbe, 0xef]),
+            Some(("0xdeadbeef".to_string(), None))
+        );
+        assert_eq!(decode_revert_data(&[]), None);
+    }
+
+    #[test]
+    fn test_revert_kinds_decide_retry() {
+        assert!(ExchangeError::SlippageExceeded("x".into()).is_requote_retryable());
+        assert!(ExchangeError::DeadlinePassed("x".into()).is_requote_retryable());
+        assert!(!ExchangeError::InsufficientLiquidity("x".into()).is_requote_retryable());
+        assert!(!ExchangeError::Network("x".into()).is_requote_retryable());
+    }
+
+    #[test]
+    fn test_base_units_overflow() {
+        // 19 ETH in wei exceeds u64 (the old `.to_u64().unwrap_or(0)` sent 0)
+        assert_eq!(
+            to_base_units(dec!(19), 18, AmountRounding::Strict),
+            Ok(U256::from(19u128 * 10u128.pow(18)))
+        );
+        assert_eq!(
+            to_base_units_u64(dec!(19), 18, AmountRounding::Strict),
+            Err(AmountError::Overflow {
+                amount: dec!(19),
+                decimals: 18,
+                bits: 64
+            })
+        );
+        // 30-decimal GMX USD sizes fit u128 up to ~3.4e8
+        assert_eq!(
+            to_base_units_u128(dec!(1000.5), 30, AmountRounding::Strict),
+            Ok(10005u128 * 10u128.pow(29))
+        );
+        assert!(matches!(
+            to_base_units_u128(dec!(1000000000), 30, AmountRounding::Strict),
+            Err(AmountError::Overflow { bits: 128, .. })
+        ));
+        assert!(matches!(
+            to_base_units(dec!(1), 78, AmountRounding::Strict),
+            Err(AmountError::Overflow { bits: 256, .. })
+        ));
+    }
+}
@@ -1,7 +1,10 @@
+use crate::client_order_id::validate_for_exchange;
 use crate::config::ExchangeConfig;
 use crate::exchange::adapter::{
-    ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, Position,
+    http_client_builder, ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, OrderStatus,
+    Position,
 };
+use crate::exchange::telemetry;
 use crate::model::Side;
 use async_trait::async_trait;
 use chrono::Utc;
@@ -63,7 +66,7 @@ impl DydxAdapter {
             passphrase,
             base_url,
             address,
-            client: Client::builder()
+            client: http_client_builder(Some(config))?
                 .timeout(Duration::from_secs(10))
                 .build()
                 .map_err(|e| ExchangeError::Network(e.to_string()))?,
@@ -117,8 +120,7 @@ impl DydxAdapter {
             request = request.body(body_str);
         }
 
-        let resp = request
-            .send()
+        let resp = telemetry::send("dydx", request)
             .await
             .map_err(|e| ExchangeError::Network(e.to_string()))?;
 
@@ -142,10 +144,7 @@ impl DydxAdapter {
     async fn indexer_get(&self, path: &str) -> Result<String, ExchangeError> {
         let url = format!("{}{}", self.base_url, path);
 
-        let resp = self
-            .client
-            .get(&url)
-            .send()
+        let resp = telemetry::send("dydx", self.client.get(&url))
             .await
             .map_err(|e| ExchangeError::Network(e.to_string()))?;
 
@@ -187,6 +186,8 @@ impl ExchangeAdapter for DydxAdapter {
     }
 
     async fn place_order(&self, order: OrderRequest) -> Result<OrderResponse, ExchangeError> {
+        validate_for_exchange("dydx", &order.client_order_id)?;
+
         // dYdX v4 order placement via signed REST API
         let path = "/orders";
 
@@ -256,7 +257,8 @@ impl ExchangeAdapter for DydxAdapter {
             order_id,
             client_order_id: client_id,
             symbol: order.symbol,
-            status,
+            status: OrderStatus::normalize(&status),
+            raw_status: status,
             avg_price: None,
             executed_qty: Decimal::zero(),
             t_ack: Utc::now().timestamp_millis(),
@@ -281,7 +283,8 @@ impl ExchangeAdapter for DydxAdapter {
             order_id: order_id.to_string(),
             client_order_id: "".to_string(),
             symbol: symbol.to_string(),
-            status: "CANCELED".to_string(),
+            status: OrderStatus::Cancelled,
+            raw_status: "CANCELED".to_string(),
             avg_price: None,
             executed_qty: Decimal::zero(),
             t_ack: Utc::now().timestamp_millis(),
@@ -329,6 +332,10 @@ impl ExchangeAdapter for DydxAdapter {
             .map_err(|e| ExchangeError::Api(format!("Decimal parse: {}", e)))
     }
 
+    fn settlement_assets(&self) -> Vec<String> {
+        vec!["USDC".to_string()]
+    }
+
     fn name(&self) -> &str {
         "dydx"
     }
@@ -409,6 +416,7 @@ impl ExchangeAdapter for DydxAdapter {
                     funding_paid: Decimal::ZERO,
                     last_mark_price: None,
                     last_update_ts: Utc::now().timestamp_millis(),
+                    entry_lots: Vec::new(),
                 });
             }
         }
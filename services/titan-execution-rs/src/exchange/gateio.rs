@@ -1,7 +1,10 @@
+use crate::client_order_id::validate_for_exchange;
 use crate::config::ExchangeConfig;
 use crate::exchange::adapter::{
-    ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, Position, Side,
+    http_client_builder, ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, OrderStatus,
+    Position, Side,
 };
+use crate::exchange::telemetry;
 use async_trait::async_trait;
 use chrono::Utc;
 use hex;
@@ -49,7 +52,7 @@ impl GateIoAdapter {
             api_key,
             secret_key,
             base_url,
-            client: Client::builder()
+            client: http_client_builder(Some(config))?
                 .timeout(Duration::from_secs(10))
                 .build()
                 .map_err(|e| ExchangeError::Network(e.to_string()))?,
@@ -127,8 +130,7 @@ impl GateIoAdapter {
             );
         }
 
-        let response = request_builder
-            .send()
+        let response = telemetry::send("gateio", request_builder)
             .await
             .map_err(|e| ExchangeError::Network(e.to_string()))?;
 
@@ -163,6 +165,8 @@ impl ExchangeAdapter for GateIoAdapter {
     }
 
     async fn place_order(&self, order: OrderRequest) -> Result<OrderResponse, ExchangeError> {
+        validate_for_exchange("gateio", &order.client_order_id)?;
+
         // Endpoint: POST /api/v4/spot/orders
         let endpoint = "/api/v4/spot/orders";
 
@@ -233,19 +237,12 @@ impl ExchangeAdapter for GateIoAdapter {
             .get("status")
             .and_then(|v| v.as_str())
             .unwrap_or("open");
-        let status = match status_raw {
-            "open" => "NEW",
-            "closed" => "FILLED",
-            "cancelled" => "CANCELED",
-            _ => status_raw,
-        }
-        .to_string();
-
         Ok(OrderResponse {
             order_id,
             client_order_id: client_oid,
             symbol: order.symbol,
-            status,
+            status: OrderStatus::normalize(status_raw),
+            raw_status: status_raw.to_string(),
             executed_qty: Decimal::zero(), // Parse from 'filled_total' usually
             avg_price: None,
             t_exchange: None,
@@ -279,7 +276,8 @@ impl ExchangeAdapter for GateIoAdapter {
             order_id: order_id.to_string(),
             client_order_id: "".to_string(),
             symbol: symbol.to_string(),
-            status: status_raw.to_string().to_uppercase(),
+            status: OrderStatus::normalize(status_raw),
+            raw_status: status_raw.to_string(),
             executed_qty: Decimal::zero(),
             avg_price: None,
             t_exchange: None,
@@ -375,6 +373,7 @@ impl ExchangeAdapter for GateIoAdapter {
                 funding_paid: Decimal::ZERO,
                 last_mark_price: None,
                 last_update_ts: Utc::now().timestamp_millis(),
+                entry_lots: Vec::new(),
             });
         }
 
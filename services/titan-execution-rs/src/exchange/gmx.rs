@@ -1,6 +1,6 @@
 use crate::config::ExchangeConfig;
 use crate::exchange::adapter::{
-    ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, Position,
+    ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, OrderStatus, Position,
 };
 use crate::exchange::dex_utils;
 use async_trait::async_trait;
@@ -114,6 +114,8 @@ pub struct GmxAdapter {
     exchange_router: Address,
     order_vault: Address,
     slippage_bps: u64,
+    amount_rounding: dex_utils::AmountRounding,
+    gas_guard: dex_utils::GasGuard,
 }
 
 impl GmxAdapter {
@@ -162,6 +164,8 @@ impl GmxAdapter {
             exchange_router,
             order_vault,
             slippage_bps,
+            amount_rounding: dex_utils::resolve_amount_rounding("GMX"),
+            gas_guard: dex_utils::GasGuard::from_env("GMX")?,
         })
     }
 
@@ -211,9 +215,7 @@ impl ExchangeAdapter for GmxAdapter {
         let (market, collateral_token, is_long) = Self::resolve_market(&order.symbol)?;
 
         // Size in USD (30 decimals for GMX V2)
-        let size_usd = (order.quantity * Decimal::from(10u64.pow(30)))
-            .to_u128()
-            .unwrap_or(0);
+        let size_usd = dex_utils::to_base_units_u128(order.quantity, 30, self.amount_rounding)?;
 
         // Execution fee: Estimate based on current gas price
         // GMX V2 requires ~2M gas for keeper execution. We use 2.5M for safety.
@@ -229,6 +231,9 @@ impl ExchangeAdapter for GmxAdapter {
         let min_fee = U256::from(1_000_000_000_000_000u64);
         let execution_fee = std::cmp::max(estimated_fee, min_fee);
 
+        // Keeper execution fee is the gas cost; order.quantity is already USD size
+        self.gas_guard.check(execution_fee, Some(order.quantity))?;
+
         // Acceptable price with slippage
         // For longs: max price = infinity (we accept any price up to slippage)
         // For shorts: min price = 0
@@ -294,7 +299,7 @@ impl ExchangeAdapter for GmxAdapter {
         let pending_tx = tx
             .send()
             .await
-            .map_err(|e| ExchangeError::Network(format!("GMX createOrder failed: {}", e)))?;
+            .map_err(|e| dex_utils::contract_error("GMX createOrder", e))?;
 
         let tx_hash = format!("{:?}", pending_tx.tx_hash());
 
@@ -302,7 +307,8 @@ impl ExchangeAdapter for GmxAdapter {
             order_id: tx_hash,
             client_order_id: order.client_order_id,
             symbol: order.symbol,
-            status: "PENDING_KEEPER".to_string(),
+            status: OrderStatus::Pending,
+            raw_status: "PENDING_KEEPER".to_string(),
             executed_qty: Decimal::zero(),
             avg_price: None,
             t_exchange: None,
@@ -358,6 +364,10 @@ impl ExchangeAdapter for GmxAdapter {
         Ok(Decimal::zero())
     }
 
+    fn settlement_assets(&self) -> Vec<String> {
+        vec!["USDC".to_string(), "ETH".to_string()]
+    }
+
     fn name(&self) -> &str {
         "GMX V2"
     }
@@ -1,13 +1,18 @@
+use crate::client_order_id::validate_for_exchange;
 use crate::config::ExchangeConfig;
 use crate::exchange::adapter::{
-    ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, Position, Side,
+    http_client_builder, ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, OrderStatus,
+    Position, Side,
 };
+use crate::exchange::dex_utils;
+use crate::exchange::nonce::NonceStore;
 use async_trait::async_trait;
 use chrono::Utc;
 use ethers::prelude::*;
 use reqwest::Client;
 use rust_decimal::prelude::*;
 use serde_json::{json, Value};
+use std::sync::Arc;
 use std::time::Duration;
 use tracing::info;
 
@@ -33,7 +38,8 @@ pub struct HyperliquidAdapter {
     wallet_address: String,
     private_key: String,
     client: Client,
-    slippage_bps: u64,
+    slippage: dex_utils::SlippageCurve,
+    nonces: Arc<NonceStore>,
 }
 
 impl HyperliquidAdapter {
@@ -63,23 +69,24 @@ impl HyperliquidAdapter {
             .unwrap_or("")
             .to_string();
 
-        let slippage_bps = std::env::var("HYPERLIQUID_SLIPPAGE_BPS")
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(50u64);
-
-        let client = Client::builder()
+        let client = http_client_builder(Some(config))?
             .timeout(Duration::from_secs(10))
             .build()
             .map_err(|e| ExchangeError::Network(e.to_string()))?;
 
+        // One nonce sequence per wallet, persisted so restarts never reuse one
+        let nonce_file = std::env::var("HYPERLIQUID_NONCE_FILE")
+            .unwrap_or_else(|_| format!("hyperliquid_{}.nonce", wallet_address.to_lowercase()));
+        let nonces = NonceStore::for_wallet(&wallet_address, Some(nonce_file.into()))?;
+
         Ok(Self {
             api_url,
             info_url,
             wallet_address,
             private_key,
             client,
-            slippage_bps,
+            slippage: dex_utils::SlippageCurve::from_env("HYPERLIQUID"),
+            nonces,
         })
     }
 
@@ -236,7 +243,7 @@ impl HyperliquidAdapter {
         limit_price: Decimal,
         reduce_only: bool,
     ) -> Result<Value, ExchangeError> {
-        let nonce = Utc::now().timestamp_millis() as u64;
+        let nonce = self.nonces.next()?;
 
         // Order payload
         let order_payload = json!({
@@ -326,6 +333,8 @@ impl ExchangeAdapter for HyperliquidAdapter {
     }
 
     async fn place_order(&self, order: OrderRequest) -> Result<OrderResponse, ExchangeError> {
+        validate_for_exchange("hyperliquid", &order.client_order_id)?;
+
         let (asset, _decimals) = Self::resolve_asset(&order.symbol)?;
 
         // Determine direction from Side enum
@@ -334,11 +343,12 @@ impl ExchangeAdapter for HyperliquidAdapter {
         // Get current mid price for slippage calculation
         let mid_price = self.get_mid_price(&asset).await?;
 
-        // Apply slippage to determine limit price
+        // Apply slippage, scaled to the order's notional, to determine limit price
+        let slippage_bps = self.slippage.bps_for(Some(order.quantity * mid_price));
         let slippage_factor = if is_buy {
-            Decimal::ONE + Decimal::from(self.slippage_bps) / Decimal::from(10000u64)
+            Decimal::ONE + Decimal::from(slippage_bps) / Decimal::from(10000u64)
         } else {
-            Decimal::ONE - Decimal::from(self.slippage_bps) / Decimal::from(10000u64)
+            Decimal::ONE - Decimal::from(slippage_bps) / Decimal::from(10000u64)
         };
         let limit_price = mid_price * slippage_factor;
 
@@ -349,7 +359,7 @@ impl ExchangeAdapter for HyperliquidAdapter {
             order.quantity,
             limit_price.round_dp(2),
             mid_price,
-            self.slippage_bps
+            slippage_bps
         );
 
         let result = self
@@ -375,9 +385,9 @@ impl ExchangeAdapter for HyperliquidAdapter {
             .unwrap_or_else(|| format!("hl_{}", Utc::now().timestamp_millis()));
 
         let final_status = if status == "ok" {
-            "FILLED".to_string()
+            OrderStatus::Filled
         } else {
-            format!("ERROR: {}", status)
+            OrderStatus::Rejected
         };
 
         Ok(OrderResponse {
@@ -385,6 +395,7 @@ impl ExchangeAdapter for HyperliquidAdapter {
             client_order_id: order.client_order_id.clone(),
             symbol: order.symbol,
             status: final_status,
+            raw_status: status.to_string(),
             executed_qty: order.quantity,
             avg_price: Some(limit_price),
             t_exchange: Some(Utc::now().timestamp_millis()),
@@ -419,7 +430,7 @@ impl ExchangeAdapter for HyperliquidAdapter {
             .post(&exchange_url)
             .json(&json!({
                 "action": cancel_payload,
-                "nonce": Utc::now().timestamp_millis(),
+                "nonce": self.nonces.next()?,
                 "vaultAddress": null
             }))
             .send()
@@ -437,7 +448,8 @@ impl ExchangeAdapter for HyperliquidAdapter {
             order_id: order_id.to_string(),
             client_order_id: String::new(),
             symbol: symbol.to_string(),
-            status: "CANCELLED".to_string(),
+            status: OrderStatus::Cancelled,
+            raw_status: "CANCELLED".to_string(),
             executed_qty: Decimal::zero(),
             avg_price: None,
             t_exchange: None,
@@ -486,6 +498,10 @@ impl ExchangeAdapter for HyperliquidAdapter {
             .map_err(|e| ExchangeError::Api(format!("Balance parse error: {}", e)))
     }
 
+    fn settlement_assets(&self) -> Vec<String> {
+        vec!["USDC".to_string()]
+    }
+
     fn name(&self) -> &str {
         "Hyperliquid"
     }
@@ -570,6 +586,7 @@ impl ExchangeAdapter for HyperliquidAdapter {
                 funding_paid: Decimal::ZERO,
                 last_mark_price: None,
                 last_update_ts: Utc::now().timestamp_millis(),
+                entry_lots: Vec::new(),
             });
         }
 
@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use rust_decimal::Decimal;
+use tracing::{info, warn};
+
+use crate::context::TimeProvider;
+use crate::exchange::adapter::{
+    ExchangeAdapter, ExchangeError, FundingPayment, OrderRequest, OrderResponse,
+};
+use crate::metrics;
+use crate::model::Position;
+
+/// Default time a submitted client order id is remembered.
+pub const DEFAULT_ORDER_DEDUP_TTL_MS: u64 = 600_000;
+
+/// Makes `place_order` idempotent per client order id. A placement whose id
+/// was submitted within the TTL is first looked up on the venue, so a retry of
+/// an order that timed out but went through returns the existing order instead
+/// of placing a second one. The router retries a lost reply once through this
+/// wrapper; every other call is forwarded to the venue unchanged.
+pub struct IdempotentAdapter {
+    inner: Arc<dyn ExchangeAdapter + Send + Sync>,
+    ttl_ms: i64,
+    time: Arc<dyn TimeProvider>,
+    /// Client order id -> submission time (ms)
+    submitted: Mutex<HashMap<String, i64>>,
+}
+
+impl IdempotentAdapter {
+    pub fn new(
+        inner: Arc<dyn ExchangeAdapter + Send + Sync>,
+        ttl_ms: u64,
+        time: Arc<dyn TimeProvider>,
+    ) -> Self {
+        Self {
+            inner,
+            ttl_ms: i64::try_from(ttl_ms).unwrap_or(i64::MAX),
+            time,
+            submitted: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Remember `id` as submitted; returns whether it already was.
+    fn mark_submitted(&self, id: &str) -> bool {
+        let now = self.time.now_millis();
+        let mut submitted = self.submitted.lock();
+        submitted.retain(|_, at| now - *at <= self.ttl_ms);
+        submitted.insert(id.to_string(), now).is_some()
+    }
+
+    fn forget(&self, id: &str) {
+        self.submitted.lock().remove(id);
+    }
+}
+
+#[async_trait]
+impl ExchangeAdapter for IdempotentAdapter {
+    async fn init(&self) -> Result<(), ExchangeError> {
+        self.inner.init().await
+    }
+
+    async fn place_order(&self, order: OrderRequest) -> Result<OrderResponse, ExchangeError> {
+        let id = order.client_order_id.clone();
+        if self.mark_submitted(&id) {
+            match self.inner.get_order(&order.symbol, &id).await {
+                Ok(Some(existing)) => {
+                    info!(
+                        "♻️ [{}] Order {} already on venue as {} ({}), not re-placing",
+                        self.inner.name(),
+                        id,
+                        existing.order_id,
+                        existing.status
+                    );
+                    metrics::inc_order_dedup_hits();
+                    return Ok(existing);
+                }
+                Ok(None) => {}
+                // Without a lookup the venue's own client id check is the only guard
+                Err(ExchangeError::NotImplemented(_)) => {}
+                Err(e) => {
+                    warn!(
+                        "[{}] Cannot confirm whether {} was placed, refusing retry: {}",
+                        self.inner.name(),
+                        id,
+                        e
+                    );
+                    return Err(e);
+                }
+            }
+        }
+
+        let result = self.inner.place_order(order).await;
+        // Only a transport failure leaves the outcome unknown; anything else is definitive
+        if let Err(e) = &result {
+            if !matches!(e, ExchangeError::Network(_)) {
+                self.forget(&id);
+            }
+        }
+        result
+    }
+
+    async fn cancel_order(
+        &self,
+        symbol: &str,
+        order_id: &str,
+    ) -> Result<OrderResponse, ExchangeError> {
+        self.inner.cancel_order(symbol, order_id).await
+    }
+
+    async fn amend_order(
+        &self,
+        symbol: &str,
+        order_id: &str,
+        price: Decimal,
+    ) -> Result<OrderResponse, ExchangeError> {
+        self.inner.amend_order(symbol, order_id, price).await
+    }
+
+    async fn get_order(
+        &self,
+        symbol: &str,
+        client_order_id: &str,
+    ) -> Result<Option<OrderResponse>, ExchangeError> {
+        self.inner.get_order(symbol, client_order_id).await
+    }
+
+    async fn get_open_orders(&self) -> Result<Vec<OrderResponse>, ExchangeError> {
+        self.inner.get_open_orders().await
+    }
+
+    async fn set_dead_mans_switch(&self, window_ms: u64) -> Result<(), ExchangeError> {
+        self.inner.set_dead_mans_switch(window_ms).await
+    }
+
+    async fn set_leverage(&self, symbol: &str, leverage: u32) -> Result<(), ExchangeError> {
+        self.inner.set_leverage(symbol, leverage).await
+    }
+
+    async fn get_funding_payments(&self, since: i64) -> Result<Vec<FundingPayment>, ExchangeError> {
+        self.inner.get_funding_payments(since).await
+    }
+
+    async fn get_balance(&self, asset: &str) -> Result<Decimal, ExchangeError> {
+        self.inner.get_balance(asset).await
+    }
+
+    fn supports_exact_out(&self) -> bool {
+        self.inner.supports_exact_out()
+    }
+
+    fn supports_native_brackets(&self) -> bool {
+        self.inner.supports_native_brackets()
+    }
+
+    fn settlement_assets(&self) -> Vec<String> {
+        self.inner.settlement_assets()
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn get_positions(&self) -> Result<Vec<Position>, ExchangeError> {
+        self.inner.get_positions().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::MockClock;
+    use crate::exchange::adapter::SwapMode;
+    use crate::model::{OrderType, Side};
+    use crate::test_support::MockAdapter;
+    use rust_decimal_macros::dec;
+
+    fn order(client_order_id: &str) -> OrderRequest {
+        OrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            side: Side::Buy,
+            order_type: OrderType::Limit,
+            quantity: dec!(0.1),
+            price: Some(dec!(50000)),
+            stop_price: None,
+            client_order_id: client_order_id.to_string(),
+            reduce_only: false,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_timeout_returns_existing_order() {
+        let venue = Arc::new(
+            MockAdapter::new("binance")
+                .resting()
+                .losing_first_replies(1),
+        );
+        let adapter = IdempotentAdapter::new(
+            venue.clone(),
+            DEFAULT_ORDER_DEDUP_TTL_MS,
+            Arc::new(MockClock::new(0)),
+        );
+
+        // Placed on the venue, but the reply never arrived
+        assert!(matches!(
+            adapter.place_order(order("tx-1")).await,
+            Err(ExchangeError::Network(_))
+        ));
+
+        // Retrying with the same id finds the live order instead of duplicating it
+        let retry = adapter.place_order(order("tx-1")).await.unwrap();
+        assert_eq!(retry.order_id, "order-tx-1");
+        assert_eq!(venue.placed.lock().len(), 1);
+
+        // A new id is placed normally
+        let next = adapter.place_order(order("tx-2")).await.unwrap();
+        assert_eq!(next.client_order_id, "tx-2");
+        assert_eq!(venue.placed.lock().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_submitted_id_forgotten_after_ttl() {
+        let venue = Arc::new(
+            MockAdapter::new("binance")
+                .resting()
+                .losing_first_replies(1),
+        );
+        let clock = Arc::new(MockClock::new(1_700_000_000_000));
+        let adapter = IdempotentAdapter::new(venue.clone(), 1_000, clock.clone());
+
+        assert!(adapter.place_order(order("tx-1")).await.is_err());
+
+        // Past the TTL the id is no longer remembered, so it is placed afresh
+        clock.advance(1_001);
+        assert!(adapter.place_order(order("tx-1")).await.is_ok());
+        assert_eq!(venue.placed.lock().len(), 2);
+    }
+}
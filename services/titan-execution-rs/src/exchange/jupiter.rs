@@ -1,14 +1,158 @@
 use crate::config::ExchangeConfig;
 use crate::exchange::adapter::{
-    ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, Position,
+    http_client_builder, ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, OrderStatus,
+    Position, SwapMode,
 };
+use crate::exchange::dex_utils;
 use async_trait::async_trait;
 use chrono::Utc;
 use reqwest::Client;
 use rust_decimal::prelude::*;
 use serde_json::Value;
+use std::future::Future;
 use std::time::Duration;
-use tracing::info;
+use tracing::{info, warn};
+
+/// How long to wait for a broadcast swap to land before giving up
+const DEFAULT_CONFIRM_TIMEOUT_MS: u64 = 30_000;
+const CONFIRM_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// On-chain state of a transaction signature per `getSignatureStatuses`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SignatureStatus {
+    /// Not yet seen or only `processed`
+    Pending,
+    /// `confirmed` or `finalized` without an error
+    Confirmed,
+    /// Landed but the transaction failed (e.g. slippage exceeded)
+    Failed(String),
+}
+
+/// Parse the first entry of a `getSignatureStatuses` RPC response.
+pub fn parse_signature_status(resp: &Value) -> Result<SignatureStatus, ExchangeError> {
+    if let Some(error) = resp.get("error") {
+        return Err(ExchangeError::Api(format!(
+            "getSignatureStatuses error: {}",
+            error
+        )));
+    }
+    let status = resp
+        .get("result")
+        .and_then(|r| r.get("value"))
+        .and_then(|v| v.get(0))
+        .ok_or_else(|| ExchangeError::Api(format!("Malformed signature status: {}", resp)))?;
+    if status.is_null() {
+        return Ok(SignatureStatus::Pending);
+    }
+    if let Some(err) = status.get("err").filter(|e| !e.is_null()) {
+        return Ok(SignatureStatus::Failed(err.to_string()));
+    }
+    match status.get("confirmationStatus").and_then(|c| c.as_str()) {
+        Some("confirmed") | Some("finalized") => Ok(SignatureStatus::Confirmed),
+        _ => Ok(SignatureStatus::Pending),
+    }
+}
+
+/// Poll `fetch_status` until the signature is confirmed, fails on-chain, or `timeout`
+/// elapses. Transient RPC errors are retried until the deadline.
+pub async fn await_confirmation<F, Fut>(
+    signature: &str,
+    mut fetch_status: F,
+    poll_interval: Duration,
+    timeout: Duration,
+) -> Result<(), ExchangeError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Value, ExchangeError>>,
+{
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        match fetch_status()
+            .await
+            .and_then(|resp| parse_signature_status(&resp))
+        {
+            Ok(SignatureStatus::Confirmed) => return Ok(()),
+            Ok(SignatureStatus::Failed(err)) => {
+                return Err(ExchangeError::OrderRejected(format!(
+                    "Jupiter swap {} failed on-chain: {}",
+                    signature, err
+                )));
+            }
+            Ok(SignatureStatus::Pending) => {}
+            Err(e) => warn!("Signature status poll for {} failed: {}", signature, e),
+        }
+        if tokio::time::Instant::now() + poll_interval > deadline {
+            return Err(ExchangeError::Network(format!(
+                "Jupiter swap {} not confirmed within {}ms",
+                signature,
+                timeout.as_millis()
+            )));
+        }
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+/// Minimum acceptable output (base units) for a quoted `out_amount` at `slippage_bps`.
+pub fn min_out_amount(out_amount: u64, slippage_bps: u64) -> u64 {
+    let keep = 10_000u128.saturating_sub(slippage_bps as u128);
+    (out_amount as u128 * keep / 10_000) as u64
+}
+
+/// Maximum acceptable input (base units) for a quoted `in_amount` at `slippage_bps`.
+pub fn max_in_amount(in_amount: u64, slippage_bps: u64) -> u64 {
+    let allow = 10_000u128 + slippage_bps as u128;
+    (in_amount as u128 * allow / 10_000).min(u64::MAX as u128) as u64
+}
+
+/// Quote request URL. `amount` is in input base units for ExactIn and in
+/// output base units for ExactOut.
+pub fn quote_url(
+    api_url: &str,
+    input_mint: &str,
+    output_mint: &str,
+    amount: u64,
+    slippage_bps: u64,
+    mode: SwapMode,
+) -> String {
+    format!(
+        "{}/quote?inputMint={}&outputMint={}&amount={}&slippageBps={}&swapMode={}",
+        api_url,
+        input_mint,
+        output_mint,
+        amount,
+        slippage_bps,
+        mode.as_str()
+    )
+}
+
+/// Both legs of a quote, in base units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapQuote {
+    pub in_amount: u64,
+    pub out_amount: u64,
+}
+
+/// Parse `inAmount` / `outAmount` from a quote response.
+pub fn parse_quote(quote: &Value) -> Result<SwapQuote, ExchangeError> {
+    if quote.get("error").is_some() {
+        return Err(ExchangeError::Api(format!(
+            "Jupiter quote error: {}",
+            quote
+        )));
+    }
+    let amount = |key: &str| {
+        quote
+            .get(key)
+            .and_then(|v| v.as_str())
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|v| *v > 0)
+            .ok_or_else(|| ExchangeError::Api(format!("Jupiter quote has no {}: {}", key, quote)))
+    };
+    Ok(SwapQuote {
+        in_amount: amount("inAmount")?,
+        out_amount: amount("outAmount")?,
+    })
+}
 
 /// Jupiter Aggregator Adapter — #1 DEX on Solana
 ///
@@ -20,7 +164,8 @@ use tracing::info;
 /// - Devnet:  https://devnet.helius-rpc.com
 ///
 /// Flow: Quote → Swap API (returns serialized tx) → Deserialize →
-///       Sign with ed25519 keypair → Broadcast via Solana RPC sendTransaction.
+///       Sign with ed25519 keypair → Broadcast via Solana RPC sendTransaction →
+///       Poll getSignatureStatuses until confirmed (only then is it a fill).
 
 #[derive(Clone)]
 pub struct JupiterAdapter {
@@ -29,7 +174,9 @@ pub struct JupiterAdapter {
     wallet_pubkey: String,
     private_key: String,
     client: Client,
-    slippage_bps: u64,
+    slippage: dex_utils::SlippageCurve,
+    amount_rounding: dex_utils::AmountRounding,
+    confirm_timeout: Duration,
 }
 
 impl JupiterAdapter {
@@ -55,13 +202,12 @@ impl JupiterAdapter {
 
         let wallet_pubkey = std::env::var("JUPITER_WALLET_PUBKEY").unwrap_or_default();
 
-        // Configurable slippage (default 50 bps = 0.5%)
-        let slippage_bps = std::env::var("JUPITER_SLIPPAGE_BPS")
+        let confirm_timeout_ms = std::env::var("JUPITER_CONFIRM_TIMEOUT_MS")
             .ok()
             .and_then(|s| s.parse().ok())
-            .unwrap_or(50u64);
+            .unwrap_or(DEFAULT_CONFIRM_TIMEOUT_MS);
 
-        let client = Client::builder()
+        let client = http_client_builder(Some(config))?
             .timeout(Duration::from_secs(15))
             .build()
             .map_err(|e| ExchangeError::Network(e.to_string()))?;
@@ -72,7 +218,10 @@ impl JupiterAdapter {
             wallet_pubkey,
             private_key,
             client,
-            slippage_bps,
+            // Configurable slippage (default 50 bps = 0.5%), tighter for small swaps
+            slippage: dex_utils::SlippageCurve::from_env("JUPITER"),
+            amount_rounding: dex_utils::resolve_amount_rounding("JUPITER"),
+            confirm_timeout: Duration::from_millis(confirm_timeout_ms),
         })
     }
 
@@ -103,6 +252,17 @@ impl JupiterAdapter {
         }
     }
 
+    /// Token decimals by mint (USDC/USDT=6, everything else assumed 9 like SOL)
+    fn mint_decimals(mint: &str) -> u32 {
+        if mint == "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"
+            || mint == "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB"
+        {
+            6
+        } else {
+            9
+        }
+    }
+
     /// Decode base58 private key to 64-byte ed25519 keypair
     fn decode_keypair(base58_key: &str) -> Result<Vec<u8>, ExchangeError> {
         bs58::decode(base58_key)
@@ -227,6 +387,31 @@ impl JupiterAdapter {
 
         Ok(tx_signature)
     }
+
+    async fn fetch_signature_status(&self, signature: &str) -> Result<Value, ExchangeError> {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getSignatureStatuses",
+            "params": [[signature], { "searchTransactionHistory": true }]
+        });
+
+        let resp = self
+            .client
+            .post(&self.rpc_url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| ExchangeError::Network(format!("getSignatureStatuses failed: {}", e)))?;
+
+        let text = resp
+            .text()
+            .await
+            .map_err(|e| ExchangeError::Network(e.to_string()))?;
+
+        serde_json::from_str(&text)
+            .map_err(|e| ExchangeError::Api(format!("RPC response parse error: {}", e)))
+    }
 }
 
 #[async_trait]
@@ -273,28 +458,40 @@ impl ExchangeAdapter for JupiterAdapter {
             ));
         };
 
-        // Determine input decimals (SOL=9, USDC/USDT=6)
-        let decimals: u32 = if input_mint == "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"
-            || input_mint == "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB"
-        {
-            6
-        } else {
-            9
+        // ExactIn sizes the input token, ExactOut the output token
+        let output_decimals = Self::mint_decimals(&output_mint);
+        let decimals = match order.swap_mode {
+            SwapMode::ExactIn => Self::mint_decimals(&input_mint),
+            SwapMode::ExactOut => output_decimals,
         };
 
-        let amount = (order.quantity * Decimal::from(10u64.pow(decimals)))
-            .to_u64()
-            .unwrap_or(0);
+        let amount = dex_utils::to_base_units_u64(order.quantity, decimals, self.amount_rounding)?;
+
+        let token_in = order.symbol.split(['/', '-']).next().unwrap_or_default();
+        let slippage_bps = self.slippage.bps_for(dex_utils::order_notional_usd(
+            token_in,
+            order.swap_mode,
+            order.quantity,
+            order.price,
+        ));
 
         // Step 1: Quote with slippage
-        let quote_url = format!(
-            "{}/quote?inputMint={}&outputMint={}&amount={}&slippageBps={}",
-            self.api_url, input_mint, output_mint, amount, self.slippage_bps
+        let quote_url = quote_url(
+            &self.api_url,
+            &input_mint,
+            &output_mint,
+            amount,
+            slippage_bps,
+            order.swap_mode,
         );
 
         info!(
-            "🪐 Jupiter quote: {} → {}, amount={}, slippage={}bps",
-            input_mint, output_mint, amount, self.slippage_bps
+            "🪐 Jupiter quote: {} → {}, amount={}, mode={}, slippage={}bps",
+            input_mint,
+            output_mint,
+            amount,
+            order.swap_mode.as_str(),
+            slippage_bps
         );
 
         let quote_resp = self
@@ -312,17 +509,16 @@ impl ExchangeAdapter for JupiterAdapter {
         let quote: Value = serde_json::from_str(&quote_text)
             .map_err(|e| ExchangeError::Api(format!("Quote parse error: {}", e)))?;
 
-        if quote.get("error").is_some() {
-            return Err(ExchangeError::Api(format!(
-                "Jupiter quote error: {}",
-                quote_text
-            )));
-        }
+        let amounts = parse_quote(&quote)?;
 
-        let out_amount_str = quote
-            .get("outAmount")
-            .and_then(|v| v.as_str())
-            .unwrap_or("0");
+        // Bound the free leg on-chain so a moved market reverts the swap instead of
+        // filling at a bad price: floor the output (ExactIn) or cap the input (ExactOut)
+        let threshold = match order.swap_mode {
+            SwapMode::ExactIn => min_out_amount(amounts.out_amount, slippage_bps),
+            SwapMode::ExactOut => max_in_amount(amounts.in_amount, slippage_bps),
+        };
+        let mut quote = quote;
+        quote["otherAmountThreshold"] = Value::String(threshold.to_string());
 
         // Step 2: Get swap transaction
         let swap_body = serde_json::json!({
@@ -364,24 +560,32 @@ impl ExchangeAdapter for JupiterAdapter {
         // Step 3: Sign and broadcast the transaction
         let tx_signature = self.sign_and_broadcast(swap_tx).await?;
 
-        info!("✅ Jupiter swap broadcast: {}", tx_signature);
+        info!("📡 Jupiter swap broadcast: {}", tx_signature);
 
-        let output_decimals: u32 = if output_mint == "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"
-            || output_mint == "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB"
-        {
-            6
-        } else {
-            9
-        };
+        // Step 4: Wait for the swap to land; a reverted tx is not a fill
+        let signature = tx_signature.as_str();
+        await_confirmation(
+            signature,
+            move || self.fetch_signature_status(signature),
+            CONFIRM_POLL_INTERVAL,
+            self.confirm_timeout,
+        )
+        .await?;
+
+        info!(
+            "✅ Jupiter swap confirmed: {} (in={}, out={}, threshold={})",
+            tx_signature, amounts.in_amount, amounts.out_amount, threshold
+        );
 
-        let executed = Decimal::from_str(out_amount_str).unwrap_or(Decimal::ZERO)
-            / Decimal::from(10u64.pow(output_decimals));
+        let executed =
+            Decimal::from(amounts.out_amount) / Decimal::from(10u64.pow(output_decimals));
 
         Ok(OrderResponse {
             order_id: tx_signature,
             client_order_id: order.client_order_id,
             symbol: order.symbol,
-            status: "CONFIRMED".to_string(),
+            status: OrderStatus::Filled,
+            raw_status: "CONFIRMED".to_string(),
             executed_qty: executed,
             avg_price: None,
             t_exchange: None,
@@ -496,6 +700,14 @@ impl ExchangeAdapter for JupiterAdapter {
         Ok(Decimal::zero())
     }
 
+    fn supports_exact_out(&self) -> bool {
+        true
+    }
+
+    fn settlement_assets(&self) -> Vec<String> {
+        vec!["USDC".to_string(), "SOL".to_string()]
+    }
+
     fn name(&self) -> &str {
         "Jupiter (Solana)"
     }
@@ -504,3 +716,120 @@ impl ExchangeAdapter for JupiterAdapter {
         Ok(Vec::new())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    fn status(entry: Value) -> Value {
+        json!({ "jsonrpc": "2.0", "result": { "context": { "slot": 1 }, "value": [entry] }, "id": 1 })
+    }
+
+    fn scripted(responses: Vec<Value>) -> Mutex<VecDeque<Value>> {
+        Mutex::new(responses.into())
+    }
+
+    #[tokio::test]
+    async fn test_failed_signature_is_not_a_fill() {
+        let responses = &scripted(vec![
+            status(Value::Null),
+            status(json!({
+                "slot": 2,
+                "confirmations": 0,
+                "err": { "InstructionError": [3, { "Custom": 6001 }] },
+                "confirmationStatus": "confirmed"
+            })),
+        ]);
+        let result = await_confirmation(
+            "sig-failed",
+            move || async move { Ok(responses.lock().unwrap().pop_front().unwrap()) },
+            Duration::from_millis(1),
+            Duration::from_secs(1),
+        )
+        .await;
+        match result {
+            Err(ExchangeError::OrderRejected(msg)) => assert!(msg.contains("6001")),
+            other => panic!("expected on-chain failure, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pending_then_confirmed_signature() {
+        let responses = &scripted(vec![
+            status(Value::Null),
+            status(
+                json!({ "slot": 2, "confirmations": 0, "err": null, "confirmationStatus": "processed" }),
+            ),
+            json!({ "jsonrpc": "2.0", "error": { "code": -32005, "message": "node is behind" }, "id": 1 }),
+            status(
+                json!({ "slot": 2, "confirmations": 1, "err": null, "confirmationStatus": "confirmed" }),
+            ),
+        ]);
+        await_confirmation(
+            "sig-ok",
+            move || async move { Ok(responses.lock().unwrap().pop_front().unwrap()) },
+            Duration::from_millis(1),
+            Duration::from_secs(1),
+        )
+        .await
+        .expect("confirmed");
+        assert!(responses.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unconfirmed_signature_times_out() {
+        let result = await_confirmation(
+            "sig-stuck",
+            || async { Ok(status(Value::Null)) },
+            Duration::from_millis(5),
+            Duration::from_millis(20),
+        )
+        .await;
+        assert!(matches!(result, Err(ExchangeError::Network(_))));
+    }
+
+    #[test]
+    fn test_min_out_amount_from_slippage() {
+        assert_eq!(min_out_amount(1_000_000, 50), 995_000);
+        assert_eq!(min_out_amount(1_000_000, 0), 1_000_000);
+        assert_eq!(
+            min_out_amount(u64::MAX, 100),
+            (u64::MAX as u128 * 99 / 100) as u64
+        );
+        assert_eq!(min_out_amount(1_000, 20_000), 0);
+    }
+
+    #[test]
+    fn test_exact_out_quote_solves_for_input() {
+        let url = quote_url(
+            "https://quote-api.jup.ag/v6",
+            "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+            "So11111111111111111111111111111111111111112",
+            2_000_000_000,
+            50,
+            SwapMode::ExactOut,
+        );
+        assert!(url.contains("amount=2000000000"));
+        assert!(url.contains("swapMode=ExactOut"));
+
+        let quote = json!({
+            "inputMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+            "inAmount": "301250000",
+            "outputMint": "So11111111111111111111111111111111111111112",
+            "outAmount": "2000000000",
+            "otherAmountThreshold": "302756250",
+            "swapMode": "ExactOut",
+            "slippageBps": 50
+        });
+        let amounts = parse_quote(&quote).unwrap();
+        assert_eq!(amounts.in_amount, 301_250_000);
+        assert_eq!(amounts.out_amount, 2_000_000_000);
+        assert_eq!(max_in_amount(amounts.in_amount, 50), 302_756_250);
+
+        let no_route = json!({ "error": "Could not find any route" });
+        assert!(matches!(parse_quote(&no_route), Err(ExchangeError::Api(_))));
+    }
+}
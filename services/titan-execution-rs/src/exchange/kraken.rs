@@ -1,4 +1,8 @@
-use crate::exchange::adapter::{ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse};
+use crate::client_order_id::validate_for_exchange;
+use crate::exchange::adapter::{
+    http_client_builder, ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, OrderStatus,
+};
+use crate::exchange::telemetry;
 use crate::model::{Position, Side};
 use async_trait::async_trait;
 use base64::{engine::general_purpose, Engine as _};
@@ -58,7 +62,9 @@ impl KrakenAdapter {
             api_key,
             secret_key,
             base_url,
-            client: Client::new(),
+            client: http_client_builder(config)?
+                .build()
+                .map_err(|e| ExchangeError::Network(e.to_string()))?,
             http_limiter,
         })
     }
@@ -102,16 +108,17 @@ impl KrakenAdapter {
         let signature = self.sign(path, &nonce, &post_data)?;
         let url = format!("{}{}", self.base_url, path);
 
-        let resp = self
-            .client
-            .post(&url)
-            .header("API-Key", &self.api_key)
-            .header("API-Sign", signature)
-            .header("Content-Type", "application/x-www-form-urlencoded")
-            .body(post_data)
-            .send()
-            .await
-            .map_err(|e| ExchangeError::Network(e.to_string()))?;
+        let resp = telemetry::send(
+            "kraken",
+            self.client
+                .post(&url)
+                .header("API-Key", &self.api_key)
+                .header("API-Sign", signature)
+                .header("Content-Type", "application/x-www-form-urlencoded")
+                .body(post_data),
+        )
+        .await
+        .map_err(|e| ExchangeError::Network(e.to_string()))?;
 
         let status = resp.status();
         let text = resp
@@ -158,6 +165,8 @@ impl ExchangeAdapter for KrakenAdapter {
     }
 
     async fn place_order(&self, order: OrderRequest) -> Result<OrderResponse, ExchangeError> {
+        validate_for_exchange("kraken", &order.client_order_id)?;
+
         // path: /0/private/AddOrder
         let path = "/0/private/AddOrder";
 
@@ -215,7 +224,8 @@ impl ExchangeAdapter for KrakenAdapter {
             order_id,
             client_order_id: order.client_order_id,
             symbol: order.symbol,
-            status: "NEW".to_string(),
+            status: OrderStatus::New,
+            raw_status: "NEW".to_string(),
             avg_price: None,
             executed_qty: Decimal::ZERO,
             t_ack: Utc::now().timestamp_millis(),
@@ -239,7 +249,8 @@ impl ExchangeAdapter for KrakenAdapter {
             order_id: order_id.to_string(),
             client_order_id: "".to_string(),
             symbol: symbol.to_string(),
-            status: "CANCELED".to_string(),
+            status: OrderStatus::Cancelled,
+            raw_status: "CANCELED".to_string(),
             avg_price: None,
             executed_qty: Decimal::ZERO,
             t_ack: Utc::now().timestamp_millis(),
@@ -249,6 +260,20 @@ impl ExchangeAdapter for KrakenAdapter {
         })
     }
 
+    async fn set_dead_mans_switch(&self, window_ms: u64) -> Result<(), ExchangeError> {
+        // CancelAllOrdersAfter takes whole seconds; a timeout of 0 disables it
+        let timeout = if window_ms == 0 {
+            0
+        } else {
+            (window_ms / 1000).max(1)
+        };
+        let path = "/0/private/CancelAllOrdersAfter";
+        let params = vec![("timeout", timeout.to_string())];
+
+        let _ = self.send_private_request(path, Some(params)).await?;
+        Ok(())
+    }
+
     async fn get_balance(&self, asset: &str) -> Result<Decimal, ExchangeError> {
         // path: /0/private/Balance
         let path = "/0/private/Balance";
@@ -280,6 +305,10 @@ impl ExchangeAdapter for KrakenAdapter {
         Ok(Decimal::ZERO)
     }
 
+    fn settlement_assets(&self) -> Vec<String> {
+        vec!["USD".to_string()]
+    }
+
     fn name(&self) -> &str {
         "Kraken Spot"
     }
@@ -343,6 +372,7 @@ impl ExchangeAdapter for KrakenAdapter {
                     funding_paid: Decimal::ZERO,
                     last_mark_price: None,
                     last_update_ts: Utc::now().timestamp_millis(),
+                    entry_lots: Vec::new(),
                 });
             }
         }
@@ -1,7 +1,10 @@
+use crate::client_order_id::validate_for_exchange;
 use crate::config::ExchangeConfig;
 use crate::exchange::adapter::{
-    ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, Position, Side,
+    http_client_builder, ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, OrderStatus,
+    Position, Side,
 };
+use crate::exchange::telemetry;
 use async_trait::async_trait;
 use base64::{engine::general_purpose, Engine as _};
 use chrono::Utc;
@@ -58,7 +61,7 @@ impl KucoinAdapter {
             secret_key,
             passphrase,
             base_url,
-            client: Client::builder()
+            client: http_client_builder(Some(config))?
                 .timeout(Duration::from_secs(10))
                 .build()
                 .map_err(|e| ExchangeError::Network(e.to_string()))?,
@@ -131,8 +134,7 @@ impl KucoinAdapter {
             request_builder = request_builder.json(&b);
         }
 
-        let response = request_builder
-            .send()
+        let response = telemetry::send("kucoin", request_builder)
             .await
             .map_err(|e| ExchangeError::Network(e.to_string()))?;
 
@@ -183,6 +185,8 @@ impl ExchangeAdapter for KucoinAdapter {
     }
 
     async fn place_order(&self, order: OrderRequest) -> Result<OrderResponse, ExchangeError> {
+        validate_for_exchange("kucoin", &order.client_order_id)?;
+
         // Endpoint: POST /api/v1/orders
         let endpoint = "/api/v1/orders";
 
@@ -264,7 +268,8 @@ impl ExchangeAdapter for KucoinAdapter {
             order_id,
             client_order_id: client_oid,
             symbol: order.symbol,
-            status: "NEW".to_string(), // KuCoin returns ID immediately, status needs query or websocket
+            status: OrderStatus::New, // KuCoin returns ID immediately, status needs query or websocket
+            raw_status: "NEW".to_string(),
             executed_qty: Decimal::zero(),
             avg_price: None, // Not provided in sync response
             t_exchange: None,
@@ -290,7 +295,8 @@ impl ExchangeAdapter for KucoinAdapter {
             order_id: order_id.to_string(),
             client_order_id: "".to_string(), // Unknown on cancel request without lookup
             symbol: symbol.to_string(),
-            status: "CANCELED".to_string(),
+            status: OrderStatus::Cancelled,
+            raw_status: "CANCELED".to_string(),
             executed_qty: Decimal::zero(),
             avg_price: None,
             t_exchange: None,
@@ -405,6 +411,7 @@ impl ExchangeAdapter for KucoinAdapter {
                 funding_paid: Decimal::ZERO,
                 last_mark_price: None,
                 last_update_ts: Utc::now().timestamp_millis(),
+                entry_lots: Vec::new(),
             });
         }
 
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use tracing::{info, warn};
+
+use crate::context::{SystemTimeProvider, TimeProvider};
+use crate::exchange::adapter::{ExchangeError, OrderResponse};
+use crate::metrics;
+
+/// Default time an exchange+symbol stays untradeable after a maintenance error.
+pub const DEFAULT_MAINTENANCE_COOLDOWN_MS: i64 = 5 * 60 * 1000;
+
+/// Venue error codes / messages that mean "market closed or under maintenance"
+/// rather than a problem with the order itself. Matched case-insensitively against
+/// the adapter error text, which embeds the raw exchange response.
+const MAINTENANCE_SIGNATURES: &[(&str, &str)] = &[
+    ("binance", "\"code\":-1013,\"msg\":\"market is closed"),
+    ("binance", "api error -1013: market is closed"),
+    ("binance", "system maintenance"),
+    ("bybit", "api error 10016"),
+    ("okx", "\"code\":\"50001\""),
+    ("okx", "\"code\":\"51155\""),
+    ("kraken", "eservice:unavailable"),
+    ("kraken", "eservice:market in cancel_only mode"),
+    ("kraken", "eservice:market in post_only mode"),
+    ("kucoin", "\"code\":\"400350\""),
+    ("gateio", "\"label\":\"trade_disabled\""),
+    ("*", "market is closed"),
+    ("*", "under maintenance"),
+    ("*", "trading is halted"),
+    ("*", "503 service unavailable"),
+];
+
+/// True if the error indicates the venue is in maintenance / the market is closed.
+pub fn is_maintenance_error(exchange: &str, error: &ExchangeError) -> bool {
+    let text = match error {
+        ExchangeError::Api(msg) | ExchangeError::OrderRejected(msg) => msg.to_lowercase(),
+        ExchangeError::VenueRejected { .. } => error.to_string().to_lowercase(),
+        _ => return false,
+    };
+    let exchange = exchange.to_lowercase();
+    MAINTENANCE_SIGNATURES
+        .iter()
+        .any(|(venue, sig)| (*venue == "*" || *venue == exchange) && text.contains(sig))
+}
+
+#[derive(Debug, Clone)]
+struct UntradeableEntry {
+    until_ms: i64,
+    reason: String,
+}
+
+/// Tracks exchange+symbol pairs that are temporarily untradeable because the venue
+/// reported maintenance or a closed market. Independent of the global halt: only
+/// the affected venue/symbol is avoided, and entries clear themselves after a cooldown
+/// or as soon as an order on that pair succeeds.
+pub struct MaintenanceTracker {
+    time: Arc<dyn TimeProvider>,
+    cooldown_ms: i64,
+    untradeable: RwLock<HashMap<(String, String), UntradeableEntry>>,
+}
+
+impl Default for MaintenanceTracker {
+    fn default() -> Self {
+        Self::new(
+            Arc::new(SystemTimeProvider),
+            DEFAULT_MAINTENANCE_COOLDOWN_MS,
+        )
+    }
+}
+
+impl MaintenanceTracker {
+    pub fn new(time: Arc<dyn TimeProvider>, cooldown_ms: i64) -> Self {
+        Self {
+            time,
+            cooldown_ms,
+            untradeable: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn key(exchange: &str, symbol: &str) -> (String, String) {
+        (
+            exchange.to_lowercase(),
+            symbol.replace("/", "").replace("_", "").to_uppercase(),
+        )
+    }
+
+    pub fn mark_untradeable(&self, exchange: &str, symbol: &str, reason: &str) {
+        let until_ms = self.time.now_millis() + self.cooldown_ms;
+        warn!(
+            "🛠️ {} {} marked untradeable for {}ms: {}",
+            exchange, symbol, self.cooldown_ms, reason
+        );
+        metrics::inc_exchange_maintenance();
+        self.untradeable.write().insert(
+            Self::key(exchange, symbol),
+            UntradeableEntry {
+                until_ms,
+                reason: reason.to_string(),
+            },
+        );
+    }
+
+    /// Successful probe/order: the pair is tradeable again.
+    pub fn clear(&self, exchange: &str, symbol: &str) {
+        if self
+            .untradeable
+            .write()
+            .remove(&Self::key(exchange, symbol))
+            .is_some()
+        {
+            info!("✅ {} {} tradeable again", exchange, symbol);
+        }
+    }
+
+    /// Returns the maintenance reason if the pair is currently untradeable.
+    /// Expired entries are cleared on read.
+    pub fn untradeable_reason(&self, exchange: &str, symbol: &str) -> Option<String> {
+        let key = Self::key(exchange, symbol);
+        let now = self.time.now_millis();
+        {
+            let map = self.untradeable.read();
+            match map.get(&key) {
+                Some(entry) if entry.until_ms > now => return Some(entry.reason.clone()),
+                Some(_) => {}
+                None => return None,
+            }
+        }
+        self.untradeable.write().remove(&key);
+        info!("✅ {} {} maintenance cooldown elapsed", key.0, key.1);
+        None
+    }
+
+    pub fn is_tradeable(&self, exchange: &str, symbol: &str) -> bool {
+        self.untradeable_reason(exchange, symbol).is_none()
+    }
+
+    /// Update state from an order outcome on `exchange`/`symbol`.
+    pub fn record_result(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        result: &Result<OrderResponse, ExchangeError>,
+    ) {
+        match result {
+            Ok(_) => self.clear(exchange, symbol),
+            Err(e) if is_maintenance_error(exchange, e) => {
+                self.mark_untradeable(exchange, symbol, &e.to_string())
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::SimulatedTimeProvider;
+
+    #[test]
+    fn test_maintenance_error_codes() {
+        let binance = ExchangeError::Api(
+            "Order failed 400 Bad Request: {\"code\":-1013,\"msg\":\"Market is closed.\"}".into(),
+        );
+        assert!(is_maintenance_error("binance", &binance));
+
+        let bybit = ExchangeError::Api("Bybit API Error 10016: service is restarting".into());
+        assert!(is_maintenance_error("bybit", &bybit));
+        let structured = ExchangeError::VenueRejected {
+            code: "10016".into(),
+            message: "service is restarting".into(),
+        };
+        assert!(is_maintenance_error("bybit", &structured));
+        // Venue-specific codes only apply to their venue
+        assert!(!is_maintenance_error("okx", &bybit));
+
+        let filter = ExchangeError::Api(
+            "Order failed 400: {\"code\":-1013,\"msg\":\"Filter failure: LOT_SIZE\"}".into(),
+        );
+        assert!(!is_maintenance_error("binance", &filter));
+        assert!(!is_maintenance_error(
+            "binance",
+            &ExchangeError::Network("timeout".into())
+        ));
+    }
+
+    #[test]
+    fn test_untradeable_clears_after_cooldown_or_success() {
+        let time = Arc::new(SimulatedTimeProvider::new(0));
+        let tracker = MaintenanceTracker::new(time.clone(), 1_000);
+
+        let err = ExchangeError::Api("Kraken error: EService:Unavailable".into());
+        tracker.record_result("kraken", "BTC/USD", &Err(err));
+        assert!(!tracker.is_tradeable("kraken", "BTCUSD"));
+        assert!(tracker.is_tradeable("kraken", "ETHUSD"));
+
+        time.advance(1_001);
+        assert!(tracker.is_tradeable("kraken", "BTCUSD"));
+
+        tracker.mark_untradeable("kraken", "BTCUSD", "probe");
+        tracker.clear("kraken", "BTCUSD");
+        assert!(tracker.is_tradeable("kraken", "BTCUSD"));
+    }
+}
@@ -1,4 +1,8 @@
-use crate::exchange::adapter::{ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse};
+use crate::client_order_id::validate_for_exchange;
+use crate::exchange::adapter::{
+    http_client_builder, ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, OrderStatus,
+};
+use crate::exchange::telemetry;
 use crate::model::{OrderType, Position, Side};
 use async_trait::async_trait;
 use hex;
@@ -46,7 +50,9 @@ impl MexcAdapter {
             env::var("MEXC_BASE_URL").unwrap_or_else(|_| "https://contract.mexc.com".to_string());
 
         Ok(Self {
-            client: Client::new(),
+            client: http_client_builder(config)?
+                .build()
+                .map_err(|e| ExchangeError::Network(e.to_string()))?,
             api_key,
             api_secret,
             base_url,
@@ -101,8 +107,7 @@ impl MexcAdapter {
             request = request.body(body_str);
         }
 
-        let response = request
-            .send()
+        let response = telemetry::send("mexc", request)
             .await
             .map_err(|e| ExchangeError::Network(e.to_string()))?;
         let status = response.status();
@@ -139,10 +144,7 @@ impl ExchangeAdapter for MexcAdapter {
         // Test connection
         // /api/v1/contract/ping
         let url = format!("{}/api/v1/contract/ping", self.base_url);
-        let resp = self
-            .client
-            .get(&url)
-            .send()
+        let resp = telemetry::send("mexc", self.client.get(&url))
             .await
             .map_err(|e| ExchangeError::Network(e.to_string()))?;
         if resp.status().is_success() {
@@ -153,6 +155,8 @@ impl ExchangeAdapter for MexcAdapter {
     }
 
     async fn place_order(&self, order: OrderRequest) -> Result<OrderResponse, ExchangeError> {
+        validate_for_exchange("mexc", &order.client_order_id)?;
+
         let side = mexc_side_code(order.side, order.reduce_only);
 
         let type_code = match order.order_type {
@@ -184,7 +188,8 @@ impl ExchangeAdapter for MexcAdapter {
             order_id: resp.order_id,
             client_order_id: order.client_order_id,
             symbol: order.symbol,
-            status: "NEW".to_string(), // MEXC Async submit
+            status: OrderStatus::New, // MEXC Async submit
+            raw_status: "NEW".to_string(),
             avg_price: None,
             executed_qty: Decimal::ZERO,
             t_ack: chrono::Utc::now().timestamp_millis(),
@@ -213,7 +218,8 @@ impl ExchangeAdapter for MexcAdapter {
             order_id: order_id.to_string(),
             client_order_id: "".to_string(),
             symbol: symbol.to_string(),
-            status: "CANCELLED".to_string(),
+            status: OrderStatus::Cancelled,
+            raw_status: "CANCELLED".to_string(),
             avg_price: None,
             executed_qty: Decimal::ZERO,
             t_ack: chrono::Utc::now().timestamp_millis(),
@@ -335,6 +341,7 @@ impl ExchangeAdapter for MexcAdapter {
                     funding_paid: Decimal::ZERO,
                     last_mark_price: None,
                     last_update_ts: chrono::Utc::now().timestamp_millis(),
+                    entry_lots: Vec::new(),
                 });
             }
         }
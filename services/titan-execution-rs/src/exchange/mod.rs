@@ -11,12 +11,19 @@ pub mod dydx;
 pub mod gateio;
 pub mod gmx;
 pub mod hyperliquid;
+pub mod idempotency;
 pub mod jupiter;
 pub mod kucoin;
+pub mod maintenance;
 pub mod mexc;
+pub mod nonce;
 pub mod okx;
 pub mod pancakeswap;
+pub mod rejection;
 pub mod router;
+pub mod routing;
 pub mod sushiswap;
+pub mod telemetry;
+pub mod throttle;
 pub mod uniswap;
 // pub mod mock;
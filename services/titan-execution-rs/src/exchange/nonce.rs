@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+use crate::context::{SystemTimeProvider, TimeProvider};
+use crate::exchange::adapter::ExchangeError;
+
+/// Nonces reserved past the one that triggers a write, so the file is written
+/// once per lease rather than once per signed action (10s of millisecond nonces).
+const NONCE_LEASE: u64 = 10_000;
+
+/// Strictly increasing signing nonces for one wallet.
+///
+/// Each nonce is `max(now_ms, last + 1)`, so a burst of signed actions within
+/// the same millisecond never reuses one. `file_path` holds a ceiling above
+/// every nonce issued, replaced atomically whenever the sequence reaches it,
+/// and a restart resumes above it, so neither a restart nor a clock stepping
+/// backwards can hand out a nonce the venue has already seen.
+pub struct NonceStore {
+    last: AtomicU64,
+    /// Highest nonce the file vouches for
+    reserved: Mutex<u64>,
+    file_path: Option<PathBuf>,
+    time: Arc<dyn TimeProvider>,
+}
+
+static WALLET_NONCES: Lazy<Mutex<HashMap<String, Arc<NonceStore>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+impl NonceStore {
+    /// Nonces that are not persisted across restarts.
+    pub fn in_memory(time: Arc<dyn TimeProvider>) -> Self {
+        Self {
+            last: AtomicU64::new(0),
+            reserved: Mutex::new(0),
+            file_path: None,
+            time,
+        }
+    }
+
+    /// Nonces persisted to `file_path`, resuming above the ceiling stored there.
+    /// A missing file starts a new sequence; one that cannot be read or parsed
+    /// is refused, since starting over could reuse a nonce.
+    pub fn with_file(
+        file_path: impl Into<PathBuf>,
+        time: Arc<dyn TimeProvider>,
+    ) -> Result<Self, ExchangeError> {
+        let file_path = file_path.into();
+        let reserved = match std::fs::read_to_string(&file_path) {
+            Ok(s) => s.trim().parse().map_err(|e| {
+                ExchangeError::Configuration(format!(
+                    "Corrupt nonce file {:?} ({}); restore or remove it",
+                    file_path, e
+                ))
+            })?,
+            Err(e) if e.kind() == ErrorKind::NotFound => 0,
+            Err(e) => {
+                return Err(ExchangeError::Configuration(format!(
+                    "Cannot read nonce file {:?}: {}",
+                    file_path, e
+                )))
+            }
+        };
+        Ok(Self {
+            last: AtomicU64::new(reserved),
+            reserved: Mutex::new(reserved),
+            file_path: Some(file_path),
+            time,
+        })
+    }
+
+    /// The process-wide store for `wallet`, so every adapter signing with the
+    /// same key draws from one sequence. The first caller picks the file.
+    pub fn for_wallet(
+        wallet: &str,
+        file_path: Option<PathBuf>,
+    ) -> Result<Arc<Self>, ExchangeError> {
+        let mut wallets = WALLET_NONCES.lock();
+        let wallet = wallet.to_lowercase();
+        if let Some(store) = wallets.get(&wallet) {
+            return Ok(store.clone());
+        }
+        let time: Arc<dyn TimeProvider> = Arc::new(SystemTimeProvider);
+        let store = Arc::new(match file_path {
+            Some(path) => Self::with_file(path, time)?,
+            None => Self::in_memory(time),
+        });
+        wallets.insert(wallet, store.clone());
+        Ok(store)
+    }
+
+    /// Issue the next nonce. Only a nonce past the stored ceiling waits for the
+    /// file to be rewritten, and none is issued if that write fails: a restart
+    /// resumes from the file, so an unpersisted nonce could be issued again.
+    pub fn next(&self) -> Result<u64, ExchangeError> {
+        let now = self.time.now_millis().max(0) as u64;
+        let nonce = match self
+            .last
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |last| {
+                Some(now.max(last + 1))
+            }) {
+            Ok(last) | Err(last) => now.max(last + 1),
+        };
+        if let Some(path) = &self.file_path {
+            let mut reserved = self.reserved.lock();
+            if nonce > *reserved {
+                let ceiling = nonce + NONCE_LEASE;
+                write_atomic(path, ceiling).map_err(|e| {
+                    ExchangeError::Configuration(format!(
+                        "Cannot persist nonce ceiling to {:?}: {}",
+                        path, e
+                    ))
+                })?;
+                *reserved = ceiling;
+            }
+        }
+        Ok(nonce)
+    }
+
+    /// The most recently issued nonce (0 if none yet).
+    pub fn last(&self) -> u64 {
+        self.last.load(Ordering::SeqCst)
+    }
+}
+
+/// Replace `path` with `ceiling` via a synced temp file, so a crash leaves
+/// either the old ceiling or the new one, never a truncated file. The parent
+/// directory is synced too, or the rename itself may not survive a crash.
+fn write_atomic(path: &Path, ceiling: u64) -> std::io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    let mut file = std::fs::File::create(&tmp)?;
+    file.write_all(ceiling.to_string().as_bytes())?;
+    file.sync_all()?;
+    std::fs::rename(&tmp, path)?;
+    #[cfg(unix)]
+    {
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        std::fs::File::open(dir)?.sync_all()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::SimulatedTimeProvider;
+    use std::collections::HashSet;
+
+    const NOW_MS: i64 = 1_700_000_000_000;
+
+    #[test]
+    fn test_burst_nonces_strictly_increase_and_survive_restart() {
+        let path = std::env::temp_dir().join(format!("nonce_{}", uuid::Uuid::new_v4()));
+        let time = Arc::new(SimulatedTimeProvider::new(NOW_MS));
+        let store = Arc::new(NonceStore::with_file(&path, time.clone()).unwrap());
+
+        // The clock never moves: every nonce in the burst still has to be fresh
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let store = store.clone();
+                std::thread::spawn(move || {
+                    (0..250).map(|_| store.next().unwrap()).collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let mut seen = HashSet::new();
+        for handle in handles {
+            let nonces = handle.join().unwrap();
+            assert!(nonces.windows(2).all(|w| w[0] < w[1]));
+            seen.extend(nonces);
+        }
+        assert_eq!(seen.len(), 1000);
+        assert_eq!(store.last(), NOW_MS as u64 + 999);
+
+        // The whole burst fit in one lease: the file was written once
+        let ceiling = NOW_MS as u64 + NONCE_LEASE;
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), ceiling.to_string());
+
+        // Restart with the clock behind the last nonce: resume above the ceiling
+        time.set_time(NOW_MS - 60_000);
+        let restarted = NonceStore::with_file(&path, time.clone()).unwrap();
+        assert_eq!(restarted.next().unwrap(), ceiling + 1);
+
+        // Once the clock overtakes the sequence, nonces follow it again
+        time.set_time(NOW_MS + 20_000);
+        assert_eq!(restarted.next().unwrap(), NOW_MS as u64 + 20_000);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[test]
+    fn test_unparsable_nonce_file_refused() {
+        let path = std::env::temp_dir().join(format!("nonce_{}", uuid::Uuid::new_v4()));
+        let time = Arc::new(SimulatedTimeProvider::new(NOW_MS));
+
+        // A truncated write must not restart the sequence from zero
+        std::fs::write(&path, "").unwrap();
+        assert!(matches!(
+            NonceStore::with_file(&path, time.clone()),
+            Err(ExchangeError::Configuration(_))
+        ));
+        std::fs::write(&path, "17000000000").unwrap();
+        assert!(NonceStore::with_file(&path, time.clone()).is_ok());
+
+        // No file yet is a fresh sequence
+        std::fs::remove_file(&path).unwrap();
+        let store = NonceStore::with_file(&path, time).unwrap();
+        assert_eq!(store.next().unwrap(), NOW_MS as u64);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[test]
+    fn test_no_nonce_issued_past_unpersisted_ceiling() {
+        let dir = std::env::temp_dir().join(format!("nonce_dir_{}", uuid::Uuid::new_v4()));
+        let path = dir.join("nonce");
+        let time = Arc::new(SimulatedTimeProvider::new(NOW_MS));
+        let store = NonceStore::with_file(&path, time).unwrap();
+
+        // The ceiling can't be written, so the nonce must not be handed out
+        assert!(matches!(store.next(), Err(ExchangeError::Configuration(_))));
+
+        std::fs::create_dir_all(&dir).unwrap();
+        let nonce = store.next().unwrap();
+        assert!(nonce > NOW_MS as u64);
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            (nonce + NONCE_LEASE).to_string()
+        );
+
+        std::fs::remove_dir_all(dir).unwrap_or(());
+    }
+}
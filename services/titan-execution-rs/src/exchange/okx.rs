@@ -1,4 +1,8 @@
-use crate::exchange::adapter::{ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse};
+use crate::client_order_id::validate_for_exchange;
+use crate::exchange::adapter::{
+    http_client_builder, ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, OrderStatus,
+};
+use crate::exchange::telemetry;
 use crate::model::{Position, Side};
 use async_trait::async_trait;
 use base64::{engine::general_purpose, Engine as _};
@@ -64,7 +68,9 @@ impl OkxAdapter {
             passphrase,
             base_url,
             simulated_trading,
-            client: Client::new(),
+            client: http_client_builder(config)?
+                .build()
+                .map_err(|e| ExchangeError::Network(e.to_string()))?,
             http_limiter,
         })
     }
@@ -109,8 +115,7 @@ impl OkxAdapter {
             request = request.body(body_str);
         }
 
-        let resp = request
-            .send()
+        let resp = telemetry::send("okx", request)
             .await
             .map_err(|e| ExchangeError::Network(e.to_string()))?;
         let status = resp.status();
@@ -152,10 +157,7 @@ impl ExchangeAdapter for OkxAdapter {
         // Public endpoint doesn't need signing usually, but using signed request for connectivity check is fine
         // Or strictly public:
         let url = format!("{}{}", self.base_url, path);
-        let resp = self
-            .client
-            .get(&url)
-            .send()
+        let resp = telemetry::send("okx", self.client.get(&url))
             .await
             .map_err(|e| ExchangeError::Network(e.to_string()))?;
 
@@ -169,6 +171,8 @@ impl ExchangeAdapter for OkxAdapter {
     }
 
     async fn place_order(&self, order: OrderRequest) -> Result<OrderResponse, ExchangeError> {
+        validate_for_exchange("okx", &order.client_order_id)?;
+
         let path = "/api/v5/trade/order";
 
         // Normalize symbol: remove '/', append '-SWAP' for perps if needed?
@@ -244,7 +248,8 @@ impl ExchangeAdapter for OkxAdapter {
             order_id,
             client_order_id: order.client_order_id,
             symbol: order.symbol,
-            status: "NEW".to_string(), // Initial status
+            status: OrderStatus::New, // Initial status
+            raw_status: "NEW".to_string(),
             avg_price: None,
             executed_qty: Decimal::ZERO,
             t_ack: Utc::now().timestamp_millis(),
@@ -287,7 +292,8 @@ impl ExchangeAdapter for OkxAdapter {
             order_id: order_id.to_string(),
             client_order_id: "".to_string(),
             symbol: symbol.to_string(),
-            status: "CANCELED".to_string(),
+            status: OrderStatus::Cancelled,
+            raw_status: "CANCELED".to_string(),
             avg_price: None,
             executed_qty: Decimal::ZERO,
             t_ack: Utc::now().timestamp_millis(),
@@ -387,6 +393,7 @@ impl ExchangeAdapter for OkxAdapter {
                 funding_paid: Decimal::ZERO,
                 last_mark_price: None,
                 last_update_ts: Utc::now().timestamp_millis(),
+                entry_lots: Vec::new(),
             });
         }
 
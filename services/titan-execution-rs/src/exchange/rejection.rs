@@ -0,0 +1,163 @@
+use serde::{Deserialize, Serialize};
+
+use crate::exchange::adapter::ExchangeError;
+
+/// What a venue's native error code says about an order refusal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RejectionClass {
+    /// Not enough balance or margin for the order
+    InsufficientBalance,
+    /// Order parameters break the venue's filters (size, precision, notional)
+    InvalidOrder,
+    /// Price outside the venue's permitted band
+    PriceOutOfRange,
+    /// Reduce-only order would open or increase a position
+    ReduceOnlyConflict,
+    /// Client order id already used
+    DuplicateOrder,
+    /// API key, signature or permission problem
+    Auth,
+    /// Request rate limit hit
+    RateLimited,
+    /// Venue-side timeout, overload or clock skew; the order itself was fine
+    Transient,
+    /// Code not in the curated table
+    Unknown,
+}
+
+impl RejectionClass {
+    /// Whether resubmitting the same order later could succeed. Unknown codes
+    /// are treated as permanent so nothing is replayed blindly.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            RejectionClass::RateLimited | RejectionClass::Transient
+        )
+    }
+}
+
+/// Curated venue codes. Anything missing classifies as `Unknown`.
+const VENUE_CODES: &[(&str, &str, RejectionClass)] = &[
+    // Bybit v5 retCode
+    ("bybit", "10001", RejectionClass::InvalidOrder),
+    ("bybit", "10002", RejectionClass::Transient),
+    ("bybit", "10003", RejectionClass::Auth),
+    ("bybit", "10004", RejectionClass::Auth),
+    ("bybit", "10006", RejectionClass::RateLimited),
+    ("bybit", "10016", RejectionClass::Transient),
+    ("bybit", "110003", RejectionClass::PriceOutOfRange),
+    ("bybit", "110004", RejectionClass::InsufficientBalance),
+    ("bybit", "110007", RejectionClass::InsufficientBalance),
+    ("bybit", "110012", RejectionClass::InsufficientBalance),
+    ("bybit", "110017", RejectionClass::ReduceOnlyConflict),
+    ("bybit", "110072", RejectionClass::DuplicateOrder),
+    ("bybit", "110094", RejectionClass::InvalidOrder),
+    // Binance code
+    ("binance", "-1001", RejectionClass::Transient),
+    ("binance", "-1003", RejectionClass::RateLimited),
+    ("binance", "-1007", RejectionClass::Transient),
+    ("binance", "-1013", RejectionClass::InvalidOrder),
+    ("binance", "-1015", RejectionClass::RateLimited),
+    ("binance", "-1021", RejectionClass::Transient),
+    ("binance", "-1111", RejectionClass::InvalidOrder),
+    ("binance", "-2010", RejectionClass::InsufficientBalance),
+    ("binance", "-2014", RejectionClass::Auth),
+    ("binance", "-2015", RejectionClass::Auth),
+    ("binance", "-2019", RejectionClass::InsufficientBalance),
+    ("binance", "-2022", RejectionClass::ReduceOnlyConflict),
+    ("binance", "-4116", RejectionClass::DuplicateOrder),
+    ("binance", "-4131", RejectionClass::PriceOutOfRange),
+    ("binance", "-4164", RejectionClass::InvalidOrder),
+];
+
+/// Classify a venue's native error code.
+pub fn classify(exchange: &str, code: &str) -> RejectionClass {
+    let exchange = exchange.to_lowercase();
+    VENUE_CODES
+        .iter()
+        .find(|(venue, c, _)| *venue == exchange && *c == code)
+        .map(|(_, _, class)| *class)
+        .unwrap_or(RejectionClass::Unknown)
+}
+
+/// A venue's own refusal of one child order, kept structured for the DLQ
+/// and the execution report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VenueRejection {
+    pub exchange: String,
+    pub code: String,
+    pub message: String,
+    pub class: RejectionClass,
+    pub retryable: bool,
+}
+
+impl VenueRejection {
+    /// Structured rejection for `error`, if the venue returned a native code.
+    pub fn from_error(exchange: &str, error: &ExchangeError) -> Option<Self> {
+        let ExchangeError::VenueRejected { code, message } = error else {
+            return None;
+        };
+        let class = classify(exchange, code);
+        Some(Self {
+            exchange: exchange.to_string(),
+            code: code.clone(),
+            message: message.clone(),
+            class,
+            retryable: class.is_retryable(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rejected(code: &str, message: &str) -> ExchangeError {
+        ExchangeError::VenueRejected {
+            code: code.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_curated_codes_map_to_retry_decisions() {
+        let balance =
+            VenueRejection::from_error("bybit", &rejected("110007", "ab not enough for new order"))
+                .unwrap();
+        assert_eq!(balance.class, RejectionClass::InsufficientBalance);
+        assert!(!balance.retryable);
+
+        let throttled =
+            VenueRejection::from_error("Bybit", &rejected("10006", "Too many visits")).unwrap();
+        assert_eq!(throttled.class, RejectionClass::RateLimited);
+        assert!(throttled.retryable);
+
+        let skew = VenueRejection::from_error(
+            "binance",
+            &rejected(
+                "-1021",
+                "Timestamp for this request is outside of the recvWindow.",
+            ),
+        )
+        .unwrap();
+        assert!(skew.retryable);
+        assert_eq!(
+            classify("binance", "-2022"),
+            RejectionClass::ReduceOnlyConflict
+        );
+
+        // Codes are per venue, and unlisted ones are never replayed
+        assert_eq!(classify("binance", "110007"), RejectionClass::Unknown);
+        assert!(
+            !VenueRejection::from_error("bybit", &rejected("999999", "?"))
+                .unwrap()
+                .retryable
+        );
+        assert!(VenueRejection::from_error(
+            "bybit",
+            &ExchangeError::Network("timeout".to_string())
+        )
+        .is_none());
+    }
+}
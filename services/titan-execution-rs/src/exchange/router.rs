@@ -1,25 +1,110 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Instant;
 
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
-use tracing::{error, info, warn};
+use tracing::{error, info, warn, Instrument, Span};
 
-use crate::config::{RoutingConfig, RoutingRule};
-use crate::exchange::adapter::{ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse};
+use crate::balances::{self, BalanceError, BalanceReport};
+use crate::client_order_id::ClientOrderIdGenerator;
+use crate::config::{ExchangeConfig, RoutingConfig};
+use crate::context::{ExecutionContext, TimeProvider};
+use crate::exchange::adapter::{
+    ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, SwapMode,
+};
+use crate::exchange::idempotency::IdempotentAdapter;
+use crate::exchange::maintenance::{MaintenanceTracker, DEFAULT_MAINTENANCE_COOLDOWN_MS};
+use crate::exchange::routing::{self, RouteTarget, RoutingStrategy, VenueHealth};
+use crate::exchange::telemetry;
+use crate::exchange::throttle::{SymbolThrottle, DEFAULT_SYMBOL_MAX_QUEUE_MS};
+use crate::market_data::engine::MarketDataEngine;
 use crate::metrics;
-use crate::model::{Intent, Position};
+use crate::model::{Intent, IntentType, Position, Side};
+use crate::risk_guard::RiskGuard;
 
-#[derive(Clone)]
-struct RouteTarget {
-    name: String,
-    adapter: Arc<dyn ExchangeAdapter + Send + Sync>,
-    weight: f64,
+/// Default tolerance for top-of-book dispersion across fan-out venues.
+pub const DEFAULT_MAX_PRICE_DISPERSION_BPS: f64 = 10.0;
+
+/// Leverage a venue is set to before a position is opened there, and the
+/// range of leverage an intent may ask for on it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LeverageLimits {
+    pub target: Option<u32>,
+    pub min: Option<u32>,
+    pub max: Option<u32>,
+}
+
+impl LeverageLimits {
+    pub fn from_config(config: &ExchangeConfig) -> Self {
+        Self {
+            target: config.leverage,
+            min: config.min_leverage,
+            max: config.max_leverage,
+        }
+    }
+
+    /// The intent's `metadata.leverage`, else the venue target, must lie in
+    /// range, and the order's `notional` over account `equity` must not
+    /// exceed the max
+    fn check(
+        &self,
+        intent: &Intent,
+        notional: Decimal,
+        equity: Option<Decimal>,
+    ) -> Result<(), String> {
+        if let (Some(max), Some(equity)) = (self.max, equity.filter(|e| *e > Decimal::ZERO)) {
+            let implied = notional / equity;
+            if implied > Decimal::from(max) {
+                return Err(format!(
+                    "implied leverage {:.2}x exceeds max {}x",
+                    implied, max
+                ));
+            }
+        }
+        let Some(leverage) = requested_leverage(intent).or(self.target) else {
+            return Ok(());
+        };
+        if let Some(max) = self.max.filter(|max| leverage > *max) {
+            return Err(format!("leverage {}x exceeds max {}x", leverage, max));
+        }
+        if let Some(min) = self.min.filter(|min| leverage < *min) {
+            return Err(format!("leverage {}x below min {}x", leverage, min));
+        }
+        Ok(())
+    }
+}
+
+fn requested_leverage(intent: &Intent) -> Option<u32> {
+    intent
+        .metadata
+        .as_ref()
+        .and_then(|m| m.get("leverage"))
+        .and_then(|v| v.as_u64())
+        .and_then(|v| u32::try_from(v).ok())
 }
 
 pub struct ExecutionRouter {
     adapters: RwLock<HashMap<String, Arc<dyn ExchangeAdapter + Send + Sync>>>,
-    routing: RoutingConfig,
+    /// Swapped as a whole by `update_routing` on a config reload
+    routing: RwLock<RoutingConfig>,
+    /// Rebuilt from `routing.strategy` whenever the routing config changes,
+    /// unless a custom strategy was installed
+    strategy: RwLock<Arc<dyn RoutingStrategy>>,
+    custom_strategy: bool,
+    /// Placement latency and failures per venue, shared with the strategy
+    venue_health: Arc<VenueHealth>,
+    client_order_ids: Arc<ClientOrderIdGenerator>,
+    market_data: Option<Arc<MarketDataEngine>>,
+    maintenance: Arc<MaintenanceTracker>,
+    throttle: RwLock<Option<Arc<SymbolThrottle>>>,
+    risk_guard: Option<Arc<RiskGuard>>,
+    leverage: RwLock<HashMap<String, LeverageLimits>>,
+    /// Venue+symbol pairs already set to their venue's target leverage;
+    /// a venue's pairs are dropped when it is re-registered or its target changes
+    leverage_applied: Arc<Mutex<HashSet<(String, String)>>>,
+    time: Arc<dyn TimeProvider>,
 }
 
 impl Default for ExecutionRouter {
@@ -34,158 +119,285 @@ impl ExecutionRouter {
     }
 
     pub fn with_routing(routing: RoutingConfig) -> Self {
+        let ctx = ExecutionContext::new_system();
+        let maintenance = Arc::new(MaintenanceTracker::new(
+            ctx.time.clone(),
+            routing
+                .maintenance_cooldown_ms
+                .unwrap_or(DEFAULT_MAINTENANCE_COOLDOWN_MS),
+        ));
+        let throttle = Self::build_throttle(&routing);
+        let venue_health = Arc::new(VenueHealth::default());
         Self {
             adapters: RwLock::new(HashMap::new()),
-            routing,
+            strategy: RwLock::new(routing::build_strategy(&routing, venue_health.clone())),
+            custom_strategy: false,
+            venue_health,
+            routing: RwLock::new(routing),
+            client_order_ids: ctx.client_order_ids,
+            market_data: None,
+            maintenance,
+            throttle: RwLock::new(throttle),
+            risk_guard: None,
+            leverage: RwLock::new(HashMap::new()),
+            leverage_applied: Arc::new(Mutex::new(HashSet::new())),
+            time: ctx.time,
+        }
+    }
+
+    fn build_throttle(routing: &RoutingConfig) -> Option<Arc<SymbolThrottle>> {
+        routing.symbol_orders_per_sec.map(|rate| {
+            Arc::new(SymbolThrottle::new(
+                routing.symbol_burst.unwrap_or(1),
+                rate,
+                routing
+                    .symbol_max_queue_ms
+                    .unwrap_or(DEFAULT_SYMBOL_MAX_QUEUE_MS),
+            ))
+        })
+    }
+
+    /// Replace the routing config of the running router (weights, fan-out,
+    /// dispersion tolerance, symbol rate limits). The symbol throttle is only
+    /// rebuilt, dropping its buckets, when its limits change. Dedup and
+    /// maintenance settings are fixed at startup and keep their values.
+    pub fn update_routing(&self, routing: RoutingConfig) {
+        let mut current = self.routing.write();
+        if (
+            routing.symbol_orders_per_sec,
+            routing.symbol_burst,
+            routing.symbol_max_queue_ms,
+        ) != (
+            current.symbol_orders_per_sec,
+            current.symbol_burst,
+            current.symbol_max_queue_ms,
+        ) {
+            *self.throttle.write() = Self::build_throttle(&routing);
         }
+        *current = RoutingConfig {
+            order_dedup_ttl_ms: current.order_dedup_ttl_ms,
+            maintenance_cooldown_ms: current.maintenance_cooldown_ms,
+            ..routing
+        };
+        if !self.custom_strategy {
+            *self.strategy.write() = routing::build_strategy(&current, self.venue_health.clone());
+        }
+        info!("🔀 Routing config updated: {:?}", *current);
+    }
+
+    pub fn routing(&self) -> RoutingConfig {
+        self.routing.read().clone()
+    }
+
+    pub fn strategy_name(&self) -> &'static str {
+        self.strategy.read().name()
+    }
+
+    /// Route with a custom strategy instead of the configured built-in. It
+    /// stays in place across routing config reloads.
+    pub fn with_routing_strategy(mut self, strategy: Arc<dyn RoutingStrategy>) -> Self {
+        info!("🔀 Routing strategy: {}", strategy.name());
+        *self.strategy.write() = strategy;
+        self.custom_strategy = true;
+        self
     }
 
+    pub fn with_maintenance_tracker(mut self, maintenance: Arc<MaintenanceTracker>) -> Self {
+        self.maintenance = maintenance;
+        self
+    }
+
+    pub fn maintenance(&self) -> Arc<MaintenanceTracker> {
+        self.maintenance.clone()
+    }
+
+    /// Enable the cross-venue consistency guard using per-venue books.
+    pub fn with_market_data(mut self, market_data: Arc<MarketDataEngine>) -> Self {
+        self.market_data = Some(market_data);
+        self
+    }
+
+    /// Skip venues whose per-exchange whitelist in the live risk policy does
+    /// not include the symbol. Closes and reduce-only orders are not filtered.
+    pub fn with_risk_guard(mut self, risk_guard: Arc<RiskGuard>) -> Self {
+        self.risk_guard = Some(risk_guard);
+        self
+    }
+
+    /// Share the context's client order id generator so collisions are detected across
+    /// every order path (pipeline, flatten, fan-out children).
+    pub fn with_client_order_ids(mut self, client_order_ids: Arc<ClientOrderIdGenerator>) -> Self {
+        self.client_order_ids = client_order_ids;
+        self
+    }
+
+    /// Adapters are wrapped for idempotent placement when `order_dedup_ttl_ms` is set.
     pub fn register(&self, name: &str, adapter: Arc<dyn ExchangeAdapter + Send + Sync>) {
+        let dedup_ttl_ms = self.routing.read().order_dedup_ttl_ms;
+        let adapter: Arc<dyn ExchangeAdapter + Send + Sync> = match dedup_ttl_ms {
+            Some(ttl_ms) => Arc::new(IdempotentAdapter::new(adapter, ttl_ms, self.time.clone())),
+            None => adapter,
+        };
         let mut map = self.adapters.write();
         map.insert(name.to_lowercase(), adapter);
+        self.forget_applied_leverage(name);
         info!("🔌 Registered Adapter: {}", name);
     }
 
+    /// Leverage to set on `name` before opening positions, and the range intents must stay in
+    pub fn set_leverage_limits(&self, name: &str, limits: LeverageLimits) {
+        let previous = self.leverage.write().insert(name.to_lowercase(), limits);
+        if previous.is_some_and(|previous| previous.target != limits.target) {
+            self.forget_applied_leverage(name);
+        }
+    }
+
+    /// Set the venue's target leverage again before its next open on every symbol
+    fn forget_applied_leverage(&self, name: &str) {
+        let venue = name.to_lowercase();
+        self.leverage_applied
+            .lock()
+            .retain(|(applied_venue, _)| *applied_venue != venue);
+    }
+
     pub fn get_adapter(&self, name: &str) -> Option<Arc<dyn ExchangeAdapter + Send + Sync>> {
         let map = self.adapters.read();
         map.get(&name.to_lowercase()).cloned()
     }
 
-    fn resolve_rule(&self, source: Option<&String>) -> RoutingRule {
-        let mut rule = RoutingRule {
-            fanout: self.routing.fanout,
-            weights: self.routing.weights.clone(),
+    pub fn adapters(&self) -> Vec<(String, Arc<dyn ExchangeAdapter + Send + Sync>)> {
+        let map = self.adapters.read();
+        map.iter()
+            .map(|(name, adapter)| (name.clone(), adapter.clone()))
+            .collect()
+    }
+
+    // Determine target exchanges based on intent
+    fn resolve_routes(&self, intent: &Intent) -> Vec<RouteTarget> {
+        let strategy = self.strategy.read().clone();
+        let mut targets = {
+            let map = self.adapters.read();
+            strategy.resolve(intent, &map, self.market_data.as_deref())
         };
 
-        if let Some(source) = source {
-            if let Some(source_rule) = self.routing.per_source.get(source) {
-                rule.fanout = source_rule.fanout.or(rule.fanout);
-                if source_rule.weights.is_some() {
-                    rule.weights = source_rule.weights.clone();
-                }
+        // Safety bound on fan-out width, independent of weights
+        let max_fanout = self.routing.read().max_fanout;
+        if let Some(max_fanout) = max_fanout {
+            let max_fanout = max_fanout.max(1);
+            if targets.len() > max_fanout {
+                warn!(
+                    "⚠️ Fan-out to {} venues exceeds max_fanout {}. Keeping highest-weight venues.",
+                    targets.len(),
+                    max_fanout
+                );
+                targets.sort_by(|a, b| {
+                    b.weight
+                        .partial_cmp(&a.weight)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                });
+                targets.truncate(max_fanout);
             }
         }
 
-        if rule.weights.is_some() {
-            rule.fanout = Some(true);
-        }
-
-        rule
+        targets
     }
 
-    // Determine target exchanges based on intent
-    fn resolve_routes(&self, intent: &Intent) -> Vec<RouteTarget> {
-        let mut targets: Vec<RouteTarget> = Vec::new();
-        let map = self.adapters.read();
+    /// Before fanning an open across venues, confirm every leg trades the intent's
+    /// direction and that target venue books agree within tolerance. Otherwise collapse
+    /// to the single best venue so we never buy on one venue and sell on another at a loss.
+    fn guard_cross_venue(
+        &self,
+        intent: &Intent,
+        order_req: &OrderRequest,
+        routes: Vec<RouteTarget>,
+    ) -> Vec<RouteTarget> {
+        if routes.len() < 2 {
+            return routes;
+        }
+        let Some(market_data) = &self.market_data else {
+            return routes;
+        };
+        let intent_is_buy = match intent.intent_type {
+            IntentType::BuySetup => true,
+            IntentType::SellSetup => false,
+            _ => return routes, // Only opens are guarded; closes must reach every venue
+        };
+        let is_buy = matches!(order_req.side, Side::Buy | Side::Long);
 
-        // 1. Explicit routing wins (always 1-to-1)
-        if let Some(exchange) = &intent.exchange {
-            let ex_lower = exchange.to_lowercase();
-            if let Some(adapter) = map.get(&ex_lower) {
-                targets.push(RouteTarget {
-                    name: ex_lower,
-                    adapter: adapter.clone(),
-                    weight: 1.0,
-                });
-            } else {
-                warn!("⚠️ Explicit exchange '{}' not registered", exchange);
-            }
-            return targets;
-        }
-
-        let rule = self.resolve_rule(intent.source.as_ref());
-        // fanout is ignored by Policy A, but filtering kept for reference or future re-enablement
-        let _fanout = rule.fanout.unwrap_or(false);
-
-        // 2. Weight-based routing (explicit)
-        if let Some(weights) = rule.weights {
-            for (exchange, weight) in weights {
-                let ex_lower = exchange.to_lowercase();
-                if let Some(adapter) = map.get(&ex_lower) {
-                    targets.push(RouteTarget {
-                        name: ex_lower,
-                        adapter: adapter.clone(),
-                        weight,
-                    });
-                } else {
-                    warn!("⚠️ Weighted exchange '{}' not registered", exchange);
-                }
-            }
+        if is_buy != intent_is_buy || (intent.direction != 0 && (intent.direction > 0) != is_buy) {
+            warn!(
+                "⚠️ Net direction inconsistent for {} ({:?} vs {:?}). Collapsing fan-out.",
+                intent.signal_id, intent.intent_type, order_req.side
+            );
+            return Self::collapse_to(routes, None);
         }
 
-        // 3. Fallback to source-based routing (only if no targets yet)
-        if targets.is_empty() {
-            if let Some(source) = &intent.source {
-                match source.as_str() {
-                    "scavenger" => {
-                        if let Some(adapter) = map.get("bybit") {
-                            targets.push(RouteTarget {
-                                name: "bybit".to_string(),
-                                adapter: adapter.clone(),
-                                weight: 1.0,
-                            });
-                        }
-                        if let Some(adapter) = map.get("mexc") {
-                            targets.push(RouteTarget {
-                                name: "mexc".to_string(),
-                                adapter: adapter.clone(),
-                                weight: 1.0,
-                            });
-                        }
-                    }
-                    "hunter" | "sentinel" => {
-                        if let Some(adapter) = map.get("binance") {
-                            targets.push(RouteTarget {
-                                name: "binance".to_string(),
-                                adapter: adapter.clone(),
-                                weight: 1.0,
-                            });
-                        }
-                    }
-                    _ => {
-                        if let Some(adapter) = map.get("binance") {
-                            targets.push(RouteTarget {
-                                name: "binance".to_string(),
-                                adapter: adapter.clone(),
-                                weight: 1.0,
-                            });
-                        }
-                    }
+        // Price we would pay (ask) or receive (bid) on each venue
+        let mut quotes: Vec<(usize, Decimal)> = Vec::with_capacity(routes.len());
+        for (idx, route) in routes.iter().enumerate() {
+            let touch = market_data
+                .get_venue_ticker(&route.name, &order_req.symbol)
+                .map(|t| if is_buy { t.best_ask } else { t.best_bid })
+                .filter(|p| *p > Decimal::ZERO);
+            match touch {
+                Some(price) => quotes.push((idx, price)),
+                None => {
+                    warn!(
+                        "⚠️ No book for {} on {}. Cannot confirm dispersion, collapsing fan-out.",
+                        order_req.symbol, route.name
+                    );
+                    return Self::collapse_to(routes, None);
                 }
-            } else if let Some(adapter) = map.get("binance") {
-                targets.push(RouteTarget {
-                    name: "binance".to_string(),
-                    adapter: adapter.clone(),
-                    weight: 1.0,
-                });
             }
         }
 
-        if targets.is_empty() {
-            warn!(
-                "⚠️ No valid adapters found for routing intent {:?}",
-                intent.source
-            );
-            return targets;
+        let min = quotes.iter().map(|(_, p)| *p).min().unwrap_or_default();
+        let max = quotes.iter().map(|(_, p)| *p).max().unwrap_or_default();
+        let dispersion_bps = ((max - min) / min * Decimal::from(10_000))
+            .to_f64()
+            .unwrap_or(f64::MAX);
+        let tolerance = self
+            .routing
+            .read()
+            .max_price_dispersion_bps
+            .unwrap_or(DEFAULT_MAX_PRICE_DISPERSION_BPS);
+
+        if dispersion_bps <= tolerance {
+            return routes;
         }
 
-        // Respect Fanout Configuration
-        let fanout_allowed = rule.fanout.unwrap_or(false);
-        if !fanout_allowed && targets.len() > 1 {
-            warn!(
-                "⚠️ Fanout disabled. Clamping to single target (from {} candidates).",
-                targets.len()
-            );
-            // Sort by weight descending to pick the "best" one
-            targets.sort_by(|a, b| {
-                b.weight
-                    .partial_cmp(&a.weight)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            });
-            targets.truncate(1);
+        let best = if is_buy {
+            quotes.iter().min_by_key(|(_, p)| *p)
+        } else {
+            quotes.iter().max_by_key(|(_, p)| *p)
         }
+        .map(|(idx, _)| *idx);
 
-        targets
+        warn!(
+            "⚠️ Cross-venue dispersion {:.2}bps > {:.2}bps for {}. Collapsing fan-out.",
+            dispersion_bps, tolerance, order_req.symbol
+        );
+        Self::collapse_to(routes, best)
+    }
+
+    /// Keep a single route: the one at `best`, or the highest weight if none given.
+    fn collapse_to(mut routes: Vec<RouteTarget>, best: Option<usize>) -> Vec<RouteTarget> {
+        metrics::inc_fanout_collapsed();
+        let idx = best.unwrap_or_else(|| {
+            routes
+                .iter()
+                .enumerate()
+                .max_by(|(_, a), (_, b)| {
+                    a.weight
+                        .partial_cmp(&b.weight)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .map(|(idx, _)| idx)
+                .unwrap_or(0)
+        });
+        let mut route = routes.swap_remove(idx);
+        route.weight = 1.0;
+        vec![route]
     }
 
     pub async fn execute(
@@ -193,7 +405,72 @@ impl ExecutionRouter {
         intent: &Intent,
         order_req: OrderRequest,
     ) -> Vec<(String, OrderRequest, Result<OrderResponse, ExchangeError>)> {
-        let routes = self.resolve_routes(intent);
+        let mut routes = self.resolve_routes(intent);
+
+        // Skip venues in maintenance for this symbol or not whitelisted for it;
+        // remaining venues absorb the size
+        let mut unavailable = Vec::new();
+        routes.retain(|route| {
+            match self
+                .maintenance
+                .untradeable_reason(&route.name, &order_req.symbol)
+            {
+                Some(reason) => {
+                    warn!(
+                        "⚠️ Skipping {} for {}: untradeable (maintenance)",
+                        route.name, order_req.symbol
+                    );
+                    unavailable.push((
+                        route.name.clone(),
+                        format!(
+                            "{} untradeable for {} (maintenance): {}",
+                            route.name, order_req.symbol, reason
+                        ),
+                    ));
+                    false
+                }
+                None => true,
+            }
+        });
+
+        // Skip venues the risk policy does not whitelist this symbol on. Exits
+        // bypass it so a symbol dropped from the whitelist can still be flattened.
+        let closing = order_req.reduce_only
+            || matches!(
+                intent.intent_type,
+                IntentType::Close | IntentType::CloseLong | IntentType::CloseShort
+            );
+        if let Some(risk_guard) = self.risk_guard.as_ref().filter(|_| !closing) {
+            let policy = risk_guard.get_policy();
+            routes.retain(|route| {
+                if policy.allows_symbol_on(&route.name, &intent.symbol) {
+                    return true;
+                }
+                warn!(
+                    "⚠️ Skipping {} for {}: not whitelisted on venue",
+                    route.name, intent.symbol
+                );
+                unavailable.push((
+                    route.name.clone(),
+                    format!("{} not whitelisted on {}", intent.symbol, route.name),
+                ));
+                false
+            });
+        }
+        if routes.is_empty() && !unavailable.is_empty() {
+            return unavailable
+                .into_iter()
+                .map(|(name, reason)| {
+                    (
+                        name,
+                        order_req.clone(),
+                        Err(ExchangeError::OrderRejected(reason)),
+                    )
+                })
+                .collect();
+        }
+
+        let routes = self.guard_cross_venue(intent, &order_req, routes);
 
         let mut results = Vec::new();
         let mut handles = Vec::new();
@@ -238,27 +515,177 @@ impl ExecutionRouter {
                 continue;
             }
 
+            if req.swap_mode == SwapMode::ExactOut && !route.adapter.supports_exact_out() {
+                req.quantity = qty;
+                results.push((
+                    route.name.clone(),
+                    req,
+                    Err(ExchangeError::OrderRejected(format!(
+                        "{} does not support exact-out swaps",
+                        route.name
+                    ))),
+                ));
+                continue;
+            }
+
+            // Venues without native brackets get the bare entry; the TP
+            // ladder places its exits once the position opens
+            if !route.adapter.supports_native_brackets() {
+                req.bracket = None;
+            }
+
+            let leverage = self
+                .leverage
+                .read()
+                .get(&route.name.to_lowercase())
+                .copied()
+                .unwrap_or_default();
+            if !req.reduce_only {
+                let price = req
+                    .price
+                    .or_else(|| intent.entry_zone.first().copied())
+                    .unwrap_or(Decimal::ZERO);
+                let equity = self.risk_guard.as_ref().map(|guard| guard.equity());
+                if let Err(reason) = leverage.check(intent, qty * price, equity) {
+                    warn!("⚠️ Refusing {} on {}: {}", req.symbol, route.name, reason);
+                    req.quantity = qty;
+                    results.push((
+                        route.name.clone(),
+                        req,
+                        Err(ExchangeError::OrderRejected(format!(
+                            "{}: {}",
+                            route.name, reason
+                        ))),
+                    ));
+                    continue;
+                }
+            }
+
+            let throttle = self.throttle.read().clone();
+            if let Some(throttle) = throttle {
+                let urgent = req.reduce_only
+                    || matches!(
+                        intent.intent_type,
+                        IntentType::Close | IntentType::CloseLong | IntentType::CloseShort
+                    );
+                if let Err(e) = throttle.admit(&route.name, &req.symbol, urgent).await {
+                    req.quantity = qty;
+                    results.push((
+                        route.name.clone(),
+                        req,
+                        Err(ExchangeError::OrderRejected(e)),
+                    ));
+                    continue;
+                }
+            }
+
             req.quantity = qty;
-            req.client_order_id = format!("{}-{}-{}", req.client_order_id, route.name, idx);
+            req.client_order_id =
+                match self
+                    .client_order_ids
+                    .child(&order_req.client_order_id, &route.name, idx)
+                {
+                    Ok(id) => id,
+                    Err(e) => {
+                        error!("❌ Refusing to route to {}: {}", route.name, e);
+                        results.push((
+                            route.name.clone(),
+                            req,
+                            Err(ExchangeError::OrderRejected(e.to_string())),
+                        ));
+                        continue;
+                    }
+                };
 
             let name_clone = route.name.clone();
             let adapter = route.adapter.clone();
+            let leverage_key = (route.name.to_lowercase(), req.symbol.clone());
+            let set_leverage = leverage
+                .target
+                .filter(|_| !req.reduce_only)
+                .filter(|_| !self.leverage_applied.lock().contains(&leverage_key));
+            let leverage_applied = self.leverage_applied.clone();
+            let venue_health = self.venue_health.clone();
+            // A lost reply is retried under the same client order id only when
+            // the dedup wrapper will look the order up before re-placing it
+            let retry_lost_reply = self.routing.read().order_dedup_ttl_ms.is_some();
 
             let req_clone = req.clone();
-            let handle = tokio::spawn(async move {
-                info!(
-                    "🚀 Routing to {}: {:?} {}",
-                    name_clone, req.side, req.symbol
-                );
-                let res = adapter.place_order(req).await;
-                (name_clone, req_clone, res)
-            });
+            let span = telemetry::order_span(
+                &route.name,
+                &req.symbol,
+                &req.client_order_id,
+                req.correlation_id.as_deref(),
+            );
+            let handle = tokio::spawn(
+                async move {
+                    info!(
+                        "🚀 Routing to {}: {:?} {}",
+                        name_clone, req.side, req.symbol
+                    );
+                    // Open at the configured leverage, not the account default
+                    if let Some(leverage) = set_leverage {
+                        match adapter.set_leverage(&req.symbol, leverage).await {
+                            Ok(()) => {}
+                            // Config validation keeps targets off such venues;
+                            // warn once rather than refuse every open
+                            Err(ExchangeError::NotImplemented(e)) => warn!(
+                                "⚠️ [{}] Opening {} at the account leverage: {}",
+                                name_clone, req.symbol, e
+                            ),
+                            Err(e) => {
+                                error!(
+                                    "❌ [{}] Failed to set {}x leverage on {}: {}",
+                                    name_clone, leverage, req.symbol, e
+                                );
+                                return (name_clone, req_clone, Err(e));
+                            }
+                        }
+                        leverage_applied.lock().insert(leverage_key);
+                    }
+                    let started = Instant::now();
+                    let res = match adapter.place_order(req.clone()).await {
+                        Err(ExchangeError::Network(e)) if retry_lost_reply => {
+                            warn!(
+                                "🔁 [{}] Placement of {} lost ({}), retrying under the same id",
+                                name_clone, req.client_order_id, e
+                            );
+                            adapter.place_order(req.clone()).await
+                        }
+                        res => res,
+                    };
+                    // A reverted swap executed nothing; placing it again re-quotes
+                    // it at the current price under a fresh deadline
+                    let res = match res {
+                        Err(e) if e.is_requote_retryable() => {
+                            warn!(
+                                "🔁 [{}] Swap {} reverted ({}), re-quoting once",
+                                name_clone, req.client_order_id, e
+                            );
+                            adapter.place_order(req).await
+                        }
+                        res => res,
+                    };
+                    venue_health.record(
+                        &name_clone,
+                        started.elapsed().as_secs_f64() * 1000.0,
+                        res.as_ref().err(),
+                    );
+                    telemetry::record_order_result(&Span::current(), started, &res);
+                    (name_clone, req_clone, res)
+                }
+                .instrument(span),
+            );
             handles.push(handle);
         }
 
         for handle in handles {
             match handle.await {
-                Ok(res) => results.push(res),
+                Ok(res) => {
+                    self.maintenance
+                        .record_result(&res.0, &res.1.symbol, &res.2);
+                    results.push(res)
+                }
                 Err(e) => error!("❌ Join Error in Execution Router: {}", e),
             }
         }
@@ -276,62 +703,55 @@ impl ExecutionRouter {
             )))
         }
     }
+
+    /// Query every settlement asset on each registered venue (or just `exchange`)
+    /// and aggregate into a per-asset, per-venue report valued in USD.
+    pub async fn fetch_balances(&self, exchange: Option<&str>) -> BalanceReport {
+        let adapters: Vec<(String, Arc<dyn ExchangeAdapter + Send + Sync>)> = {
+            let map = self.adapters.read();
+            map.iter()
+                .filter(|(name, _)| exchange.is_none_or(|e| e.eq_ignore_ascii_case(name)))
+                .map(|(name, adapter)| (name.clone(), adapter.clone()))
+                .collect()
+        };
+
+        let mut rows = Vec::new();
+        let mut errors = Vec::new();
+        for (venue, adapter) in adapters {
+            for asset in adapter.settlement_assets() {
+                match adapter.get_balance(&asset).await {
+                    Ok(total) => rows.push((venue.clone(), asset, total)),
+                    Err(e) => {
+                        warn!("Balance query failed for {} {}: {}", venue, asset, e);
+                        errors.push(BalanceError {
+                            venue: venue.clone(),
+                            currency: asset,
+                            error: e.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+
+        balances::aggregate(
+            rows,
+            errors,
+            self.market_data.as_deref(),
+            self.time.now_millis(),
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::exchange::adapter::{ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse};
-    use crate::model::{OrderType, Position, Side};
-    use async_trait::async_trait;
+    use crate::exchange::adapter::{ExchangeError, OrderRequest, OrderStatus};
+    use crate::model::{OrderType, Side};
+    use crate::test_support::MockAdapter;
     use rust_decimal::Decimal;
     use rust_decimal_macros::dec;
     use std::collections::HashMap;
 
-    struct MockAdapter;
-
-    #[async_trait]
-    impl ExchangeAdapter for MockAdapter {
-        async fn init(&self) -> Result<(), ExchangeError> {
-            Ok(())
-        }
-
-        async fn place_order(&self, order: OrderRequest) -> Result<OrderResponse, ExchangeError> {
-            Ok(OrderResponse {
-                order_id: format!("order-{}", order.client_order_id),
-                client_order_id: order.client_order_id,
-                symbol: order.symbol,
-                status: "NEW".to_string(),
-                avg_price: None,
-                executed_qty: order.quantity,
-                t_exchange: None,
-                t_ack: 0,
-                fee: None,
-                fee_asset: None,
-            })
-        }
-
-        async fn cancel_order(
-            &self,
-            _symbol: &str,
-            _order_id: &str,
-        ) -> Result<OrderResponse, ExchangeError> {
-            Err(ExchangeError::Api("not implemented".to_string()))
-        }
-
-        async fn get_balance(&self, _asset: &str) -> Result<Decimal, ExchangeError> {
-            Ok(Decimal::ZERO)
-        }
-
-        fn name(&self) -> &str {
-            "mock"
-        }
-
-        async fn get_positions(&self) -> Result<Vec<Position>, ExchangeError> {
-            Ok(vec![])
-        }
-    }
-
     fn base_intent() -> Intent {
         Intent {
             signal_id: "sig-1".to_string(),
@@ -380,8 +800,8 @@ mod tests {
         };
 
         let router = ExecutionRouter::with_routing(routing);
-        router.register("binance", Arc::new(MockAdapter));
-        router.register("bybit", Arc::new(MockAdapter));
+        router.register("binance", Arc::new(MockAdapter::new("mock")));
+        router.register("bybit", Arc::new(MockAdapter::new("mock")));
 
         let intent = base_intent();
         let order_req = OrderRequest {
@@ -393,6 +813,9 @@ mod tests {
             stop_price: None,
             client_order_id: "root".to_string(),
             reduce_only: false,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
         };
 
         let results = router.execute(&intent, order_req).await;
@@ -410,6 +833,216 @@ mod tests {
         assert!(quantities.contains(&dec!(3.0)));
     }
 
+    #[tokio::test]
+    async fn test_update_routing_reweights_subsequent_orders() {
+        let weights = |binance: f64, bybit: f64| RoutingConfig {
+            fanout: Some(true),
+            weights: Some(HashMap::from([
+                ("binance".to_string(), binance),
+                ("bybit".to_string(), bybit),
+            ])),
+            ..Default::default()
+        };
+
+        let router = ExecutionRouter::with_routing(weights(0.7, 0.3));
+        router.register("binance", Arc::new(MockAdapter::new("mock")));
+        router.register("bybit", Arc::new(MockAdapter::new("mock")));
+
+        let order_req = OrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            side: Side::Buy,
+            order_type: OrderType::Market,
+            quantity: dec!(10.0),
+            price: None,
+            stop_price: None,
+            client_order_id: "root".to_string(),
+            reduce_only: false,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
+        };
+        let split = |results: Vec<(String, OrderRequest, _)>| -> HashMap<String, Decimal> {
+            results
+                .into_iter()
+                .map(|(venue, req, _)| (venue, req.quantity))
+                .collect()
+        };
+
+        let before = split(router.execute(&base_intent(), order_req.clone()).await);
+        assert_eq!(before["binance"], dec!(7.0));
+        assert_eq!(before["bybit"], dec!(3.0));
+
+        router.update_routing(weights(0.2, 0.8));
+
+        let after = split(router.execute(&base_intent(), order_req).await);
+        assert_eq!(after["binance"], dec!(2.0));
+        assert_eq!(after["bybit"], dec!(8.0));
+    }
+
+    fn market_buy(quantity: Decimal) -> OrderRequest {
+        OrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            side: Side::Buy,
+            order_type: OrderType::Market,
+            quantity,
+            price: None,
+            stop_price: None,
+            client_order_id: "root".to_string(),
+            reduce_only: false,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_leverage_set_to_configured_target_before_opening() {
+        let router = ExecutionRouter::new();
+        let adapter = Arc::new(MockAdapter::new("leverage").with_leverage());
+        router.register("bybit", adapter.clone());
+        router.set_leverage_limits(
+            "bybit",
+            LeverageLimits {
+                target: Some(5),
+                min: None,
+                max: Some(10),
+            },
+        );
+
+        let results = router.execute(&base_intent(), market_buy(dec!(1))).await;
+        assert!(results[0].2.is_ok());
+        let results = router.execute(&base_intent(), market_buy(dec!(1))).await;
+        assert!(results[0].2.is_ok());
+
+        // Set once per symbol, before the first opening order
+        assert_eq!(
+            *adapter.leverage_calls.lock(),
+            vec![("BTCUSDT".to_string(), 5)]
+        );
+        assert_eq!(adapter.placed.lock().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_leverage_above_venue_max_rejected() {
+        let router = ExecutionRouter::new();
+        let adapter = Arc::new(MockAdapter::new("leverage").with_leverage());
+        router.register("bybit", adapter.clone());
+        router.set_leverage_limits(
+            "bybit",
+            LeverageLimits {
+                target: Some(5),
+                min: None,
+                max: Some(10),
+            },
+        );
+
+        let mut intent = base_intent();
+        intent.metadata = Some(serde_json::json!({ "leverage": 20 }));
+        let results = router.execute(&intent, market_buy(dec!(1))).await;
+
+        assert_eq!(results.len(), 1);
+        assert!(matches!(
+            &results[0].2,
+            Err(ExchangeError::OrderRejected(reason)) if reason.contains("exceeds max 10x")
+        ));
+        assert!(adapter.leverage_calls.lock().is_empty());
+        assert_eq!(adapter.placed.lock().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_implied_leverage_above_venue_max_rejected() {
+        use crate::persistence::redb_store::RedbStore;
+        use crate::persistence::store::PersistenceStore;
+        use crate::persistence::wal::WalManager;
+        use crate::risk_policy::RiskPolicy;
+        use crate::shadow_state::ShadowState;
+
+        let path = format!("/tmp/test_router_leverage_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let persistence = Arc::new(PersistenceStore::new(redb, wal));
+        let state = Arc::new(RwLock::new(ShadowState::new(
+            persistence,
+            Arc::new(ExecutionContext::new_system()),
+            Some(10_000.0),
+        )));
+        let risk_guard = Arc::new(RiskGuard::new(RiskPolicy::default(), state));
+
+        let router = ExecutionRouter::new().with_risk_guard(risk_guard);
+        let adapter = Arc::new(MockAdapter::new("leverage").with_leverage());
+        router.register("bybit", adapter.clone());
+        router.set_leverage_limits(
+            "bybit",
+            LeverageLimits {
+                target: Some(5),
+                min: None,
+                max: Some(10),
+            },
+        );
+
+        let mut intent = base_intent();
+        intent.entry_zone = vec![dec!(50000)];
+        // 2.5 BTC at 50k on 10k equity is 12.5x, whatever the hint says
+        let results = router.execute(&intent, market_buy(dec!(2.5))).await;
+        assert!(matches!(
+            &results[0].2,
+            Err(ExchangeError::OrderRejected(reason))
+                if reason.contains("implied leverage 12.50x exceeds max 10x")
+        ));
+        assert_eq!(adapter.placed.lock().len(), 0);
+
+        // 1 BTC is 5x
+        let results = router.execute(&intent, market_buy(dec!(1))).await;
+        assert!(results[0].2.is_ok());
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_leverage_reapplied_after_target_change() {
+        let router = ExecutionRouter::new();
+        let adapter = Arc::new(MockAdapter::new("leverage").with_leverage());
+        router.register("bybit", adapter.clone());
+        let limits = |target| LeverageLimits {
+            target: Some(target),
+            min: None,
+            max: Some(10),
+        };
+        router.set_leverage_limits("bybit", limits(5));
+        router.execute(&base_intent(), market_buy(dec!(1))).await;
+
+        // Unchanged target: still applied
+        router.set_leverage_limits("bybit", limits(5));
+        router.execute(&base_intent(), market_buy(dec!(1))).await;
+
+        router.set_leverage_limits("bybit", limits(3));
+        router.execute(&base_intent(), market_buy(dec!(1))).await;
+
+        assert_eq!(
+            *adapter.leverage_calls.lock(),
+            vec![("BTCUSDT".to_string(), 5), ("BTCUSDT".to_string(), 3)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_venue_without_leverage_control_still_opens() {
+        let router = ExecutionRouter::new();
+        let adapter = Arc::new(MockAdapter::new("spot"));
+        router.register("kraken", adapter.clone());
+        router.set_leverage_limits(
+            "kraken",
+            LeverageLimits {
+                target: Some(2),
+                min: None,
+                max: None,
+            },
+        );
+
+        let results = router.execute(&base_intent(), market_buy(dec!(1))).await;
+        assert!(results[0].2.is_ok());
+        assert_eq!(adapter.placed.lock().len(), 1);
+    }
+
     #[tokio::test]
     async fn test_fanout_disabled_defaults_to_single_route() {
         let routing = RoutingConfig {
@@ -418,8 +1051,8 @@ mod tests {
         };
 
         let router = ExecutionRouter::with_routing(routing);
-        router.register("bybit", Arc::new(MockAdapter));
-        router.register("mexc", Arc::new(MockAdapter));
+        router.register("bybit", Arc::new(MockAdapter::new("mock")));
+        router.register("mexc", Arc::new(MockAdapter::new("mock")));
 
         let intent = base_intent();
         let order_req = OrderRequest {
@@ -431,6 +1064,9 @@ mod tests {
             stop_price: None,
             client_order_id: "root".to_string(),
             reduce_only: false,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
         };
 
         let results = router.execute(&intent, order_req).await;
@@ -453,9 +1089,9 @@ mod tests {
         };
 
         let router = ExecutionRouter::with_routing(routing);
-        router.register("A", Arc::new(MockAdapter));
-        router.register("B", Arc::new(MockAdapter));
-        router.register("C", Arc::new(MockAdapter));
+        router.register("A", Arc::new(MockAdapter::new("mock")));
+        router.register("B", Arc::new(MockAdapter::new("mock")));
+        router.register("C", Arc::new(MockAdapter::new("mock")));
 
         let intent = base_intent();
         // Use a quantity that doesn't divide cleanly: 1.0 / 3 = 0.333333...
@@ -469,6 +1105,9 @@ mod tests {
             stop_price: None,
             client_order_id: "root".to_string(),
             reduce_only: false,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
         };
 
         let results = router.execute(&intent, order_req).await;
@@ -483,4 +1122,678 @@ mod tests {
             assert!(req.quantity > Decimal::ZERO);
         }
     }
+
+    fn book(symbol: &str, bid: Decimal, ask: Decimal) -> crate::market_data::types::BookTicker {
+        crate::market_data::types::BookTicker {
+            symbol: symbol.to_string(),
+            best_bid: bid,
+            best_bid_qty: dec!(1.0),
+            best_ask: ask,
+            best_ask_qty: dec!(1.0),
+            transaction_time: 0,
+            event_time: 0,
+        }
+    }
+
+    fn guarded_router(market_data: Arc<MarketDataEngine>) -> ExecutionRouter {
+        let routing = RoutingConfig {
+            fanout: Some(true),
+            weights: Some(HashMap::from([
+                ("binance".to_string(), 0.6),
+                ("bybit".to_string(), 0.4),
+            ])),
+            max_price_dispersion_bps: Some(10.0),
+            ..Default::default()
+        };
+        let router = ExecutionRouter::with_routing(routing).with_market_data(market_data);
+        router.register("binance", Arc::new(MockAdapter::new("mock")));
+        router.register("bybit", Arc::new(MockAdapter::new("mock")));
+        router
+    }
+
+    fn open_order(side: Side) -> OrderRequest {
+        OrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            side,
+            order_type: OrderType::Market,
+            quantity: dec!(1.0),
+            price: None,
+            stop_price: None,
+            client_order_id: "root".to_string(),
+            reduce_only: false,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dispersed_venues_collapse_to_best_price() {
+        let market_data = Arc::new(MarketDataEngine::new(None));
+        // 100bps apart: bybit is the cheaper ask
+        market_data.update_venue_ticker("binance", book("BTCUSDT", dec!(50490), dec!(50500)));
+        market_data.update_venue_ticker("bybit", book("BTCUSDT", dec!(49990), dec!(50000)));
+        let router = guarded_router(market_data);
+
+        let results = router.execute(&base_intent(), open_order(Side::Buy)).await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "bybit");
+        assert_eq!(results[0].1.quantity, dec!(1.0));
+
+        // Selling into the same books goes to the higher bid
+        let mut sell_intent = base_intent();
+        sell_intent.intent_type = crate::model::IntentType::SellSetup;
+        sell_intent.direction = -1;
+        let results = router.execute(&sell_intent, open_order(Side::Sell)).await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "binance");
+    }
+
+    #[tokio::test]
+    async fn test_consistent_venues_keep_fanout() {
+        let market_data = Arc::new(MarketDataEngine::new(None));
+        // 2bps apart: within tolerance
+        market_data.update_venue_ticker("binance", book("BTCUSDT", dec!(49999), dec!(50000)));
+        market_data.update_venue_ticker("bybit", book("BTCUSDT", dec!(50009), dec!(50010)));
+        let router = guarded_router(market_data);
+
+        let results = router.execute(&base_intent(), open_order(Side::Buy)).await;
+        assert_eq!(results.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_missing_book_or_inconsistent_direction_collapses() {
+        let market_data = Arc::new(MarketDataEngine::new(None));
+        market_data.update_venue_ticker("binance", book("BTCUSDT", dec!(49999), dec!(50000)));
+        let router = guarded_router(market_data.clone());
+
+        // No bybit book: fall back to highest weight
+        let results = router.execute(&base_intent(), open_order(Side::Buy)).await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "binance");
+
+        // Buy setup routed as a sell must never fan out
+        market_data.update_venue_ticker("bybit", book("BTCUSDT", dec!(49999), dec!(50000)));
+        let results = router.execute(&base_intent(), open_order(Side::Sell)).await;
+        assert_eq!(results.len(), 1);
+    }
+
+    type CapturedSpans =
+        Arc<parking_lot::Mutex<Vec<(String, Option<String>, HashMap<String, String>)>>>;
+
+    /// Records (name, parent name, fields) for every span when it closes.
+    #[derive(Clone, Default)]
+    struct SpanCapture(CapturedSpans);
+
+    struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+    impl tracing::field::Visit for FieldVisitor<'_> {
+        fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .insert(field.name().to_string(), format!("{:?}", value));
+        }
+    }
+
+    impl<S> tracing_subscriber::Layer<S> for SpanCapture
+    where
+        S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+    {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            id: &tracing::span::Id,
+            ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            let mut fields = HashMap::new();
+            attrs.record(&mut FieldVisitor(&mut fields));
+            if let Some(span) = ctx.span(id) {
+                span.extensions_mut().insert(fields);
+            }
+        }
+
+        fn on_record(
+            &self,
+            id: &tracing::span::Id,
+            values: &tracing::span::Record<'_>,
+            ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            if let Some(span) = ctx.span(id) {
+                if let Some(fields) = span.extensions_mut().get_mut::<HashMap<String, String>>() {
+                    values.record(&mut FieldVisitor(fields));
+                }
+            }
+        }
+
+        fn on_close(&self, id: tracing::span::Id, ctx: tracing_subscriber::layer::Context<'_, S>) {
+            if let Some(span) = ctx.span(&id) {
+                let fields = span
+                    .extensions()
+                    .get::<HashMap<String, String>>()
+                    .cloned()
+                    .unwrap_or_default();
+                let parent = span.parent().map(|p| p.name().to_string());
+                self.0
+                    .lock()
+                    .push((span.name().to_string(), parent, fields));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_order_call_emits_adapter_span() {
+        use tracing::Instrument;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let capture = SpanCapture::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+        let _default = tracing::subscriber::set_default(subscriber);
+
+        let router = ExecutionRouter::new();
+        router.register("binance", Arc::new(MockAdapter::new("mock")));
+
+        let mut intent = base_intent();
+        intent.exchange = Some("binance".to_string());
+        let order_req = OrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            side: Side::Buy,
+            order_type: OrderType::Market,
+            quantity: dec!(1.0),
+            price: None,
+            stop_price: None,
+            client_order_id: "root".to_string(),
+            reduce_only: false,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
+        };
+
+        let intent_span = tracing::info_span!("execute_intent", correlation_id = "corr-1");
+        let results = router
+            .execute(&intent, order_req)
+            .instrument(intent_span)
+            .await;
+        assert_eq!(results.len(), 1);
+
+        let spans = capture.0.lock();
+        let (_, parent, fields) = spans
+            .iter()
+            .find(|(name, _, _)| name == "adapter.place_order")
+            .expect("adapter.place_order span emitted");
+
+        // Tied to the intent span carrying the correlation id
+        assert_eq!(parent.as_deref(), Some("execute_intent"));
+        assert_eq!(fields.get("exchange").map(String::as_str), Some("binance"));
+        assert_eq!(fields.get("symbol").map(String::as_str), Some("BTCUSDT"));
+        assert_eq!(
+            fields.get("client_order_id").map(String::as_str),
+            Some("root-0")
+        );
+        assert_eq!(fields.get("status").map(String::as_str), Some("NEW"));
+        assert_eq!(
+            fields.get("exchange_order_id").map(String::as_str),
+            Some("order-root-0")
+        );
+        assert!(fields.contains_key("latency_ms"));
+        assert!(!fields.contains_key("error"));
+    }
+
+    #[tokio::test]
+    async fn test_max_fanout_keeps_top_weighted_venues() {
+        let routing = RoutingConfig {
+            fanout: Some(true),
+            weights: Some(HashMap::from([
+                ("binance".to_string(), 0.5),
+                ("bybit".to_string(), 0.3),
+                ("okx".to_string(), 0.15),
+                ("mexc".to_string(), 0.05),
+            ])),
+            max_fanout: Some(2),
+            ..Default::default()
+        };
+
+        let router = ExecutionRouter::with_routing(routing);
+        for name in ["binance", "bybit", "okx", "mexc"] {
+            router.register(name, Arc::new(MockAdapter::new("mock")));
+        }
+
+        let order_req = OrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            side: Side::Buy,
+            order_type: OrderType::Market,
+            quantity: dec!(8.0),
+            price: None,
+            stop_price: None,
+            client_order_id: "root".to_string(),
+            reduce_only: false,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
+        };
+
+        let results = router.execute(&base_intent(), order_req).await;
+        assert_eq!(results.len(), 2);
+
+        let venues: HashMap<String, Decimal> = results
+            .iter()
+            .map(|(name, req, _)| (name.clone(), req.quantity))
+            .collect();
+        // Weights renormalised over the surviving venues: 0.5 / 0.8 and 0.3 / 0.8
+        assert_eq!(venues.get("binance"), Some(&dec!(5.0)));
+        assert_eq!(venues.get("bybit"), Some(&dec!(3.0)));
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_venue_avoided_until_cooldown() {
+        let routing = RoutingConfig {
+            fanout: Some(true),
+            weights: Some(HashMap::from([
+                ("binance".to_string(), 0.5),
+                ("bybit".to_string(), 0.5),
+            ])),
+            ..Default::default()
+        };
+        let time = Arc::new(crate::context::SimulatedTimeProvider::new(0));
+        let tracker = Arc::new(MaintenanceTracker::new(time.clone(), 60_000));
+        let router =
+            ExecutionRouter::with_routing(routing).with_maintenance_tracker(tracker.clone());
+        router.register(
+            "binance",
+            Arc::new(MockAdapter::new("maintenance").failing(|| {
+                ExchangeError::Api(
+                    "Order failed 400 Bad Request: {\"code\":-1013,\"msg\":\"Market is closed.\"}"
+                        .to_string(),
+                )
+            })),
+        );
+        router.register("bybit", Arc::new(MockAdapter::new("mock")));
+
+        let order = || OrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            side: Side::Buy,
+            order_type: OrderType::Market,
+            quantity: dec!(2.0),
+            price: None,
+            stop_price: None,
+            client_order_id: "root".to_string(),
+            reduce_only: false,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
+        };
+
+        // First attempt hits the maintenance error and marks binance/BTCUSDT untradeable
+        let results = router.execute(&base_intent(), order()).await;
+        assert_eq!(results.len(), 2);
+        assert!(!tracker.is_tradeable("binance", "BTCUSDT"));
+        assert!(tracker.is_tradeable("bybit", "BTCUSDT"));
+
+        // Next order routes the full size elsewhere
+        let results = router.execute(&base_intent(), order()).await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "bybit");
+        assert_eq!(results[0].1.quantity, dec!(2.0));
+
+        // Explicit routing to the untradeable venue is rejected with a clear reason
+        let mut explicit = base_intent();
+        explicit.exchange = Some("binance".to_string());
+        let results = router.execute(&explicit, order()).await;
+        assert_eq!(results.len(), 1);
+        match &results[0].2 {
+            Err(ExchangeError::OrderRejected(msg)) => assert!(msg.contains("maintenance")),
+            other => panic!("expected maintenance rejection, got {:?}", other),
+        }
+
+        // After the cooldown binance is tried again
+        time.advance(60_001);
+        let results = router.execute(&base_intent(), order()).await;
+        assert_eq!(results.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_venue_whitelist_blocks_routing_to_that_venue() {
+        use crate::persistence::redb_store::RedbStore;
+        use crate::persistence::store::PersistenceStore;
+        use crate::persistence::wal::WalManager;
+        use crate::risk_policy::RiskPolicy;
+        use crate::shadow_state::ShadowState;
+
+        let path = format!("/tmp/test_router_whitelist_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let persistence = Arc::new(PersistenceStore::new(redb, wal));
+        let state = Arc::new(RwLock::new(ShadowState::new(
+            persistence,
+            Arc::new(ExecutionContext::new_system()),
+            None,
+        )));
+        let mut policy = RiskPolicy {
+            symbol_whitelist: ["BTCUSDT"].map(String::from).into(),
+            ..Default::default()
+        };
+        policy
+            .exchange_symbol_whitelist
+            .insert("bybit".to_string(), ["ETHUSDT"].map(String::from).into());
+        let risk_guard = Arc::new(RiskGuard::new(policy, state));
+
+        let routing = RoutingConfig {
+            fanout: Some(true),
+            weights: Some(HashMap::from([
+                ("binance".to_string(), 0.5),
+                ("bybit".to_string(), 0.5),
+            ])),
+            ..Default::default()
+        };
+        let router = ExecutionRouter::with_routing(routing).with_risk_guard(risk_guard);
+        router.register("binance", Arc::new(MockAdapter::new("mock")));
+        router.register("bybit", Arc::new(MockAdapter::new("mock")));
+
+        let order = || OrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            side: Side::Buy,
+            order_type: OrderType::Market,
+            quantity: dec!(2.0),
+            price: None,
+            stop_price: None,
+            client_order_id: "root".to_string(),
+            reduce_only: false,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
+        };
+
+        // BTCUSDT passes the global whitelist but not bybit's own
+        let results = router.execute(&base_intent(), order()).await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "binance");
+        assert_eq!(results[0].1.quantity, dec!(2.0));
+
+        let mut explicit = base_intent();
+        explicit.exchange = Some("bybit".to_string());
+        let results = router.execute(&explicit, order()).await;
+        assert_eq!(results.len(), 1);
+        match &results[0].2 {
+            Err(ExchangeError::OrderRejected(msg)) => assert!(msg.contains("not whitelisted")),
+            other => panic!("expected whitelist rejection, got {:?}", other),
+        }
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_exits_routed_for_symbol_off_whitelist() {
+        use crate::persistence::redb_store::RedbStore;
+        use crate::persistence::store::PersistenceStore;
+        use crate::persistence::wal::WalManager;
+        use crate::risk_policy::RiskPolicy;
+        use crate::shadow_state::ShadowState;
+
+        let path = format!("/tmp/test_router_delisted_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let persistence = Arc::new(PersistenceStore::new(redb, wal));
+        let state = Arc::new(RwLock::new(ShadowState::new(
+            persistence,
+            Arc::new(ExecutionContext::new_system()),
+            None,
+        )));
+        let policy = RiskPolicy {
+            symbol_whitelist: ["ETHUSDT"].map(String::from).into(),
+            ..Default::default()
+        };
+        let router =
+            ExecutionRouter::new().with_risk_guard(Arc::new(RiskGuard::new(policy, state)));
+        let venue = Arc::new(MockAdapter::new("binance"));
+        router.register("binance", venue.clone());
+
+        let order = |id: &str, reduce_only: bool| OrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            side: Side::Sell,
+            order_type: OrderType::Market,
+            quantity: dec!(1.0),
+            price: None,
+            stop_price: None,
+            client_order_id: id.to_string(),
+            reduce_only,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
+        };
+        let mut intent = base_intent();
+        intent.exchange = Some("binance".to_string());
+
+        // Opening the symbol is refused
+        let results = router.execute(&intent, order("t-1", false)).await;
+        assert!(matches!(
+            &results[0].2,
+            Err(ExchangeError::OrderRejected(msg)) if msg.contains("not whitelisted")
+        ));
+
+        // Flattening it is not
+        let results = router.execute(&intent, order("t-2", true)).await;
+        assert!(results[0].2.is_ok());
+        intent.intent_type = crate::model::IntentType::Close;
+        let results = router.execute(&intent, order("t-3", false)).await;
+        assert!(results[0].2.is_ok());
+        assert_eq!(venue.placed().len(), 2);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_symbol_throttle_limits_rapid_orders_per_symbol() {
+        let routing = RoutingConfig {
+            symbol_orders_per_sec: Some(1.0),
+            symbol_max_queue_ms: Some(0),
+            ..Default::default()
+        };
+        let router = ExecutionRouter::with_routing(routing);
+        router.register("binance", Arc::new(MockAdapter::new("mock")));
+
+        let mut intent = base_intent();
+        intent.exchange = Some("binance".to_string());
+        let order = |symbol: &str, id: &str| OrderRequest {
+            symbol: symbol.to_string(),
+            side: Side::Buy,
+            order_type: OrderType::Market,
+            quantity: dec!(1.0),
+            price: None,
+            stop_price: None,
+            client_order_id: id.to_string(),
+            reduce_only: false,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
+        };
+
+        let first = router.execute(&intent, order("BTCUSDT", "t-1")).await;
+        assert!(first[0].2.is_ok());
+
+        let second = router.execute(&intent, order("BTCUSDT", "t-2")).await;
+        assert!(matches!(
+            &second[0].2,
+            Err(ExchangeError::OrderRejected(msg)) if msg.contains("throttled")
+        ));
+
+        let other = router.execute(&intent, order("ETHUSDT", "t-3")).await;
+        assert!(other[0].2.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_lost_reply_retried_without_duplicate_order() {
+        let routing = RoutingConfig {
+            order_dedup_ttl_ms: Some(60_000),
+            ..Default::default()
+        };
+        let router = ExecutionRouter::with_routing(routing);
+        let venue = Arc::new(MockAdapter::new("binance").losing_first_replies(1));
+        router.register("binance", venue.clone());
+
+        let mut intent = base_intent();
+        intent.exchange = Some("binance".to_string());
+        let req = OrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            side: Side::Buy,
+            order_type: OrderType::Market,
+            quantity: dec!(1.0),
+            price: None,
+            stop_price: None,
+            client_order_id: "t-1".to_string(),
+            reduce_only: false,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
+        };
+
+        let results = router.execute(&intent, req).await;
+        // The retry found the order the timed-out call placed
+        let response = results[0].2.as_ref().unwrap();
+        assert_eq!(response.status, OrderStatus::Filled);
+        assert_eq!(venue.placed().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_reverted_swap_requoted_once() {
+        let router = ExecutionRouter::new();
+        let venue = Arc::new(MockAdapter::new("uniswap").failing_first(1, || {
+            ExchangeError::SlippageExceeded("Too little received".to_string())
+        }));
+        router.register("uniswap", venue.clone());
+        let stuck = Arc::new(MockAdapter::new("curve").failing_first(2, || {
+            ExchangeError::DeadlinePassed("Transaction too old".to_string())
+        }));
+        router.register("curve", stuck.clone());
+        let dry = Arc::new(MockAdapter::new("sushiswap").failing_first(1, || {
+            ExchangeError::InsufficientLiquidity("no pool".to_string())
+        }));
+        router.register("sushiswap", dry.clone());
+
+        let req = |id: &str| OrderRequest {
+            symbol: "WETH/USDC".to_string(),
+            side: Side::Buy,
+            order_type: OrderType::Market,
+            quantity: dec!(1.0),
+            price: None,
+            stop_price: None,
+            client_order_id: id.to_string(),
+            reduce_only: false,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
+        };
+        let mut intent = base_intent();
+
+        intent.exchange = Some("uniswap".to_string());
+        let results = router.execute(&intent, req("t-1")).await;
+        assert!(results[0].2.is_ok());
+        assert_eq!(venue.placed().len(), 2);
+
+        // Only one re-quote per placement
+        intent.exchange = Some("curve".to_string());
+        let results = router.execute(&intent, req("t-2")).await;
+        assert!(matches!(
+            results[0].2,
+            Err(ExchangeError::DeadlinePassed(_))
+        ));
+        assert_eq!(stuck.placed().len(), 2);
+
+        // Missing liquidity needs a smaller size or another venue, not a re-quote
+        intent.exchange = Some("sushiswap".to_string());
+        let results = router.execute(&intent, req("t-3")).await;
+        assert!(matches!(
+            results[0].2,
+            Err(ExchangeError::InsufficientLiquidity(_))
+        ));
+        assert_eq!(dry.placed().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_exact_out_rejected_on_unsupported_venue() {
+        let router = ExecutionRouter::new();
+        router.register("binance", Arc::new(MockAdapter::new("mock")));
+
+        let mut intent = base_intent();
+        intent.exchange = Some("binance".to_string());
+        let req = OrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            side: Side::Buy,
+            order_type: OrderType::Market,
+            quantity: dec!(1.0),
+            price: None,
+            stop_price: None,
+            client_order_id: "t-1".to_string(),
+            reduce_only: false,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactOut,
+            bracket: None,
+        };
+
+        let results = router.execute(&intent, req).await;
+        assert_eq!(results.len(), 1);
+        assert!(matches!(
+            &results[0].2,
+            Err(ExchangeError::OrderRejected(msg)) if msg.contains("exact-out")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_balances_aggregated_across_venue_settlement_assets() {
+        let market_data = Arc::new(MarketDataEngine::new(None));
+        market_data.update_venue_ticker("bybit", book("BTCUSDT", dec!(59990), dec!(60010)));
+        let router = ExecutionRouter::new().with_market_data(market_data);
+        router.register(
+            "binance",
+            Arc::new(
+                MockAdapter::new("wallet")
+                    .with_balances(&[("USDT", dec!(1000)), ("USDC", dec!(250))]),
+            ),
+        );
+        router.register(
+            "bybit",
+            Arc::new(
+                MockAdapter::new("wallet")
+                    .with_balances(&[("USDC", dec!(500)), ("BTC", dec!(0.1))]),
+            ),
+        );
+
+        let report = router.fetch_balances(None).await;
+        assert!(report.errors.is_empty());
+
+        let currencies: Vec<&str> = report
+            .balances
+            .iter()
+            .map(|b| b.currency.as_str())
+            .collect();
+        assert_eq!(currencies, vec!["BTC", "USDC", "USDT"]);
+
+        let usdc = &report.balances[1];
+        assert_eq!(usdc.total, dec!(750));
+        let usdc_venues: Vec<(&str, Decimal)> = usdc
+            .venues
+            .iter()
+            .map(|v| (v.venue.as_str(), v.total))
+            .collect();
+        assert_eq!(
+            usdc_venues,
+            vec![("binance", dec!(250)), ("bybit", dec!(500))]
+        );
+
+        // BTC valued off the holding venue's book mid
+        let btc = &report.balances[0];
+        assert_eq!(btc.usd_value, Some(dec!(6000)));
+        assert_eq!(btc.venues[0].venue, "bybit");
+
+        assert_eq!(report.total_usd, dec!(7750));
+
+        // Venue filter
+        let bybit_only = router.fetch_balances(Some("BYBIT")).await;
+        assert_eq!(bybit_only.total_usd, dec!(6500));
+        assert!(bybit_only
+            .balances
+            .iter()
+            .all(|b| b.venues.iter().all(|v| v.venue == "bybit")));
+    }
 }
@@ -0,0 +1,738 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use tracing::warn;
+
+use crate::config::{RoutingConfig, RoutingRule, RoutingStrategyKind};
+use crate::exchange::adapter::{ExchangeAdapter, ExchangeError};
+use crate::exchange::rejection;
+use crate::market_data::engine::MarketDataEngine;
+use crate::model::{Intent, IntentType};
+
+pub type AdapterMap = HashMap<String, Arc<dyn ExchangeAdapter + Send + Sync>>;
+
+/// A venue an order is sent to and its share of the size (weights are
+/// normalized by the router).
+#[derive(Clone)]
+pub struct RouteTarget {
+    pub name: String,
+    pub adapter: Arc<dyn ExchangeAdapter + Send + Sync>,
+    pub weight: f64,
+}
+
+impl RouteTarget {
+    fn new(name: &str, adapter: &Arc<dyn ExchangeAdapter + Send + Sync>, weight: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            adapter: adapter.clone(),
+            weight,
+        }
+    }
+}
+
+/// Picks the venues an intent is sent to. The router applies maintenance,
+/// whitelist, fan-out width and cross-venue checks to whatever is returned.
+pub trait RoutingStrategy: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// `available_adapters` is keyed by lowercase venue name.
+    fn resolve(
+        &self,
+        intent: &Intent,
+        available_adapters: &AdapterMap,
+        market_data: Option<&MarketDataEngine>,
+    ) -> Vec<RouteTarget>;
+}
+
+/// The built-in strategy selected by `routing.strategy`.
+pub fn build_strategy(
+    routing: &RoutingConfig,
+    health: Arc<VenueHealth>,
+) -> Arc<dyn RoutingStrategy> {
+    let fallback = WeightedStatic::new(routing.clone());
+    match routing.strategy {
+        RoutingStrategyKind::ExplicitOnly => Arc::new(ExplicitOnly),
+        RoutingStrategyKind::WeightedStatic => Arc::new(fallback),
+        RoutingStrategyKind::BestPrice => Arc::new(BestPrice::new(fallback, health)),
+        RoutingStrategyKind::LiquidityProportional => {
+            Arc::new(LiquidityProportional::new(fallback))
+        }
+    }
+}
+
+/// The intent's `exchange`, if it names one. Explicit routing always wins and
+/// is 1-to-1; an unregistered venue yields no routes.
+fn explicit_route(intent: &Intent, adapters: &AdapterMap) -> Option<Vec<RouteTarget>> {
+    let exchange = intent.exchange.as_ref()?;
+    let ex_lower = exchange.to_lowercase();
+    match adapters.get(&ex_lower) {
+        Some(adapter) => Some(vec![RouteTarget::new(&ex_lower, adapter, 1.0)]),
+        None => {
+            warn!("⚠️ Explicit exchange '{}' not registered", exchange);
+            Some(vec![])
+        }
+    }
+}
+
+/// Side an opening intent takes at the touch; None for closes, which must go
+/// where the position lives rather than to the best book.
+fn opening_side_is_buy(intent: &Intent) -> Option<bool> {
+    match intent.intent_type {
+        IntentType::BuySetup => Some(true),
+        IntentType::SellSetup => Some(false),
+        _ => None,
+    }
+}
+
+/// Top of a venue's book on the side an order takes.
+struct Touch {
+    /// Ask for buys, bid for sells
+    price: Decimal,
+    /// Size displayed at `price`
+    qty: Decimal,
+    spread: Decimal,
+}
+
+/// The touch on each venue with a book, in venue name order.
+fn touches(
+    intent: &Intent,
+    is_buy: bool,
+    adapters: &AdapterMap,
+    market_data: &MarketDataEngine,
+) -> Vec<(String, Touch)> {
+    let mut names: Vec<&String> = adapters.keys().collect();
+    names.sort();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let ticker = market_data.get_venue_ticker(name, &intent.symbol)?;
+            let (price, qty) = if is_buy {
+                (ticker.best_ask, ticker.best_ask_qty)
+            } else {
+                (ticker.best_bid, ticker.best_bid_qty)
+            };
+            let touch = Touch {
+                price,
+                qty,
+                spread: (ticker.best_ask - ticker.best_bid).max(Decimal::ZERO),
+            };
+            (price > Decimal::ZERO).then(|| (name.clone(), touch))
+        })
+        .collect()
+}
+
+/// Consecutive venue errors after which a venue loses best-price ties
+const UNHEALTHY_AFTER_ERRORS: u32 = 1;
+/// Weight of the newest sample in the latency average
+const LATENCY_EWMA_ALPHA: f64 = 0.2;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct VenueStats {
+    /// Moving average of order placement round trips
+    pub latency_ms: Option<f64>,
+    /// Network/API failures since the last successful placement
+    pub consecutive_errors: u32,
+}
+
+impl VenueStats {
+    pub fn is_healthy(&self) -> bool {
+        self.consecutive_errors < UNHEALTHY_AFTER_ERRORS
+    }
+}
+
+/// Placement latency and failures per venue, recorded by the router and used
+/// to break best-price ties.
+#[derive(Debug, Default)]
+pub struct VenueHealth {
+    stats: RwLock<HashMap<String, VenueStats>>,
+}
+
+impl VenueHealth {
+    /// Record a placement round trip and its error, if it failed.
+    pub fn record(&self, venue: &str, latency_ms: f64, error: Option<&ExchangeError>) {
+        let mut stats = self.stats.write();
+        let entry = stats.entry(venue.to_lowercase()).or_default();
+        entry.latency_ms = Some(match entry.latency_ms {
+            Some(avg) => avg + LATENCY_EWMA_ALPHA * (latency_ms - avg),
+            None => latency_ms,
+        });
+        match error {
+            None => entry.consecutive_errors = 0,
+            // The venue answered; a rejected order says nothing about its health
+            Some(
+                ExchangeError::OrderRejected(_)
+                | ExchangeError::SlippageExceeded(_)
+                | ExchangeError::InsufficientLiquidity(_)
+                | ExchangeError::DeadlinePassed(_),
+            ) => {}
+            // Only a venue-side timeout, overload or throttle is a health signal;
+            // balance, filter and auth refusals would follow the order anywhere
+            Some(ExchangeError::VenueRejected { code, .. }) => {
+                if rejection::classify(venue, code).is_retryable() {
+                    entry.consecutive_errors += 1;
+                }
+            }
+            Some(_) => entry.consecutive_errors += 1,
+        }
+    }
+
+    pub fn get(&self, venue: &str) -> VenueStats {
+        self.stats
+            .read()
+            .get(&venue.to_lowercase())
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// Routes only intents that name their exchange.
+pub struct ExplicitOnly;
+
+impl RoutingStrategy for ExplicitOnly {
+    fn name(&self) -> &'static str {
+        "explicit_only"
+    }
+
+    fn resolve(
+        &self,
+        intent: &Intent,
+        available_adapters: &AdapterMap,
+        _market_data: Option<&MarketDataEngine>,
+    ) -> Vec<RouteTarget> {
+        explicit_route(intent, available_adapters).unwrap_or_else(|| {
+            warn!(
+                "⚠️ No exchange on intent {} under explicit-only routing",
+                intent.signal_id
+            );
+            vec![]
+        })
+    }
+}
+
+/// Configured weights (global or per source), else the venue each signal
+/// source trades on by default.
+pub struct WeightedStatic {
+    routing: RoutingConfig,
+}
+
+impl WeightedStatic {
+    pub fn new(routing: RoutingConfig) -> Self {
+        Self { routing }
+    }
+
+    fn resolve_rule(&self, source: Option<&String>) -> RoutingRule {
+        let mut rule = RoutingRule {
+            fanout: self.routing.fanout,
+            weights: self.routing.weights.clone(),
+        };
+
+        if let Some(source) = source {
+            if let Some(source_rule) = self.routing.per_source.get(source) {
+                rule.fanout = source_rule.fanout.or(rule.fanout);
+                if source_rule.weights.is_some() {
+                    rule.weights = source_rule.weights.clone();
+                }
+            }
+        }
+
+        if rule.weights.is_some() {
+            rule.fanout = Some(true);
+        }
+
+        rule
+    }
+}
+
+impl RoutingStrategy for WeightedStatic {
+    fn name(&self) -> &'static str {
+        "weighted_static"
+    }
+
+    fn resolve(
+        &self,
+        intent: &Intent,
+        available_adapters: &AdapterMap,
+        _market_data: Option<&MarketDataEngine>,
+    ) -> Vec<RouteTarget> {
+        if let Some(targets) = explicit_route(intent, available_adapters) {
+            return targets;
+        }
+        let map = available_adapters;
+        let mut targets: Vec<RouteTarget> = Vec::new();
+
+        let rule = self.resolve_rule(intent.source.as_ref());
+
+        // Weight-based routing (explicit)
+        if let Some(weights) = &rule.weights {
+            for (exchange, weight) in weights {
+                let ex_lower = exchange.to_lowercase();
+                if let Some(adapter) = map.get(&ex_lower) {
+                    targets.push(RouteTarget::new(&ex_lower, adapter, *weight));
+                } else {
+                    warn!("⚠️ Weighted exchange '{}' not registered", exchange);
+                }
+            }
+        }
+
+        // Fallback to source-based routing (only if no targets yet)
+        if targets.is_empty() {
+            let venues: &[&str] = match intent.source.as_deref() {
+                Some("scavenger") => &["bybit", "mexc"],
+                _ => &["binance"],
+            };
+            for venue in venues {
+                if let Some(adapter) = map.get(*venue) {
+                    targets.push(RouteTarget::new(venue, adapter, 1.0));
+                }
+            }
+        }
+
+        if targets.is_empty() {
+            warn!(
+                "⚠️ No valid adapters found for routing intent {:?}",
+                intent.source
+            );
+            return targets;
+        }
+
+        // Respect Fanout Configuration
+        let fanout_allowed = rule.fanout.unwrap_or(false);
+        if !fanout_allowed && targets.len() > 1 {
+            warn!(
+                "⚠️ Fanout disabled. Clamping to single target (from {} candidates).",
+                targets.len()
+            );
+            // Sort by weight descending to pick the "best" one
+            targets.sort_by(|a, b| {
+                b.weight
+                    .partial_cmp(&a.weight)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            targets.truncate(1);
+        }
+
+        targets
+    }
+}
+
+/// Sends opens to the single venue with the best effective price: the touch,
+/// worsened by the spread for size beyond what is displayed there, after the
+/// venue's taker fee. Ties go to healthy venues, then the lowest latency.
+/// Closes, and opens without any venue book, route as `WeightedStatic`.
+pub struct BestPrice {
+    fallback: WeightedStatic,
+    fee_bps: HashMap<String, f64>,
+    health: Arc<VenueHealth>,
+}
+
+impl BestPrice {
+    pub fn new(fallback: WeightedStatic, health: Arc<VenueHealth>) -> Self {
+        let fee_bps = fallback
+            .routing
+            .fee_bps
+            .iter()
+            .map(|(venue, bps)| (venue.to_lowercase(), *bps))
+            .collect();
+        Self {
+            fallback,
+            fee_bps,
+            health,
+        }
+    }
+
+    /// What filling `size` at `touch` on `venue` is expected to cost per unit
+    /// (buys) or yield per unit (sells), fees included.
+    fn effective_price(&self, venue: &str, touch: &Touch, size: Decimal, is_buy: bool) -> Decimal {
+        let slippage = if size > touch.qty && size > Decimal::ZERO {
+            // The part not shown at the touch is assumed to fill a spread away
+            touch.spread * (size - touch.qty) / size
+        } else {
+            Decimal::ZERO
+        };
+        let fee = self
+            .fee_bps
+            .get(venue)
+            .and_then(|bps| Decimal::from_f64(*bps))
+            .unwrap_or_default()
+            / Decimal::from(10_000);
+        if is_buy {
+            (touch.price + slippage) * (Decimal::ONE + fee)
+        } else {
+            (touch.price - slippage) * (Decimal::ONE - fee)
+        }
+    }
+}
+
+impl RoutingStrategy for BestPrice {
+    fn name(&self) -> &'static str {
+        "best_price"
+    }
+
+    fn resolve(
+        &self,
+        intent: &Intent,
+        available_adapters: &AdapterMap,
+        market_data: Option<&MarketDataEngine>,
+    ) -> Vec<RouteTarget> {
+        if let Some(targets) = explicit_route(intent, available_adapters) {
+            return targets;
+        }
+        let (Some(is_buy), Some(market_data)) = (opening_side_is_buy(intent), market_data) else {
+            return self
+                .fallback
+                .resolve(intent, available_adapters, market_data);
+        };
+
+        // Lower is better: price paid on buys, negated proceeds on sells
+        let best = touches(intent, is_buy, available_adapters, market_data)
+            .into_iter()
+            .map(|(name, touch)| {
+                let price = self.effective_price(&name, &touch, intent.size, is_buy);
+                let cost = if is_buy { price } else { -price };
+                let stats = self.health.get(&name);
+                (cost, !stats.is_healthy(), stats.latency_ms, name)
+            })
+            .min_by(|a, b| {
+                a.0.cmp(&b.0).then(a.1.cmp(&b.1)).then(
+                    // Unmeasured latency sorts last
+                    match (a.2, b.2) {
+                        (Some(x), Some(y)) => x.partial_cmp(&y).unwrap_or(Ordering::Equal),
+                        (Some(_), None) => Ordering::Less,
+                        (None, Some(_)) => Ordering::Greater,
+                        (None, None) => Ordering::Equal,
+                    },
+                )
+            });
+        match best {
+            Some((_, _, _, name)) => {
+                vec![RouteTarget::new(&name, &available_adapters[&name], 1.0)]
+            }
+            None => self
+                .fallback
+                .resolve(intent, available_adapters, Some(market_data)),
+        }
+    }
+}
+
+/// Splits opens across every venue with a book, in proportion to the size
+/// displayed at the touch on the side taken. Closes, and opens without any
+/// displayed size, route as `WeightedStatic`.
+pub struct LiquidityProportional {
+    fallback: WeightedStatic,
+}
+
+impl LiquidityProportional {
+    pub fn new(fallback: WeightedStatic) -> Self {
+        Self { fallback }
+    }
+}
+
+impl RoutingStrategy for LiquidityProportional {
+    fn name(&self) -> &'static str {
+        "liquidity_proportional"
+    }
+
+    fn resolve(
+        &self,
+        intent: &Intent,
+        available_adapters: &AdapterMap,
+        market_data: Option<&MarketDataEngine>,
+    ) -> Vec<RouteTarget> {
+        if let Some(targets) = explicit_route(intent, available_adapters) {
+            return targets;
+        }
+        let (Some(is_buy), Some(market_data)) = (opening_side_is_buy(intent), market_data) else {
+            return self
+                .fallback
+                .resolve(intent, available_adapters, market_data);
+        };
+
+        let targets: Vec<RouteTarget> = touches(intent, is_buy, available_adapters, market_data)
+            .into_iter()
+            .filter_map(|(name, touch)| {
+                let weight = touch.qty.to_f64().filter(|w| *w > 0.0)?;
+                Some(RouteTarget::new(&name, &available_adapters[&name], weight))
+            })
+            .collect();
+        if targets.is_empty() {
+            return self
+                .fallback
+                .resolve(intent, available_adapters, Some(market_data));
+        }
+        targets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::adapter::ExchangeError;
+    use crate::market_data::types::BookTicker;
+    use crate::test_support::MockAdapter;
+    use rust_decimal_macros::dec;
+
+    fn adapters() -> AdapterMap {
+        ["binance", "bybit", "okx"]
+            .into_iter()
+            .map(|name| {
+                let adapter: Arc<dyn ExchangeAdapter + Send + Sync> =
+                    Arc::new(MockAdapter::new(name));
+                (name.to_string(), adapter)
+            })
+            .collect()
+    }
+
+    fn market_data() -> MarketDataEngine {
+        let md = MarketDataEngine::new(None);
+        for (venue, ask, ask_qty) in [
+            ("binance", dec!(50010), dec!(1)),
+            ("bybit", dec!(50005), dec!(3)),
+            ("okx", dec!(50020), dec!(4)),
+        ] {
+            md.update_venue_ticker(
+                venue,
+                BookTicker {
+                    symbol: "BTCUSDT".to_string(),
+                    best_bid: ask - dec!(10),
+                    best_bid_qty: dec!(1),
+                    best_ask: ask,
+                    best_ask_qty: ask_qty,
+                    transaction_time: 0,
+                    event_time: 0,
+                },
+            );
+        }
+        md
+    }
+
+    fn buy_intent() -> Intent {
+        serde_json::from_value(serde_json::json!({
+            "signal_id": "sig-route",
+            "source": "hunter",
+            "symbol": "BTC/USDT",
+            "direction": 1,
+            "type": "BUY_SETUP",
+            "entry_zone": [50000.0],
+            "size": 1.0,
+            "status": "VALIDATED",
+            "t_signal": 0,
+        }))
+        .unwrap()
+    }
+
+    fn weighted_config() -> RoutingConfig {
+        RoutingConfig {
+            weights: Some(HashMap::from([
+                ("binance".to_string(), 0.6),
+                ("okx".to_string(), 0.4),
+            ])),
+            ..Default::default()
+        }
+    }
+
+    fn resolve(kind: RoutingStrategyKind, intent: &Intent) -> Vec<(String, f64)> {
+        let strategy = build_strategy(
+            &RoutingConfig {
+                strategy: kind,
+                ..weighted_config()
+            },
+            Arc::new(VenueHealth::default()),
+        );
+        let mut routes: Vec<(String, f64)> = strategy
+            .resolve(intent, &adapters(), Some(&market_data()))
+            .into_iter()
+            .map(|t| (t.name, t.weight))
+            .collect();
+        routes.sort_by(|a, b| a.0.cmp(&b.0));
+        routes
+    }
+
+    #[test]
+    fn test_builtin_strategies_on_same_intent() {
+        let intent = buy_intent();
+
+        assert!(resolve(RoutingStrategyKind::ExplicitOnly, &intent).is_empty());
+        assert_eq!(
+            resolve(RoutingStrategyKind::WeightedStatic, &intent),
+            vec![("binance".to_string(), 0.6), ("okx".to_string(), 0.4)]
+        );
+        // Lowest ask
+        assert_eq!(
+            resolve(RoutingStrategyKind::BestPrice, &intent),
+            vec![("bybit".to_string(), 1.0)]
+        );
+        // Displayed ask size 1 : 3 : 4
+        assert_eq!(
+            resolve(RoutingStrategyKind::LiquidityProportional, &intent),
+            vec![
+                ("binance".to_string(), 1.0),
+                ("bybit".to_string(), 3.0),
+                ("okx".to_string(), 4.0)
+            ]
+        );
+
+        // An explicit exchange wins under every strategy
+        let explicit = Intent {
+            exchange: Some("OKX".to_string()),
+            ..buy_intent()
+        };
+        for kind in [
+            RoutingStrategyKind::ExplicitOnly,
+            RoutingStrategyKind::WeightedStatic,
+            RoutingStrategyKind::BestPrice,
+            RoutingStrategyKind::LiquidityProportional,
+        ] {
+            assert_eq!(resolve(kind, &explicit), vec![("okx".to_string(), 1.0)]);
+        }
+
+        // Closes are not price-routed
+        let close = Intent {
+            intent_type: IntentType::CloseLong,
+            ..buy_intent()
+        };
+        assert_eq!(
+            resolve(RoutingStrategyKind::BestPrice, &close),
+            resolve(RoutingStrategyKind::WeightedStatic, &close)
+        );
+    }
+
+    fn best_price(fee_bps: &[(&str, f64)], health: Arc<VenueHealth>) -> BestPrice {
+        BestPrice::new(
+            WeightedStatic::new(RoutingConfig {
+                fee_bps: fee_bps
+                    .iter()
+                    .map(|(venue, bps)| (venue.to_string(), *bps))
+                    .collect(),
+                ..Default::default()
+            }),
+            health,
+        )
+    }
+
+    fn book(md: &MarketDataEngine, venue: &str, bid: Decimal, ask: Decimal, qty: Decimal) {
+        md.update_venue_ticker(
+            venue,
+            BookTicker {
+                symbol: "BTCUSDT".to_string(),
+                best_bid: bid,
+                best_bid_qty: qty,
+                best_ask: ask,
+                best_ask_qty: qty,
+                transaction_time: 0,
+                event_time: 0,
+            },
+        );
+    }
+
+    fn winner(strategy: &BestPrice, intent: &Intent, md: &MarketDataEngine) -> Vec<String> {
+        strategy
+            .resolve(intent, &adapters(), Some(md))
+            .into_iter()
+            .map(|t| t.name)
+            .collect()
+    }
+
+    #[test]
+    fn test_best_price_accounts_for_fees() {
+        let md = MarketDataEngine::new(None);
+        // Binance is 10 cheaper at the touch but charges 10bps against 2bps
+        book(&md, "binance", dec!(49990), dec!(50000), dec!(5));
+        book(&md, "bybit", dec!(50000), dec!(50010), dec!(5));
+        let health = Arc::new(VenueHealth::default());
+
+        let raw = best_price(&[], health.clone());
+        assert_eq!(winner(&raw, &buy_intent(), &md), vec!["binance"]);
+
+        // 50000 * 1.001 = 50050 vs 50010 * 1.0002 = 50020.002
+        let fees = best_price(&[("binance", 10.0), ("BYBIT", 2.0)], health.clone());
+        assert_eq!(winner(&fees, &buy_intent(), &md), vec!["bybit"]);
+
+        // Sells compare proceeds: 49990 * 0.999 = 49940.01 vs 50000 * 0.9998 = 49990
+        let sell = Intent {
+            direction: -1,
+            intent_type: IntentType::SellSetup,
+            ..buy_intent()
+        };
+        assert_eq!(winner(&fees, &sell, &md), vec!["bybit"]);
+    }
+
+    #[test]
+    fn test_best_price_accounts_for_size_beyond_the_touch() {
+        let md = MarketDataEngine::new(None);
+        // Binance is cheaper but shows 1 of the 4 wanted, with a 20 wide spread
+        book(&md, "binance", dec!(49980), dec!(50000), dec!(1));
+        book(&md, "bybit", dec!(50000), dec!(50010), dec!(10));
+        let strategy = best_price(&[], Arc::new(VenueHealth::default()));
+
+        assert_eq!(winner(&strategy, &buy_intent(), &md), vec!["binance"]);
+        // 50000 + 20 * 3/4 = 50015 vs 50010
+        let large = Intent {
+            size: dec!(4),
+            ..buy_intent()
+        };
+        assert_eq!(winner(&strategy, &large, &md), vec!["bybit"]);
+    }
+
+    #[test]
+    fn test_best_price_ties_go_to_healthy_then_fastest_venue() {
+        let md = MarketDataEngine::new(None);
+        for venue in ["binance", "bybit", "okx"] {
+            book(&md, venue, dec!(49990), dec!(50000), dec!(5));
+        }
+        let health = Arc::new(VenueHealth::default());
+        let strategy = best_price(&[], health.clone());
+
+        // Measured latency beats unmeasured
+        health.record("okx", 40.0, None);
+        assert_eq!(winner(&strategy, &buy_intent(), &md), vec!["okx"]);
+
+        health.record("bybit", 15.0, None);
+        assert_eq!(winner(&strategy, &buy_intent(), &md), vec!["bybit"]);
+
+        // A network failure makes the fastest venue lose the tie
+        health.record(
+            "bybit",
+            15.0,
+            Some(&ExchangeError::Network("timeout".into())),
+        );
+        assert!(!health.get("bybit").is_healthy());
+        assert_eq!(winner(&strategy, &buy_intent(), &md), vec!["okx"]);
+
+        // Rejections are not venue faults; a success restores it
+        health.record(
+            "bybit",
+            15.0,
+            Some(&ExchangeError::OrderRejected("qty".into())),
+        );
+        assert!(!health.get("bybit").is_healthy());
+        health.record("bybit", 15.0, None);
+        assert_eq!(winner(&strategy, &buy_intent(), &md), vec!["bybit"]);
+    }
+
+    #[test]
+    fn test_only_transient_venue_rejections_count_against_health() {
+        let health = VenueHealth::default();
+        let rejected = |code: &str| ExchangeError::VenueRejected {
+            code: code.to_string(),
+            message: String::new(),
+        };
+
+        // Insufficient balance, invalid order, unknown code
+        for code in ["110007", "10001", "999999"] {
+            health.record("bybit", 15.0, Some(&rejected(code)));
+            assert!(health.get("bybit").is_healthy(), "code {}", code);
+        }
+
+        // Rate limited
+        health.record("bybit", 15.0, Some(&rejected("10006")));
+        assert!(!health.get("bybit").is_healthy());
+
+        // Venue overloaded
+        health.record("binance", 15.0, Some(&rejected("-1001")));
+        assert!(!health.get("binance").is_healthy());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::config::ExchangeConfig;
 use crate::exchange::adapter::{
-    ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, Position,
+    ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, OrderStatus, Position,
 };
 use crate::exchange::dex_utils;
 use async_trait::async_trait;
@@ -58,6 +58,8 @@ pub struct SushiSwapAdapter {
     #[allow(dead_code)]
     chain_name: String,
     slippage_bps: u64,
+    amount_rounding: dex_utils::AmountRounding,
+    gas_guard: dex_utils::GasGuard,
 }
 
 impl SushiSwapAdapter {
@@ -121,6 +123,8 @@ impl SushiSwapAdapter {
             router_address,
             chain_name,
             slippage_bps,
+            amount_rounding: dex_utils::resolve_amount_rounding("SUSHISWAP"),
+            gas_guard: dex_utils::GasGuard::from_env("SUSHISWAP")?,
         })
     }
 }
@@ -171,10 +175,7 @@ impl ExchangeAdapter for SushiSwapAdapter {
             .map_err(|_| ExchangeError::OrderRejected("Invalid Token Out".into()))?;
 
         let decimals = dex_utils::token_decimals_from_address(token_in_str);
-        let amount_in_raw = (order.quantity * Decimal::from(10u64.pow(decimals)))
-            .to_u64()
-            .unwrap_or(0);
-        let amount_in = U256::from(amount_in_raw);
+        let amount_in = dex_utils::to_base_units(order.quantity, decimals, self.amount_rounding)?;
 
         // ERC-20 approval
         dex_utils::ensure_approval(
@@ -209,6 +210,14 @@ impl ExchangeAdapter for SushiSwapAdapter {
         };
 
         let tx = contract.exact_input_single(params);
+
+        self.gas_guard
+            .enforce(
+                self.client.clone(),
+                &tx.tx,
+                dex_utils::swap_notional_usd(token_in_str, order.quantity, order.price),
+            )
+            .await?;
         let pending_tx = tx
             .send()
             .await
@@ -220,7 +229,8 @@ impl ExchangeAdapter for SushiSwapAdapter {
             order_id: tx_hash,
             client_order_id: order.client_order_id,
             symbol: order.symbol,
-            status: "PENDING".to_string(),
+            status: OrderStatus::Pending,
+            raw_status: "PENDING".to_string(),
             executed_qty: Decimal::zero(),
             avg_price: None,
             t_exchange: None,
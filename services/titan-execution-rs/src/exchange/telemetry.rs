@@ -0,0 +1,172 @@
+use std::time::Instant;
+
+use reqwest::header::HeaderMap;
+use reqwest::RequestBuilder;
+use tracing::{field, info_span, Instrument, Span};
+
+use crate::exchange::adapter::{ExchangeError, OrderResponse};
+
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Query parameters that carry credentials or signatures.
+const SENSITIVE_PARAMS: &[&str] = &[
+    "signature",
+    "sign",
+    "apikey",
+    "api_key",
+    "key",
+    "passphrase",
+    "secret",
+    "token",
+];
+
+/// Header name fragments that carry credentials or signatures
+/// (X-MBX-APIKEY, X-BAPI-SIGN, OK-ACCESS-PASSPHRASE, Authorization, ...).
+const SENSITIVE_HEADER_FRAGMENTS: &[&str] = &[
+    "key",
+    "sign",
+    "passphrase",
+    "secret",
+    "token",
+    "authorization",
+    "cookie",
+];
+
+pub fn is_sensitive_header(name: &str) -> bool {
+    let name = name.to_lowercase();
+    SENSITIVE_HEADER_FRAGMENTS.iter().any(|f| name.contains(f))
+}
+
+/// Strip credential-bearing query parameters from a URL before it is recorded.
+pub fn redact_endpoint(url: &str) -> String {
+    let Some((path, query)) = url.split_once('?') else {
+        return url.to_string();
+    };
+    let params: Vec<String> = query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((k, _)) if SENSITIVE_PARAMS.contains(&k.to_lowercase().as_str()) => {
+                format!("{}={}", k, REDACTED)
+            }
+            _ => pair.to_string(),
+        })
+        .collect();
+    format!("{}?{}", path, params.join("&"))
+}
+
+/// Render headers for tracing with sensitive values replaced.
+pub fn redact_headers(headers: &HeaderMap) -> String {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if is_sensitive_header(name.as_str()) {
+                REDACTED
+            } else {
+                value.to_str().unwrap_or("<binary>")
+            };
+            format!("{}={}", name, value)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Span for one order call through an adapter. Created in the caller's context so it
+/// inherits the intent span (and its OTel parent / correlation id).
+pub fn order_span(
+    exchange: &str,
+    symbol: &str,
+    client_order_id: &str,
+    correlation_id: Option<&str>,
+) -> Span {
+    info_span!(
+        "adapter.place_order",
+        exchange = %exchange,
+        symbol = %symbol,
+        client_order_id = %client_order_id,
+        correlation_id = correlation_id.unwrap_or_default(),
+        status = field::Empty,
+        latency_ms = field::Empty,
+        exchange_order_id = field::Empty,
+        error = field::Empty,
+    )
+}
+
+pub fn record_order_result(
+    span: &Span,
+    started: Instant,
+    result: &Result<OrderResponse, ExchangeError>,
+) {
+    span.record("latency_ms", started.elapsed().as_millis() as u64);
+    match result {
+        Ok(resp) => {
+            span.record("status", resp.status.as_str());
+            span.record("exchange_order_id", resp.order_id.as_str());
+        }
+        Err(e) => {
+            span.record("status", "ERROR");
+            span.record("error", field::display(e));
+        }
+    }
+}
+
+/// Send an adapter HTTP request inside an `adapter.http` span recording the redacted
+/// endpoint, redacted headers, response status and latency.
+pub async fn send(
+    exchange: &str,
+    request: RequestBuilder,
+) -> Result<reqwest::Response, reqwest::Error> {
+    let (client, request) = request.build_split();
+    let request = request?;
+
+    let span = info_span!(
+        "adapter.http",
+        exchange = %exchange,
+        method = %request.method(),
+        endpoint = %redact_endpoint(request.url().as_str()),
+        headers = %redact_headers(request.headers()),
+        status = field::Empty,
+        latency_ms = field::Empty,
+    );
+
+    let started = Instant::now();
+    let result = client.execute(request).instrument(span.clone()).await;
+    span.record("latency_ms", started.elapsed().as_millis() as u64);
+    match &result {
+        Ok(resp) => span.record("status", resp.status().as_u16()),
+        Err(e) => span.record("status", field::display(e)),
+    };
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_redact_endpoint() {
+        let url = "https://fapi.binance.com/fapi/v2/balance?timestamp=1&signature=abcdef";
+        assert_eq!(
+            redact_endpoint(url),
+            "https://fapi.binance.com/fapi/v2/balance?timestamp=1&signature=[REDACTED]"
+        );
+        assert_eq!(
+            redact_endpoint("/api/v5/trade/order"),
+            "/api/v5/trade/order"
+        );
+    }
+
+    #[test]
+    fn test_redact_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-MBX-APIKEY", HeaderValue::from_static("my-key"));
+        headers.insert("X-BAPI-SIGN", HeaderValue::from_static("deadbeef"));
+        headers.insert("Content-Type", HeaderValue::from_static("application/json"));
+
+        let rendered = redact_headers(&headers);
+        assert!(!rendered.contains("my-key"));
+        assert!(!rendered.contains("deadbeef"));
+        assert!(rendered.contains("x-mbx-apikey=[REDACTED]"));
+        assert!(rendered.contains("content-type=application/json"));
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use parking_lot::RwLock;
+use tracing::warn;
+
+use crate::metrics;
+use crate::rate_limiter::TokenBucket;
+
+/// Default time an urgent order may wait for a per-symbol token.
+pub const DEFAULT_SYMBOL_MAX_QUEUE_MS: u64 = 250;
+
+/// Per-(exchange, symbol) order throttle, layered on top of each adapter's
+/// venue-wide `TokenBucket`. Stops a single symbol (e.g. a repricing loop) from
+/// eating the venue budget. Urgent orders wait briefly for a token; everything
+/// else is rejected as soon as the symbol's bucket is empty.
+pub struct SymbolThrottle {
+    burst: usize,
+    orders_per_sec: f64,
+    max_queue: Duration,
+    buckets: RwLock<HashMap<(String, String), TokenBucket>>,
+}
+
+impl SymbolThrottle {
+    pub fn new(burst: usize, orders_per_sec: f64, max_queue_ms: u64) -> Self {
+        Self {
+            burst: burst.max(1),
+            orders_per_sec,
+            max_queue: Duration::from_millis(max_queue_ms),
+            buckets: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn bucket(&self, exchange: &str, symbol: &str) -> TokenBucket {
+        let key = (
+            exchange.to_lowercase(),
+            symbol.replace("/", "").replace("_", "").to_uppercase(),
+        );
+        if let Some(bucket) = self.buckets.read().get(&key) {
+            return bucket.clone();
+        }
+        self.buckets
+            .write()
+            .entry(key)
+            .or_insert_with(|| TokenBucket::new(self.burst, self.orders_per_sec))
+            .clone()
+    }
+
+    /// Take a token for one order on `exchange`/`symbol`. `urgent` orders queue for
+    /// up to the configured wait before being rejected.
+    pub async fn admit(&self, exchange: &str, symbol: &str, urgent: bool) -> Result<(), String> {
+        let bucket = self.bucket(exchange, symbol);
+        if bucket.try_acquire(1) {
+            return Ok(());
+        }
+        if urgent
+            && tokio::time::timeout(self.max_queue, bucket.acquire(1))
+                .await
+                .is_ok()
+        {
+            return Ok(());
+        }
+
+        warn!(
+            "🚦 {} {} throttled (> {}/s per symbol)",
+            exchange, symbol, self.orders_per_sec
+        );
+        metrics::inc_symbol_throttled();
+        Err(format!(
+            "{} {} throttled: per-symbol order rate exceeded ({}/s)",
+            exchange, symbol, self.orders_per_sec
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_same_symbol_throttled_other_symbols_not() {
+        let throttle = SymbolThrottle::new(1, 1.0, 0);
+
+        assert!(throttle.admit("binance", "BTC/USDT", false).await.is_ok());
+        assert!(throttle.admit("binance", "BTCUSDT", false).await.is_err());
+
+        // Different symbol, and same symbol on a different venue, have their own buckets
+        assert!(throttle.admit("binance", "ETH/USDT", false).await.is_ok());
+        assert!(throttle.admit("bybit", "BTC/USDT", false).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_urgent_order_queues_briefly() {
+        // One token every 50ms; urgent orders may wait up to 500ms
+        let throttle = SymbolThrottle::new(1, 20.0, 500);
+
+        assert!(throttle.admit("binance", "BTCUSDT", false).await.is_ok());
+        assert!(throttle.admit("binance", "BTCUSDT", false).await.is_err());
+        assert!(throttle.admit("binance", "BTCUSDT", true).await.is_ok());
+    }
+}
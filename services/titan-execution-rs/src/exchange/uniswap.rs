@@ -1,6 +1,6 @@
 use crate::config::ExchangeConfig;
 use crate::exchange::adapter::{
-    ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, Position,
+    ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, OrderStatus, Position, SwapMode,
 };
 use crate::exchange::dex_utils;
 use async_trait::async_trait;
@@ -8,11 +8,18 @@ use chrono::Utc;
 use ethers::prelude::*;
 use rust_decimal::prelude::*;
 use std::convert::TryFrom;
+use std::future::Future;
 use std::str::FromStr;
 use std::sync::Arc;
-use tracing::info;
+use tracing::{debug, info, warn};
 
-// JSON ABI for SwapRouter02 exactInputSingle
+const WETH: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+const USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+
+/// Pool fee tiers (hundredths of a bip) tried for every hop: 0.05%, 0.3%, 1%
+pub const DEFAULT_FEE_TIERS: [u32; 3] = [500, 3000, 10000];
+
+// JSON ABI for SwapRouter02 exactInputSingle / exactOutputSingle / exactInput
 abigen!(
     ISwapRouter,
     r#"[
@@ -38,15 +45,284 @@ abigen!(
           "outputs": [{ "internalType": "uint256", "name": "amountOut", "type": "uint256" }],
           "stateMutability": "payable",
           "type": "function"
+        },
+        {
+          "inputs": [
+            {
+              "components": [
+                { "internalType": "address", "name": "tokenIn", "type": "address" },
+                { "internalType": "address", "name": "tokenOut", "type": "address" },
+                { "internalType": "uint24", "name": "fee", "type": "uint24" },
+                { "internalType": "address", "name": "recipient", "type": "address" },
+                { "internalType": "uint256", "name": "deadline", "type": "uint256" },
+                { "internalType": "uint256", "name": "amountOut", "type": "uint256" },
+                { "internalType": "uint256", "name": "amountInMaximum", "type": "uint256" },
+                { "internalType": "uint160", "name": "sqrtPriceLimitX96", "type": "uint160" }
+              ],
+              "internalType": "struct ISwapRouter.ExactOutputSingleParams",
+              "name": "params",
+              "type": "tuple"
+            }
+          ],
+          "name": "exactOutputSingle",
+          "outputs": [{ "internalType": "uint256", "name": "amountIn", "type": "uint256" }],
+          "stateMutability": "payable",
+          "type": "function"
+        },
+        {
+          "inputs": [
+            {
+              "components": [
+                { "internalType": "bytes", "name": "path", "type": "bytes" },
+                { "internalType": "address", "name": "recipient", "type": "address" },
+                { "internalType": "uint256", "name": "deadline", "type": "uint256" },
+                { "internalType": "uint256", "name": "amountIn", "type": "uint256" },
+                { "internalType": "uint256", "name": "amountOutMinimum", "type": "uint256" }
+              ],
+              "internalType": "struct ISwapRouter.ExactInputParams",
+              "name": "params",
+              "type": "tuple"
+            }
+          ],
+          "name": "exactInput",
+          "outputs": [{ "internalType": "uint256", "name": "amountOut", "type": "uint256" }],
+          "stateMutability": "payable",
+          "type": "function"
         }
     ]"#
 );
 
+// JSON ABI for QuoterV2 quoteExactInput: simulates a swap along an encoded path
+abigen!(
+    IQuoterV2,
+    r#"[
+        {
+          "inputs": [
+            { "internalType": "bytes", "name": "path", "type": "bytes" },
+            { "internalType": "uint256", "name": "amountIn", "type": "uint256" }
+          ],
+          "name": "quoteExactInput",
+          "outputs": [
+            { "internalType": "uint256", "name": "amountOut", "type": "uint256" },
+            { "internalType": "uint160[]", "name": "sqrtPriceX96AfterList", "type": "uint160[]" },
+            { "internalType": "uint32[]", "name": "initializedTicksCrossedList", "type": "uint32[]" },
+            { "internalType": "uint256", "name": "gasEstimate", "type": "uint256" }
+          ],
+          "stateMutability": "nonpayable",
+          "type": "function"
+        }
+    ]"#
+);
+
+/// A V3 swap route: `tokens[i]` is swapped for `tokens[i + 1]` in the pool with fee `fees[i]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct V3Path {
+    pub tokens: Vec<Address>,
+    pub fees: Vec<u32>,
+}
+
+impl V3Path {
+    pub fn hops(&self) -> usize {
+        self.fees.len()
+    }
+
+    /// Packed encoding the router and quoter take: token (20 bytes), fee (3 bytes), token, ...
+    pub fn encode(&self) -> Bytes {
+        let mut out = Vec::with_capacity(20 + 23 * self.fees.len());
+        for (token, fee) in self.tokens.iter().zip(&self.fees) {
+            out.extend_from_slice(token.as_bytes());
+            out.extend_from_slice(&fee.to_be_bytes()[1..]);
+        }
+        if let Some(last) = self.tokens.last() {
+            out.extend_from_slice(last.as_bytes());
+        }
+        Bytes::from(out)
+    }
+}
+
+/// Which V3 paths to quote between two tokens.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathFinder {
+    pub fee_tiers: Vec<u32>,
+    /// Tokens a two-hop route may pass through; empty for direct pools only
+    pub intermediates: Vec<Address>,
+}
+
+impl Default for PathFinder {
+    fn default() -> Self {
+        Self {
+            fee_tiers: DEFAULT_FEE_TIERS.to_vec(),
+            intermediates: [WETH, USDC]
+                .iter()
+                .map(|a| Address::from_str(a).expect("well-known address"))
+                .collect(),
+        }
+    }
+}
+
+impl PathFinder {
+    /// Reads `{PREFIX}_FEE_TIERS` (e.g. `500,3000`, falling back to a single
+    /// `{PREFIX}_FEE_TIER`) and `{PREFIX}_INTERMEDIATES` (comma-separated token
+    /// addresses; set it empty to quote direct pools only).
+    pub fn from_env(prefix: &str) -> Self {
+        let mut finder = Self::default();
+        let tiers = std::env::var(format!("{}_FEE_TIERS", prefix))
+            .or_else(|_| std::env::var(format!("{}_FEE_TIER", prefix)));
+        if let Ok(spec) = tiers {
+            let parsed: Vec<u32> = spec
+                .split(',')
+                .filter_map(|t| t.trim().parse().ok())
+                .collect();
+            if !parsed.is_empty() {
+                finder.fee_tiers = parsed;
+            }
+        }
+        if let Ok(spec) = std::env::var(format!("{}_INTERMEDIATES", prefix)) {
+            finder.intermediates = spec
+                .split(',')
+                .map(str::trim)
+                .filter(|a| !a.is_empty())
+                .filter_map(|a| Address::from_str(a).ok())
+                .collect();
+        }
+        finder
+    }
+
+    /// Direct pools at every fee tier, then two-hop routes through each
+    /// intermediate at every pair of tiers.
+    pub fn candidates(&self, token_in: Address, token_out: Address) -> Vec<V3Path> {
+        let mut paths: Vec<V3Path> = self
+            .fee_tiers
+            .iter()
+            .map(|fee| V3Path {
+                tokens: vec![token_in, token_out],
+                fees: vec![*fee],
+            })
+            .collect();
+        for mid in &self.intermediates {
+            if *mid == token_in || *mid == token_out {
+                continue;
+            }
+            for first in &self.fee_tiers {
+                for second in &self.fee_tiers {
+                    paths.push(V3Path {
+                        tokens: vec![token_in, *mid, token_out],
+                        fees: vec![*first, *second],
+                    });
+                }
+            }
+        }
+        paths
+    }
+}
+
+/// Prices a path's gas estimate in output-token base units, so routes that
+/// cross more pools pay for the extra gas they burn.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct GasPricing {
+    pub gas_price_wei: U256,
+    /// Output base units one native token (ETH) is worth; zero when unknown,
+    /// which ranks paths by gross output
+    pub out_per_native: U256,
+}
+
+impl GasPricing {
+    /// Pricing for swaps into `token_out`: ETH-denominated for WETH, at
+    /// `native_usd` for USD stables, unknown otherwise.
+    pub fn new(token_out: &str, gas_price_wei: U256, native_usd: Option<Decimal>) -> Self {
+        let decimals = dex_utils::token_decimals_from_address(token_out);
+        let out_per_native = if token_out.eq_ignore_ascii_case(WETH) {
+            U256::exp10(18)
+        } else {
+            native_usd
+                .filter(|_| dex_utils::swap_notional_usd(token_out, Decimal::ONE, None).is_some())
+                .and_then(|px| {
+                    dex_utils::to_base_units(px, decimals, dex_utils::AmountRounding::Down).ok()
+                })
+                .unwrap_or_default()
+        };
+        Self {
+            gas_price_wei,
+            out_per_native,
+        }
+    }
+
+    /// Cost of `gas_units` in output base units
+    pub fn cost(&self, gas_units: U256) -> U256 {
+        gas_units
+            .saturating_mul(self.gas_price_wei)
+            .saturating_mul(self.out_per_native)
+            / U256::exp10(18)
+    }
+}
+
+/// Output of one quoted path
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathQuote {
+    pub path: V3Path,
+    pub amount_out: U256,
+    pub gas_estimate: U256,
+    /// `amount_out` less the gas cost, in output base units
+    pub net_out: U256,
+}
+
+/// Quote every path with `quote` (returning output amount and gas units) and
+/// pick the one with the most output net of gas. Paths that fail to quote
+/// (no pool, not enough liquidity) are skipped; if none is left the swap is
+/// rejected.
+pub async fn best_path<F, Fut>(
+    paths: Vec<V3Path>,
+    gas: GasPricing,
+    quote: F,
+) -> Result<PathQuote, ExchangeError>
+where
+    F: Fn(V3Path) -> Fut,
+    Fut: Future<Output = Result<(U256, U256), ExchangeError>>,
+{
+    let candidates = paths.len();
+    let quotes = futures::future::join_all(paths.into_iter().map(|path| {
+        let quoted = quote(path.clone());
+        async move { (path, quoted.await) }
+    }))
+    .await;
+
+    let mut best: Option<PathQuote> = None;
+    for (path, quoted) in quotes {
+        let (amount_out, gas_estimate) = match quoted {
+            Ok(q) => q,
+            Err(e) => {
+                debug!("Uniswap path {:?} not quotable: {}", path.fees, e);
+                continue;
+            }
+        };
+        let net_out = amount_out.saturating_sub(gas.cost(gas_estimate));
+        if net_out.is_zero() || best.as_ref().is_some_and(|b| net_out <= b.net_out) {
+            continue;
+        }
+        best = Some(PathQuote {
+            path,
+            amount_out,
+            gas_estimate,
+            net_out,
+        });
+    }
+    best.ok_or_else(|| {
+        ExchangeError::InsufficientLiquidity(format!(
+            "no viable Uniswap V3 path among {} candidates",
+            candidates
+        ))
+    })
+}
+
 #[derive(Clone)]
 pub struct UniswapAdapter {
     client: Arc<SignerMiddleware<Provider<Http>, LocalWallet>>,
     router_address: Address,
-    slippage_bps: u64,
+    quoter_address: Address,
+    path_finder: PathFinder,
+    slippage: dex_utils::SlippageCurve,
+    amount_rounding: dex_utils::AmountRounding,
+    gas_guard: dex_utils::GasGuard,
 }
 
 impl UniswapAdapter {
@@ -88,15 +364,62 @@ impl UniswapAdapter {
         let router_address = Address::from_str(&router_addr)
             .map_err(|e| ExchangeError::Configuration(format!("Invalid Router Address: {}", e)))?;
 
+        // QuoterV2
+        // Mainnet: 0x61fFE014bA17989E743c5F6cB21bF9697530B21e
+        // Sepolia: 0xEd1f6473345F45b75F8179591dd5bA1888cf2FB3
+        let default_quoter = if config.testnet {
+            "0xEd1f6473345F45b75F8179591dd5bA1888cf2FB3"
+        } else {
+            "0x61fFE014bA17989E743c5F6cB21bF9697530B21e"
+        };
+        let quoter_addr =
+            std::env::var("UNISWAP_QUOTER_ADDRESS").unwrap_or_else(|_| default_quoter.to_string());
+        let quoter_address = Address::from_str(&quoter_addr)
+            .map_err(|e| ExchangeError::Configuration(format!("Invalid Quoter Address: {}", e)))?;
+
         // Slippage protection (configurable via UNISWAP_SLIPPAGE_BPS, default 50 = 0.5%)
-        let slippage_bps = dex_utils::resolve_slippage("UNISWAP");
 
         Ok(Self {
             client,
             router_address,
-            slippage_bps,
+            quoter_address,
+            path_finder: PathFinder::from_env("UNISWAP"),
+            slippage: dex_utils::SlippageCurve::from_env("UNISWAP"),
+            amount_rounding: dex_utils::resolve_amount_rounding("UNISWAP"),
+            gas_guard: dex_utils::GasGuard::from_env("UNISWAP")?,
         })
     }
+
+    /// Best direct or two-hop path for spending `amount_in` of `token_in`.
+    async fn route(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        token_out_str: &str,
+        amount_in: U256,
+    ) -> Result<PathQuote, ExchangeError> {
+        let gas = match self.client.get_gas_price().await {
+            Ok(price) => GasPricing::new(token_out_str, price, self.gas_guard.native_usd),
+            Err(e) => {
+                warn!("Gas price fetch failed, ranking Uniswap paths gross: {}", e);
+                GasPricing::default()
+            }
+        };
+        let quoter = &IQuoterV2::new(self.quoter_address, self.client.clone());
+        best_path(
+            self.path_finder.candidates(token_in, token_out),
+            gas,
+            move |path| async move {
+                let (amount_out, _, _, gas_estimate) = quoter
+                    .quote_exact_input(path.encode(), amount_in)
+                    .call()
+                    .await
+                    .map_err(|e| ExchangeError::Network(e.to_string()))?;
+                Ok((amount_out, gas_estimate))
+            },
+        )
+        .await
+    }
 }
 
 #[async_trait]
@@ -165,12 +488,54 @@ impl ExchangeAdapter for UniswapAdapter {
         let token_out = Address::from_str(token_out_str)
             .map_err(|_| ExchangeError::OrderRejected("Invalid Token Out address".into()))?;
 
-        // Resolve decimals for input token
-        let decimals = dex_utils::token_decimals_from_address(token_in_str);
-        let amount_in_raw = (order.quantity * Decimal::from(10u64.pow(decimals)))
-            .to_u64()
-            .unwrap_or(0);
-        let amount_in = U256::from(amount_in_raw);
+        let in_decimals = dex_utils::token_decimals_from_address(token_in_str);
+
+        let notional = dex_utils::order_notional_usd(
+            token_in_str,
+            order.swap_mode,
+            order.quantity,
+            order.price,
+        );
+        let slippage_bps = self.slippage.bps_for(notional);
+
+        // ExactIn spends `quantity` of token_in. ExactOut buys `quantity` of token_out and
+        // needs the limit price (token_in per token_out) to cap what it may spend.
+        let (amount, input_limit, spend, route) = match order.swap_mode {
+            SwapMode::ExactIn => {
+                let amount_in =
+                    dex_utils::to_base_units(order.quantity, in_decimals, self.amount_rounding)?;
+                // Slippage protection off the best quoted path's output
+                let best = self
+                    .route(token_in, token_out, token_out_str, amount_in)
+                    .await?;
+                info!(
+                    "🧭 Uniswap route fees {:?} via {} hop(s): out {} (net of gas {})",
+                    best.path.fees,
+                    best.path.hops(),
+                    best.amount_out,
+                    best.net_out
+                );
+                let amount_out_minimum = dex_utils::calc_min_output(best.amount_out, slippage_bps);
+                (amount_in, amount_out_minimum, amount_in, Some(best.path))
+            }
+            SwapMode::ExactOut => {
+                let price = order.price.filter(|p| *p > Decimal::ZERO).ok_or_else(|| {
+                    ExchangeError::OrderRejected(
+                        "Uniswap exact-out swap requires a limit price".into(),
+                    )
+                })?;
+                let out_decimals = dex_utils::token_decimals_from_address(token_out_str);
+                let amount_out =
+                    dex_utils::to_base_units(order.quantity, out_decimals, self.amount_rounding)?;
+                let quoted_in = dex_utils::to_base_units(
+                    order.quantity * price,
+                    in_decimals,
+                    dex_utils::AmountRounding::Down,
+                )?;
+                let amount_in_maximum = dex_utils::calc_max_input(quoted_in, slippage_bps);
+                (amount_out, amount_in_maximum, amount_in_maximum, None)
+            }
+        };
 
         // ERC-20 Approval: ensure router can spend our tokens
         // Skip for native ETH wrapping scenarios
@@ -180,47 +545,70 @@ impl ExchangeAdapter for UniswapAdapter {
                 token_in,
                 self.router_address,
                 self.client.address(),
-                amount_in,
+                spend,
             )
             .await
             .map_err(|e| ExchangeError::Network(format!("Token approval failed: {}", e)))?;
         }
 
-        // Slippage protection: min output = amount_in * (1 - slippage)
-        // This is a rough floor — production systems should pre-quote via Quoter contract
-        let amount_out_minimum = dex_utils::calc_min_output(amount_in, self.slippage_bps);
-
         info!(
-            "🔄 Uniswap swap: {} {} → {}, slippage {}bps, min_out={}",
-            amount_in, token_in_str, token_out_str, self.slippage_bps, amount_out_minimum
+            "🔄 Uniswap swap {}: {} {} → {}, slippage {}bps, limit={}",
+            order.swap_mode.as_str(),
+            amount,
+            token_in_str,
+            token_out_str,
+            slippage_bps,
+            input_limit
         );
 
         let contract = ISwapRouter::new(self.router_address, self.client.clone());
 
-        // Determine fee tier — 3000 (0.3%) default, config override via env
+        // Exact-out swaps use a single pool: 3000 (0.3%) default, config override via env
         let fee_tier: u32 = std::env::var("UNISWAP_FEE_TIER")
             .ok()
             .and_then(|s| s.parse().ok())
             .unwrap_or(3000);
-
-        let params = ExactInputSingleParams {
-            token_in,
-            token_out,
-            fee: fee_tier,
-            recipient: self.client.address(),
-            deadline: U256::from(Utc::now().timestamp() + 300), // 5 min
-            amount_in,
-            amount_out_minimum,
-            sqrt_price_limit_x96: U256::zero(),
+        let deadline = U256::from(Utc::now().timestamp() + 300); // 5 min
+
+        let tx = match route {
+            Some(path) if path.hops() > 1 => contract.exact_input(ExactInputParams {
+                path: path.encode(),
+                recipient: self.client.address(),
+                deadline,
+                amount_in: amount,
+                amount_out_minimum: input_limit,
+            }),
+            Some(path) => contract.exact_input_single(ExactInputSingleParams {
+                token_in,
+                token_out,
+                fee: path.fees[0],
+                recipient: self.client.address(),
+                deadline,
+                amount_in: amount,
+                amount_out_minimum: input_limit,
+                sqrt_price_limit_x96: U256::zero(),
+            }),
+            None => contract.exact_output_single(ExactOutputSingleParams {
+                token_in,
+                token_out,
+                fee: fee_tier,
+                recipient: self.client.address(),
+                deadline,
+                amount_out: amount,
+                amount_in_maximum: input_limit,
+                sqrt_price_limit_x96: U256::zero(),
+            }),
         };
 
-        let tx = contract.exact_input_single(params);
+        self.gas_guard
+            .enforce(self.client.clone(), &tx.tx, notional)
+            .await?;
 
         // EIP-1559 gas estimation — let ethers handle it (it auto-detects EIP-1559)
         let pending_tx = tx
             .send()
             .await
-            .map_err(|e| ExchangeError::Network(format!("Failed to send swap: {}", e)))?;
+            .map_err(|e| dex_utils::contract_error("Uniswap swap", e))?;
 
         let tx_hash = format!("{:?}", pending_tx.tx_hash());
 
@@ -228,7 +616,8 @@ impl ExchangeAdapter for UniswapAdapter {
             order_id: tx_hash,
             client_order_id: order.client_order_id,
             symbol: order.symbol,
-            status: "PENDING".to_string(),
+            status: OrderStatus::Pending,
+            raw_status: "PENDING".to_string(),
             executed_qty: Decimal::zero(),
             avg_price: None,
             t_exchange: None,
@@ -285,6 +674,14 @@ impl ExchangeAdapter for UniswapAdapter {
         Ok(Decimal::zero())
     }
 
+    fn supports_exact_out(&self) -> bool {
+        true
+    }
+
+    fn settlement_assets(&self) -> Vec<String> {
+        vec!["USDC".to_string(), "ETH".to_string()]
+    }
+
     fn name(&self) -> &str {
         "uniswap"
     }
@@ -293,3 +690,120 @@ impl ExchangeAdapter for UniswapAdapter {
         Ok(Vec::new())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    const WBTC: &str = "0x2260FAC5E5542a773Aa44fBCfeDf7C193bc2C599";
+
+    fn addr(a: &str) -> Address {
+        Address::from_str(a).unwrap()
+    }
+
+    /// Quoter mock: output per path, keyed by intermediate token and fees.
+    /// Paths not listed revert as if their pool did not exist.
+    fn quotes(
+        table: Vec<(Option<&'static str>, Vec<u32>, u64, u64)>,
+    ) -> impl Fn(V3Path) -> std::future::Ready<Result<(U256, U256), ExchangeError>> {
+        move |path: V3Path| {
+            let mid = (path.hops() > 1).then(|| path.tokens[1]);
+            let hit = table
+                .iter()
+                .find(|(m, fees, _, _)| m.map(addr) == mid && *fees == path.fees)
+                .map(|(_, _, out, gas)| (U256::from(*out), U256::from(*gas)));
+            std::future::ready(
+                hit.ok_or_else(|| ExchangeError::Network("execution reverted".into())),
+            )
+        }
+    }
+
+    #[test]
+    fn test_path_encoding_and_candidates() {
+        let path = V3Path {
+            tokens: vec![addr(WBTC), addr(WETH), addr(USDC)],
+            fees: vec![3000, 500],
+        };
+        let encoded = path.encode();
+        assert_eq!(encoded.len(), 20 + 3 + 20 + 3 + 20);
+        assert_eq!(&encoded[..20], addr(WBTC).as_bytes());
+        assert_eq!(&encoded[20..23], &[0x00, 0x0b, 0xb8]);
+        assert_eq!(&encoded[43..46], &[0x00, 0x01, 0xf4]);
+        assert_eq!(&encoded[46..], addr(USDC).as_bytes());
+
+        // 3 direct + 9 via WETH; USDC is the output so it is not an intermediate
+        let candidates = PathFinder::default().candidates(addr(WBTC), addr(USDC));
+        assert_eq!(candidates.len(), 12);
+        assert!(candidates[..3].iter().all(|p| p.hops() == 1));
+        assert!(candidates[3..].iter().all(|p| p.tokens[1] == addr(WETH)));
+    }
+
+    #[tokio::test]
+    async fn test_multi_hop_beats_thin_direct_pool() {
+        let finder = PathFinder::default();
+        let quoter = quotes(vec![
+            (None, vec![3000], 9_000, 100_000),
+            (None, vec![10000], 9_500, 100_000),
+            (Some(WETH), vec![3000, 500], 10_000, 180_000),
+            (Some(WETH), vec![500, 500], 10_400, 180_000),
+        ]);
+        let best = best_path(
+            finder.candidates(addr(WBTC), addr(USDC)),
+            GasPricing::default(),
+            quoter,
+        )
+        .await
+        .unwrap();
+        assert_eq!(best.path.tokens[1], addr(WETH));
+        assert_eq!(best.path.fees, vec![500, 500]);
+        assert_eq!(best.amount_out, U256::from(10_400u64));
+    }
+
+    #[tokio::test]
+    async fn test_gas_outweighs_extra_hop_output() {
+        // 100k gas at 1 gwei is 0.0001 ETH, $0.20 with ETH at $2000
+        let gas = GasPricing::new(USDC, U256::exp10(9), Some(dec!(2000)));
+        assert_eq!(gas.cost(U256::from(100_000u64)), U256::from(200_000u64));
+
+        let finder = PathFinder::default();
+        // Two-hop grosses $0.30 more but burns 250k more gas ($0.50)
+        let quoter = quotes(vec![
+            (None, vec![500], 100_000_000, 100_000),
+            (Some(WETH), vec![500, 500], 100_300_000, 350_000),
+        ]);
+        let best = best_path(finder.candidates(addr(WBTC), addr(USDC)), gas, quoter)
+            .await
+            .unwrap();
+        assert_eq!(best.path.hops(), 1);
+        assert_eq!(best.net_out, U256::from(99_800_000u64));
+    }
+
+    #[tokio::test]
+    async fn test_no_viable_path_is_rejected() {
+        let finder = PathFinder::default();
+        let result = best_path(
+            finder.candidates(addr(WBTC), addr(USDC)),
+            GasPricing::default(),
+            quotes(vec![]),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(ExchangeError::InsufficientLiquidity(_))
+        ));
+
+        // Output entirely eaten by gas is no better than no route
+        let gas = GasPricing::new(WETH, U256::exp10(9), None);
+        let result = best_path(
+            finder.candidates(addr(USDC), addr(WETH)),
+            gas,
+            quotes(vec![(None, vec![3000], 1_000, 100_000)]),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(ExchangeError::InsufficientLiquidity(_))
+        ));
+    }
+}
@@ -5,11 +5,13 @@
 //! The Execution Engine enforces these constraints mechanically without
 //! understanding the underlying power-law logic.
 
+use crate::context::{SystemTimeProvider, TimeProvider};
 use parking_lot::RwLock;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
 use tracing::{info, warn};
 
 // --- Enums ---
@@ -203,9 +205,8 @@ impl Default for ExecutionConstraints {
 }
 
 impl ExecutionConstraints {
-    /// Check if this constraint is still valid (not expired)
-    pub fn is_valid(&self) -> bool {
-        let now = chrono::Utc::now().timestamp_millis();
+    /// Check if this constraint is still valid (not expired) at `now` (ms)
+    pub fn is_valid(&self, now: i64) -> bool {
         let expires_at = self.issued_ts + (self.ttl_ms as i64);
         now < expires_at
     }
@@ -215,15 +216,16 @@ impl ExecutionConstraints {
         matches!(self.mode, PolicyMode::Enforcement)
     }
 
-    /// Returns defensive constraints for a symbol when no constraints are available
-    pub fn defensive(venue: &str, account: &str, symbol: &str) -> Self {
+    /// Returns defensive constraints for a symbol when no constraints are available,
+    /// issued at `now` (ms)
+    pub fn defensive(venue: &str, account: &str, symbol: &str, now: i64) -> Self {
         Self {
             schema_version: "1".to_string(),
             venue: venue.to_string(),
             account: account.to_string(),
             symbol: symbol.to_string(),
             ttl_ms: 60000,
-            issued_ts: chrono::Utc::now().timestamp_millis(),
+            issued_ts: now,
             risk_mode: RiskMode::Defensive,
             mode: PolicyMode::Enforcement,
             limits: ConstraintLimits::default(),
@@ -244,13 +246,16 @@ impl ExecutionConstraints {
 pub struct ConstraintsStore {
     constraints: RwLock<HashMap<String, ExecutionConstraints>>,
     last_update_ts: AtomicI64,
+    /// Clock expiry is judged against
+    time: Arc<dyn TimeProvider>,
 }
 
 impl ConstraintsStore {
-    pub fn new() -> Self {
+    pub fn new(time: Arc<dyn TimeProvider>) -> Self {
         Self {
             constraints: RwLock::new(HashMap::new()),
             last_update_ts: AtomicI64::new(0),
+            time,
         }
     }
 
@@ -274,9 +279,10 @@ impl ConstraintsStore {
     /// Get constraints for a symbol, returns defensive fallback if missing/expired
     pub fn get(&self, venue: &str, account: &str, symbol: &str) -> ExecutionConstraints {
         let guard = self.constraints.read();
+        let now = self.time.now_millis();
 
         if let Some(constraints) = guard.get(symbol) {
-            if constraints.is_valid() {
+            if constraints.is_valid(now) {
                 return constraints.clone();
             }
             warn!(
@@ -286,13 +292,14 @@ impl ConstraintsStore {
         }
 
         // Fail-closed: return defensive constraints
-        ExecutionConstraints::defensive(venue, account, symbol)
+        ExecutionConstraints::defensive(venue, account, symbol, now)
     }
 
     /// Check if any constraints exist and are valid for a symbol
     pub fn has_valid_constraints(&self, symbol: &str) -> bool {
         let guard = self.constraints.read();
-        guard.get(symbol).map(|c| c.is_valid()).unwrap_or(false)
+        let now = self.time.now_millis();
+        guard.get(symbol).map(|c| c.is_valid(now)).unwrap_or(false)
     }
 
     /// Get the last update timestamp
@@ -308,18 +315,19 @@ impl ConstraintsStore {
 
 impl Default for ConstraintsStore {
     fn default() -> Self {
-        Self::new()
+        Self::new(Arc::new(SystemTimeProvider))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::context::MockClock;
     use rust_decimal_macros::dec;
 
     #[test]
     fn test_defensive_constraints() {
-        let c = ExecutionConstraints::defensive("bybit", "main", "BTCUSDT");
+        let c = ExecutionConstraints::defensive("bybit", "main", "BTCUSDT", 1_000);
 
         assert_eq!(c.symbol, "BTCUSDT");
         assert_eq!(c.risk_mode, RiskMode::Defensive);
@@ -330,7 +338,7 @@ mod tests {
 
     #[test]
     fn test_constraints_store_fallback() {
-        let store = ConstraintsStore::new();
+        let store = ConstraintsStore::default();
 
         // No constraints stored - should return defensive
         let c = store.get("bybit", "main", "ETHUSDT");
@@ -340,7 +348,8 @@ mod tests {
 
     #[test]
     fn test_constraints_store_update() {
-        let store = ConstraintsStore::new();
+        let clock = Arc::new(MockClock::new(1_700_000_000_000));
+        let store = ConstraintsStore::new(clock.clone());
 
         let c = ExecutionConstraints {
             symbol: "BTCUSDT".to_string(),
@@ -352,7 +361,7 @@ mod tests {
                 max_leverage: dec!(3.0),
                 reduce_only: false,
             },
-            issued_ts: chrono::Utc::now().timestamp_millis(),
+            issued_ts: clock.now_millis(),
             ttl_ms: 60000,
             ..Default::default()
         };
@@ -366,19 +375,24 @@ mod tests {
 
     #[test]
     fn test_expired_constraints_fallback() {
-        let store = ConstraintsStore::new();
+        let clock = Arc::new(MockClock::new(1_700_000_000_000));
+        let store = ConstraintsStore::new(clock.clone());
 
-        // Create expired constraints
         let c = ExecutionConstraints {
             symbol: "BTCUSDT".to_string(),
             risk_mode: RiskMode::Normal,
             mode: PolicyMode::Enforcement,
-            issued_ts: chrono::Utc::now().timestamp_millis() - 120000, // 2 minutes ago
-            ttl_ms: 60000,                                             // 1 minute TTL - expired
+            issued_ts: clock.now_millis(),
+            ttl_ms: 60000,
             ..Default::default()
         };
 
         store.update(c);
+        assert!(store.has_valid_constraints("BTCUSDT"));
+
+        // Two minutes on, the one minute TTL has run out
+        clock.advance(120_000);
+        assert!(!store.has_valid_constraints("BTCUSDT"));
 
         // Should get defensive fallback
         let retrieved = store.get("bybit", "main", "BTCUSDT");
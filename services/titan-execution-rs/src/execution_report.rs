@@ -0,0 +1,190 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use parking_lot::RwLock;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::exchange::rejection::VenueRejection;
+use crate::model::{FillReport, Side};
+
+/// Reports kept for API lookup before the oldest are evicted.
+pub const DEFAULT_REPORT_CAPACITY: usize = 1000;
+
+/// Consolidated outcome of one intent across all of its child fills.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExecutionReport {
+    pub correlation_id: String,
+    pub signal_id: String,
+    pub symbol: String,
+    pub side: Side,
+    pub total_filled: Decimal,
+    /// Volume-weighted average fill price across venues
+    pub avg_price: Decimal,
+    /// Total fees per fee asset
+    pub fees: BTreeMap<String, Decimal>,
+    /// Participating venues in order of first fill
+    pub venues: Vec<String>,
+    pub child_fills: usize,
+    pub timestamp: i64,
+    /// Child orders the venues refused, with their native codes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rejections: Vec<VenueRejection>,
+}
+
+impl ExecutionReport {
+    /// Aggregate `(exchange, fill)` pairs. Returns `None` when nothing filled.
+    pub fn from_fills(
+        correlation_id: &str,
+        fills: &[(String, FillReport)],
+        timestamp: i64,
+    ) -> Option<Self> {
+        let fills: Vec<&(String, FillReport)> = fills
+            .iter()
+            .filter(|(_, f)| f.qty > Decimal::ZERO)
+            .collect();
+        let (_, first) = fills.first()?;
+
+        let mut total_filled = Decimal::ZERO;
+        let mut notional = Decimal::ZERO;
+        let mut fees = BTreeMap::new();
+        let mut venues: Vec<String> = Vec::new();
+
+        for (exchange, fill) in &fills {
+            total_filled += fill.qty;
+            notional += fill.qty * fill.price;
+            *fees
+                .entry(fill.fee_currency.clone())
+                .or_insert(Decimal::ZERO) += fill.fee;
+            if !venues.contains(exchange) {
+                venues.push(exchange.clone());
+            }
+        }
+
+        Some(Self {
+            correlation_id: correlation_id.to_string(),
+            signal_id: first.signal_id.clone(),
+            symbol: first.symbol.clone(),
+            side: first.side.clone(),
+            total_filled,
+            avg_price: notional / total_filled,
+            fees,
+            venues,
+            child_fills: fills.len(),
+            timestamp,
+            rejections: Vec::new(),
+        })
+    }
+}
+
+/// Recent execution reports by correlation id, for the API.
+pub struct ExecutionReportStore {
+    capacity: usize,
+    inner: RwLock<(HashMap<String, ExecutionReport>, VecDeque<String>)>,
+}
+
+impl Default for ExecutionReportStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_REPORT_CAPACITY)
+    }
+}
+
+impl ExecutionReportStore {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            inner: RwLock::new((HashMap::new(), VecDeque::new())),
+        }
+    }
+
+    pub fn insert(&self, report: ExecutionReport) {
+        let mut guard = self.inner.write();
+        let (reports, order) = &mut *guard;
+        let key = report.correlation_id.clone();
+        if reports.insert(key.clone(), report).is_none() {
+            order.push_back(key);
+        }
+        while order.len() > self.capacity {
+            if let Some(oldest) = order.pop_front() {
+                reports.remove(&oldest);
+            }
+        }
+    }
+
+    pub fn get(&self, correlation_id: &str) -> Option<ExecutionReport> {
+        self.inner.read().0.get(correlation_id).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn fill(price: Decimal, qty: Decimal, fee: Decimal, fee_currency: &str) -> FillReport {
+        FillReport {
+            fill_id: format!("f-{}", price),
+            signal_id: "sig-1".to_string(),
+            symbol: "BTC/USDT".to_string(),
+            side: Side::Buy,
+            price,
+            qty,
+            fee,
+            fee_currency: fee_currency.to_string(),
+            t_signal: 0,
+            t_ingress: 0,
+            t_decision: 0,
+            t_ack: 0,
+            t_exchange: 0,
+            client_order_id: "tx-1".to_string(),
+            execution_id: format!("e-{}", price),
+            status: "FILLED".to_string(),
+            timestamp: 0,
+            dex_proof: None,
+            correlation_id: None,
+        }
+    }
+
+    #[test]
+    fn test_report_from_three_child_fills() {
+        let fills = vec![
+            (
+                "binance".to_string(),
+                fill(dec!(50000), dec!(0.5), dec!(5.0), "USDT"),
+            ),
+            (
+                "bybit".to_string(),
+                fill(dec!(50100), dec!(0.3), dec!(3.5), "USDT"),
+            ),
+            (
+                "okx".to_string(),
+                fill(dec!(49900), dec!(0.2), dec!(0.00001), "BTC"),
+            ),
+        ];
+
+        let report = ExecutionReport::from_fills("corr-1", &fills, 1_000).unwrap();
+        assert_eq!(report.total_filled, dec!(1.0));
+        // (25000 + 15030 + 9980) / 1.0
+        assert_eq!(report.avg_price, dec!(50010));
+        assert_eq!(report.fees.get("USDT"), Some(&dec!(8.5)));
+        assert_eq!(report.fees.get("BTC"), Some(&dec!(0.00001)));
+        assert_eq!(report.venues, vec!["binance", "bybit", "okx"]);
+        assert_eq!(report.child_fills, 3);
+        assert_eq!(report.signal_id, "sig-1");
+
+        assert!(ExecutionReport::from_fills("corr-2", &[], 0).is_none());
+    }
+
+    #[test]
+    fn test_store_evicts_oldest() {
+        let store = ExecutionReportStore::new(2);
+        let fills = vec![(
+            "binance".to_string(),
+            fill(dec!(100), dec!(1), dec!(0), "USDT"),
+        )];
+        for id in ["a", "b", "c"] {
+            store.insert(ExecutionReport::from_fills(id, &fills, 0).unwrap());
+        }
+        assert!(store.get("a").is_none());
+        assert_eq!(store.get("c").unwrap().correlation_id, "c");
+    }
+}
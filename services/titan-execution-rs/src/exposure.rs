@@ -11,6 +11,10 @@ pub struct ExposureMetrics {
     pub long_notional: Decimal,
     pub short_notional: Decimal,
     pub position_count: usize,
+    /// Positions still marked at their last fresh price because newer ticks
+    /// were too old to use
+    #[serde(default)]
+    pub stale_symbols: Vec<String>,
 }
 
 pub struct ExposureCalculator;
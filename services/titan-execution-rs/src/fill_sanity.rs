@@ -0,0 +1,129 @@
+use std::sync::Arc;
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::market_data::engine::MarketDataEngine;
+use crate::model::Intent;
+
+/// Max fill deviation (%) from mid used when the config leaves it unset
+pub const DEFAULT_MAX_FILL_DEVIATION_PCT: u32 = 10;
+
+/// A fill whose price is implausibly far from the market (adapter parse bug,
+/// wrong field mapped). Routed to the DLQ instead of being applied to positions.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FillAnomaly {
+    pub signal_id: String,
+    pub child_order_id: String,
+    pub symbol: String,
+    pub exchange: String,
+    pub fill_price: Decimal,
+    pub mid: Decimal,
+    pub deviation_pct: Decimal,
+}
+
+/// Sanity check of reported fill prices against the current mid for the symbol.
+/// Prefers the filling venue's book, falling back to the aggregated ticker. Fills
+/// on symbols without a usable book pass, since there is nothing to compare to.
+pub struct FillPriceGuard {
+    market_data: Arc<MarketDataEngine>,
+    max_deviation_pct: Decimal,
+}
+
+impl FillPriceGuard {
+    pub fn new(market_data: Arc<MarketDataEngine>, max_deviation_pct: Decimal) -> Self {
+        Self {
+            market_data,
+            max_deviation_pct,
+        }
+    }
+
+    fn mid(&self, exchange: &str, symbol: &str) -> Option<Decimal> {
+        let ticker = self
+            .market_data
+            .get_venue_ticker(exchange, symbol)
+            .or_else(|| self.market_data.get_ticker(symbol))?;
+        let mid = (ticker.best_bid + ticker.best_ask) / Decimal::from(2);
+        (mid > Decimal::ZERO).then_some(mid)
+    }
+
+    #[allow(clippy::result_large_err)]
+    pub fn check(
+        &self,
+        signal_id: &str,
+        child_order_id: &str,
+        symbol: &str,
+        exchange: &str,
+        fill_price: Decimal,
+    ) -> Result<(), FillAnomaly> {
+        let Some(mid) = self.mid(exchange, symbol) else {
+            return Ok(());
+        };
+        let deviation_pct = (fill_price - mid).abs() / mid * Decimal::from(100);
+        if deviation_pct <= self.max_deviation_pct {
+            return Ok(());
+        }
+        Err(FillAnomaly {
+            signal_id: signal_id.to_string(),
+            child_order_id: child_order_id.to_string(),
+            symbol: symbol.to_string(),
+            exchange: exchange.to_string(),
+            fill_price,
+            mid,
+            deviation_pct,
+        })
+    }
+}
+
+/// Over-fill tolerance (%) of intent size used when the config leaves it unset
+pub const DEFAULT_OVERFILL_TOLERANCE_PCT: u32 = 1;
+
+/// A fill that would take an intent's cumulative filled size past its size by
+/// more than the tolerance (double-counted fill, venue over-fill). Applied only
+/// up to the intent size; the excess is quarantined in the DLQ and alerted.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OverfillAnomaly {
+    pub signal_id: String,
+    pub child_order_id: String,
+    pub symbol: String,
+    pub exchange: String,
+    pub intent_size: Decimal,
+    /// Cumulative filled size before this fill
+    pub filled_size: Decimal,
+    pub fill_size: Decimal,
+    /// Part of the fill applied, up to `intent_size`
+    pub applied: Decimal,
+    /// Part of the fill not applied
+    pub excess: Decimal,
+}
+
+/// Checks that applying `fill_size` keeps the intent's cumulative fills within
+/// `intent.size` plus `tolerance_pct` percent of it.
+#[allow(clippy::result_large_err)]
+pub fn check_overfill(
+    intent: &Intent,
+    child_order_id: &str,
+    exchange: &str,
+    fill_size: Decimal,
+    tolerance_pct: Decimal,
+) -> Result<(), OverfillAnomaly> {
+    let cumulative = intent.filled_size + fill_size;
+    let limit = intent.size + intent.size * tolerance_pct / Decimal::from(100);
+    if cumulative <= limit {
+        return Ok(());
+    }
+    let applied = (intent.size - intent.filled_size)
+        .max(Decimal::ZERO)
+        .min(fill_size);
+    Err(OverfillAnomaly {
+        signal_id: intent.signal_id.clone(),
+        child_order_id: child_order_id.to_string(),
+        symbol: intent.symbol.clone(),
+        exchange: exchange.to_string(),
+        intent_size: intent.size,
+        filled_size: intent.filled_size,
+        fill_size,
+        applied,
+        excess: fill_size - applied,
+    })
+}
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use tracing::warn;
+
+use crate::config::FundingGateConfig;
+use crate::context::{SystemTimeProvider, TimeProvider};
+use crate::market_data::model::FundingRate;
+use crate::model::Intent;
+use crate::risk_guard::RiskRejectionReason;
+
+/// Blocks new perp opens shortly before a funding payment the position would pay.
+/// Longs pay positive funding, shorts pay negative funding. Only symbols with
+/// recorded funding data (i.e. perps) are gated; reduce-only intents always pass.
+pub struct FundingGate {
+    config: FundingGateConfig,
+    time: Arc<dyn TimeProvider>,
+    rates: RwLock<HashMap<String, FundingRate>>,
+}
+
+impl FundingGate {
+    pub fn new(config: FundingGateConfig) -> Self {
+        Self::with_time(config, Arc::new(SystemTimeProvider))
+    }
+
+    pub fn with_time(config: FundingGateConfig, time: Arc<dyn TimeProvider>) -> Self {
+        Self {
+            config,
+            time,
+            rates: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn key(symbol: &str) -> String {
+        symbol.replace("/", "").replace("_", "").to_uppercase()
+    }
+
+    /// Latest funding snapshot from the funding feed.
+    pub fn record(&self, rate: FundingRate) {
+        self.rates.write().insert(Self::key(&rate.symbol), rate);
+    }
+
+    pub fn check(&self, intent: &Intent, reduces_risk: bool) -> Result<(), RiskRejectionReason> {
+        if !self.config.enabled || reduces_risk || intent.direction == 0 {
+            return Ok(());
+        }
+        let Some(funding) = self.rates.read().get(&Self::key(&intent.symbol)).cloned() else {
+            return Ok(());
+        };
+
+        let now_ms = self.time.now_millis();
+        let ms_to_funding = funding.next_funding_time.timestamp_millis() - now_ms;
+        if ms_to_funding < 0 || ms_to_funding > self.config.window_secs * 1000 {
+            return Ok(());
+        }
+
+        // Rate paid by this side, in bps: positive means the new position pays
+        let paid_bps =
+            funding.rate * Decimal::from(10_000) * Decimal::from(intent.direction.signum());
+        if paid_bps.to_f64().unwrap_or(0.0) <= self.config.max_adverse_rate_bps {
+            return Ok(());
+        }
+
+        warn!(
+            signal_id = %intent.signal_id,
+            symbol = %intent.symbol,
+            rate = %funding.rate,
+            ms_to_funding,
+            "Risk Reject: adverse funding imminent"
+        );
+        Err(RiskRejectionReason::AdverseFundingImminent {
+            symbol: intent.symbol.clone(),
+            rate_bps: paid_bps,
+            seconds_to_funding: ms_to_funding / 1000,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::SimulatedTimeProvider;
+    use crate::model::{IntentStatus, IntentType};
+    use chrono::{TimeZone, Utc};
+    use rust_decimal_macros::dec;
+
+    const NOW_MS: i64 = 1_700_000_000_000;
+
+    fn gate() -> FundingGate {
+        FundingGate::with_time(
+            FundingGateConfig {
+                enabled: true,
+                window_secs: 300,
+                max_adverse_rate_bps: 1.0,
+            },
+            Arc::new(SimulatedTimeProvider::new(NOW_MS)),
+        )
+    }
+
+    fn funding(rate: Decimal, in_secs: i64) -> FundingRate {
+        FundingRate {
+            symbol: "BTCUSDT".to_string(),
+            rate,
+            timestamp: Utc.timestamp_millis_opt(NOW_MS).unwrap(),
+            next_funding_time: Utc.timestamp_millis_opt(NOW_MS + in_secs * 1000).unwrap(),
+            exchange: "binance".to_string(),
+        }
+    }
+
+    fn intent(direction: i32, intent_type: IntentType) -> Intent {
+        serde_json::from_value(serde_json::json!({
+            "signal_id": "sig-funding",
+            "symbol": "BTC/USDT",
+            "direction": direction,
+            "type": "BUY_SETUP",
+            "size": 1.0,
+            "status": "PENDING",
+            "t_signal": NOW_MS,
+        }))
+        .map(|mut i: Intent| {
+            i.intent_type = intent_type;
+            i.status = IntentStatus::Pending;
+            i
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_adverse_funding_blocks_open() {
+        let gate = gate();
+        // 5 bps paid by longs, two minutes out
+        gate.record(funding(dec!(0.0005), 120));
+
+        let result = gate.check(&intent(1, IntentType::BuySetup), false);
+        assert!(matches!(
+            result,
+            Err(RiskRejectionReason::AdverseFundingImminent {
+                seconds_to_funding: 120,
+                ..
+            })
+        ));
+
+        // Reduce-only exits are never gated
+        assert!(gate.check(&intent(1, IntentType::CloseLong), true).is_ok());
+    }
+
+    #[test]
+    fn test_benign_funding_allows_open() {
+        let gate = gate();
+        // Shorts receive positive funding
+        gate.record(funding(dec!(0.0005), 120));
+        assert!(gate
+            .check(&intent(-1, IntentType::SellSetup), false)
+            .is_ok());
+
+        // Adverse but below threshold
+        gate.record(funding(dec!(0.00005), 120));
+        assert!(gate.check(&intent(1, IntentType::BuySetup), false).is_ok());
+
+        // Adverse but funding is outside the window
+        gate.record(funding(dec!(0.0005), 3_600));
+        assert!(gate.check(&intent(1, IntentType::BuySetup), false).is_ok());
+    }
+}
@@ -0,0 +1,242 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::RwLock;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::config::FundingSyncConfig;
+use crate::context::ExecutionContext;
+use crate::event_log::EventLog;
+use crate::exchange::adapter::ExchangeError;
+use crate::exchange::router::ExecutionRouter;
+use crate::model::Position;
+use crate::shadow_state::{ExecutionEvent, ShadowState};
+use crate::startup_reconciliation::symbol_key;
+
+/// Periodically pulls the funding each venue settled on our positions and
+/// applies it to shadow state, which keeps the per-payment history and skips
+/// epochs already applied. Venues without a funding history are skipped.
+pub struct FundingSync {
+    router: Arc<ExecutionRouter>,
+    shadow_state: Arc<RwLock<ShadowState>>,
+    event_log: Arc<EventLog>,
+    ctx: Arc<ExecutionContext>,
+    config: FundingSyncConfig,
+}
+
+impl FundingSync {
+    pub fn new(
+        router: Arc<ExecutionRouter>,
+        shadow_state: Arc<RwLock<ShadowState>>,
+        event_log: Arc<EventLog>,
+        ctx: Arc<ExecutionContext>,
+        config: FundingSyncConfig,
+    ) -> Self {
+        Self {
+            router,
+            shadow_state,
+            event_log,
+            ctx,
+            config,
+        }
+    }
+
+    pub fn start(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_millis(self.config.interval_ms));
+            loop {
+                interval.tick().await;
+                self.run_once().await;
+            }
+        })
+    }
+
+    /// Pull and apply once. Returns the funding events applied.
+    pub async fn run_once(&self) -> Vec<ExecutionEvent> {
+        let now = self.ctx.time.now_millis();
+        let since = now - self.config.lookback_ms;
+        let mut events = Vec::new();
+        for (venue, adapter) in self.router.adapters() {
+            let payments = match adapter.get_funding_payments(since).await {
+                Ok(payments) => payments,
+                Err(ExchangeError::NotImplemented(_)) => continue,
+                Err(e) => {
+                    warn!("Funding sync skipped {}: {}", venue, e);
+                    continue;
+                }
+            };
+
+            for payment in payments {
+                let positions = self.shadow_state.read().get_all_positions();
+                let Some(position) = positions
+                    .values()
+                    .find(|p| holds_on(p, &venue, &payment.symbol))
+                else {
+                    continue;
+                };
+                // Settlements from before this position opened belong to an earlier one
+                if payment.funding_time < position.opened_at.timestamp_millis() {
+                    continue;
+                }
+                let symbol = position.symbol.clone();
+                let event = self.shadow_state.write().apply_funding(
+                    &venue,
+                    &symbol,
+                    payment.amount,
+                    payment.rate,
+                    payment.asset,
+                    payment.funding_time,
+                );
+                let Some(event) = event else {
+                    continue;
+                };
+                if let Err(e) = self.event_log.append_execution_event(&event, None, now) {
+                    error!("Failed to record funding for {}: {}", symbol, e);
+                }
+                events.push(event);
+            }
+        }
+        if !events.is_empty() {
+            info!("💸 Funding sync applied {} settlements", events.len());
+        }
+        events
+    }
+}
+
+/// Whether `position` is the one `venue` reports as `venue_symbol`
+fn holds_on(position: &Position, venue: &str, venue_symbol: &str) -> bool {
+    symbol_key(&position.symbol) == symbol_key(venue_symbol)
+        && position
+            .exchange
+            .as_deref()
+            .is_none_or(|exchange| exchange.eq_ignore_ascii_case(venue))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_log::TYPE_FUNDING_PAID;
+    use crate::exchange::adapter::FundingPayment;
+    use crate::model::Side;
+    use crate::persistence::redb_store::RedbStore;
+    use crate::persistence::store::PersistenceStore;
+    use crate::persistence::wal::WalManager;
+    use crate::test_support::MockAdapter;
+    use chrono::TimeZone;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    const NOW_MS: i64 = 1_700_000_000_000;
+    const HOUR_MS: i64 = 3_600_000;
+
+    fn position(opened_at_ms: i64) -> Position {
+        Position {
+            symbol: "BTC/USDT".to_string(),
+            side: Side::Long,
+            size: dec!(1),
+            entry_price: dec!(50000),
+            stop_loss: Decimal::ZERO,
+            take_profits: vec![],
+            signal_id: "sig-1".to_string(),
+            opened_at: chrono::Utc.timestamp_millis_opt(opened_at_ms).unwrap(),
+            regime_state: None,
+            phase: None,
+            metadata: None,
+            exchange: Some("bybit".to_string()),
+            position_mode: None,
+            realized_pnl: Decimal::ZERO,
+            unrealized_pnl: Decimal::ZERO,
+            fees_paid: Decimal::ZERO,
+            funding_paid: Decimal::ZERO,
+            last_mark_price: None,
+            last_update_ts: 0,
+            entry_lots: Vec::new(),
+        }
+    }
+
+    fn payment(symbol: &str, amount: Decimal, funding_time: i64) -> FundingPayment {
+        FundingPayment {
+            symbol: symbol.to_string(),
+            amount,
+            rate: Some(dec!(0.0001)),
+            asset: "USDT".to_string(),
+            funding_time,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_venue_settlements_applied_once_and_recorded() {
+        let path = format!("/tmp/test_funding_sync_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let persistence = Arc::new(PersistenceStore::new(redb, wal));
+        persistence
+            .save_position(&position(NOW_MS - 10 * HOUR_MS))
+            .unwrap();
+
+        let ctx = Arc::new(ExecutionContext::new_simulated(NOW_MS));
+        let shadow_state = Arc::new(RwLock::new(ShadowState::new(
+            persistence.clone(),
+            ctx.clone(),
+            Some(10_000.0),
+        )));
+        let router = Arc::new(ExecutionRouter::new());
+        router.register(
+            "bybit",
+            Arc::new(MockAdapter::new("bybit").with_funding_payments(vec![
+                // Settled on a position held before this one opened
+                payment("BTCUSDT", dec!(9), NOW_MS - 16 * HOUR_MS),
+                payment("BTCUSDT", dec!(5), NOW_MS - 8 * HOUR_MS),
+                payment("BTCUSDT", dec!(-2), NOW_MS),
+                // No shadow position
+                payment("ETHUSDT", dec!(1), NOW_MS),
+            ])),
+        );
+        // No funding history at all
+        router.register("kraken", Arc::new(MockAdapter::new("kraken")));
+
+        let event_log = Arc::new(EventLog::new(persistence.clone()));
+        let sync = FundingSync::new(
+            router,
+            shadow_state.clone(),
+            event_log.clone(),
+            ctx,
+            FundingSyncConfig::default(),
+        );
+
+        assert_eq!(sync.run_once().await.len(), 2);
+        {
+            let state = shadow_state.read();
+            assert_eq!(
+                state.get_position("BTC/USDT").unwrap().funding_paid,
+                dec!(3)
+            );
+            assert_eq!(state.get_cash_balance(), dec!(9997));
+        }
+        let history = persistence
+            .load_funding_history(Some("BTC/USDT"), None)
+            .unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].exchange, "bybit");
+        assert_eq!(history[0].amount, dec!(5));
+
+        let logged = event_log.replay_from(1, 10).unwrap();
+        assert_eq!(logged.len(), 2);
+        assert_eq!(logged[0].event_type, TYPE_FUNDING_PAID);
+
+        // The next pull sees the same settlements and applies none of them again
+        assert!(sync.run_once().await.is_empty());
+        assert_eq!(
+            shadow_state
+                .read()
+                .get_position("BTC/USDT")
+                .unwrap()
+                .funding_paid,
+            dec!(3)
+        );
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+}
@@ -0,0 +1,379 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, warn};
+
+use crate::alerts::{Alert, AlertSeverity, AlertSink, KIND_HEARTBEAT_LOST};
+use crate::circuit_breaker::{GlobalHalt, HaltLevel};
+use crate::config::{HeartbeatLossConfig, HeartbeatLossResponse};
+use crate::context::TimeProvider;
+use crate::metrics;
+use crate::persistence::redb_store::StoreError;
+use crate::persistence::store::PersistenceStore;
+use crate::shutdown::Flattener;
+
+/// Metadata key of the derived heartbeat state, so a loss survives a restart
+const HEARTBEAT_STATE_KEY: &str = "heartbeat_state";
+
+/// Derived Brain heartbeat state, as persisted and served by the status API.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HeartbeatStatus {
+    pub lost: bool,
+    pub lost_since: Option<i64>,
+    pub last_heartbeat: i64,
+    pub gap_ms: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeartbeatTransition {
+    Lost { gap_ms: i64 },
+    Restored { lost_for_ms: i64 },
+}
+
+struct MonitorState {
+    last_heartbeat: i64,
+    lost_since: Option<i64>,
+    /// First heartbeat since the loss without another gap, for the recovery dwell
+    resumed_at: Option<i64>,
+}
+
+/// Tracks Brain's heartbeat and derives lost / healthy with hysteresis: lost
+/// once the gap passes `timeout_ms`, healthy again only after heartbeats have
+/// flowed for `recovery_ms` without another gap.
+pub struct HeartbeatMonitor {
+    config: HeartbeatLossConfig,
+    time: Arc<dyn TimeProvider>,
+    state: Mutex<MonitorState>,
+    persistence: Option<Arc<PersistenceStore>>,
+}
+
+impl HeartbeatMonitor {
+    pub fn new(config: HeartbeatLossConfig, time: Arc<dyn TimeProvider>) -> Self {
+        let now = time.now_millis();
+        Self {
+            config,
+            time,
+            state: Mutex::new(MonitorState {
+                last_heartbeat: now,
+                lost_since: None,
+                resumed_at: None,
+            }),
+            persistence: None,
+        }
+    }
+
+    /// Persist the derived state, restoring a loss recorded before a restart.
+    /// A restored loss holds until heartbeats flow for the recovery dwell.
+    pub fn with_persistence(mut self, persistence: Arc<PersistenceStore>) -> Self {
+        match persistence.load_metadata(HEARTBEAT_STATE_KEY) {
+            Ok(Some(value)) => match serde_json::from_value::<HeartbeatStatus>(value) {
+                Ok(stored) if stored.lost => {
+                    warn!("💔 Brain heartbeat was lost before restart; staying in loss response");
+                    self.state.get_mut().lost_since = stored.lost_since;
+                    metrics::set_heartbeat_lost(true);
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Ignoring unreadable heartbeat state: {}", e),
+            },
+            Ok(None) => {}
+            Err(e) => error!("❌ Failed to load heartbeat state: {}", e),
+        }
+        self.persistence = Some(persistence);
+        self
+    }
+
+    pub fn response(&self) -> HeartbeatLossResponse {
+        self.config.response
+    }
+
+    pub fn check_interval(&self) -> Duration {
+        Duration::from_millis(self.config.check_interval_ms)
+    }
+
+    pub fn record_heartbeat(&self) {
+        let now = self.time.now_millis();
+        let mut state = self.state.lock();
+        state.last_heartbeat = now;
+        if state.lost_since.is_some() && state.resumed_at.is_none() {
+            state.resumed_at = Some(now);
+        }
+    }
+
+    pub fn is_lost(&self) -> bool {
+        self.state.lock().lost_since.is_some()
+    }
+
+    /// Lost, or past the timeout but not yet evaluated. For hot-path checks
+    /// that must not wait for the next evaluation tick.
+    pub fn is_stale(&self) -> bool {
+        let state = self.state.lock();
+        state.lost_since.is_some()
+            || self.time.now_millis() - state.last_heartbeat > self.config.timeout_ms
+    }
+
+    pub fn status(&self) -> HeartbeatStatus {
+        let state = self.state.lock();
+        HeartbeatStatus {
+            lost: state.lost_since.is_some(),
+            lost_since: state.lost_since,
+            last_heartbeat: state.last_heartbeat,
+            gap_ms: self.time.now_millis() - state.last_heartbeat,
+        }
+    }
+
+    /// Re-derive the state, returning the transition if it changed.
+    pub fn evaluate(&self) -> Option<HeartbeatTransition> {
+        let now = self.time.now_millis();
+        let transition = {
+            let mut state = self.state.lock();
+            let gap_ms = now - state.last_heartbeat;
+            match state.lost_since {
+                None if gap_ms > self.config.timeout_ms => {
+                    state.lost_since = Some(now);
+                    state.resumed_at = None;
+                    HeartbeatTransition::Lost { gap_ms }
+                }
+                None => return None,
+                Some(_) if gap_ms > self.config.timeout_ms => {
+                    // Another gap: the recovery dwell starts over
+                    state.resumed_at = None;
+                    return None;
+                }
+                Some(lost_since) => match state.resumed_at {
+                    Some(resumed_at) if now - resumed_at >= self.config.recovery_ms => {
+                        state.lost_since = None;
+                        state.resumed_at = None;
+                        HeartbeatTransition::Restored {
+                            lost_for_ms: now - lost_since,
+                        }
+                    }
+                    _ => return None,
+                },
+            }
+        };
+
+        match transition {
+            HeartbeatTransition::Lost { gap_ms } => warn!(
+                "💔 Brain heartbeat LOST ({}ms since last); response: {}",
+                gap_ms,
+                self.config.response.as_str()
+            ),
+            HeartbeatTransition::Restored { lost_for_ms } => {
+                info!("💓 Brain heartbeat RESTORED after {}ms", lost_for_ms)
+            }
+        }
+        metrics::set_heartbeat_lost(matches!(transition, HeartbeatTransition::Lost { .. }));
+        self.persist();
+        Some(transition)
+    }
+
+    fn persist(&self) {
+        let Some(persistence) = &self.persistence else {
+            return;
+        };
+        let saved = serde_json::to_value(self.status())
+            .map_err(StoreError::from)
+            .and_then(|value| persistence.save_metadata(HEARTBEAT_STATE_KEY, value));
+        if let Err(e) = saved {
+            error!("❌ Failed to persist heartbeat state: {}", e);
+        }
+    }
+}
+
+/// Applies the configured response when the heartbeat is lost and lifts it
+/// once the monitor reports it restored. Only a halt this responder engaged
+/// is lifted.
+pub struct HeartbeatResponder {
+    monitor: Arc<HeartbeatMonitor>,
+    halt: Arc<GlobalHalt>,
+    flattener: Option<Arc<Flattener>>,
+    alert_sink: Option<Arc<dyn AlertSink>>,
+    halted: AtomicBool,
+}
+
+impl HeartbeatResponder {
+    pub fn new(monitor: Arc<HeartbeatMonitor>, halt: Arc<GlobalHalt>) -> Self {
+        // A loss restored from before a restart left its halt in the lockfile
+        let halted = monitor.is_lost()
+            && monitor.response() != HeartbeatLossResponse::Defensive
+            && halt.level() == HaltLevel::Hard;
+        Self {
+            monitor,
+            halt,
+            flattener: None,
+            alert_sink: None,
+            halted: AtomicBool::new(halted),
+        }
+    }
+
+    pub fn with_flattener(mut self, flattener: Arc<Flattener>) -> Self {
+        self.flattener = Some(flattener);
+        self
+    }
+
+    pub fn with_alert_sink(mut self, sink: Arc<dyn AlertSink>) -> Self {
+        self.alert_sink = Some(sink);
+        self
+    }
+
+    /// Evaluate the monitor once and act on any transition.
+    pub async fn check(&self) -> Option<HeartbeatTransition> {
+        let transition = self.monitor.evaluate()?;
+        match transition {
+            HeartbeatTransition::Lost { gap_ms } => self.on_lost(gap_ms).await,
+            HeartbeatTransition::Restored { lost_for_ms } => self.on_restored(lost_for_ms),
+        }
+        Some(transition)
+    }
+
+    async fn on_lost(&self, gap_ms: i64) {
+        let response = self.monitor.response();
+        let reason = format!("Brain heartbeat lost ({}ms since last)", gap_ms);
+        self.alert(
+            if response == HeartbeatLossResponse::Defensive {
+                AlertSeverity::Warning
+            } else {
+                AlertSeverity::Critical
+            },
+            format!("{}; response: {}", reason, response.as_str()),
+        );
+        if response == HeartbeatLossResponse::Defensive {
+            return;
+        }
+
+        if self.halt.level() != HaltLevel::Hard {
+            self.halt.set_halt(true, &reason);
+            self.halted.store(true, Ordering::SeqCst);
+        }
+        if response == HeartbeatLossResponse::Flatten {
+            match &self.flattener {
+                Some(flattener) => {
+                    flattener.flatten(&reason).await;
+                }
+                None => error!("❌ Heartbeat loss response is flatten but no flattener is set"),
+            }
+        }
+    }
+
+    fn on_restored(&self, lost_for_ms: i64) {
+        let reason = format!("Brain heartbeat restored after {}ms", lost_for_ms);
+        if self.halted.swap(false, Ordering::SeqCst) && self.halt.level() == HaltLevel::Hard {
+            self.halt.set_halt(false, &reason);
+        }
+        self.alert(AlertSeverity::Info, reason);
+    }
+
+    fn alert(&self, severity: AlertSeverity, message: String) {
+        if let Some(sink) = &self.alert_sink {
+            sink.send(Alert::new(severity, KIND_HEARTBEAT_LOST, message));
+        }
+    }
+
+    /// Evaluate on the monitor's check interval until the task is dropped.
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.monitor.check_interval());
+            loop {
+                interval.tick().await;
+                self.check().await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::SimulatedTimeProvider;
+    use crate::persistence::redb_store::RedbStore;
+    use crate::persistence::wal::WalManager;
+
+    fn config(response: HeartbeatLossResponse) -> HeartbeatLossConfig {
+        HeartbeatLossConfig {
+            timeout_ms: 5_000,
+            response,
+            recovery_ms: 3_000,
+            check_interval_ms: 1_000,
+        }
+    }
+
+    fn temp_halt() -> (Arc<GlobalHalt>, std::path::PathBuf) {
+        let path = std::env::temp_dir().join(format!("titan_halt_{}", uuid::Uuid::new_v4()));
+        (Arc::new(GlobalHalt::with_file(&path)), path)
+    }
+
+    #[tokio::test]
+    async fn test_halt_engages_on_loss_and_clears_after_recovery() {
+        let time = Arc::new(SimulatedTimeProvider::new(0));
+        let monitor = Arc::new(HeartbeatMonitor::new(
+            config(HeartbeatLossResponse::Halt),
+            time.clone(),
+        ));
+        let (halt, path) = temp_halt();
+        let responder = HeartbeatResponder::new(monitor.clone(), halt.clone());
+
+        time.advance(5_000);
+        assert_eq!(responder.check().await, None, "At the timeout, not past it");
+        time.advance(1);
+        assert_eq!(
+            responder.check().await,
+            Some(HeartbeatTransition::Lost { gap_ms: 5_001 })
+        );
+        assert!(halt.is_halted());
+        assert!(monitor.status().lost);
+
+        // Heartbeats resume, but a new gap restarts the recovery dwell
+        monitor.record_heartbeat();
+        time.advance(2_000);
+        assert_eq!(responder.check().await, None);
+        time.advance(5_001);
+        assert_eq!(responder.check().await, None);
+        assert!(halt.is_halted());
+
+        for _ in 0..3 {
+            monitor.record_heartbeat();
+            time.advance(1_000);
+            responder.check().await;
+        }
+        monitor.record_heartbeat();
+        assert!(!halt.is_halted(), "Lifted once heartbeats flowed for 3s");
+        assert!(!monitor.is_lost());
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_defensive_response_leaves_halt_and_persists_loss() {
+        let time = Arc::new(SimulatedTimeProvider::new(0));
+        let db_path = format!("/tmp/test_heartbeat_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&db_path).unwrap());
+        let persistence = Arc::new(PersistenceStore::new(
+            redb.clone(),
+            Arc::new(WalManager::new(redb)),
+        ));
+        let monitor = Arc::new(
+            HeartbeatMonitor::new(config(HeartbeatLossResponse::Defensive), time.clone())
+                .with_persistence(persistence.clone()),
+        );
+        let (halt, path) = temp_halt();
+        let responder = HeartbeatResponder::new(monitor.clone(), halt.clone());
+
+        time.advance(6_000);
+        assert!(monitor.is_stale(), "Stale before the next evaluation");
+        responder.check().await;
+        assert!(!halt.is_halted());
+
+        // The loss survives a restart until heartbeats return
+        let restarted = HeartbeatMonitor::new(config(HeartbeatLossResponse::Defensive), time)
+            .with_persistence(persistence);
+        assert!(restarted.is_lost());
+        assert_eq!(restarted.status().lost_since, Some(6_000));
+
+        std::fs::remove_file(path).unwrap_or(());
+        std::fs::remove_file(db_path).unwrap_or(());
+    }
+}
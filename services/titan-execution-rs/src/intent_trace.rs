@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::persistence::store::PersistenceStore;
+
+/// Point in an intent's life recorded on its trace.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceStageKind {
+    Received,
+    RiskChecked,
+    Validated,
+    Routed,
+    ChildPlaced,
+    ChildFilled,
+    ChildFailed,
+    Closed,
+}
+
+/// One timestamped entry of an intent trace.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TraceStage {
+    pub stage: TraceStageKind,
+    pub ts: i64,
+    /// Stage specifics: venue, order id, rejection reason, ...
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Full timeline of one intent, keyed by correlation id.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IntentTrace {
+    pub correlation_id: String,
+    pub stages: Vec<TraceStage>,
+}
+
+/// Appends lifecycle stages of every intent to the persisted trace table.
+/// Tracing is best effort: a failed write is logged and never fails the intent.
+pub struct IntentTracer {
+    persistence: Arc<PersistenceStore>,
+}
+
+impl IntentTracer {
+    pub fn new(persistence: Arc<PersistenceStore>) -> Self {
+        Self { persistence }
+    }
+
+    pub fn record(
+        &self,
+        correlation_id: &str,
+        stage: TraceStageKind,
+        ts: i64,
+        detail: Option<String>,
+    ) {
+        let entry = TraceStage { stage, ts, detail };
+        let result = serde_json::to_value(&entry)
+            .map_err(Into::into)
+            .and_then(|value| self.persistence.append_trace_stage(correlation_id, value));
+        if let Err(e) = result {
+            warn!(correlation_id = %correlation_id, "Failed to record {:?} trace stage: {}", stage, e);
+        }
+    }
+
+    /// Recorded timeline of `correlation_id`, oldest stage first.
+    pub fn get(&self, correlation_id: &str) -> Option<IntentTrace> {
+        let stages = match self.persistence.load_trace(correlation_id) {
+            Ok(stages) => stages?,
+            Err(e) => {
+                warn!(correlation_id = %correlation_id, "Failed to load trace: {}", e);
+                return None;
+            }
+        };
+        let stages = stages
+            .into_iter()
+            .filter_map(|v| serde_json::from_value(v).ok())
+            .collect();
+        Some(IntentTrace {
+            correlation_id: correlation_id.to_string(),
+            stages,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::redb_store::RedbStore;
+    use crate::persistence::wal::WalManager;
+
+    #[test]
+    fn test_stages_append_in_order_per_correlation_id() {
+        let path = format!("/tmp/test_intent_trace_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let tracer = IntentTracer::new(Arc::new(PersistenceStore::new(redb, wal)));
+
+        assert!(tracer.get("corr-1").is_none());
+
+        tracer.record("corr-1", TraceStageKind::Received, 1, None);
+        tracer.record("corr-2", TraceStageKind::Received, 2, None);
+        tracer.record(
+            "corr-1",
+            TraceStageKind::Closed,
+            3,
+            Some("rejected".to_string()),
+        );
+
+        let trace = tracer.get("corr-1").unwrap();
+        assert_eq!(
+            trace.stages,
+            vec![
+                TraceStage {
+                    stage: TraceStageKind::Received,
+                    ts: 1,
+                    detail: None
+                },
+                TraceStage {
+                    stage: TraceStageKind::Closed,
+                    ts: 3,
+                    detail: Some("rejected".to_string())
+                },
+            ]
+        );
+        assert_eq!(tracer.get("corr-2").unwrap().stages.len(), 1);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+}
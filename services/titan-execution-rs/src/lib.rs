@@ -1,36 +1,68 @@
+pub mod admission;
+pub mod alerts;
 pub mod api;
 pub mod armed_state;
+pub mod balances;
 pub mod circuit_breaker;
+pub mod client_order_id;
 pub mod config;
+pub mod config_reload;
 pub mod context;
 pub mod contracts;
+pub mod dead_mans_switch;
+pub mod depth_gate;
 pub mod dex_validator;
+pub mod dlq;
 pub mod drift_detector;
 pub mod engine;
+pub mod entry_zone;
+pub mod event_log;
 pub mod exchange;
 pub mod execution_constraints;
+pub mod execution_report;
 pub mod exposure;
+pub mod fill_sanity;
+pub mod funding_gate;
+pub mod funding_sync;
+pub mod heartbeat;
 pub mod impact_calculator;
+pub mod intent_trace;
 pub mod intent_validation;
+pub mod maintenance_mode;
 pub mod market_data;
 pub mod metrics;
 pub mod model;
 pub mod nats_engine;
 pub mod order_fsm;
 pub mod order_manager;
+pub mod panic_watchdog;
+pub mod partial_fill;
 pub mod performance;
 pub mod persistence;
 pub mod pipeline;
+pub mod position_sizing;
+pub mod position_sync;
 pub mod rate_limiter;
+pub mod rebalance;
+pub mod rejection_breaker;
 pub mod replay_engine;
 pub mod replay_model;
+pub mod repricer;
 pub mod risk_guard;
 pub mod risk_policy;
 pub mod risk_state_manager;
 pub mod security;
 pub mod shadow_state;
+pub mod shutdown;
 pub mod simulation_engine;
+pub mod spread_gate;
 pub mod sre;
 pub mod staleness;
+pub mod startup_reconciliation;
 pub mod subjects;
+#[cfg(test)]
+pub mod test_support;
 pub mod tests;
+pub mod tp_ladder;
+pub mod trade_retention;
+pub mod volatility;
@@ -4,14 +4,23 @@ use actix_web::{web, App, HttpServer};
 use actix_web_prom::PrometheusMetricsBuilder;
 use auth_middleware::AuthMiddleware;
 use parking_lot::RwLock;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
 use std::env;
 use std::fs;
+use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
+use titan_execution_rs::alerts::{AlertSink, WebhookAlertSink};
 use titan_execution_rs::api;
 use titan_execution_rs::armed_state::ArmedState;
-use titan_execution_rs::circuit_breaker::GlobalHalt;
+use titan_execution_rs::circuit_breaker::{DrawdownBreaker, GlobalHalt};
+use titan_execution_rs::config_reload::{ConfigReloader, DEFAULT_FRESHNESS_THRESHOLD_MS};
 use titan_execution_rs::context::ExecutionContext;
+use titan_execution_rs::dead_mans_switch::DeadMansSwitch;
+use titan_execution_rs::depth_gate::DepthGate;
 use titan_execution_rs::drift_detector::DriftDetector;
+use titan_execution_rs::entry_zone::EntryZoneExecutor;
+use titan_execution_rs::event_log::EventLog;
 use titan_execution_rs::exchange::adapter::ExchangeAdapter;
 use titan_execution_rs::exchange::binance::BinanceAdapter;
 use titan_execution_rs::exchange::bybit::BybitAdapter;
@@ -28,23 +37,45 @@ use titan_execution_rs::exchange::kucoin::KucoinAdapter;
 use titan_execution_rs::exchange::mexc::MexcAdapter;
 use titan_execution_rs::exchange::okx::OkxAdapter;
 use titan_execution_rs::exchange::pancakeswap::PancakeSwapAdapter;
-use titan_execution_rs::exchange::router::ExecutionRouter;
+use titan_execution_rs::exchange::router::{ExecutionRouter, LeverageLimits};
 use titan_execution_rs::exchange::sushiswap::SushiSwapAdapter;
 use titan_execution_rs::exchange::uniswap::UniswapAdapter;
 use titan_execution_rs::execution_constraints::ConstraintsStore;
+use titan_execution_rs::execution_report::ExecutionReportStore;
+use titan_execution_rs::fill_sanity::{
+    FillPriceGuard, DEFAULT_MAX_FILL_DEVIATION_PCT, DEFAULT_OVERFILL_TOLERANCE_PCT,
+};
+use titan_execution_rs::funding_gate::FundingGate;
+use titan_execution_rs::funding_sync::FundingSync;
+use titan_execution_rs::heartbeat::{HeartbeatMonitor, HeartbeatResponder};
+use titan_execution_rs::intent_trace::IntentTracer;
+use titan_execution_rs::maintenance_mode::MaintenanceMode;
 use titan_execution_rs::market_data::engine::MarketDataEngine;
 use titan_execution_rs::nats_engine;
-use titan_execution_rs::order_manager::OrderManager;
+use titan_execution_rs::order_manager::{OrderManager, OrderManagerConfig};
+use titan_execution_rs::panic_watchdog::PanicWatchdog;
+use titan_execution_rs::partial_fill::PartialFillCanceller;
 use titan_execution_rs::persistence::redb_store::RedbStore;
 use titan_execution_rs::persistence::store::PersistenceStore;
 use titan_execution_rs::persistence::wal::WalManager;
+use titan_execution_rs::position_sync::PositionSync;
+use titan_execution_rs::rebalance::PositionTransfer;
+use titan_execution_rs::rejection_breaker::RejectionRateBreaker;
+use titan_execution_rs::repricer::LimitRepricer;
 use titan_execution_rs::risk_guard::RiskGuard;
 use titan_execution_rs::risk_policy::RiskPolicy;
 use titan_execution_rs::shadow_state::ShadowState;
+use titan_execution_rs::shutdown::{Flattener, ShutdownReport};
 use titan_execution_rs::simulation_engine::SimulationEngine;
+use titan_execution_rs::spread_gate::SpreadGate;
 use titan_execution_rs::sre::SreMonitor;
+use titan_execution_rs::staleness::DEFAULT_RECONNECT_WARMUP_TICKS;
+use titan_execution_rs::startup_reconciliation::{cancel_orphan_orders, StartupReconciler};
 use titan_execution_rs::subjects; // Canonical Subjects
-                                  // use tracing_subscriber::FmtSubscriber;
+use titan_execution_rs::tp_ladder::TpLadderExecutor;
+use titan_execution_rs::trade_retention::TradeRetentionPruner;
+use titan_execution_rs::volatility::VolatilityTracker;
+// use tracing_subscriber::FmtSubscriber;
 
 fn load_secrets_from_files() {
     const FILE_SUFFIX: &str = "_FILE";
@@ -133,8 +164,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let allow_empty = env::var("HMAC_ALLOW_EMPTY_SECRET")
             .map(|v| v == "true")
             .unwrap_or(false);
+        let ed25519_only = env::var("INTENT_ED25519_PUBLIC_KEY")
+            .map(|k| !k.is_empty())
+            .unwrap_or(false);
 
-        if hmac_secret.is_empty() && !allow_empty {
+        if hmac_secret.is_empty() && ed25519_only {
+            info!(
+                "🔐 HMAC_SECRET not set; intents verified with Ed25519 only (HMAC-signed risk commands will be rejected)"
+            );
+        } else if hmac_secret.is_empty() && !allow_empty {
             error!(
                 "❌ FATAL: HMAC_SECRET environment variable is required for production. \
                  Set HMAC_ALLOW_EMPTY_SECRET=true only for testing."
@@ -213,7 +251,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     };
 
     // Load Configuration
-    use titan_execution_rs::config::Settings;
+    use titan_execution_rs::config::{HeartbeatLossResponse, MarketType, Settings, SizingMode};
     let settings = Settings::new().expect("❌ critical: Failed to load configuration");
     let exchanges = settings.exchanges.as_ref();
 
@@ -231,33 +269,183 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let initial_balance = execution_config.initial_balance;
 
     let shadow_state = Arc::new(RwLock::new(ShadowState::new(
-        persistence,
+        persistence.clone(),
         ctx.clone(),
         initial_balance,
     )));
 
     // Initialize Market Data Engine (Truth Layer) - Moved up for dependency injection
-    let market_data_engine = Arc::new(MarketDataEngine::new(Some(nats_client.clone())));
+    let market_data_engine = Arc::new(
+        MarketDataEngine::new(Some(nats_client.clone()))
+            .with_tick_history(&execution_config.tick_history)
+            .with_source_priority(&execution_config.market_data_sources)
+            .with_book_sanity(&execution_config.book_sanity),
+    );
     let _md_handle = market_data_engine.start().await;
     info!("✅ Market Data Engine started");
 
+    // Fill price sanity check against the live mid
+    let max_fill_deviation_pct = execution_config
+        .max_fill_deviation_pct
+        .and_then(Decimal::from_f64)
+        .unwrap_or(Decimal::from(DEFAULT_MAX_FILL_DEVIATION_PCT));
+    shadow_state
+        .write()
+        .set_fill_price_guard(FillPriceGuard::new(
+            market_data_engine.clone(),
+            max_fill_deviation_pct,
+        ));
+    info!(
+        "✅ Fill price sanity check active (max {}% from mid)",
+        max_fill_deviation_pct
+    );
+
+    // Cumulative fills past the intent size are refused, not applied
+    let overfill_tolerance_pct = execution_config
+        .overfill_tolerance_pct
+        .and_then(Decimal::from_f64)
+        .unwrap_or(Decimal::from(DEFAULT_OVERFILL_TOLERANCE_PCT));
+    shadow_state
+        .write()
+        .set_overfill_tolerance_pct(overfill_tolerance_pct);
+    info!(
+        "✅ Over-fill check active (max {}% past intent size)",
+        overfill_tolerance_pct
+    );
+
+    // Critical event alerts (webhook), on top of logs and NATS
+    let alert_sink: Option<Arc<dyn AlertSink>> =
+        WebhookAlertSink::from_config(&execution_config.alerts).map(|sink| {
+            info!(
+                "🔔 Alert webhook enabled (min {:?})",
+                execution_config.alerts.min_severity
+            );
+            Arc::new(sink) as Arc<dyn AlertSink>
+        });
+
     // Initialize Global Halt (Circuit Breaker)
-    let global_halt = Arc::new(GlobalHalt::new());
+    let mut global_halt = GlobalHalt::new();
+    if let Some(sink) = &alert_sink {
+        global_halt = global_halt.with_alert_sink(sink.clone());
+        shadow_state.write().set_alert_sink(sink.clone());
+    }
+    let global_halt = Arc::new(global_halt);
 
     // Initialize Armed State (Physical Interlock - defaults DISARMED)
     let armed_state = Arc::new(ArmedState::new());
 
-    let order_manager = OrderManager::new(None, market_data_engine.clone(), global_halt.clone()); // Use default config
+    // Maintenance mode: freeze new opens, keep managing exits (persisted)
+    let maintenance = Arc::new(MaintenanceMode::new());
+
+    let order_manager = OrderManager::new(
+        Some(OrderManagerConfig::from_execution(&execution_config)),
+        market_data_engine.clone(),
+        global_halt.clone(),
+    )
+    .with_maintenance_mode(maintenance.clone());
 
     // Initialize Risk Guard
     let risk_policy = RiskPolicy::default();
     let policy_hash = RiskPolicy::get_hash();
     info!("✅ Risk Policy Loaded. Hash: {}", policy_hash);
-    let risk_guard = Arc::new(RiskGuard::new(risk_policy, shadow_state.clone()));
+    let mut risk_guard = RiskGuard::new(risk_policy, shadow_state.clone());
+    if execution_config.funding_gate.enabled {
+        info!(
+            "✅ Funding gate enabled (window {}s, max adverse {} bps)",
+            execution_config.funding_gate.window_secs,
+            execution_config.funding_gate.max_adverse_rate_bps
+        );
+        risk_guard.set_funding_gate(Arc::new(FundingGate::new(
+            execution_config.funding_gate.clone(),
+        )));
+    }
+    if execution_config.depth_gate.enabled {
+        info!(
+            "✅ Depth gate enabled ({}x size within {} bps)",
+            execution_config.depth_gate.safety_factor, execution_config.depth_gate.band_bps
+        );
+        risk_guard.set_depth_gate(Arc::new(DepthGate::new(
+            execution_config.depth_gate.clone(),
+        )));
+    }
+    if execution_config.spread_gate.enabled {
+        info!(
+            "✅ Spread gate enabled (market orders up to {} bps, {} symbol overrides)",
+            execution_config.spread_gate.max_spread_bps,
+            execution_config.spread_gate.symbols.len()
+        );
+        risk_guard.set_spread_gate(Arc::new(SpreadGate::new(
+            execution_config.spread_gate.clone(),
+            market_data_engine.clone(),
+        )));
+    }
+    if execution_config.volatility_gate.enabled {
+        info!(
+            "✅ Volatility reduce-only enabled (enter {} bps, exit {} bps over {}ms)",
+            execution_config.volatility_gate.enter_vol_bps,
+            execution_config.volatility_gate.exit_vol_bps,
+            execution_config.volatility_gate.window_ms
+        );
+        risk_guard.set_volatility_tracker(Arc::new(VolatilityTracker::new(
+            execution_config.volatility_gate.clone(),
+            market_data_engine.clone(),
+        )));
+    }
+    if execution_config.edge_gate.enabled {
+        let min_pct = execution_config.edge_gate.min_profit_after_fees_pct;
+        info!(
+            "✅ Edge gate enabled (opens must keep {}% after round-trip fees)",
+            min_pct
+        );
+        risk_guard.set_min_edge_after_fees(Decimal::from_f64(min_pct).unwrap_or(Decimal::ZERO));
+    }
+    if execution_config.sizing.mode == SizingMode::RiskBudget {
+        let risk = execution_config.sizing.risk_per_trade;
+        info!("✅ Risk-budget sizing enabled ({} at risk per trade)", risk);
+        risk_guard.set_risk_per_trade(Decimal::from_f64(risk).unwrap_or(Decimal::ZERO));
+        risk_guard.set_lot_sizes(
+            execution_config
+                .instruments
+                .iter()
+                .filter_map(|(symbol, instrument)| {
+                    Some((symbol.clone(), Decimal::from_f64(instrument.lot_size?)?))
+                })
+                .collect(),
+        );
+    }
+    let heartbeat_loss = &execution_config.heartbeat_loss;
+    info!(
+        "💓 Brain heartbeat loss after {}ms responds with {} (recovery {}ms)",
+        heartbeat_loss.timeout_ms,
+        heartbeat_loss.response.as_str(),
+        heartbeat_loss.recovery_ms
+    );
+    let heartbeat_monitor = Arc::new(
+        HeartbeatMonitor::new(heartbeat_loss.clone(), ctx.time.clone())
+            .with_persistence(persistence.clone()),
+    );
+    risk_guard.set_heartbeat_monitor(heartbeat_monitor.clone());
+    if exchanges
+        .and_then(|e| e.binance.as_ref())
+        .is_some_and(|c| c.enabled && c.market_type == MarketType::Spot)
+    {
+        info!("✅ Binance trades spot: leverage limits skipped for its intents");
+        risk_guard.set_spot_venues(["binance".to_string()]);
+    }
+    if let Some(sink) = &alert_sink {
+        risk_guard.set_alert_sink(sink.clone());
+    }
+    let risk_guard = Arc::new(risk_guard);
+    risk_guard.track_trade_outcomes();
+    risk_guard.set_reconnect_warmup_ticks(
+        execution_config
+            .reconnect_warmup_ticks
+            .unwrap_or(DEFAULT_RECONNECT_WARMUP_TICKS),
+    );
     info!("✅ Risk Guard initialized with default policy");
 
     // Initialize Constraints Store (PowerLaw Execution Constraints)
-    let constraints_store = Arc::new(ConstraintsStore::new());
+    let constraints_store = Arc::new(ConstraintsStore::new(ctx.time.clone()));
     info!("✅ Constraints Store initialized");
 
     // Initialize Drift Detector
@@ -279,6 +467,43 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     });
     info!("✅ SRE Monitor active");
 
+    // Trading day boundary for the opening-equity snapshot (intraday PnL)
+    let daily_reset_hour_utc = execution_config
+        .risk_guard
+        .daily_reset_hour_utc
+        .unwrap_or(0);
+    shadow_state
+        .write()
+        .set_daily_reset_hour_utc(daily_reset_hour_utc);
+    info!(
+        "✅ Daily PnL anchored to opening equity at {:02}:00 UTC",
+        daily_reset_hour_utc
+    );
+
+    // Trailing drawdown halt (equity high-water mark)
+    if let Some(pct) = execution_config
+        .risk_guard
+        .max_drawdown_pct
+        .and_then(Decimal::from_f64)
+    {
+        let breaker = DrawdownBreaker::new(pct, global_halt.clone());
+        let state_for_drawdown = shadow_state.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+                let (equity, hwm) = {
+                    let state = state_for_drawdown.read();
+                    (state.get_equity(), state.get_equity_hwm())
+                };
+                breaker.check(equity, hwm);
+            }
+        });
+        info!(
+            "✅ Trailing drawdown halt active ({}% from equity HWM)",
+            pct
+        );
+    }
+
     // --- Operator ARM/DISARM Command Listener ---
     let armed_for_listener = armed_state.clone();
     let client_for_arm = nats_client.clone();
@@ -320,7 +545,51 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
             armed_for_disarm.set_armed(false, &reason);
         }
     });
-    info!("✅ Execution ARM/DISARM listeners active");
+    let armed_for_approval = armed_state.clone();
+    let client_for_approval = nats_client.clone();
+    tokio::spawn(async move {
+        use futures::StreamExt;
+        // Listen for operator sign-off on a blocked arm (e.g. failed startup reconciliation)
+        let mut approve_sub = match client_for_approval
+            .subscribe(subjects::CMD_OPERATOR_APPROVE_ARMING)
+            .await
+        {
+            Ok(s) => s,
+            Err(e) => {
+                error!("Failed to subscribe to APPROVE_ARMING commands: {}", e);
+                return;
+            }
+        };
+        while let Some(msg) = approve_sub.next().await {
+            let reason = String::from_utf8_lossy(&msg.payload).to_string();
+            info!("✅ Received APPROVE_ARMING command: {}", reason);
+            armed_for_approval.approve_arming(&reason);
+        }
+    });
+    for (subject, active) in [
+        (subjects::CMD_OPERATOR_MAINTENANCE_ENTER, true),
+        (subjects::CMD_OPERATOR_MAINTENANCE_EXIT, false),
+    ] {
+        let maintenance_for_listener = maintenance.clone();
+        let client_for_maintenance = nats_client.clone();
+        tokio::spawn(async move {
+            use futures::StreamExt;
+            // Listen for maintenance mode enter / exit
+            let mut maintenance_sub = match client_for_maintenance.subscribe(subject).await {
+                Ok(s) => s,
+                Err(e) => {
+                    error!("Failed to subscribe to {}: {}", subject, e);
+                    return;
+                }
+            };
+            while let Some(msg) = maintenance_sub.next().await {
+                let reason = String::from_utf8_lossy(&msg.payload).to_string();
+                info!("🛠️ Received maintenance command on {}: {}", subject, reason);
+                maintenance_for_listener.set_active(active, &reason);
+            }
+        });
+    }
+    info!("✅ Execution ARM/DISARM and maintenance listeners active");
 
     info!("✅ Core components initialized");
 
@@ -336,7 +605,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         .as_ref()
         .and_then(|e| e.routing.clone())
         .unwrap_or_default();
-    let router = Arc::new(ExecutionRouter::with_routing(routing));
+    let router = Arc::new(
+        ExecutionRouter::with_routing(routing)
+            .with_client_order_ids(ctx.client_order_ids.clone())
+            .with_market_data(market_data_engine.clone())
+            .with_risk_guard(risk_guard.clone()),
+    );
+    for (name, config) in exchanges.into_iter().flat_map(|e| e.iter()) {
+        router.set_leverage_limits(name, LeverageLimits::from_config(config));
+    }
 
     // 1. Binance
     let binance_config = exchanges.and_then(|e| e.binance.as_ref());
@@ -644,7 +921,269 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         info!("🚫 Hyperliquid disabled or missing in config");
     }
 
+    // --- Startup Reconciliation (hydrated state vs venues, before arming) ---
+    let startup_reconciler = if execution_config.startup_reconciliation.enabled {
+        let reconciler = Arc::new(
+            StartupReconciler::new(
+                router.clone(),
+                shadow_state.clone(),
+                ctx.clone(),
+                &execution_config.startup_reconciliation,
+            )
+            .with_tolerances(&execution_config.reconciliation_tolerance),
+        );
+        let report = reconciler.run(&armed_state).await;
+        if !report.passed {
+            error!("🔒 Arming blocked until operator approval (startup reconciliation failed)");
+        }
+        Some(reconciler)
+    } else {
+        None
+    };
+
+    // Orders left resting by a crash, with no hydrated intent to account for their fills
+    if env::var("CANCEL_ORPHANS_ON_START")
+        .map(|v| v == "true")
+        .unwrap_or(false)
+    {
+        cancel_orphan_orders(&router, &shadow_state).await;
+    }
+
+    let dead_mans_switch = if execution_config.dead_mans_switch.enabled {
+        info!(
+            "🪦 Dead man's switch enabled ({}ms window)",
+            execution_config.dead_mans_switch.window_ms
+        );
+        let switch = DeadMansSwitch::new(router.adapters(), &execution_config.dead_mans_switch);
+        switch.start();
+        Some(switch)
+    } else {
+        None
+    };
+
     // --- Start NATS Engine ---
+    let tp_ladder = if execution_config.tp_ladder.enabled {
+        info!(
+            "🪜 TP ladder enabled ({:?})",
+            execution_config.tp_ladder.distribution
+        );
+        let executor = Arc::new(
+            TpLadderExecutor::new(
+                router.clone(),
+                ctx.clone(),
+                execution_config.tp_ladder.clone(),
+            )
+            .with_shadow_state(shadow_state.clone()),
+        );
+        executor.clone().start();
+        Some(executor)
+    } else {
+        None
+    };
+
+    let repricer = if execution_config.repricing.enabled {
+        info!(
+            "🎯 Limit repricing enabled ({}bps, max {} repegs)",
+            execution_config.repricing.threshold_bps, execution_config.repricing.max_repegs
+        );
+        Some(Arc::new(
+            LimitRepricer::new(
+                market_data_engine.clone(),
+                ctx.clone(),
+                execution_config.repricing.clone(),
+            )
+            .with_shadow_state(shadow_state.clone()),
+        ))
+    } else {
+        None
+    };
+
+    let entry_zone = if execution_config.entry_zone.enabled {
+        info!(
+            "📐 Entry zone scaling enabled ({} levels, {:?})",
+            execution_config.entry_zone.levels, execution_config.entry_zone.distribution
+        );
+        Some(Arc::new(EntryZoneExecutor::new(
+            shadow_state.clone(),
+            router.clone(),
+            execution_config.entry_zone.clone(),
+        )))
+    } else {
+        None
+    };
+
+    shadow_state
+        .write()
+        .set_partial_fill_budget_ms(execution_config.partial_fill.timeout_ms);
+    if execution_config.partial_fill.enabled {
+        info!(
+            "✂️ Partial fill remainders cancelled after {}ms",
+            execution_config.partial_fill.timeout_ms
+        );
+        Arc::new(PartialFillCanceller::new(
+            shadow_state.clone(),
+            router.clone(),
+            execution_config.partial_fill.sweep_interval_ms,
+        ))
+        .start();
+    }
+
+    let retention = &execution_config.trade_retention;
+    shadow_state
+        .write()
+        .set_max_trade_history(retention.in_memory);
+    if retention.max_count.is_some()
+        || retention.max_age_ms.is_some()
+        || retention.intent_max_age_ms.is_some()
+    {
+        info!(
+            "🧹 Persisted trades retained: max {:?} trades, max age {:?}ms; terminal intents max age {:?}ms",
+            retention.max_count, retention.max_age_ms, retention.intent_max_age_ms
+        );
+        Arc::new(TradeRetentionPruner::new(
+            persistence.clone(),
+            retention.clone(),
+            ctx.time.clone(),
+        ))
+        .start();
+    }
+
+    shadow_state
+        .write()
+        .set_lot_method(execution_config.lot_method);
+    if let Some(max_age_ms) = execution_config.max_valuation_tick_age_ms {
+        info!(
+            "⏱️ Ticks older than {}ms are not used for valuation",
+            max_age_ms
+        );
+        shadow_state
+            .write()
+            .set_max_valuation_tick_age_ms(max_age_ms);
+    }
+
+    let execution_reports = Arc::new(ExecutionReportStore::default());
+    let event_log = Arc::new(EventLog::new(persistence.clone()));
+    let intent_tracer = Arc::new(IntentTracer::new(persistence.clone()));
+    if execution_config.intent_trace.enabled {
+        info!("🧭 Intent lifecycle tracing enabled");
+    }
+
+    if execution_config.position_sync.enabled {
+        info!(
+            "🔄 Position sync every {}ms (auto-correct up to {}%)",
+            execution_config.position_sync.interval_ms,
+            execution_config.position_sync.max_auto_correct_pct
+        );
+        let mut position_sync = PositionSync::new(
+            router.clone(),
+            shadow_state.clone(),
+            global_halt.clone(),
+            event_log.clone(),
+            ctx.clone(),
+            execution_config.position_sync.clone(),
+        )
+        .with_tolerances(execution_config.reconciliation_tolerance.clone());
+        if let Some(sink) = &alert_sink {
+            position_sync = position_sync.with_alert_sink(sink.clone());
+        }
+        Arc::new(position_sync).start();
+    }
+
+    if execution_config.funding_sync.enabled {
+        info!(
+            "💸 Funding sync every {}ms",
+            execution_config.funding_sync.interval_ms
+        );
+        Arc::new(FundingSync::new(
+            router.clone(),
+            shadow_state.clone(),
+            event_log.clone(),
+            ctx.clone(),
+            execution_config.funding_sync.clone(),
+        ))
+        .start();
+    }
+
+    // --- Config hot-reload (SIGHUP) ---
+    let freshness_threshold = Arc::new(AtomicU64::new(
+        execution_config
+            .freshness_threshold_ms
+            .unwrap_or(DEFAULT_FRESHNESS_THRESHOLD_MS),
+    ));
+    let config_reloader = Arc::new(
+        ConfigReloader::new(
+            settings.clone(),
+            router.clone(),
+            freshness_threshold.clone(),
+        )
+        .with_order_manager_config(order_manager.shared_config()),
+    );
+    if let Err(e) = config_reloader.listen_for_sighup() {
+        error!(
+            "❌ Failed to install SIGHUP handler, config hot-reload disabled: {}",
+            e
+        );
+    }
+
+    if let Ok(Some(report)) = ShutdownReport::load_last(&persistence) {
+        info!(
+            "📝 Last flatten ({}): {} orders cancelled, {} positions closed, equity {}",
+            report.reason,
+            report.orders_cancelled.len(),
+            report.positions_closed.len(),
+            report.final_equity
+        );
+    }
+    // Shared by every flatten: shutdown, the flatten command, the panic halt,
+    // heartbeat loss and the emergency exit endpoint
+    let exit_flattener = Arc::new(
+        Flattener::new(
+            router.clone(),
+            shadow_state.clone(),
+            persistence.clone(),
+            ctx.clone(),
+        )
+        .with_publisher(nats_client.clone()),
+    );
+    let mut position_transfer =
+        PositionTransfer::new(router.clone(), shadow_state.clone(), ctx.clone());
+    if let Some(sink) = &alert_sink {
+        position_transfer = position_transfer.with_alert_sink(sink.clone());
+    }
+    let position_transfer = Arc::new(position_transfer);
+    let flattener = execution_config.flatten_on_shutdown.then(|| {
+        info!("🧯 Positions will be flattened on shutdown and on a panic halt");
+        exit_flattener.clone()
+    });
+
+    let mut heartbeat_responder = HeartbeatResponder::new(heartbeat_monitor, global_halt.clone());
+    if execution_config.heartbeat_loss.response == HeartbeatLossResponse::Flatten {
+        heartbeat_responder = heartbeat_responder.with_flattener(exit_flattener.clone());
+    }
+    if let Some(sink) = &alert_sink {
+        heartbeat_responder = heartbeat_responder.with_alert_sink(sink.clone());
+    }
+    Arc::new(heartbeat_responder).spawn();
+
+    // Shared with the HTTP control endpoints
+    let armed_for_api = armed_state.clone();
+    let halt_for_api = global_halt.clone();
+    let maintenance_for_api = maintenance.clone();
+    let event_log_for_api = event_log.clone();
+    let clock_for_api = ctx.time.clone();
+
+    let mut panic_watchdog =
+        PanicWatchdog::new(global_halt.clone(), &execution_config.panic_watchdog);
+    if execution_config.flatten_on_shutdown {
+        panic_watchdog = panic_watchdog.with_flattener(exit_flattener.clone());
+    }
+    let panic_watchdog = Arc::new(panic_watchdog);
+    let mut rejection_breaker =
+        RejectionRateBreaker::new(execution_config.rejection_breaker.clone());
+    if let Some(sink) = &alert_sink {
+        rejection_breaker = rejection_breaker.with_alert_sink(sink.clone());
+    }
+
     let nats_handle = nats_engine::start_nats_engine(
         nats_client.clone(),
         shadow_state.clone(),
@@ -653,11 +1192,27 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         simulation_engine,
         global_halt,
         armed_state.clone(),
+        maintenance,
         risk_guard.clone(),
         ctx.clone(),
-        execution_config.freshness_threshold_ms.unwrap_or(5000),
+        freshness_threshold,
         drift_detector.clone(),
         constraints_store.clone(),
+        tp_ladder,
+        repricer,
+        entry_zone,
+        startup_reconciler,
+        execution_reports.clone(),
+        event_log,
+        execution_config
+            .intent_trace
+            .enabled
+            .then(|| intent_tracer.clone()),
+        execution_config.confirmation.clone(),
+        execution_config.allowed_producers.clone(),
+        panic_watchdog,
+        Arc::new(rejection_breaker),
+        exit_flattener.clone(),
     )
     .await?;
 
@@ -667,6 +1222,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     info!("🚀 Starting API Server on {}", bind_address);
 
     let state_for_api = shadow_state.clone();
+    let persistence_for_api = persistence.clone();
     let state_for_truth = shadow_state.clone();
     let risk_guard_for_truth = risk_guard.clone();
     let nats_for_truth = nats_client.clone();
@@ -678,7 +1234,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
             interval.tick().await;
 
             let timestamp = chrono::Utc::now().timestamp_millis();
-            let positions = state_for_truth.read().get_all_positions();
+            let (positions, cash, equity) = {
+                let state = state_for_truth.read();
+                (
+                    state.get_all_positions(),
+                    state.get_cash_balance(),
+                    state.get_equity(),
+                )
+            };
             let policy_hash = risk_guard_for_truth.get_current_policy_hash();
 
             // Construct Snapshot
@@ -686,6 +1249,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                 "timestamp": timestamp,
                 "service": "titan-execution-rs",
                 "positions": positions,
+                "balances": {
+                    "cash": cash,
+                    "equity": equity,
+                },
                 "policy_hash": policy_hash,
                 "meta": {
                     "version": env!("CARGO_PKG_VERSION"),
@@ -716,17 +1283,37 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
             .app_data(web::Data::new(state_for_api.clone()))
             .app_data(web::Data::new(nats_client.clone()))
             .app_data(web::Data::new(risk_guard.clone()))
+            .app_data(web::Data::new(execution_reports.clone()))
+            .app_data(web::Data::new(intent_tracer.clone()))
+            .app_data(web::Data::new(persistence_for_api.clone()))
+            .app_data(web::Data::new(armed_for_api.clone()))
+            .app_data(web::Data::new(halt_for_api.clone()))
+            .app_data(web::Data::new(maintenance_for_api.clone()))
+            .app_data(web::Data::new(event_log_for_api.clone()))
+            .app_data(web::Data::new(clock_for_api.clone()))
+            .app_data(web::Data::new(exit_flattener.clone()))
+            .app_data(web::Data::new(position_transfer.clone()))
             .configure(api::config)
     })
     .bind(&bind_address)?
     .run()
     .await?;
 
+    // Flatten while the NATS engine can still apply the closing fills
+    if let Some(flattener) = flattener {
+        flattener.flatten("shutdown").await;
+    }
+
     // Wait for NATS task if server stops (unlikely unless signal)
     // Stop the NATS listener
     info!("Stopping NATS Engine...");
     nats_handle.abort();
     info!("✅ NATS Engine stopped");
 
+    // Clean shutdown: resting orders should survive, so disarm the venue timers
+    if let Some(switch) = dead_mans_switch {
+        switch.shutdown().await;
+    }
+
     Ok(())
 }
@@ -0,0 +1,101 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Maintenance Mode - freeze new opens, keep managing what is open
+///
+/// Softer than a halt: new entries are refused, while closes, take-profit
+/// ladders, repricing of resting exits and valuation carry on as normal.
+/// Separate from `GlobalHalt` so lifting a halt never ends maintenance (and
+/// vice versa), and entering it raises no halt alert.
+#[derive(Clone, Debug)]
+pub struct MaintenanceMode {
+    active: Arc<AtomicBool>,
+    file_path: std::path::PathBuf,
+}
+
+impl Default for MaintenanceMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MaintenanceMode {
+    pub fn new() -> Self {
+        Self::with_file("execution.maintenance")
+    }
+
+    /// Maintenance state persisted to the lockfile at `file_path`.
+    pub fn with_file(file_path: impl Into<std::path::PathBuf>) -> Self {
+        let file_path = file_path.into();
+        let exists = file_path.exists();
+
+        if exists {
+            warn!(
+                "🛠️ Execution initialized in MAINTENANCE mode (execution.maintenance file found)"
+            );
+        }
+
+        Self {
+            active: Arc::new(AtomicBool::new(exists)),
+            file_path,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    /// Whether an order may proceed. `reduces_risk` marks closes / reduce-only intents.
+    pub fn allows(&self, reduces_risk: bool) -> bool {
+        reduces_risk || !self.is_active()
+    }
+
+    pub fn set_active(&self, active: bool, reason: &str) {
+        let prev = self.active.swap(active, Ordering::SeqCst);
+
+        // Sync to disk for persistence across restarts
+        if active {
+            if let Err(e) = std::fs::write(&self.file_path, reason) {
+                warn!("Failed to persist maintenance lockfile: {}", e);
+            }
+        } else if self.file_path.exists() {
+            if let Err(e) = std::fs::remove_file(&self.file_path) {
+                warn!("Failed to remove maintenance lockfile: {}", e);
+            }
+        }
+
+        if prev != active {
+            if active {
+                warn!(
+                    "🛠️ MAINTENANCE MODE ENTERED: {} - new opens frozen, exits managed",
+                    reason
+                );
+            } else {
+                info!("✅ MAINTENANCE MODE EXITED: {} - new opens allowed", reason);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maintenance_freezes_opens_and_survives_restart() {
+        let path = std::env::temp_dir().join(format!("titan_maint_{}", uuid::Uuid::new_v4()));
+        let mode = MaintenanceMode::with_file(&path);
+        assert!(!mode.is_active());
+        assert!(mode.allows(false));
+
+        mode.set_active(true, "venue upgrade");
+        assert!(!mode.allows(false), "Opens frozen");
+        assert!(mode.allows(true), "Exits still flow");
+        assert!(MaintenanceMode::with_file(&path).is_active());
+
+        mode.set_active(false, "done");
+        assert!(mode.allows(false));
+        assert!(!path.exists());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::market_data::binance::message::{BinanceStreamWrapper, BinanceWsMessage};
 use crate::market_data::connector::{
-    MarketDataConnector, MarketDataError, StreamType, Subscription,
+    signal_reconnect, MarketDataConnector, MarketDataError, StreamType, Subscription,
 };
 use crate::market_data::model::MarketDataEvent;
 use async_trait::async_trait;
@@ -79,6 +79,7 @@ impl MarketDataConnector for BinanceConnector {
         let (ws_stream, _) = connect_async(url)
             .await
             .map_err(|e| MarketDataError::Connection(e.to_string()))?;
+        let reconnecting = self.write_tx.is_some();
         info!("Connected to Binance WebSocket");
 
         let (mut write, mut read) = ws_stream.split();
@@ -96,6 +97,15 @@ impl MarketDataConnector for BinanceConnector {
         });
 
         // Spawn reader
+        if reconnecting {
+            signal_reconnect(
+                &self.event_tx,
+                "binance",
+                self.subscriptions.iter().cloned().collect(),
+            )
+            .await;
+        }
+
         let event_tx = self.event_tx.clone();
         tokio::spawn(async move {
             while let Some(msg) = read.next().await {
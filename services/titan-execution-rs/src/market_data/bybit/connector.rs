@@ -1,6 +1,6 @@
 use crate::market_data::bybit::message::{BybitOrderBook, BybitTrade, BybitWsMessage};
 use crate::market_data::connector::{
-    MarketDataConnector, MarketDataError, StreamType, Subscription,
+    signal_reconnect, MarketDataConnector, MarketDataError, StreamType, Subscription,
 };
 use crate::market_data::model::MarketDataEvent;
 use async_trait::async_trait;
@@ -98,6 +98,7 @@ impl MarketDataConnector for BybitConnector {
         let (ws_stream, _) = connect_async(url)
             .await
             .map_err(|e| MarketDataError::Connection(e.to_string()))?;
+        let reconnecting = self.write_tx.is_some();
         info!("Connected to Bybit WebSocket");
 
         let (mut write, mut read) = ws_stream.split();
@@ -127,6 +128,18 @@ impl MarketDataConnector for BybitConnector {
         });
 
         // Spawn reader loop
+        if reconnecting {
+            signal_reconnect(
+                &self.event_tx,
+                "bybit",
+                self.subscriptions
+                    .iter()
+                    .filter_map(|topic| topic.rsplit('.').next().map(str::to_string))
+                    .collect(),
+            )
+            .await;
+        }
+
         let event_tx = self.event_tx.clone();
         tokio::spawn(async move {
             while let Some(msg) = read.next().await {
@@ -1,4 +1,4 @@
-use crate::market_data::model::MarketDataEvent;
+use crate::market_data::model::{MarketDataEvent, Reconnect};
 use async_trait::async_trait;
 use thiserror::Error;
 use tokio::sync::mpsc;
@@ -29,6 +29,20 @@ pub struct Subscription {
     pub stream_type: StreamType,
 }
 
+/// Tell downstream consumers that `exchange` reconnected and `symbols` may have gaps.
+pub async fn signal_reconnect(
+    tx: &mpsc::Sender<MarketDataEvent>,
+    exchange: &str,
+    symbols: Vec<String>,
+) {
+    let event = MarketDataEvent::Reconnected(Reconnect {
+        exchange: exchange.to_string(),
+        symbols,
+        timestamp: chrono::Utc::now(),
+    });
+    let _ = tx.send(event).await;
+}
+
 #[async_trait]
 pub trait MarketDataConnector: Send + Sync {
     /// Initialize the connection
@@ -1,6 +1,10 @@
+use crate::config::{BookSanityConfig, MarketDataSourcesConfig, TickHistoryConfig};
 use crate::market_data::connector::{MarketDataConnector, StreamType, Subscription};
 use crate::market_data::model::MarketDataEvent;
+use crate::market_data::tick_history::TickHistory;
 use crate::market_data::types::BookTicker;
+use crate::metrics;
+use crate::subjects;
 use chrono::Utc;
 use rust_decimal::Decimal;
 use std::collections::HashMap;
@@ -12,8 +16,18 @@ use tracing::{error, info, warn};
 pub struct MarketDataEngine {
     prices: Arc<RwLock<HashMap<String, Decimal>>>,
     pub tickers: Arc<RwLock<HashMap<String, crate::market_data::types::BookTicker>>>,
+    /// Latest book per (venue, symbol), used for cross-venue consistency checks
+    venue_tickers: Arc<RwLock<HashMap<(String, String), BookTicker>>>,
+    /// Bounded recent ticks per symbol, for interval TCA and trigger logic
+    tick_history: Arc<RwLock<TickHistory>>,
     connectors: Arc<RwLock<Vec<Box<dyn MarketDataConnector + Send + Sync>>>>,
     nats_client: Option<async_nats::Client>,
+    /// Venues in order of preference when several quote the same symbol
+    source_priority: Arc<Vec<String>>,
+    source_stale_after_ms: i64,
+    /// Symbol -> venue whose ticks currently set the consolidated price
+    authoritative: Arc<RwLock<HashMap<String, String>>>,
+    book_sanity: BookSanityConfig,
 }
 
 impl MarketDataEngine {
@@ -21,8 +35,144 @@ impl MarketDataEngine {
         Self {
             prices: Arc::new(RwLock::new(HashMap::new())),
             tickers: Arc::new(RwLock::new(HashMap::new())),
+            venue_tickers: Arc::new(RwLock::new(HashMap::new())),
+            tick_history: Arc::new(RwLock::new(TickHistory::new(&TickHistoryConfig::default()))),
             connectors: Arc::new(RwLock::new(Vec::new())),
             nats_client,
+            source_priority: Arc::new(Vec::new()),
+            source_stale_after_ms: MarketDataSourcesConfig::default().stale_after_ms,
+            authoritative: Arc::new(RwLock::new(HashMap::new())),
+            book_sanity: BookSanityConfig::default(),
+        }
+    }
+
+    /// Size the tick history buffer. Call before `start`.
+    pub fn with_tick_history(self, config: &TickHistoryConfig) -> Self {
+        Self {
+            tick_history: Arc::new(RwLock::new(TickHistory::new(config))),
+            ..self
+        }
+    }
+
+    /// Rank connectors that quote the same symbol. Call before `start`.
+    pub fn with_source_priority(self, config: &MarketDataSourcesConfig) -> Self {
+        Self {
+            source_priority: Arc::new(config.priority.iter().map(|v| v.to_lowercase()).collect()),
+            source_stale_after_ms: config.stale_after_ms,
+            ..self
+        }
+    }
+
+    /// Configure the crossed/non-positive tick guard. Call before `start`.
+    pub fn with_book_sanity(self, config: &BookSanityConfig) -> Self {
+        Self {
+            book_sanity: config.clone(),
+            ..self
+        }
+    }
+
+    /// Why `ticker` fails the sanity guard, if it does.
+    fn bad_tick_reason(&self, ticker: &BookTicker) -> Option<&'static str> {
+        if !self.book_sanity.enabled {
+            return None;
+        }
+        if ticker.best_bid <= Decimal::ZERO || ticker.best_ask <= Decimal::ZERO {
+            return Some("non_positive");
+        }
+        if ticker.best_bid > ticker.best_ask
+            || (ticker.best_bid == ticker.best_ask && !self.book_sanity.allow_locked)
+        {
+            return Some("crossed");
+        }
+        None
+    }
+
+    /// Venue whose ticks currently set `symbol`'s consolidated price.
+    pub fn authoritative_source(&self, symbol: &str) -> Option<String> {
+        let clean = symbol.replace("/", "").replace("_", "");
+        self.authoritative
+            .read()
+            .ok()
+            .and_then(|map| map.get(&clean).cloned())
+    }
+
+    /// Record a tick from `venue` and, if `venue` is the highest-priority
+    /// source still live for the symbol, make it the consolidated quote.
+    /// Returns whether the consolidated quote was updated. Ticks failing the
+    /// sanity guard are dropped, leaving the last good quote in place.
+    pub fn apply_tick(&self, venue: &str, ticker: BookTicker) -> bool {
+        let venue = venue.to_lowercase();
+        let key = ticker.symbol.replace("/", "").replace("_", "");
+        if let Some(reason) = self.bad_tick_reason(&ticker) {
+            warn!(
+                symbol = %key,
+                venue = %venue,
+                bid = %ticker.best_bid,
+                ask = %ticker.best_ask,
+                reason,
+                "Rejected bad market data tick"
+            );
+            metrics::inc_market_data_bad_ticks(&venue, reason);
+            return false;
+        }
+        self.update_venue_ticker(&venue, ticker.clone());
+
+        if self.preferred_source_live(&venue, &key, ticker.event_time) {
+            // This tick only feeds the per-venue view
+            return false;
+        }
+
+        if let Ok(mut map) = self.authoritative.write() {
+            let previous = map.insert(key.clone(), venue.clone());
+            if previous.as_deref() != Some(venue.as_str()) {
+                if let Some(previous) = &previous {
+                    warn!(symbol = %key, from = %previous, to = %venue, "Market data source failover");
+                }
+                metrics::set_market_data_source(&key, previous.as_deref(), &venue);
+            }
+        }
+        if let Ok(mut map) = self.prices.write() {
+            map.insert(
+                key.clone(),
+                (ticker.best_bid + ticker.best_ask) / Decimal::TWO,
+            );
+        }
+        if let Ok(mut map) = self.tickers.write() {
+            map.insert(key, ticker.clone());
+        }
+        self.record_tick(ticker);
+        true
+    }
+
+    /// Whether a venue ranked above `venue` has ticked `key` within the
+    /// staleness window at `now`.
+    fn preferred_source_live(&self, venue: &str, key: &str, now: i64) -> bool {
+        let rank = self
+            .source_priority
+            .iter()
+            .position(|v| v == venue)
+            .unwrap_or(self.source_priority.len());
+        let Ok(map) = self.venue_tickers.read() else {
+            return false;
+        };
+        self.source_priority[..rank].iter().any(|v| {
+            map.get(&(v.clone(), key.to_string()))
+                .is_some_and(|t| now - t.event_time <= self.source_stale_after_ms)
+        })
+    }
+
+    pub fn record_tick(&self, ticker: BookTicker) {
+        if let Ok(mut history) = self.tick_history.write() {
+            history.record(ticker);
+        }
+    }
+
+    /// Buffered ticks for `symbol` with event time in `[t0, t1]`, oldest first.
+    pub fn ticks_between(&self, symbol: &str, t0: i64, t1: i64) -> Vec<BookTicker> {
+        if let Ok(history) = self.tick_history.read() {
+            history.ticks_between(symbol, t0, t1)
+        } else {
+            Vec::new()
         }
     }
 
@@ -35,6 +185,28 @@ impl MarketDataEngine {
         }
     }
 
+    pub fn get_venue_ticker(&self, venue: &str, symbol: &str) -> Option<BookTicker> {
+        let key = (
+            venue.to_lowercase(),
+            symbol.replace("/", "").replace("_", ""),
+        );
+        if let Ok(map) = self.venue_tickers.read() {
+            map.get(&key).cloned()
+        } else {
+            None
+        }
+    }
+
+    pub fn update_venue_ticker(&self, venue: &str, ticker: BookTicker) {
+        let key = (
+            venue.to_lowercase(),
+            ticker.symbol.replace("/", "").replace("_", ""),
+        );
+        if let Ok(mut map) = self.venue_tickers.write() {
+            map.insert(key, ticker);
+        }
+    }
+
     pub fn add_connector(&self, connector: Box<dyn MarketDataConnector + Send + Sync>) {
         if let Ok(mut connectors) = self.connectors.write() {
             connectors.push(connector);
@@ -65,13 +237,10 @@ impl MarketDataEngine {
             }
         }
 
-        let prices = self.prices.clone();
-        let tickers = self.tickers.clone();
         let nats = self.nats_client.clone();
 
         for mut connector in connectors_to_run {
-            let prices_clone = prices.clone();
-            let tickers_clone = tickers.clone();
+            let engine = self.clone();
             let nats_clone = nats.clone();
 
             let handle = tokio::spawn(async move {
@@ -105,16 +274,51 @@ impl MarketDataEngine {
                     );
                 }
 
+                // "Binance Futures" -> "binance", matching router adapter names
+                let venue = connector
+                    .name()
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or_default()
+                    .to_lowercase();
+
                 let mut stream = connector.event_stream();
                 info!("Connector {} running event loop", connector.name());
 
                 while let Some(event) = stream.recv().await {
+                    if let MarketDataEvent::Funding(funding) = &event {
+                        // Funding snapshots feed the risk guard's entry gating
+                        if let Some(nc) = &nats_clone {
+                            let key = funding.symbol.replace("_", "").replace("/", "");
+                            let subject =
+                                format!("{}.{}.{}", subjects::DATA_MARKET_FUNDING, venue, key);
+                            if let Ok(payload) = serde_json::to_vec(funding) {
+                                let _ = nc.publish(subject, payload.into()).await;
+                            }
+                        }
+                    }
+                    if let MarketDataEvent::OrderBook(book) = &event {
+                        // L2 snapshots and deltas feed the market order depth gate
+                        if let Some(nc) = &nats_clone {
+                            let key = book.symbol.replace("_", "").replace("/", "");
+                            let subject =
+                                format!("{}.{}.{}", subjects::DATA_MARKET_BOOK, venue, key);
+                            if let Ok(payload) = serde_json::to_vec(book) {
+                                let _ = nc.publish(subject, payload.into()).await;
+                            }
+                        }
+                    }
+                    if let MarketDataEvent::Reconnected(reconnect) = &event {
+                        // Risk guard holds these symbols as stale until they warm up
+                        if let Some(nc) = &nats_clone {
+                            let subject = format!("{}.{}", subjects::DATA_MARKET_RECONNECT, venue);
+                            if let Ok(payload) = serde_json::to_vec(reconnect) {
+                                let _ = nc.publish(subject, payload.into()).await;
+                            }
+                        }
+                    }
                     if let MarketDataEvent::Trade(trade) = event {
-                        // Update Price Cache
                         let key = trade.symbol.replace("_", "").replace("/", "");
-                        if let Ok(mut map) = prices_clone.write() {
-                            map.insert(key.clone(), trade.price);
-                        }
 
                         // Construct Fake Ticker
                         let ticker = crate::market_data::types::BookTicker {
@@ -127,9 +331,8 @@ impl MarketDataEngine {
                             event_time: Utc::now().timestamp_millis(),
                         };
 
-                        if let Ok(mut map) = tickers_clone.write() {
-                            map.insert(key.clone(), ticker.clone());
-                        }
+                        // Only the authoritative source moves the price cache
+                        let authoritative = engine.apply_tick(&venue, ticker.clone());
 
                         // NATS Publish
                         if let Some(nc) = &nats_clone {
@@ -140,9 +343,11 @@ impl MarketDataEngine {
                             }
 
                             // Publish Ticker (Price)
-                            let subject_price = format!("market.price.{}", key);
-                            if let Ok(payload) = serde_json::to_vec(&ticker) {
-                                let _ = nc.publish(subject_price, payload.into()).await;
+                            if authoritative {
+                                let subject_price = format!("market.price.{}", key);
+                                if let Ok(payload) = serde_json::to_vec(&ticker) {
+                                    let _ = nc.publish(subject_price, payload.into()).await;
+                                }
                             }
                         }
                     }
@@ -164,3 +369,128 @@ impl MarketDataEngine {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    const NOW_MS: i64 = 1_700_000_000_000;
+
+    fn tick(price: Decimal, at: i64) -> BookTicker {
+        BookTicker {
+            symbol: "BTCUSDT".to_string(),
+            best_bid: price,
+            best_bid_qty: dec!(1),
+            best_ask: price,
+            best_ask_qty: dec!(1),
+            transaction_time: at,
+            event_time: at,
+        }
+    }
+
+    #[test]
+    fn test_fails_over_from_stale_primary_and_back() {
+        let engine = MarketDataEngine::new(None).with_source_priority(&MarketDataSourcesConfig {
+            priority: vec!["Binance".to_string(), "bybit".to_string()],
+            stale_after_ms: 1_000,
+        });
+
+        assert!(engine.apply_tick("binance", tick(dec!(50000), NOW_MS)));
+        // Primary is live: the secondary's bad print does not reach valuation
+        assert!(!engine.apply_tick("bybit", tick(dec!(1), NOW_MS + 500)));
+        assert_eq!(engine.get_price("BTC/USDT"), Some(dec!(50000)));
+        assert_eq!(
+            engine.authoritative_source("BTC/USDT").as_deref(),
+            Some("binance")
+        );
+        assert_eq!(
+            engine
+                .get_venue_ticker("bybit", "BTCUSDT")
+                .unwrap()
+                .best_bid,
+            dec!(1)
+        );
+
+        // Primary goes quiet past the staleness window: secondary takes over
+        assert!(engine.apply_tick("bybit", tick(dec!(50100), NOW_MS + 1_500)));
+        assert_eq!(engine.get_price("BTC/USDT"), Some(dec!(50100)));
+        assert_eq!(
+            engine.authoritative_source("BTC/USDT").as_deref(),
+            Some("bybit")
+        );
+
+        // Primary recovers and reclaims the symbol
+        assert!(engine.apply_tick("binance", tick(dec!(50200), NOW_MS + 2_000)));
+        assert!(!engine.apply_tick("bybit", tick(dec!(50300), NOW_MS + 2_100)));
+        assert_eq!(engine.get_price("BTC/USDT"), Some(dec!(50200)));
+        assert_eq!(
+            engine.authoritative_source("BTC/USDT").as_deref(),
+            Some("binance")
+        );
+    }
+
+    #[test]
+    fn test_crossed_and_non_positive_ticks_keep_last_good_quote() {
+        let engine = MarketDataEngine::new(None);
+        let mut good = tick(dec!(50000), NOW_MS);
+        good.best_ask = dec!(50001);
+        assert!(engine.apply_tick("binance", good));
+
+        let mut crossed = tick(dec!(50010), NOW_MS + 10);
+        crossed.best_ask = dec!(49990);
+        assert!(!engine.apply_tick("binance", crossed));
+
+        let mut zero_bid = tick(dec!(50000), NOW_MS + 20);
+        zero_bid.best_bid = Decimal::ZERO;
+        assert!(!engine.apply_tick("binance", zero_bid));
+
+        let ticker = engine.get_ticker("BTC/USDT").unwrap();
+        assert_eq!(ticker.best_bid, dec!(50000));
+        assert_eq!(ticker.best_ask, dec!(50001));
+        assert_eq!(engine.get_price("BTCUSDT"), Some(dec!(50000.5)));
+        assert_eq!(
+            engine
+                .get_venue_ticker("binance", "BTCUSDT")
+                .unwrap()
+                .event_time,
+            NOW_MS
+        );
+        assert!(
+            metrics::MARKET_DATA_BAD_TICKS
+                .with_label_values(&["binance", "crossed"])
+                .get()
+                >= 1
+        );
+    }
+
+    #[test]
+    fn test_locked_book_rejected_only_when_configured() {
+        // Trade-derived tickers are locked (bid == ask) and pass by default
+        let engine = MarketDataEngine::new(None);
+        assert!(engine.apply_tick("binance", tick(dec!(50000), NOW_MS)));
+
+        let strict = MarketDataEngine::new(None).with_book_sanity(&BookSanityConfig {
+            enabled: true,
+            allow_locked: false,
+        });
+        assert!(!strict.apply_tick("binance", tick(dec!(50000), NOW_MS)));
+        assert_eq!(strict.get_price("BTCUSDT"), None);
+
+        let disabled = MarketDataEngine::new(None).with_book_sanity(&BookSanityConfig {
+            enabled: false,
+            ..Default::default()
+        });
+        let mut crossed = tick(dec!(50010), NOW_MS);
+        crossed.best_ask = dec!(49990);
+        assert!(disabled.apply_tick("binance", crossed));
+    }
+
+    #[test]
+    fn test_no_priority_keeps_last_writer() {
+        let engine = MarketDataEngine::new(None);
+        assert!(engine.apply_tick("binance", tick(dec!(50000), NOW_MS)));
+        assert!(engine.apply_tick("bybit", tick(dec!(50100), NOW_MS + 10)));
+        assert_eq!(engine.get_price("BTC/USDT"), Some(dec!(50100)));
+    }
+}
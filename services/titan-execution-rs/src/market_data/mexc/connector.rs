@@ -1,5 +1,5 @@
 use crate::market_data::connector::{
-    MarketDataConnector, MarketDataError, StreamType, Subscription,
+    signal_reconnect, MarketDataConnector, MarketDataError, StreamType, Subscription,
 };
 use crate::market_data::mexc::message::{MexcDeal, MexcWsMessage};
 use crate::market_data::model::MarketDataEvent;
@@ -93,6 +93,7 @@ impl MarketDataConnector for MexcConnector {
         let (ws_stream, _) = connect_async(url)
             .await
             .map_err(|e| MarketDataError::Connection(e.to_string()))?;
+        let reconnecting = self.write_tx.is_some();
         info!("Connected to MEXC WebSocket");
 
         let (mut write, mut read) = ws_stream.split();
@@ -122,6 +123,15 @@ impl MarketDataConnector for MexcConnector {
         });
 
         // Spawn reader loop
+        if reconnecting {
+            signal_reconnect(
+                &self.event_tx,
+                "mexc",
+                self.subscriptions.iter().cloned().collect(),
+            )
+            .await;
+        }
+
         let event_tx = self.event_tx.clone();
         tokio::spawn(async move {
             while let Some(msg) = read.next().await {
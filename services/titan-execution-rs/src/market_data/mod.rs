@@ -8,4 +8,5 @@ pub mod engine;
 
 pub mod model;
 pub mod orderbook_manager;
+pub mod tick_history;
 pub mod types;
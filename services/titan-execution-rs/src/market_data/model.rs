@@ -55,10 +55,20 @@ pub struct Liquidation {
     pub exchange: String,
 }
 
+/// A connector re-established its stream after a disconnect. Data for `symbols`
+/// may have gaps; an empty list means every symbol on the venue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reconnect {
+    pub exchange: String,
+    pub symbols: Vec<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MarketDataEvent {
     Trade(PublicTrade),
     OrderBook(OrderBookL2),
     Funding(FundingRate),
     Liquidation(Liquidation),
+    Reconnected(Reconnect),
 }
@@ -0,0 +1,139 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::config::TickHistoryConfig;
+use crate::market_data::types::BookTicker;
+
+/// Recent ticks per symbol, ordered by event time. Each symbol keeps at most
+/// `max_ticks` ticks, and none older than `window_ms` behind its newest one.
+pub struct TickHistory {
+    max_ticks: usize,
+    window_ms: i64,
+    ticks: HashMap<String, VecDeque<BookTicker>>,
+}
+
+impl TickHistory {
+    pub fn new(config: &TickHistoryConfig) -> Self {
+        Self {
+            max_ticks: config.max_ticks,
+            window_ms: config.window_ms,
+            ticks: HashMap::new(),
+        }
+    }
+
+    fn key(symbol: &str) -> String {
+        symbol.replace("/", "").replace("_", "")
+    }
+
+    pub fn record(&mut self, ticker: BookTicker) {
+        if self.max_ticks == 0 {
+            return;
+        }
+        let buffer = self.ticks.entry(Self::key(&ticker.symbol)).or_default();
+
+        // Feeds are near-ordered; a late tick is slotted in rather than appended
+        let at = buffer.partition_point(|t| t.event_time <= ticker.event_time);
+        buffer.insert(at, ticker);
+
+        while buffer.len() > self.max_ticks {
+            buffer.pop_front();
+        }
+        if let Some(newest) = buffer.back().map(|t| t.event_time) {
+            while buffer
+                .front()
+                .is_some_and(|t| t.event_time < newest - self.window_ms)
+            {
+                buffer.pop_front();
+            }
+        }
+    }
+
+    /// Ticks with `t0 <= event_time <= t1`, oldest first.
+    pub fn ticks_between(&self, symbol: &str, t0: i64, t1: i64) -> Vec<BookTicker> {
+        let Some(buffer) = self.ticks.get(&Self::key(symbol)) else {
+            return Vec::new();
+        };
+        let start = buffer.partition_point(|t| t.event_time < t0);
+        let end = buffer.partition_point(|t| t.event_time <= t1);
+        if start >= end {
+            return Vec::new();
+        }
+        buffer.range(start..end).cloned().collect()
+    }
+
+    pub fn len(&self, symbol: &str) -> usize {
+        self.ticks.get(&Self::key(symbol)).map_or(0, VecDeque::len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn tick(symbol: &str, price: i64, ts: i64) -> BookTicker {
+        BookTicker {
+            symbol: symbol.to_string(),
+            best_bid: Decimal::from(price),
+            best_bid_qty: Decimal::ONE,
+            best_ask: Decimal::from(price),
+            best_ask_qty: Decimal::ONE,
+            transaction_time: ts,
+            event_time: ts,
+        }
+    }
+
+    fn history(max_ticks: usize, window_ms: i64) -> TickHistory {
+        TickHistory::new(&TickHistoryConfig {
+            max_ticks,
+            window_ms,
+        })
+    }
+
+    #[test]
+    fn test_buffer_bounded_by_depth_and_window() {
+        let mut history = history(3, 10_000);
+        for ts in [1_000, 2_000, 3_000, 4_000, 5_000] {
+            history.record(tick("BTCUSDT", 50_000, ts));
+        }
+        // Depth keeps the three newest
+        let kept: Vec<i64> = history
+            .ticks_between("BTCUSDT", 0, i64::MAX)
+            .iter()
+            .map(|t| t.event_time)
+            .collect();
+        assert_eq!(kept, vec![3_000, 4_000, 5_000]);
+
+        // A tick far ahead ages everything else out of the window
+        history.record(tick("BTCUSDT", 50_100, 20_000));
+        assert_eq!(history.len("BTCUSDT"), 1);
+
+        // Symbols are bounded independently
+        history.record(tick("ETHUSDT", 2_000, 1_000));
+        assert_eq!(history.len("ETH/USDT"), 1);
+        assert_eq!(history.len("BTC/USDT"), 1);
+    }
+
+    #[test]
+    fn test_ticks_between_is_inclusive_and_ordered() {
+        let mut history = history(100, 60_000);
+        for (price, ts) in [(100, 1_000), (101, 2_000), (103, 4_000), (104, 5_000)] {
+            history.record(tick("BTC_USDT", price, ts));
+        }
+        // Late tick lands in order
+        history.record(tick("BTCUSDT", 102, 3_000));
+
+        let prices: Vec<Decimal> = history
+            .ticks_between("BTC/USDT", 2_000, 4_000)
+            .iter()
+            .map(|t| t.best_bid)
+            .collect();
+        assert_eq!(
+            prices,
+            vec![Decimal::from(101), Decimal::from(102), Decimal::from(103)]
+        );
+
+        assert!(history.ticks_between("BTCUSDT", 6_000, 9_000).is_empty());
+        assert!(history.ticks_between("BTCUSDT", 4_000, 2_000).is_empty());
+        assert!(history.ticks_between("SOLUSDT", 0, i64::MAX).is_empty());
+    }
+}
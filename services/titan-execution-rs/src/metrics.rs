@@ -1,8 +1,11 @@
 use once_cell::sync::Lazy;
+use parking_lot::Mutex;
 use prometheus::{
-    register_histogram, register_int_counter, register_int_counter_vec, register_int_gauge,
-    Histogram, IntCounter, IntCounterVec, IntGauge,
+    register_counter_vec, register_gauge_vec, register_histogram, register_int_counter,
+    register_int_counter_vec, register_int_gauge, register_int_gauge_vec, CounterVec, GaugeVec,
+    Histogram, IntCounter, IntCounterVec, IntGauge, IntGaugeVec,
 };
+use std::collections::HashMap;
 
 // --- Execution Metrics (Phase 2 Remediation) ---
 
@@ -50,6 +53,14 @@ pub static RISK_STATE: Lazy<IntGauge> = Lazy::new(|| {
     .expect("risk_state gauge")
 });
 
+pub static HEARTBEAT_LOST: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "titan_execution_heartbeat_lost",
+        "Brain heartbeat lost (1) or healthy (0)"
+    )
+    .expect("heartbeat_lost gauge")
+});
+
 pub static ACTIVE_POSITIONS: Lazy<IntGauge> = Lazy::new(|| {
     register_int_gauge!(
         "titan_execution_active_positions",
@@ -82,6 +93,22 @@ pub static EXPIRED_INTENTS: Lazy<IntCounter> = Lazy::new(|| {
     .expect("expired_intents counter")
 });
 
+pub static INTENT_DEDUP_HITS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "titan_execution_intent_dedup_hits_total",
+        "Intents rejected as a duplicate causation_id inside the dedup window"
+    )
+    .expect("intent_dedup_hits counter")
+});
+
+pub static INTENT_DEDUP_MISSES: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "titan_execution_intent_dedup_misses_total",
+        "Intents whose causation_id was not seen inside the dedup window"
+    )
+    .expect("intent_dedup_misses counter")
+});
+
 pub static DLQ_PUBLISHED: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
         "titan_execution_dlq_published_total",
@@ -90,6 +117,14 @@ pub static DLQ_PUBLISHED: Lazy<IntCounter> = Lazy::new(|| {
     .expect("dlq_published counter")
 });
 
+pub static PIPELINE_PANICS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "titan_execution_pipeline_panics_total",
+        "Intents whose processing panicked and was dead-lettered"
+    )
+    .expect("pipeline_panics counter")
+});
+
 pub static FANOUT_ORDERS: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
         "titan_execution_fanout_orders_total",
@@ -98,6 +133,78 @@ pub static FANOUT_ORDERS: Lazy<IntCounter> = Lazy::new(|| {
     .expect("fanout_orders counter")
 });
 
+pub static FANOUT_COLLAPSED: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "titan_execution_fanout_collapsed_total",
+        "Fan-outs collapsed to a single venue by the cross-venue guard"
+    )
+    .expect("fanout_collapsed counter")
+});
+
+pub static ORDER_REPEGS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "titan_execution_order_repegs_total",
+        "Resting limit orders repriced after mid drift"
+    )
+    .expect("order_repegs counter")
+});
+
+pub static REPEGS_SKIPPED: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "titan_execution_repegs_skipped_total",
+        "Repegs skipped because the price change was below the minimum improvement"
+    )
+    .expect("repegs_skipped counter")
+});
+
+pub static EXCHANGE_MAINTENANCE: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "titan_execution_exchange_maintenance_total",
+        "Exchange/symbol pairs marked untradeable due to maintenance or closed market"
+    )
+    .expect("exchange_maintenance counter")
+});
+
+pub static FILL_ANOMALIES: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "titan_execution_fill_anomalies_total",
+        "Fills rejected for a price implausibly far from the mid"
+    )
+    .expect("fill_anomalies counter")
+});
+
+pub static OVERFILLS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "titan_execution_overfills_total",
+        "Fills refused for taking an intent past its size"
+    )
+    .expect("overfills counter")
+});
+
+pub static SIMULATED_INTENTS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "titan_execution_simulated_intents_total",
+        "Intents flagged simulate_only that were shadow-filled without reaching a venue"
+    )
+    .expect("simulated_intents counter")
+});
+
+pub static SYMBOL_THROTTLED: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "titan_execution_symbol_throttled_total",
+        "Orders rejected by the per exchange/symbol throttle"
+    )
+    .expect("symbol_throttled counter")
+});
+
+pub static ORDER_DEDUP_HITS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "titan_execution_order_dedup_hits_total",
+        "Order placements answered from an existing venue order with the same client order id"
+    )
+    .expect("order_dedup_hits counter")
+});
+
 pub static POSITION_FLIPS: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
         "titan_execution_position_flips_total",
@@ -172,10 +279,58 @@ pub fn inc_dlq_published() {
     DLQ_PUBLISHED.inc();
 }
 
+pub fn inc_pipeline_panics() {
+    PIPELINE_PANICS.inc();
+}
+
+pub fn inc_intent_dedup_hits() {
+    INTENT_DEDUP_HITS.inc();
+}
+
+pub fn inc_intent_dedup_misses() {
+    INTENT_DEDUP_MISSES.inc();
+}
+
 pub fn inc_fanout_orders(count: u64) {
     FANOUT_ORDERS.inc_by(count);
 }
 
+pub fn inc_fanout_collapsed() {
+    FANOUT_COLLAPSED.inc();
+}
+
+pub fn inc_order_repegs() {
+    ORDER_REPEGS.inc();
+}
+
+pub fn inc_repegs_skipped() {
+    REPEGS_SKIPPED.inc();
+}
+
+pub fn inc_exchange_maintenance() {
+    EXCHANGE_MAINTENANCE.inc();
+}
+
+pub fn inc_simulated_intents() {
+    SIMULATED_INTENTS.inc();
+}
+
+pub fn inc_symbol_throttled() {
+    SYMBOL_THROTTLED.inc();
+}
+
+pub fn inc_order_dedup_hits() {
+    ORDER_DEDUP_HITS.inc();
+}
+
+pub fn inc_fill_anomalies() {
+    FILL_ANOMALIES.inc();
+}
+
+pub fn inc_overfills() {
+    OVERFILLS.inc();
+}
+
 pub fn inc_position_flips() {
     POSITION_FLIPS.inc();
 }
@@ -218,6 +373,10 @@ pub fn set_risk_state(state: i64) {
     RISK_STATE.set(state);
 }
 
+pub fn set_heartbeat_lost(lost: bool) {
+    HEARTBEAT_LOST.set(lost as i64);
+}
+
 pub fn set_active_positions(count: i64) {
     ACTIVE_POSITIONS.set(count);
 }
@@ -226,6 +385,79 @@ pub fn inc_filled_orders() {
     FILLED_ORDERS.inc();
 }
 
+// --- Per-Venue Execution Metrics ---
+// Series are labelled exchange=<venue> and symbol=<symbol>. Callers pass
+// OTHER_SYMBOL for symbols outside the risk whitelist to keep cardinality bounded.
+
+/// Symbol label for instruments outside the whitelist.
+pub const OTHER_SYMBOL: &str = "other";
+
+pub static VENUE_ORDERS_PLACED: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "titan_execution_venue_orders_placed_total",
+        "Orders acknowledged by the venue",
+        &["exchange", "symbol"]
+    )
+    .expect("venue_orders_placed counter_vec")
+});
+
+pub static VENUE_ORDERS_FILLED: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "titan_execution_venue_orders_filled_total",
+        "Orders with a confirmed fill",
+        &["exchange", "symbol"]
+    )
+    .expect("venue_orders_filled counter_vec")
+});
+
+pub static VENUE_ORDERS_REJECTED: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "titan_execution_venue_orders_rejected_total",
+        "Orders refused by the venue or failed on submit",
+        &["exchange", "symbol"]
+    )
+    .expect("venue_orders_rejected counter_vec")
+});
+
+pub static VENUE_FEES: Lazy<CounterVec> = Lazy::new(|| {
+    register_counter_vec!(
+        "titan_execution_venue_fees_total",
+        "Fees paid on fills, in the fee asset",
+        &["exchange", "symbol"]
+    )
+    .expect("venue_fees counter_vec")
+});
+
+pub static VENUE_VOLUME: Lazy<CounterVec> = Lazy::new(|| {
+    register_counter_vec!(
+        "titan_execution_venue_volume_notional_total",
+        "Filled notional (price * qty)",
+        &["exchange", "symbol"]
+    )
+    .expect("venue_volume counter_vec")
+});
+
+pub fn inc_venue_orders_placed(exchange: &str, symbol: &str) {
+    VENUE_ORDERS_PLACED
+        .with_label_values(&[exchange, symbol])
+        .inc();
+}
+
+pub fn inc_venue_orders_rejected(exchange: &str, symbol: &str) {
+    VENUE_ORDERS_REJECTED
+        .with_label_values(&[exchange, symbol])
+        .inc();
+}
+
+pub fn record_venue_fill(exchange: &str, symbol: &str, notional: f64, fee: f64) {
+    let labels = [exchange, symbol];
+    VENUE_ORDERS_FILLED.with_label_values(&labels).inc();
+    VENUE_VOLUME
+        .with_label_values(&labels)
+        .inc_by(notional.abs());
+    VENUE_FEES.with_label_values(&labels).inc_by(fee.max(0.0));
+}
+
 // --- Per-Subject NATS Rate Metrics (P2 Observability) ---
 
 pub static NATS_PUBLISH_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
@@ -265,3 +497,175 @@ pub fn inc_nats_consume(subject: &str) {
 pub fn inc_reconciliation_drift() {
     RECONCILIATION_DRIFT.inc();
 }
+
+// --- Market Data Source Authority ---
+
+pub static MARKET_DATA_SOURCE: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "titan_market_data_authoritative_source",
+        "1 for the venue currently setting a symbol's consolidated price",
+        &["symbol", "venue"]
+    )
+    .expect("market_data_authoritative_source gauge_vec")
+});
+
+pub static MARKET_DATA_FAILOVERS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "titan_market_data_source_failovers_total",
+        "Changes of authoritative market data source per symbol",
+        &["symbol"]
+    )
+    .expect("market_data_source_failovers counter_vec")
+});
+
+/// Move a symbol's authority from `previous` (if any) to `venue`.
+pub fn set_market_data_source(symbol: &str, previous: Option<&str>, venue: &str) {
+    if let Some(previous) = previous {
+        MARKET_DATA_SOURCE
+            .with_label_values(&[symbol, previous])
+            .set(0);
+        MARKET_DATA_FAILOVERS.with_label_values(&[symbol]).inc();
+    }
+    MARKET_DATA_SOURCE
+        .with_label_values(&[symbol, venue])
+        .set(1);
+}
+
+pub static MARKET_DATA_BAD_TICKS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "titan_market_data_bad_ticks_total",
+        "Ticks rejected by the book sanity guard",
+        &["venue", "reason"]
+    )
+    .expect("market_data_bad_ticks counter_vec")
+});
+
+pub fn inc_market_data_bad_ticks(venue: &str, reason: &str) {
+    MARKET_DATA_BAD_TICKS
+        .with_label_values(&[venue, reason])
+        .inc();
+}
+
+// --- Strategy Performance (realized PnL per symbol and per signal source) ---
+// Series are labelled scope="symbol"|"source" and name=<symbol or source>.
+
+pub static CLOSED_TRADES_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "titan_execution_closed_trades_total",
+        "Total closed trades",
+        &["scope", "name"]
+    )
+    .expect("closed_trades_total counter_vec")
+});
+
+pub static REALIZED_PNL: Lazy<GaugeVec> = Lazy::new(|| {
+    register_gauge_vec!(
+        "titan_execution_realized_pnl",
+        "Cumulative realized PnL of closed trades",
+        &["scope", "name"]
+    )
+    .expect("realized_pnl gauge_vec")
+});
+
+pub static WIN_RATE: Lazy<GaugeVec> = Lazy::new(|| {
+    register_gauge_vec!(
+        "titan_execution_win_rate",
+        "Share of closed trades with positive PnL (0-1)",
+        &["scope", "name"]
+    )
+    .expect("win_rate gauge_vec")
+});
+
+pub static AVG_WIN: Lazy<GaugeVec> = Lazy::new(|| {
+    register_gauge_vec!(
+        "titan_execution_avg_win",
+        "Average PnL of winning trades",
+        &["scope", "name"]
+    )
+    .expect("avg_win gauge_vec")
+});
+
+pub static AVG_LOSS: Lazy<GaugeVec> = Lazy::new(|| {
+    register_gauge_vec!(
+        "titan_execution_avg_loss",
+        "Average PnL of losing trades (negative)",
+        &["scope", "name"]
+    )
+    .expect("avg_loss gauge_vec")
+});
+
+/// Running totals behind the strategy performance gauges.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TradeStats {
+    pub trades: u64,
+    pub wins: u64,
+    pub losses: u64,
+    pub realized_pnl: f64,
+    pub gross_win: f64,
+    pub gross_loss: f64,
+}
+
+impl TradeStats {
+    fn record(&mut self, pnl: f64) {
+        self.trades += 1;
+        self.realized_pnl += pnl;
+        if pnl > 0.0 {
+            self.wins += 1;
+            self.gross_win += pnl;
+        } else if pnl < 0.0 {
+            self.losses += 1;
+            self.gross_loss += pnl;
+        }
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        if self.trades == 0 {
+            return 0.0;
+        }
+        self.wins as f64 / self.trades as f64
+    }
+
+    pub fn avg_win(&self) -> f64 {
+        if self.wins == 0 {
+            return 0.0;
+        }
+        self.gross_win / self.wins as f64
+    }
+
+    pub fn avg_loss(&self) -> f64 {
+        if self.losses == 0 {
+            return 0.0;
+        }
+        self.gross_loss / self.losses as f64
+    }
+}
+
+static TRADE_STATS: Lazy<Mutex<HashMap<(&'static str, String), TradeStats>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Fold a closed trade into the per-symbol and per-source stats and gauges.
+pub fn record_closed_trade(symbol: &str, source: &str, pnl: f64) {
+    let mut all = TRADE_STATS.lock();
+    for (scope, name) in [("symbol", symbol), ("source", source)] {
+        let stats = all.entry((scope, name.to_string())).or_default();
+        stats.record(pnl);
+
+        let labels = [scope, name];
+        CLOSED_TRADES_TOTAL.with_label_values(&labels).inc();
+        REALIZED_PNL
+            .with_label_values(&labels)
+            .set(stats.realized_pnl);
+        WIN_RATE.with_label_values(&labels).set(stats.win_rate());
+        AVG_WIN.with_label_values(&labels).set(stats.avg_win());
+        AVG_LOSS.with_label_values(&labels).set(stats.avg_loss());
+    }
+}
+
+/// Stats for one `scope` ("symbol" or "source") and name.
+pub fn trade_stats(scope: &str, name: &str) -> Option<TradeStats> {
+    TRADE_STATS
+        .lock()
+        .iter()
+        .find(|((s, n), _)| *s == scope && n == name)
+        .map(|(_, stats)| stats.clone())
+}
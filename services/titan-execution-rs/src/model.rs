@@ -179,6 +179,30 @@ pub struct Position {
     pub last_mark_price: Option<Decimal>,
     #[serde(default)]
     pub last_update_ts: i64,
+    /// Open entry lots, oldest first; their sizes sum to `size` and their
+    /// size-weighted price is `entry_price`. Empty for positions adopted from
+    /// an exchange, which only report an average entry.
+    #[serde(default)]
+    pub entry_lots: Vec<EntryLot>,
+}
+
+/// One fill that added to a position, reduced in place by partial closes
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EntryLot {
+    pub fill_id: String,
+    pub price: Decimal,
+    /// Remaining (unclosed) size
+    pub size: Decimal,
+    pub opened_at: i64,
+}
+
+/// The part of an entry lot consumed by a close, with its realized PnL
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LotClose {
+    pub fill_id: String,
+    pub entry_price: Decimal,
+    pub size: Decimal,
+    pub pnl: Decimal,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -197,6 +221,21 @@ pub struct TradeRecord {
     pub closed_at: DateTime<Utc>,
     pub close_reason: String,
     pub metadata: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lots: Vec<LotClose>,
+}
+
+/// One funding payment applied to a position. Positive amounts were paid.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FundingRecord {
+    pub exchange: String,
+    pub symbol: String,
+    /// Signal that opened the position the payment was charged to
+    pub position_signal_id: String,
+    pub amount: Decimal,
+    pub asset: String,
+    pub rate: Option<Decimal>,
+    pub funding_time: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -231,6 +270,8 @@ pub struct FillReport {
     pub timestamp: i64,
     #[serde(default)]
     pub dex_proof: Option<DexFillProof>,
+    #[serde(default)]
+    pub correlation_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -244,6 +285,9 @@ pub struct OrderParams {
     pub take_profits: Option<Vec<Decimal>>,
     pub signal_type: Option<String>,
     pub expected_profit_pct: Option<Decimal>,
+    /// Intent TTL and how much of it is left, for urgency-driven order types
+    pub ttl_ms: Option<i64>,
+    pub remaining_ttl_ms: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -262,6 +306,20 @@ pub struct FeeAnalysis {
     pub profit_after_impact_taker: Decimal,
 }
 
+impl FeeAnalysis {
+    /// Expected profit (%) after impact and fees on both legs: the entry at
+    /// the maker or taker rate, the exit at the taker rate since stops and
+    /// flattens cross the spread.
+    pub fn round_trip_profit(&self, maker_entry: bool) -> Decimal {
+        let after_entry = if maker_entry {
+            self.profit_after_impact_maker
+        } else {
+            self.profit_after_impact_taker
+        };
+        after_entry - self.taker_fee_pct
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderDecision {
     pub order_type: OrderType, // LIMIT, MARKET
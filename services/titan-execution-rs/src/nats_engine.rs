@@ -1,25 +1,42 @@
 use futures::StreamExt;
 use parking_lot::RwLock;
 use serde_json::Value;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tracing::{error, info, warn};
 
+use crate::admission::{priority_channel, Priority, DEFAULT_INTAKE_CAPACITY};
 use crate::armed_state::ArmedState;
-use crate::circuit_breaker::GlobalHalt;
+use crate::circuit_breaker::{GlobalHalt, HaltLevel};
+use crate::config::ConfirmationConfig;
 use crate::context::ExecutionContext;
+use crate::dlq::{dlq_payload, dlq_payload_with_rejections, DlqReasonCode};
 use crate::drift_detector::DriftDetector;
-use crate::exchange::adapter::OrderRequest;
+use crate::entry_zone::EntryZoneExecutor;
+use crate::event_log::{
+    EventLog, LoggedEvent, ReplayRequest, TYPE_FILL, TYPE_INTENT_DEAD_LETTERED,
+};
 use crate::exchange::router::ExecutionRouter;
 use crate::execution_constraints::ConstraintsStore;
+use crate::execution_report::ExecutionReportStore;
+use crate::intent_trace::IntentTracer;
 use crate::intent_validation::validate_intent_payload;
+use crate::maintenance_mode::MaintenanceMode;
+use crate::market_data::model::{FundingRate, OrderBookL2, Reconnect};
 use crate::metrics;
-use crate::model::IntentType;
 use crate::order_manager::OrderManager;
+use crate::panic_watchdog::PanicWatchdog;
+use crate::persistence::redb_store::StoreError;
 use crate::pipeline::ExecutionPipeline;
+use crate::rejection_breaker::RejectionRateBreaker;
+use crate::repricer::LimitRepricer;
 use crate::risk_guard::RiskGuard;
 use crate::shadow_state::{ExecutionEvent, ShadowState};
+use crate::shutdown::Flattener;
 use crate::simulation_engine::SimulationEngine;
+use crate::startup_reconciliation::StartupReconciler;
 use crate::subjects; // Canonical Subjects
+use crate::tp_ladder::TpLadderExecutor;
 
 /// Start the NATS Engine (Consumer Loop and Halt Listener)
 /// Returns a handle to the consumer task
@@ -32,11 +49,24 @@ pub async fn start_nats_engine(
     simulation_engine: Arc<SimulationEngine>,
     global_halt: Arc<GlobalHalt>,
     armed_state: Arc<ArmedState>,
+    maintenance: Arc<MaintenanceMode>,
     risk_guard: Arc<RiskGuard>,
     ctx: Arc<ExecutionContext>,
-    freshness_threshold: u64,
+    freshness_threshold: Arc<AtomicU64>,
     drift_detector: Arc<DriftDetector>,
     _constraints_store: Arc<ConstraintsStore>,
+    tp_ladder: Option<Arc<TpLadderExecutor>>,
+    repricer: Option<Arc<LimitRepricer>>,
+    entry_zone: Option<Arc<EntryZoneExecutor>>,
+    startup_reconciler: Option<Arc<StartupReconciler>>,
+    execution_reports: Arc<ExecutionReportStore>,
+    event_log: Arc<EventLog>,
+    tracer: Option<Arc<IntentTracer>>,
+    confirmation: ConfirmationConfig,
+    allowed_producers: Vec<String>,
+    panic_watchdog: Arc<PanicWatchdog>,
+    rejection_breaker: Arc<RejectionRateBreaker>,
+    flattener: Arc<Flattener>,
 ) -> Result<tokio::task::JoinHandle<()>, Box<dyn std::error::Error + Send + Sync>> {
     // --- System Halt Listener (Core NATS) ---
     // ... (unchanged)
@@ -49,16 +79,33 @@ pub async fn start_nats_engine(
     // Wait, the Instruction says "Update signature". I should use multi_replace.
 
     // --- PIPELINE CONSTRUCTION ---
-    let pipeline = Arc::new(ExecutionPipeline::new(
+    let mut pipeline = ExecutionPipeline::new(
         shadow_state.clone(),
         order_manager.clone(),
         router.clone(),
         simulation_engine.clone(),
         risk_guard.clone(),
         ctx.clone(),
-        freshness_threshold,
+        freshness_threshold.load(Ordering::Relaxed),
         drift_detector.clone(),
-    ));
+    )
+    .with_freshness_threshold(freshness_threshold)
+    .with_confirmation(confirmation)
+    .with_panic_watchdog(panic_watchdog)
+    .with_event_log(event_log.clone());
+    if let Some(tp_ladder) = tp_ladder {
+        pipeline = pipeline.with_tp_ladder(tp_ladder);
+    }
+    if let Some(repricer) = repricer {
+        pipeline = pipeline.with_repricer(repricer);
+    }
+    if let Some(entry_zone) = entry_zone {
+        pipeline = pipeline.with_entry_zone(entry_zone);
+    }
+    if let Some(tracer) = tracer {
+        pipeline = pipeline.with_tracer(tracer);
+    }
+    let pipeline = Arc::new(pipeline);
 
     // --- Market Data Listener (Staleness) ---
     let mut ticker_sub = client
@@ -85,6 +132,65 @@ pub async fn start_nats_engine(
         }
     });
 
+    // --- Market Data Reconnect Listener (Staleness Warm-up) ---
+    let mut reconnect_sub = client
+        .subscribe(subjects::DATA_MARKET_RECONNECT_PREFIX)
+        .await
+        .map_err(|e| {
+            error!("❌ Failed to subscribe to market data reconnects: {}", e);
+            e
+        })?;
+    let risk_guard_for_reconnect = risk_guard.clone();
+    tokio::spawn(async move {
+        while let Some(msg) = reconnect_sub.next().await {
+            match serde_json::from_slice::<Reconnect>(&msg.payload) {
+                Ok(reconnect) => risk_guard_for_reconnect
+                    .record_market_data_reconnect(&reconnect.exchange, &reconnect.symbols),
+                Err(e) => warn!("Invalid reconnect payload on {}: {}", msg.subject, e),
+            }
+        }
+    });
+
+    // --- Funding Rate Listener (Entry Gating) ---
+    let mut funding_sub = client
+        .subscribe(subjects::DATA_MARKET_FUNDING_PREFIX)
+        .await
+        .map_err(|e| {
+            error!("❌ Failed to subscribe to funding rates: {}", e);
+            e
+        })?;
+    let risk_guard_for_funding = risk_guard.clone();
+    tokio::spawn(async move {
+        while let Some(msg) = funding_sub.next().await {
+            match serde_json::from_slice::<FundingRate>(&msg.payload) {
+                Ok(rate) => risk_guard_for_funding.record_funding_rate(rate),
+                Err(e) => warn!("Invalid funding rate payload on {}: {}", msg.subject, e),
+            }
+        }
+    });
+
+    // --- Order Book Listener (Market Order Depth Gate) ---
+    let mut book_sub = client
+        .subscribe(subjects::DATA_MARKET_BOOK_PREFIX)
+        .await
+        .map_err(|e| {
+            error!("❌ Failed to subscribe to order books: {}", e);
+            e
+        })?;
+    let risk_guard_for_book = risk_guard.clone();
+    tokio::spawn(async move {
+        while let Some(msg) = book_sub.next().await {
+            // Topic: titan.data.market.book.v1.<venue>.<symbol>
+            let Some(venue) = msg.subject.split('.').nth(5).map(str::to_string) else {
+                continue;
+            };
+            match serde_json::from_slice::<OrderBookL2>(&msg.payload) {
+                Ok(book) => risk_guard_for_book.record_order_book(&venue, &book),
+                Err(e) => warn!("Invalid order book payload on {}: {}", msg.subject, e),
+            }
+        }
+    });
+
     // --- System Halt Listener (Unified SystemState) ---
     // Payload: { "state": "OPEN" | "SOFT_HALT" | "HARD_HALT", "reason": "...", "timestamp": ... }
     let mut halt_sub = client
@@ -110,18 +216,15 @@ pub async fn start_nats_engine(
                     match state_str {
                         "OPEN" => {
                             info!("🟢 System State: OPEN. Resuming operations.");
-                            halt_state_clone.set_halt(false, reason);
+                            halt_state_clone.set_level(HaltLevel::Open, reason);
                         }
                         "SOFT_HALT" => {
-                            // Rust currently treats Soft/Hard same (Halt)
-                            warn!(
-                                "🟡 System State: SOFT_HALT. Reducing risk (treated as HALT in Phase 2)."
-                            );
-                            halt_state_clone.set_halt(true, reason);
+                            warn!("🟡 System State: SOFT_HALT. Reduce-only: rejecting new opens.");
+                            halt_state_clone.set_level(HaltLevel::Soft, reason);
                         }
                         "HARD_HALT" => {
                             error!("🔴 System State: HARD_HALT. Emergency Stop.");
-                            halt_state_clone.set_halt(true, reason);
+                            halt_state_clone.set_level(HaltLevel::Hard, reason);
                         }
                         _ => {
                             warn!("Received unknown system state: {}", state_str);
@@ -185,7 +288,7 @@ pub async fn start_nats_engine(
         }
     });
 
-    // --- Get Balances Stub ---
+    // --- Get Balances Request-Reply Handler ---
     let mut balances_sub = client
         .subscribe(subjects::RPC_GET_BALANCES_PREFIX)
         .await
@@ -194,29 +297,91 @@ pub async fn start_nats_engine(
             e
         })?;
     let client_for_balances = client.clone();
-    let state_for_balances = shadow_state.clone();
+    let router_for_balances = router.clone();
 
     tokio::spawn(async move {
+        info!("👂 Listening for get_balances requests...");
         while let Some(msg) = balances_sub.next().await {
             if let Some(reply_to) = msg.reply {
-                let (equity, cash) = {
-                    let state = state_for_balances.read();
-                    (state.get_equity(), state.get_cash_balance())
-                };
+                // titan.rpc.execution.get_balances.v1.<venue>; unknown venues mean all
+                let subject_str = msg.subject.to_string();
+                let venue = subject_str
+                    .rsplit('.')
+                    .next()
+                    .filter(|v| router_for_balances.get_adapter(v).is_some());
+
+                let report = router_for_balances.fetch_balances(venue).await;
+                if let Ok(payload) = serde_json::to_vec(&report) {
+                    client_for_balances
+                        .publish(reply_to, payload.into())
+                        .await
+                        .ok();
+                }
+            }
+        }
+    });
 
+    // --- Health RPC (arming interlock and startup reconciliation) ---
+    let mut health_sub = client
+        .subscribe(subjects::RPC_GET_HEALTH)
+        .await
+        .map_err(|e| {
+            error!("❌ Failed to subscribe to get_health: {}", e);
+            e
+        })?;
+    let client_for_health = client.clone();
+    let armed_for_health = armed_state.clone();
+    let halt_for_health = global_halt.clone();
+    let maintenance_for_health = maintenance.clone();
+
+    tokio::spawn(async move {
+        info!("👂 Listening for get_health requests...");
+        while let Some(msg) = health_sub.next().await {
+            if let Some(reply_to) = msg.reply {
+                let reconciliation = startup_reconciler.as_ref().and_then(|r| r.report());
                 let response = serde_json::json!({
-                   "balances": [
-                       {
-                           "currency": "USDT",
-                           "available": cash,
-                           "locked": equity - cash,
-                           "total": equity,
-                           "updateTime": chrono::Utc::now().timestamp_millis()
-                       }
-                   ]
+                    "armed": armed_for_health.is_armed(),
+                    "arm_block": armed_for_health.arm_block(),
+                    "halt": halt_for_health.level().as_str(),
+                    "maintenance": maintenance_for_health.is_active(),
+                    "reconciliation": reconciliation,
                 });
                 if let Ok(payload) = serde_json::to_vec(&response) {
-                    client_for_balances
+                    client_for_health
+                        .publish(reply_to, payload.into())
+                        .await
+                        .ok();
+                }
+            }
+        }
+    });
+
+    // --- CDC Replay RPC (consumers that detect a seq gap re-read from here) ---
+    let mut replay_sub = client
+        .subscribe(subjects::RPC_REPLAY_EVENTS)
+        .await
+        .map_err(|e| {
+            error!("❌ Failed to subscribe to replay_events: {}", e);
+            e
+        })?;
+    let client_for_replay = client.clone();
+    let event_log_for_replay = event_log.clone();
+
+    tokio::spawn(async move {
+        info!("👂 Listening for event replay requests...");
+        while let Some(msg) = replay_sub.next().await {
+            if let Some(reply_to) = msg.reply {
+                let response = match serde_json::from_slice::<ReplayRequest>(&msg.payload) {
+                    Ok(request) => match event_log_for_replay.handle_replay(&request) {
+                        Ok(response) => serde_json::to_value(&response).unwrap_or_default(),
+                        Err(e) => serde_json::json!({ "error": e.to_string() }),
+                    },
+                    Err(e) => {
+                        serde_json::json!({ "error": format!("Invalid replay request: {}", e) })
+                    }
+                };
+                if let Ok(payload) = serde_json::to_vec(&response) {
+                    client_for_replay
                         .publish(reply_to, payload.into())
                         .await
                         .ok();
@@ -259,6 +424,32 @@ pub async fn start_nats_engine(
         }
     });
 
+    // --- Risk Pre-check RPC (what-if, no state mutation) ---
+    let mut precheck_sub = client
+        .subscribe(subjects::RPC_RISK_PRECHECK)
+        .await
+        .map_err(|e| {
+            error!("❌ Failed to subscribe to risk_precheck: {}", e);
+            e
+        })?;
+    let risk_guard_for_precheck = risk_guard.clone();
+    let client_for_precheck = client.clone();
+
+    tokio::spawn(async move {
+        info!("👂 Listening for risk pre-check requests...");
+        while let Some(msg) = precheck_sub.next().await {
+            if let Some(reply_to) = msg.reply {
+                let response = risk_guard_for_precheck.precheck_reply(&msg.payload);
+                if let Ok(payload) = serde_json::to_vec(&response) {
+                    client_for_precheck
+                        .publish(reply_to, payload.into())
+                        .await
+                        .ok();
+                }
+            }
+        }
+    });
+
     // --- Flatten Command Listener ---
     let mut flatten_sub = client
         .subscribe(subjects::CMD_RISK_FLATTEN)
@@ -267,80 +458,21 @@ pub async fn start_nats_engine(
             error!("❌ Failed to subscribe to flatten: {}", e);
             e
         })?;
-    let state_for_flatten = shadow_state.clone();
-    let router_flatten = router.clone();
-    let ctx_flatten = ctx.clone();
+    let client_for_flatten = client.clone();
 
     tokio::spawn(async move {
         info!("👂 Listening for risk flatten commands...");
-        while let Some(_msg) = flatten_sub.next().await {
+        while let Some(msg) = flatten_sub.next().await {
             warn!("🚨 RECEIVED FLATTEN COMMAND - CLOSING ALL POSITIONS");
-            let positions = state_for_flatten.read().get_all_positions();
-
-            for (symbol, pos) in positions {
-                let side_to_close = match pos.side {
-                    crate::model::Side::Buy | crate::model::Side::Long => crate::model::Side::Sell,
-                    crate::model::Side::Sell | crate::model::Side::Short => crate::model::Side::Buy,
-                };
-
-                info!("🚨 Flattening {} ({:?} {})", symbol, pos.side, pos.size);
-
-                // Create strict Market Order
-                let order_req = OrderRequest {
-                    symbol: symbol.replace("/", ""),
-                    side: side_to_close,
-                    order_type: crate::model::OrderType::Market,
-                    quantity: pos.size,
-                    price: None,
-                    stop_price: None,
-                    client_order_id: format!("flatten-{}", ctx_flatten.id.new_id()),
-                    reduce_only: true, // Important: Reduce Only to avoid flipping if async race
-                };
-
-                // We create a synthetic intent for the router
-                // Wait, router.execute takes &Intent and OrderRequest.
-                // We need a dummy intent.
-                let dummy_intent = crate::model::Intent {
-                    signal_id: "flatten-cmd".to_string(),
-                    symbol: symbol.clone(),
-                    direction: 0,
-                    intent_type: IntentType::Close,
-                    entry_zone: vec![],
-                    stop_loss: rust_decimal::Decimal::ZERO,
-                    take_profits: vec![],
-                    size: pos.size,
-                    status: crate::model::IntentStatus::Validated,
-                    source: Some("RiskFlatten".to_string()),
-                    policy_hash: None,
-                    t_signal: ctx_flatten.time.now_millis(),
-                    t_analysis: None,
-                    t_decision: None,
-                    t_ingress: None,
-                    t_exchange: None,
-                    max_slippage_bps: None,
-                    rejection_reason: None,
-                    regime_state: None,
-                    phase: None,
-                    metadata: None,
-                    exchange: None,
-                    // Envelope Standards
-                    ttl_ms: Some(5000),
-                    partition_key: None,
-                    causation_id: None,
-                    env: None,
-                    subject: None,
-                    position_mode: None,
-                    child_fills: vec![],
-                    filled_size: rust_decimal::Decimal::ZERO,
-                };
-
-                // Execute fire-and-forget (log errors)
-                let results = router_flatten.execute(&dummy_intent, order_req).await;
-                for (ex, _, res) in results {
-                    match res {
-                        Ok(_) => info!("✅ Flattened {} on {}", symbol, ex),
-                        Err(e) => error!("❌ Failed to flatten {} on {}: {}", symbol, ex, e),
-                    }
+            // Same path as the shutdown flatten: cancels first, closes
+            // reduce-only, books the fills and persists and publishes the report
+            let report = flattener.flatten("risk flatten command").await;
+            if let Some(reply_to) = msg.reply {
+                if let Ok(payload) = serde_json::to_vec(&report) {
+                    client_for_flatten
+                        .publish(reply_to, payload.into())
+                        .await
+                        .ok();
                 }
             }
         }
@@ -553,7 +685,8 @@ pub async fn start_nats_engine(
     );
 
     // Initialize HmacValidator once (reusable and thread-safe logic)
-    let hmac_validator = crate::security::HmacValidator::new();
+    let hmac_validator =
+        crate::security::HmacValidator::new().with_allowed_producers(&allowed_producers);
 
     // Pull messages
     let mut messages = consumer.messages().await.map_err(|e| {
@@ -561,6 +694,21 @@ pub async fn start_nats_engine(
         e
     })?;
 
+    // --- Priority Admission ---
+    // Exits and risk commands must not queue behind a backlog of opens: drain JetStream
+    // into a small bounded priority channel that feeds the pipeline loop below.
+    let (intake_tx, mut intake) = priority_channel(DEFAULT_INTAKE_CAPACITY);
+    tokio::spawn(async move {
+        while let Some(msg_result) = messages.next().await {
+            let priority = match &msg_result {
+                Ok(msg) => Priority::classify(&msg.subject, &msg.payload),
+                Err(_) => Priority::Normal,
+            };
+            intake_tx.send(priority, msg_result).await;
+        }
+        warn!("JetStream message stream ended");
+    });
+
     // Pre-clone for Risk Consumer (to avoid move into nats_handle)
     let global_halt_risk = global_halt.clone();
     let hmac_validator_risk = hmac_validator.clone();
@@ -569,41 +717,56 @@ pub async fn start_nats_engine(
     let nats_handle = tokio::spawn(async move {
         loop {
             tokio::select! {
-                Some(msg_result) = messages.next() => {
+                Some(msg_result) = intake.recv() => {
                     match msg_result {
                         Ok(msg) => {
                             // --- GLOBAL HALT CHECK ---
-                            if global_halt.is_halted() {
-                                warn!("⛔ Rejecting Intent (System Halted)");
+                            // SOFT_HALT admits closes / reduce-only intents; HARD_HALT admits nothing
+                            let reduces_risk = Priority::classify(&msg.subject, &msg.payload) != Priority::Normal;
+                            if !global_halt.allows(reduces_risk) {
+                                warn!("⛔ Rejecting Intent (System Halted: {})", global_halt.level().as_str());
                                 if let Err(e) = msg.ack().await {
                                      error!("Failed to ACK rejected intent: {}", e);
                                 }
                                 continue;
                             }
 
+                            // --- MAINTENANCE CHECK ---
+                            // New opens frozen; exits keep flowing so positions stay managed
+                            if !maintenance.allows(reduces_risk) {
+                                warn!("⛔ Rejecting Intent (Maintenance mode - new opens frozen)");
+                                publish_rejection_event(
+                                    &client_clone,
+                                    "maintenance_mode",
+                                    None,
+                                    None,
+                                    intent_id_of(&msg.payload).as_deref(),
+                                    None,
+                                    &ctx_nats,
+                                ).await;
+                                if let Err(e) = msg.ack().await {
+                                     error!("Failed to ACK rejected intent: {}", e);
+                                }
+                                continue;
+                            }
+
+                            // --- REJECTION-RATE BREAKER ---
+                            // Tripped with pause: leave new opens unacked so JetStream redelivers them later
+                            if !reduces_risk && rejection_breaker.is_paused(ctx_nats.time.now_millis()) {
+                                warn!("⏸️ Holding Intent (rejection-rate breaker paused new opens)");
+                                continue;
+                            }
+
                             // --- ARMED CHECK (Physical Interlock) ---
                             if !armed_state.is_armed() {
                                 warn!("⛔ Rejecting Intent (Execution DISARMED - physical interlock)");
 
-                                // Try to extract ID for telemetry (Best Effort)
-                                let intent_id: Option<String> = serde_json::from_slice::<serde_json::Value>(&msg.payload)
-                                    .ok()
-                                    .and_then(|v| {
-                                        // v is owned here. We must extract String before v is dropped.
-                                        let id_val = if let Some(p) = v.get("payload") {
-                                            p.get("signal_id").or(v.get("signal_id"))
-                                        } else {
-                                            v.get("signal_id")
-                                        };
-                                        id_val.and_then(|val| val.as_str().map(|s| s.to_string()))
-                                    });
-
                                 publish_rejection_event(
                                     &client_clone,
                                     "system_disarmed",
                                     None,
                                     None,
-                                    intent_id.as_deref(),
+                                    intent_id_of(&msg.payload).as_deref(),
                                     None,
                                     &ctx_nats,
                                 ).await;
@@ -639,6 +802,11 @@ pub async fn start_nats_engine(
                                     if let Ok(envelope) = serde_json::from_value::<crate::contracts::IntentEnvelope>(value.clone()) {
                                         if let Err(e) = hmac_validator.validate(&envelope, &value["payload"]) {
                                             error!("⛔ REJECTED Intent (Signature Verify Failed): {}", e);
+                                            let (rejection, reason_code) = if hmac_validator.is_producer_allowed(&envelope.producer) {
+                                                ("hmac_signature_mismatch", DlqReasonCode::HmacMismatch)
+                                            } else {
+                                                ("producer_not_allowed", DlqReasonCode::ProducerNotAllowed)
+                                            };
 
                                             // Extract ID for telemetry
                                             let intent_id = value.get("payload")
@@ -647,13 +815,23 @@ pub async fn start_nats_engine(
 
                                             publish_rejection_event(
                                                 &client_clone,
-                                                "hmac_signature_mismatch",
+                                                rejection,
                                                 None,
                                                 None,
                                                 intent_id,
                                                 None,
                                                 &ctx_nats,
                                             ).await;
+                                            publish_dlq(
+                                                &client_clone,
+                                                &event_log,
+                                                &msg.payload,
+                                                reason_code,
+                                                &format!("Signature verify failed: {}", e),
+                                                envelope.correlation_id.as_deref(),
+                                                &ctx_nats,
+                                            ).await;
+                                            rejection_breaker.record(ctx_nats.time.now_millis(), true);
 
                                             // ACK to prevent retry loops of bad messages
                                             if let Err(e) = msg.ack().await { error!("Failed to ACK rejected intent: {}", e); }
@@ -713,10 +891,14 @@ pub async fn start_nats_engine(
                                             ).await;
                                             publish_dlq(
                                                 &client_clone,
+                                                &event_log,
                                                 &msg.payload,
+                                                DlqReasonCode::PolicyHashMismatch,
                                                 &format!("Policy Hash mismatch: exp {} got {}", current_hash, hash),
-                                                &ctx_nats
+                                                Some(&correlation_id),
+                                                &ctx_nats,
                                             ).await;
+                                            rejection_breaker.record(ctx_nats.time.now_millis(), true);
                                             if let Err(e) = msg.ack().await {
                                                 error!("Failed to ACK rejected intent: {}", e);
                                             }
@@ -766,8 +948,9 @@ pub async fn start_nats_engine(
 
                                     // --- EXECUTION PIPELINE ---
                                     metrics::inc_nats_consume(subjects::CMD_EXECUTION_PLACE_PREFIX);
-                                    let result = pipeline.process_intent(intent.clone(), correlation_id.clone()).await;
+                                    let result = pipeline.process_intent_guarded(intent.clone(), correlation_id.clone()).await;
 
+                                    rejection_breaker.record(ctx_nats.time.now_millis(), result.is_err());
                                     match result {
                                         Ok(pipeline_result) => {
                                             // 1. Shadow Fill
@@ -789,6 +972,10 @@ pub async fn start_nats_engine(
 
                                             // 3. Execution Events
                                             for event in pipeline_result.events {
+                                                publish_cdc(
+                                                    &client_clone,
+                                                    event_log.append_execution_event(&event, Some(correlation_id.as_str()), ctx_nats.time.now_millis()),
+                                                ).await;
                                                 match event {
                                                     ExecutionEvent::Opened(pos) => info!("Pos Open: {} {}", pos.symbol, pos.size),
                                                     ExecutionEvent::Updated(pos) => info!("Pos Upd: {} {}", pos.symbol, pos.size),
@@ -816,6 +1003,7 @@ pub async fn start_nats_engine(
                                                             "version": 1,
                                                             "ts": ctx_nats.time.now_millis(),
                                                             "producer": "titan-execution-rs",
+                                                            "correlation_id": correlation_id,
                                                             "payload": {
                                                                 "symbol": symbol,
                                                                 "amount": amount,
@@ -842,11 +1030,85 @@ pub async fn start_nats_engine(
                                                         }
                                                     }
 
+                                                    ExecutionEvent::FillAnomaly(anomaly) => {
+                                                        let reason = format!(
+                                                            "Fill price anomaly: {} on {} filled at {} vs mid {} ({}% off)",
+                                                            anomaly.symbol,
+                                                            anomaly.exchange,
+                                                            anomaly.fill_price,
+                                                            anomaly.mid,
+                                                            anomaly.deviation_pct.round_dp(2)
+                                                        );
+                                                        if let Ok(bytes) = serde_json::to_vec(&anomaly) {
+                                                            publish_dlq(
+                                                                &client_clone,
+                                                                &event_log,
+                                                                &bytes,
+                                                                DlqReasonCode::AdapterError,
+                                                                &reason,
+                                                                Some(&correlation_id),
+                                                                &ctx_nats,
+                                                            ).await;
+                                                        }
+                                                        publish_rejection_event(
+                                                            &client_clone,
+                                                            "fill_price_anomaly",
+                                                            None,
+                                                            None,
+                                                            Some(anomaly.signal_id.as_str()),
+                                                            None,
+                                                            &ctx_nats,
+                                                        ).await;
+                                                    }
+
+                                                    ExecutionEvent::Overfill(overfill) => {
+                                                        let reason = format!(
+                                                            "Over-fill: {} on {} fill of {} after {} filled exceeds intent size {}; {} quarantined",
+                                                            overfill.symbol,
+                                                            overfill.exchange,
+                                                            overfill.fill_size,
+                                                            overfill.filled_size,
+                                                            overfill.intent_size,
+                                                            overfill.excess
+                                                        );
+                                                        if let Ok(bytes) = serde_json::to_vec(&overfill) {
+                                                            publish_dlq(
+                                                                &client_clone,
+                                                                &event_log,
+                                                                &bytes,
+                                                                DlqReasonCode::Overfill,
+                                                                &reason,
+                                                                Some(&correlation_id),
+                                                                &ctx_nats,
+                                                            ).await;
+                                                        }
+                                                        publish_rejection_event(
+                                                            &client_clone,
+                                                            "fill_overfill",
+                                                            None,
+                                                            None,
+                                                            Some(overfill.signal_id.as_str()),
+                                                            None,
+                                                            &ctx_nats,
+                                                        ).await;
+                                                        // The venue holds more than shadow state: no new opens until reconciled
+                                                        if global_halt.level() < HaltLevel::Soft {
+                                                            error!("🚨 OVER-FILL → SOFT HALT: {}", reason);
+                                                            global_halt.set_level(HaltLevel::Soft, &reason);
+                                                        }
+                                                    }
+
                                                 }
                                             }
 
                                             // 4. Fill Reports
                                             for (exchange_name, fill_report) in pipeline_result.fill_reports {
+                                                publish_cdc(
+                                                    &client_clone,
+                                                    serde_json::to_value(&fill_report)
+                                                        .map_err(StoreError::from)
+                                                        .and_then(|payload| event_log.append(TYPE_FILL, Some(correlation_id.as_str()), payload, ctx_nats.time.now_millis())),
+                                                ).await;
                                                 let subject = format!(
                                                     "{}.{}.main.{}",
                                                     subjects::EVT_EXECUTION_FILL,
@@ -869,6 +1131,24 @@ pub async fn start_nats_engine(
                                                 }
                                             }
 
+                                            // 5. Consolidated Execution Report
+                                            if let Some(report) = pipeline_result.execution_report {
+                                                let envelope = serde_json::json!({
+                                                    "id": ctx_nats.id.new_id(),
+                                                    "type": "titan.event.execution.report.v1",
+                                                    "version": 1,
+                                                    "ts": ctx_nats.time.now_millis(),
+                                                    "producer": "titan-execution-rs",
+                                                    "correlation_id": correlation_id,
+                                                    "payload": report
+                                                });
+
+                                                if let Ok(payload) = serde_json::to_vec(&envelope) {
+                                                    client_clone.publish(subjects::EVT_EXECUTION_REPORT, payload.into()).await.ok();
+                                                }
+                                                execution_reports.insert(report);
+                                            }
+
                                             // ACK
                                             if let Err(e) = msg.ack().await {
                                                 error!("❌ Failed to ACK message: {}", e);
@@ -882,17 +1162,31 @@ pub async fn start_nats_engine(
                                                 global_halt.set_halt(true, "Reconciliation drift detected");
                                             }
                                         }
-                                        Err(reason) => {
+                                        Err(err) => {
                                             error!(
                                                 correlation_id = %correlation_id,
                                                 signal_id = %intent.signal_id,
+                                                reason_code = %err.code,
                                                 "Pipeline Failure: {}",
-                                                reason
+                                                err.reason
+                                            );
+                                            // Risk rejections, stale intents and venue refusals all
+                                            // surface here; the pipeline tags which one it was.
+                                            let now = ctx_nats.time.now_millis();
+                                            let record = dlq_payload_with_rejections(
+                                                err.code,
+                                                &err.reason,
+                                                &msg.payload,
+                                                now,
+                                                &err.rejections,
                                             );
-                                            // We publish DLQ inside pipeline? No, inside here.
-                                            // But risk rejection was inside pipeline which returned Err.
-                                            // And Latency checks too.
-                                            publish_dlq(&client_clone, &msg.payload, &reason, &ctx_nats).await;
+                                            publish_dlq_record(
+                                                &client_clone,
+                                                &event_log,
+                                                record,
+                                                Some(&correlation_id),
+                                                now,
+                                            ).await;
 
                                             // Must ACK to prevent redelivery loop if it's a permanent failure
                                             // Logic assumption: If pipeline returned Err, it's rejected/dropped suitable for DLQ.
@@ -906,7 +1200,16 @@ pub async fn start_nats_engine(
                                 Err(e) => {
                                     error!("Failed to validate intent: {}", e);
                                     metrics::inc_invalid_intents();
-                                    publish_dlq(&client_clone, &msg.payload, &format!("Invalid intent: {}", e), &ctx_nats).await;
+                                    publish_dlq(
+                                        &client_clone,
+                                        &event_log,
+                                        &msg.payload,
+                                        DlqReasonCode::ValidationFailure,
+                                        &format!("Invalid intent: {}", e),
+                                        None,
+                                        &ctx_nats,
+                                    ).await;
+                                    rejection_breaker.record(ctx_nats.time.now_millis(), true);
                                     msg.ack().await.ok();
                                 }
                             }
@@ -1036,20 +1339,53 @@ pub async fn start_nats_engine(
     Ok(nats_handle)
 }
 
+/// Publish a freshly sequenced event-log record on the CDC feed.
+async fn publish_cdc(client: &async_nats::Client, record: Result<LoggedEvent, StoreError>) {
+    match record {
+        Ok(record) => {
+            if let Ok(payload) = serde_json::to_vec(&record) {
+                client
+                    .publish(subjects::EVT_EXECUTION_CDC, payload.into())
+                    .await
+                    .ok();
+            }
+        }
+        Err(e) => error!("❌ Failed to append to event log: {}", e),
+    }
+}
+
+/// Dead-letter a rejected message on both DLQ subjects and record it in the
+/// event log, tagged with a structured reason code.
 async fn publish_dlq(
     client: &async_nats::Client,
+    event_log: &EventLog,
     payload: &[u8],
+    code: DlqReasonCode,
     reason: &str,
+    correlation_id: Option<&str>,
     ctx: &ExecutionContext,
 ) {
-    let parsed_payload = serde_json::from_slice::<Value>(payload)
-        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(payload).to_string()));
+    let now = ctx.time.now_millis();
+    let record = dlq_payload(code, reason, payload, now);
+    publish_dlq_record(client, event_log, record, correlation_id, now).await;
+}
 
-    let dlq_payload = serde_json::json!({
-        "reason": reason,
-        "payload": parsed_payload,
-        "t_ingress": ctx.time.now_millis(),
-    });
+/// Publish an already-built DLQ record and record it in the event log.
+async fn publish_dlq_record(
+    client: &async_nats::Client,
+    event_log: &EventLog,
+    dlq_payload: serde_json::Value,
+    correlation_id: Option<&str>,
+    now: i64,
+) {
+    if let Err(e) = event_log.append(
+        TYPE_INTENT_DEAD_LETTERED,
+        correlation_id,
+        dlq_payload.clone(),
+        now,
+    ) {
+        error!("❌ Failed to record dead-lettered message: {}", e);
+    }
 
     if let Ok(bytes) = serde_json::to_vec(&dlq_payload) {
         let _ = client
@@ -1062,6 +1398,16 @@ async fn publish_dlq(
     }
 }
 
+/// Signal id of a raw or enveloped intent, for rejection telemetry (best effort).
+fn intent_id_of(payload: &[u8]) -> Option<String> {
+    let v = serde_json::from_slice::<serde_json::Value>(payload).ok()?;
+    let id_val = match v.get("payload") {
+        Some(p) => p.get("signal_id").or(v.get("signal_id")),
+        None => v.get("signal_id"),
+    };
+    id_val.and_then(|val| val.as_str().map(|s| s.to_string()))
+}
+
 /// Publish rejection telemetry event for observability and alerting
 /// Subject: titan.evt.execution.reject.v1
 async fn publish_rejection_event(
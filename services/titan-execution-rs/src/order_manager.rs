@@ -1,9 +1,14 @@
 use crate::circuit_breaker::GlobalHalt;
+use crate::config::{ExecutionConfig, OrderTypeConfig, OrderTypeMode};
 use crate::impact_calculator::{ImpactCalculator, OrderRouting};
+use crate::maintenance_mode::MaintenanceMode;
 use crate::market_data::engine::MarketDataEngine;
+use crate::market_data::types::BookTicker;
 use crate::model::{FeeAnalysis, OrderDecision, OrderParams, OrderType, Side};
+use parking_lot::{RwLock, RwLockReadGuard};
 use rust_decimal::prelude::*;
 use rust_decimal::Decimal;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{info, warn};
 
@@ -16,12 +21,25 @@ pub const MIN_PROFIT_MARGIN: &str = "0.001"; // 0.1%
 pub const IMBALANCE_THRESHOLD_BUY: &str = "0.6";
 pub const IMBALANCE_THRESHOLD_SELL: &str = "-0.6";
 
+/// Top-of-book imbalance: (BidQty - AskQty) / (BidQty + AskQty) -> Range [-1, 1]
+pub fn book_imbalance(ticker: &BookTicker) -> Decimal {
+    let total_qty = ticker.best_bid_qty + ticker.best_ask_qty;
+    if total_qty.is_zero() {
+        Decimal::ZERO
+    } else {
+        (ticker.best_bid_qty - ticker.best_ask_qty) / total_qty
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct OrderManagerConfig {
     pub maker_fee_pct: Decimal,
     pub taker_fee_pct: Decimal,
     pub chase_timeout_ms: u64,
     pub min_profit_margin: Decimal,
+    pub order_type: OrderTypeConfig,
+    /// Price increment per symbol; urgency prices are rounded onto it
+    pub tick_sizes: HashMap<String, Decimal>,
 }
 
 impl Default for OrderManagerConfig {
@@ -34,16 +52,38 @@ impl Default for OrderManagerConfig {
             chase_timeout_ms: DEFAULT_CHASE_TIMEOUT_MS,
             min_profit_margin: Decimal::from_str(MIN_PROFIT_MARGIN)
                 .expect("Invalid min profit constant"),
+            order_type: OrderTypeConfig::default(),
+            tick_sizes: HashMap::new(),
+        }
+    }
+}
+
+impl OrderManagerConfig {
+    /// Order type schedule and tick sizes from the execution config; fees
+    /// and chase timing keep their defaults.
+    pub fn from_execution(exec: &ExecutionConfig) -> Self {
+        Self {
+            order_type: exec.order_type.clone(),
+            tick_sizes: exec
+                .instruments
+                .iter()
+                .filter_map(|(symbol, instrument)| {
+                    Some((symbol.clone(), Decimal::from_f64(instrument.tick_size?)?))
+                })
+                .collect(),
+            ..Default::default()
         }
     }
 }
 
 #[derive(Clone)]
 pub struct OrderManager {
-    config: OrderManagerConfig,
+    /// Shared with the config reloader, which swaps it on SIGHUP
+    config: Arc<RwLock<OrderManagerConfig>>,
     market_data: Arc<MarketDataEngine>,
     impact_calculator: ImpactCalculator,
     global_halt: Arc<GlobalHalt>,
+    maintenance: Option<Arc<MaintenanceMode>>,
 }
 
 impl OrderManager {
@@ -62,13 +102,29 @@ impl OrderManager {
         );
 
         Self {
-            config,
+            config: Arc::new(RwLock::new(config)),
             market_data,
             impact_calculator: ImpactCalculator::new(),
             global_halt,
+            maintenance: None,
         }
     }
 
+    /// Handle to the live config, for swapping it without a restart
+    pub fn shared_config(&self) -> Arc<RwLock<OrderManagerConfig>> {
+        self.config.clone()
+    }
+
+    fn config(&self) -> RwLockReadGuard<'_, OrderManagerConfig> {
+        self.config.read()
+    }
+
+    /// Refuse new opens while maintenance mode is active
+    pub fn with_maintenance_mode(mut self, maintenance: Arc<MaintenanceMode>) -> Self {
+        self.maintenance = Some(maintenance);
+        self
+    }
+
     /// Assess liquidity quality for a symbol
     /// Returns: (spread_bps, imbalance_ratio)
     /// Imbalance: (BidQty - AskQty) / (BidQty + AskQty) -> Range [-1, 1]
@@ -85,17 +141,7 @@ impl OrderManager {
         let spread_diff = ticker.best_ask - ticker.best_bid;
         let spread_bps = (spread_diff / mid_price) * Decimal::from(10000);
 
-        // Imbalance
-        let total_qty = ticker.best_bid_qty + ticker.best_ask_qty;
-        let diff_qty = ticker.best_bid_qty - ticker.best_ask_qty;
-
-        let imbalance = if total_qty.is_zero() {
-            Decimal::ZERO
-        } else {
-            diff_qty / total_qty
-        };
-
-        Some((spread_bps, imbalance))
+        Some((spread_bps, book_imbalance(&ticker)))
     }
 
     pub fn analyze_fees(
@@ -103,18 +149,17 @@ impl OrderManager {
         expected_profit_pct: Decimal,
         estimated_impact_pct: Decimal,
     ) -> FeeAnalysis {
-        let profit_after_maker =
-            expected_profit_pct - self.config.maker_fee_pct - estimated_impact_pct;
-        let profit_after_taker =
-            expected_profit_pct - self.config.taker_fee_pct - estimated_impact_pct;
+        let config = self.config();
+        let profit_after_maker = expected_profit_pct - config.maker_fee_pct - estimated_impact_pct;
+        let profit_after_taker = expected_profit_pct - config.taker_fee_pct - estimated_impact_pct;
 
         FeeAnalysis {
-            maker_fee_pct: self.config.maker_fee_pct,
-            taker_fee_pct: self.config.taker_fee_pct,
+            maker_fee_pct: config.maker_fee_pct,
+            taker_fee_pct: config.taker_fee_pct,
             expected_profit_pct,
-            profit_after_maker: expected_profit_pct - self.config.maker_fee_pct,
-            profit_after_taker: expected_profit_pct - self.config.taker_fee_pct,
-            taker_profitable: profit_after_taker > self.config.min_profit_margin,
+            profit_after_maker: expected_profit_pct - config.maker_fee_pct,
+            profit_after_taker: expected_profit_pct - config.taker_fee_pct,
+            taker_profitable: profit_after_taker > config.min_profit_margin,
             estimated_impact_pct,
             profit_after_impact_maker: profit_after_maker,
             profit_after_impact_taker: profit_after_taker,
@@ -135,9 +180,15 @@ impl OrderManager {
     }
 
     pub fn decide_order_type(&self, params: &OrderParams) -> OrderDecision {
+        let reduce_only = Self::is_exit_signal(params.signal_type.as_ref());
+
         // --- 0. SAFETY CHECK: GLOBAL HALT ---
-        if self.global_halt.is_halted() {
-            warn!("⛔ ORDER REJECTED: SYSTEM HALTED");
+        // SOFT_HALT still lets exits through so risk can be reduced
+        if !self.global_halt.allows(reduce_only) {
+            warn!(
+                "⛔ ORDER REJECTED: SYSTEM HALTED ({})",
+                self.global_halt.level().as_str()
+            );
             return OrderDecision {
                 order_type: OrderType::Limit, // Dummy
                 post_only: true,
@@ -147,8 +198,21 @@ impl OrderManager {
                 fee_analysis: None,
             };
         }
-
-        let reduce_only = Self::is_exit_signal(params.signal_type.as_ref());
+        if self
+            .maintenance
+            .as_ref()
+            .is_some_and(|m| !m.allows(reduce_only))
+        {
+            warn!("⛔ ORDER REJECTED: MAINTENANCE MODE (new opens frozen)");
+            return OrderDecision {
+                order_type: OrderType::Limit, // Dummy
+                post_only: true,
+                reduce_only: true,
+                limit_price: None,
+                reason: "MAINTENANCE_MODE".to_string(),
+                fee_analysis: None,
+            };
+        }
 
         // Default decision: Maker order
         let mut decision = OrderDecision {
@@ -160,7 +224,16 @@ impl OrderManager {
             fee_analysis: None,
         };
 
-        // --- IMPACT ANALYSIS ---
+        // --- URGENCY: aggressiveness follows the remaining TTL ---
+        // Sets the starting point only; impact, spread and profitability
+        // checks below can still back it off to a maker order.
+        let urgent = self.config().order_type.mode == OrderTypeMode::Urgency;
+        if urgent {
+            if let Some(urgent_decision) = self.urgency_decision(params, &decision) {
+                decision = urgent_decision;
+            }
+        }
+
         // --- IMPACT ANALYSIS ---
         // Simplified notional calculation (size * price). using Limit price if available or best ask
         let mut exec_price = params.limit_price.unwrap_or(Decimal::ZERO);
@@ -203,7 +276,7 @@ impl OrderManager {
             // Enforce routing based on impact
             match impact_est.recommended_routing {
                 OrderRouting::PostOnly => {
-                    decision.post_only = true;
+                    Self::back_off_to_maker(&mut decision, params);
                     // decision.reason += " (Impact: PostOnly)";
                 }
                 OrderRouting::Limit => {
@@ -215,7 +288,7 @@ impl OrderManager {
                     }
                 }
                 OrderRouting::Rejected => {
-                    decision.post_only = true; // Fallback
+                    Self::back_off_to_maker(&mut decision, params); // Fallback
                 }
             }
         }
@@ -233,30 +306,33 @@ impl OrderManager {
             // Don't cross wide spreads.
             if spread_bps > Decimal::from(10) {
                 decision.reason = format!("WIDE_SPREAD_MAKER: {}bps", spread_bps);
-                decision.post_only = true;
+                Self::back_off_to_maker(&mut decision, params);
                 return decision;
             }
 
-            // 2. Imbalance Sniping (FOMO / Panic)
-            // If we are Buying and Imbalance > 0.6 (Strong Buy Pressure), liquidity is fleeting.
-            // Switch to TAKER (Market or Aggressive Limit) to swipe before it's gone.
-            let imb_buy_thresh =
-                Decimal::from_str(IMBALANCE_THRESHOLD_BUY).unwrap_or(Decimal::new(6, 1));
-            if params.side == Side::Buy && imbalance > imb_buy_thresh {
-                decision.order_type = OrderType::Market;
-                decision.post_only = false;
-                decision.reason = format!("IMBALANCE_SNIPE_BUY: Imb {}", imbalance);
-                return decision;
-            }
+            // In urgency mode the TTL schedule, not the book, sets aggressiveness
+            if !urgent {
+                // 2. Imbalance Sniping (FOMO / Panic)
+                // If we are Buying and Imbalance > 0.6 (Strong Buy Pressure), liquidity is fleeting.
+                // Switch to TAKER (Market or Aggressive Limit) to swipe before it's gone.
+                let imb_buy_thresh =
+                    Decimal::from_str(IMBALANCE_THRESHOLD_BUY).unwrap_or(Decimal::new(6, 1));
+                if params.side == Side::Buy && imbalance > imb_buy_thresh {
+                    decision.order_type = OrderType::Market;
+                    decision.post_only = false;
+                    decision.reason = format!("IMBALANCE_SNIPE_BUY: Imb {}", imbalance);
+                    return decision;
+                }
 
-            // If we are Selling and Imbalance < -0.6 (Strong Sell Pressure)
-            let imb_sell_thresh =
-                Decimal::from_str(IMBALANCE_THRESHOLD_SELL).unwrap_or(Decimal::new(-6, 1));
-            if params.side == Side::Sell && imbalance < imb_sell_thresh {
-                decision.order_type = OrderType::Market;
-                decision.post_only = false;
-                decision.reason = format!("IMBALANCE_SNIPE_SELL: Imb {}", imbalance);
-                return decision;
+                // If we are Selling and Imbalance < -0.6 (Strong Sell Pressure)
+                let imb_sell_thresh =
+                    Decimal::from_str(IMBALANCE_THRESHOLD_SELL).unwrap_or(Decimal::new(-6, 1));
+                if params.side == Side::Sell && imbalance < imb_sell_thresh {
+                    decision.order_type = OrderType::Market;
+                    decision.post_only = false;
+                    decision.reason = format!("IMBALANCE_SNIPE_SELL: Imb {}", imbalance);
+                    return decision;
+                }
             }
         }
 
@@ -276,13 +352,14 @@ impl OrderManager {
         let fee_analysis = self.analyze_fees(expected_profit, impact_pct);
 
         // Strict profitability check after impact
-        // If Maker strategy is selected (or forced), check if Maker is profitable
-        if decision.order_type != OrderType::Market {
-            if fee_analysis.profit_after_impact_maker < self.config.min_profit_margin {
+        // If Maker strategy is selected (or forced), check if Maker is profitable.
+        // A limit crossing the spread pays taker fees like a market order.
+        if decision.post_only {
+            if fee_analysis.profit_after_impact_maker < self.config().min_profit_margin {
                 decision.reason = format!(
                     "UNPROFITABLE_MAKER: {}% < {}% (Impact: {}bps)",
                     fee_analysis.profit_after_impact_maker.round_dp(4),
-                    self.config.min_profit_margin,
+                    self.config().min_profit_margin,
                     estimated_impact_bps
                 );
                 // We flag it. The caller (Executor) should handle rejection logic based on reason or specific flag.
@@ -292,16 +369,15 @@ impl OrderManager {
             }
         } else {
             // If Taker is selected, check if Taker is profitable
-            if fee_analysis.profit_after_impact_taker < self.config.min_profit_margin {
+            if fee_analysis.profit_after_impact_taker < self.config().min_profit_margin {
                 decision.reason = format!(
                     "UNPROFITABLE_TAKER: {}% < {}% (Impact: {}bps)",
                     fee_analysis.profit_after_impact_taker.round_dp(4),
-                    self.config.min_profit_margin,
+                    self.config().min_profit_margin,
                     estimated_impact_bps
                 );
                 // Revert to Maker if Taker is too expensive
-                decision.order_type = OrderType::Limit;
-                decision.post_only = true;
+                Self::back_off_to_maker(&mut decision, params);
                 warn!(
                     "Profitability Rejection (Taker): Reverting to Maker. {}",
                     decision.reason
@@ -325,6 +401,93 @@ impl OrderManager {
         decision
     }
 
+    /// Turn a taker or crossing decision into a post-only limit at the
+    /// intent's price.
+    fn back_off_to_maker(decision: &mut OrderDecision, params: &OrderParams) {
+        if !decision.post_only || decision.order_type == OrderType::Market {
+            decision.limit_price = params.limit_price;
+        }
+        decision.order_type = OrderType::Limit;
+        decision.post_only = true;
+    }
+
+    /// Round `price` onto the symbol's tick, away from crossing: down for
+    /// buys, up for sells. Unchanged when no tick size is configured.
+    fn round_to_tick(&self, symbol: &str, side: &Side, price: Decimal) -> Decimal {
+        let Some(tick) = self
+            .config()
+            .tick_sizes
+            .get(symbol)
+            .copied()
+            .filter(|t| !t.is_zero())
+        else {
+            return price;
+        };
+        let ticks = price / tick;
+        let ticks = match side {
+            Side::Buy | Side::Long => ticks.floor(),
+            Side::Sell | Side::Short => ticks.ceil(),
+        };
+        ticks * tick
+    }
+
+    /// Time-sliced aggressiveness: maker at the touch while most of the TTL is
+    /// left, then limits crossing a growing share of the spread, then a market
+    /// order near expiry. A crossing limit never goes past the intent's limit
+    /// price. None when the intent carries no TTL.
+    fn urgency_decision(
+        &self,
+        params: &OrderParams,
+        base: &OrderDecision,
+    ) -> Option<OrderDecision> {
+        let ttl = params.ttl_ms.filter(|ttl| *ttl > 0)?;
+        let remaining = params.remaining_ttl_ms?.clamp(0, ttl) as f64 / ttl as f64;
+        let schedule = self.config().order_type.clone();
+        let mut decision = base.clone();
+
+        if remaining <= schedule.taker_at_or_below {
+            decision.order_type = OrderType::Market;
+            decision.post_only = false;
+            decision.limit_price = None;
+            decision.reason = format!("URGENCY_TAKER: {:.0}% TTL left", remaining * 100.0);
+            return Some(decision);
+        }
+
+        let ticker = self.market_data.get_ticker(&params.symbol);
+        let (near, far) = match (&ticker, &params.side) {
+            (Some(t), Side::Buy | Side::Long) => (t.best_bid, t.best_ask),
+            (Some(t), Side::Sell | Side::Short) => (t.best_ask, t.best_bid),
+            (None, _) => return None,
+        };
+
+        decision.order_type = OrderType::Limit;
+        if remaining > schedule.maker_above {
+            decision.post_only = true;
+            decision.limit_price = Some(near);
+            decision.reason = format!("URGENCY_MAKER: {:.0}% TTL left", remaining * 100.0);
+        } else {
+            let cross = (schedule.maker_above - remaining)
+                / (schedule.maker_above - schedule.taker_at_or_below);
+            let cross = Decimal::from_f64(cross)
+                .unwrap_or(Decimal::ZERO)
+                .round_dp(4);
+            let mut price = near + (far - near) * cross;
+            if let Some(limit) = params.limit_price {
+                price = match params.side {
+                    Side::Buy | Side::Long => price.min(limit),
+                    Side::Sell | Side::Short => price.max(limit),
+                };
+            }
+            decision.post_only = false;
+            decision.limit_price = Some(self.round_to_tick(&params.symbol, &params.side, price));
+            decision.reason = format!(
+                "URGENCY_CROSS: {}% of spread",
+                (cross * Decimal::ONE_HUNDRED).round()
+            );
+        }
+        Some(decision)
+    }
+
     pub fn evaluate_taker_conversion(
         &self,
         signal_id: &str,
@@ -332,12 +495,13 @@ impl OrderManager {
         elapsed_ms: u64,
     ) -> TakerConversionResult {
         // If not past chase timeout, wait
-        if elapsed_ms < self.config.chase_timeout_ms {
+        if elapsed_ms < self.config().chase_timeout_ms {
             return TakerConversionResult {
                 action: TakerAction::Wait,
                 reason: format!(
                     "Chase timeout not reached ({}ms < {}ms)",
-                    elapsed_ms, self.config.chase_timeout_ms
+                    elapsed_ms,
+                    self.config().chase_timeout_ms
                 ),
                 fee_analysis: None,
             };
@@ -376,7 +540,8 @@ impl OrderManager {
             action: TakerAction::Cancel,
             reason: format!(
                 "INSUFFICIENT_PROFIT_FOR_TAKER: {}% < {}%",
-                fee_analysis.profit_after_taker, self.config.min_profit_margin
+                fee_analysis.profit_after_taker,
+                self.config().min_profit_margin
             ),
             fee_analysis: Some(fee_analysis),
         }
@@ -0,0 +1,217 @@
+use std::any::Any;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use futures::FutureExt;
+use parking_lot::Mutex;
+use tracing::error;
+
+use crate::circuit_breaker::GlobalHalt;
+use crate::config::PanicWatchdogConfig;
+use crate::metrics;
+use crate::shutdown::{Flattener, ShutdownReport};
+
+/// Catches panics in intent processing so the consumer keeps running, and
+/// halts trading fail-closed once they repeat: a panic can leave shared
+/// state half-updated, so a burst of them means the process is not safe to trade.
+pub struct PanicWatchdog {
+    halt: Arc<GlobalHalt>,
+    max_panics: usize,
+    window_ms: i64,
+    /// Times (ms) of the panics still inside the window
+    recent: Mutex<VecDeque<i64>>,
+    total: AtomicU64,
+    /// Flattens once the halt trips, when set
+    flattener: Option<Arc<Flattener>>,
+}
+
+impl PanicWatchdog {
+    pub fn new(halt: Arc<GlobalHalt>, config: &PanicWatchdogConfig) -> Self {
+        Self {
+            halt,
+            max_panics: config.max_panics.max(1),
+            window_ms: config.window_ms,
+            recent: Mutex::new(VecDeque::new()),
+            total: AtomicU64::new(0),
+            flattener: None,
+        }
+    }
+
+    /// Flatten positions when repeated panics trip the halt
+    pub fn with_flattener(mut self, flattener: Arc<Flattener>) -> Self {
+        self.flattener = Some(flattener);
+        self
+    }
+
+    /// Run `fut` to completion, returning the panic message if it panicked.
+    pub async fn guard<F: Future>(&self, fut: F) -> Result<F::Output, String> {
+        AssertUnwindSafe(fut)
+            .catch_unwind()
+            .await
+            .map_err(|payload| panic_message(payload.as_ref()))
+    }
+
+    /// Count a caught panic. Returns true if it tripped the global halt.
+    pub fn record_panic(&self, now_ms: i64, reason: &str) -> bool {
+        metrics::inc_pipeline_panics();
+        self.total.fetch_add(1, Ordering::Relaxed);
+
+        let mut recent = self.recent.lock();
+        recent.push_back(now_ms);
+        while recent.front().is_some_and(|t| now_ms - *t > self.window_ms) {
+            recent.pop_front();
+        }
+        if recent.len() < self.max_panics {
+            return false;
+        }
+        error!(
+            "🚨 PANIC → HALT: {} pipeline panics within {}ms (last: {})",
+            recent.len(),
+            self.window_ms,
+            reason
+        );
+        self.halt.set_halt(
+            true,
+            &format!(
+                "{} pipeline panics within {}ms",
+                recent.len(),
+                self.window_ms
+            ),
+        );
+        true
+    }
+
+    /// Flatten after the halt has tripped, if a flattener is set. The report
+    /// is persisted and published like any other flatten.
+    pub async fn flatten(&self, reason: &str) -> Option<ShutdownReport> {
+        let flattener = self.flattener.as_ref()?;
+        Some(flattener.flatten(&format!("panic halt: {}", reason)).await)
+    }
+
+    /// Panics caught since start.
+    pub fn panics_total(&self) -> u64 {
+        self.total.load(Ordering::Relaxed)
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::ExecutionContext;
+    use crate::exchange::router::ExecutionRouter;
+    use crate::model::Intent;
+    use crate::persistence::redb_store::RedbStore;
+    use crate::persistence::store::PersistenceStore;
+    use crate::persistence::wal::WalManager;
+    use crate::shadow_state::ShadowState;
+    use crate::test_support::MockAdapter;
+    use parking_lot::RwLock;
+    use rust_decimal_macros::dec;
+
+    #[tokio::test]
+    async fn test_repeated_panics_trip_halt_within_window() {
+        let halt_path = std::env::temp_dir().join(format!("titan_halt_{}", uuid::Uuid::new_v4()));
+        let halt = Arc::new(GlobalHalt::with_file(&halt_path));
+        let watchdog = PanicWatchdog::new(
+            halt.clone(),
+            &PanicWatchdogConfig {
+                max_panics: 2,
+                window_ms: 1_000,
+            },
+        );
+
+        assert_eq!(watchdog.guard(async { 7 }).await, Ok(7));
+        let caught = watchdog.guard(async { panic!("boom in step {}", 3) }).await;
+        assert_eq!(caught, Err::<(), _>("boom in step 3".to_string()));
+
+        // Two panics, but further apart than the window
+        assert!(!watchdog.record_panic(0, "first"));
+        assert!(!watchdog.record_panic(5_000, "second"));
+        assert!(!halt.is_halted());
+
+        assert!(watchdog.record_panic(5_500, "third"));
+        assert!(halt.is_halted());
+        assert_eq!(watchdog.panics_total(), 3);
+
+        std::fs::remove_file(halt_path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_tripped_halt_flattens_and_reports() {
+        let path = format!("/tmp/test_panic_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let persistence = Arc::new(PersistenceStore::new(redb, wal));
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let mut state = ShadowState::new(persistence.clone(), ctx.clone(), Some(10_000.0));
+        let intent: Intent = serde_json::from_value(serde_json::json!({
+            "signal_id": "sig-open",
+            "symbol": "BTC/USDT",
+            "direction": 1,
+            "type": "BUY_SETUP",
+            "entry_zone": [50000.0],
+            "size": 0.1,
+            "status": "PENDING",
+            "t_signal": ctx.time.now_millis(),
+        }))
+        .unwrap();
+        state.process_intent(intent);
+        state.confirm_execution(
+            "sig-open",
+            "child-open",
+            dec!(50000),
+            dec!(0.1),
+            true,
+            dec!(0),
+            "USDT".to_string(),
+            "binance",
+        );
+        let state = Arc::new(RwLock::new(state));
+        let adapter = Arc::new(MockAdapter::new("binance"));
+        let router = Arc::new(ExecutionRouter::new());
+        router.register("binance", adapter.clone());
+        let flattener = Arc::new(Flattener::new(
+            router,
+            state.clone(),
+            persistence.clone(),
+            ctx,
+        ));
+
+        let halt_path = std::env::temp_dir().join(format!("titan_halt_{}", uuid::Uuid::new_v4()));
+        let halt = Arc::new(GlobalHalt::with_file(&halt_path));
+        let watchdog = PanicWatchdog::new(
+            halt.clone(),
+            &PanicWatchdogConfig {
+                max_panics: 1,
+                window_ms: 1_000,
+            },
+        )
+        .with_flattener(flattener);
+
+        assert!(watchdog.record_panic(0, "boom"));
+        let report = watchdog.flatten("boom").await.expect("flattened");
+        assert_eq!(report.reason, "panic halt: boom");
+        assert_eq!(report.positions_closed.len(), 1);
+        assert!(!state.read().has_position("BTC/USDT"));
+        assert_eq!(adapter.placed().len(), 1);
+        assert!(adapter.placed()[0].reduce_only);
+        assert_eq!(
+            ShutdownReport::load_last(&persistence).unwrap(),
+            Some(report)
+        );
+
+        std::fs::remove_file(halt_path).unwrap_or(());
+        std::fs::remove_file(path).unwrap_or(());
+    }
+}
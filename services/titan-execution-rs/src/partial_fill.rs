@@ -0,0 +1,348 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::RwLock;
+use rust_decimal::Decimal;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::exchange::router::ExecutionRouter;
+use crate::shadow_state::ShadowState;
+
+/// Cancels what is left of partially filled intents once their partial-fill
+/// budget runs out, and closes them as PartiallyCompleted with the fills kept.
+pub struct PartialFillCanceller {
+    shadow_state: Arc<RwLock<ShadowState>>,
+    router: Arc<ExecutionRouter>,
+    sweep_interval_ms: u64,
+}
+
+impl PartialFillCanceller {
+    pub fn new(
+        shadow_state: Arc<RwLock<ShadowState>>,
+        router: Arc<ExecutionRouter>,
+        sweep_interval_ms: u64,
+    ) -> Self {
+        Self {
+            shadow_state,
+            router,
+            sweep_interval_ms,
+        }
+    }
+
+    pub fn start(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_millis(self.sweep_interval_ms));
+            loop {
+                interval.tick().await;
+                self.sweep().await;
+            }
+        })
+    }
+
+    /// Cancel and close every expired partial fill. Returns the intents closed.
+    pub async fn sweep(&self) -> usize {
+        let expired = self.shadow_state.read().expired_partial_fills();
+        let mut closed = 0;
+        for (signal_id, symbol) in expired {
+            // A remainder that may still be working keeps the intent open;
+            // the next sweep tries the cancel again
+            let Some(cancelled) = self.cancel_remainder(&signal_id, &symbol).await else {
+                continue;
+            };
+            let mut state = self.shadow_state.write();
+            // A fill reported by the cancel may have closed the intent already
+            if !state.has_pending_intent(&signal_id)
+                || state
+                    .complete_partial_intent(&signal_id, "Partial fill budget elapsed".to_string())
+                    .is_some()
+            {
+                info!(
+                    "⏱️ Partial fill budget for {} elapsed: {} orders cancelled",
+                    signal_id, cancelled
+                );
+                closed += 1;
+            }
+        }
+        closed
+    }
+
+    /// Cancel the intent's child orders that can still fill and book what
+    /// they executed before the cancel landed. Returns the cancel count, or
+    /// None if any cancel failed.
+    async fn cancel_remainder(&self, signal_id: &str, symbol: &str) -> Option<usize> {
+        let children = self
+            .shadow_state
+            .read()
+            .get_child_orders(signal_id)
+            .cloned()
+            .unwrap_or_default();
+
+        let mut cancelled = 0;
+        let mut failed = false;
+        for child in children.iter().filter(|c| !c.status.is_terminal()) {
+            let Some(adapter) = self.router.get_adapter(&child.exchange) else {
+                continue;
+            };
+            let response = match adapter
+                .cancel_order(&symbol.replace("/", ""), &child.execution_order_id)
+                .await
+            {
+                Ok(response) => response,
+                Err(e) => {
+                    error!(
+                        "❌ Failed to cancel remainder {} on {}: {}",
+                        child.execution_order_id, child.exchange, e
+                    );
+                    failed = true;
+                    continue;
+                }
+            };
+            cancelled += 1;
+
+            let late_fill = response.executed_qty.min(child.size) - child.filled;
+            if late_fill <= Decimal::ZERO {
+                continue;
+            }
+            let mut state = self.shadow_state.write();
+            let Some(price) = response
+                .avg_price
+                .or_else(|| state.get_position(symbol).map(|p| p.entry_price))
+            else {
+                warn!(
+                    "Remainder {} filled {} before its cancel, no price to book it at",
+                    child.execution_order_id, late_fill
+                );
+                continue;
+            };
+            info!(
+                "✂️ Remainder {} filled {} before its cancel",
+                child.execution_order_id, late_fill
+            );
+            // Its own fill key: the order's earlier fill was applied under its id
+            state.confirm_execution(
+                signal_id,
+                &format!("{}:cancel", child.execution_order_id),
+                price,
+                late_fill,
+                true,
+                response.fee.unwrap_or(Decimal::ZERO),
+                response.fee_asset.clone().unwrap_or("USDT".to_string()),
+                &child.exchange,
+            );
+        }
+        (!failed).then_some(cancelled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::{DeterministicIdProvider, ExecutionContext, SimulatedTimeProvider};
+    use crate::model::{Intent, IntentStatus, IntentType};
+    use crate::persistence::redb_store::RedbStore;
+    use crate::persistence::store::PersistenceStore;
+    use crate::persistence::wal::WalManager;
+    use crate::test_support::MockAdapter;
+    use rust_decimal_macros::dec;
+
+    const NOW_MS: i64 = 1_700_000_000_000;
+
+    fn intent(id: &str, metadata: Option<serde_json::Value>) -> Intent {
+        Intent {
+            signal_id: id.to_string(),
+            source: None,
+            symbol: "BTC/USDT".to_string(),
+            direction: 1,
+            intent_type: IntentType::BuySetup,
+            entry_zone: vec![dec!(50000)],
+            stop_loss: dec!(49000),
+            take_profits: vec![],
+            size: dec!(1.0),
+            status: IntentStatus::Pending,
+            t_signal: NOW_MS,
+            t_analysis: None,
+            t_decision: None,
+            t_ingress: None,
+            t_exchange: None,
+            ttl_ms: None,
+            partition_key: None,
+            causation_id: None,
+            env: None,
+            subject: None,
+            max_slippage_bps: None,
+            rejection_reason: None,
+            regime_state: None,
+            phase: None,
+            metadata,
+            exchange: None,
+            position_mode: None,
+            child_fills: vec![],
+            filled_size: Decimal::ZERO,
+            policy_hash: None,
+        }
+    }
+
+    struct Fixture {
+        canceller: PartialFillCanceller,
+        shadow_state: Arc<RwLock<ShadowState>>,
+        persistence: Arc<PersistenceStore>,
+        time: Arc<SimulatedTimeProvider>,
+        path: String,
+    }
+
+    fn setup(adapter: Arc<MockAdapter>) -> Fixture {
+        let path = format!("/tmp/test_partial_fill_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let persistence = Arc::new(PersistenceStore::new(redb, wal));
+        let time = Arc::new(SimulatedTimeProvider::new(NOW_MS));
+        let ctx = Arc::new(ExecutionContext::from_providers(
+            time.clone(),
+            Arc::new(DeterministicIdProvider::new()),
+        ));
+        let shadow_state = Arc::new(RwLock::new(ShadowState::new(
+            persistence.clone(),
+            ctx,
+            Some(100_000.0),
+        )));
+        let router = Arc::new(ExecutionRouter::new());
+        router.register("binance", adapter);
+        Fixture {
+            canceller: PartialFillCanceller::new(shadow_state.clone(), router, 1_000),
+            shadow_state,
+            persistence,
+            time,
+            path,
+        }
+    }
+
+    /// Open `id` for 1.0 with one child order, 0.4 of it filled
+    fn partially_fill(state: &mut ShadowState, id: &str, metadata: Option<serde_json::Value>) {
+        state.process_intent(intent(id, metadata));
+        state.record_child_order(
+            id,
+            "binance".to_string(),
+            format!("{}-client", id),
+            format!("{}-order", id),
+            dec!(1.0),
+        );
+        state.confirm_execution(
+            id,
+            &format!("{}-order", id),
+            dec!(50000),
+            dec!(0.4),
+            true,
+            Decimal::ZERO,
+            "USDT".to_string(),
+            "binance",
+        );
+    }
+
+    fn stored_intent(persistence: &PersistenceStore, id: &str) -> Intent {
+        persistence
+            .load_intents()
+            .unwrap()
+            .into_iter()
+            .find(|i| i.signal_id == id)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_expired_partial_fill_cancels_remainder_and_completes() {
+        let adapter = Arc::new(MockAdapter::new("binance"));
+        let Fixture {
+            canceller,
+            shadow_state,
+            persistence,
+            time,
+            path,
+        } = setup(adapter.clone());
+
+        {
+            let mut state = shadow_state.write();
+            // One intent on the default budget, one with a longer budget of its own
+            partially_fill(&mut state, "sig-default", None);
+            partially_fill(
+                &mut state,
+                "sig-patient",
+                Some(serde_json::json!({ "partial_fill_timeout_ms": 20_000 })),
+            );
+        }
+
+        // Within budget: nothing to do
+        assert_eq!(canceller.sweep().await, 0);
+        assert!(adapter.cancelled.lock().is_empty());
+
+        time.advance(6_000);
+        assert_eq!(canceller.sweep().await, 1);
+        assert_eq!(
+            *adapter.cancelled.lock(),
+            vec![("BTCUSDT".to_string(), "sig-default-order".to_string())]
+        );
+        let stored = stored_intent(&persistence, "sig-default");
+        assert_eq!(stored.status, IntentStatus::PartiallyCompleted);
+        assert_eq!(stored.filled_size, dec!(0.4));
+        assert!(shadow_state
+            .read()
+            .get_cash_reservation("sig-default")
+            .is_none());
+
+        // The per-intent budget is honoured
+        time.advance(15_000);
+        assert_eq!(canceller.sweep().await, 1);
+        assert_eq!(adapter.cancelled.lock().len(), 2);
+        assert_eq!(canceller.sweep().await, 0);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+    #[tokio::test]
+    async fn test_failed_cancel_keeps_intent_working() {
+        let Fixture {
+            canceller,
+            shadow_state,
+            persistence,
+            time,
+            path,
+        } = setup(Arc::new(MockAdapter::new("binance").failing_cancels()));
+        partially_fill(&mut shadow_state.write(), "sig-1", None);
+
+        time.advance(6_000);
+        assert_eq!(canceller.sweep().await, 0);
+        assert_eq!(
+            stored_intent(&persistence, "sig-1").status,
+            IntentStatus::PartiallyFilled
+        );
+        assert_eq!(shadow_state.read().expired_partial_fills().len(), 1);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_fill_reported_by_cancel_is_booked() {
+        let Fixture {
+            canceller,
+            shadow_state,
+            persistence,
+            time,
+            path,
+        } = setup(Arc::new(
+            MockAdapter::new("binance").with_cancel_fills(dec!(0.7)),
+        ));
+        partially_fill(&mut shadow_state.write(), "sig-1", None);
+
+        time.advance(6_000);
+        assert_eq!(canceller.sweep().await, 1);
+        let stored = stored_intent(&persistence, "sig-1");
+        assert_eq!(stored.status, IntentStatus::PartiallyCompleted);
+        assert_eq!(stored.filled_size, dec!(0.7));
+        let pos = shadow_state
+            .read()
+            .get_position("BTC/USDT")
+            .cloned()
+            .unwrap();
+        assert_eq!(pos.size, dec!(0.7));
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+}
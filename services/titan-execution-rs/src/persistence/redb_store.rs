@@ -46,11 +46,15 @@ impl RedbStore {
         Ok(self.db.begin_read()?)
     }
 
-    pub fn check_idempotency(&self, key: &str, _ttl_ms: i64) -> Result<bool, StoreError> {
+    /// Whether `key` is new as of `now` (ms): never marked, or marked and expired.
+    pub fn check_idempotency(&self, key: &str, now: i64) -> Result<bool, StoreError> {
         let read_txn = self.db.begin_read()?;
-        let table = read_txn.open_table(IDEMPOTENCY_TABLE)?;
-
-        let now = chrono::Utc::now().timestamp_millis();
+        let table = match read_txn.open_table(IDEMPOTENCY_TABLE) {
+            Ok(table) => table,
+            // Nothing was ever marked on a fresh database
+            Err(redb::TableError::TableDoesNotExist(_)) => return Ok(true),
+            Err(e) => return Err(e.into()),
+        };
 
         if let Some(expiry) = table.get(key)? {
             let expiry_ts = expiry.value();
@@ -68,11 +72,11 @@ impl RedbStore {
         Ok(true)
     }
 
-    pub fn set_idempotency(&self, key: &str, ttl_ms: i64) -> Result<(), StoreError> {
+    /// Mark `key` as seen at `now` (ms) for `ttl_ms`.
+    pub fn set_idempotency(&self, key: &str, ttl_ms: i64, now: i64) -> Result<(), StoreError> {
         let write_txn = self.db.begin_write()?;
         {
             let mut table = write_txn.open_table(IDEMPOTENCY_TABLE)?;
-            let now = chrono::Utc::now().timestamp_millis();
             let expiry = now + ttl_ms;
             table.insert(key, expiry)?;
         }
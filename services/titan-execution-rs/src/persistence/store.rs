@@ -1,8 +1,9 @@
-use crate::model::{Intent, Position, TradeRecord};
+use crate::model::{FundingRecord, Intent, Position, TradeRecord};
 use crate::order_fsm::OrderFsm;
 use crate::persistence::redb_store::{RedbStore, StoreError};
 use crate::persistence::wal::{WalEntry, WalManager};
 use redb::{ReadableTable, TableDefinition};
+use rust_decimal::Decimal;
 use std::sync::Arc;
 
 // Tables
@@ -11,12 +12,38 @@ const INTENTS_TABLE: TableDefinition<&str, Vec<u8>> = TableDefinition::new("inte
 const TRADES_TABLE: TableDefinition<&str, Vec<u8>> = TableDefinition::new("trades");
 const METADATA_TABLE: TableDefinition<&str, Vec<u8>> = TableDefinition::new("metadata");
 const FSM_TABLE: TableDefinition<&str, Vec<u8>> = TableDefinition::new("order_fsm");
+const EVENT_LOG_TABLE: TableDefinition<u64, Vec<u8>> = TableDefinition::new("event_log");
+const PROCESSED_FILLS_TABLE: TableDefinition<&str, i64> = TableDefinition::new("processed_fills");
+const INTENT_TRACE_TABLE: TableDefinition<&str, Vec<u8>> = TableDefinition::new("intent_trace");
+/// "exchange:symbol" -> funding time (ms) of the last payment applied
+const FUNDING_APPLIED_TABLE: TableDefinition<&str, i64> = TableDefinition::new("funding_applied");
+/// "exchange:symbol:funding time" -> applied funding payment
+const FUNDING_HISTORY_TABLE: TableDefinition<&str, Vec<u8>> =
+    TableDefinition::new("funding_history");
+
+/// Everything one applied fill changed, written together with its
+/// processed-fill marker. `None` for the intent or position means it is gone.
+pub struct FillCommit<'a> {
+    pub fill_id: &'a str,
+    pub signal_id: &'a str,
+    /// Intent after the fill; kept once terminal so replays can be refused
+    pub intent: &'a Intent,
+    pub symbol: &'a str,
+    pub position: Option<&'a Position>,
+    pub trades: &'a [TradeRecord],
+    pub cash_balance: Decimal,
+    pub ts: i64,
+}
 
 pub struct PersistenceStore {
     store: Arc<RedbStore>,
     wal: Arc<WalManager>,
 }
 
+fn funding_key(exchange: &str, symbol: &str) -> String {
+    format!("{}:{}", exchange.to_lowercase(), symbol)
+}
+
 impl PersistenceStore {
     pub fn new(store: Arc<RedbStore>, wal: Arc<WalManager>) -> Self {
         Self { store, wal }
@@ -46,6 +73,20 @@ impl PersistenceStore {
         Ok(items)
     }
 
+    pub fn load_intent(&self, signal_id: &str) -> Result<Option<Intent>, StoreError> {
+        let txn = self.store.begin_read()?;
+        let table = match txn.open_table(INTENTS_TABLE) {
+            Ok(table) => table,
+            Err(redb::TableError::TableDoesNotExist(_)) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let intent = table
+            .get(signal_id)?
+            .map(|v| serde_json::from_slice(&v.value()))
+            .transpose()?;
+        Ok(intent)
+    }
+
     pub fn load_trades(&self) -> Result<Vec<TradeRecord>, StoreError> {
         let txn = self.store.begin_read()?;
         let table = txn.open_table(TRADES_TABLE)?;
@@ -86,6 +127,90 @@ impl PersistenceStore {
         Ok(items)
     }
 
+    /// Delete persisted trades beyond the newest `max_count` or closed before
+    /// `closed_before` (ms). Returns how many were removed.
+    pub fn prune_trades(
+        &self,
+        max_count: Option<usize>,
+        closed_before: Option<i64>,
+    ) -> Result<usize, StoreError> {
+        let mut trades: Vec<(String, i64)> = {
+            let txn = self.store.begin_read()?;
+            let table = match txn.open_table(TRADES_TABLE) {
+                Ok(table) => table,
+                Err(redb::TableError::TableDoesNotExist(_)) => return Ok(0),
+                Err(e) => return Err(e.into()),
+            };
+            let mut trades = Vec::new();
+            for res in table.range::<&str>(..)? {
+                let (k, v) = res?;
+                let trade: TradeRecord = serde_json::from_slice(&v.value())?;
+                trades.push((k.value().to_string(), trade.closed_at.timestamp_millis()));
+            }
+            trades
+        };
+
+        // Newest first, so everything past max_count is the oldest
+        trades.sort_by(|a, b| b.1.cmp(&a.1));
+        let expired: Vec<&str> = trades
+            .iter()
+            .enumerate()
+            .filter(|(i, (_, closed_at))| {
+                max_count.is_some_and(|max| *i >= max)
+                    || closed_before.is_some_and(|cutoff| *closed_at < cutoff)
+            })
+            .map(|(_, (key, _))| key.as_str())
+            .collect();
+        if expired.is_empty() {
+            return Ok(0);
+        }
+
+        let txn = self.store.begin_write()?;
+        {
+            let mut table = txn.open_table(TRADES_TABLE)?;
+            for key in &expired {
+                table.remove(*key)?;
+            }
+        }
+        txn.commit()?;
+        Ok(expired.len())
+    }
+
+    /// Delete intents in a terminal state signalled before `signalled_before`
+    /// (ms). Returns how many were removed.
+    pub fn prune_terminal_intents(&self, signalled_before: i64) -> Result<usize, StoreError> {
+        let expired: Vec<String> = {
+            let txn = self.store.begin_read()?;
+            let table = match txn.open_table(INTENTS_TABLE) {
+                Ok(table) => table,
+                Err(redb::TableError::TableDoesNotExist(_)) => return Ok(0),
+                Err(e) => return Err(e.into()),
+            };
+            let mut expired = Vec::new();
+            for res in table.range::<&str>(..)? {
+                let (k, v) = res?;
+                let intent: Intent = serde_json::from_slice(&v.value())?;
+                if !intent.status.is_active() && intent.t_signal < signalled_before {
+                    expired.push(k.value().to_string());
+                }
+            }
+            expired
+        };
+        if expired.is_empty() {
+            return Ok(0);
+        }
+
+        let txn = self.store.begin_write()?;
+        {
+            let mut table = txn.open_table(INTENTS_TABLE)?;
+            for key in &expired {
+                table.remove(key.as_str())?;
+            }
+        }
+        txn.commit()?;
+        Ok(expired.len())
+    }
+
     pub fn save_intent(&self, intent: &Intent) -> Result<(), StoreError> {
         // WAL first
         self.wal
@@ -167,6 +292,17 @@ impl PersistenceStore {
         Ok(())
     }
 
+    /// `OrderPlaced` entries from the WAL, oldest first.
+    pub fn load_placed_orders(&self) -> Result<Vec<WalEntry>, StoreError> {
+        Ok(self
+            .wal
+            .read_from(0)?
+            .into_iter()
+            .map(|(_, entry)| entry)
+            .filter(|entry| matches!(entry, WalEntry::OrderPlaced { .. }))
+            .collect())
+    }
+
     pub fn save_metadata(&self, key: &str, value: serde_json::Value) -> Result<(), StoreError> {
         let txn = self.store.begin_write()?;
         {
@@ -191,12 +327,12 @@ impl PersistenceStore {
         Ok(result_json)
     }
 
-    pub fn check_idempotency(&self, key: &str, ttl_ms: i64) -> Result<bool, StoreError> {
-        self.store.check_idempotency(key, ttl_ms)
+    pub fn check_idempotency(&self, key: &str, now: i64) -> Result<bool, StoreError> {
+        self.store.check_idempotency(key, now)
     }
 
-    pub fn set_idempotency(&self, key: &str, ttl_ms: i64) -> Result<(), StoreError> {
-        self.store.set_idempotency(key, ttl_ms)
+    pub fn set_idempotency(&self, key: &str, ttl_ms: i64, now: i64) -> Result<(), StoreError> {
+        self.store.set_idempotency(key, ttl_ms, now)
     }
 
     /// Persist an OrderFsm to Redb for crash recovery (Phase 3.3)
@@ -256,4 +392,222 @@ impl PersistenceStore {
         txn.commit()?;
         Ok(())
     }
+
+    /// Whether a fill id has already been applied to state
+    pub fn is_fill_processed(&self, fill_id: &str) -> Result<bool, StoreError> {
+        let txn = self.store.begin_read()?;
+        let table = match txn.open_table(PROCESSED_FILLS_TABLE) {
+            Ok(table) => table,
+            Err(redb::TableError::TableDoesNotExist(_)) => return Ok(false),
+            Err(e) => return Err(e.into()),
+        };
+        let processed = table.get(fill_id)?.is_some();
+        Ok(processed)
+    }
+
+    /// Persist a fill's state changes and mark the fill processed in one
+    /// transaction, so a crash leaves either all of it or none of it.
+    pub fn commit_fill(&self, commit: &FillCommit<'_>) -> Result<(), StoreError> {
+        // WAL first
+        self.wal.append(&WalEntry::ExecutionReport {
+            signal_id: commit.signal_id.to_string(),
+            fill_id: commit.fill_id.to_string(),
+            payload: serde_json::json!({
+                "symbol": commit.symbol,
+                "position": commit.position,
+                "trades": commit.trades,
+                "cashBalance": commit.cash_balance.to_string(),
+            }),
+        })?;
+
+        let txn = self.store.begin_write()?;
+        {
+            let mut fills = txn.open_table(PROCESSED_FILLS_TABLE)?;
+            fills.insert(commit.fill_id, commit.ts)?;
+
+            let mut intents = txn.open_table(INTENTS_TABLE)?;
+            intents.insert(commit.signal_id, serde_json::to_vec(commit.intent)?)?;
+
+            let mut positions = txn.open_table(POSITIONS_TABLE)?;
+            match commit.position {
+                Some(position) => {
+                    positions.insert(commit.symbol, serde_json::to_vec(position)?)?;
+                }
+                None => {
+                    positions.remove(commit.symbol)?;
+                }
+            }
+
+            let mut trades = txn.open_table(TRADES_TABLE)?;
+            for trade in commit.trades {
+                trades.insert(trade.signal_id.as_str(), serde_json::to_vec(trade)?)?;
+            }
+
+            let mut metadata = txn.open_table(METADATA_TABLE)?;
+            let cash = serde_json::Value::String(commit.cash_balance.to_string());
+            metadata.insert("cash_balance", serde_json::to_vec(&cash)?)?;
+        }
+        txn.commit()?;
+        Ok(())
+    }
+
+    /// Funding time (ms) of the last payment applied for `symbol` on `exchange`
+    pub fn last_funding_applied(
+        &self,
+        exchange: &str,
+        symbol: &str,
+    ) -> Result<Option<i64>, StoreError> {
+        let txn = self.store.begin_read()?;
+        let table = match txn.open_table(FUNDING_APPLIED_TABLE) {
+            Ok(table) => table,
+            Err(redb::TableError::TableDoesNotExist(_)) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let last = table
+            .get(funding_key(exchange, symbol).as_str())?
+            .map(|v| v.value());
+        Ok(last)
+    }
+
+    /// Persist a funding payment's position and cash effects together with
+    /// its applied marker and history record, so a restart can neither lose
+    /// nor repeat it.
+    pub fn commit_funding(
+        &self,
+        record: &FundingRecord,
+        position: &Position,
+        cash_balance: Decimal,
+    ) -> Result<(), StoreError> {
+        let txn = self.store.begin_write()?;
+        {
+            let key = funding_key(&record.exchange, &position.symbol);
+            let mut funding = txn.open_table(FUNDING_APPLIED_TABLE)?;
+            funding.insert(key.as_str(), record.funding_time)?;
+
+            let mut history = txn.open_table(FUNDING_HISTORY_TABLE)?;
+            history.insert(
+                format!("{}:{}", key, record.funding_time).as_str(),
+                serde_json::to_vec(record)?,
+            )?;
+
+            let mut positions = txn.open_table(POSITIONS_TABLE)?;
+            positions.insert(position.symbol.as_str(), serde_json::to_vec(position)?)?;
+
+            let mut metadata = txn.open_table(METADATA_TABLE)?;
+            let cash = serde_json::Value::String(cash_balance.to_string());
+            metadata.insert("cash_balance", serde_json::to_vec(&cash)?)?;
+        }
+        txn.commit()?;
+        Ok(())
+    }
+
+    /// Append a sequenced CDC record; `seq` is the key, so replay is an ordered range scan
+    /// Applied funding payments, oldest first, optionally for one symbol and
+    /// from `since` (ms, inclusive).
+    pub fn load_funding_history(
+        &self,
+        symbol: Option<&str>,
+        since: Option<i64>,
+    ) -> Result<Vec<FundingRecord>, StoreError> {
+        let txn = self.store.begin_read()?;
+        let table = match txn.open_table(FUNDING_HISTORY_TABLE) {
+            Ok(table) => table,
+            Err(redb::TableError::TableDoesNotExist(_)) => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut items = Vec::new();
+        for res in table.range::<&str>(..)? {
+            let (_, v) = res?;
+            let item: FundingRecord = serde_json::from_slice(&v.value())?;
+            if symbol.is_some_and(|s| s != item.symbol)
+                || since.is_some_and(|t| item.funding_time < t)
+            {
+                continue;
+            }
+            items.push(item);
+        }
+        items.sort_by_key(|r| r.funding_time);
+        Ok(items)
+    }
+
+    pub fn append_event(&self, seq: u64, data: &[u8]) -> Result<(), StoreError> {
+        let txn = self.store.begin_write()?;
+        {
+            let mut table = txn.open_table(EVENT_LOG_TABLE)?;
+            table.insert(seq, data.to_vec())?;
+        }
+        txn.commit()?;
+        Ok(())
+    }
+
+    /// Append one JSON stage to the trace of `correlation_id`, in a single write
+    pub fn append_trace_stage(
+        &self,
+        correlation_id: &str,
+        stage: serde_json::Value,
+    ) -> Result<(), StoreError> {
+        let txn = self.store.begin_write()?;
+        {
+            let mut table = txn.open_table(INTENT_TRACE_TABLE)?;
+            let mut stages: Vec<serde_json::Value> = match table.get(correlation_id)? {
+                Some(v) => serde_json::from_slice(&v.value())?,
+                None => Vec::new(),
+            };
+            stages.push(stage);
+            let data = serde_json::to_vec(&stages)?;
+            table.insert(correlation_id, data)?;
+        }
+        txn.commit()?;
+        Ok(())
+    }
+
+    /// Recorded stages of `correlation_id`, oldest first (None if never traced)
+    pub fn load_trace(
+        &self,
+        correlation_id: &str,
+    ) -> Result<Option<Vec<serde_json::Value>>, StoreError> {
+        let txn = self.store.begin_read()?;
+        let table = match txn.open_table(INTENT_TRACE_TABLE) {
+            Ok(table) => table,
+            Err(redb::TableError::TableDoesNotExist(_)) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let stages = table
+            .get(correlation_id)?
+            .map(|v| serde_json::from_slice(&v.value()))
+            .transpose()?;
+        Ok(stages)
+    }
+
+    /// Highest sequence number in the event log (None when empty)
+    pub fn last_event_seq(&self) -> Result<Option<u64>, StoreError> {
+        let txn = self.store.begin_read()?;
+        let table = match txn.open_table(EVENT_LOG_TABLE) {
+            Ok(table) => table,
+            Err(redb::TableError::TableDoesNotExist(_)) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let last = table.last()?.map(|(k, _)| k.value());
+        Ok(last)
+    }
+
+    /// Raw records with sequence >= `from_seq`, oldest first, at most `limit`
+    pub fn load_events_from(
+        &self,
+        from_seq: u64,
+        limit: usize,
+    ) -> Result<Vec<(u64, Vec<u8>)>, StoreError> {
+        let txn = self.store.begin_read()?;
+        let table = match txn.open_table(EVENT_LOG_TABLE) {
+            Ok(table) => table,
+            Err(redb::TableError::TableDoesNotExist(_)) => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut items = Vec::new();
+        for res in table.range(from_seq..)?.take(limit) {
+            let (k, v) = res?;
+            items.push((k.value(), v.value()));
+        }
+        Ok(items)
+    }
 }
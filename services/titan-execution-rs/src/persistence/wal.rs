@@ -68,7 +68,12 @@ impl WalManager {
 
     pub fn read_from(&self, start_seq: u64) -> Result<Vec<(u64, WalEntry)>, StoreError> {
         let txn = self.store.begin_read()?;
-        let table = txn.open_table(WAL_TABLE)?;
+        let table = match txn.open_table(WAL_TABLE) {
+            Ok(table) => table,
+            // Nothing was ever appended on a fresh database
+            Err(redb::TableError::TableDoesNotExist(_)) => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
 
         let mut entries = Vec::new();
         for result in table.range(start_seq..)? {
@@ -1,22 +1,35 @@
 use parking_lot::RwLock;
 use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tracing::{error, info, warn};
 
+use crate::client_order_id::{correlation_prefix, DEFAULT_MAX_LEN};
+use crate::config::{ConfirmationConfig, ConfirmationMode};
 use crate::context::ExecutionContext;
+use crate::dlq::DlqReasonCode;
 use crate::drift_detector::DriftDetector;
-use crate::exchange::adapter::OrderRequest;
+use crate::entry_zone::EntryZoneExecutor;
+use crate::event_log::{EventLog, TYPE_INTENT_TRANSITION};
+use crate::exchange::adapter::{
+    Bracket, ExchangeError, OrderRequest, OrderResponse, OrderStatus, SwapMode,
+};
+use crate::exchange::rejection::VenueRejection;
 use crate::exchange::router::ExecutionRouter;
+use crate::execution_report::ExecutionReport;
+use crate::intent_trace::{IntentTracer, TraceStageKind};
 use crate::metrics;
 use crate::model::TradeRecord;
-use crate::model::{FillReport, Intent, IntentType, Side};
+use crate::model::{FillReport, Intent, IntentType, OrderType, Side};
 use crate::order_fsm::{OrderFsm, OrderLifecycleState};
 use crate::order_manager::OrderManager;
+use crate::panic_watchdog::PanicWatchdog;
+use crate::repricer::LimitRepricer;
 use crate::risk_guard::RiskGuard;
 use crate::shadow_state::{ExecutionEvent, ShadowState};
 use crate::simulation_engine::SimulationEngine;
-use chrono::Utc;
+use crate::tp_ladder::TpLadderExecutor;
 
 /// usage:
 /// let pipeline = ExecutionPipeline::new(...deps...);
@@ -28,8 +41,16 @@ pub struct ExecutionPipeline {
     simulation_engine: Arc<SimulationEngine>,
     risk_guard: Arc<RiskGuard>,
     ctx: Arc<ExecutionContext>,
-    freshness_threshold: u64,
+    /// Max intent age (ms); shared so a config reload can change it
+    freshness_threshold: Arc<AtomicU64>,
     drift_detector: Arc<DriftDetector>,
+    tp_ladder: Option<Arc<TpLadderExecutor>>,
+    repricer: Option<Arc<LimitRepricer>>,
+    entry_zone: Option<Arc<EntryZoneExecutor>>,
+    tracer: Option<Arc<IntentTracer>>,
+    confirmation: ConfirmationConfig,
+    panic_watchdog: Option<Arc<PanicWatchdog>>,
+    event_log: Option<Arc<EventLog>>,
 }
 
 use crate::exposure::ExposureMetrics;
@@ -39,10 +60,37 @@ pub struct PipelineResult {
     pub events: Vec<ExecutionEvent>,
     pub exposure: Option<ExposureMetrics>,
     pub fill_reports: Vec<(String, FillReport)>, // Exchange -> Report
+    /// All child fills of this intent consolidated (None if nothing filled)
+    pub execution_report: Option<ExecutionReport>,
     pub fsm: Option<OrderFsm>,
     pub drift_detected: bool,
 }
 
+/// Why an intent was dropped, tagged with the DLQ code it is filed under.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipelineError {
+    pub code: DlqReasonCode,
+    pub reason: String,
+    /// Venue-native refusals behind an `AdapterError`
+    pub rejections: Vec<VenueRejection>,
+}
+
+impl PipelineError {
+    fn new(code: DlqReasonCode, reason: String) -> Self {
+        Self {
+            code,
+            reason,
+            rejections: Vec::new(),
+        }
+    }
+}
+
+impl std::fmt::Display for PipelineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.reason)
+    }
+}
+
 impl ExecutionPipeline {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -62,17 +110,187 @@ impl ExecutionPipeline {
             simulation_engine,
             risk_guard,
             ctx,
-            freshness_threshold,
+            freshness_threshold: Arc::new(AtomicU64::new(freshness_threshold)),
             drift_detector,
+            tp_ladder: None,
+            repricer: None,
+            entry_zone: None,
+            tracer: None,
+            confirmation: ConfirmationConfig::default(),
+            panic_watchdog: None,
+            event_log: None,
+        }
+    }
+
+    /// Read the freshness threshold from `threshold`, which the owner may update
+    /// while the pipeline runs.
+    pub fn with_freshness_threshold(mut self, threshold: Arc<AtomicU64>) -> Self {
+        self.freshness_threshold = threshold;
+        self
+    }
+
+    /// Maintain take-profit ladders from the position events this pipeline produces.
+    pub fn with_tp_ladder(mut self, tp_ladder: Arc<TpLadderExecutor>) -> Self {
+        self.tp_ladder = Some(tp_ladder);
+        self
+    }
+
+    /// Keep resting maker orders pegged to the touch while they wait for a fill.
+    pub fn with_repricer(mut self, repricer: Arc<LimitRepricer>) -> Self {
+        self.repricer = Some(repricer);
+        self
+    }
+
+    /// Scale limit entries across the intent's entry zone instead of its first price.
+    pub fn with_entry_zone(mut self, entry_zone: Arc<EntryZoneExecutor>) -> Self {
+        self.entry_zone = Some(entry_zone);
+        self
+    }
+
+    /// Record every intent's lifecycle stages to the persisted trace table.
+    pub fn with_tracer(mut self, tracer: Arc<IntentTracer>) -> Self {
+        self.tracer = Some(tracer);
+        self
+    }
+
+    /// Write every intent state transition to the CDC event log.
+    pub fn with_event_log(mut self, event_log: Arc<EventLog>) -> Self {
+        self.event_log = Some(event_log);
+        self
+    }
+
+    /// Catch panics in `process_intent_guarded`, halting once they repeat.
+    pub fn with_panic_watchdog(mut self, watchdog: Arc<PanicWatchdog>) -> Self {
+        self.panic_watchdog = Some(watchdog);
+        self
+    }
+
+    /// Choose per source (or per intent) whether placement waits for the fill.
+    pub fn with_confirmation(mut self, confirmation: ConfirmationConfig) -> Self {
+        self.confirmation = confirmation;
+        self
+    }
+
+    /// `metadata.confirmation_mode`, else the source's configured mode, else the default.
+    fn confirmation_mode(&self, intent: &Intent) -> ConfirmationMode {
+        intent
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get("confirmation_mode"))
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .or_else(|| {
+                intent
+                    .source
+                    .as_ref()
+                    .and_then(|source| self.confirmation.sources.get(source).copied())
+            })
+            .unwrap_or(self.confirmation.mode)
+    }
+
+    /// Poll the venue until the order is terminal or the confirmation timeout
+    /// passes. Returns the latest known state of the order.
+    async fn await_terminal(
+        &self,
+        exchange_name: &str,
+        request: &OrderRequest,
+        placed: OrderResponse,
+    ) -> OrderResponse {
+        let Some(adapter) = self.router.get_adapter(exchange_name) else {
+            return placed;
+        };
+        let poll_interval = std::time::Duration::from_millis(self.confirmation.poll_interval_ms);
+        let deadline = tokio::time::Instant::now()
+            + std::time::Duration::from_millis(self.confirmation.timeout_ms);
+        let mut latest = placed;
+        while !latest.status.is_terminal() && tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(poll_interval).await;
+            match adapter
+                .get_order(&request.symbol, &request.client_order_id)
+                .await
+            {
+                Ok(Some(order)) => latest = order,
+                Ok(None) => {}
+                Err(ExchangeError::NotImplemented(_)) => {
+                    warn!(
+                        "[{}] cannot query orders; {} left to the fill stream",
+                        exchange_name, latest.order_id
+                    );
+                    break;
+                }
+                Err(e) => warn!(
+                    "[{}] order status poll for {} failed: {}",
+                    exchange_name, latest.order_id, e
+                ),
+            }
+        }
+        latest
+    }
+
+    /// Log the transitions of an FSM that was just saved. Each intent's FSM
+    /// is saved once, when processing ends, so no transition is logged twice.
+    fn log_transitions(&self, fsm: &OrderFsm, correlation_id: &str) {
+        let Some(event_log) = &self.event_log else {
+            return;
+        };
+        for transition in &fsm.transitions {
+            let payload = serde_json::json!({
+                "signalId": fsm.signal_id,
+                "symbol": fsm.symbol,
+                "from": transition.from,
+                "to": transition.to,
+                "reason": transition.reason,
+                "timestampMs": transition.timestamp_ms,
+            });
+            if let Err(e) = event_log.append(
+                TYPE_INTENT_TRANSITION,
+                Some(correlation_id),
+                payload,
+                transition.timestamp_ms,
+            ) {
+                error!("Failed to log FSM transition for {}: {}", fsm.signal_id, e);
+            }
+        }
+    }
+
+    fn trace(&self, correlation_id: &str, stage: TraceStageKind, detail: Option<String>) {
+        if let Some(tracer) = &self.tracer {
+            tracer.record(correlation_id, stage, self.ctx.time.now_millis(), detail);
+        }
+    }
+
+    /// `process_intent`, with a panic anywhere in it filed as a failure (and
+    /// so dead-lettered) instead of unwinding through the consumer.
+    pub async fn process_intent_guarded(
+        &self,
+        intent: Intent,
+        correlation_id: String,
+    ) -> Result<PipelineResult, PipelineError> {
+        let Some(watchdog) = &self.panic_watchdog else {
+            return self.process_intent(intent, correlation_id).await;
+        };
+        let signal_id = intent.signal_id.clone();
+        match watchdog
+            .guard(self.process_intent(intent, correlation_id.clone()))
+            .await
+        {
+            Ok(result) => result,
+            Err(panic) => {
+                let reason = format!("Pipeline panicked on {}: {}", signal_id, panic);
+                error!(correlation_id = %correlation_id, "💥 {}", reason);
+                if watchdog.record_panic(self.ctx.time.now_millis(), &reason) {
+                    watchdog.flatten(&reason).await;
+                }
+                Err(PipelineError::new(DlqReasonCode::Panic, reason))
+            }
         }
     }
 
     /// Process a single Intent through the full execution lifecycle.
     pub async fn process_intent(
         &self,
-        intent: Intent,
+        mut intent: Intent,
         correlation_id: String,
-    ) -> Result<PipelineResult, String> {
+    ) -> Result<PipelineResult, PipelineError> {
         let now_ms = self.ctx.time.now_millis();
         let mut fsm = OrderFsm::new(intent.signal_id.clone(), intent.symbol.clone());
 
@@ -81,12 +299,19 @@ impl ExecutionPipeline {
             events: Vec::new(),
             exposure: None,
             fill_reports: Vec::new(),
+            execution_report: None,
             fsm: None,
             drift_detected: false,
         };
+        self.trace(&correlation_id, TraceStageKind::Received, None);
 
         // --- RISK GUARD CHECK ---
-        if let Err(reason) = self.risk_guard.check_pre_trade(&intent) {
+        // Risk-budget sizing runs first so the limits judge the size actually sent
+        let risk_check = self
+            .risk_guard
+            .apply_risk_sizing(&mut intent)
+            .and_then(|_| self.risk_guard.check_pre_trade(&intent));
+        if let Err(reason) = risk_check {
             let msg = format!("❌ RISK REJECTION: {}", reason);
             error!(correlation_id = %correlation_id, signal_id = %intent.signal_id, "{}", msg);
             metrics::inc_risk_rejections();
@@ -100,8 +325,11 @@ impl ExecutionPipeline {
                 let state = self.shadow_state.read();
                 state.save_fsm(&fsm);
             }
-            return Err(msg);
+            self.log_transitions(&fsm, &correlation_id);
+            self.trace(&correlation_id, TraceStageKind::Closed, Some(msg.clone()));
+            return Err(PipelineError::new(DlqReasonCode::RiskRejection, msg));
         }
+        self.trace(&correlation_id, TraceStageKind::RiskChecked, None);
 
         // FSM: Validated (passed risk guard)
         if let Err(e) = fsm.transition(OrderLifecycleState::Validated, now_ms, None) {
@@ -114,9 +342,25 @@ impl ExecutionPipeline {
             state.process_intent(intent.clone())
         };
 
+        // Shadow state refused it (duplicate or already-terminal replay): nothing to execute
+        if !processed_intent.status.is_active() {
+            let msg = format!(
+                "Intent not executable ({:?}): {}",
+                processed_intent.status,
+                processed_intent
+                    .rejection_reason
+                    .as_deref()
+                    .unwrap_or("already processed")
+            );
+            warn!(correlation_id = %correlation_id, signal_id = %processed_intent.signal_id, "{}", msg);
+            self.trace(&correlation_id, TraceStageKind::Closed, Some(msg));
+            return Ok(pipeline_result);
+        }
+
         // Enforce Timestamp Freshness
         let now = self.ctx.time.now_millis();
-        if now - processed_intent.t_signal > self.freshness_threshold as i64 {
+        if now - processed_intent.t_signal > self.freshness_threshold.load(Ordering::Relaxed) as i64
+        {
             let msg = format!(
                 "Intent EXPIRED: {} ms latency",
                 now - processed_intent.t_signal
@@ -143,8 +387,11 @@ impl ExecutionPipeline {
                 let state = self.shadow_state.read();
                 state.save_fsm(&fsm);
             }
-            return Err(msg);
+            self.log_transitions(&fsm, &correlation_id);
+            self.trace(&correlation_id, TraceStageKind::Closed, Some(msg.clone()));
+            return Err(PipelineError::new(DlqReasonCode::Timeout, msg));
         }
+        self.trace(&correlation_id, TraceStageKind::Validated, None);
 
         // FSM: Accepted (passed freshness, ready for execution)
         if let Err(e) = fsm.transition(OrderLifecycleState::Accepted, now_ms, None) {
@@ -152,11 +399,52 @@ impl ExecutionPipeline {
         }
 
         // --- SHADOW EXECUTION (Concurrent side-effect) ---
-        pipeline_result.shadow_fill = self.simulation_engine.simulate_execution(&processed_intent);
+        pipeline_result.shadow_fill = self
+            .simulation_engine
+            .simulate_execution(&processed_intent)
+            .map(|mut fill| {
+                fill.correlation_id = Some(correlation_id.clone());
+                fill
+            });
+
+        // Canary strategies: shadow fill only, the intent never reaches a venue
+        if simulate_only(&processed_intent) {
+            info!(
+                correlation_id = %correlation_id,
+                signal_id = %processed_intent.signal_id,
+                "👻 Simulation only: skipping venue routing"
+            );
+            metrics::inc_simulated_intents();
+            {
+                let mut state = self.shadow_state.write();
+                state.close_simulated_intent(&processed_intent.signal_id);
+                state.save_fsm(&fsm);
+            }
+            self.log_transitions(&fsm, &correlation_id);
+            pipeline_result.fsm = Some(fsm);
+            self.trace(
+                &correlation_id,
+                TraceStageKind::Closed,
+                Some("simulation only".to_string()),
+            );
+            return Ok(pipeline_result);
+        }
+
+        // Hold the order's margin back from available cash until it fills or fails
+        {
+            let reservation = self.risk_guard.cash_reservation(&processed_intent);
+            let mut state = self.shadow_state.write();
+            state.reserve_cash(
+                &processed_intent.signal_id,
+                reservation,
+                processed_intent.size,
+            );
+        }
 
         let side = self.infer_side(&processed_intent);
 
         // Order Manager Decision
+        let expected_profit_pct = expected_profit_pct_of(&processed_intent);
         let decision = {
             let order_params = crate::model::OrderParams {
                 signal_id: processed_intent.signal_id.clone(),
@@ -173,12 +461,124 @@ impl ExecutionPipeline {
                 stop_loss: Some(processed_intent.stop_loss),
                 take_profits: Some(processed_intent.take_profits.clone()),
                 signal_type: Some(format!("{:?}", processed_intent.intent_type)),
-                expected_profit_pct: None,
+                expected_profit_pct,
+                ttl_ms: processed_intent.ttl_ms,
+                remaining_ttl_ms: processed_intent
+                    .ttl_ms
+                    .map(|ttl| ttl - (self.ctx.time.now_millis() - processed_intent.t_signal)),
             };
             self.order_manager.decide_order_type(&order_params)
         };
         let t_decision = self.ctx.time.now_millis();
 
+        // Halted, or opens frozen for maintenance: the order manager refused it
+        if matches!(
+            decision.reason.as_str(),
+            "SYSTEM_HALTED" | "MAINTENANCE_MODE"
+        ) {
+            let msg = format!("❌ ORDER REFUSED: {}", decision.reason);
+            warn!(correlation_id = %correlation_id, signal_id = %processed_intent.signal_id, "{}", msg);
+            let _ = fsm.transition(
+                OrderLifecycleState::Rejected,
+                now_ms,
+                Some(decision.reason.clone()),
+            );
+            {
+                let mut state = self.shadow_state.write();
+                state.reject_intent(&processed_intent.signal_id, decision.reason.clone());
+                state.save_fsm(&fsm);
+            }
+            self.log_transitions(&fsm, &correlation_id);
+            pipeline_result.fsm = Some(fsm.clone());
+            self.trace(&correlation_id, TraceStageKind::Closed, Some(msg.clone()));
+            return Err(PipelineError::new(DlqReasonCode::RiskRejection, msg));
+        }
+
+        // Opens with a known edge must clear round-trip fees. Market orders
+        // sweep the book: refuse when it is too thin to absorb one or the
+        // spread is too wide to cross
+        let gates = match expected_profit_pct {
+            Some(expected) => {
+                let fees = decision
+                    .fee_analysis
+                    .clone()
+                    .unwrap_or_else(|| self.order_manager.analyze_fees(expected, Decimal::ZERO));
+                let maker_entry = decision.order_type != OrderType::Market && decision.post_only;
+                self.risk_guard.check_edge_after_fees(
+                    &processed_intent,
+                    &fees,
+                    maker_entry,
+                    decision.reduce_only,
+                )
+            }
+            None => Ok(()),
+        }
+        .and_then(|_| {
+            if decision.order_type != OrderType::Market {
+                return Ok(());
+            }
+            self.risk_guard
+                .check_market_depth(&processed_intent, &side, decision.reduce_only)
+                .and_then(|_| {
+                    self.risk_guard
+                        .check_market_spread(&processed_intent, decision.reduce_only)
+                })
+        });
+        if let Err(reason) = gates {
+            let msg = format!("❌ RISK REJECTION: {}", reason);
+            error!(correlation_id = %correlation_id, signal_id = %processed_intent.signal_id, "{}", msg);
+            metrics::inc_risk_rejections();
+            let _ = fsm.transition(
+                OrderLifecycleState::Rejected,
+                now_ms,
+                Some(format!("{:?}", reason)),
+            );
+            {
+                let mut state = self.shadow_state.write();
+                state.reject_intent(&processed_intent.signal_id, reason.to_string());
+                state.save_fsm(&fsm);
+            }
+            self.log_transitions(&fsm, &correlation_id);
+            pipeline_result.fsm = Some(fsm.clone());
+            self.trace(&correlation_id, TraceStageKind::Closed, Some(msg.clone()));
+            return Err(PipelineError::new(DlqReasonCode::RiskRejection, msg));
+        }
+
+        let zone_plan = match (&self.entry_zone, &decision.order_type) {
+            (Some(zone), OrderType::Limit) if !decision.reduce_only => {
+                zone.plan(&processed_intent, &side)
+            }
+            _ => None,
+        };
+
+        // Ids for the order and every zone level are drawn before anything is
+        // sent, so a failed one cannot leave part of the zone working
+        let id_prefix = correlation_prefix("tx", &correlation_id);
+        let ids = (0..=zone_plan.as_ref().map_or(0, Vec::len))
+            .map(|_| {
+                self.ctx
+                    .client_order_ids
+                    .generate(&id_prefix, DEFAULT_MAX_LEN)
+            })
+            .collect::<Result<Vec<_>, _>>();
+        let (client_order_id, level_ids) = match ids {
+            Ok(mut ids) => (ids.remove(0), ids),
+            Err(e) => {
+                let msg = format!("❌ ORDER NOT SENT: {}", e);
+                error!(correlation_id = %correlation_id, signal_id = %processed_intent.signal_id, "{}", msg);
+                let _ = fsm.transition(OrderLifecycleState::Rejected, now_ms, Some(e.to_string()));
+                {
+                    let mut state = self.shadow_state.write();
+                    state.reject_intent(&processed_intent.signal_id, e.to_string());
+                    state.save_fsm(&fsm);
+                }
+                self.log_transitions(&fsm, &correlation_id);
+                pipeline_result.fsm = Some(fsm.clone());
+                self.trace(&correlation_id, TraceStageKind::Closed, Some(msg.clone()));
+                return Err(PipelineError::new(DlqReasonCode::AdapterError, msg));
+            }
+        };
+
         let order_req = OrderRequest {
             symbol: processed_intent.symbol.replace("/", ""),
             side: side.clone(),
@@ -186,8 +586,15 @@ impl ExecutionPipeline {
             quantity: processed_intent.size,
             price: decision.limit_price,
             stop_price: None,
-            client_order_id: format!("{}-{}", processed_intent.signal_id, self.ctx.id.new_id()),
+            client_order_id,
             reduce_only: decision.reduce_only,
+            correlation_id: Some(correlation_id.clone()),
+            swap_mode: swap_mode_of(&processed_intent),
+            bracket: if decision.reduce_only {
+                None
+            } else {
+                Bracket::from_levels(processed_intent.stop_loss, &processed_intent.take_profits)
+            },
         };
 
         info!(
@@ -203,14 +610,46 @@ impl ExecutionPipeline {
             warn!("FSM transition error: {}", e);
         }
 
-        let results = self
-            .router
-            .execute(&processed_intent, order_req.clone())
-            .await;
+        let results = if let Some(levels) = &zone_plan {
+            info!(
+                correlation_id = %correlation_id,
+                "📐 Scaling {} across entry zone in {} orders",
+                processed_intent.symbol,
+                levels.len()
+            );
+            let mut results = Vec::new();
+            for ((price, quantity), client_order_id) in levels.iter().zip(level_ids) {
+                let mut level_req = order_req.clone();
+                level_req.price = Some(*price);
+                level_req.quantity = *quantity;
+                level_req.client_order_id = client_order_id;
+                results.extend(self.router.execute(&processed_intent, level_req).await);
+            }
+            results
+        } else {
+            self.router
+                .execute(&processed_intent, order_req.clone())
+                .await
+        };
 
+        let attempted = results.len();
+        self.trace(
+            &correlation_id,
+            TraceStageKind::Routed,
+            Some(format!("{} child orders", attempted)),
+        );
+        let mut venue_errors = Vec::new();
+        let mut rejections = Vec::new();
+        let confirmation_mode = self.confirmation_mode(&processed_intent);
+        let symbol_label = if self.risk_guard.is_whitelisted(&processed_intent.symbol) {
+            processed_intent.symbol.as_str()
+        } else {
+            metrics::OTHER_SYMBOL
+        };
         for (exchange_name, request, result) in results {
             match result {
                 Ok(response) => {
+                    metrics::inc_venue_orders_placed(&exchange_name, symbol_label);
                     info!(
                         correlation_id = %correlation_id,
                         "✅ [{}] Order Placed: ID {}",
@@ -232,14 +671,84 @@ impl ExecutionPipeline {
 
                     // FSM: Acked (exchange acknowledged the order)
                     let _ = fsm.transition(OrderLifecycleState::Acked, now_ms, None);
+                    self.trace(
+                        &correlation_id,
+                        TraceStageKind::ChildPlaced,
+                        Some(format!("{} {}", exchange_name, response.order_id)),
+                    );
+
+                    // Sync confirmation: finalize on the venue's terminal state, not its ack
+                    let response = if confirmation_mode == ConfirmationMode::Sync {
+                        self.await_terminal(&exchange_name, &request, response)
+                            .await
+                    } else {
+                        response
+                    };
+
+                    // Venue refused or killed the order before anything executed.
+                    // A cancel/expiry after a partial execution still applies that part.
+                    if response.status.is_dead() && response.executed_qty.is_zero() {
+                        metrics::inc_venue_orders_rejected(&exchange_name, symbol_label);
+                        warn!(
+                            correlation_id = %correlation_id,
+                            raw_status = %response.raw_status,
+                            "[{}] Order {} {} by venue",
+                            exchange_name,
+                            response.order_id,
+                            response.status
+                        );
+                        {
+                            let mut state = self.shadow_state.write();
+                            state.confirm_execution(
+                                &processed_intent.signal_id,
+                                &response.order_id,
+                                Decimal::ZERO,
+                                Decimal::ZERO,
+                                false,
+                                Decimal::ZERO,
+                                "USDT".to_string(),
+                                &exchange_name,
+                            );
+                        }
+                        self.trace(
+                            &correlation_id,
+                            TraceStageKind::ChildFailed,
+                            Some(format!(
+                                "{} {} {}",
+                                exchange_name, response.order_id, response.status
+                            )),
+                        );
+                        let next = if response.status == OrderStatus::Cancelled {
+                            OrderLifecycleState::Canceled
+                        } else {
+                            OrderLifecycleState::Failed
+                        };
+                        let _ = fsm.transition(
+                            next,
+                            now_ms,
+                            Some(format!("Venue status: {}", response.raw_status)),
+                        );
+                        continue;
+                    }
+
+                    // The venue holds the order: it counts towards the notional
+                    // velocity limit, a killed one only for what it executed
+                    let opened = if response.status.is_dead() {
+                        response.executed_qty
+                    } else {
+                        request.quantity
+                    };
+                    self.risk_guard
+                        .record_opened_notional(&processed_intent, opened);
 
                     let fill_price = response
                         .avg_price
-                        .unwrap_or(decision.limit_price.unwrap_or_default());
+                        .unwrap_or(request.price.or(decision.limit_price).unwrap_or_default());
 
                     // --- SLIPPAGE CHECK ---
-                    let expected_price = decision
-                        .limit_price
+                    let expected_price = request
+                        .price
+                        .or(decision.limit_price)
                         .or(processed_intent.entry_zone.first().cloned())
                         .unwrap_or(Decimal::ZERO);
                     if expected_price > Decimal::ZERO && fill_price > Decimal::ZERO {
@@ -257,6 +766,7 @@ impl ExecutionPipeline {
                     if response.executed_qty <= Decimal::ZERO || fill_price <= Decimal::ZERO {
                         warn!(
                             correlation_id = %correlation_id,
+                            status = %response.status,
                             executed_qty = %response.executed_qty,
                             fill_price = %fill_price,
                             "Order Placed but PENDING/Zero Fill - Tracking in ShadowState"
@@ -265,6 +775,24 @@ impl ExecutionPipeline {
                         // No, FillReport implies a FILL.
                         // But we might want to emit an "ExecutionReport" saying "New/Pending".
                         // For Phase 4, we stick to Fills-only for events, but State has it.
+
+                        if let (Some(repricer), Some(price), OrderType::Limit) =
+                            (&self.repricer, request.price, &request.order_type)
+                        {
+                            if let Some(adapter) = self.router.get_adapter(&exchange_name) {
+                                let resting = repricer.track(
+                                    &processed_intent.signal_id,
+                                    &exchange_name,
+                                    &request,
+                                    price,
+                                    &response.order_id,
+                                );
+                                let repricer = repricer.clone();
+                                tokio::spawn(async move {
+                                    repricer.run(adapter, resting).await;
+                                });
+                            }
+                        }
                         continue;
                     }
 
@@ -284,6 +812,27 @@ impl ExecutionPipeline {
                         (events, exposure)
                     };
 
+                    // Anomalous fill price or pure over-fill: nothing was applied, leave it to the DLQ
+                    if events_to_publish.iter().any(|e| match e {
+                        ExecutionEvent::FillAnomaly(_) => true,
+                        ExecutionEvent::Overfill(overfill) => overfill.applied.is_zero(),
+                        _ => false,
+                    }) {
+                        pipeline_result.events.extend(events_to_publish);
+                        continue;
+                    }
+
+                    if response.status.is_dead() {
+                        self.shadow_state.write().settle_cancelled_child(
+                            &processed_intent.signal_id,
+                            &response.order_id,
+                        );
+                    }
+
+                    if let Some(tp_ladder) = &self.tp_ladder {
+                        tp_ladder.apply_events(&events_to_publish).await;
+                    }
+
                     pipeline_result.events.extend(events_to_publish);
                     pipeline_result.exposure = Some(exposure);
 
@@ -294,8 +843,8 @@ impl ExecutionPipeline {
                         side: order_req.side.clone(),
                         price: fill_price,
                         qty: response.executed_qty,
-                        fee: Decimal::ZERO,
-                        fee_currency: "USDT".to_string(),
+                        fee: response.fee.unwrap_or(Decimal::ZERO),
+                        fee_currency: response.fee_asset.clone().unwrap_or("USDT".to_string()),
                         t_signal: processed_intent.t_signal,
                         t_ingress: processed_intent
                             .t_ingress
@@ -308,14 +857,29 @@ impl ExecutionPipeline {
                         status: "FILLED".to_string(),
                         timestamp: response.t_exchange.unwrap_or(self.ctx.time.now_millis()),
                         dex_proof: None,
+                        correlation_id: Some(correlation_id.clone()),
                     };
 
+                    metrics::record_venue_fill(
+                        &exchange_name,
+                        symbol_label,
+                        (fill_price * response.executed_qty).to_f64().unwrap_or(0.0),
+                        fill_report.fee.to_f64().unwrap_or(0.0),
+                    );
                     pipeline_result
                         .fill_reports
                         .push((exchange_name, fill_report));
 
                     // FSM: Filled (confirmed execution)
                     let _ = fsm.transition(OrderLifecycleState::Filled, now_ms, None);
+                    self.trace(
+                        &correlation_id,
+                        TraceStageKind::ChildFilled,
+                        Some(format!(
+                            "{} {} @ {}",
+                            response.order_id, response.executed_qty, fill_price
+                        )),
+                    );
 
                     // --- METRICS RECORDING (Phase 3) ---
                     // 1. End-to-End Latency
@@ -355,10 +919,11 @@ impl ExecutionPipeline {
                         pnl_pct: Decimal::ZERO,
                         fee: response.fee.unwrap_or(Decimal::ZERO),
                         fee_asset: response.fee_asset.clone().unwrap_or_default(),
-                        opened_at: Utc::now(), // Approx execution time
-                        closed_at: Utc::now(),
+                        opened_at: self.ctx.time.now(), // Approx execution time
+                        closed_at: self.ctx.time.now(),
                         close_reason: "Open".to_string(),
                         metadata: None,
+                        lots: Vec::new(),
                     };
 
                     let drifts = self
@@ -372,6 +937,17 @@ impl ExecutionPipeline {
                 }
                 Err(e) => {
                     error!("❌ [{}] Execution Failed: {}", exchange_name, e);
+                    metrics::inc_venue_orders_rejected(&exchange_name, symbol_label);
+                    venue_errors.push(format!("{}: {}", exchange_name, e));
+                    rejections.extend(VenueRejection::from_error(&exchange_name, &e));
+                    self.trace(
+                        &correlation_id,
+                        TraceStageKind::ChildFailed,
+                        Some(format!("{}: {}", exchange_name, e)),
+                    );
+                    self.shadow_state
+                        .write()
+                        .release_cash(&processed_intent.signal_id, Some(request.quantity));
                     let _ = fsm.transition(
                         OrderLifecycleState::Failed,
                         now_ms,
@@ -381,13 +957,49 @@ impl ExecutionPipeline {
             }
         }
 
+        // Zone orders left unfilled are pulled once the intent's TTL runs out
+        if let (Some(zone), Some(_)) = (&self.entry_zone, &zone_plan) {
+            let zone = zone.clone();
+            let ttl = zone.ttl(&processed_intent);
+            let signal_id = processed_intent.signal_id.clone();
+            let symbol = processed_intent.symbol.clone();
+            tokio::spawn(async move {
+                zone.expire_after(&signal_id, &symbol, ttl).await;
+            });
+        }
+
         // Persist FSM state to Redb for crash recovery
         {
             let state = self.shadow_state.read();
             state.save_fsm(&fsm);
         }
+        self.log_transitions(&fsm, &correlation_id);
         pipeline_result.fsm = Some(fsm);
 
+        // Every venue refused the order: nothing is working, so the intent is dead
+        if attempted > 0 && venue_errors.len() == attempted {
+            let msg = format!("❌ EXECUTION FAILED: {}", venue_errors.join("; "));
+            self.trace(&correlation_id, TraceStageKind::Closed, Some(msg.clone()));
+            let mut err = PipelineError::new(DlqReasonCode::AdapterError, msg);
+            err.rejections = rejections;
+            return Err(err);
+        }
+
+        pipeline_result.execution_report = ExecutionReport::from_fills(
+            &correlation_id,
+            &pipeline_result.fill_reports,
+            self.ctx.time.now_millis(),
+        )
+        .map(|report| ExecutionReport {
+            rejections,
+            ..report
+        });
+        self.trace(
+            &correlation_id,
+            TraceStageKind::Closed,
+            Some(format!("{} fills", pipeline_result.fill_reports.len())),
+        );
+
         Ok(pipeline_result)
     }
 
@@ -414,3 +1026,956 @@ impl ExecutionPipeline {
         }
     }
 }
+
+/// Swap mode requested in `metadata.swap_mode` ("ExactIn" / "ExactOut"), ExactIn otherwise
+fn swap_mode_of(intent: &Intent) -> SwapMode {
+    intent
+        .metadata
+        .as_ref()
+        .and_then(|m| m.get("swap_mode"))
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Expected edge (%) the signal attached as `metadata.expected_profit_pct`
+fn expected_profit_pct_of(intent: &Intent) -> Option<Decimal> {
+    intent
+        .metadata
+        .as_ref()
+        .and_then(|m| m.get("expected_profit_pct"))
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+}
+
+/// Whether `metadata.simulate_only` asks for a shadow fill without a venue order
+fn simulate_only(intent: &Intent) -> bool {
+    intent
+        .metadata
+        .as_ref()
+        .and_then(|m| m.get("simulate_only"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit_breaker::GlobalHalt;
+    use crate::exchange::adapter::{ExchangeAdapter, ExchangeError};
+    use crate::market_data::engine::MarketDataEngine;
+    use crate::persistence::redb_store::RedbStore;
+    use crate::persistence::store::PersistenceStore;
+    use crate::persistence::wal::WalManager;
+    use crate::test_support::MockAdapter;
+    use rust_decimal_macros::dec;
+
+    fn test_pipeline(
+        adapter: Arc<dyn ExchangeAdapter + Send + Sync>,
+        initial_balance: f64,
+    ) -> (ExecutionPipeline, Arc<RwLock<ShadowState>>, String) {
+        test_pipeline_with_market_data(
+            adapter,
+            initial_balance,
+            Arc::new(MarketDataEngine::new(None)),
+        )
+    }
+
+    fn test_pipeline_with_market_data(
+        adapter: Arc<dyn ExchangeAdapter + Send + Sync>,
+        initial_balance: f64,
+        market_data: Arc<MarketDataEngine>,
+    ) -> (ExecutionPipeline, Arc<RwLock<ShadowState>>, String) {
+        let router = ExecutionRouter::new();
+        router.register("binance", adapter);
+        test_pipeline_with_router(router, initial_balance, market_data)
+    }
+
+    fn test_pipeline_with_router(
+        router: ExecutionRouter,
+        initial_balance: f64,
+        market_data: Arc<MarketDataEngine>,
+    ) -> (ExecutionPipeline, Arc<RwLock<ShadowState>>, String) {
+        test_pipeline_with_guard(router, initial_balance, market_data, |_| {})
+    }
+
+    fn test_pipeline_with_guard(
+        router: ExecutionRouter,
+        initial_balance: f64,
+        market_data: Arc<MarketDataEngine>,
+        configure_guard: impl FnOnce(&mut RiskGuard),
+    ) -> (ExecutionPipeline, Arc<RwLock<ShadowState>>, String) {
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let path = format!("/tmp/test_pipeline_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let persistence = Arc::new(PersistenceStore::new(redb, wal));
+        let shadow_state = Arc::new(RwLock::new(ShadowState::new(
+            persistence,
+            ctx.clone(),
+            Some(initial_balance),
+        )));
+
+        let halt_path = std::env::temp_dir().join(format!("titan_halt_{}", uuid::Uuid::new_v4()));
+        let order_manager = OrderManager::new(
+            None,
+            market_data.clone(),
+            Arc::new(GlobalHalt::with_file(halt_path)),
+        );
+        let mut risk_guard = RiskGuard::new(Default::default(), shadow_state.clone());
+        configure_guard(&mut risk_guard);
+        let pipeline = ExecutionPipeline::new(
+            shadow_state.clone(),
+            order_manager,
+            Arc::new(router),
+            Arc::new(SimulationEngine::new(market_data, ctx.clone())),
+            Arc::new(risk_guard),
+            ctx,
+            5000,
+            Arc::new(DriftDetector::new(50.0, 1000, 100.0)),
+        );
+        (pipeline, shadow_state, path)
+    }
+
+    fn buy_intent(signal_id: &str, size: f64) -> Intent {
+        serde_json::from_value(serde_json::json!({
+            "signal_id": signal_id,
+            "source": "hunter",
+            "symbol": "BTC/USDT",
+            "direction": 1,
+            "type": "BUY_SETUP",
+            "entry_zone": [50000.0],
+            "size": size,
+            "status": "PENDING",
+            "t_signal": chrono::Utc::now().timestamp_millis(),
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_refuses_opens_but_ladder_keeps_managing() {
+        use crate::config::{TpDistribution, TpLadderConfig};
+        use crate::maintenance_mode::MaintenanceMode;
+
+        let adapter = Arc::new(MockAdapter::new("binance"));
+        let router = Arc::new(ExecutionRouter::new());
+        router.register("binance", adapter.clone());
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let path = format!("/tmp/test_pipeline_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let persistence = Arc::new(PersistenceStore::new(redb, wal));
+        let state = Arc::new(RwLock::new(ShadowState::new(
+            persistence,
+            ctx.clone(),
+            Some(10_000.0),
+        )));
+        let market_data = Arc::new(MarketDataEngine::new(None));
+        let tmp = std::env::temp_dir();
+        let maintenance = Arc::new(MaintenanceMode::with_file(
+            tmp.join(format!("titan_maint_{}", uuid::Uuid::new_v4())),
+        ));
+        let order_manager = OrderManager::new(
+            None,
+            market_data.clone(),
+            Arc::new(GlobalHalt::with_file(
+                tmp.join(format!("titan_halt_{}", uuid::Uuid::new_v4())),
+            )),
+        )
+        .with_maintenance_mode(maintenance.clone());
+        let tp_ladder = Arc::new(TpLadderExecutor::new(
+            router.clone(),
+            ctx.clone(),
+            TpLadderConfig {
+                enabled: true,
+                distribution: TpDistribution::Equal,
+                ..Default::default()
+            },
+        ));
+        let pipeline = ExecutionPipeline::new(
+            state.clone(),
+            order_manager,
+            router,
+            Arc::new(SimulationEngine::new(market_data, ctx.clone())),
+            Arc::new(RiskGuard::new(Default::default(), state.clone())),
+            ctx,
+            5000,
+            Arc::new(DriftDetector::new(50.0, 1000, 100.0)),
+        )
+        .with_tp_ladder(tp_ladder.clone());
+        let intent = |signal_id: &str, kind: &str, size: f64| -> Intent {
+            serde_json::from_value(serde_json::json!({
+                "signal_id": signal_id,
+                "source": "hunter",
+                "symbol": "BTC/USDT",
+                "direction": if kind == "BUY_SETUP" { 1 } else { -1 },
+                "type": kind,
+                "entry_zone": [50000.0],
+                "take_profits": [51000.0, 52000.0, 53000.0],
+                "size": size,
+                "status": "PENDING",
+                "t_signal": chrono::Utc::now().timestamp_millis(),
+            }))
+            .unwrap()
+        };
+
+        // Open with a three-rung ladder before maintenance starts
+        pipeline
+            .process_intent(intent("sig-open", "BUY_SETUP", 0.03), "corr-1".to_string())
+            .await
+            .unwrap();
+        assert_eq!(adapter.placed().len(), 4);
+
+        maintenance.set_active(true, "venue upgrade");
+
+        // New opens are refused before anything reaches the venue
+        let err = pipeline
+            .process_intent(intent("sig-add", "BUY_SETUP", 0.01), "corr-2".to_string())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, DlqReasonCode::RiskRejection);
+        assert!(err.reason.contains("MAINTENANCE_MODE"), "{}", err);
+        assert_eq!(adapter.placed().len(), 4);
+        assert_eq!(
+            state.read().get_position("BTC/USDT").unwrap().size,
+            dec!(0.03)
+        );
+
+        // A partial close still goes out, and the ladder is trimmed to match
+        pipeline
+            .process_intent(
+                intent("sig-trim", "CLOSE_LONG", 0.015),
+                "corr-3".to_string(),
+            )
+            .await
+            .unwrap();
+        let placed = adapter.placed();
+        assert_eq!(placed.len(), 6);
+        assert!(placed[4].reduce_only);
+        assert_eq!(placed[4].quantity, dec!(0.015));
+        // The middle rung is re-placed at its reduced size
+        assert!(placed[5].reduce_only);
+        assert_eq!(placed[5].price, Some(dec!(52000)));
+        assert_eq!(placed[5].quantity, dec!(0.005));
+        assert_eq!(adapter.cancelled_ids().len(), 2);
+        assert_eq!(
+            tp_ladder.ladder("BTC/USDT").await.unwrap().outstanding(),
+            dec!(0.015)
+        );
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_working_orders_reserve_cash_until_filled() {
+        // Default policy: 10x max leverage, so each order holds 10% of notional
+        let (pipeline, state, path) =
+            test_pipeline(Arc::new(MockAdapter::new("binance").resting()), 1_000.0);
+
+        // 0.12 BTC @ 50k = 6,000 notional -> 600 reserved
+        pipeline
+            .process_intent(buy_intent("sig-res-1", 0.12), "corr-1".to_string())
+            .await
+            .unwrap();
+        {
+            let state = state.read();
+            assert_eq!(state.get_cash_balance(), dec!(1000));
+            assert_eq!(state.get_reserved_cash(), dec!(600));
+            assert_eq!(state.get_available_cash(), dec!(400));
+        }
+
+        // Together the two orders need 1,200 of the 1,000 cash
+        let Err(err) = pipeline
+            .process_intent(buy_intent("sig-res-2", 0.12), "corr-2".to_string())
+            .await
+        else {
+            panic!("second order must not fit in available cash");
+        };
+        assert_eq!(err.code, DlqReasonCode::RiskRejection);
+        assert!(
+            err.reason.contains("Insufficient available cash"),
+            "{}",
+            err
+        );
+        assert!(state.read().get_cash_reservation("sig-res-2").is_none());
+
+        // A partial fill releases its share, a reject releases the rest
+        {
+            let mut state = state.write();
+            state.confirm_execution(
+                "sig-res-1",
+                "child-1",
+                dec!(50000),
+                dec!(0.06),
+                true,
+                Decimal::ZERO,
+                "USDT".to_string(),
+                "binance",
+            );
+            assert_eq!(state.get_reserved_cash(), dec!(300));
+
+            state.reject_intent("sig-res-1", "cancelled".to_string());
+            assert_eq!(state.get_reserved_cash(), Decimal::ZERO);
+        }
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_simulate_only_intent_shadow_fills_without_adapter_call() {
+        let market_data = Arc::new(MarketDataEngine::new(None));
+        market_data.apply_tick(
+            "binance",
+            crate::market_data::types::BookTicker {
+                symbol: "BTCUSDT".to_string(),
+                best_bid: dec!(49990),
+                best_bid_qty: dec!(1),
+                best_ask: dec!(50000),
+                best_ask_qty: dec!(1),
+                transaction_time: 0,
+                event_time: 0,
+            },
+        );
+        let adapter = Arc::new(MockAdapter::new("binance"));
+        let (pipeline, state, path) =
+            test_pipeline_with_market_data(adapter.clone(), 10_000.0, market_data);
+
+        let mut intent = buy_intent("sig-sim-1", 0.1);
+        intent.metadata = Some(serde_json::json!({ "simulate_only": true }));
+        let result = pipeline
+            .process_intent(intent, "corr-sim".to_string())
+            .await
+            .unwrap();
+
+        let shadow_fill = result.shadow_fill.expect("shadow fill");
+        assert_eq!(shadow_fill.price, dec!(50000));
+        assert_eq!(shadow_fill.qty, dec!(0.1));
+        assert!(adapter.placed.lock().is_empty());
+        assert!(result.fill_reports.is_empty());
+        {
+            let state = state.read();
+            assert!(state.get_position("BTC/USDT").is_none());
+            assert_eq!(state.get_reserved_cash(), Decimal::ZERO);
+        }
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_sync_confirmation_waits_for_terminal_state() {
+        let adapter = Arc::new(MockAdapter::new("binance").resting().filling_after_polls(3));
+        let (pipeline, state, path) = test_pipeline(adapter.clone(), 10_000.0);
+        let pipeline = pipeline.with_confirmation(ConfirmationConfig {
+            mode: ConfirmationMode::Sync,
+            poll_interval_ms: 5,
+            ..Default::default()
+        });
+
+        let result = pipeline
+            .process_intent(buy_intent("sig-sync-1", 0.01), "corr-sync".to_string())
+            .await
+            .unwrap();
+        assert_eq!(adapter.polls(), 3);
+        assert_eq!(result.fill_reports.len(), 1);
+        let position = state.read().get_position("BTC/USDT").cloned().unwrap();
+        assert_eq!(position.size, dec!(0.01));
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_async_confirmation_leaves_fill_to_stream() {
+        let adapter = Arc::new(MockAdapter::new("binance").resting().filling_after_polls(1));
+        let (pipeline, state, path) = test_pipeline(adapter.clone(), 10_000.0);
+        // Sync by default, but this source opts out
+        let pipeline = pipeline.with_confirmation(ConfirmationConfig {
+            mode: ConfirmationMode::Sync,
+            sources: [("hunter".to_string(), ConfirmationMode::Async)].into(),
+            poll_interval_ms: 5,
+            ..Default::default()
+        });
+
+        let result = pipeline
+            .process_intent(buy_intent("sig-async-1", 0.01), "corr-async".to_string())
+            .await
+            .unwrap();
+        assert_eq!(adapter.polls(), 0);
+        assert!(result.fill_reports.is_empty());
+        assert!(state.read().get_position("BTC/USDT").is_none());
+
+        // The fill arrives later over the execution stream
+        state.write().confirm_execution(
+            "sig-async-1",
+            "order-fill",
+            dec!(50000),
+            dec!(0.01),
+            true,
+            Decimal::ZERO,
+            "USDT".to_string(),
+            "binance",
+        );
+        assert!(state.read().get_position("BTC/USDT").is_some());
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_venue_metrics_labelled_by_exchange_and_symbol() {
+        let (pipeline, _state, path) =
+            test_pipeline(Arc::new(MockAdapter::new("binance")), 100_000.0);
+        pipeline
+            .router
+            .register("metrics-a", Arc::new(MockAdapter::new("binance")));
+        pipeline
+            .router
+            .register("metrics-b", Arc::new(MockAdapter::new("binance")));
+        for venue in ["metrics-a", "metrics-b"] {
+            pipeline
+                .risk_guard
+                .record_market_data_update(venue, "BTC/USDT");
+        }
+
+        let placed = |venue: &str| {
+            metrics::VENUE_ORDERS_PLACED
+                .with_label_values(&[venue, "BTC/USDT"])
+                .get()
+        };
+        let filled = |venue: &str| {
+            metrics::VENUE_ORDERS_FILLED
+                .with_label_values(&[venue, "BTC/USDT"])
+                .get()
+        };
+        let volume = |venue: &str| {
+            metrics::VENUE_VOLUME
+                .with_label_values(&[venue, "BTC/USDT"])
+                .get()
+        };
+
+        for (i, venue) in ["metrics-a", "metrics-b", "metrics-b"].iter().enumerate() {
+            let mut intent = buy_intent(&format!("sig-metrics-{}", i), 0.01);
+            intent.exchange = Some(venue.to_string());
+            pipeline
+                .process_intent(intent, format!("corr-metrics-{}", i))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!((placed("metrics-a"), filled("metrics-a")), (1, 1));
+        assert_eq!((placed("metrics-b"), filled("metrics-b")), (2, 2));
+        assert_eq!(volume("metrics-a"), 500.0);
+        assert_eq!(volume("metrics-b"), 1_000.0);
+        assert_eq!(
+            metrics::VENUE_ORDERS_REJECTED
+                .with_label_values(&["metrics-a", "BTC/USDT"])
+                .get(),
+            0
+        );
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_order_with_partial_execution_applies_fill() {
+        let adapter = Arc::new(MockAdapter::new("binance").cancelling_after(dec!(0.004)));
+        let (pipeline, state, path) = test_pipeline(adapter, 10_000.0);
+
+        let result = pipeline
+            .process_intent(buy_intent("sig-ioc-1", 0.01), "corr-ioc".to_string())
+            .await
+            .unwrap();
+        assert_eq!(result.fill_reports.len(), 1);
+        assert_eq!(result.fill_reports[0].1.qty, dec!(0.004));
+
+        // The executed part stands; the cancelled remainder closes the intent
+        let state = state.read();
+        assert_eq!(state.get_position("BTC/USDT").unwrap().size, dec!(0.004));
+        assert_eq!(state.count_open_intents_for_symbol("BTC/USDT"), 0);
+        assert!(state.get_cash_reservation("sig-ioc-1").is_none());
+        drop(state);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_dropped_intents_carry_dlq_reason_code() {
+        let (pipeline, state, path) = test_pipeline(
+            Arc::new(
+                MockAdapter::new("binance").failing(|| ExchangeError::VenueRejected {
+                    code: "-2019".to_string(),
+                    message: "insufficient margin".to_string(),
+                }),
+            ),
+            10_000.0,
+        );
+
+        let mut stale = buy_intent("sig-dlq-1", 0.01);
+        stale.t_signal -= 60_000;
+        let Err(err) = pipeline.process_intent(stale, "corr-1".to_string()).await else {
+            panic!("stale intent must be dropped");
+        };
+        assert_eq!(err.code, DlqReasonCode::Timeout);
+
+        let Err(err) = pipeline
+            .process_intent(buy_intent("sig-dlq-2", 0.01), "corr-2".to_string())
+            .await
+        else {
+            panic!("intent refused by every venue must be dropped");
+        };
+        assert_eq!(err.code, DlqReasonCode::AdapterError);
+        assert!(err.reason.contains("insufficient margin"), "{}", err);
+        assert!(state.read().get_cash_reservation("sig-dlq-2").is_none());
+        // The venue's own code survives into the DLQ record, classified no-retry
+        assert_eq!(err.rejections.len(), 1);
+        assert_eq!(err.rejections[0].exchange, "binance");
+        assert_eq!(err.rejections[0].code, "-2019");
+        assert!(!err.rejections[0].retryable);
+        let record = crate::dlq::dlq_payload_with_rejections(
+            err.code,
+            &err.reason,
+            b"{}",
+            0,
+            &err.rejections,
+        );
+        assert_eq!(
+            record["venue_rejections"][0]["class"],
+            "insufficient_balance"
+        );
+        assert_eq!(record["retryable"], false);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_only_accepted_opens_count_towards_notional_velocity() {
+        let refusing = MockAdapter::new("binance").failing(|| ExchangeError::VenueRejected {
+            code: "-2019".to_string(),
+            message: "insufficient margin".to_string(),
+        });
+        for (adapter, accepted) in [(refusing, false), (MockAdapter::new("binance"), true)] {
+            let router = ExecutionRouter::new();
+            router.register("binance", Arc::new(adapter));
+            let (pipeline, _state, path) = test_pipeline_with_guard(
+                router,
+                10_000.0,
+                Arc::new(MarketDataEngine::new(None)),
+                |guard| {
+                    guard.update_policy(crate::risk_policy::RiskPolicy {
+                        max_notional_per_window: Some(dec!(600)),
+                        notional_window_ms: 60_000,
+                        ..Default::default()
+                    })
+                },
+            );
+
+            // $500 each: a second open only fits if the first opened nothing
+            let result = pipeline
+                .process_intent(buy_intent("sig-vel-1", 0.01), "corr-vel-1".to_string())
+                .await;
+            assert_eq!(result.is_ok(), accepted);
+            let next = buy_intent("sig-vel-2", 0.01);
+            assert_eq!(
+                pipeline.risk_guard.check_pre_trade(&next).is_err(),
+                accepted
+            );
+
+            std::fs::remove_file(path).unwrap_or(());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_risk_budget_sizing_sets_order_quantity() {
+        let adapter = Arc::new(MockAdapter::new("binance"));
+        let router = ExecutionRouter::new();
+        router.register("binance", adapter.clone());
+        let (pipeline, _state, path) = test_pipeline_with_guard(
+            router,
+            100_000.0,
+            Arc::new(MarketDataEngine::new(None)),
+            |guard| guard.set_risk_per_trade(dec!(250)),
+        );
+
+        // Long 50000, stop 49000: $1000 per unit -> 0.25 instead of the 0.01 sent
+        let mut intent = buy_intent("sig-risk-1", 0.01);
+        intent.stop_loss = dec!(49000);
+        pipeline
+            .process_intent(intent, "corr-risk-1".to_string())
+            .await
+            .unwrap();
+        assert_eq!(adapter.placed.lock()[0].quantity, dec!(0.25));
+
+        // No stop to size from: refused before anything is sent
+        let Err(err) = pipeline
+            .process_intent(buy_intent("sig-risk-2", 0.01), "corr-risk-2".to_string())
+            .await
+        else {
+            panic!("an open without a stop cannot be risk-sized");
+        };
+        assert_eq!(err.code, DlqReasonCode::RiskRejection);
+        assert!(err.reason.contains("risk budget"), "{}", err);
+        assert_eq!(adapter.placed.lock().len(), 1);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    fn zone_executor(
+        state: &Arc<RwLock<ShadowState>>,
+        adapter: Arc<dyn ExchangeAdapter + Send + Sync>,
+        distribution: crate::config::TpDistribution,
+    ) -> Arc<EntryZoneExecutor> {
+        let router = Arc::new(ExecutionRouter::new());
+        router.register("binance", adapter);
+        Arc::new(EntryZoneExecutor::new(
+            state.clone(),
+            router,
+            crate::config::EntryZoneConfig {
+                enabled: true,
+                levels: 3,
+                distribution,
+                ttl_ms: 60_000,
+            },
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_entry_zone_orders_span_zone_and_sum_to_size() {
+        let adapter = Arc::new(MockAdapter::new("binance"));
+        let (pipeline, state, path) = test_pipeline(adapter.clone(), 100_000.0);
+        let pipeline = pipeline.with_entry_zone(zone_executor(
+            &state,
+            adapter.clone(),
+            crate::config::TpDistribution::FrontLoaded,
+        ));
+
+        let mut intent = buy_intent("sig-zone-1", 0.3);
+        intent.entry_zone = vec![dec!(49000), dec!(50000)];
+        let result = pipeline
+            .process_intent(intent, "corr-zone-1".to_string())
+            .await
+            .unwrap();
+
+        let sent = adapter.placed.lock().clone();
+        let prices: Vec<Decimal> = sent.iter().filter_map(|o| o.price).collect();
+        let sizes: Vec<Decimal> = sent.iter().map(|o| o.quantity).collect();
+        // Buys work down from the top of the zone, biggest clip first
+        assert_eq!(prices, vec![dec!(50000), dec!(49500), dec!(49000)]);
+        assert_eq!(sizes, vec![dec!(0.15), dec!(0.1), dec!(0.05)]);
+        assert_eq!(sizes.iter().sum::<Decimal>(), dec!(0.3));
+        assert!(sent.iter().all(|o| o.order_type == OrderType::Limit));
+
+        let ids: std::collections::HashSet<&str> =
+            sent.iter().map(|o| o.client_order_id.as_str()).collect();
+        assert_eq!(ids.len(), 3);
+
+        // Each level fills at its own price
+        let fill_prices: Vec<Decimal> = result
+            .fill_reports
+            .iter()
+            .map(|(_, fill)| fill.price)
+            .collect();
+        assert_eq!(fill_prices, prices);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_entry_zone_expiry_cancels_resting_orders() {
+        let adapter = Arc::new(MockAdapter::new("binance").resting());
+        let (pipeline, state, path) = test_pipeline(adapter.clone(), 100_000.0);
+        let zone = zone_executor(
+            &state,
+            adapter.clone(),
+            crate::config::TpDistribution::Equal,
+        );
+        let pipeline = pipeline.with_entry_zone(zone.clone());
+
+        let mut intent = buy_intent("sig-zone-2", 0.3);
+        intent.entry_zone = vec![dec!(49000), dec!(50000)];
+        pipeline
+            .process_intent(intent, "corr-zone-2".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            state.read().get_child_orders("sig-zone-2").unwrap().len(),
+            3
+        );
+        assert!(state.read().get_reserved_cash() > Decimal::ZERO);
+
+        assert_eq!(zone.expire("sig-zone-2", "BTC/USDT").await, 3);
+        assert_eq!(adapter.cancelled.lock().len(), 3);
+        {
+            let state = state.read();
+            assert!(state.get_cash_reservation("sig-zone-2").is_none());
+            assert_eq!(state.get_reserved_cash(), Decimal::ZERO);
+        }
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_replayed_terminal_signal_does_not_reopen() {
+        let adapter = Arc::new(MockAdapter::new("binance"));
+        let (pipeline, state, path) = test_pipeline(adapter.clone(), 100_000.0);
+
+        let mut intent = buy_intent("sig-replay", 0.01);
+        intent.causation_id = Some("cause-1".to_string());
+        pipeline
+            .process_intent(intent, "corr-1".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            state.read().get_position("BTC/USDT").unwrap().size,
+            dec!(0.01)
+        );
+
+        // Same signal, fresh causation_id: the executed intent is not run again
+        let mut replay = buy_intent("sig-replay", 0.01);
+        replay.causation_id = Some("cause-2".to_string());
+        let result = pipeline
+            .process_intent(replay, "corr-2".to_string())
+            .await
+            .unwrap();
+        assert!(result.fill_reports.is_empty());
+        assert_eq!(adapter.placed.lock().len(), 1);
+        assert_eq!(
+            state.read().get_position("BTC/USDT").unwrap().size,
+            dec!(0.01)
+        );
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_trace_records_lifecycle_in_order() {
+        let (pipeline, _state, path) =
+            test_pipeline(Arc::new(MockAdapter::new("binance")), 100_000.0);
+        let trace_path = format!("/tmp/test_pipeline_trace_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&trace_path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let tracer = Arc::new(IntentTracer::new(Arc::new(PersistenceStore::new(
+            redb, wal,
+        ))));
+        let pipeline = pipeline.with_tracer(tracer.clone());
+
+        let intent: Intent = serde_json::from_value(serde_json::json!({
+            "signal_id": "sig-trace",
+            "source": "hunter",
+            "symbol": "BTC/USDT",
+            "direction": 1,
+            "type": "BUY_SETUP",
+            "entry_zone": [50000.0],
+            "size": 0.01,
+            "status": "PENDING",
+            "t_signal": chrono::Utc::now().timestamp_millis(),
+        }))
+        .unwrap();
+
+        pipeline
+            .process_intent(intent, "corr-trace".to_string())
+            .await
+            .unwrap();
+
+        let trace = tracer.get("corr-trace").unwrap();
+        let stages: Vec<TraceStageKind> = trace.stages.iter().map(|s| s.stage).collect();
+        assert_eq!(
+            stages,
+            vec![
+                TraceStageKind::Received,
+                TraceStageKind::RiskChecked,
+                TraceStageKind::Validated,
+                TraceStageKind::Routed,
+                TraceStageKind::ChildPlaced,
+                TraceStageKind::ChildFilled,
+                TraceStageKind::Closed,
+            ]
+        );
+        assert!(trace.stages.windows(2).all(|w| w[0].ts <= w[1].ts));
+        assert_eq!(trace.stages[6].detail.as_deref(), Some("1 fills"));
+
+        std::fs::remove_file(path).unwrap_or(());
+        std::fs::remove_file(trace_path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_intent_transitions_written_to_event_log() {
+        let (pipeline, _state, path) =
+            test_pipeline(Arc::new(MockAdapter::new("binance")), 100_000.0);
+        let log_path = format!("/tmp/test_pipeline_cdc_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&log_path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let event_log = Arc::new(EventLog::new(Arc::new(PersistenceStore::new(redb, wal))));
+        let pipeline = pipeline.with_event_log(event_log.clone());
+
+        pipeline
+            .process_intent(buy_intent("sig-cdc-1", 0.01), "corr-cdc-1".to_string())
+            .await
+            .unwrap();
+        let mut stale = buy_intent("sig-cdc-2", 0.01);
+        stale.t_signal -= 60_000;
+        assert!(pipeline
+            .process_intent(stale, "corr-cdc-2".to_string())
+            .await
+            .is_err());
+
+        let logged = event_log.replay_from(1, 50).unwrap();
+        assert!(logged
+            .iter()
+            .all(|e| e.event_type == TYPE_INTENT_TRANSITION));
+        assert!(logged.windows(2).all(|w| w[0].seq < w[1].seq));
+        let transitions = |corr: &str| -> Vec<serde_json::Value> {
+            logged
+                .iter()
+                .filter(|e| e.correlation_id.as_deref() == Some(corr))
+                .map(|e| e.payload.clone())
+                .collect()
+        };
+
+        let filled = transitions("corr-cdc-1");
+        assert!(filled.len() > 1);
+        assert!(filled.iter().all(|p| p["signalId"] == "sig-cdc-1"));
+        assert_eq!(filled.last().unwrap()["to"], "FILLED");
+
+        let expired = transitions("corr-cdc-2");
+        assert_eq!(expired.last().unwrap()["to"], "FAILED");
+
+        std::fs::remove_file(path).unwrap_or(());
+        std::fs::remove_file(log_path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_correlation_id_reaches_order_and_fill_report() {
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let path = format!("/tmp/test_pipeline_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let persistence = Arc::new(PersistenceStore::new(redb, wal));
+        let shadow_state = Arc::new(RwLock::new(ShadowState::new(
+            persistence,
+            ctx.clone(),
+            Some(100_000.0),
+        )));
+
+        let market_data = Arc::new(MarketDataEngine::new(None));
+        let halt_path = std::env::temp_dir().join(format!("titan_halt_{}", uuid::Uuid::new_v4()));
+        let order_manager = OrderManager::new(
+            None,
+            market_data.clone(),
+            Arc::new(GlobalHalt::with_file(halt_path)),
+        );
+        let adapter = Arc::new(MockAdapter::new("binance"));
+        let router = Arc::new(ExecutionRouter::new());
+        router.register("binance", adapter.clone());
+
+        let pipeline = ExecutionPipeline::new(
+            shadow_state.clone(),
+            order_manager,
+            router,
+            Arc::new(SimulationEngine::new(market_data, ctx.clone())),
+            Arc::new(RiskGuard::new(Default::default(), shadow_state)),
+            ctx.clone(),
+            5000,
+            Arc::new(DriftDetector::new(50.0, 1000, 100.0)),
+        );
+
+        let intent: Intent = serde_json::from_value(serde_json::json!({
+            "signal_id": "sig-corr",
+            "source": "hunter",
+            "symbol": "BTC/USDT",
+            "direction": 1,
+            "type": "BUY_SETUP",
+            "entry_zone": [50000.0],
+            "size": 0.01,
+            "status": "PENDING",
+            "t_signal": ctx.time.now_millis(),
+        }))
+        .unwrap();
+
+        let correlation_id = "3f2a9c1e-7b44-4d2e-9a10-5c6d7e8f9a0b".to_string();
+        let result = pipeline
+            .process_intent(intent, correlation_id.clone())
+            .await
+            .unwrap();
+
+        let sent = adapter.placed.lock().clone();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(
+            sent[0].correlation_id.as_deref(),
+            Some(correlation_id.as_str())
+        );
+        assert!(sent[0].client_order_id.starts_with("tx3f2a9c1e-"));
+
+        let (_, fill) = &result.fill_reports[0];
+        assert_eq!(
+            fill.correlation_id.as_deref(),
+            Some(correlation_id.as_str())
+        );
+        let payload = serde_json::to_value(fill).unwrap();
+        assert_eq!(payload["correlationId"], correlation_id.as_str());
+        assert_eq!(
+            result.execution_report.unwrap().correlation_id,
+            correlation_id
+        );
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    struct PanickingStrategy;
+
+    impl crate::exchange::routing::RoutingStrategy for PanickingStrategy {
+        fn name(&self) -> &'static str {
+            "panicking"
+        }
+
+        fn resolve(
+            &self,
+            _intent: &Intent,
+            _available_adapters: &crate::exchange::routing::AdapterMap,
+            _market_data: Option<&MarketDataEngine>,
+        ) -> Vec<crate::exchange::routing::RouteTarget> {
+            panic!("routing table corrupted");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_panic_in_processing_is_dead_lettered_and_counted() {
+        let router = ExecutionRouter::new().with_routing_strategy(Arc::new(PanickingStrategy));
+        router.register("binance", Arc::new(MockAdapter::new("binance")));
+        let (pipeline, _state, path) =
+            test_pipeline_with_router(router, 10_000.0, Arc::new(MarketDataEngine::new(None)));
+        let halt_path = std::env::temp_dir().join(format!("titan_halt_{}", uuid::Uuid::new_v4()));
+        let halt = Arc::new(GlobalHalt::with_file(&halt_path));
+        let watchdog = Arc::new(PanicWatchdog::new(
+            halt.clone(),
+            &crate::config::PanicWatchdogConfig {
+                max_panics: 2,
+                window_ms: 60_000,
+            },
+        ));
+        let pipeline = pipeline.with_panic_watchdog(watchdog.clone());
+        let metric_before = metrics::PIPELINE_PANICS.get();
+
+        let Err(err) = pipeline
+            .process_intent_guarded(buy_intent("sig-panic-1", 0.1), "corr-p1".to_string())
+            .await
+        else {
+            panic!("a panicking intent must fail");
+        };
+        // Filed under its own DLQ code, so the consumer dead-letters and ACKs it
+        assert_eq!(err.code, DlqReasonCode::Panic);
+        assert!(err.reason.contains("routing table corrupted"));
+        assert_eq!(
+            crate::dlq::dlq_payload(err.code, &err.reason, b"{}", 0)["reason_code"],
+            "panic"
+        );
+        assert_eq!(watchdog.panics_total(), 1);
+        assert!(metrics::PIPELINE_PANICS.get() > metric_before);
+        assert!(!halt.is_halted());
+
+        // The second within the window halts trading
+        let _ = pipeline
+            .process_intent_guarded(buy_intent("sig-panic-2", 0.1), "corr-p2".to_string())
+            .await;
+        assert_eq!(watchdog.panics_total(), 2);
+        assert!(halt.is_halted());
+
+        std::fs::remove_file(path).unwrap_or(());
+        std::fs::remove_file(halt_path).unwrap_or(());
+    }
+}
@@ -0,0 +1,149 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::model::Intent;
+use crate::risk_guard::RiskRejectionReason;
+
+/// Decimal places a risk-budget size is kept to. Rounded down so a stop-out
+/// never loses more than the budget.
+pub const SIZE_DP: u32 = 8;
+
+/// Size at which a stop-out of `intent` loses exactly `risk_per_trade`:
+/// `risk_per_trade / |entry - stop_loss|`, with the entry taken from the first
+/// entry-zone price, rounded down to a whole number of `lot_size` when the
+/// instrument has one. The stop must sit on the losing side of the entry.
+pub fn risk_budget_size(
+    intent: &Intent,
+    risk_per_trade: Decimal,
+    lot_size: Option<Decimal>,
+) -> Result<Decimal, RiskRejectionReason> {
+    let reject = |reason: &str| RiskRejectionReason::RiskSizingFailed {
+        symbol: intent.symbol.clone(),
+        reason: reason.to_string(),
+    };
+
+    let entry = intent.entry_zone.first().copied().unwrap_or(Decimal::ZERO);
+    if entry <= Decimal::ZERO {
+        return Err(reject("no entry price"));
+    }
+    if intent.stop_loss <= Decimal::ZERO {
+        return Err(reject("no stop loss"));
+    }
+
+    // Long loses as price falls to the stop, short as it rises
+    let stop_distance = if intent.direction > 0 {
+        entry - intent.stop_loss
+    } else {
+        intent.stop_loss - entry
+    };
+    if stop_distance <= Decimal::ZERO {
+        return Err(reject("stop loss on the wrong side of entry"));
+    }
+
+    let size =
+        (risk_per_trade / stop_distance).round_dp_with_strategy(SIZE_DP, RoundingStrategy::ToZero);
+    let size = match lot_size.filter(|lot| *lot > Decimal::ZERO) {
+        Some(lot) => (size / lot).trunc() * lot,
+        None => size,
+    };
+    if size <= Decimal::ZERO {
+        return Err(reject("risk budget too small for stop distance"));
+    }
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{IntentStatus, IntentType};
+    use rust_decimal_macros::dec;
+
+    fn intent(direction: i32, entry: Decimal, stop_loss: Decimal) -> Intent {
+        Intent {
+            signal_id: "sig-size".to_string(),
+            source: None,
+            symbol: "BTC/USDT".to_string(),
+            direction,
+            intent_type: if direction > 0 {
+                IntentType::BuySetup
+            } else {
+                IntentType::SellSetup
+            },
+            entry_zone: vec![entry],
+            stop_loss,
+            take_profits: vec![],
+            size: dec!(5),
+            status: IntentStatus::Pending,
+            filled_size: Decimal::ZERO,
+            child_fills: vec![],
+            ttl_ms: None,
+            partition_key: None,
+            causation_id: None,
+            env: None,
+            subject: None,
+            t_signal: 0,
+            t_analysis: None,
+            t_decision: None,
+            t_ingress: None,
+            t_exchange: None,
+            max_slippage_bps: None,
+            rejection_reason: None,
+            regime_state: None,
+            phase: None,
+            metadata: None,
+            exchange: None,
+            policy_hash: None,
+            position_mode: None,
+        }
+    }
+
+    #[test]
+    fn test_size_from_stop_distance_long_and_short() {
+        // Long 50000 stop 49000: $1000 per unit, $250 budget -> 0.25
+        let long = intent(1, dec!(50000), dec!(49000));
+        let size = risk_budget_size(&long, dec!(250), None).unwrap();
+        assert_eq!(size, dec!(0.25));
+        assert_eq!(size * (dec!(50000) - dec!(49000)), dec!(250));
+
+        // Short 3000 stop 3150: $150 per unit, $300 budget -> 2
+        let short = intent(-1, dec!(3000), dec!(3150));
+        assert_eq!(risk_budget_size(&short, dec!(300), None).unwrap(), dec!(2));
+
+        // Uneven ratios round down, never over-risking
+        let odd = intent(1, dec!(100), dec!(97));
+        let size = risk_budget_size(&odd, dec!(10), None).unwrap();
+        assert_eq!(size, dec!(3.33333333));
+        assert!(size * dec!(3) <= dec!(10));
+    }
+
+    #[test]
+    fn test_size_rounded_down_to_lot() {
+        // 3.33333333 units in lots of 0.5 -> 3
+        let odd = intent(1, dec!(100), dec!(97));
+        assert_eq!(
+            risk_budget_size(&odd, dec!(10), Some(dec!(0.5))).unwrap(),
+            dec!(3)
+        );
+
+        // Long 50000 stop 49000 with $0.5 at risk: 0.0005 units, under one 0.001 lot
+        let small = intent(1, dec!(50000), dec!(49000));
+        assert!(matches!(
+            risk_budget_size(&small, dec!(0.5), Some(dec!(0.001))),
+            Err(RiskRejectionReason::RiskSizingFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_unusable_stop_is_rejected() {
+        for bad in [
+            intent(1, dec!(50000), dec!(51000)),
+            intent(-1, dec!(3000), dec!(2900)),
+            intent(1, dec!(50000), Decimal::ZERO),
+            intent(1, Decimal::ZERO, dec!(49000)),
+        ] {
+            assert!(matches!(
+                risk_budget_size(&bad, dec!(250), None),
+                Err(RiskRejectionReason::RiskSizingFailed { .. })
+            ));
+        }
+    }
+}
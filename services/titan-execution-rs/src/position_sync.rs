@@ -0,0 +1,310 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::RwLock;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::alerts::{Alert, AlertSink, KIND_POSITION_DIVERGENCE};
+use crate::circuit_breaker::GlobalHalt;
+use crate::config::{PositionSyncConfig, ReconciliationToleranceConfig};
+use crate::context::ExecutionContext;
+use crate::event_log::{EventLog, TYPE_POSITION_SYNCED};
+use crate::exchange::router::ExecutionRouter;
+use crate::model::Position;
+use crate::shadow_state::ShadowState;
+use crate::startup_reconciliation::{
+    diff_positions, fetch_live_positions, symbol_key, DriftTolerance, PositionDivergence,
+};
+
+/// What one reconciliation pass did.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncOutcome {
+    InSync,
+    /// Small divergences overwritten with the venue sizes
+    Corrected(Vec<PositionDivergence>),
+    /// At least one divergence was too large to trust either side; trading halted
+    Halted(Vec<PositionDivergence>),
+    /// A venue could not be queried, so the pass was skipped
+    Skipped,
+}
+
+/// Periodically compares shadow positions with what the venues hold. Small
+/// drift is corrected from the venue with an audit record on the event log;
+/// large drift engages the global halt for an operator to resolve.
+pub struct PositionSync {
+    router: Arc<ExecutionRouter>,
+    shadow_state: Arc<RwLock<ShadowState>>,
+    global_halt: Arc<GlobalHalt>,
+    event_log: Arc<EventLog>,
+    ctx: Arc<ExecutionContext>,
+    config: PositionSyncConfig,
+    tolerances: ReconciliationToleranceConfig,
+    alert_sink: Option<Arc<dyn AlertSink>>,
+}
+
+impl PositionSync {
+    pub fn new(
+        router: Arc<ExecutionRouter>,
+        shadow_state: Arc<RwLock<ShadowState>>,
+        global_halt: Arc<GlobalHalt>,
+        event_log: Arc<EventLog>,
+        ctx: Arc<ExecutionContext>,
+        config: PositionSyncConfig,
+    ) -> Self {
+        Self {
+            router,
+            shadow_state,
+            global_halt,
+            event_log,
+            ctx,
+            config,
+            tolerances: ReconciliationToleranceConfig::default(),
+            alert_sink: None,
+        }
+    }
+
+    /// Per-symbol dust allowances on top of `tolerance_pct`
+    pub fn with_tolerances(mut self, tolerances: ReconciliationToleranceConfig) -> Self {
+        self.tolerances = tolerances;
+        self
+    }
+
+    /// Raise an alert whenever a divergence is too large to auto-correct
+    pub fn with_alert_sink(mut self, sink: Arc<dyn AlertSink>) -> Self {
+        self.alert_sink = Some(sink);
+        self
+    }
+
+    pub fn start(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_millis(self.config.interval_ms));
+            // The first tick fires immediately; startup has its own reconciliation
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                self.run_once().await;
+            }
+        })
+    }
+
+    pub async fn run_once(&self) -> SyncOutcome {
+        let (_, live, errors) = fetch_live_positions(&self.router).await;
+        if !errors.is_empty() {
+            warn!("Position sync skipped: {}", errors.join("; "));
+            return SyncOutcome::Skipped;
+        }
+
+        let local: Vec<Position> = self
+            .shadow_state
+            .read()
+            .get_all_positions()
+            .into_values()
+            .collect();
+        let tolerance = DriftTolerance::new(
+            Decimal::from_f64(self.config.tolerance_pct).unwrap_or(Decimal::ZERO),
+        )
+        .with_symbols(&self.tolerances);
+        let max_auto_correct =
+            Decimal::from_f64(self.config.max_auto_correct_pct).unwrap_or(Decimal::ZERO);
+
+        let divergences = diff_positions(&local, &live, &tolerance);
+        if divergences.is_empty() {
+            return SyncOutcome::InSync;
+        }
+
+        // Only a size drift on a position we already hold is correctable
+        let correctable = |d: &PositionDivergence| {
+            d.divergence_pct <= max_auto_correct
+                && d.local_size.is_sign_positive() == d.venue_size.is_sign_positive()
+        };
+        if !divergences.iter().all(correctable) {
+            for d in &divergences {
+                error!(
+                    "🚨 Position sync divergence {}: shadow {} vs venues {} ({}%)",
+                    d.symbol, d.local_size, d.venue_size, d.divergence_pct
+                );
+            }
+            if let Some(sink) = &self.alert_sink {
+                let symbols: Vec<String> = divergences
+                    .iter()
+                    .map(|d| format!("{} ({}%)", d.symbol, d.divergence_pct.round_dp(2)))
+                    .collect();
+                sink.send(Alert::critical(
+                    KIND_POSITION_DIVERGENCE,
+                    format!(
+                        "Shadow positions diverge from venues: {}",
+                        symbols.join(", ")
+                    ),
+                ));
+            }
+            self.global_halt.set_halt(
+                true,
+                &format!(
+                    "Position sync: {} symbols diverge beyond {}%",
+                    divergences.len(),
+                    max_auto_correct
+                ),
+            );
+            return SyncOutcome::Halted(divergences);
+        }
+
+        for d in &divergences {
+            let Some(symbol) = local
+                .iter()
+                .find(|p| symbol_key(&p.symbol) == d.symbol)
+                .map(|p| p.symbol.clone())
+            else {
+                continue;
+            };
+            let synced = self
+                .shadow_state
+                .write()
+                .sync_position_size(&symbol, d.venue_size);
+            if synced.is_none() {
+                continue;
+            }
+
+            warn!(
+                "🔧 Position sync corrected {}: {} -> {} ({}%)",
+                symbol, d.local_size, d.venue_size, d.divergence_pct
+            );
+            let payload = serde_json::json!({
+                "symbol": symbol,
+                "shadowSize": d.local_size,
+                "venueSize": d.venue_size,
+                "divergencePct": d.divergence_pct,
+            });
+            if let Err(e) = self.event_log.append(
+                TYPE_POSITION_SYNCED,
+                None,
+                payload,
+                self.ctx.time.now_millis(),
+            ) {
+                error!("Failed to record position sync for {}: {}", symbol, e);
+            }
+        }
+        info!("Position sync corrected {} symbols", divergences.len());
+        SyncOutcome::Corrected(divergences)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Side;
+    use crate::persistence::redb_store::RedbStore;
+    use crate::persistence::store::PersistenceStore;
+    use crate::persistence::wal::WalManager;
+    use crate::test_support::MockAdapter;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn position(symbol: &str, size: Decimal) -> Position {
+        Position {
+            symbol: symbol.to_string(),
+            side: Side::Long,
+            size,
+            entry_price: dec!(50000),
+            stop_loss: Decimal::ZERO,
+            take_profits: vec![],
+            signal_id: "sig-1".to_string(),
+            opened_at: Utc::now(),
+            regime_state: None,
+            phase: None,
+            metadata: None,
+            exchange: Some("binance".to_string()),
+            position_mode: None,
+            realized_pnl: Decimal::ZERO,
+            unrealized_pnl: Decimal::ZERO,
+            fees_paid: Decimal::ZERO,
+            funding_paid: Decimal::ZERO,
+            last_mark_price: None,
+            last_update_ts: 0,
+            entry_lots: Vec::new(),
+        }
+    }
+
+    /// Shadow state holding 1 BTC long, against a venue holding `venue_size`.
+    fn setup(venue_size: Decimal) -> (PositionSync, Arc<RwLock<ShadowState>>, String) {
+        let path = format!("/tmp/test_position_sync_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let persistence = Arc::new(PersistenceStore::new(redb, wal));
+        persistence
+            .save_position(&position("BTC/USDT", dec!(1.0)))
+            .unwrap();
+
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let shadow_state = Arc::new(RwLock::new(ShadowState::new(
+            persistence.clone(),
+            ctx.clone(),
+            Some(10_000.0),
+        )));
+        let router = Arc::new(ExecutionRouter::new());
+        router.register(
+            "binance",
+            Arc::new(
+                MockAdapter::new("binance").with_positions(vec![position("BTCUSDT", venue_size)]),
+            ),
+        );
+        let halt_path = std::env::temp_dir().join(format!("titan_halt_{}", uuid::Uuid::new_v4()));
+
+        let sync = PositionSync::new(
+            router,
+            shadow_state.clone(),
+            Arc::new(GlobalHalt::with_file(halt_path)),
+            Arc::new(EventLog::new(persistence)),
+            ctx,
+            PositionSyncConfig {
+                enabled: true,
+                interval_ms: 1_000,
+                tolerance_pct: 0.1,
+                max_auto_correct_pct: 5.0,
+            },
+        );
+        (sync, shadow_state, path)
+    }
+
+    #[tokio::test]
+    async fn test_small_divergence_is_corrected_with_audit_event() {
+        let (sync, state, path) = setup(dec!(0.98));
+
+        let outcome = sync.run_once().await;
+        assert!(matches!(&outcome, SyncOutcome::Corrected(d) if d.len() == 1));
+        assert_eq!(
+            state.read().get_position("BTC/USDT").unwrap().size,
+            dec!(0.98)
+        );
+        assert!(!sync.global_halt.is_halted());
+
+        let events = sync.event_log.replay_from(1, 10).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, TYPE_POSITION_SYNCED);
+        assert_eq!(events[0].payload["symbol"], "BTC/USDT");
+
+        // Corrected state is now in sync
+        assert_eq!(sync.run_once().await, SyncOutcome::InSync);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_large_divergence_halts_without_correcting() {
+        let (sync, state, path) = setup(dec!(0.5));
+
+        let outcome = sync.run_once().await;
+        assert!(matches!(&outcome, SyncOutcome::Halted(d) if d[0].divergence_pct == dec!(50)));
+        assert!(sync.global_halt.is_halted());
+        assert_eq!(
+            state.read().get_position("BTC/USDT").unwrap().size,
+            dec!(1.0)
+        );
+        assert!(sync.event_log.replay_from(1, 10).unwrap().is_empty());
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+}
@@ -0,0 +1,588 @@
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use thiserror::Error;
+use tracing::{error, info, warn};
+
+use crate::alerts::{Alert, AlertSeverity, AlertSink, KIND_POSITION_TRANSFER};
+use crate::client_order_id::{correlation_prefix, DEFAULT_MAX_LEN};
+use crate::context::ExecutionContext;
+use crate::exchange::adapter::{
+    ExchangeAdapter, OrderRequest, OrderResponse, OrderStatus, SwapMode,
+};
+use crate::exchange::router::ExecutionRouter;
+use crate::model::{Intent, IntentStatus, IntentType, OrderType, Position, Side};
+use crate::shadow_state::ShadowState;
+use crate::shutdown::normalize_symbol;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum TransferError {
+    #[error("No position held in {0}")]
+    NoPosition(String),
+    #[error("Position in {0} has no venue to move from")]
+    NoSourceVenue(String),
+    #[error("Position in {symbol} is already on {venue}")]
+    SameVenue { symbol: String, venue: String },
+    #[error("Adapter '{0}' not registered")]
+    UnknownVenue(String),
+}
+
+/// How a transfer ended.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransferOutcome {
+    /// Closed on the source and reopened in full on the target
+    Moved,
+    /// The close was not confirmed, so nothing was opened
+    CloseFailed,
+    /// The open fell short and the shortfall was reopened on the source
+    RolledBack,
+    /// The open and the reopen both fell short: exposure was lost on both venues
+    Stranded,
+}
+
+/// Outcome of moving one symbol's position between venues. Both legs carry
+/// `correlation_id`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferReport {
+    pub correlation_id: String,
+    pub symbol: String,
+    pub from: String,
+    pub to: String,
+    pub side: Side,
+    /// Size confirmed closed on the source
+    pub closed: Decimal,
+    /// Size confirmed opened on the target
+    pub opened: Decimal,
+    pub outcome: TransferOutcome,
+    pub errors: Vec<String>,
+}
+
+/// Consolidates a symbol's exposure onto another venue, e.g. off one that is
+/// degrading: close on the source, and only once that is confirmed open the
+/// same size on the target. An open that falls short is reopened on the source.
+/// Every leg is booked in the shadow state at its confirmed fill, so the close
+/// realizes PnL and fees and the new leg carries its own entry and fees.
+pub struct PositionTransfer {
+    router: Arc<ExecutionRouter>,
+    shadow_state: Arc<RwLock<ShadowState>>,
+    ctx: Arc<ExecutionContext>,
+    alert_sink: Option<Arc<dyn AlertSink>>,
+}
+
+impl PositionTransfer {
+    pub fn new(
+        router: Arc<ExecutionRouter>,
+        shadow_state: Arc<RwLock<ShadowState>>,
+        ctx: Arc<ExecutionContext>,
+    ) -> Self {
+        Self {
+            router,
+            shadow_state,
+            ctx,
+            alert_sink: None,
+        }
+    }
+
+    /// Alert when a transfer rolls back or strands exposure
+    pub fn with_alert_sink(mut self, sink: Arc<dyn AlertSink>) -> Self {
+        self.alert_sink = Some(sink);
+        self
+    }
+
+    pub async fn transfer(&self, symbol: &str, to: &str) -> Result<TransferReport, TransferError> {
+        let to = to.to_lowercase();
+        let key = normalize_symbol(symbol);
+        let pos = self
+            .shadow_state
+            .read()
+            .get_all_positions()
+            .into_values()
+            .find(|p| normalize_symbol(&p.symbol) == key)
+            .ok_or_else(|| TransferError::NoPosition(symbol.to_string()))?;
+        let from = pos
+            .exchange
+            .as_deref()
+            .map(str::to_lowercase)
+            .ok_or_else(|| TransferError::NoSourceVenue(pos.symbol.clone()))?;
+        if from == to {
+            return Err(TransferError::SameVenue {
+                symbol: pos.symbol.clone(),
+                venue: to,
+            });
+        }
+        let source = self
+            .router
+            .get_adapter(&from)
+            .ok_or_else(|| TransferError::UnknownVenue(from.clone()))?;
+        let target = self
+            .router
+            .get_adapter(&to)
+            .ok_or_else(|| TransferError::UnknownVenue(to.clone()))?;
+
+        let correlation_id = self.ctx.id.new_id();
+        let (close_side, open_side) = match pos.side {
+            Side::Buy | Side::Long => (Side::Sell, Side::Buy),
+            Side::Sell | Side::Short => (Side::Buy, Side::Sell),
+        };
+        info!(
+            correlation_id = %correlation_id,
+            "🔀 Moving {} {:?} {} from {} to {}",
+            pos.symbol, pos.side, pos.size, from, to
+        );
+        let mut report = TransferReport {
+            correlation_id,
+            symbol: pos.symbol.clone(),
+            from,
+            to,
+            side: pos.side.clone(),
+            closed: Decimal::ZERO,
+            opened: Decimal::ZERO,
+            outcome: TransferOutcome::CloseFailed,
+            errors: Vec::new(),
+        };
+
+        // Nothing is opened elsewhere until the close is confirmed
+        report.closed = self
+            .leg(
+                &*source,
+                &report.from,
+                &pos,
+                &report,
+                close_side,
+                pos.size,
+                true,
+            )
+            .await
+            .unwrap_or_else(|e| {
+                report_error(&mut report.errors, &report.from, e);
+                Decimal::ZERO
+            });
+        if report.closed.is_zero() {
+            warn!(
+                correlation_id = %report.correlation_id,
+                "Close of {} on {} not confirmed, nothing moved",
+                report.symbol, report.from
+            );
+            return Ok(report);
+        }
+
+        report.opened = self
+            .leg(
+                &*target,
+                &report.to,
+                &pos,
+                &report,
+                open_side.clone(),
+                report.closed,
+                false,
+            )
+            .await
+            .unwrap_or_else(|e| {
+                report_error(&mut report.errors, &report.to, e);
+                Decimal::ZERO
+            });
+        if report.opened >= report.closed {
+            if report.closed < pos.size {
+                // The rest still sits on the source; the book keeps one venue per symbol
+                warn!(
+                    correlation_id = %report.correlation_id,
+                    "Only {} of {} {} closed on {}, position now split across venues",
+                    report.closed, pos.size, report.symbol, report.from
+                );
+                report.errors.push(format!(
+                    "{}: {} of {} left on the source",
+                    report.from,
+                    pos.size - report.closed,
+                    pos.size
+                ));
+            } else {
+                self.shadow_state
+                    .write()
+                    .set_position_venue(&report.symbol, &report.to);
+            }
+            report.outcome = TransferOutcome::Moved;
+            info!(
+                correlation_id = %report.correlation_id,
+                "✅ Moved {} {} from {} to {}",
+                report.symbol, report.opened, report.from, report.to
+            );
+            return Ok(report);
+        }
+
+        // Put back on the source what the target did not take
+        let shortfall = report.closed - report.opened;
+        warn!(
+            correlation_id = %report.correlation_id,
+            "Open of {} on {} short by {}, reopening on {}",
+            report.symbol, report.to, shortfall, report.from
+        );
+        let reopened = self
+            .leg(
+                &*source,
+                &report.from,
+                &pos,
+                &report,
+                open_side,
+                shortfall,
+                false,
+            )
+            .await
+            .unwrap_or_else(|e| {
+                report_error(&mut report.errors, &report.from, e);
+                Decimal::ZERO
+            });
+        // Most of what is left lives on the source again
+        if reopened >= report.opened {
+            self.shadow_state
+                .write()
+                .set_position_venue(&report.symbol, &report.from);
+        }
+        if reopened >= shortfall {
+            report.outcome = TransferOutcome::RolledBack;
+            self.alert(
+                AlertSeverity::Warning,
+                format!(
+                    "Transfer of {} from {} to {} rolled back: {}",
+                    report.symbol,
+                    report.from,
+                    report.to,
+                    report.errors.join("; ")
+                ),
+            );
+        } else {
+            report.outcome = TransferOutcome::Stranded;
+            error!(
+                correlation_id = %report.correlation_id,
+                "🚨 Transfer of {} stranded {} of exposure",
+                report.symbol,
+                shortfall - reopened
+            );
+            self.alert(
+                AlertSeverity::Critical,
+                format!(
+                    "Transfer of {} from {} to {} lost {} of exposure: {}",
+                    report.symbol,
+                    report.from,
+                    report.to,
+                    shortfall - reopened,
+                    report.errors.join("; ")
+                ),
+            );
+        }
+        Ok(report)
+    }
+
+    /// Send one market leg on `venue`, book its fill in the shadow state and
+    /// return the size the venue confirmed filled.
+    #[allow(clippy::too_many_arguments)]
+    async fn leg(
+        &self,
+        adapter: &(dyn ExchangeAdapter + Send + Sync),
+        venue: &str,
+        pos: &Position,
+        report: &TransferReport,
+        side: Side,
+        quantity: Decimal,
+        reduce_only: bool,
+    ) -> Result<Decimal, String> {
+        let client_order_id = self
+            .ctx
+            .client_order_ids
+            .generate(
+                &correlation_prefix("mv", &report.correlation_id),
+                DEFAULT_MAX_LEN,
+            )
+            .map_err(|e| e.to_string())?;
+        let order = OrderRequest {
+            symbol: report.symbol.replace("/", ""),
+            side: side.clone(),
+            order_type: OrderType::Market,
+            quantity,
+            price: None,
+            stop_price: None,
+            client_order_id: client_order_id.clone(),
+            reduce_only,
+            correlation_id: Some(report.correlation_id.clone()),
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
+        };
+        let intent = self.leg_intent(
+            pos,
+            report,
+            venue,
+            &client_order_id,
+            side,
+            quantity,
+            reduce_only,
+        );
+        let signal_id = intent.signal_id.clone();
+        self.shadow_state.write().process_intent(intent);
+
+        let response = match adapter.place_order(order).await {
+            Ok(response) => response,
+            Err(e) => {
+                self.shadow_state
+                    .write()
+                    .reject_intent(&signal_id, e.to_string());
+                return Err(e.to_string());
+            }
+        };
+        let filled = filled_qty(&response, quantity);
+        let mut state = self.shadow_state.write();
+        state.record_child_order(
+            &signal_id,
+            venue.to_string(),
+            client_order_id,
+            response.order_id.clone(),
+            quantity,
+        );
+        if filled.is_zero() {
+            let reason = format!(
+                "order {} not filled ({})",
+                response.order_id, response.status
+            );
+            state.reject_intent(&signal_id, reason.clone());
+            return Err(reason);
+        }
+        // Market orders may come back without an average price
+        let fill_price = response
+            .avg_price
+            .or(pos.last_mark_price)
+            .unwrap_or(pos.entry_price);
+        state.confirm_execution(
+            &signal_id,
+            &response.order_id,
+            fill_price,
+            filled,
+            true,
+            response.fee.unwrap_or(Decimal::ZERO),
+            response.fee_asset.clone().unwrap_or("USDT".to_string()),
+            venue,
+        );
+        if filled < quantity {
+            state.complete_partial_intent(&signal_id, "Transfer leg filled short".to_string());
+        }
+        Ok(filled)
+    }
+
+    /// Intent a transfer leg is booked under: a close of the moved position,
+    /// or an open carrying its stop and targets.
+    #[allow(clippy::too_many_arguments)]
+    fn leg_intent(
+        &self,
+        pos: &Position,
+        report: &TransferReport,
+        venue: &str,
+        client_order_id: &str,
+        side: Side,
+        quantity: Decimal,
+        reduce_only: bool,
+    ) -> Intent {
+        let (intent_type, direction) = match (reduce_only, side) {
+            (true, _) => (IntentType::Close, 0),
+            (false, Side::Buy | Side::Long) => (IntentType::BuySetup, 1),
+            (false, Side::Sell | Side::Short) => (IntentType::SellSetup, -1),
+        };
+        Intent {
+            // Unique per leg: a terminal leg must not block the next one
+            signal_id: format!("transfer-{}-{}", pos.symbol, client_order_id),
+            source: Some("transfer".to_string()),
+            symbol: pos.symbol.clone(),
+            direction,
+            intent_type,
+            entry_zone: vec![],
+            stop_loss: pos.stop_loss,
+            take_profits: pos.take_profits.clone(),
+            size: quantity,
+            status: IntentStatus::Validated,
+            filled_size: Decimal::ZERO,
+            child_fills: vec![],
+            ttl_ms: None,
+            partition_key: None,
+            causation_id: Some(report.correlation_id.clone()),
+            env: None,
+            subject: None,
+            t_signal: self.ctx.time.now_millis(),
+            t_analysis: None,
+            t_decision: None,
+            t_ingress: None,
+            t_exchange: None,
+            max_slippage_bps: None,
+            rejection_reason: None,
+            regime_state: pos.regime_state,
+            phase: pos.phase,
+            metadata: None,
+            exchange: Some(venue.to_string()),
+            policy_hash: None,
+            position_mode: None,
+        }
+    }
+
+    fn alert(&self, severity: AlertSeverity, message: String) {
+        if let Some(sink) = &self.alert_sink {
+            sink.send(Alert::new(severity, KIND_POSITION_TRANSFER, message));
+        }
+    }
+}
+
+fn report_error(errors: &mut Vec<String>, venue: &str, e: String) {
+    error!("❌ Transfer leg on {} failed: {}", venue, e);
+    errors.push(format!("{}: {}", venue, e));
+}
+
+/// Size a market order response confirms filled. Venues that acknowledge a
+/// fill without a quantity are taken to have filled all of `requested`.
+fn filled_qty(response: &OrderResponse, requested: Decimal) -> Decimal {
+    match response.status {
+        OrderStatus::Filled if response.executed_qty.is_zero() => requested,
+        OrderStatus::Filled | OrderStatus::PartiallyFilled => response.executed_qty.min(requested),
+        _ => Decimal::ZERO,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::adapter::ExchangeError;
+    use crate::model::Position;
+    use crate::persistence::redb_store::RedbStore;
+    use crate::persistence::store::PersistenceStore;
+    use crate::persistence::wal::WalManager;
+    use crate::test_support::{CapturingAlertSink, MockAdapter};
+    use rust_decimal_macros::dec;
+
+    fn setup(
+        target: Arc<MockAdapter>,
+    ) -> (
+        PositionTransfer,
+        Arc<MockAdapter>,
+        Arc<RwLock<ShadowState>>,
+        Arc<CapturingAlertSink>,
+        String,
+    ) {
+        let path = format!("/tmp/test_rebalance_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let persistence = Arc::new(PersistenceStore::new(redb, wal));
+        let position: Position = serde_json::from_value(serde_json::json!({
+            "symbol": "BTC/USDT",
+            "side": "LONG",
+            "size": "0.5",
+            "entry_price": "50000",
+            "stop_loss": "0",
+            "take_profits": [],
+            "signal_id": "sig-btc",
+            "opened_at": chrono::Utc::now(),
+            "regime_state": null,
+            "phase": null,
+            "metadata": null,
+            "exchange": "binance",
+        }))
+        .unwrap();
+        persistence.save_position(&position).unwrap();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let state = Arc::new(RwLock::new(ShadowState::new(
+            persistence,
+            ctx.clone(),
+            Some(10_000.0),
+        )));
+
+        let source = Arc::new(MockAdapter::new("venue"));
+        let router = Arc::new(ExecutionRouter::new());
+        router.register("binance", source.clone());
+        router.register("bybit", target);
+        let sink = Arc::new(CapturingAlertSink::default());
+        let transfer =
+            PositionTransfer::new(router, state.clone(), ctx).with_alert_sink(sink.clone());
+        (transfer, source, state, sink, path)
+    }
+
+    #[tokio::test]
+    async fn test_transfer_closes_then_opens_under_one_correlation_id() {
+        let target = Arc::new(MockAdapter::new("venue").with_fill_price(dec!(51000)));
+        let (transfer, source, state, sink, path) = setup(target.clone());
+
+        let report = transfer.transfer("BTC/USDT", "Bybit").await.unwrap();
+        assert_eq!(report.outcome, TransferOutcome::Moved);
+        assert_eq!(report.closed, dec!(0.5));
+        assert_eq!(report.opened, dec!(0.5));
+        assert!(report.errors.is_empty());
+
+        let closed = source.placed.lock().clone();
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].side, Side::Sell);
+        assert!(closed[0].reduce_only);
+        let opened = target.placed.lock().clone();
+        assert_eq!(opened.len(), 1);
+        assert_eq!(opened[0].side, Side::Buy);
+        assert_eq!(opened[0].quantity, dec!(0.5));
+        assert!(!opened[0].reduce_only);
+        assert_eq!(
+            closed[0].correlation_id.as_deref(),
+            Some(report.correlation_id.as_str())
+        );
+        assert_eq!(closed[0].correlation_id, opened[0].correlation_id);
+
+        // The book follows the fills: the source leg is closed out and the
+        // target leg carries its own entry
+        let pos = state.read().get_position("BTC/USDT").cloned().unwrap();
+        assert_eq!(pos.exchange.as_deref(), Some("bybit"));
+        assert_eq!(pos.size, dec!(0.5));
+        assert_eq!(pos.entry_price, dec!(51000));
+        let trades = state.read().get_trade_history().clone();
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].size, dec!(0.5));
+        assert_eq!(trades[0].exit_price, dec!(50000));
+        assert!(sink.0.lock().is_empty());
+
+        // Already there: refused without touching either venue
+        assert_eq!(
+            transfer.transfer("BTC_USDT", "bybit").await,
+            Err(TransferError::SameVenue {
+                symbol: "BTC/USDT".to_string(),
+                venue: "bybit".to_string(),
+            })
+        );
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_failed_open_reopens_on_source_and_alerts() {
+        let target = Arc::new(
+            MockAdapter::new("venue")
+                .failing(|| ExchangeError::OrderRejected("venue degraded".to_string())),
+        );
+        let (transfer, source, state, sink, path) = setup(target.clone());
+
+        let report = transfer.transfer("BTC/USDT", "bybit").await.unwrap();
+        assert_eq!(report.outcome, TransferOutcome::RolledBack);
+        assert_eq!(report.closed, dec!(0.5));
+        assert_eq!(report.opened, Decimal::ZERO);
+        assert_eq!(report.errors.len(), 1);
+        assert!(report.errors[0].starts_with("bybit:"));
+
+        // Closed, then reopened the same way on the source
+        let placed = source.placed.lock().clone();
+        assert_eq!(placed.len(), 2);
+        assert_eq!(placed[0].side, Side::Sell);
+        assert_eq!(placed[1].side, Side::Buy);
+        assert_eq!(placed[1].quantity, dec!(0.5));
+        assert!(!placed[1].reduce_only);
+        assert_eq!(placed[1].correlation_id, placed[0].correlation_id);
+
+        let pos = state.read().get_position("BTC/USDT").cloned().unwrap();
+        assert_eq!(pos.exchange.as_deref(), Some("binance"));
+        assert_eq!(pos.size, dec!(0.5));
+        let alerts = sink.0.lock().clone();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].kind, KIND_POSITION_TRANSFER);
+        assert_eq!(alerts[0].severity, AlertSeverity::Warning);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+}
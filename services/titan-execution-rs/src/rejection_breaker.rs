@@ -0,0 +1,189 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use tracing::{error, info};
+
+use crate::alerts::{Alert, AlertSink, KIND_REJECTION_RATE};
+use crate::config::RejectionBreakerConfig;
+
+struct BreakerState {
+    /// (time ms, rejected) of the intents still inside the window
+    outcomes: VecDeque<(i64, bool)>,
+    tripped: bool,
+    paused_until: Option<i64>,
+}
+
+/// Watches the share of intents rejected at intake (signature, policy hash,
+/// validation, pipeline). A spike usually means Brain and execution disagree
+/// on config, so past the threshold it alerts and can pause new opens rather
+/// than drain a flood of intents that will all be rejected.
+pub struct RejectionRateBreaker {
+    config: RejectionBreakerConfig,
+    state: Mutex<BreakerState>,
+    alert_sink: Option<Arc<dyn AlertSink>>,
+}
+
+impl RejectionRateBreaker {
+    pub fn new(config: RejectionBreakerConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(BreakerState {
+                outcomes: VecDeque::new(),
+                tripped: false,
+                paused_until: None,
+            }),
+            alert_sink: None,
+        }
+    }
+
+    pub fn with_alert_sink(mut self, sink: Arc<dyn AlertSink>) -> Self {
+        self.alert_sink = Some(sink);
+        self
+    }
+
+    /// Count one intent outcome. Returns true if it tripped the breaker.
+    pub fn record(&self, now_ms: i64, rejected: bool) -> bool {
+        if !self.config.enabled {
+            return false;
+        }
+
+        let mut state = self.state.lock();
+        state.outcomes.push_back((now_ms, rejected));
+        while state
+            .outcomes
+            .front()
+            .is_some_and(|(t, _)| now_ms - *t > self.config.window_ms)
+        {
+            state.outcomes.pop_front();
+        }
+
+        let total = state.outcomes.len();
+        let rejections = state.outcomes.iter().filter(|(_, r)| *r).count();
+        let rate = rejections as f64 / total as f64;
+        let over = total >= self.config.min_samples && rate > self.config.max_rejection_rate;
+
+        if !over {
+            if state.tripped && state.paused_until.is_none() {
+                info!(
+                    "✅ Rejection rate back to {:.0}%, breaker re-armed",
+                    rate * 100.0
+                );
+                state.tripped = false;
+            }
+            return false;
+        }
+        if state.tripped {
+            return false;
+        }
+
+        state.tripped = true;
+        let mut message = format!(
+            "{} of {} intents rejected within {}ms ({:.0}% > {:.0}%); check Brain config and policy hash",
+            rejections,
+            total,
+            self.config.window_ms,
+            rate * 100.0,
+            self.config.max_rejection_rate * 100.0
+        );
+        if self.config.pause_consumer {
+            state.paused_until = Some(now_ms + self.config.pause_ms);
+            message.push_str(&format!(
+                "; new opens paused for {}ms",
+                self.config.pause_ms
+            ));
+        }
+        drop(state);
+
+        error!("🚨 REJECTION RATE BREAKER: {}", message);
+        if let Some(sink) = &self.alert_sink {
+            sink.send(Alert::critical(KIND_REJECTION_RATE, message));
+        }
+        true
+    }
+
+    /// Whether new opens should be left unacked. The pause ends on its own
+    /// after `pause_ms`, starting a fresh window.
+    pub fn is_paused(&self, now_ms: i64) -> bool {
+        let mut state = self.state.lock();
+        match state.paused_until {
+            Some(until) if now_ms < until => true,
+            Some(_) => {
+                info!("▶️ Rejection-rate pause over, resuming intake");
+                state.paused_until = None;
+                state.tripped = false;
+                state.outcomes.clear();
+                false
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::AlertSeverity;
+    use crate::test_support::CapturingAlertSink;
+
+    fn config(pause_consumer: bool) -> RejectionBreakerConfig {
+        RejectionBreakerConfig {
+            enabled: true,
+            window_ms: 10_000,
+            max_rejection_rate: 0.5,
+            min_samples: 10,
+            pause_consumer,
+            pause_ms: 30_000,
+        }
+    }
+
+    #[test]
+    fn test_rejection_burst_alerts_and_pauses_intake() {
+        let sink = Arc::new(CapturingAlertSink::default());
+        let breaker = RejectionRateBreaker::new(config(true)).with_alert_sink(sink.clone());
+
+        // Healthy traffic with the odd rejection
+        for i in 0..10 {
+            assert!(!breaker.record(i * 100, i % 4 == 0));
+        }
+        assert!(!breaker.is_paused(1_000));
+
+        // A burst of rejections (e.g. stale policy hash) pushes the rate past 50%
+        let tripped: Vec<bool> = (0..10).map(|i| breaker.record(2_000 + i, true)).collect();
+        assert_eq!(tripped.iter().filter(|t| **t).count(), 1, "Trips once");
+        assert!(breaker.is_paused(2_010));
+
+        let alerts = sink.0.lock();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].severity, AlertSeverity::Critical);
+        assert_eq!(alerts[0].kind, KIND_REJECTION_RATE);
+        assert!(alerts[0].message.contains("paused"));
+        drop(alerts);
+
+        // The pause lifts on its own and starts a fresh window
+        assert!(breaker.is_paused(32_000));
+        assert!(!breaker.is_paused(32_010));
+        assert!(!breaker.record(32_020, true), "Below min_samples");
+    }
+
+    #[test]
+    fn test_alert_only_breaker_never_pauses_and_rearms() {
+        let sink = Arc::new(CapturingAlertSink::default());
+        let breaker = RejectionRateBreaker::new(config(false)).with_alert_sink(sink.clone());
+
+        for i in 0..10 {
+            breaker.record(i, true);
+        }
+        assert!(!breaker.is_paused(10));
+        assert_eq!(sink.0.lock().len(), 1);
+
+        // Once the window rolls past the burst the breaker can trip again
+        assert!(!breaker.record(20_000, false));
+        assert!((0..10).any(|i| breaker.record(21_000 + i, true)));
+        assert_eq!(sink.0.lock().len(), 2);
+
+        // Disabled: nothing is counted
+        let off = RejectionRateBreaker::new(RejectionBreakerConfig::default());
+        assert!((0..100).all(|i| !off.record(i, true)));
+    }
+}
@@ -66,7 +66,7 @@ impl ReplayEngine {
                 }
                 ReplayEvent::RiskPolicy { policy, .. } => {
                     info!("🛡️ Updating Risk Policy");
-                    self.risk_guard.update_policy(policy);
+                    self.risk_guard.update_policy(*policy);
                 }
                 ReplayEvent::Signal(intent) => {
                     info!("📶 Processing Signal: {}", intent.signal_id);
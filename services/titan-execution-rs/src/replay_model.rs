@@ -13,7 +13,7 @@ pub enum ReplayEvent {
     Signal(Box<Intent>),
 
     /// Risk Policy Update -> Updates RiskGuard
-    RiskPolicy { policy: RiskPolicy, ts: i64 },
+    RiskPolicy { policy: Box<RiskPolicy>, ts: i64 },
 
     /// Time advancement (optional, explicit tick)
     Tick { timestamp: i64 },
@@ -0,0 +1,526 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::RwLock;
+use rust_decimal::prelude::*;
+use rust_decimal::Decimal;
+use tracing::{info, warn};
+
+use crate::client_order_id::max_len_for;
+use crate::config::RepricingConfig;
+use crate::context::ExecutionContext;
+use crate::exchange::adapter::{
+    ExchangeAdapter, ExchangeError, OrderRequest, OrderStatus, SwapMode,
+};
+use crate::market_data::engine::MarketDataEngine;
+use crate::market_data::types::BookTicker;
+use crate::model::{OrderType, Side};
+use crate::order_manager::{book_imbalance, IMBALANCE_THRESHOLD_BUY, IMBALANCE_THRESHOLD_SELL};
+use crate::shadow_state::ShadowState;
+
+/// A limit order resting on a venue that the repricer keeps pegged to the touch.
+#[derive(Debug, Clone)]
+pub struct RestingOrder {
+    pub signal_id: String,
+    pub exchange: String,
+    pub symbol: String,
+    pub side: Side,
+    pub quantity: Decimal,
+    pub price: Decimal,
+    pub reduce_only: bool,
+    pub client_order_id: String,
+    pub order_id: String,
+    pub placed_at_ms: i64,
+    /// Mid at the time of the last (re)peg; drift is measured from here
+    pub anchor_mid: Option<Decimal>,
+    pub repegs: u32,
+    pub correlation_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RepriceOutcome {
+    Hold,
+    Repegged(Decimal),
+    MaxRepegs,
+    Expired,
+    /// Amend / replace failed (typically the order already filled or was cancelled)
+    Failed(String),
+}
+
+impl RepriceOutcome {
+    pub fn is_terminal(&self) -> bool {
+        !matches!(self, RepriceOutcome::Hold | RepriceOutcome::Repegged(_))
+    }
+}
+
+fn mid(ticker: &BookTicker) -> Option<Decimal> {
+    let mid = (ticker.best_bid + ticker.best_ask) / Decimal::from(2);
+    (mid > Decimal::ZERO).then_some(mid)
+}
+
+fn is_buy(side: &Side) -> bool {
+    matches!(side, Side::Buy | Side::Long)
+}
+
+/// Repegs resting maker orders when the mid drifts away from them, instead of
+/// waiting out the chase timeout and converting to taker.
+pub struct LimitRepricer {
+    market_data: Arc<MarketDataEngine>,
+    ctx: Arc<ExecutionContext>,
+    config: RepricingConfig,
+    threshold_bps: Decimal,
+    min_improvement_bps: Decimal,
+    shadow_state: Option<Arc<RwLock<ShadowState>>>,
+}
+
+impl LimitRepricer {
+    pub fn new(
+        market_data: Arc<MarketDataEngine>,
+        ctx: Arc<ExecutionContext>,
+        config: RepricingConfig,
+    ) -> Self {
+        let threshold_bps = Decimal::from_f64(config.threshold_bps).unwrap_or(Decimal::from(5));
+        let min_improvement_bps =
+            Decimal::from_f64(config.min_improvement_bps).unwrap_or(Decimal::ONE);
+        Self {
+            market_data,
+            ctx,
+            config,
+            threshold_bps,
+            min_improvement_bps,
+            shadow_state: None,
+        }
+    }
+
+    /// Record replacement child orders (venues without amend) in ShadowState.
+    pub fn with_shadow_state(mut self, shadow_state: Arc<RwLock<ShadowState>>) -> Self {
+        self.shadow_state = Some(shadow_state);
+        self
+    }
+
+    /// Start tracking an acknowledged limit order, anchored at the current mid.
+    pub fn track(
+        &self,
+        signal_id: &str,
+        exchange: &str,
+        request: &OrderRequest,
+        price: Decimal,
+        order_id: &str,
+    ) -> RestingOrder {
+        let anchor_mid = self.ticker(exchange, &request.symbol).and_then(|t| mid(&t));
+        RestingOrder {
+            signal_id: signal_id.to_string(),
+            exchange: exchange.to_string(),
+            symbol: request.symbol.clone(),
+            side: request.side.clone(),
+            quantity: request.quantity,
+            price,
+            reduce_only: request.reduce_only,
+            client_order_id: request.client_order_id.clone(),
+            order_id: order_id.to_string(),
+            placed_at_ms: self.ctx.time.now_millis(),
+            anchor_mid,
+            repegs: 0,
+            correlation_id: request.correlation_id.clone(),
+        }
+    }
+
+    fn ticker(&self, exchange: &str, symbol: &str) -> Option<BookTicker> {
+        self.market_data
+            .get_venue_ticker(exchange, symbol)
+            .or_else(|| self.market_data.get_ticker(symbol))
+    }
+
+    /// New peg price if the mid has drifted far enough from the anchor and the
+    /// order's own price would move by at least the minimum improvement.
+    /// Imbalance pressing against the order (bids stacking while we buy, asks while
+    /// we sell) halves the threshold so we follow a trending book sooner.
+    pub fn repeg_price(&self, order: &RestingOrder, ticker: &BookTicker) -> Option<Decimal> {
+        let mid = mid(ticker)?;
+        let anchor = order.anchor_mid?;
+        if anchor.is_zero() {
+            return None;
+        }
+        let drift_bps = ((mid - anchor).abs() / anchor) * Decimal::from(10000);
+
+        let imbalance = book_imbalance(ticker);
+        let adverse_pressure = if is_buy(&order.side) {
+            imbalance > Decimal::from_str(IMBALANCE_THRESHOLD_BUY).unwrap_or(Decimal::new(6, 1))
+        } else {
+            imbalance < Decimal::from_str(IMBALANCE_THRESHOLD_SELL).unwrap_or(Decimal::new(-6, 1))
+        };
+        let threshold = if adverse_pressure {
+            self.threshold_bps / Decimal::from(2)
+        } else {
+            self.threshold_bps
+        };
+        if drift_bps < threshold {
+            return None;
+        }
+
+        let peg = if is_buy(&order.side) {
+            ticker.best_bid
+        } else {
+            ticker.best_ask
+        };
+        if peg <= Decimal::ZERO || peg == order.price {
+            return None;
+        }
+        // Mid drift can leave our side of the book nearly where it was: an amend
+        // for a tick or two costs an API call and queue position for no gain
+        if order.price > Decimal::ZERO {
+            let move_bps = ((peg - order.price).abs() / order.price) * Decimal::from(10000);
+            if move_bps < self.min_improvement_bps {
+                crate::metrics::inc_repegs_skipped();
+                return None;
+            }
+        }
+        Some(peg)
+    }
+
+    /// One evaluation of a resting order against the current book.
+    pub async fn tick(
+        &self,
+        adapter: &(dyn ExchangeAdapter + Send + Sync),
+        order: &mut RestingOrder,
+    ) -> RepriceOutcome {
+        if self.ctx.time.now_millis() - order.placed_at_ms >= self.config.ttl_ms {
+            return RepriceOutcome::Expired;
+        }
+        if order.repegs >= self.config.max_repegs {
+            return RepriceOutcome::MaxRepegs;
+        }
+
+        let Some(ticker) = self.ticker(&order.exchange, &order.symbol) else {
+            return RepriceOutcome::Hold;
+        };
+        if order.anchor_mid.is_none() {
+            // No book at placement: anchor on the first one we see
+            order.anchor_mid = mid(&ticker);
+            return RepriceOutcome::Hold;
+        }
+        let Some(price) = self.repeg_price(order, &ticker) else {
+            return RepriceOutcome::Hold;
+        };
+
+        if let Err(e) = self.repeg(adapter, order, price).await {
+            warn!(
+                "⚠️ Repeg {} {} -> {} failed: {}",
+                order.symbol, order.order_id, price, e
+            );
+            return RepriceOutcome::Failed(e.to_string());
+        }
+
+        info!(
+            "🎯 Repegged {} {} {} -> {} (repeg {}/{})",
+            order.exchange,
+            order.symbol,
+            order.price,
+            price,
+            order.repegs + 1,
+            self.config.max_repegs
+        );
+        order.price = price;
+        order.anchor_mid = mid(&ticker);
+        order.repegs += 1;
+        crate::metrics::inc_order_repegs();
+        RepriceOutcome::Repegged(price)
+    }
+
+    /// Amend in place; venues without amend get a cancel + replace. Only a
+    /// confirmed cancel is replaced, and only for what it left unexecuted.
+    async fn repeg(
+        &self,
+        adapter: &(dyn ExchangeAdapter + Send + Sync),
+        order: &mut RestingOrder,
+        price: Decimal,
+    ) -> Result<(), ExchangeError> {
+        match adapter
+            .amend_order(&order.symbol, &order.order_id, price)
+            .await
+        {
+            Ok(resp) => {
+                order.order_id = resp.order_id;
+                return Ok(());
+            }
+            Err(ExchangeError::NotImplemented(_)) => {}
+            Err(e) => return Err(e),
+        }
+
+        let cancelled = adapter.cancel_order(&order.symbol, &order.order_id).await?;
+        if cancelled.status != OrderStatus::Cancelled {
+            return Err(ExchangeError::Api(format!(
+                "cancel of {} came back {}, not replacing",
+                order.order_id, cancelled.status
+            )));
+        }
+        let remaining = order.quantity - cancelled.executed_qty;
+        if remaining <= Decimal::ZERO {
+            return Err(ExchangeError::Api(format!(
+                "{} fully executed before the cancel, nothing to replace",
+                order.order_id
+            )));
+        }
+        let client_order_id = self
+            .ctx
+            .client_order_ids
+            .generate("rp", max_len_for(&order.exchange))
+            .map_err(|e| ExchangeError::OrderRejected(e.to_string()))?;
+        let resp = adapter
+            .place_order(OrderRequest {
+                symbol: order.symbol.clone(),
+                side: order.side.clone(),
+                order_type: OrderType::Limit,
+                quantity: remaining,
+                price: Some(price),
+                stop_price: None,
+                client_order_id: client_order_id.clone(),
+                reduce_only: order.reduce_only,
+                correlation_id: order.correlation_id.clone(),
+                swap_mode: SwapMode::ExactIn,
+                bracket: None,
+            })
+            .await?;
+
+        if let Some(shadow_state) = &self.shadow_state {
+            shadow_state.write().record_child_order(
+                &order.signal_id,
+                order.exchange.clone(),
+                client_order_id.clone(),
+                resp.order_id.clone(),
+                remaining,
+            );
+        }
+        order.quantity = remaining;
+        order.client_order_id = client_order_id;
+        order.order_id = resp.order_id;
+        Ok(())
+    }
+
+    /// Reprice until the order exhausts its repegs, hits the TTL or can no longer be
+    /// amended.
+    pub async fn run(
+        &self,
+        adapter: Arc<dyn ExchangeAdapter + Send + Sync>,
+        mut order: RestingOrder,
+    ) -> RestingOrder {
+        loop {
+            let outcome = self.tick(adapter.as_ref(), &mut order).await;
+            if outcome.is_terminal() {
+                info!(
+                    "Repricing ended for {} {}: {:?} after {} repegs",
+                    order.symbol, order.order_id, outcome, order.repegs
+                );
+                return order;
+            }
+            tokio::time::sleep(Duration::from_millis(self.config.poll_interval_ms)).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::{DeterministicIdProvider, SimulatedTimeProvider};
+    use crate::test_support::MockAdapter;
+    use rust_decimal_macros::dec;
+
+    fn set_book(md: &MarketDataEngine, bid: Decimal, ask: Decimal, bid_qty: Decimal) {
+        md.update_venue_ticker(
+            "binance",
+            BookTicker {
+                symbol: "BTCUSDT".to_string(),
+                best_bid: bid,
+                best_bid_qty: bid_qty,
+                best_ask: ask,
+                best_ask_qty: dec!(1),
+                transaction_time: 0,
+                event_time: 0,
+            },
+        );
+    }
+
+    fn setup(
+        max_repegs: u32,
+    ) -> (
+        LimitRepricer,
+        Arc<MarketDataEngine>,
+        Arc<SimulatedTimeProvider>,
+        RestingOrder,
+    ) {
+        let time = Arc::new(SimulatedTimeProvider::new(0));
+        let ctx = Arc::new(ExecutionContext::from_providers(
+            time.clone(),
+            Arc::new(DeterministicIdProvider::new()),
+        ));
+        let md = Arc::new(MarketDataEngine::new(None));
+        set_book(&md, dec!(100.00), dec!(100.02), dec!(1));
+
+        let repricer = LimitRepricer::new(
+            md.clone(),
+            ctx,
+            RepricingConfig {
+                enabled: true,
+                threshold_bps: 5.0,
+                max_repegs,
+                ttl_ms: 10_000,
+                poll_interval_ms: 1,
+                min_improvement_bps: 2.0,
+            },
+        );
+        let request = OrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            side: Side::Buy,
+            order_type: OrderType::Limit,
+            quantity: dec!(1),
+            price: Some(dec!(100.00)),
+            stop_price: None,
+            client_order_id: "tx-1".to_string(),
+            reduce_only: false,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
+        };
+        let order = repricer.track("sig-1", "binance", &request, dec!(100.00), "oid-1");
+        (repricer, md, time, order)
+    }
+
+    #[tokio::test]
+    async fn test_mid_drift_repegs_up_to_max() {
+        let (repricer, md, _time, mut order) = setup(2);
+        let adapter = MockAdapter::new("binance").resting().with_amend();
+
+        // 2 bps drift: below threshold
+        set_book(&md, dec!(100.02), dec!(100.04), dec!(1));
+        assert_eq!(
+            repricer.tick(&adapter, &mut order).await,
+            RepriceOutcome::Hold
+        );
+
+        // Trend up in 10 bps steps: each step repegs to the new bid
+        for (i, bid) in [dec!(100.10), dec!(100.20), dec!(100.30)]
+            .into_iter()
+            .enumerate()
+        {
+            set_book(&md, bid, bid + dec!(0.02), dec!(1));
+            let outcome = repricer.tick(&adapter, &mut order).await;
+            if i < 2 {
+                assert_eq!(outcome, RepriceOutcome::Repegged(bid));
+            } else {
+                assert_eq!(outcome, RepriceOutcome::MaxRepegs);
+            }
+        }
+
+        assert_eq!(order.repegs, 2);
+        assert_eq!(order.price, dec!(100.20));
+        assert_eq!(*adapter.amended.lock(), vec![dec!(100.10), dec!(100.20)]);
+    }
+
+    #[tokio::test]
+    async fn test_adverse_imbalance_lowers_threshold() {
+        let (repricer, md, _time, mut order) = setup(5);
+        let adapter = MockAdapter::new("binance").resting().with_amend();
+
+        // 3 bps drift with balanced book: hold
+        set_book(&md, dec!(100.03), dec!(100.05), dec!(1));
+        assert_eq!(
+            repricer.tick(&adapter, &mut order).await,
+            RepriceOutcome::Hold
+        );
+
+        // Same drift with bids stacking (imbalance 0.8): follow the book
+        set_book(&md, dec!(100.03), dec!(100.05), dec!(9));
+        assert_eq!(
+            repricer.tick(&adapter, &mut order).await,
+            RepriceOutcome::Repegged(dec!(100.03))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sub_threshold_price_move_skips_amend() {
+        let (repricer, md, _time, mut order) = setup(5);
+        let adapter = MockAdapter::new("binance").resting().with_amend();
+
+        // Ask lifts the mid 6 bps but the bid only moves 1 bp: not worth an amend
+        set_book(&md, dec!(100.01), dec!(100.13), dec!(1));
+        assert_eq!(
+            repricer.tick(&adapter, &mut order).await,
+            RepriceOutcome::Hold
+        );
+        assert!(adapter.amended.lock().is_empty());
+        assert_eq!(order.price, dec!(100.00));
+        assert_eq!(order.repegs, 0);
+
+        // Bid follows 5 bps: meaningful improvement, amend
+        set_book(&md, dec!(100.05), dec!(100.13), dec!(1));
+        assert_eq!(
+            repricer.tick(&adapter, &mut order).await,
+            RepriceOutcome::Repegged(dec!(100.05))
+        );
+        assert_eq!(*adapter.amended.lock(), vec![dec!(100.05)]);
+    }
+
+    #[tokio::test]
+    async fn test_ttl_stops_repricing_and_replace_fallback() {
+        let (repricer, md, time, order) = setup(10);
+        let adapter = Arc::new(MockAdapter::new("binance").resting());
+
+        set_book(&md, dec!(100.10), dec!(100.12), dec!(1));
+        let mut order = order;
+        assert_eq!(
+            repricer.tick(adapter.as_ref(), &mut order).await,
+            RepriceOutcome::Repegged(dec!(100.10))
+        );
+        // No amend support: cancel + re-place under a fresh client id
+        assert_eq!(adapter.cancelled_ids(), vec!["oid-1".to_string()]);
+        assert_eq!(adapter.placed.lock()[0].price, Some(dec!(100.10)));
+        assert_ne!(order.client_order_id, "tx-1");
+        assert_eq!(order.order_id, format!("order-{}", order.client_order_id));
+
+        time.advance(10_000);
+        set_book(&md, dec!(100.50), dec!(100.52), dec!(1));
+        let order = repricer.run(adapter.clone(), order).await;
+        assert_eq!(order.repegs, 1);
+        assert_eq!(adapter.placed.lock().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_replace_covers_only_unexecuted_remainder() {
+        let (repricer, md, _time, mut order) = setup(10);
+        let adapter = MockAdapter::new("binance")
+            .resting()
+            .with_cancel_fills(dec!(0.4));
+
+        set_book(&md, dec!(100.10), dec!(100.12), dec!(1));
+        assert_eq!(
+            repricer.tick(&adapter, &mut order).await,
+            RepriceOutcome::Repegged(dec!(100.10))
+        );
+        // 0.4 executed before the cancel landed: only 0.6 goes back out
+        assert_eq!(adapter.placed()[0].quantity, dec!(0.6));
+        assert_eq!(order.quantity, dec!(0.6));
+    }
+
+    #[tokio::test]
+    async fn test_failed_or_filled_cancel_is_not_replaced() {
+        let (repricer, md, _time, mut order) = setup(10);
+        set_book(&md, dec!(100.10), dec!(100.12), dec!(1));
+
+        let refusing = MockAdapter::new("binance").resting().failing_cancels();
+        assert!(matches!(
+            repricer.tick(&refusing, &mut order).await,
+            RepriceOutcome::Failed(_)
+        ));
+        assert!(refusing.placed().is_empty());
+
+        let filled = MockAdapter::new("binance")
+            .resting()
+            .with_cancel_fills(dec!(1));
+        assert!(matches!(
+            repricer.tick(&filled, &mut order).await,
+            RepriceOutcome::Failed(msg) if msg.contains("fully executed")
+        ));
+        assert!(filled.placed().is_empty());
+        assert_eq!(order.order_id, "oid-1");
+    }
+}
@@ -1,19 +1,35 @@
+use crate::alerts::{Alert, AlertSeverity, AlertSink, KIND_BREAKER_TRIPPED};
+use crate::config::HeartbeatLossConfig;
+use crate::context::TimeProvider;
+use crate::depth_gate::DepthGate;
 use crate::execution_constraints::{ConstraintsStore, PolicyMode, RiskMode};
-use crate::model::Intent;
-use crate::risk_policy::RiskPolicy;
+use crate::funding_gate::FundingGate;
+use crate::heartbeat::{HeartbeatMonitor, HeartbeatStatus};
+use crate::intent_validation::validate_intent_payload;
+use crate::market_data::model::{FundingRate, OrderBookL2};
+use crate::model::{FeeAnalysis, Intent, IntentType, Position, Side, TradeRecord};
+use crate::position_sizing::risk_budget_size;
 use crate::risk_policy::RiskState;
+use crate::risk_policy::{LeverageMode, RiskPolicy};
 
 use crate::risk_state_manager::RiskStateManager;
 use crate::shadow_state::ShadowState;
+use crate::spread_gate::SpreadGate;
 use crate::staleness::StalenessMonitor;
-use parking_lot::RwLock;
+use crate::volatility::VolatilityTracker;
+use parking_lot::{Mutex, RwLock};
 use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 use tracing::{info, warn};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum RiskRejectionReason {
     SymbolNotWhitelisted(String),
+    SymbolNotWhitelistedOnVenue {
+        symbol: String,
+        venue: String,
+    },
     MaxPositionNotionalExceeded {
         symbol: String,
         current: Decimal,
@@ -25,15 +41,41 @@ pub enum RiskRejectionReason {
         current: usize,
         limit: usize,
     },
+    MaxOpenPositionsExceeded {
+        symbol: String,
+        current: usize,
+        limit: usize,
+    },
     DailyLossLimitExceeded {
         current_loss: Decimal,
         limit: Decimal,
     },
+    NotionalVelocityExceeded {
+        opened: Decimal,
+        additional: Decimal,
+        limit: Decimal,
+        window_ms: i64,
+    },
     MaxAccountLeverageExceeded {
         current: Decimal,
         limit: Decimal,
     },
+    GroupNotionalExceeded {
+        group: String,
+        /// Group notional including the intent
+        current: Decimal,
+        limit: Decimal,
+    },
     InvalidSize,
+    BelowMinIntentNotional {
+        symbol: String,
+        notional: Decimal,
+        limit: Decimal,
+    },
+    InsufficientAvailableCash {
+        required: Decimal,
+        available: Decimal,
+    },
 
     PolicyMissing,
     PolicyHashMismatch {
@@ -41,6 +83,39 @@ pub enum RiskRejectionReason {
         actual: String,
     },
     MarketDataStale(String),
+    AdverseFundingImminent {
+        symbol: String,
+        rate_bps: Decimal,
+        seconds_to_funding: i64,
+    },
+    InsufficientBookDepth {
+        symbol: String,
+        required: Decimal,
+        available: Decimal,
+    },
+    SpreadTooWide {
+        symbol: String,
+        spread_bps: Decimal,
+        max_bps: Decimal,
+    },
+    VolatilityReduceOnly {
+        symbol: String,
+    },
+    InsufficientEdgeAfterFees {
+        symbol: String,
+        expected_pct: Decimal,
+        after_fees_pct: Decimal,
+        min_pct: Decimal,
+    },
+    /// Risk-budget sizing needs a usable entry and stop
+    RiskSizingFailed {
+        symbol: String,
+        reason: String,
+    },
+    OutsideTradingSession {
+        symbol: String,
+        reason: String,
+    },
 
     // Execution Constraints Violations (PowerLaw)
     ConstraintMaxOrderNotionalExceeded {
@@ -57,12 +132,60 @@ pub enum RiskRejectionReason {
     },
 }
 
+impl RiskRejectionReason {
+    /// Stable machine-readable code, used in RPC replies.
+    pub fn code(&self) -> &'static str {
+        match self {
+            RiskRejectionReason::SymbolNotWhitelisted(_) => "SYMBOL_NOT_WHITELISTED",
+            RiskRejectionReason::SymbolNotWhitelistedOnVenue { .. } => {
+                "SYMBOL_NOT_WHITELISTED_ON_VENUE"
+            }
+            RiskRejectionReason::MaxPositionNotionalExceeded { .. } => {
+                "MAX_POSITION_NOTIONAL_EXCEEDED"
+            }
+            RiskRejectionReason::MaxOpenOrdersExceeded { .. } => "MAX_OPEN_ORDERS_EXCEEDED",
+            RiskRejectionReason::MaxOpenPositionsExceeded { .. } => "MAX_OPEN_POSITIONS_EXCEEDED",
+            RiskRejectionReason::DailyLossLimitExceeded { .. } => "DAILY_LOSS_LIMIT_EXCEEDED",
+            RiskRejectionReason::NotionalVelocityExceeded { .. } => "NOTIONAL_VELOCITY_EXCEEDED",
+            RiskRejectionReason::MaxAccountLeverageExceeded { .. } => {
+                "MAX_ACCOUNT_LEVERAGE_EXCEEDED"
+            }
+            RiskRejectionReason::GroupNotionalExceeded { .. } => "GROUP_NOTIONAL_EXCEEDED",
+            RiskRejectionReason::InvalidSize => "INVALID_SIZE",
+            RiskRejectionReason::BelowMinIntentNotional { .. } => "BELOW_MIN_INTENT_NOTIONAL",
+            RiskRejectionReason::InsufficientAvailableCash { .. } => "INSUFFICIENT_AVAILABLE_CASH",
+            RiskRejectionReason::PolicyMissing => "POLICY_MISSING",
+            RiskRejectionReason::PolicyHashMismatch { .. } => "POLICY_HASH_MISMATCH",
+            RiskRejectionReason::MarketDataStale(_) => "MARKET_DATA_STALE",
+            RiskRejectionReason::ConstraintMaxOrderNotionalExceeded { .. } => {
+                "CONSTRAINT_MAX_ORDER_NOTIONAL_EXCEEDED"
+            }
+            RiskRejectionReason::ConstraintReduceOnlyViolation { .. } => {
+                "CONSTRAINT_REDUCE_ONLY_VIOLATION"
+            }
+            RiskRejectionReason::ConstraintMaxLeverageExceeded { .. } => {
+                "CONSTRAINT_MAX_LEVERAGE_EXCEEDED"
+            }
+            RiskRejectionReason::AdverseFundingImminent { .. } => "ADVERSE_FUNDING_IMMINENT",
+            RiskRejectionReason::InsufficientBookDepth { .. } => "INSUFFICIENT_BOOK_DEPTH",
+            RiskRejectionReason::SpreadTooWide { .. } => "SPREAD_TOO_WIDE",
+            RiskRejectionReason::VolatilityReduceOnly { .. } => "VOLATILITY_REDUCE_ONLY",
+            RiskRejectionReason::InsufficientEdgeAfterFees { .. } => "INSUFFICIENT_EDGE_AFTER_FEES",
+            RiskRejectionReason::RiskSizingFailed { .. } => "RISK_SIZING_FAILED",
+            RiskRejectionReason::OutsideTradingSession { .. } => "OUTSIDE_TRADING_SESSION",
+        }
+    }
+}
+
 impl std::fmt::Display for RiskRejectionReason {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             RiskRejectionReason::SymbolNotWhitelisted(s) => {
                 write!(f, "Symbol '{}' not in whitelist", s)
             }
+            RiskRejectionReason::SymbolNotWhitelistedOnVenue { symbol, venue } => {
+                write!(f, "Symbol '{}' not in whitelist for {}", symbol, venue)
+            }
             RiskRejectionReason::MarketDataStale(details) => {
                 write!(f, "Market Data Stale: {}", details)
             }
@@ -85,6 +208,15 @@ impl std::fmt::Display for RiskRejectionReason {
                 "Too many open orders for {}: {} >= Limit {}",
                 symbol, current, limit
             ),
+            RiskRejectionReason::MaxOpenPositionsExceeded {
+                symbol,
+                current,
+                limit,
+            } => write!(
+                f,
+                "Too many open positions to open {}: {} >= Limit {}",
+                symbol, current, limit
+            ),
             RiskRejectionReason::DailyLossLimitExceeded {
                 current_loss,
                 limit,
@@ -93,13 +225,49 @@ impl std::fmt::Display for RiskRejectionReason {
                 "Daily loss limit hit: {:.2} <= {:.2}",
                 current_loss, limit
             ),
+            RiskRejectionReason::NotionalVelocityExceeded {
+                opened,
+                additional,
+                limit,
+                window_ms,
+            } => write!(
+                f,
+                "Notional velocity limit hit: {:.2} opened in last {}ms + {:.2} > {:.2}",
+                opened, window_ms, additional, limit
+            ),
             RiskRejectionReason::MaxAccountLeverageExceeded { current, limit } => write!(
                 f,
                 "Account Leverage Limit Exceeded: {:.2}x > {:.2}x",
                 current, limit
             ),
+            RiskRejectionReason::GroupNotionalExceeded {
+                group,
+                current,
+                limit,
+            } => write!(
+                f,
+                "Correlation group {} notional {:.2} > Limit {:.2}",
+                group, current, limit
+            ),
 
             RiskRejectionReason::InvalidSize => write!(f, "Invalid size (<= 0)"),
+            RiskRejectionReason::BelowMinIntentNotional {
+                symbol,
+                notional,
+                limit,
+            } => write!(
+                f,
+                "Intent notional {:.2} on {} below floor {:.2}",
+                notional, symbol, limit
+            ),
+            RiskRejectionReason::InsufficientAvailableCash {
+                required,
+                available,
+            } => write!(
+                f,
+                "Insufficient available cash: need {:.2}, have {:.2} after reservations",
+                required, available
+            ),
             RiskRejectionReason::PolicyMissing => write!(f, "Risk Policy not loaded"),
             RiskRejectionReason::PolicyHashMismatch { expected, actual } => write!(
                 f,
@@ -122,6 +290,54 @@ impl std::fmt::Display for RiskRejectionReason {
                     symbol
                 )
             }
+            RiskRejectionReason::AdverseFundingImminent {
+                symbol,
+                rate_bps,
+                seconds_to_funding,
+            } => write!(
+                f,
+                "Adverse funding on {}: {:.2} bps payable in {}s",
+                symbol, rate_bps, seconds_to_funding
+            ),
+            RiskRejectionReason::InsufficientBookDepth {
+                symbol,
+                required,
+                available,
+            } => write!(
+                f,
+                "Book too thin for a market order on {}: {} within band, need {}; use a limit order",
+                symbol, available, required
+            ),
+            RiskRejectionReason::SpreadTooWide {
+                symbol,
+                spread_bps,
+                max_bps,
+            } => write!(
+                f,
+                "Spread too wide for a market order on {}: {} bps > {} bps; use a limit order",
+                symbol, spread_bps, max_bps
+            ),
+            RiskRejectionReason::VolatilityReduceOnly { symbol } => write!(
+                f,
+                "{} is reduce-only under high volatility, new positions blocked",
+                symbol
+            ),
+            RiskRejectionReason::InsufficientEdgeAfterFees {
+                symbol,
+                expected_pct,
+                after_fees_pct,
+                min_pct,
+            } => write!(
+                f,
+                "Expected edge on {} of {}% leaves {}% after round-trip fees, below {}%",
+                symbol, expected_pct, after_fees_pct, min_pct
+            ),
+            RiskRejectionReason::RiskSizingFailed { symbol, reason } => {
+                write!(f, "Cannot size {} from risk budget: {}", symbol, reason)
+            }
+            RiskRejectionReason::OutsideTradingSession { symbol, reason } => {
+                write!(f, "No new positions on {}: {}", symbol, reason)
+            }
             RiskRejectionReason::ConstraintMaxLeverageExceeded { current, limit } => {
                 write!(
                     f,
@@ -133,29 +349,122 @@ impl std::fmt::Display for RiskRejectionReason {
     }
 }
 
-use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::atomic::{AtomicI64, AtomicU32, Ordering};
+
+/// Quote and contract suffixes stripped from unseparated venue symbols.
+const QUOTE_SUFFIXES: [&str; 6] = ["PERP", "USDT", "USDC", "BUSD", "USD", "EUR"];
+
+/// Base asset of a symbol, so BTC/USDT, BTC-PERP and BTCUSDT all net together.
+fn base_asset(symbol: &str) -> String {
+    let upper = symbol.to_uppercase();
+    if let Some(base) = upper
+        .split(['/', '-', '_', ':'])
+        .next()
+        .filter(|b| *b != upper)
+    {
+        return base.to_string();
+    }
+    QUOTE_SUFFIXES
+        .iter()
+        .find_map(|q| upper.strip_suffix(q).filter(|b| !b.is_empty()))
+        .unwrap_or(&upper)
+        .to_string()
+}
+
+/// Symbols added to and removed from the whitelist at runtime, replayed over
+/// every policy Brain pushes afterwards so an edit is not silently undone
+#[derive(Default)]
+struct WhitelistEdits {
+    added: HashSet<String>,
+    removed: HashSet<String>,
+}
+
+impl WhitelistEdits {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    fn apply(&self, whitelist: &mut HashSet<String>) {
+        for symbol in &self.removed {
+            whitelist.remove(symbol);
+        }
+        whitelist.extend(self.added.iter().cloned());
+    }
+}
 
 pub struct RiskGuard {
     policy: RwLock<RiskPolicy>,
     shadow_state: Arc<RwLock<ShadowState>>,
     // current_state: AtomicI64, // Removed unused field
-    last_heartbeat: AtomicI64,
+    /// Brain heartbeat tracking; shared with the loss responder
+    heartbeat: Arc<HeartbeatMonitor>,
     state_manager: RwLock<RiskStateManager>,
     staleness_monitor: RwLock<StalenessMonitor>,
     constraints_store: Option<Arc<ConstraintsStore>>,
+    funding_gate: Option<Arc<FundingGate>>,
+    depth_gate: Option<Arc<DepthGate>>,
+    spread_gate: Option<Arc<SpreadGate>>,
+    volatility: Option<Arc<VolatilityTracker>>,
+    /// Least expected profit (%) an open must keep after round-trip fees
+    min_edge_after_fees_pct: Option<Decimal>,
+    /// Quote loss at the stop that opens are sized to, overriding `intent.size`
+    risk_per_trade: Option<Decimal>,
+    /// Quantity increment per symbol that risk-budget sizes are rounded down to
+    lot_sizes: HashMap<String, Decimal>,
+    /// Venues (lowercase) trading spot: no leverage, orders hold their full notional
+    spot_venues: HashSet<String>,
+    /// Canonical policy hash, recomputed when the whitelist is edited at runtime
+    policy_hash: RwLock<String>,
+    /// Runtime whitelist edits, kept across policy updates
+    whitelist_edits: Mutex<WhitelistEdits>,
+    /// (timestamp ms, notional) of recent opens, for the velocity limit
+    opened_notional: Mutex<VecDeque<(i64, Decimal)>>,
+    /// Whether each of the last `slippage_rate_window` fills breached max_slippage_bps
+    slippage_breaches: Mutex<VecDeque<bool>>,
+    /// Losing trades in a row since the last win (or breaker trip)
+    consecutive_losses: AtomicU32,
+    /// When the current risk state was entered (ms), for the minimum dwell
+    state_entered_at: AtomicI64,
+    /// Last time a breaker condition fired (ms), for the recovery cooldown
+    last_breach_at: AtomicI64,
+    alert_sink: Option<Arc<dyn AlertSink>>,
+    /// The shadow state's clock, so tests can drive cooldowns deterministically
+    time: Arc<dyn TimeProvider>,
 }
 
 impl RiskGuard {
     pub fn new(policy: RiskPolicy, shadow_state: Arc<RwLock<ShadowState>>) -> Self {
         info!("🛡️ RiskGuard Initialized with policy: {:?}", policy);
+        shadow_state.write().set_dedup_ttl_ms(policy.dedup_ttl_ms);
+        let time = shadow_state.read().time();
         Self {
             policy: RwLock::new(policy),
             shadow_state,
             // current_state: AtomicI64::new(0),
-            last_heartbeat: AtomicI64::new(chrono::Utc::now().timestamp_millis()),
+            heartbeat: Arc::new(HeartbeatMonitor::new(
+                HeartbeatLossConfig::default(),
+                time.clone(),
+            )),
             state_manager: RwLock::new(RiskStateManager::new()),
-            staleness_monitor: RwLock::new(StalenessMonitor::new()),
+            staleness_monitor: RwLock::new(StalenessMonitor::new(time.clone())),
             constraints_store: None,
+            funding_gate: None,
+            depth_gate: None,
+            spread_gate: None,
+            volatility: None,
+            min_edge_after_fees_pct: None,
+            risk_per_trade: None,
+            lot_sizes: HashMap::new(),
+            spot_venues: HashSet::new(),
+            policy_hash: RwLock::new(RiskPolicy::get_hash()),
+            whitelist_edits: Mutex::new(WhitelistEdits::default()),
+            opened_notional: Mutex::new(VecDeque::new()),
+            slippage_breaches: Mutex::new(VecDeque::new()),
+            consecutive_losses: AtomicU32::new(0),
+            state_entered_at: AtomicI64::new(0),
+            last_breach_at: AtomicI64::new(0),
+            alert_sink: None,
+            time,
         }
     }
 
@@ -166,13 +475,35 @@ impl RiskGuard {
         constraints_store: Arc<ConstraintsStore>,
     ) -> Self {
         info!("🛡️ RiskGuard Initialized with PowerLaw constraints enforcement");
+        shadow_state.write().set_dedup_ttl_ms(policy.dedup_ttl_ms);
+        let time = shadow_state.read().time();
         Self {
             policy: RwLock::new(policy),
             shadow_state,
-            last_heartbeat: AtomicI64::new(chrono::Utc::now().timestamp_millis()),
+            heartbeat: Arc::new(HeartbeatMonitor::new(
+                HeartbeatLossConfig::default(),
+                time.clone(),
+            )),
             state_manager: RwLock::new(RiskStateManager::new()),
-            staleness_monitor: RwLock::new(StalenessMonitor::new()),
+            staleness_monitor: RwLock::new(StalenessMonitor::new(time.clone())),
             constraints_store: Some(constraints_store),
+            funding_gate: None,
+            depth_gate: None,
+            spread_gate: None,
+            volatility: None,
+            min_edge_after_fees_pct: None,
+            risk_per_trade: None,
+            lot_sizes: HashMap::new(),
+            spot_venues: HashSet::new(),
+            policy_hash: RwLock::new(RiskPolicy::get_hash()),
+            whitelist_edits: Mutex::new(WhitelistEdits::default()),
+            opened_notional: Mutex::new(VecDeque::new()),
+            slippage_breaches: Mutex::new(VecDeque::new()),
+            consecutive_losses: AtomicU32::new(0),
+            state_entered_at: AtomicI64::new(0),
+            last_breach_at: AtomicI64::new(0),
+            alert_sink: None,
+            time,
         }
     }
 
@@ -181,24 +512,297 @@ impl RiskGuard {
         self.constraints_store = Some(store);
     }
 
+    /// Set funding-rate entry gating after construction
+    pub fn set_funding_gate(&mut self, gate: Arc<FundingGate>) {
+        self.funding_gate = Some(gate);
+    }
+
+    pub fn record_funding_rate(&self, rate: FundingRate) {
+        if let Some(gate) = &self.funding_gate {
+            gate.record(rate);
+        }
+    }
+
+    /// Set the market-order book depth gate after construction
+    pub fn set_depth_gate(&mut self, gate: Arc<DepthGate>) {
+        self.depth_gate = Some(gate);
+    }
+
+    /// Set the market-order spread gate after construction
+    pub fn set_spread_gate(&mut self, gate: Arc<SpreadGate>) {
+        self.spread_gate = Some(gate);
+    }
+
+    /// Require opens with a known edge to keep `min_pct` after round-trip fees
+    pub fn set_min_edge_after_fees(&mut self, min_pct: Decimal) {
+        self.min_edge_after_fees_pct = Some(min_pct);
+    }
+
+    /// Use a configured heartbeat monitor in place of the default 5s threshold
+    pub fn set_heartbeat_monitor(&mut self, monitor: Arc<HeartbeatMonitor>) {
+        self.heartbeat = monitor;
+    }
+
+    /// Size opens so that a stop-out loses `risk_per_trade`
+    pub fn set_risk_per_trade(&mut self, risk_per_trade: Decimal) {
+        self.risk_per_trade = Some(risk_per_trade);
+    }
+
+    /// Round risk-budget sizes down to each symbol's lot size
+    pub fn set_lot_sizes(&mut self, lot_sizes: HashMap<String, Decimal>) {
+        self.lot_sizes = lot_sizes;
+    }
+
+    /// Replace an opening intent's size with its risk-budget size, when
+    /// risk-budget sizing is on. Closes keep the size they were sent with.
+    pub fn apply_risk_sizing(&self, intent: &mut Intent) -> Result<(), RiskRejectionReason> {
+        let Some(risk_per_trade) = self.risk_per_trade else {
+            return Ok(());
+        };
+        if Self::is_reduce_only(intent) {
+            return Ok(());
+        }
+        let size = risk_budget_size(
+            intent,
+            risk_per_trade,
+            self.lot_sizes.get(&intent.symbol).copied(),
+        )?;
+        info!(
+            signal_id = %intent.signal_id,
+            requested = %intent.size,
+            sized = %size,
+            "Sized from risk budget {}",
+            risk_per_trade
+        );
+        intent.size = size;
+        Ok(())
+    }
+
+    /// Set volatility-driven per-symbol reduce-only mode after construction
+    pub fn set_volatility_tracker(&mut self, tracker: Arc<VolatilityTracker>) {
+        self.volatility = Some(tracker);
+    }
+
+    /// Mark venues that trade spot, so leverage limits don't apply to their intents
+    pub fn set_spot_venues(&mut self, venues: impl IntoIterator<Item = String>) {
+        self.spot_venues = venues.into_iter().map(|v| v.to_lowercase()).collect();
+    }
+
+    fn is_spot(&self, intent: &Intent) -> bool {
+        intent
+            .exchange
+            .as_deref()
+            .is_some_and(|venue| self.spot_venues.contains(&venue.to_lowercase()))
+    }
+
+    /// Raise alerts when a breaker degrades the risk state
+    pub fn set_alert_sink(&mut self, sink: Arc<dyn AlertSink>) {
+        self.alert_sink = Some(sink);
+    }
+
+    fn alert(&self, severity: AlertSeverity, message: String) {
+        if let Some(sink) = &self.alert_sink {
+            sink.send(Alert::new(severity, KIND_BREAKER_TRIPPED, message));
+        }
+    }
+
+    pub fn record_order_book(&self, venue: &str, book: &OrderBookL2) {
+        if let Some(gate) = &self.depth_gate {
+            gate.record(venue, book);
+        }
+    }
+
+    /// Runs once the order manager has chosen a market order, since the
+    /// order type is not known at `check_pre_trade` time.
+    pub fn check_market_depth(
+        &self,
+        intent: &Intent,
+        side: &Side,
+        reduce_only: bool,
+    ) -> Result<(), RiskRejectionReason> {
+        match &self.depth_gate {
+            Some(gate) => gate.check(
+                intent.exchange.as_deref(),
+                &intent.symbol,
+                side,
+                intent.size,
+                reduce_only,
+            ),
+            None => Ok(()),
+        }
+    }
+
+    /// Like `check_market_depth`, refuse a market order across a blown-out spread.
+    pub fn check_market_spread(
+        &self,
+        intent: &Intent,
+        reduce_only: bool,
+    ) -> Result<(), RiskRejectionReason> {
+        match &self.spread_gate {
+            Some(gate) => gate.check(intent.exchange.as_deref(), &intent.symbol, reduce_only),
+            None => Ok(()),
+        }
+    }
+
+    /// Refuse an open whose expected edge does not cover entry and exit fees.
+    /// `fees` comes from `OrderManager::analyze_fees` for the intent's
+    /// expected profit; `maker_entry` is whether the entry rests as a maker.
+    pub fn check_edge_after_fees(
+        &self,
+        intent: &Intent,
+        fees: &FeeAnalysis,
+        maker_entry: bool,
+        reduce_only: bool,
+    ) -> Result<(), RiskRejectionReason> {
+        let Some(min_pct) = self.min_edge_after_fees_pct else {
+            return Ok(());
+        };
+        if reduce_only || Self::is_reduce_only(intent) {
+            return Ok(());
+        }
+        let after_fees_pct = fees.round_trip_profit(maker_entry);
+        if after_fees_pct >= min_pct {
+            return Ok(());
+        }
+        Err(RiskRejectionReason::InsufficientEdgeAfterFees {
+            symbol: intent.symbol.clone(),
+            expected_pct: fees.expected_profit_pct,
+            after_fees_pct,
+            min_pct,
+        })
+    }
+
+    /// Count `quantity` of an open the venue accepted towards the notional
+    /// velocity limit. Kept apart from `check_pre_trade` so what-if prechecks
+    /// and refused orders record nothing.
+    pub fn record_opened_notional(&self, intent: &Intent, quantity: Decimal) {
+        if Self::is_reduce_only(intent) {
+            return;
+        }
+        let price = intent.entry_zone.first().cloned().unwrap_or(Decimal::ZERO);
+        let notional = quantity * price;
+        if notional <= Decimal::ZERO {
+            return;
+        }
+        self.opened_notional
+            .lock()
+            .push_back((self.time.now_millis(), notional));
+    }
+
+    /// Notional opened within the last `window_ms`, dropping older entries.
+    fn opened_notional_within(&self, window_ms: i64) -> Decimal {
+        let cutoff = self.time.now_millis() - window_ms;
+        let mut opened = self.opened_notional.lock();
+        while opened.front().is_some_and(|(ts, _)| *ts <= cutoff) {
+            opened.pop_front();
+        }
+        opened.iter().map(|(_, notional)| *notional).sum()
+    }
+
     pub fn record_market_data_update(&self, exchange: &str, symbol: &str) {
         self.staleness_monitor.write().update(exchange, symbol);
     }
 
-    pub fn update_policy(&self, new_policy: RiskPolicy) {
+    /// A market data connector reconnected; `symbols` are stale until warmed up.
+    pub fn record_market_data_reconnect(&self, exchange: &str, symbols: &[String]) {
+        self.staleness_monitor
+            .read()
+            .mark_reconnect(exchange, symbols);
+    }
+
+    pub fn set_reconnect_warmup_ticks(&self, ticks: u32) {
+        self.staleness_monitor.write().set_warmup_ticks(ticks);
+    }
+
+    pub fn update_policy(&self, mut new_policy: RiskPolicy) {
+        self.shadow_state
+            .write()
+            .set_dedup_ttl_ms(new_policy.dedup_ttl_ms);
         let mut policy = self.policy.write();
+        let now = self.time.now_millis();
+        if let Some(reason) = self.recovery_block(&policy, new_policy.current_state, now) {
+            warn!(
+                "🛡️ Keeping {:?} over policy state {:?}: {}",
+                policy.current_state, new_policy.current_state, reason
+            );
+            new_policy.current_state = policy.current_state;
+        }
+        if new_policy.current_state != policy.current_state {
+            self.state_entered_at.store(now, Ordering::Relaxed);
+        }
+        let edits = self.whitelist_edits.lock();
+        if !edits.is_empty() {
+            edits.apply(&mut new_policy.symbol_whitelist);
+            *self.policy_hash.write() =
+                RiskPolicy::get_hash_with_whitelist(&new_policy.symbol_whitelist);
+        }
+        drop(edits);
         *policy = new_policy;
         info!("🛡️ Risk Policy Updated: {:?}", policy);
     }
 
+    /// Why moving from the current state to the less severe `new_state` must
+    /// wait, if it must: the degraded state has not been held for its minimum
+    /// dwell, or breaker conditions have not been clear for the cooldown.
+    /// Emergency is left to manual recovery and has no dwell.
+    fn recovery_block(
+        &self,
+        policy: &RiskPolicy,
+        new_state: crate::risk_policy::RiskState,
+        now: i64,
+    ) -> Option<String> {
+        if new_state.severity() >= policy.current_state.severity() {
+            return None;
+        }
+        let dwell_ms = match policy.current_state {
+            crate::risk_policy::RiskState::Cautious => policy.min_cautious_dwell_ms,
+            crate::risk_policy::RiskState::Defensive => policy.min_defensive_dwell_ms,
+            _ => return None,
+        };
+        let held_ms = now - self.state_entered_at.load(Ordering::Relaxed);
+        if held_ms < dwell_ms {
+            return Some(format!(
+                "held for {}ms of {}ms minimum dwell",
+                held_ms, dwell_ms
+            ));
+        }
+        let clear_ms = now - self.last_breach_at.load(Ordering::Relaxed);
+        if clear_ms < policy.recovery_cooldown_ms {
+            return Some(format!(
+                "conditions clear for {}ms of {}ms cooldown",
+                clear_ms, policy.recovery_cooldown_ms
+            ));
+        }
+        None
+    }
+
+    /// Enter `new_state`, starting its dwell clock
+    fn enter_state(&self, policy: &mut RiskPolicy, new_state: crate::risk_policy::RiskState) {
+        let now = self.time.now_millis();
+        if new_state.severity() > policy.current_state.severity() {
+            self.last_breach_at.store(now, Ordering::Relaxed);
+        }
+        self.state_entered_at.store(now, Ordering::Relaxed);
+        policy.current_state = new_state;
+    }
+
+    /// Upgrades out of Cautious/Defensive are refused until the state's minimum
+    /// dwell has elapsed and breaker conditions have been clear for the cooldown.
     pub fn update_risk_state(&self, new_state: crate::risk_policy::RiskState) {
         let mut policy = self.policy.write();
+        if let Some(reason) = self.recovery_block(&policy, new_state, self.time.now_millis()) {
+            warn!(
+                "🛡️ Refusing Risk State upgrade {:?} -> {:?}: {}",
+                policy.current_state, new_state, reason
+            );
+            return;
+        }
         if policy.current_state != new_state {
             warn!(
                 "🛡️ Risk State Transition: {:?} -> {:?}",
                 policy.current_state, new_state
             );
-            policy.current_state = new_state;
+            self.enter_state(&mut policy, new_state);
 
             // Metrics Export
             let metric_val = match new_state {
@@ -209,21 +813,49 @@ impl RiskGuard {
             };
             use crate::metrics;
             metrics::set_risk_state(metric_val);
+
+            if matches!(
+                new_state,
+                crate::risk_policy::RiskState::Defensive | crate::risk_policy::RiskState::Emergency
+            ) {
+                self.alert(
+                    AlertSeverity::Critical,
+                    format!("Risk state set to {:?}", new_state),
+                );
+            }
         }
     }
 
     pub fn record_heartbeat(&self) {
-        self.last_heartbeat
-            .store(chrono::Utc::now().timestamp_millis(), Ordering::Relaxed);
+        self.heartbeat.record_heartbeat();
+    }
+
+    pub fn heartbeat_status(&self) -> HeartbeatStatus {
+        self.heartbeat.status()
     }
 
     /// Record a slippage event observed during execution.
     /// If slippage exceeds policy limits, trigger state transition.
     pub fn record_slippage(&self, slippage_bps: u32) {
+        self.record_single_trade_slippage(slippage_bps);
+
+        // A steady stream of moderately bad fills never trips the per-trade
+        // check, so also track the breach rate over recent fills.
+        let (breached, window, max_rate) = {
+            let policy = self.policy.read();
+            (
+                slippage_bps > policy.max_slippage_bps,
+                policy.slippage_rate_window,
+                policy.max_slippage_rate,
+            )
+        };
+        self.record_slippage_breach(breached, window, max_rate);
+    }
+
+    /// Single trade slippage > max_slippage_bps degrades state right away.
+    fn record_single_trade_slippage(&self, slippage_bps: u32) {
         let policy = self.policy.read();
 
-        // Simple Logic: If single trade slippage > max_slippage_bps, we degrade state.
-        // A more robust system would use a windowed error rate.
         if slippage_bps > policy.max_slippage_bps {
             warn!(
                 "⚠️ High Slippage Detected: {} bps > {} bps limit",
@@ -233,6 +865,8 @@ impl RiskGuard {
             // If massive slippage (> 2x limit), go DEFENSIVE immediately.
             // If just above limit, go CAUTIOUS.
             drop(policy); // Drop read lock to acquire write lock
+            self.last_breach_at
+                .store(self.time.now_millis(), Ordering::Relaxed);
 
             let mut policy_write = self.policy.write();
             if slippage_bps > policy_write.max_slippage_bps * 2 {
@@ -240,25 +874,213 @@ impl RiskGuard {
                     && policy_write.current_state != crate::risk_policy::RiskState::Emergency
                 {
                     tracing::error!("🛡️ CIRCUIT BREAKER: Excessive Slippage -> DEFENSIVE");
-                    policy_write.current_state = crate::risk_policy::RiskState::Defensive;
+                    self.enter_state(&mut policy_write, crate::risk_policy::RiskState::Defensive);
                     use crate::metrics;
                     metrics::set_risk_state(2); // Defensive
+                    self.alert(
+                        AlertSeverity::Critical,
+                        format!("Excessive slippage {} bps -> DEFENSIVE", slippage_bps),
+                    );
                 }
             } else if policy_write.current_state == crate::risk_policy::RiskState::Normal {
                 warn!("🛡️ CIRCUIT BREAKER: High Slippage -> CAUTIOUS");
-                policy_write.current_state = crate::risk_policy::RiskState::Cautious;
+                self.enter_state(&mut policy_write, crate::risk_policy::RiskState::Cautious);
                 use crate::metrics;
                 metrics::set_risk_state(1); // Cautious
+                self.alert(
+                    AlertSeverity::Warning,
+                    format!("High slippage {} bps -> CAUTIOUS", slippage_bps),
+                );
+            }
+        }
+    }
+
+    /// Windowed slippage breaker: once the window holds `window` fills and the
+    /// share of them over the limit exceeds `max_rate`, degrade to DEFENSIVE.
+    fn record_slippage_breach(&self, breached: bool, window: Option<usize>, max_rate: Decimal) {
+        let Some(window) = window.filter(|w| *w > 0) else {
+            return;
+        };
+        let rate = {
+            let mut breaches = self.slippage_breaches.lock();
+            breaches.push_back(breached);
+            while breaches.len() > window {
+                breaches.pop_front();
             }
+            if breaches.len() < window {
+                return;
+            }
+            Decimal::from(breaches.iter().filter(|b| **b).count()) / Decimal::from(window)
+        };
+        if rate <= max_rate {
+            return;
+        }
+
+        let mut policy = self.policy.write();
+        if policy.current_state != crate::risk_policy::RiskState::Defensive
+            && policy.current_state != crate::risk_policy::RiskState::Emergency
+        {
+            tracing::error!(
+                "🛡️ CIRCUIT BREAKER: Slippage breach rate {} over last {} fills > {} -> DEFENSIVE",
+                rate,
+                window,
+                max_rate
+            );
+            self.enter_state(&mut policy, crate::risk_policy::RiskState::Defensive);
+            use crate::metrics;
+            metrics::set_risk_state(2); // Defensive
+            self.alert(
+                AlertSeverity::Critical,
+                format!(
+                    "Slippage breach rate {} over last {} fills -> DEFENSIVE",
+                    rate, window
+                ),
+            );
+        }
+    }
+
+    /// Feed every trade the shadow state closes into the losing-streak breaker,
+    /// whether a pipeline fill, a flatten, a transfer or a sweep closed it.
+    /// Trades arrive over a channel so the breaker, which takes the policy
+    /// lock, never runs under the shadow state lock.
+    pub fn track_trade_outcomes(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        self.shadow_state.write().set_trade_outcome_sender(tx);
+        let guard = Arc::downgrade(self);
+        tokio::spawn(async move {
+            while let Some(trade) = rx.recv().await {
+                let Some(guard) = guard.upgrade() else {
+                    break;
+                };
+                guard.record_trade_outcome(&trade);
+            }
+        })
+    }
+
+    /// Count a closed trade towards the losing-streak breaker: a loss extends
+    /// the streak, anything else ends it. Reaching `max_consecutive_losses`
+    /// degrades to DEFENSIVE and starts a new count; leaving DEFENSIVE follows
+    /// the usual dwell and cooldown, or an operator state update.
+    pub fn record_trade_outcome(&self, trade: &TradeRecord) {
+        if trade.pnl >= Decimal::ZERO {
+            self.consecutive_losses.store(0, Ordering::Relaxed);
+            return;
+        }
+        let streak = self.consecutive_losses.fetch_add(1, Ordering::Relaxed) + 1;
+        let mut policy = self.policy.write();
+        let Some(max) = policy.max_consecutive_losses else {
+            return;
+        };
+        if streak < max {
+            return;
+        }
+        self.consecutive_losses.store(0, Ordering::Relaxed);
+        self.last_breach_at
+            .store(self.time.now_millis(), Ordering::Relaxed);
+        if policy.current_state != crate::risk_policy::RiskState::Defensive
+            && policy.current_state != crate::risk_policy::RiskState::Emergency
+        {
+            tracing::error!(
+                "🛡️ CIRCUIT BREAKER: {} consecutive losing trades (last {} on {}) -> DEFENSIVE",
+                streak,
+                trade.pnl,
+                trade.symbol
+            );
+            self.enter_state(&mut policy, crate::risk_policy::RiskState::Defensive);
+            use crate::metrics;
+            metrics::set_risk_state(2); // Defensive
+            self.alert(
+                AlertSeverity::Critical,
+                format!("{} consecutive losing trades -> DEFENSIVE", streak),
+            );
         }
     }
 
+    pub fn consecutive_losses(&self) -> u32 {
+        self.consecutive_losses.load(Ordering::Relaxed)
+    }
+
     pub fn get_policy(&self) -> RiskPolicy {
         self.policy.read().clone()
     }
 
+    /// Account equity (cash plus unrealized PnL) from shadow state
+    pub fn equity(&self) -> Decimal {
+        self.shadow_state.read().get_equity()
+    }
+
+    /// Whether `symbol` is on the global whitelist.
+    pub fn is_whitelisted(&self, symbol: &str) -> bool {
+        self.policy.read().symbol_whitelist.contains(symbol)
+    }
+
     pub fn get_current_policy_hash(&self) -> String {
-        RiskPolicy::get_hash()
+        self.policy_hash.read().clone()
+    }
+
+    /// Add and remove whitelisted symbols at runtime. Returns the new policy hash,
+    /// which Brain needs to resync. The edit is applied to the live policy and
+    /// the hash recomputed under the policy write lock, and it is replayed over
+    /// any policy pushed later.
+    pub fn update_symbol_whitelist(&self, add: &[String], remove: &[String]) -> String {
+        let mut policy = self.policy.write();
+        let mut edits = self.whitelist_edits.lock();
+        for symbol in remove {
+            edits.added.remove(symbol);
+            edits.removed.insert(symbol.clone());
+        }
+        for symbol in add {
+            edits.removed.remove(symbol);
+            edits.added.insert(symbol.clone());
+        }
+        edits.apply(&mut policy.symbol_whitelist);
+        let hash = RiskPolicy::get_hash_with_whitelist(&policy.symbol_whitelist);
+        *self.policy_hash.write() = hash.clone();
+        info!(
+            "🛡️ Symbol whitelist updated (+{:?} -{:?}), policy hash {}",
+            add, remove, hash
+        );
+        hash
+    }
+
+    /// What-if pre-trade check for the risk_precheck RPC. Accepts an enveloped or raw
+    /// intent and replies with the outcome of `check_pre_trade`; read-only, nothing is
+    /// recorded in ShadowState and nothing is sent to an exchange.
+    pub fn precheck_reply(&self, payload: &[u8]) -> serde_json::Value {
+        let intent = serde_json::from_slice::<serde_json::Value>(payload)
+            .map_err(|e| format!("Invalid JSON: {}", e))
+            .and_then(|v| {
+                let inner = match v.get("payload") {
+                    Some(p) if p.is_object() => p.clone(),
+                    _ => v,
+                };
+                serde_json::to_vec(&inner).map_err(|e| e.to_string())
+            })
+            .and_then(|bytes| validate_intent_payload(&bytes));
+
+        let intent = match intent {
+            Ok(intent) => intent,
+            Err(e) => {
+                return serde_json::json!({
+                    "ok": false,
+                    "reason": "INVALID_INTENT",
+                    "message": e,
+                })
+            }
+        };
+
+        match self.check_pre_trade(&intent) {
+            Ok(()) => serde_json::json!({
+                "ok": true,
+                "signal_id": intent.signal_id,
+            }),
+            Err(reason) => serde_json::json!({
+                "ok": false,
+                "signal_id": intent.signal_id,
+                "reason": reason.code(),
+                "message": reason.to_string(),
+            }),
+        }
     }
 
     /// Validates an Intent BEFORE it enters the Order Manager.
@@ -306,27 +1128,26 @@ impl RiskGuard {
         }
 
         // 1. Check Circuit Breakers (Staleness)
-        // If we haven't heard from Brain in 5 seconds, assume Brain is dead -> DEFENSIVE
-        let now = chrono::Utc::now().timestamp_millis();
-        let last = self.last_heartbeat.load(Ordering::Relaxed);
-        let time_since_heartbeat = now - last;
-
-        let mut is_stale = false;
-        if time_since_heartbeat > 5000 {
-            // We don't write lock here to update policy state to avoid contention in hot path,
-            // but we treat it as DEFENSIVE locally.
-            // Ideally we should have an atomic flag for "derived state".
-            // For now, let's log once per stale check (might be spammy, but safe).
+        // If Brain's heartbeat is lost (or still recovering), assume Brain is dead -> DEFENSIVE.
+        // Halt / flatten responses are applied by the HeartbeatResponder.
+        let is_stale = self.heartbeat.is_stale();
+        if is_stale {
             warn!(
                 "⚠️ Heartbeat STALE ({}ms). Treating as DEFENSIVE.",
-                time_since_heartbeat
+                self.heartbeat.status().gap_ms
             );
-            is_stale = true;
         }
 
         // Check Market Data Staleness
         if let Some(exchange) = &intent.exchange {
             let monitor = self.staleness_monitor.read();
+            if monitor.is_suspect(exchange, &intent.symbol) {
+                warn!(signal_id = %intent.signal_id, exchange, symbol = %intent.symbol, "Rejected: market data warming up after reconnect");
+                return Err(RiskRejectionReason::MarketDataStale(format!(
+                    "{} on {} (warming up after reconnect)",
+                    intent.symbol, exchange
+                )));
+            }
             let max_staleness = policy.max_staleness_ms;
             if max_staleness > 0 && monitor.is_stale(exchange, &intent.symbol, max_staleness) {
                 warn!(signal_id = %intent.signal_id, exchange, symbol = %intent.symbol, "Rejected due to STALE market data");
@@ -382,25 +1203,88 @@ impl RiskGuard {
             ));
         }
 
+        // 1.1. Explicitly routed intents must also pass the venue's own whitelist
+        if let Some(venue) = &intent.exchange {
+            if !policy.allows_symbol_on(venue, &intent.symbol) {
+                warn!(
+                    "Risk Reject: Symbol {} not in whitelist for {}",
+                    intent.symbol, venue
+                );
+                return Err(RiskRejectionReason::SymbolNotWhitelistedOnVenue {
+                    symbol: intent.symbol.clone(),
+                    venue: venue.clone(),
+                });
+            }
+        }
+
         // 2. Validate Size
         if intent.size <= Decimal::ZERO {
             return Err(RiskRejectionReason::InvalidSize);
         }
 
-        // 2.5. EXECUTION CONSTRAINTS ENFORCEMENT (PowerLaw)
-        // If we have a constraints store, check the symbol-specific constraints
-        if let Some(ref constraints_store) = self.constraints_store {
-            let venue = intent.exchange.as_deref().unwrap_or("unknown");
-            let account = intent
+        let reduce_only = Self::is_reduce_only(intent)
+            || intent
                 .metadata
                 .as_ref()
-                .and_then(|m| m.get("account"))
-                .and_then(|v| v.as_str())
-                .unwrap_or("main");
-            let constraints = constraints_store.get(venue, account, &intent.symbol);
-
-            // Only enforce if mode is ENFORCEMENT (skip for SHADOW/ADVISORY)
-            if matches!(constraints.mode, PolicyMode::Enforcement) {
+                .and_then(|m| m.get("reduce_only"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+
+        // 2.1. Notional floor: opens too small to be worth their fees
+        if let Some(limit) = policy.min_intent_notional {
+            let price = intent.entry_zone.first().cloned().unwrap_or(Decimal::ZERO);
+            let notional = intent.size * price;
+            if !reduce_only && price > Decimal::ZERO && notional < limit {
+                warn!(
+                    "Risk Reject: Intent notional {:.2} below floor {:.2}",
+                    notional, limit
+                );
+                return Err(RiskRejectionReason::BelowMinIntentNotional {
+                    symbol: intent.symbol.clone(),
+                    notional,
+                    limit,
+                });
+            }
+        }
+
+        // 2.2. Trading sessions: opens only inside the allowed UTC windows
+        if !reduce_only {
+            if let Some(reason) = policy
+                .trading_session_for(&intent.symbol, intent.source.as_deref())
+                .and_then(|session| session.blocked_reason(self.time.now_millis()))
+            {
+                warn!("Risk Reject: {} {}", intent.symbol, reason);
+                return Err(RiskRejectionReason::OutsideTradingSession {
+                    symbol: intent.symbol.clone(),
+                    reason,
+                });
+            }
+        }
+
+        // 2.25. Funding window: don't open into an adverse funding payment
+        if let Some(ref gate) = self.funding_gate {
+            gate.check(intent, reduce_only)?;
+        }
+
+        // 2.3. High volatility: the symbol only accepts exits
+        if let Some(ref tracker) = self.volatility {
+            tracker.check(intent, reduce_only)?;
+        }
+
+        // 2.5. EXECUTION CONSTRAINTS ENFORCEMENT (PowerLaw)
+        // If we have a constraints store, check the symbol-specific constraints
+        if let Some(ref constraints_store) = self.constraints_store {
+            let venue = intent.exchange.as_deref().unwrap_or("unknown");
+            let account = intent
+                .metadata
+                .as_ref()
+                .and_then(|m| m.get("account"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("main");
+            let constraints = constraints_store.get(venue, account, &intent.symbol);
+
+            // Only enforce if mode is ENFORCEMENT (skip for SHADOW/ADVISORY)
+            if matches!(constraints.mode, PolicyMode::Enforcement) {
                 // Check reduce_only constraint
                 if constraints.limits.reduce_only && !Self::is_reduce_only(intent) {
                     warn!(
@@ -433,16 +1317,16 @@ impl RiskGuard {
                 }
 
                 // Check max_leverage against current account leverage
-                if !Self::is_reduce_only(intent) && constraints.limits.max_leverage > Decimal::ZERO
+                if !Self::is_reduce_only(intent)
+                    && !self.is_spot(intent)
+                    && constraints.limits.max_leverage > Decimal::ZERO
                 {
-                    let total_pos_notional: Decimal = state
-                        .get_all_positions()
-                        .values()
-                        .map(|p| p.size * p.entry_price)
-                        .sum();
-
-                    let new_notional = intent.size * check_price;
-                    let total_exposure = total_pos_notional + new_notional;
+                    let total_exposure = Self::leverage_exposure(
+                        policy.leverage_mode,
+                        &state.get_all_positions(),
+                        intent,
+                        intent.size * check_price,
+                    );
                     let equity = state.get_equity();
 
                     if equity > Decimal::ZERO {
@@ -494,17 +1378,29 @@ impl RiskGuard {
             }
         }
 
-        // 4. Daily Loss Limit
+        // 3.5. Max Open Positions (across all symbols)
+        // Adding to an already exposed symbol doesn't grow the count
+        if let Some(limit) = policy.max_open_positions {
+            if !Self::is_reduce_only(intent) {
+                let exposed = state.exposed_symbols();
+                if !exposed.contains(&intent.symbol) && exposed.len() >= limit {
+                    warn!(
+                        "Risk Reject: Max Open Positions {} >= Limit {}",
+                        exposed.len(),
+                        limit
+                    );
+                    return Err(RiskRejectionReason::MaxOpenPositionsExceeded {
+                        symbol: intent.symbol.clone(),
+                        current: exposed.len(),
+                        limit,
+                    });
+                }
+            }
+        }
 
         // 4. Daily Loss Limit
-        // Sum PnL from trade history for today (UTC).
-        let today = chrono::Utc::now().date_naive();
-        let current_pnl: Decimal = state
-            .get_trade_history()
-            .iter()
-            .filter(|t| t.closed_at.date_naive() == today)
-            .map(|t| t.pnl)
-            .sum();
+        // Equity change since the day's open, so open losses count too
+        let current_pnl = state.intraday_pnl();
 
         if current_pnl <= policy.max_daily_loss {
             // Allow CLOSE intents to reduce risk?
@@ -522,6 +1418,27 @@ impl RiskGuard {
             }
         }
 
+        // 4.5. Notional Velocity: cap how much capital is deployed per rolling window
+        if let Some(limit) = policy.max_notional_per_window {
+            let price = intent.entry_zone.first().cloned().unwrap_or(Decimal::ZERO);
+            let additional = intent.size * price;
+            if !Self::is_reduce_only(intent) && additional > Decimal::ZERO {
+                let opened = self.opened_notional_within(policy.notional_window_ms);
+                if opened + additional > limit {
+                    warn!(
+                        "Risk Reject: Notional velocity {:.2} + {:.2} > {:.2} per {}ms",
+                        opened, additional, limit, policy.notional_window_ms
+                    );
+                    return Err(RiskRejectionReason::NotionalVelocityExceeded {
+                        opened,
+                        additional,
+                        limit,
+                        window_ms: policy.notional_window_ms,
+                    });
+                }
+            }
+        }
+
         // 5. Max Position Notional
         // If opening/increasing position, check size limit.
         let is_reduce = Self::is_reduce_only(intent);
@@ -561,19 +1478,44 @@ impl RiskGuard {
             }
         }
 
-        // 6. Max Account Leverage (Global)
+        // 5.5. Correlation groups: members share one notional cap
+        if !is_reduce && check_price > Decimal::ZERO {
+            let positions = state.get_all_positions();
+            for (name, group) in &policy.correlation_groups {
+                if !group.symbols.contains(&intent.symbol) {
+                    continue;
+                }
+                let held: Decimal = positions
+                    .values()
+                    .filter(|p| group.symbols.contains(&p.symbol))
+                    .map(|p| p.size * p.entry_price)
+                    .sum();
+                let current = held + intent.size * check_price;
+                if current > group.max_group_notional {
+                    warn!(
+                        "Risk Reject: Group {} notional {:.2} > {:.2}",
+                        name, current, group.max_group_notional
+                    );
+                    return Err(RiskRejectionReason::GroupNotionalExceeded {
+                        group: name.clone(),
+                        current,
+                        limit: group.max_group_notional,
+                    });
+                }
+            }
+        }
+
+        // 6. Max Account Leverage (Global), not applicable to spot
         // Leverage = Total Notional / Equity
         // Total Notional = Sum(|Position Notional|) + New Intent Notional
-        if !is_reduce {
-            let total_pos_notional: Decimal = state
-                .get_all_positions()
-                .values()
-                .map(|p| p.size * p.entry_price) // using entry price as approximation for now
-                .sum();
-
+        if !is_reduce && !self.is_spot(intent) {
             // New Intent Notional (using check_price calculated earlier)
-            let new_notional = intent.size * check_price;
-            let total_exposure = total_pos_notional + new_notional;
+            let total_exposure = Self::leverage_exposure(
+                policy.leverage_mode,
+                &state.get_all_positions(),
+                intent,
+                intent.size * check_price,
+            );
 
             let equity = state.get_equity();
 
@@ -600,9 +1542,86 @@ impl RiskGuard {
             }
         }
 
+        // 7. Available Cash
+        // Working orders hold their margin back, so raw cash would over-commit
+        let required = Self::reservation_for(&policy, intent, self.is_spot(intent));
+        if required > Decimal::ZERO {
+            let available = state.get_available_cash();
+            if required > available {
+                warn!(
+                    "Risk Reject: Insufficient available cash {:.2} < {:.2} required",
+                    available, required
+                );
+                return Err(RiskRejectionReason::InsufficientAvailableCash {
+                    required,
+                    available,
+                });
+            }
+        }
+
         Ok(())
     }
 
+    /// Exposure for the leverage limits once `intent` adds `new_notional`.
+    /// Gross sums every notional; net sums |long - short| per base asset, so a
+    /// hedge lowers exposure instead of adding to it. Positions are valued at
+    /// entry price.
+    fn leverage_exposure(
+        mode: LeverageMode,
+        positions: &HashMap<String, Position>,
+        intent: &Intent,
+        new_notional: Decimal,
+    ) -> Decimal {
+        match mode {
+            LeverageMode::Gross => {
+                positions
+                    .values()
+                    .map(|p| p.size * p.entry_price)
+                    .sum::<Decimal>()
+                    + new_notional
+            }
+            LeverageMode::Net => {
+                let mut net: HashMap<String, Decimal> = HashMap::new();
+                for p in positions.values() {
+                    let signed = match p.side {
+                        Side::Long | Side::Buy => p.size * p.entry_price,
+                        Side::Short | Side::Sell => -(p.size * p.entry_price),
+                    };
+                    *net.entry(base_asset(&p.symbol)).or_default() += signed;
+                }
+                let signed_new = match intent.intent_type {
+                    IntentType::SellSetup => -new_notional,
+                    _ => new_notional,
+                };
+                *net.entry(base_asset(&intent.symbol)).or_default() += signed_new;
+                net.values().map(|n| n.abs()).sum()
+            }
+        }
+    }
+
+    /// Cash to hold back while the intent's orders are working. Reduce-only
+    /// intents and intents without a price reserve nothing.
+    pub fn cash_reservation(&self, intent: &Intent) -> Decimal {
+        Self::reservation_for(&self.policy.read(), intent, self.is_spot(intent))
+    }
+
+    fn reservation_for(policy: &RiskPolicy, intent: &Intent, spot: bool) -> Decimal {
+        let price = intent.entry_zone.first().cloned().unwrap_or(Decimal::ZERO);
+        if Self::is_reduce_only(intent) || price <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+        let ratio = match policy.order_margin_ratio {
+            // Spot buys are paid in full
+            _ if spot => Decimal::ONE,
+            Some(ratio) => ratio,
+            None if policy.max_account_leverage > Decimal::ZERO => {
+                Decimal::ONE / policy.max_account_leverage
+            }
+            None => Decimal::ONE,
+        };
+        intent.size * price * ratio
+    }
+
     pub fn is_reduce_only(intent: &Intent) -> bool {
         use crate::model::IntentType;
         matches!(
@@ -618,8 +1637,8 @@ impl RiskGuard {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::context::ExecutionContext;
-    use crate::model::{IntentStatus, IntentType};
+    use crate::context::{ExecutionContext, MockClock};
+    use crate::model::IntentStatus;
     use crate::persistence::store::PersistenceStore;
 
     use chrono::Utc;
@@ -701,6 +1720,555 @@ mod tests {
         std::fs::remove_file(path).unwrap_or(());
     }
 
+    #[test]
+    fn test_whitelist_edit_survives_policy_update() {
+        let (p, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let state = Arc::new(RwLock::new(ShadowState::new(p, ctx, Some(10000.0))));
+        let policy = RiskPolicy {
+            symbol_whitelist: ["BTC/USDT", "ETH/USDT"].map(String::from).into(),
+            ..Default::default()
+        };
+        let guard = RiskGuard::new(policy.clone(), state);
+
+        let hash =
+            guard.update_symbol_whitelist(&["SOL/USDT".to_string()], &["ETH/USDT".to_string()]);
+        assert!(guard.is_whitelisted("SOL/USDT"));
+        assert!(!guard.is_whitelisted("ETH/USDT"));
+        assert_eq!(guard.get_current_policy_hash(), hash);
+
+        // Brain pushes its policy again without the edit
+        guard.update_policy(policy);
+        assert!(guard.is_whitelisted("SOL/USDT"));
+        assert!(!guard.is_whitelisted("ETH/USDT"));
+        assert_eq!(
+            guard.get_current_policy_hash(),
+            RiskPolicy::get_hash_with_whitelist(&guard.get_policy().symbol_whitelist)
+        );
+        assert_eq!(guard.get_current_policy_hash(), hash);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[test]
+    fn test_venue_whitelist_restricts_global_whitelist() {
+        let (p, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let state = Arc::new(RwLock::new(ShadowState::new(p, ctx, Some(10000.0))));
+        let mut policy = RiskPolicy {
+            symbol_whitelist: ["BTC/USDT", "ETH/USDT"].map(String::from).into(),
+            ..Default::default()
+        };
+        policy
+            .exchange_symbol_whitelist
+            .insert("mexc".to_string(), ["BTC/USDT"].map(String::from).into());
+        let guard = RiskGuard::new(policy, state);
+
+        let mut intent = simple_intent("ETH/USDT", dec!(1.0), dec!(2000), IntentType::BuySetup);
+        intent.exchange = Some("MEXC".to_string());
+        guard.record_market_data_update("MEXC", "ETH/USDT");
+        guard.record_market_data_update("binance", "ETH/USDT");
+        assert!(matches!(
+            guard.check_pre_trade(&intent),
+            Err(RiskRejectionReason::SymbolNotWhitelistedOnVenue { ref venue, .. }) if venue == "MEXC"
+        ));
+
+        // Venues without their own list fall back to the global one
+        intent.exchange = Some("binance".to_string());
+        assert!(guard.check_pre_trade(&intent).is_ok());
+        intent.exchange = None;
+        assert!(guard.check_pre_trade(&intent).is_ok());
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[test]
+    fn test_max_open_positions_allows_reduce_only() {
+        let (p, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let state = Arc::new(RwLock::new(ShadowState::new(p, ctx, Some(10000.0))));
+        let policy = RiskPolicy {
+            max_open_positions: Some(2),
+            ..Default::default()
+        };
+        let guard = RiskGuard::new(policy, state.clone());
+
+        // Open up to the cap
+        for (symbol, price) in [("BTC/USDT", dec!(50000)), ("ETH/USDT", dec!(2000))] {
+            let intent = simple_intent(symbol, dec!(0.01), price, IntentType::BuySetup);
+            assert!(guard.check_pre_trade(&intent).is_ok());
+            state.write().process_intent(intent);
+        }
+
+        let open = simple_intent("SOL/USDT", dec!(1.0), dec!(100), IntentType::BuySetup);
+        assert!(matches!(
+            guard.check_pre_trade(&open),
+            Err(RiskRejectionReason::MaxOpenPositionsExceeded {
+                current: 2,
+                limit: 2,
+                ..
+            })
+        ));
+
+        // Adding to an exposed symbol and closing stay allowed
+        let add = simple_intent("BTC/USDT", dec!(0.01), dec!(50000), IntentType::BuySetup);
+        assert!(guard.check_pre_trade(&add).is_ok());
+        let close = simple_intent("SOL/USDT", dec!(1.0), dec!(100), IntentType::Close);
+        assert!(guard.check_pre_trade(&close).is_ok());
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[test]
+    fn test_volatility_reduce_only_blocks_opens_on_that_symbol() {
+        use crate::config::VolatilityGateConfig;
+        use crate::context::SimulatedTimeProvider;
+        use crate::market_data::engine::MarketDataEngine;
+        use crate::market_data::types::BookTicker;
+
+        let (p, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let state = Arc::new(RwLock::new(ShadowState::new(p, ctx, Some(10000.0))));
+        let mut guard = RiskGuard::new(RiskPolicy::default(), state);
+
+        let now = Utc::now().timestamp_millis();
+        let engine = Arc::new(MarketDataEngine::new(None));
+        let tick = |symbol: &str, mid: Decimal, at: i64| BookTicker {
+            symbol: symbol.to_string(),
+            best_bid: mid - dec!(0.5),
+            best_bid_qty: dec!(1),
+            best_ask: mid + dec!(0.5),
+            best_ask_qty: dec!(1),
+            transaction_time: at,
+            event_time: at,
+        };
+        // BTC whipsaws ~2% per tick, ETH barely moves
+        for (i, (btc, eth)) in [(50000, 3000), (51000, 3001), (50000, 3000), (51000, 3001)]
+            .into_iter()
+            .enumerate()
+        {
+            let at = now - 4_000 + i as i64 * 1_000;
+            engine.record_tick(tick("BTCUSDT", Decimal::from(btc), at));
+            engine.record_tick(tick("ETHUSDT", Decimal::from(eth), at));
+        }
+        guard.set_volatility_tracker(Arc::new(VolatilityTracker::with_time(
+            VolatilityGateConfig {
+                enabled: true,
+                window_ms: 10_000,
+                enter_vol_bps: 150.0,
+                exit_vol_bps: 100.0,
+                min_ticks: 3,
+            },
+            engine,
+            Arc::new(SimulatedTimeProvider::new(now)),
+        )));
+
+        let open = simple_intent("BTC/USDT", dec!(0.01), dec!(50000), IntentType::BuySetup);
+        assert_eq!(
+            guard.check_pre_trade(&open),
+            Err(RiskRejectionReason::VolatilityReduceOnly {
+                symbol: "BTC/USDT".to_string()
+            })
+        );
+
+        let close = simple_intent("BTC/USDT", dec!(0.01), dec!(50000), IntentType::CloseLong);
+        assert!(guard.check_pre_trade(&close).is_ok());
+
+        let other = simple_intent("ETH/USDT", dec!(0.1), dec!(3000), IntentType::BuySetup);
+        assert!(guard.check_pre_trade(&other).is_ok());
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[test]
+    fn test_notional_velocity_limit_rolls_with_window() {
+        let (p, path) = create_test_persistence();
+        let clock = Arc::new(MockClock::new(Utc::now().timestamp_millis()));
+        let ctx = Arc::new(ExecutionContext::new_test(clock.clone()));
+        let state = Arc::new(RwLock::new(ShadowState::new(p, ctx, Some(10000.0))));
+        let policy = RiskPolicy {
+            max_notional_per_window: Some(dec!(3000)),
+            notional_window_ms: 200,
+            ..Default::default()
+        };
+        let guard = RiskGuard::new(policy, state);
+
+        // Three $1k opens reach the limit exactly
+        for _ in 0..3 {
+            let open = simple_intent("BTC/USDT", dec!(0.02), dec!(50000), IntentType::BuySetup);
+            assert!(guard.check_pre_trade(&open).is_ok());
+            guard.record_opened_notional(&open, open.size);
+        }
+
+        let open = simple_intent("BTC/USDT", dec!(0.02), dec!(50000), IntentType::BuySetup);
+        assert!(matches!(
+            guard.check_pre_trade(&open),
+            Err(RiskRejectionReason::NotionalVelocityExceeded { opened, .. }) if opened == dec!(3000)
+        ));
+
+        // Reduce-only is exempt
+        let close = simple_intent("BTC/USDT", dec!(0.02), dec!(50000), IntentType::CloseLong);
+        assert!(guard.check_pre_trade(&close).is_ok());
+
+        // Once the window rolls the earlier opens no longer count
+        clock.advance(199);
+        assert!(guard.check_pre_trade(&open).is_err());
+        clock.advance(1);
+        assert!(guard.check_pre_trade(&open).is_ok());
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[test]
+    fn test_min_intent_notional_rejects_dust_opens_only() {
+        let (p, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let state = Arc::new(RwLock::new(ShadowState::new(p, ctx, Some(10000.0))));
+        let policy = RiskPolicy {
+            min_intent_notional: Some(dec!(10)),
+            ..Default::default()
+        };
+        let guard = RiskGuard::new(policy, state);
+
+        // 0.0001 BTC @ 50k = $5
+        let dust_open = simple_intent("BTC/USDT", dec!(0.0001), dec!(50000), IntentType::BuySetup);
+        assert_eq!(
+            guard.check_pre_trade(&dust_open),
+            Err(RiskRejectionReason::BelowMinIntentNotional {
+                symbol: "BTC/USDT".to_string(),
+                notional: dec!(5),
+                limit: dec!(10),
+            })
+        );
+
+        let dust_close =
+            simple_intent("BTC/USDT", dec!(0.0001), dec!(50000), IntentType::CloseLong);
+        assert!(guard.check_pre_trade(&dust_close).is_ok());
+
+        let open = simple_intent("BTC/USDT", dec!(0.001), dec!(50000), IntentType::BuySetup);
+        assert!(guard.check_pre_trade(&open).is_ok());
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[test]
+    fn test_trading_sessions_block_opens_outside_windows_and_in_blackouts() {
+        use chrono::TimeZone;
+
+        let at = |h, m| {
+            Utc.with_ymd_and_hms(2026, 3, 4, h, m, 0)
+                .unwrap()
+                .timestamp_millis()
+        };
+        let (p, path) = create_test_persistence();
+        let clock = Arc::new(MockClock::new(at(10, 0)));
+        let ctx = Arc::new(ExecutionContext::new_test(clock.clone()));
+        let state = Arc::new(RwLock::new(ShadowState::new(p, ctx, Some(10000.0))));
+        let sessions = serde_json::json!({
+            "BTC/USDT": {
+                "windows": [
+                    { "start": "08:00", "end": "16:00" },
+                    { "start": "22:00", "end": "02:00" }
+                ],
+                "blackouts": [{ "startMs": at(13, 25), "endMs": at(13, 45), "label": "CPI" }]
+            },
+            "scalper": { "windows": [{ "start": "08:00", "end": "16:00" }] }
+        });
+        let policy = RiskPolicy {
+            trading_sessions: serde_json::from_value(sessions).unwrap(),
+            ..Default::default()
+        };
+        let guard = RiskGuard::new(policy, state);
+        let open = simple_intent("BTC/USDT", dec!(0.01), dec!(50000), IntentType::BuySetup);
+        let close = simple_intent("BTC/USDT", dec!(0.01), dec!(50000), IntentType::CloseLong);
+        let check_at = |h, m, intent: &Intent| {
+            clock.set_time(at(h, m));
+            guard.record_heartbeat();
+            guard.check_pre_trade(intent)
+        };
+
+        // Inside either window, including the one running past midnight
+        assert!(check_at(10, 0, &open).is_ok());
+        assert!(check_at(23, 30, &open).is_ok());
+        assert!(check_at(1, 59, &open).is_ok());
+
+        // Outside: opens rejected, exits still flow
+        assert!(matches!(
+            check_at(5, 0, &open),
+            Err(RiskRejectionReason::OutsideTradingSession { ref reason, .. })
+                if reason.contains("05:00 UTC")
+        ));
+        assert!(check_at(16, 0, &open).is_err(), "End is exclusive");
+        assert!(check_at(5, 0, &close).is_ok());
+
+        // A blackout inside an open window
+        assert!(matches!(
+            check_at(13, 30, &open),
+            Err(RiskRejectionReason::OutsideTradingSession { ref reason, .. })
+                if reason.contains("CPI")
+        ));
+        assert!(check_at(13, 30, &close).is_ok());
+        assert!(check_at(13, 45, &open).is_ok());
+
+        // Source-keyed sessions apply to symbols without their own
+        let mut eth = simple_intent("ETH/USDT", dec!(0.1), dec!(3000), IntentType::BuySetup);
+        eth.source = Some("scalper".to_string());
+        assert!(check_at(5, 0, &eth).is_err());
+        eth.source = Some("swing".to_string());
+        assert!(check_at(5, 0, &eth).is_ok());
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[test]
+    fn test_slippage_rate_breaker_trips_on_windowed_breaches() {
+        let (p, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let state = Arc::new(RwLock::new(ShadowState::new(p, ctx, Some(10000.0))));
+        let policy = RiskPolicy {
+            max_slippage_bps: 50,
+            slippage_rate_window: Some(10),
+            max_slippage_rate: dec!(0.3),
+            ..Default::default()
+        };
+        let guard = RiskGuard::new(policy, state);
+
+        // 3 of 10 fills over the limit: a 30% rate is tolerated (single
+        // breaches still move Normal -> Cautious)
+        for bps in [10, 10, 10, 60, 10, 10, 60, 10, 10, 60] {
+            guard.record_slippage(bps);
+        }
+        assert_eq!(guard.get_policy().current_state, RiskState::Cautious);
+
+        // A fourth breach in the last 10 fills pushes the rate to 40%
+        guard.record_slippage(60);
+        assert_eq!(guard.get_policy().current_state, RiskState::Defensive);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[test]
+    fn test_slippage_rate_breaker_waits_for_full_window() {
+        let (p, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let state = Arc::new(RwLock::new(ShadowState::new(p, ctx, Some(10000.0))));
+        let policy = RiskPolicy {
+            max_slippage_bps: 50,
+            slippage_rate_window: Some(5),
+            max_slippage_rate: dec!(0.5),
+            ..Default::default()
+        };
+        let guard = RiskGuard::new(policy, state);
+
+        // Early breaches alone are not a rate until the window has filled
+        guard.record_slippage(60);
+        guard.record_slippage(60);
+        assert_eq!(guard.get_policy().current_state, RiskState::Cautious);
+
+        // Old breaches roll out of the window
+        for _ in 0..5 {
+            guard.record_slippage(10);
+        }
+        guard.record_slippage(60);
+        guard.record_slippage(60);
+        assert_eq!(guard.get_policy().current_state, RiskState::Cautious);
+
+        guard.record_slippage(60);
+        assert_eq!(guard.get_policy().current_state, RiskState::Defensive);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    fn closed_trade(pnl: Decimal) -> TradeRecord {
+        TradeRecord {
+            signal_id: "sig-streak".to_string(),
+            symbol: "BTC/USDT".to_string(),
+            side: Side::Long,
+            entry_price: dec!(50000),
+            exit_price: dec!(50000) + pnl,
+            size: dec!(1),
+            pnl,
+            pnl_pct: pnl / dec!(500),
+            fee: dec!(0),
+            fee_asset: "USDT".to_string(),
+            opened_at: Utc::now(),
+            closed_at: Utc::now(),
+            close_reason: "MANUAL".to_string(),
+            metadata: None,
+            lots: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_consecutive_losses_breaker_trips_at_limit() {
+        let (p, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let state = Arc::new(RwLock::new(ShadowState::new(p, ctx, Some(10000.0))));
+        let policy = RiskPolicy {
+            max_consecutive_losses: Some(3),
+            ..Default::default()
+        };
+        let guard = RiskGuard::new(policy, state);
+
+        guard.record_trade_outcome(&closed_trade(dec!(-10)));
+        guard.record_trade_outcome(&closed_trade(dec!(-10)));
+        assert_eq!(guard.consecutive_losses(), 2);
+        assert_eq!(guard.get_policy().current_state, RiskState::Normal);
+
+        guard.record_trade_outcome(&closed_trade(dec!(-10)));
+        assert_eq!(guard.get_policy().current_state, RiskState::Defensive);
+        // Tripping starts a fresh count
+        assert_eq!(guard.consecutive_losses(), 0);
+
+        // Defensive is close-only
+        let open = simple_intent("BTC/USDT", dec!(0.1), dec!(50000), IntentType::BuySetup);
+        assert!(guard.check_pre_trade(&open).is_err());
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_losing_closes_from_shadow_state_trip_breaker() {
+        let (p, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let state = Arc::new(RwLock::new(ShadowState::new(p, ctx, Some(100000.0))));
+        let policy = RiskPolicy {
+            max_consecutive_losses: Some(3),
+            ..Default::default()
+        };
+        let guard = Arc::new(RiskGuard::new(policy, state.clone()));
+        guard.track_trade_outcomes();
+
+        // Closed straight on the shadow state, as a flatten or transfer does
+        for i in 0..3 {
+            let mut state = state.write();
+            let mut open = simple_intent("BTC/USDT", dec!(0.1), dec!(50000), IntentType::BuySetup);
+            open.signal_id = format!("open-{}", i);
+            state.process_intent(open);
+            state.confirm_execution(
+                &format!("open-{}", i),
+                &format!("open-child-{}", i),
+                dec!(50000),
+                dec!(0.1),
+                true,
+                dec!(0),
+                "USDT".to_string(),
+                "binance",
+            );
+            let mut close =
+                simple_intent("BTC/USDT", dec!(0.1), dec!(49000), IntentType::CloseLong);
+            close.signal_id = format!("close-{}", i);
+            close.direction = -1;
+            state.process_intent(close);
+            state.confirm_execution(
+                &format!("close-{}", i),
+                &format!("close-child-{}", i),
+                dec!(49000),
+                dec!(0.1),
+                true,
+                dec!(0),
+                "USDT".to_string(),
+                "binance",
+            );
+        }
+        assert_eq!(state.read().get_trade_history().len(), 3);
+
+        for _ in 0..100 {
+            if guard.get_policy().current_state == RiskState::Defensive {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+        assert_eq!(guard.get_policy().current_state, RiskState::Defensive);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[test]
+    fn test_winning_trade_resets_loss_streak() {
+        let (p, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let state = Arc::new(RwLock::new(ShadowState::new(p, ctx, Some(10000.0))));
+        let policy = RiskPolicy {
+            max_consecutive_losses: Some(3),
+            ..Default::default()
+        };
+        let guard = RiskGuard::new(policy, state);
+
+        guard.record_trade_outcome(&closed_trade(dec!(-10)));
+        guard.record_trade_outcome(&closed_trade(dec!(-10)));
+        guard.record_trade_outcome(&closed_trade(dec!(25)));
+        assert_eq!(guard.consecutive_losses(), 0);
+
+        // Two more losses: four of the last five, but never three in a row
+        guard.record_trade_outcome(&closed_trade(dec!(-10)));
+        guard.record_trade_outcome(&closed_trade(dec!(-10)));
+        assert_eq!(guard.consecutive_losses(), 2);
+        assert_eq!(guard.get_policy().current_state, RiskState::Normal);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[test]
+    fn test_upgrade_from_defensive_waits_for_dwell_and_cooldown() {
+        let (p, path) = create_test_persistence();
+        let clock = Arc::new(MockClock::new(Utc::now().timestamp_millis()));
+        let ctx = Arc::new(ExecutionContext::new_test(clock.clone()));
+        let state = Arc::new(RwLock::new(ShadowState::new(p, ctx, Some(10000.0))));
+        let policy = RiskPolicy {
+            max_slippage_bps: 50,
+            min_cautious_dwell_ms: 150,
+            min_defensive_dwell_ms: 300,
+            recovery_cooldown_ms: 200,
+            ..Default::default()
+        };
+        let guard = RiskGuard::new(policy, state);
+
+        // Excessive slippage trips Defensive
+        guard.record_slippage(150);
+        assert_eq!(guard.get_policy().current_state, RiskState::Defensive);
+
+        // External upgrades are refused during the dwell, by state or by policy push
+        guard.update_risk_state(RiskState::Normal);
+        assert_eq!(guard.get_policy().current_state, RiskState::Defensive);
+        guard.update_policy(RiskPolicy {
+            max_slippage_bps: 50,
+            min_cautious_dwell_ms: 150,
+            min_defensive_dwell_ms: 300,
+            recovery_cooldown_ms: 200,
+            ..Default::default()
+        });
+        assert_eq!(guard.get_policy().current_state, RiskState::Defensive);
+        // Escalation is never held back
+        guard.update_risk_state(RiskState::Emergency);
+        assert_eq!(guard.get_policy().current_state, RiskState::Emergency);
+        guard.update_risk_state(RiskState::Defensive);
+        assert_eq!(guard.get_policy().current_state, RiskState::Defensive);
+
+        // Dwell elapses, but a fresh breach restarts the cooldown
+        clock.advance(300);
+        guard.record_slippage(60);
+        guard.update_risk_state(RiskState::Cautious);
+        assert_eq!(guard.get_policy().current_state, RiskState::Defensive);
+
+        clock.advance(199);
+        guard.update_risk_state(RiskState::Cautious);
+        assert_eq!(guard.get_policy().current_state, RiskState::Defensive);
+        clock.advance(1);
+        guard.update_risk_state(RiskState::Cautious);
+        assert_eq!(guard.get_policy().current_state, RiskState::Cautious);
+
+        // Cautious has its own dwell before Normal
+        clock.advance(149);
+        guard.update_risk_state(RiskState::Normal);
+        assert_eq!(guard.get_policy().current_state, RiskState::Cautious);
+        clock.advance(1);
+        guard.update_risk_state(RiskState::Normal);
+        assert_eq!(guard.get_policy().current_state, RiskState::Normal);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
     #[test]
     fn test_max_notional_rejection() {
         let (p, path) = create_test_persistence();
@@ -728,6 +2296,151 @@ mod tests {
         std::fs::remove_file(path).unwrap_or(());
     }
 
+    #[test]
+    fn test_risk_budget_sizing_overrides_size_within_limits() {
+        let (p, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let state = Arc::new(RwLock::new(ShadowState::new(p, ctx, Some(10000.0))));
+        let policy = RiskPolicy {
+            max_position_notional: dec!(10000.0),
+            ..Default::default()
+        };
+        let mut guard = RiskGuard::new(policy, state);
+        guard.set_risk_per_trade(dec!(100));
+
+        // Long 10000, stop 9800: $200 per unit -> 0.5, whatever size was sent
+        let mut long = simple_intent("BTC/USDT", dec!(3), dec!(10000), IntentType::BuySetup);
+        long.stop_loss = dec!(9800);
+        guard.apply_risk_sizing(&mut long).unwrap();
+        assert_eq!(long.size, dec!(0.5));
+        assert!(guard.check_pre_trade(&long).is_ok());
+
+        // Short 10000, stop 10400: $400 per unit -> 0.25
+        let mut short = simple_intent("BTC/USDT", dec!(3), dec!(10000), IntentType::SellSetup);
+        short.direction = -1;
+        short.stop_loss = dec!(10400);
+        guard.apply_risk_sizing(&mut short).unwrap();
+        assert_eq!(short.size, dec!(0.25));
+
+        // A tight stop implies $20k notional, which the position cap refuses
+        let mut tight = simple_intent("BTC/USDT", dec!(0.1), dec!(10000), IntentType::BuySetup);
+        tight.stop_loss = dec!(9950);
+        guard.apply_risk_sizing(&mut tight).unwrap();
+        assert_eq!(tight.size, dec!(2));
+        assert!(matches!(
+            guard.check_pre_trade(&tight),
+            Err(RiskRejectionReason::MaxPositionNotionalExceeded { .. })
+        ));
+
+        // Closes keep their size; opens without a stop are refused
+        let mut close = simple_intent("BTC/USDT", dec!(3), dec!(10000), IntentType::CloseLong);
+        guard.apply_risk_sizing(&mut close).unwrap();
+        assert_eq!(close.size, dec!(3));
+        let mut no_stop = simple_intent("BTC/USDT", dec!(3), dec!(10000), IntentType::BuySetup);
+        assert!(matches!(
+            guard.apply_risk_sizing(&mut no_stop),
+            Err(RiskRejectionReason::RiskSizingFailed { .. })
+        ));
+
+        // Lots of 0.2: 0.25 rounds down to 0.2, and a size under one lot is refused
+        guard.set_lot_sizes(HashMap::from([("BTC/USDT".to_string(), dec!(0.2))]));
+        let mut lotted = simple_intent("BTC/USDT", dec!(3), dec!(10000), IntentType::SellSetup);
+        lotted.direction = -1;
+        lotted.stop_loss = dec!(10400);
+        guard.apply_risk_sizing(&mut lotted).unwrap();
+        assert_eq!(lotted.size, dec!(0.2));
+        let mut wide = simple_intent("BTC/USDT", dec!(3), dec!(10000), IntentType::BuySetup);
+        wide.stop_loss = dec!(9000);
+        assert!(matches!(
+            guard.apply_risk_sizing(&mut wide),
+            Err(RiskRejectionReason::RiskSizingFailed { .. })
+        ));
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[test]
+    fn test_precheck_reply_notional_rejection() {
+        let (p, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let state = Arc::new(RwLock::new(ShadowState::new(p, ctx, Some(10000.0))));
+        let policy = RiskPolicy {
+            max_position_notional: dec!(10000.0),
+            ..Default::default()
+        };
+        let guard = RiskGuard::new(policy, state.clone());
+
+        let intent_json = |signal_id: &str, size: f64| {
+            serde_json::json!({
+                "signal_id": signal_id,
+                "symbol": "BTC/USDT",
+                "direction": 1,
+                "type": "BUY_SETUP",
+                "entry_zone": [10000.0],
+                "size": size,
+                "status": "PENDING",
+                "t_signal": Utc::now().timestamp_millis(),
+            })
+        };
+
+        let envelope = serde_json::json!({
+            "type": "titan.cmd.execution.place.v1",
+            "payload": intent_json("sig-over", 1.1),
+        });
+        let reply = guard.precheck_reply(&serde_json::to_vec(&envelope).unwrap());
+        assert_eq!(reply["ok"], false);
+        assert_eq!(reply["reason"], "MAX_POSITION_NOTIONAL_EXCEEDED");
+        assert_eq!(reply["signal_id"], "sig-over");
+
+        // Raw (un-enveloped) intent within limits passes
+        let reply = guard.precheck_reply(&serde_json::to_vec(&intent_json("sig-ok", 0.5)).unwrap());
+        assert_eq!(reply["ok"], true);
+
+        // Nothing was recorded
+        assert_eq!(state.read().count_open_intents_for_symbol("BTC/USDT"), 0);
+
+        let reply = guard.precheck_reply(b"not json");
+        assert_eq!(reply["reason"], "INVALID_INTENT");
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[test]
+    fn test_reconnect_rejects_until_warmed_up() {
+        let (p, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let state = Arc::new(RwLock::new(ShadowState::new(p, ctx, Some(10000.0))));
+        let guard = RiskGuard::new(RiskPolicy::default(), state);
+        guard.set_reconnect_warmup_ticks(3);
+
+        let mut intent = simple_intent("BTC/USDT", dec!(0.01), dec!(50000), IntentType::BuySetup);
+        intent.exchange = Some("binance".to_string());
+
+        guard.record_market_data_update("binance", "BTC/USDT");
+        assert!(guard.check_pre_trade(&intent).is_ok());
+
+        guard.record_market_data_reconnect("binance", &["BTCUSDT".to_string()]);
+        // First post-reconnect ticks look fresh but are not trusted yet
+        for _ in 0..2 {
+            guard.record_market_data_update("binance", "BTC/USDT");
+            assert!(matches!(
+                guard.check_pre_trade(&intent),
+                Err(RiskRejectionReason::MarketDataStale(_))
+            ));
+        }
+
+        guard.record_market_data_update("binance", "BTC/USDT");
+        assert!(guard.check_pre_trade(&intent).is_ok());
+
+        // Other venues are unaffected
+        intent.exchange = Some("bybit".to_string());
+        guard.record_market_data_update("bybit", "BTC/USDT");
+        guard.record_market_data_reconnect("binance", &[]);
+        assert!(guard.check_pre_trade(&intent).is_ok());
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
     #[test]
     fn test_daily_loss_rejection() {
         let (p, path) = create_test_persistence();
@@ -882,6 +2595,177 @@ mod tests {
         std::fs::remove_file(path).unwrap_or(());
     }
 
+    #[test]
+    fn test_spot_venue_skips_leverage_and_reserves_full_notional() {
+        let (p, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let state = Arc::new(RwLock::new(ShadowState::new(p, ctx, Some(1000.0))));
+        let policy = RiskPolicy {
+            max_account_leverage: dec!(4.0),
+            ..Default::default()
+        };
+        let mut guard = RiskGuard::new(policy, state.clone());
+        guard.set_spot_venues(["binance".to_string()]);
+
+        // $4000 open: the account sits exactly at 4x
+        let open = simple_intent("BTC/USDT", dec!(0.1), dec!(40000), IntentType::BuySetup);
+        {
+            let mut s = state.write();
+            s.process_intent(open.clone());
+            s.confirm_execution(
+                &open.signal_id,
+                "fill-1",
+                dec!(40000),
+                dec!(0.1),
+                true,
+                dec!(0),
+                "USDT".to_string(),
+                "Binance",
+            );
+        }
+
+        // Not a spot venue: 4.5x breaches the limit
+        let futures = simple_intent("ETH/USDT", dec!(0.25), dec!(2000), IntentType::BuySetup);
+        assert!(matches!(
+            guard.check_pre_trade(&futures),
+            Err(RiskRejectionReason::MaxAccountLeverageExceeded { .. })
+        ));
+
+        let mut spot = futures.clone();
+        spot.exchange = Some("Binance".to_string());
+        guard.record_market_data_update("Binance", "ETH/USDT");
+        assert!(guard.check_pre_trade(&spot).is_ok());
+        assert_eq!(guard.cash_reservation(&spot), dec!(500));
+
+        // Spot has to pay for the whole order out of cash
+        spot.size = dec!(0.75);
+        assert!(matches!(
+            guard.check_pre_trade(&spot),
+            Err(RiskRejectionReason::InsufficientAvailableCash { .. })
+        ));
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[test]
+    fn test_net_leverage_mode_lets_hedges_through() {
+        let (p, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let state = Arc::new(RwLock::new(ShadowState::new(p, ctx, Some(1000.0))));
+        let gross_policy = RiskPolicy {
+            max_account_leverage: dec!(5.0),
+            symbol_whitelist: HashSet::new(),
+            ..Default::default()
+        };
+        let gross = RiskGuard::new(gross_policy.clone(), state.clone());
+        let net = RiskGuard::new(
+            RiskPolicy {
+                leverage_mode: LeverageMode::Net,
+                ..gross_policy
+            },
+            state.clone(),
+        );
+
+        // $4000 long BTC: 4x either way
+        let open = simple_intent("BTC/USDT", dec!(0.1), dec!(40000), IntentType::BuySetup);
+        {
+            let mut s = state.write();
+            s.process_intent(open.clone());
+            s.confirm_execution(
+                &open.signal_id,
+                "fill-1",
+                dec!(40000),
+                dec!(0.1),
+                true,
+                dec!(0),
+                "USDT".to_string(),
+                "Binance",
+            );
+        }
+
+        // A $2000 short on the perp hedges half the long: 6x gross, 2x net
+        let hedge = simple_intent("BTC-PERP", dec!(0.05), dec!(40000), IntentType::SellSetup);
+        assert!(matches!(
+            gross.check_pre_trade(&hedge),
+            Err(RiskRejectionReason::MaxAccountLeverageExceeded { current, .. })
+            if current == dec!(6.0)
+        ));
+        assert!(net.check_pre_trade(&hedge).is_ok());
+
+        // Adding to the long in another venue's symbol is no hedge
+        let more = simple_intent("BTCUSDT", dec!(0.05), dec!(40000), IntentType::BuySetup);
+        assert!(matches!(
+            net.check_pre_trade(&more),
+            Err(RiskRejectionReason::MaxAccountLeverageExceeded { current, .. })
+            if current == dec!(6.0)
+        ));
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[test]
+    fn test_correlation_group_caps_combined_notional() {
+        use crate::risk_policy::CorrelationGroup;
+
+        let (p, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let state = Arc::new(RwLock::new(ShadowState::new(p, ctx, Some(100_000.0))));
+        let mut policy = RiskPolicy::default();
+        policy.correlation_groups.insert(
+            "l1".to_string(),
+            CorrelationGroup {
+                symbols: ["ETH/USDT", "SOL/USDT"].map(String::from).into(),
+                max_group_notional: dec!(5000),
+            },
+        );
+        let guard = RiskGuard::new(policy, state.clone());
+
+        let fill = |intent: &Intent, price: Decimal| {
+            let mut s = state.write();
+            s.process_intent(intent.clone());
+            s.confirm_execution(
+                &intent.signal_id,
+                &format!("fill-{}", intent.signal_id),
+                price,
+                intent.size,
+                true,
+                dec!(0),
+                "USDT".to_string(),
+                "Binance",
+            );
+        };
+
+        let eth = simple_intent("ETH/USDT", dec!(1.0), dec!(2000), IntentType::BuySetup);
+        assert!(guard.check_pre_trade(&eth).is_ok());
+        fill(&eth, dec!(2000));
+        let sol = simple_intent("SOL/USDT", dec!(20), dec!(100), IntentType::BuySetup);
+        assert!(guard.check_pre_trade(&sol).is_ok());
+        fill(&sol, dec!(100));
+
+        // 2000 ETH + 2000 SOL + 1500 more SOL breaches the 5000 group cap
+        let more_sol = simple_intent("SOL/USDT", dec!(15), dec!(100), IntentType::BuySetup);
+        match guard.check_pre_trade(&more_sol) {
+            Err(RiskRejectionReason::GroupNotionalExceeded {
+                group,
+                current,
+                limit,
+            }) => {
+                assert_eq!(group, "l1");
+                assert_eq!(current, dec!(5500));
+                assert_eq!(limit, dec!(5000));
+            }
+            other => panic!("expected group rejection, got {:?}", other),
+        }
+
+        // Symbols outside the group and exits are unaffected
+        let btc = simple_intent("BTC/USDT", dec!(0.1), dec!(50000), IntentType::BuySetup);
+        assert!(guard.check_pre_trade(&btc).is_ok());
+        let close = simple_intent("SOL/USDT", dec!(20), dec!(100), IntentType::CloseLong);
+        assert!(guard.check_pre_trade(&close).is_ok());
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
     #[test]
     fn test_max_open_orders_rejection() {
         let (p, path) = create_test_persistence();
@@ -923,4 +2807,65 @@ mod tests {
 
         std::fs::remove_file(path).unwrap_or(());
     }
+
+    #[test]
+    fn test_edge_gate_requires_profit_after_round_trip_fees() {
+        use crate::circuit_breaker::GlobalHalt;
+        use crate::market_data::engine::MarketDataEngine;
+        use crate::order_manager::OrderManager;
+
+        let (p, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let state = Arc::new(RwLock::new(ShadowState::new(p, ctx, Some(10000.0))));
+        let mut guard = RiskGuard::new(RiskPolicy::default(), state);
+        let halt_path = std::env::temp_dir().join(format!("titan_halt_{}", uuid::Uuid::new_v4()));
+        let order_manager = OrderManager::new(
+            None,
+            Arc::new(MarketDataEngine::new(None)),
+            Arc::new(GlobalHalt::with_file(&halt_path)),
+        );
+        let open = simple_intent("BTC/USDT", dec!(0.01), dec!(50000), IntentType::BuySetup);
+
+        // Without a configured minimum the gate is off
+        let thin = order_manager.analyze_fees(dec!(0.05), Decimal::ZERO);
+        assert!(guard
+            .check_edge_after_fees(&open, &thin, false, false)
+            .is_ok());
+
+        guard.set_min_edge_after_fees(dec!(0.01));
+
+        // 0.05% edge: maker entry 0.02% + taker exit 0.05% eats it
+        assert_eq!(
+            guard.check_edge_after_fees(&open, &thin, true, false),
+            Err(RiskRejectionReason::InsufficientEdgeAfterFees {
+                symbol: "BTC/USDT".to_string(),
+                expected_pct: dec!(0.05),
+                after_fees_pct: dec!(-0.02),
+                min_pct: dec!(0.01),
+            })
+        );
+
+        // 0.09% edge clears a maker entry (0.02% left) but not a taker one
+        let fair = order_manager.analyze_fees(dec!(0.09), Decimal::ZERO);
+        assert!(guard
+            .check_edge_after_fees(&open, &fair, true, false)
+            .is_ok());
+        assert!(matches!(
+            guard.check_edge_after_fees(&open, &fair, false, false),
+            Err(RiskRejectionReason::InsufficientEdgeAfterFees { after_fees_pct, .. })
+                if after_fees_pct == dec!(-0.01)
+        ));
+
+        // Exits are never held back by the edge they no longer need
+        let close = simple_intent("BTC/USDT", dec!(0.01), dec!(50000), IntentType::CloseLong);
+        assert!(guard
+            .check_edge_after_fees(&close, &thin, false, false)
+            .is_ok());
+        assert!(guard
+            .check_edge_after_fees(&open, &thin, false, true)
+            .is_ok());
+
+        std::fs::remove_file(path).unwrap_or(());
+        std::fs::remove_file(halt_path).unwrap_or(());
+    }
 }
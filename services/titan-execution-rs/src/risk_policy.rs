@@ -1,7 +1,8 @@
+use chrono::{NaiveTime, TimeZone, Utc};
 use rust_decimal::dec;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
 use sha2::{Digest, Sha256};
 
@@ -14,6 +15,106 @@ pub enum RiskState {
     Emergency,
 }
 
+impl RiskState {
+    /// Ordering from Normal (0) to Emergency (3)
+    pub fn severity(&self) -> u8 {
+        match self {
+            RiskState::Normal => 0,
+            RiskState::Cautious => 1,
+            RiskState::Defensive => 2,
+            RiskState::Emergency => 3,
+        }
+    }
+}
+
+/// Exposure that account leverage is measured on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LeverageMode {
+    /// Sum of every position's notional
+    #[default]
+    Gross,
+    /// Longs and shorts in the same base asset offset each other
+    Net,
+}
+
+/// Correlated symbols (e.g. L1 tokens) whose positions are capped together.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CorrelationGroup {
+    pub symbols: HashSet<String>,
+    /// Most notional the members may hold between them
+    #[serde(alias = "maxGroupNotional")]
+    pub max_group_notional: Decimal,
+}
+
+/// Daily UTC window in which opens are allowed, `start` inclusive and `end`
+/// exclusive. A window that ends before it starts runs past midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionWindow {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl SessionWindow {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// No opens from `start_ms` until `end_ms`, e.g. around a scheduled macro release.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionBlackout {
+    #[serde(alias = "startMs")]
+    pub start_ms: i64,
+    #[serde(alias = "endMs")]
+    pub end_ms: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// When a symbol or source may open positions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TradingSession {
+    /// Allowed windows; empty allows the whole day
+    #[serde(default)]
+    pub windows: Vec<SessionWindow>,
+    /// Event blackouts, applied on top of the windows
+    #[serde(default)]
+    pub blackouts: Vec<SessionBlackout>,
+}
+
+impl TradingSession {
+    /// Why an open at `now_ms` falls outside this session, or `None` if it is inside.
+    pub fn blocked_reason(&self, now_ms: i64) -> Option<String> {
+        if let Some(blackout) = self
+            .blackouts
+            .iter()
+            .find(|b| b.start_ms <= now_ms && now_ms < b.end_ms)
+        {
+            return Some(match &blackout.label {
+                Some(label) => format!("blackout for {} until {}", label, blackout.end_ms),
+                None => format!("blackout until {}", blackout.end_ms),
+            });
+        }
+        if self.windows.is_empty() {
+            return None;
+        }
+        let time = Utc.timestamp_millis_opt(now_ms).single()?.time();
+        if self.windows.iter().any(|w| w.contains(time)) {
+            None
+        } else {
+            Some(format!(
+                "outside trading windows at {} UTC",
+                time.format("%H:%M")
+            ))
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RiskPolicy {
     /// Current Global Risk State
@@ -28,6 +129,10 @@ pub struct RiskPolicy {
     #[serde(alias = "maxAccountLeverage")]
     pub max_account_leverage: Decimal,
 
+    /// Whether hedged positions count gross or net towards max_account_leverage
+    #[serde(default, alias = "leverageMode")]
+    pub leverage_mode: LeverageMode,
+
     /// Maximum daily loss limit (negative value)
     #[serde(alias = "maxDailyLoss")]
     pub max_daily_loss: Decimal,
@@ -36,18 +141,123 @@ pub struct RiskPolicy {
     #[serde(alias = "maxOpenOrdersPerSymbol")]
     pub max_open_orders_per_symbol: usize,
 
+    /// Maximum symbols with an open position or pending open at once (unset: no cap)
+    #[serde(
+        default,
+        alias = "maxOpenPositions",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_open_positions: Option<usize>,
+
+    /// Most notional that may be opened within any `notional_window_ms` span
+    /// (unset: no velocity limit). Reduce-only intents are exempt.
+    #[serde(
+        default,
+        alias = "maxNotionalPerWindow",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_notional_per_window: Option<Decimal>,
+
+    /// Smallest notional an opening intent may have (unset: no floor).
+    /// Reduce-only intents are exempt so a dust remainder can always be closed.
+    #[serde(
+        default,
+        alias = "minIntentNotional",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub min_intent_notional: Option<Decimal>,
+
+    /// Rolling window (ms) over which `max_notional_per_window` applies
+    #[serde(default = "default_notional_window", alias = "notionalWindowMs")]
+    pub notional_window_ms: i64,
+
     /// Whitelisted symbols
     #[serde(alias = "symbolWhitelist")]
     pub symbol_whitelist: HashSet<String>,
 
+    /// Per-venue whitelists that further restrict the global one. Venues
+    /// without an entry fall back to `symbol_whitelist`.
+    #[serde(
+        default,
+        alias = "exchangeSymbolWhitelist",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub exchange_symbol_whitelist: BTreeMap<String, HashSet<String>>,
+
+    /// Named groups of correlated symbols, each with a combined notional cap
+    #[serde(
+        default,
+        alias = "correlationGroups",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub correlation_groups: BTreeMap<String, CorrelationGroup>,
+
+    /// UTC trading sessions for opens, keyed by symbol or intent source. A
+    /// symbol's entry takes precedence; intents matching neither trade any time.
+    #[serde(
+        default,
+        alias = "tradingSessions",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub trading_sessions: BTreeMap<String, TradingSession>,
+
     /// Maximum allowed slippage in basis points (Circuit Breaker)
     #[serde(default = "default_max_slippage", alias = "maxSlippageBps")]
     pub max_slippage_bps: u32,
 
+    /// Number of most recent fills over which the slippage breach rate is
+    /// measured (unset: no rate breaker, only the per-trade one)
+    #[serde(
+        default,
+        alias = "slippageRateWindow",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub slippage_rate_window: Option<usize>,
+
+    /// Fraction of fills in the window that may exceed `max_slippage_bps`
+    /// before the state degrades to Defensive
+    #[serde(default = "default_max_slippage_rate", alias = "maxSlippageRate")]
+    pub max_slippage_rate: Decimal,
+
+    /// Losing trades in a row that degrade the state to Defensive (unset: no
+    /// streak breaker). A winning trade resets the count.
+    #[serde(
+        default,
+        alias = "maxConsecutiveLosses",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_consecutive_losses: Option<u32>,
+
+    /// Minimum time (ms) spent in Cautious before an upgrade is accepted
+    #[serde(default = "default_cautious_dwell", alias = "minCautiousDwellMs")]
+    pub min_cautious_dwell_ms: i64,
+
+    /// Minimum time (ms) spent in Defensive before an upgrade is accepted
+    #[serde(default = "default_defensive_dwell", alias = "minDefensiveDwellMs")]
+    pub min_defensive_dwell_ms: i64,
+
+    /// How long (ms) breaker conditions must stay clear before an upgrade
+    #[serde(default = "default_recovery_cooldown", alias = "recoveryCooldownMs")]
+    pub recovery_cooldown_ms: i64,
+
     /// Maximum allowed staleness for market data in ms (Circuit Breaker)
     #[serde(default = "default_max_staleness", alias = "maxStalenessMs")]
     pub max_staleness_ms: i64,
 
+    /// Window (ms) in which a repeated causation_id is rejected as a duplicate,
+    /// for intents that carry no ttl_ms of their own
+    #[serde(default = "default_dedup_ttl", alias = "dedupTtlMs")]
+    pub dedup_ttl_ms: i64,
+
+    /// Fraction of an order's notional reserved from available cash while it
+    /// is working. Unset: initial margin at max_account_leverage.
+    #[serde(
+        default,
+        alias = "orderMarginRatio",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub order_margin_ratio: Option<Decimal>,
+
     // --- Strategy Constraints (Brain Veto) ---
     // These are informational for Rust (for now) but strictly enforced by Brain.
     // We ingest them to ensure full Policy portability.
@@ -78,10 +288,42 @@ fn default_max_slippage() -> u32 {
     100 // 1%
 }
 
+fn default_max_slippage_rate() -> Decimal {
+    dec!(0.2)
+}
+
+pub const DEFAULT_CAUTIOUS_DWELL_MS: i64 = 60_000;
+pub const DEFAULT_DEFENSIVE_DWELL_MS: i64 = 300_000;
+pub const DEFAULT_RECOVERY_COOLDOWN_MS: i64 = 30_000;
+
+fn default_cautious_dwell() -> i64 {
+    DEFAULT_CAUTIOUS_DWELL_MS
+}
+
+fn default_defensive_dwell() -> i64 {
+    DEFAULT_DEFENSIVE_DWELL_MS
+}
+
+fn default_recovery_cooldown() -> i64 {
+    DEFAULT_RECOVERY_COOLDOWN_MS
+}
+
 fn default_max_staleness() -> i64 {
     5000 // 5 seconds
 }
 
+pub const DEFAULT_NOTIONAL_WINDOW_MS: i64 = 60_000;
+
+fn default_notional_window() -> i64 {
+    DEFAULT_NOTIONAL_WINDOW_MS
+}
+
+pub const DEFAULT_DEDUP_TTL_MS: i64 = 5000;
+
+fn default_dedup_ttl() -> i64 {
+    DEFAULT_DEDUP_TTL_MS
+}
+
 fn default_max_correlation() -> Decimal {
     dec!(0.7)
 }
@@ -113,11 +355,27 @@ impl RiskPolicy {
             current_state: RiskState::Emergency,
             max_position_notional: dec!(0.0),
             max_account_leverage: dec!(0.0),
+            leverage_mode: LeverageMode::Gross,
             max_daily_loss: dec!(0.0),
             max_open_orders_per_symbol: 0,
+            max_open_positions: Some(0),
+            max_notional_per_window: Some(dec!(0.0)),
+            min_intent_notional: None,
+            notional_window_ms: DEFAULT_NOTIONAL_WINDOW_MS,
             symbol_whitelist: HashSet::new(),
+            exchange_symbol_whitelist: BTreeMap::new(),
+            correlation_groups: BTreeMap::new(),
+            trading_sessions: BTreeMap::new(),
             max_slippage_bps: 0,
+            slippage_rate_window: Some(1),
+            max_slippage_rate: dec!(0.0),
+            max_consecutive_losses: Some(0),
+            min_cautious_dwell_ms: DEFAULT_CAUTIOUS_DWELL_MS,
+            min_defensive_dwell_ms: DEFAULT_DEFENSIVE_DWELL_MS,
+            recovery_cooldown_ms: DEFAULT_RECOVERY_COOLDOWN_MS,
             max_staleness_ms: 0,
+            dedup_ttl_ms: DEFAULT_DEDUP_TTL_MS,
+            order_margin_ratio: None,
 
             max_correlation: dec!(0.0),
             correlation_penalty: dec!(1.0),
@@ -128,11 +386,50 @@ impl RiskPolicy {
         }
     }
 
+    /// Whether `symbol` may be routed to `venue`: it must pass the global
+    /// whitelist (when set) and the venue's own list (when it has one).
+    pub fn allows_symbol_on(&self, venue: &str, symbol: &str) -> bool {
+        if !self.symbol_whitelist.is_empty() && !self.symbol_whitelist.contains(symbol) {
+            return false;
+        }
+        self.exchange_symbol_whitelist
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(venue))
+            .is_none_or(|(_, symbols)| symbols.contains(symbol))
+    }
+
+    /// Session governing opens of `symbol` from `source`, if one is configured.
+    pub fn trading_session_for(
+        &self,
+        symbol: &str,
+        source: Option<&str>,
+    ) -> Option<&TradingSession> {
+        self.trading_sessions
+            .get(symbol)
+            .or_else(|| source.and_then(|s| self.trading_sessions.get(s)))
+    }
+
     /// Returns the SHA256 hash of the canonical policy JSON.
     /// Parses and re-serializes to compact JSON to match TypeScript's JSON.stringify().
     pub fn get_hash() -> String {
-        let value: serde_json::Value = serde_json::from_str(RISK_POLICY_JSON)
-            .expect("Failed to parse embedded risk_policy.json");
+        Self::hash_canonical(Self::canonical_json())
+    }
+
+    /// Canonical hash after a runtime whitelist edit: the embedded policy JSON with
+    /// `symbolWhitelist` replaced by `whitelist`, sorted so Brain can reproduce it.
+    pub fn get_hash_with_whitelist(whitelist: &HashSet<String>) -> String {
+        let mut value = Self::canonical_json();
+        let mut symbols: Vec<&String> = whitelist.iter().collect();
+        symbols.sort();
+        value["symbolWhitelist"] = serde_json::json!(symbols);
+        Self::hash_canonical(value)
+    }
+
+    fn canonical_json() -> serde_json::Value {
+        serde_json::from_str(RISK_POLICY_JSON).expect("Failed to parse embedded risk_policy.json")
+    }
+
+    fn hash_canonical(value: serde_json::Value) -> String {
         let compact = serde_json::to_string(&value).expect("Failed to serialize to compact JSON");
         let mut hasher = Sha256::new();
         hasher.update(compact.as_bytes());
@@ -1,18 +1,62 @@
 use crate::contracts::IntentEnvelope;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
 use hex;
 use hmac::{Hmac, Mac};
 use serde_json::Value;
 use sha2::Sha256;
+use std::collections::{HashMap, HashSet};
 use std::env;
-use tracing::{info, warn};
+use tracing::{error, info, warn};
 
 type HmacSha256 = Hmac<Sha256>;
 
+/// Envelope signature algorithm, from the envelope's `sig_alg` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigAlg {
+    /// Shared-secret HMAC; the default when `sig_alg` is absent
+    HmacSha256,
+    /// Asymmetric; only the brain's public key lives here
+    Ed25519,
+}
+
+impl SigAlg {
+    pub fn parse(sig_alg: Option<&str>) -> Result<Self, String> {
+        match sig_alg.map(|a| a.to_ascii_lowercase()).as_deref() {
+            None | Some("hmac-sha256") => Ok(SigAlg::HmacSha256),
+            Some("ed25519") => Ok(SigAlg::Ed25519),
+            Some(other) => Err(format!("Unsupported signature algorithm: {}", other)),
+        }
+    }
+}
+
+/// Key a single producer signs its envelopes with.
+#[derive(Clone)]
+pub enum ProducerKey {
+    Hmac(String),
+    Ed25519(VerifyingKey),
+}
+
+impl ProducerKey {
+    fn alg(&self) -> SigAlg {
+        match self {
+            ProducerKey::Hmac(_) => SigAlg::HmacSha256,
+            ProducerKey::Ed25519(_) => SigAlg::Ed25519,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct HmacValidator {
     secret: String,
+    /// Brain's Ed25519 public key, for envelopes with `sig_alg: "ed25519"`
+    ed25519_key: Option<VerifyingKey>,
     _require_timestamp: bool,
     timestamp_tolerance: i64, // seconds
+    /// Envelope producers allowed to submit intents; None allows any signed producer
+    allowed_producers: Option<HashSet<String>>,
+    /// Per-producer keys. A keyed producer's envelopes sign the producer name
+    /// too, so one producer's key cannot vouch for another.
+    producer_keys: HashMap<String, ProducerKey>,
 }
 
 impl Default for HmacValidator {
@@ -31,10 +75,25 @@ impl HmacValidator {
             .unwrap_or("300".to_string())
             .parse::<i64>()
             .unwrap_or(300);
-
-        // FAIL-CLOSED INVARIANT: Empty secret is FATAL unless explicitly allowed for testing
+        let ed25519_key = match env::var("INTENT_ED25519_PUBLIC_KEY") {
+            Ok(hex_key) if !hex_key.is_empty() => match parse_ed25519_public_key(&hex_key) {
+                Ok(key) => Some(key),
+                Err(e) => panic!("FATAL: INTENT_ED25519_PUBLIC_KEY is invalid: {}", e),
+            },
+            _ => None,
+        };
+        let producer_keys = match env::var("INTENT_PRODUCER_KEYS") {
+            Ok(spec) if !spec.is_empty() => match parse_producer_keys(&spec) {
+                Ok(keys) => keys,
+                Err(e) => panic!("FATAL: INTENT_PRODUCER_KEYS is invalid: {}", e),
+            },
+            _ => HashMap::new(),
+        };
+
+        // FAIL-CLOSED INVARIANT: Empty secret is FATAL unless explicitly allowed for testing,
+        // or the deployment verifies Ed25519 signatures only.
         // This prevents production startup with missing credentials
-        if secret.is_empty() {
+        if secret.is_empty() && ed25519_key.is_none() && producer_keys.is_empty() {
             let allow_empty = env::var("HMAC_ALLOW_EMPTY_SECRET")
                 .map(|v| v == "true")
                 .unwrap_or(false);
@@ -49,15 +108,69 @@ impl HmacValidator {
             }
         } else {
             info!(
-                "🔐 HMAC Validator initialized (tol: {}s)",
+                "🔐 Signature Validator initialized (hmac: {}, ed25519: {}, producer keys: {}, tol: {}s)",
+                !secret.is_empty(),
+                ed25519_key.is_some(),
+                producer_keys.len(),
                 timestamp_tolerance
             );
         }
 
         Self {
             secret,
+            ed25519_key,
             _require_timestamp: require_timestamp,
             timestamp_tolerance,
+            allowed_producers: None,
+            producer_keys,
+        }
+    }
+
+    /// Validator with explicit keys instead of the environment.
+    pub fn with_keys(
+        secret: String,
+        ed25519_key: Option<VerifyingKey>,
+        timestamp_tolerance: i64,
+    ) -> Self {
+        Self {
+            secret,
+            ed25519_key,
+            _require_timestamp: true,
+            timestamp_tolerance,
+            allowed_producers: None,
+            producer_keys: HashMap::new(),
+        }
+    }
+
+    pub fn with_producer_keys(mut self, keys: HashMap<String, ProducerKey>) -> Self {
+        self.producer_keys = keys;
+        self
+    }
+
+    /// Only accept envelopes from these producers, each signed with its own
+    /// key from `INTENT_PRODUCER_KEYS`. An empty list leaves every producer
+    /// allowed.
+    pub fn with_allowed_producers(mut self, producers: &[String]) -> Self {
+        self.allowed_producers =
+            (!producers.is_empty()).then(|| producers.iter().cloned().collect());
+        if let Some(allowed) = &self.allowed_producers {
+            info!("🔐 Intent producers restricted to {:?}", allowed);
+            for producer in allowed {
+                if !self.producer_keys.contains_key(producer) {
+                    error!(
+                        "❌ Allowed producer {} has no signing key; its intents will be refused",
+                        producer
+                    );
+                }
+            }
+        }
+        self
+    }
+
+    pub fn is_producer_allowed(&self, producer: &str) -> bool {
+        match &self.allowed_producers {
+            Some(allowed) => allowed.contains(producer),
+            None => true,
         }
     }
 
@@ -66,12 +179,7 @@ impl HmacValidator {
         envelope: &IntentEnvelope,
         raw_payload_value: &Value,
     ) -> Result<(), String> {
-        if self.secret.is_empty() {
-            // Fail open if not configured? No, we want security.
-            // But if user hasn't set env var yet, system breaks.
-            // Assuming this is deployed with secrets.
-            return Err("HMAC validation enabled but no secret configured".to_string());
-        }
+        let alg = SigAlg::parse(envelope.sig_alg.as_deref())?;
 
         // 1. Check existence
         let sig = envelope.sig.as_deref().ok_or("Missing signature")?;
@@ -98,33 +206,62 @@ impl HmacValidator {
         let payload_str = serde_json::to_string(raw_payload_value)
             .map_err(|e| format!("Failed to serialize payload: {}", e))?;
 
-        // 4. Canonical String: ts.nonce.payload_json
-        let canonical = format!("{}.{}.{}", ts, nonce, payload_str);
+        // 4. Producer allowlist
+        if !self.is_producer_allowed(&envelope.producer) {
+            return Err(format!("Producer not allowed: {}", envelope.producer));
+        }
 
-        // 5. Verify
-        let mut mac = HmacSha256::new_from_slice(self.secret.as_bytes())
-            .map_err(|_| "Invalid secret key length".to_string())?;
+        // 5. Keyed producers sign ts.nonce.producer.payload_json with their
+        // own key, so the producer name is bound to the signature
+        if let Some(key) = self.producer_keys.get(&envelope.producer) {
+            if key.alg() != alg {
+                return Err(format!(
+                    "Producer {} must sign with {:?}",
+                    envelope.producer,
+                    key.alg()
+                ));
+            }
+            let canonical = format!("{}.{}.{}.{}", ts, nonce, envelope.producer, payload_str);
+            return match key {
+                ProducerKey::Hmac(secret) => verify_hmac_with(secret, &canonical, sig),
+                ProducerKey::Ed25519(key) => verify_ed25519_with(key, &canonical, sig),
+            };
+        }
+        // With an allowlist the shared keys cannot tell producers apart
+        if self.allowed_producers.is_some() {
+            return Err(format!(
+                "No signing key configured for producer {}",
+                envelope.producer
+            ));
+        }
 
-        mac.update(canonical.as_bytes());
-        let result = mac.finalize();
-        let expected_bytes = result.into_bytes();
-        let _expected_sig = hex::encode(expected_bytes);
+        // 6. Canonical String: ts.nonce.payload_json
+        let canonical = format!("{}.{}.{}", ts, nonce, payload_str);
 
-        // Constant time comparison
-        // But we are in Rust, hex string comparison is not constant time usually.
-        // We should verify bytes.
-        let sig_bytes = hex::decode(sig).map_err(|_| "Invalid hex signature")?;
+        // 7. Verify
+        match alg {
+            SigAlg::HmacSha256 => self.verify_hmac(&canonical, sig),
+            SigAlg::Ed25519 => self.verify_ed25519(&canonical, sig),
+        }
+    }
 
-        // Hmac crate provides verify method which is constant time
-        let mut mac_verify = HmacSha256::new_from_slice(self.secret.as_bytes())
-            .map_err(|_| "Invalid secret key length".to_string())?;
-        mac_verify.update(canonical.as_bytes());
+    fn verify_hmac(&self, canonical: &str, sig: &str) -> Result<(), String> {
+        if self.secret.is_empty() {
+            // Fail open if not configured? No, we want security.
+            // But if user hasn't set env var yet, system breaks.
+            // Assuming this is deployed with secrets.
+            return Err("HMAC validation enabled but no secret configured".to_string());
+        }
 
-        mac_verify
-            .verify_slice(&sig_bytes)
-            .map_err(|_| "Signature mismatch.".to_string())?;
+        verify_hmac_with(&self.secret, canonical, sig)
+    }
 
-        Ok(())
+    fn verify_ed25519(&self, canonical: &str, sig: &str) -> Result<(), String> {
+        let key = self
+            .ed25519_key
+            .as_ref()
+            .ok_or("Ed25519 signature received but no public key configured")?;
+        verify_ed25519_with(key, canonical, sig)
     }
 
     /// Validate a Risk Command (Halt/Override) using deterministic signature
@@ -184,9 +321,63 @@ impl HmacValidator {
     }
 }
 
+fn verify_hmac_with(secret: &str, canonical: &str, sig: &str) -> Result<(), String> {
+    // Constant time comparison
+    // But we are in Rust, hex string comparison is not constant time usually.
+    // We should verify bytes.
+    let sig_bytes = hex::decode(sig).map_err(|_| "Invalid hex signature")?;
+
+    // Hmac crate provides verify method which is constant time
+    let mut mac_verify = HmacSha256::new_from_slice(secret.as_bytes())
+        .map_err(|_| "Invalid secret key length".to_string())?;
+    mac_verify.update(canonical.as_bytes());
+
+    mac_verify
+        .verify_slice(&sig_bytes)
+        .map_err(|_| "Signature mismatch.".to_string())
+}
+
+fn verify_ed25519_with(key: &VerifyingKey, canonical: &str, sig: &str) -> Result<(), String> {
+    let sig_bytes: [u8; 64] = hex::decode(sig)
+        .map_err(|_| "Invalid hex signature")?
+        .try_into()
+        .map_err(|_| "Invalid signature length (expected 64 bytes for Ed25519)")?;
+    let signature = Signature::from_bytes(&sig_bytes);
+
+    key.verify(canonical.as_bytes(), &signature)
+        .map_err(|_| "Signature mismatch.".to_string())
+}
+
+/// Producer keys as `producer=hmac:<secret>` or `producer=ed25519:<hex public
+/// key>`, comma separated.
+pub fn parse_producer_keys(spec: &str) -> Result<HashMap<String, ProducerKey>, String> {
+    let mut keys = HashMap::new();
+    for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let (producer, key) = entry
+            .split_once('=')
+            .ok_or("Expected producer=alg:key entries")?;
+        let key = match key.split_once(':') {
+            Some(("hmac", secret)) if !secret.is_empty() => ProducerKey::Hmac(secret.to_string()),
+            Some(("ed25519", hex_key)) => ProducerKey::Ed25519(parse_ed25519_public_key(hex_key)?),
+            _ => return Err(format!("Unsupported key for producer {}", producer)),
+        };
+        keys.insert(producer.trim().to_string(), key);
+    }
+    Ok(keys)
+}
+
+/// Hex-encoded 32-byte Ed25519 public key.
+pub fn parse_ed25519_public_key(hex_key: &str) -> Result<VerifyingKey, String> {
+    let bytes: [u8; 32] = hex::decode(hex_key.trim())
+        .map_err(|_| "Invalid public key hex".to_string())?
+        .try_into()
+        .map_err(|_| "Invalid public key length (expected 32 bytes)".to_string())?;
+    VerifyingKey::from_bytes(&bytes).map_err(|_| "Invalid public key".to_string())
+}
+
 #[cfg(test)]
 mod tests {
-
+    use super::*;
     use serde_json::json;
 
     #[test]
@@ -202,4 +393,174 @@ mod tests {
         let serialized = serde_json::to_string(&payload).unwrap();
         assert_eq!(serialized, r#"{"a":1,"b":2,"c":[3,2,1]}"#);
     }
+
+    fn signed_envelope(sig_alg: Option<&str>, sign: impl Fn(&str) -> String) -> Value {
+        let ts = chrono::Utc::now().timestamp_millis();
+        let payload = json!({
+            "signal_id": "sig-ed25519",
+            "symbol": "BTC/USDT",
+            "direction": 1,
+            "type": "BUY_SETUP",
+            "size": 0.1,
+            "status": "PENDING",
+        });
+        let canonical = format!("{}.{}.{}", ts, "nonce-1", payload);
+        json!({
+            "type": "titan.cmd.execution.place.v1",
+            "version": 1,
+            "producer": "titan-brain",
+            "ts": ts,
+            "nonce": "nonce-1",
+            "sig": sign(&canonical),
+            "sig_alg": sig_alg,
+            "payload": payload,
+        })
+    }
+
+    fn validate(validator: &HmacValidator, value: &Value) -> Result<(), String> {
+        let envelope: IntentEnvelope = serde_json::from_value(value.clone()).unwrap();
+        validator.validate(&envelope, &value["payload"])
+    }
+
+    #[test]
+    fn test_ed25519_envelope_verifies_with_public_key_only() {
+        use ed25519_dalek::{Signer, SigningKey};
+        use rand::rngs::OsRng;
+
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let public_key_hex = hex::encode(signing_key.verifying_key().to_bytes());
+        let validator = HmacValidator::with_keys(
+            String::new(),
+            Some(parse_ed25519_public_key(&public_key_hex).unwrap()),
+            300,
+        );
+
+        let mut value = signed_envelope(Some("ed25519"), |canonical| {
+            hex::encode(signing_key.sign(canonical.as_bytes()).to_bytes())
+        });
+        assert_eq!(validate(&validator, &value), Ok(()));
+
+        // Tampered payload
+        value["payload"]["size"] = json!(10.0);
+        assert_eq!(
+            validate(&validator, &value),
+            Err("Signature mismatch.".to_string())
+        );
+
+        // No shared secret here, so HMAC envelopes cannot pass
+        let hmac_value = signed_envelope(None, |_| "00".repeat(32));
+        assert!(validate(&validator, &hmac_value).is_err());
+    }
+
+    #[test]
+    fn test_sig_alg_dispatch_defaults_to_hmac_and_rejects_unknown() {
+        let secret = "test-secret";
+        let validator = HmacValidator::with_keys(secret.to_string(), None, 300);
+        let hmac_sign = |canonical: &str| {
+            let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+            mac.update(canonical.as_bytes());
+            hex::encode(mac.finalize().into_bytes())
+        };
+
+        assert_eq!(
+            validate(&validator, &signed_envelope(None, hmac_sign)),
+            Ok(())
+        );
+        assert_eq!(
+            validate(&validator, &signed_envelope(Some("hmac-sha256"), hmac_sign)),
+            Ok(())
+        );
+        assert_eq!(
+            validate(&validator, &signed_envelope(Some("rsa"), hmac_sign)),
+            Err("Unsupported signature algorithm: rsa".to_string())
+        );
+        assert!(validate(&validator, &signed_envelope(Some("ed25519"), hmac_sign)).is_err());
+    }
+
+    fn hmac_signer(secret: &'static str) -> impl Fn(&str) -> String {
+        move |canonical: &str| {
+            let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+            mac.update(canonical.as_bytes());
+            hex::encode(mac.finalize().into_bytes())
+        }
+    }
+
+    /// Envelope from `producer`, signed over the producer-bound canonical string
+    fn producer_envelope(producer: &str, sign: impl Fn(&str) -> String) -> Value {
+        let mut value = signed_envelope(None, |_| String::new());
+        value["producer"] = json!(producer);
+        let canonical = format!(
+            "{}.{}.{}.{}",
+            value["ts"], "nonce-1", producer, value["payload"]
+        );
+        value["sig"] = json!(sign(&canonical));
+        value
+    }
+
+    #[test]
+    fn test_producer_allowlist_rejects_signed_envelope_from_unknown_producer() {
+        let keys = parse_producer_keys("titan-brain=hmac:brain-secret").unwrap();
+        let validator = HmacValidator::with_keys("shared-secret".to_string(), None, 300)
+            .with_producer_keys(keys)
+            .with_allowed_producers(&["titan-brain".to_string()]);
+
+        let allowed = producer_envelope("titan-brain", hmac_signer("brain-secret"));
+        assert_eq!(validate(&validator, &allowed), Ok(()));
+
+        // Producers off the list are refused however they are signed
+        let mut relabelled = allowed.clone();
+        relabelled["producer"] = json!("titan-scavenger");
+        assert_eq!(
+            validate(&validator, &relabelled),
+            Err("Producer not allowed: titan-scavenger".to_string())
+        );
+        assert!(!validator.is_producer_allowed("titan-scavenger"));
+
+        // The shared secret no longer vouches for an allowed producer
+        let shared = signed_envelope(None, hmac_signer("shared-secret"));
+        assert_eq!(
+            validate(&validator, &shared),
+            Err("Signature mismatch.".to_string())
+        );
+
+        // No allowlist configured: any correctly signed producer passes
+        let open = HmacValidator::with_keys("shared-secret".to_string(), None, 300)
+            .with_allowed_producers(&[]);
+        let mut unknown = signed_envelope(None, hmac_signer("shared-secret"));
+        unknown["producer"] = json!("titan-scavenger");
+        assert_eq!(validate(&open, &unknown), Ok(()));
+    }
+
+    #[test]
+    fn test_producer_key_binds_producer_name() {
+        let keys =
+            parse_producer_keys("titan-brain=hmac:brain-secret, titan-hunter=hmac:hunter-secret")
+                .unwrap();
+        let validator = HmacValidator::with_keys(String::new(), None, 300)
+            .with_producer_keys(keys)
+            .with_allowed_producers(&["titan-brain".to_string(), "titan-hunter".to_string()]);
+
+        // The hunter's key cannot sign for the brain
+        let forged = producer_envelope("titan-brain", hmac_signer("hunter-secret"));
+        assert_eq!(
+            validate(&validator, &forged),
+            Err("Signature mismatch.".to_string())
+        );
+        let own = producer_envelope("titan-hunter", hmac_signer("hunter-secret"));
+        assert_eq!(validate(&validator, &own), Ok(()));
+
+        // An allowed producer without a key of its own is refused
+        let keyless = HmacValidator::with_keys("shared-secret".to_string(), None, 300)
+            .with_allowed_producers(&["titan-brain".to_string()]);
+        assert_eq!(
+            validate(
+                &keyless,
+                &signed_envelope(None, hmac_signer("shared-secret"))
+            ),
+            Err("No signing key configured for producer titan-brain".to_string())
+        );
+
+        assert!(parse_producer_keys("titan-brain=rsa:abc").is_err());
+        assert!(parse_producer_keys("titan-brain").is_err());
+    }
 }
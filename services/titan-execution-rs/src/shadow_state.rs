@@ -1,15 +1,26 @@
-use crate::context::ExecutionContext;
+use crate::alerts::{Alert, AlertSink, KIND_OVERFILL};
+use crate::config::LotMethod;
+use crate::context::{ExecutionContext, TimeProvider};
+use crate::exchange::adapter::OrderStatus;
 use crate::exposure::{ExposureCalculator, ExposureMetrics};
+use crate::fill_sanity::{
+    check_overfill, FillAnomaly, FillPriceGuard, OverfillAnomaly, DEFAULT_OVERFILL_TOLERANCE_PCT,
+};
 use crate::metrics;
-use crate::model::{Intent, IntentStatus, IntentType, Position, Side, TradeRecord};
-use crate::persistence::store::PersistenceStore;
-use chrono::Utc;
-
-use rust_decimal::prelude::ToPrimitive;
+use crate::model::{
+    EntryLot, FundingRecord, Intent, IntentStatus, IntentType, LotClose, Position, Side,
+    TradeRecord,
+};
+use crate::persistence::store::{FillCommit, PersistenceStore};
+use crate::persistence::wal::WalEntry;
+use crate::risk_policy::DEFAULT_DEDUP_TTL_MS;
+
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use tokio::sync::mpsc::UnboundedSender;
 use tracing::{error, info, warn};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +30,8 @@ pub enum ExecutionEvent {
     Closed(TradeRecord),
     FundingPaid(String, Decimal, String), // Symbol, Amount, Asset
     BalanceUpdated(Decimal, Decimal),     // Total Equity, Available Cash
+    FillAnomaly(FillAnomaly),             // Fill rejected by the price sanity check
+    Overfill(OverfillAnomaly),            // Fill past its intent size; only the remainder applied
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,11 +42,53 @@ pub struct OrderChild {
     pub size: Decimal,
     pub created_at: i64,
     #[serde(default)]
-    pub status: String, // "FILLED", "REJECTED", "PENDING"
+    pub status: OrderStatus,
+    /// Quantity confirmed against this order so far
+    #[serde(default)]
+    pub filled: Decimal,
+}
+
+/// Cash held back for a working order until it fills, is cancelled or rejected.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CashReservation {
+    pub amount: Decimal,
+    /// Order quantity still unfilled; fills release the reservation pro rata
+    pub open_qty: Decimal,
+}
+
+/// Equity at the start of a trading day, the anchor for intraday PnL.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyOpen {
+    /// Start of the trading day (ms)
+    pub day_start: i64,
+    pub equity: Decimal,
 }
 
-// Constants
-const MAX_TRADE_HISTORY: usize = 5000;
+/// Start (ms) of the trading day containing `now_ms`, for days that begin at
+/// `reset_hour_utc`.
+pub fn trading_day_start(now_ms: i64, reset_hour_utc: u32) -> i64 {
+    const HOUR_MS: i64 = 3_600_000;
+    const DAY_MS: i64 = 24 * HOUR_MS;
+    let offset = i64::from(reset_hour_utc) * HOUR_MS;
+    (now_ms - offset).div_euclid(DAY_MS) * DAY_MS + offset
+}
+
+/// Closed trades kept in memory unless configured otherwise
+pub const DEFAULT_TRADE_HISTORY_CAP: usize = 5000;
+/// Time from ingress a partially filled intent may keep working before the
+/// remainder is cancelled, unless the intent sets `partial_fill_timeout_ms`
+pub const DEFAULT_PARTIAL_FILL_BUDGET_MS: i64 = 5000;
+
+fn partial_fill_budget(intent: &Intent, default_ms: i64) -> i64 {
+    intent
+        .metadata
+        .as_ref()
+        .and_then(|m| m.get("partial_fill_timeout_ms"))
+        .and_then(|v| v.as_i64())
+        .filter(|ms| *ms > 0)
+        .unwrap_or(default_ms)
+}
 
 pub struct ShadowState {
     positions: HashMap<String, Position>,
@@ -45,6 +100,35 @@ pub struct ShadowState {
     ctx: Arc<ExecutionContext>,
     cash_balance: Decimal,
     initial_balance: Decimal,
+    /// Highest equity seen (persisted); trailing drawdown is measured from here
+    equity_hwm: Decimal,
+    /// Opening equity of the current trading day (persisted)
+    daily_open: Option<DailyOpen>,
+    /// UTC hour at which the trading day, and with it intraday PnL, resets
+    daily_reset_hour_utc: u32,
+    fill_price_guard: Option<FillPriceGuard>,
+    /// Fills may take an intent at most this far (%) past its size
+    overfill_tolerance_pct: Decimal,
+    alert_sink: Option<Arc<dyn AlertSink>>,
+    /// causation_id dedup window for intents without their own ttl_ms (from policy)
+    dedup_ttl_ms: i64,
+    /// Partial-fill budget for intents without their own `partial_fill_timeout_ms`
+    partial_fill_budget_ms: i64,
+    /// Per-signal cash reservations for working orders
+    cash_reservations: HashMap<String, CashReservation>,
+    /// Some while a fill is being applied: store writes are held back and
+    /// committed with the processed-fill marker (closed trades collect here)
+    staged_trades: Option<Vec<TradeRecord>>,
+    /// Symbol -> signal source of the open position, for strategy metrics
+    position_sources: HashMap<String, String>,
+    /// Order in which entry lots are consumed by closes
+    lot_method: LotMethod,
+    /// Ticks older than this (ms) are not used to mark positions
+    max_valuation_tick_age_ms: Option<i64>,
+    /// Symbols whose last tick was too old to mark the position with
+    stale_valuations: HashSet<String>,
+    /// Every closed trade, whichever path closed it, for the loss-streak breaker
+    trade_outcomes: Option<UnboundedSender<TradeRecord>>,
 }
 
 impl ShadowState {
@@ -54,7 +138,7 @@ impl ShadowState {
         initial_balance_f64: Option<f64>,
     ) -> Self {
         let initial = if let Some(b) = initial_balance_f64 {
-            Decimal::from_f64_retain(b).unwrap_or(Decimal::ZERO)
+            Decimal::from_f64(b).unwrap_or(Decimal::ZERO)
         } else {
             Decimal::ZERO
         };
@@ -63,17 +147,118 @@ impl ShadowState {
             positions: HashMap::new(),
             pending_intents: HashMap::new(),
             trade_history: Vec::new(),
-            max_trade_history: MAX_TRADE_HISTORY,
+            max_trade_history: DEFAULT_TRADE_HISTORY_CAP,
             order_children: HashMap::new(),
             persistence,
             ctx,
             cash_balance: initial,
             initial_balance: initial,
+            equity_hwm: initial,
+            daily_open: None,
+            daily_reset_hour_utc: 0,
+            fill_price_guard: None,
+            overfill_tolerance_pct: Decimal::from(DEFAULT_OVERFILL_TOLERANCE_PCT),
+            alert_sink: None,
+            dedup_ttl_ms: DEFAULT_DEDUP_TTL_MS,
+            partial_fill_budget_ms: DEFAULT_PARTIAL_FILL_BUDGET_MS,
+            cash_reservations: HashMap::new(),
+            staged_trades: None,
+            position_sources: HashMap::new(),
+            lot_method: LotMethod::Fifo,
+            max_valuation_tick_age_ms: None,
+            stale_valuations: HashSet::new(),
+            trade_outcomes: None,
         };
         state.hydrate_from_persistence();
+        state.roll_daily_open();
+        state.refresh_equity_hwm();
         state
     }
 
+    /// Reject fills whose price is implausibly far from the current mid.
+    pub fn set_fill_price_guard(&mut self, guard: FillPriceGuard) {
+        self.fill_price_guard = Some(guard);
+    }
+
+    /// Refuse fills that would take an intent more than `pct` percent past its size.
+    pub fn set_overfill_tolerance_pct(&mut self, pct: Decimal) {
+        self.overfill_tolerance_pct = pct;
+    }
+
+    /// Raise alerts for fills refused by the over-fill check
+    pub fn set_alert_sink(&mut self, sink: Arc<dyn AlertSink>) {
+        self.alert_sink = Some(sink);
+    }
+
+    /// Send every trade closed from here on to `tx`
+    pub fn set_trade_outcome_sender(&mut self, tx: UnboundedSender<TradeRecord>) {
+        self.trade_outcomes = Some(tx);
+    }
+
+    /// Start the trading day at `hour` UTC, re-anchoring intraday PnL if that
+    /// moves the current day's start.
+    pub fn set_daily_reset_hour_utc(&mut self, hour: u32) {
+        self.daily_reset_hour_utc = hour;
+        self.roll_daily_open();
+    }
+
+    /// Clock every time read in the shadow state goes through.
+    pub fn time(&self) -> Arc<dyn TimeProvider> {
+        self.ctx.time.clone()
+    }
+
+    pub fn set_dedup_ttl_ms(&mut self, ttl_ms: i64) {
+        self.dedup_ttl_ms = ttl_ms;
+    }
+
+    pub fn set_partial_fill_budget_ms(&mut self, budget_ms: i64) {
+        self.partial_fill_budget_ms = budget_ms;
+    }
+
+    pub fn set_lot_method(&mut self, method: LotMethod) {
+        self.lot_method = method;
+    }
+
+    /// Ignore ticks older than `max_age_ms` when marking positions, leaving
+    /// the symbol flagged stale until a fresh tick arrives.
+    pub fn set_max_valuation_tick_age_ms(&mut self, max_age_ms: i64) {
+        self.max_valuation_tick_age_ms = Some(max_age_ms);
+    }
+
+    /// Cap the in-memory trade history, reloading the most recent trades from
+    /// the store so a larger cap takes effect immediately.
+    pub fn set_max_trade_history(&mut self, max: usize) {
+        self.max_trade_history = max;
+        match self.persistence.load_recent_trades(max) {
+            Ok(trades) => self.trade_history = trades,
+            Err(e) => {
+                error!("Failed to reload trade history: {}", e);
+                let excess = self.trade_history.len().saturating_sub(max);
+                self.trade_history.drain(..excess);
+            }
+        }
+    }
+
+    /// Partial-fill budget of `intent`: `metadata.partial_fill_timeout_ms` when
+    /// set, else the configured default.
+    pub fn partial_fill_budget_ms(&self, intent: &Intent) -> i64 {
+        partial_fill_budget(intent, self.partial_fill_budget_ms)
+    }
+
+    /// (signal_id, symbol) of partially filled intents whose budget has run out.
+    pub fn expired_partial_fills(&self) -> Vec<(String, String)> {
+        let now = self.ctx.time.now_millis();
+        self.pending_intents
+            .values()
+            .filter(|i| i.status == IntentStatus::PartiallyFilled)
+            .filter(|i| {
+                i.t_ingress
+                    .is_some_and(|t0| now > t0 + self.partial_fill_budget_ms(i))
+            })
+            .map(|i| (i.signal_id.clone(), i.symbol.clone()))
+            .collect()
+    }
+
     fn hydrate_from_persistence(&mut self) {
         match self.persistence.load_positions() {
             Ok(positions) => {
@@ -88,7 +273,8 @@ impl ShadowState {
 
         match self.persistence.load_intents() {
             Ok(intents) => {
-                for intent in intents {
+                // Terminal intents stay in the store as records, not as work
+                for intent in intents.into_iter().filter(|i| i.status.is_active()) {
                     self.pending_intents
                         .insert(intent.signal_id.clone(), intent);
                 }
@@ -97,6 +283,47 @@ impl ShadowState {
             Err(e) => error!("Failed to hydrate intents: {}", e),
         }
 
+        // Child orders of the hydrated intents, so orders they left resting are still tracked
+        match self.persistence.load_placed_orders() {
+            Ok(entries) => {
+                for entry in entries {
+                    let WalEntry::OrderPlaced {
+                        signal_id,
+                        exchange,
+                        client_order_id,
+                        request_payload,
+                    } = entry
+                    else {
+                        continue;
+                    };
+                    if !self.pending_intents.contains_key(&signal_id) {
+                        continue;
+                    }
+                    self.order_children
+                        .entry(signal_id)
+                        .or_default()
+                        .push(OrderChild {
+                            exchange,
+                            client_order_id,
+                            execution_order_id: request_payload["execution_id"]
+                                .as_str()
+                                .unwrap_or_default()
+                                .to_string(),
+                            size: serde_json::from_value(request_payload["size"].clone())
+                                .unwrap_or_default(),
+                            created_at: request_payload["created_at"].as_i64().unwrap_or_default(),
+                            status: OrderStatus::Pending,
+                            filled: Decimal::ZERO,
+                        });
+                }
+                info!(
+                    "Child orders hydrated: {}",
+                    self.order_children.values().map(Vec::len).sum::<usize>()
+                );
+            }
+            Err(e) => error!("Failed to hydrate child orders: {}", e),
+        }
+
         match self.persistence.load_recent_trades(self.max_trade_history) {
             Ok(trades) => {
                 self.trade_history = trades;
@@ -111,11 +338,20 @@ impl ShadowState {
 
         // Hydrate Cash Balance
         match self.persistence.load_metadata("cash_balance") {
-            Ok(Some(val)) => {
-                if let Some(f) = val.as_f64() {
-                    self.cash_balance = Decimal::from_f64_retain(f).unwrap_or(self.initial_balance);
+            Ok(Some(serde_json::Value::String(s))) => match s.parse::<Decimal>() {
+                Ok(balance) => {
+                    self.cash_balance = balance;
                     info!("Cash Balance hydrated: {}", self.cash_balance);
                 }
+                Err(e) => error!("Invalid persisted cash balance '{}': {}", s, e),
+            },
+            Ok(Some(val)) => {
+                // Legacy f64 balance: migrate to the decimal string representation
+                if let Some(balance) = val.as_f64().and_then(Decimal::from_f64) {
+                    self.cash_balance = balance;
+                    info!("Cash Balance hydrated (migrated from f64): {}", balance);
+                    self.persist_cash_balance();
+                }
             }
             Ok(None) => {
                 info!(
@@ -126,6 +362,30 @@ impl ShadowState {
             }
             Err(e) => error!("Failed to hydrate cash balance: {}", e),
         }
+
+        match self.persistence.load_metadata("equity_hwm") {
+            Ok(Some(serde_json::Value::String(s))) => match s.parse::<Decimal>() {
+                Ok(hwm) => {
+                    self.equity_hwm = hwm;
+                    info!("Equity HWM hydrated: {}", hwm);
+                }
+                Err(e) => error!("Invalid persisted equity HWM '{}': {}", s, e),
+            },
+            Ok(_) => {}
+            Err(e) => error!("Failed to hydrate equity HWM: {}", e),
+        }
+
+        match self.persistence.load_metadata("daily_open") {
+            Ok(Some(val)) => match serde_json::from_value::<DailyOpen>(val) {
+                Ok(open) => {
+                    self.daily_open = Some(open);
+                    info!("Daily opening equity hydrated: {}", open.equity);
+                }
+                Err(e) => error!("Invalid persisted daily open: {}", e),
+            },
+            Ok(None) => {}
+            Err(e) => error!("Failed to hydrate daily open: {}", e),
+        }
     }
 
     pub fn process_intent(&mut self, mut intent: Intent) -> Intent {
@@ -138,6 +398,23 @@ impl ShadowState {
             return intent;
         }
 
+        // A replay of a signal that already ran to a terminal state must not
+        // open again, even under a fresh causation_id
+        match self.persistence.load_intent(&intent.signal_id) {
+            Ok(Some(existing)) if !existing.status.is_active() => {
+                metrics::inc_intent_dedup_hits();
+                warn!(signal_id = %intent.signal_id, status = ?existing.status, "Intent already terminal - ignoring replay");
+                return existing;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                error!("Terminal intent lookup failed: {}", e);
+                intent.status = IntentStatus::Rejected;
+                intent.rejection_reason = Some("Idempotency check failed".to_string());
+                return intent;
+            }
+        }
+
         // --- Phase 2: Shadow Reconciliation (ForceSync) ---
         if let IntentType::ForceSync = intent.intent_type {
             info!(signal_id = %intent.signal_id, "FORCE SYNC: Overwriting state for {}", intent.symbol);
@@ -167,7 +444,7 @@ impl ShadowState {
                     stop_loss: intent.stop_loss,
                     take_profits: intent.take_profits.clone(),
                     signal_id: intent.signal_id.clone(),
-                    opened_at: Utc::now(),
+                    opened_at: self.ctx.time.now(),
                     regime_state: intent.regime_state,
                     phase: intent.phase,
                     metadata: intent.metadata.clone(),
@@ -179,7 +456,8 @@ impl ShadowState {
                     fees_paid: Decimal::ZERO,
                     funding_paid: Decimal::ZERO,
                     last_mark_price: None,
-                    last_update_ts: Utc::now().timestamp_millis(),
+                    last_update_ts: self.ctx.time.now_millis(),
+                    entry_lots: Vec::new(),
                 };
 
                 self.positions
@@ -199,11 +477,11 @@ impl ShadowState {
 
         // 1. Idempotency Check (Explicit)
         if let Some(causation_id) = &intent.causation_id {
-            match self
-                .persistence
-                .check_idempotency(causation_id, intent.ttl_ms.unwrap_or(5000))
-            {
+            let dedup_ttl_ms = intent.ttl_ms.unwrap_or(self.dedup_ttl_ms);
+            let now = self.ctx.time.now_millis();
+            match self.persistence.check_idempotency(causation_id, now) {
                 Ok(false) => {
+                    metrics::inc_intent_dedup_hits();
                     warn!(signal_id = %intent.signal_id, causation_id = %causation_id, "Duplicate causation_id detected - rejecting");
                     intent.status = IntentStatus::Rejected;
                     intent.rejection_reason = Some("Duplicate causation_id".to_string());
@@ -216,10 +494,11 @@ impl ShadowState {
                     return intent;
                 }
                 Ok(true) => {
+                    metrics::inc_intent_dedup_misses();
                     // Mark as seen (At Most Once)
-                    if let Err(e) = self
-                        .persistence
-                        .set_idempotency(causation_id, intent.ttl_ms.unwrap_or(5000))
+                    if let Err(e) =
+                        self.persistence
+                            .set_idempotency(causation_id, dedup_ttl_ms, now)
                     {
                         error!("Failed to set idempotency key: {}", e);
                         intent.status = IntentStatus::Rejected;
@@ -274,6 +553,7 @@ impl ShadowState {
         if let Some(mut intent) = self.pending_intents.remove(signal_id) {
             intent.status = IntentStatus::Rejected;
             intent.rejection_reason = Some(reason.clone());
+            self.release_cash(signal_id, None);
 
             // Retain for audit trail
             if let Err(e) = self.persistence.save_intent(&intent) {
@@ -300,6 +580,7 @@ impl ShadowState {
         if let Some(mut intent) = self.pending_intents.remove(signal_id) {
             intent.status = IntentStatus::Expired;
             intent.rejection_reason = Some(reason.clone());
+            self.release_cash(signal_id, None);
 
             // Retain for audit trail
             if let Err(e) = self.persistence.save_intent(&intent) {
@@ -322,6 +603,86 @@ impl ShadowState {
         None
     }
 
+    /// Close a `simulate_only` intent once its shadow fill is out: no venue
+    /// order exists, so it leaves no position and its cash is released.
+    pub fn close_simulated_intent(&mut self, signal_id: &str) -> Option<Intent> {
+        if let Some(mut intent) = self.pending_intents.remove(signal_id) {
+            intent.status = IntentStatus::Cancelled;
+            intent.rejection_reason = Some("Simulation only".to_string());
+            self.release_cash(signal_id, None);
+
+            // Retain for audit trail
+            if let Err(e) = self.persistence.save_intent(&intent) {
+                error!(
+                    "Failed to update intent persistence (SIMULATED) {}: {}",
+                    signal_id, e
+                );
+            }
+
+            info!(
+                signal_id = %signal_id,
+                symbol = %intent.symbol,
+                "SIMULATED - Shadow fill only, position state NOT updated"
+            );
+
+            return Some(intent);
+        }
+        warn!(signal_id = %signal_id, "Intent not found for simulated close");
+        None
+    }
+
+    /// Close a partially filled intent once its remainder is cancelled: the
+    /// fills stand, the intent becomes PartiallyCompleted and its cash is released.
+    pub fn complete_partial_intent(&mut self, signal_id: &str, reason: String) -> Option<Intent> {
+        if let Some(mut intent) = self.pending_intents.remove(signal_id) {
+            intent.status = IntentStatus::PartiallyCompleted;
+            intent.rejection_reason = Some(reason.clone());
+            self.release_cash(signal_id, None);
+
+            // Retain for audit trail
+            if let Err(e) = self.persistence.save_intent(&intent) {
+                error!(
+                    "Failed to update intent persistence (PARTIALLY_COMPLETED) {}: {}",
+                    signal_id, e
+                );
+            }
+
+            warn!(
+                signal_id = %signal_id,
+                reason = %reason,
+                symbol = %intent.symbol,
+                filled = %intent.filled_size,
+                size = %intent.size,
+                "PARTIALLY COMPLETED - Remainder cancelled"
+            );
+
+            return Some(intent);
+        }
+        warn!(signal_id = %signal_id, "Intent not found for partial completion");
+        None
+    }
+
+    /// The venue cancelled child `order_id` after part of it executed (that
+    /// part already confirmed). Once no other child is working, a partially
+    /// filled intent is completed with what it has.
+    pub fn settle_cancelled_child(&mut self, signal_id: &str, order_id: &str) -> Option<Intent> {
+        let children = self.order_children.get_mut(signal_id)?;
+        for child in children.iter_mut() {
+            if child.execution_order_id == order_id || child.client_order_id == order_id {
+                child.status = OrderStatus::Cancelled;
+            }
+        }
+        let still_working = children.iter().any(|c| !c.status.is_terminal());
+        let partially_filled = self
+            .pending_intents
+            .get(signal_id)
+            .is_some_and(|i| i.status == IntentStatus::PartiallyFilled);
+        if still_working || !partially_filled {
+            return None;
+        }
+        self.complete_partial_intent(signal_id, "Remainder cancelled by venue".to_string())
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn confirm_execution(
         &mut self,
@@ -340,29 +701,127 @@ impl ShadowState {
         );
         let mut events = Vec::new();
 
+        // Price sanity: an anomalous fill must not touch intent or position state
+        if let (true, Some(guard), Some(intent)) = (
+            filled,
+            &self.fill_price_guard,
+            self.pending_intents.get(signal_id),
+        ) {
+            if let Err(anomaly) = guard.check(
+                signal_id,
+                child_order_id,
+                &intent.symbol,
+                exchange,
+                fill_price,
+            ) {
+                error!(
+                    signal_id = %signal_id,
+                    child_id = %child_order_id,
+                    symbol = %anomaly.symbol,
+                    fill_price = %anomaly.fill_price,
+                    mid = %anomaly.mid,
+                    deviation_pct = %anomaly.deviation_pct,
+                    "🚨 FILL ANOMALY - fill price too far from mid, not applied"
+                );
+                metrics::inc_fill_anomalies();
+                events.push(ExecutionEvent::FillAnomaly(anomaly));
+                return events;
+            }
+        }
+
+        // Durable dedupe: the in-memory child_fills may be rebuilt from an
+        // intent record that lags the last applied fill. Child ids are only
+        // unique within their intent, so the marker is scoped to the signal.
+        let fill_id = format!("{}:{}:{}", signal_id, exchange, child_order_id);
+        if filled {
+            match self.persistence.is_fill_processed(&fill_id) {
+                Ok(false) => {}
+                Ok(true) => {
+                    warn!(signal_id = %signal_id, fill_id = %fill_id, "Fill already applied - ignoring redelivery");
+                    return events;
+                }
+                Err(e) => {
+                    // Better to stall the fill than risk applying it twice
+                    error!(fill_id = %fill_id, "Processed-fill lookup failed, not applied: {}", e);
+                    return events;
+                }
+            }
+        }
+
+        // Over-fill: a double-counted or excess fill must not grow the position
+        // past what the intent asked for. The venue did fill it, so the part up
+        // to the intent size is still applied and only the excess is held back.
+        let mut fill_size = fill_size;
+        let mut fee = fee;
+        if let (true, Some(intent)) = (filled, self.pending_intents.get(signal_id)) {
+            let is_duplicate = intent.child_fills.iter().any(|c| c == child_order_id);
+            if !is_duplicate {
+                if let Err(overfill) = check_overfill(
+                    intent,
+                    child_order_id,
+                    exchange,
+                    fill_size,
+                    self.overfill_tolerance_pct,
+                ) {
+                    error!(
+                        signal_id = %signal_id,
+                        child_id = %child_order_id,
+                        symbol = %overfill.symbol,
+                        intent_size = %overfill.intent_size,
+                        filled_size = %overfill.filled_size,
+                        fill_size = %overfill.fill_size,
+                        applied = %overfill.applied,
+                        excess = %overfill.excess,
+                        "🚨 OVER-FILL - fill exceeds intent size, excess not applied"
+                    );
+                    metrics::inc_overfills();
+                    if let Some(sink) = &self.alert_sink {
+                        sink.send(Alert::critical(
+                            KIND_OVERFILL,
+                            format!(
+                                "Over-fill on {} ({}): {} filled + {} exceeds intent size {}; applied {}, quarantined {}",
+                                overfill.symbol,
+                                overfill.exchange,
+                                overfill.filled_size,
+                                overfill.fill_size,
+                                overfill.intent_size,
+                                overfill.applied,
+                                overfill.excess
+                            ),
+                        ));
+                    }
+                    let applied = overfill.applied;
+                    events.push(ExecutionEvent::Overfill(overfill));
+                    if applied.is_zero() {
+                        return events;
+                    }
+                    fee = fee * applied / fill_size;
+                    fill_size = applied;
+                }
+            }
+        }
+
         // 0. Update Child Order Status
-        if let Some(children) = self.order_children.get_mut(signal_id) {
+        let already_applied = self
+            .pending_intents
+            .get(signal_id)
+            .is_some_and(|i| i.child_fills.iter().any(|c| c == child_order_id));
+        if let (false, Some(children)) = (already_applied, self.order_children.get_mut(signal_id)) {
             for child in children {
                 // Heuristic: matching execution_id OR client_order_id if execution_id unknown
                 if child.execution_order_id == child_order_id
                     || child.client_order_id == child_order_id
                 {
-                    match filled {
-                        true => child.status = "FILLED".to_string(),
-                        false => child.status = "REJECTED".to_string(), // Or Partial if fill_size > 0
+                    if filled {
+                        child.filled += fill_size;
                     }
-                    // Refined logic: if fill_size > 0 but not filled, assume PARTIAL?
-                    // Function arg `filled` means "Is this a terminal fill/reject event?"
-                    // Wait, `filled` arg is boolean? "filled: bool".
-                    // Looking at usage, it means "Is this a valid fill (true) or a reject (false)?"
-
-                    if !filled {
-                        child.status = "REJECTED".to_string();
-                    } else if fill_size < child.size {
-                        child.status = "PARTIALLY_FILLED".to_string();
+                    child.status = if !filled {
+                        OrderStatus::Rejected
+                    } else if child.filled < child.size {
+                        OrderStatus::PartiallyFilled
                     } else {
-                        child.status = "FILLED".to_string();
-                    }
+                        OrderStatus::Filled
+                    };
                 }
             }
         }
@@ -411,8 +870,9 @@ impl ShadowState {
 
                         // Time Budget Check (Lazy)
                         let now = self.ctx.time.now_millis();
+                        let budget_ms = partial_fill_budget(intent, self.partial_fill_budget_ms);
                         if let Some(t0) = intent.t_ingress {
-                            if now > t0 + 5000 {
+                            if now > t0 + budget_ms {
                                 warn!(signal_id = %signal_id, "Time Budget Exceeded during Partial Fill");
                                 intent.status = IntentStatus::PartiallyCompleted;
                                 true // Remove (Terminal Partial)
@@ -424,18 +884,19 @@ impl ShadowState {
                         }
                     };
 
-                    // Save State if NOT complete/removing (if removing, we delete later)
-                    if !is_complete {
-                        if let Err(e) = self.persistence.save_intent(intent) {
-                            error!("Failed to update intent state: {}", e);
-                        }
-                    }
-
+                    // Intent state is persisted with the fill (see commit_fill)
                     (is_complete, Some(intent.clone()))
                 }
             }
         };
 
+        // Filled or rejected, this child no longer needs cash held back for it
+        if should_remove {
+            self.release_cash(signal_id, None);
+        } else if filled {
+            self.release_cash(signal_id, Some(fill_size));
+        }
+
         let intent = if let Some(i) = intent_snapshot {
             i
         } else {
@@ -446,21 +907,28 @@ impl ShadowState {
         if !filled {
             if should_remove {
                 self.pending_intents.remove(signal_id);
-                if let Err(e) = self.persistence.delete_intent(signal_id) {
-                    error!("Failed to delete intent persistence {}: {}", signal_id, e);
+                // Retain for audit trail
+                if let Err(e) = self.persistence.save_intent(&intent) {
+                    error!(
+                        "Failed to update intent persistence (REJECTED) {}: {}",
+                        signal_id, e
+                    );
                 }
             }
             return events;
         }
 
         // --- POSITION LOGIC (Driven by Snapshot) ---
+        // Store writes from here on are staged and committed atomically
+        self.staged_trades = Some(Vec::new());
         let symbol = intent.symbol.clone();
-        let intent_type = intent.intent_type;
+        let intent_type = intent.intent_type.clone();
         let direction = intent.direction;
         let stop_loss = intent.stop_loss;
         let take_profits = intent.take_profits.clone();
         let regime_state = intent.regime_state;
         let phase = intent.phase;
+        let source = intent.source.clone();
 
         match intent_type {
             IntentType::CloseLong | IntentType::CloseShort | IntentType::Close => {
@@ -477,10 +945,8 @@ impl ShadowState {
                 }
                 if should_remove {
                     self.pending_intents.remove(signal_id);
-                    if let Err(e) = self.persistence.delete_intent(signal_id) {
-                        error!("Failed to delete intent persistence {}: {}", signal_id, e);
-                    }
                 }
+                self.commit_fill(&fill_id, &intent, &symbol);
                 return events;
             }
             _ => {}
@@ -506,13 +972,19 @@ impl ShadowState {
                         (old_val + new_val) / total_size
                     };
 
+                    // Only extend the lot history if it still accounts for the
+                    // whole position (not the case for venue-adopted positions)
+                    if Self::lots_cover(existing_position) {
+                        existing_position.entry_lots.push(EntryLot {
+                            fill_id: fill_id.clone(),
+                            price: fill_price,
+                            size: fill_size,
+                            opened_at: self.ctx.time.now_millis(),
+                        });
+                    }
                     existing_position.size = total_size;
                     existing_position.entry_price = avg_price;
                     existing_position.fees_paid += fee;
-
-                    if let Err(e) = self.persistence.save_position(existing_position) {
-                        error!("Failed to persist position update {}: {}", symbol, e);
-                    }
                     events.push(ExecutionEvent::Updated(existing_position.clone()));
                 }
             } else {
@@ -565,7 +1037,7 @@ impl ShadowState {
                         regime_state,
                         phase,
                         metadata: intent.metadata.clone(),
-                        exchange: Some("BYBIT".to_string()),
+                        exchange: Some(exchange.to_string()),
                         position_mode: Some("ONE_WAY".to_string()),
                         realized_pnl: Decimal::ZERO,
                         unrealized_pnl: Decimal::ZERO,
@@ -573,12 +1045,16 @@ impl ShadowState {
                         funding_paid: Decimal::ZERO,
                         last_mark_price: None,
                         last_update_ts: self.ctx.time.now_millis(),
+                        entry_lots: vec![EntryLot {
+                            fill_id: fill_id.clone(),
+                            price: fill_price,
+                            size: remainder,
+                            opened_at: self.ctx.time.now_millis(),
+                        }],
                     };
 
                     self.positions.insert(symbol.clone(), position.clone());
-                    if let Err(e) = self.persistence.save_position(&position) {
-                        error!("Failed to persist new position {}: {}", symbol, e);
-                    }
+                    self.track_position_source(&symbol, source.as_deref());
                     events.push(ExecutionEvent::Opened(position));
                 }
             }
@@ -596,7 +1072,7 @@ impl ShadowState {
                 regime_state,
                 phase,
                 metadata: intent.metadata.clone(),
-                exchange: Some("BYBIT".to_string()),
+                exchange: Some(exchange.to_string()),
                 position_mode: Some("ONE_WAY".to_string()),
                 realized_pnl: Decimal::ZERO,
                 unrealized_pnl: Decimal::ZERO,
@@ -604,25 +1080,46 @@ impl ShadowState {
                 funding_paid: Decimal::ZERO,
                 last_mark_price: None,
                 last_update_ts: self.ctx.time.now_millis(),
+                entry_lots: vec![EntryLot {
+                    fill_id: fill_id.clone(),
+                    price: fill_price,
+                    size: fill_size,
+                    opened_at: self.ctx.time.now_millis(),
+                }],
             };
             self.positions.insert(symbol.clone(), position.clone());
-            if let Err(e) = self.persistence.save_position(&position) {
-                error!("Failed to persist new position {}: {}", symbol, e);
-            }
+            self.track_position_source(&symbol, source.as_deref());
             events.push(ExecutionEvent::Opened(position));
         }
 
         // Final Cleanup
         if should_remove {
             self.pending_intents.remove(signal_id);
-            if let Err(e) = self.persistence.delete_intent(signal_id) {
-                error!("Failed to delete intent persistence {}: {}", signal_id, e);
-            }
         }
+        self.commit_fill(&fill_id, &intent, &symbol);
 
         events
     }
 
+    /// Write the staged effects of one fill plus its processed marker in a
+    /// single store transaction.
+    fn commit_fill(&mut self, fill_id: &str, intent: &Intent, symbol: &str) {
+        let trades = self.staged_trades.take().unwrap_or_default();
+        let commit = FillCommit {
+            fill_id,
+            signal_id: &intent.signal_id,
+            intent,
+            symbol,
+            position: self.positions.get(symbol),
+            trades: &trades,
+            cash_balance: self.cash_balance,
+            ts: self.ctx.time.now_millis(),
+        };
+        if let Err(e) = self.persistence.commit_fill(&commit) {
+            error!(fill_id = %fill_id, "Failed to commit fill: {}", e);
+        }
+    }
+
     fn calculate_pnl(
         side: &Side,
         entry_price: Decimal,
@@ -653,6 +1150,55 @@ impl ShadowState {
         }
     }
 
+    /// Whether the entry lots still add up to the position's size
+    fn lots_cover(position: &Position) -> bool {
+        !position.entry_lots.is_empty()
+            && position.entry_lots.iter().map(|l| l.size).sum::<Decimal>() == position.size
+    }
+
+    /// Size-weighted entry price of `lots`
+    fn lots_entry_price(lots: &[EntryLot]) -> Decimal {
+        let size: Decimal = lots.iter().map(|l| l.size).sum();
+        if size.is_zero() {
+            return Decimal::ZERO;
+        }
+        lots.iter().map(|l| l.price * l.size).sum::<Decimal>() / size
+    }
+
+    /// Split a position's lots into the portions closed by `close_size`
+    /// (consumed in `method` order) and what remains open, oldest first.
+    /// None if the lots don't account for the position.
+    fn split_lots(
+        position: &Position,
+        close_size: Decimal,
+        method: LotMethod,
+    ) -> Option<(Vec<EntryLot>, Vec<EntryLot>)> {
+        if !Self::lots_cover(position) {
+            return None;
+        }
+        let mut open = position.entry_lots.clone();
+        let mut closed = Vec::new();
+        let mut left = close_size;
+        while left > Decimal::ZERO {
+            let idx = match method {
+                LotMethod::Fifo => 0,
+                LotMethod::Lifo => open.len() - 1,
+            };
+            let lot = &mut open[idx];
+            let take = lot.size.min(left);
+            closed.push(EntryLot {
+                size: take,
+                ..lot.clone()
+            });
+            lot.size -= take;
+            left -= take;
+            if lot.size.is_zero() {
+                open.remove(idx);
+            }
+        }
+        Some((closed, open))
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn close_position(
         &mut self,
@@ -692,18 +1238,42 @@ impl ShadowState {
 
         let is_partial_close = actual_close_size < position.size;
 
-        let (pnl, pnl_pct) = Self::calculate_pnl(
-            &position.side,
-            position.entry_price,
-            exit_price,
-            actual_close_size,
-        );
+        // With a lot history, the closed size carries the cost basis of the
+        // lots it consumes; otherwise it is closed at the average entry
+        let lot_split = Self::split_lots(&position, actual_close_size, self.lot_method);
+        let lots: Vec<LotClose> = lot_split
+            .as_ref()
+            .map(|(closed, _)| {
+                closed
+                    .iter()
+                    .map(|lot| LotClose {
+                        fill_id: lot.fill_id.clone(),
+                        entry_price: lot.price,
+                        size: lot.size,
+                        pnl: Self::calculate_pnl(&position.side, lot.price, exit_price, lot.size).0,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let entry_price = lot_split
+            .as_ref()
+            .map(|(closed, _)| Self::lots_entry_price(closed))
+            .unwrap_or(position.entry_price);
+
+        let (pnl, pnl_pct) =
+            Self::calculate_pnl(&position.side, entry_price, exit_price, actual_close_size);
+        // Exact per-lot sum, free of the average's rounding
+        let pnl = if lots.is_empty() {
+            pnl
+        } else {
+            lots.iter().map(|l| l.pnl).sum()
+        };
 
         let trade_record = TradeRecord {
             signal_id: position.signal_id.clone(),
             symbol: symbol.to_string(),
             side: position.side.clone(),
-            entry_price: position.entry_price,
+            entry_price,
             exit_price,
             size: actual_close_size,
             pnl,
@@ -714,14 +1284,29 @@ impl ShadowState {
             metadata: position.metadata.clone(),
             fee,
             fee_asset,
+            lots,
         };
 
-        if let Err(e) = self.persistence.save_trade(&trade_record) {
+        if let Some(staged) = self.staged_trades.as_mut() {
+            staged.push(trade_record.clone());
+        } else if let Err(e) = self.persistence.save_trade(&trade_record) {
             error!(
                 "Failed to persist trade record {}: {}",
                 trade_record.signal_id, e
             );
         }
+        if let Some(tx) = &self.trade_outcomes {
+            let _ = tx.send(trade_record.clone());
+        }
+
+        metrics::record_closed_trade(
+            symbol,
+            self.position_sources
+                .get(symbol)
+                .map(String::as_str)
+                .unwrap_or("unknown"),
+            pnl.to_f64().unwrap_or(0.0),
+        );
 
         // Update Cash Balance (PnL - Fee)
         // Check if fee is same asset as PnL (Quote). Assuming yes for now.
@@ -730,14 +1315,21 @@ impl ShadowState {
 
         self.trade_history.push(trade_record.clone());
         if self.trade_history.len() > self.max_trade_history {
-            self.trade_history.remove(0); // O(n) but simple for Vec. Deque might be better if frequent.
+            let excess = self.trade_history.len() - self.max_trade_history;
+            self.trade_history.drain(..excess);
         }
 
         if is_partial_close {
             if let Some(real_pos) = self.positions.get_mut(symbol) {
                 real_pos.size -= actual_close_size;
-                if let Err(e) = self.persistence.save_position(real_pos) {
-                    error!("Failed to persist partial close {}: {}", symbol, e);
+                if let Some((_, open)) = lot_split {
+                    real_pos.entry_price = Self::lots_entry_price(&open);
+                    real_pos.entry_lots = open;
+                }
+                if self.staged_trades.is_none() {
+                    if let Err(e) = self.persistence.save_position(real_pos) {
+                        error!("Failed to persist partial close {}: {}", symbol, e);
+                    }
                 }
                 info!(
                     signal_id = %signal_id,
@@ -751,10 +1343,13 @@ impl ShadowState {
             None
         } else {
             // Full Close
-            if let Err(e) = self.persistence.delete_position(symbol) {
-                error!("Failed to delete closed position {}: {}", symbol, e);
+            if self.staged_trades.is_none() {
+                if let Err(e) = self.persistence.delete_position(symbol) {
+                    error!("Failed to delete closed position {}: {}", symbol, e);
+                }
             }
             self.positions.remove(symbol);
+            self.position_sources.remove(symbol);
             info!(
                 signal_id = %signal_id,
                 symbol = %symbol,
@@ -765,11 +1360,116 @@ impl ShadowState {
         }
     }
 
+    fn track_position_source(&mut self, symbol: &str, source: Option<&str>) {
+        self.position_sources
+            .insert(symbol.to_string(), source.unwrap_or("unknown").to_string());
+    }
+
     fn update_cash_balance(&mut self, amount: Decimal) {
+        self.roll_daily_open();
         self.cash_balance += amount;
+        self.persist_cash_balance();
+        self.refresh_equity_hwm();
+    }
+
+    /// Raise the equity high-water mark if equity made a new high.
+    fn refresh_equity_hwm(&mut self) {
+        let equity = self.get_equity();
+        if equity <= self.equity_hwm {
+            return;
+        }
+        self.equity_hwm = equity;
+        if let Err(e) = self.persistence.save_metadata(
+            "equity_hwm",
+            serde_json::Value::String(self.equity_hwm.to_string()),
+        ) {
+            error!("Failed to persist equity HWM: {}", e);
+        }
+    }
+
+    pub fn get_equity_hwm(&self) -> Decimal {
+        self.equity_hwm
+    }
+
+    /// Snapshot equity as the day's opening balance once a new trading day has
+    /// started. Runs before every equity update is applied, so the snapshot is
+    /// the equity carried over the reset, not the first update after it.
+    fn roll_daily_open(&mut self) {
+        if self.current_daily_open().is_some() {
+            return;
+        }
+        let day_start = trading_day_start(self.ctx.time.now_millis(), self.daily_reset_hour_utc);
+        self.set_daily_open(self.get_equity(), day_start);
+        info!(
+            "Trading day opened at {} with equity {}",
+            day_start,
+            self.get_equity()
+        );
+    }
+
+    /// Anchor intraday PnL for the trading day starting at `day_start` (ms).
+    pub fn set_daily_open(&mut self, equity: Decimal, day_start: i64) {
+        let open = DailyOpen { day_start, equity };
+        self.daily_open = Some(open);
+        match serde_json::to_value(open) {
+            Ok(val) => {
+                if let Err(e) = self.persistence.save_metadata("daily_open", val) {
+                    error!("Failed to persist daily open: {}", e);
+                }
+            }
+            Err(e) => error!("Failed to serialize daily open: {}", e),
+        }
+    }
+
+    pub fn get_daily_open(&self) -> Option<DailyOpen> {
+        self.daily_open
+    }
+
+    /// The snapshot, unless a trading day has started since it was taken. A
+    /// snapshot from a later reset hour on the same day still counts, so one
+    /// taken before the reset hour is configured survives a restart.
+    fn current_daily_open(&self) -> Option<DailyOpen> {
+        let day_start = trading_day_start(self.ctx.time.now_millis(), self.daily_reset_hour_utc);
+        self.daily_open.filter(|open| open.day_start >= day_start)
+    }
+
+    /// Equity change since the day's open, realized and unrealized. Zero when
+    /// no update has happened yet today, since equity is then still the open.
+    pub fn intraday_pnl(&self) -> Decimal {
+        match self.current_daily_open() {
+            Some(open) => self.get_equity() - open.equity,
+            None => Decimal::ZERO,
+        }
+    }
+
+    /// Percent below the day's opening equity (0 when up on the day).
+    pub fn intraday_drawdown_pct(&self) -> Decimal {
+        let Some(open) = self
+            .current_daily_open()
+            .filter(|o| o.equity > Decimal::ZERO)
+        else {
+            return Decimal::ZERO;
+        };
+        (-self.intraday_pnl() / open.equity * Decimal::from(100)).max(Decimal::ZERO)
+    }
+
+    /// Percent below the equity high-water mark (0 at a new high).
+    pub fn drawdown_pct(&self) -> Decimal {
+        if self.equity_hwm <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+        ((self.equity_hwm - self.get_equity()) / self.equity_hwm * Decimal::from(100))
+            .max(Decimal::ZERO)
+    }
+
+    /// Stored as a decimal string so no precision is lost to f64.
+    fn persist_cash_balance(&mut self) {
+        if self.staged_trades.is_some() {
+            return; // written by commit_fill
+        }
         if let Err(e) = self.persistence.save_metadata(
             "cash_balance",
-            serde_json::json!(self.cash_balance.to_f64().unwrap_or(0.0)),
+            serde_json::Value::String(self.cash_balance.to_string()),
         ) {
             error!("Failed to persist cash balance: {}", e);
         }
@@ -783,10 +1483,67 @@ impl ShadowState {
     pub fn get_cash_balance(&self) -> Decimal {
         self.cash_balance
     }
+
+    /// Cash not held back for working orders.
+    pub fn get_available_cash(&self) -> Decimal {
+        self.cash_balance - self.get_reserved_cash()
+    }
+
+    pub fn get_reserved_cash(&self) -> Decimal {
+        self.cash_reservations.values().map(|r| r.amount).sum()
+    }
+
+    pub fn get_cash_reservation(&self, signal_id: &str) -> Option<CashReservation> {
+        self.cash_reservations.get(signal_id).copied()
+    }
+
+    /// Hold back `amount` of cash for the orders of `signal_id` (total quantity `qty`).
+    pub fn reserve_cash(&mut self, signal_id: &str, amount: Decimal, qty: Decimal) {
+        if amount <= Decimal::ZERO || qty <= Decimal::ZERO {
+            return;
+        }
+        let reservation = self
+            .cash_reservations
+            .entry(signal_id.to_string())
+            .or_insert(CashReservation {
+                amount: Decimal::ZERO,
+                open_qty: Decimal::ZERO,
+            });
+        reservation.amount += amount;
+        reservation.open_qty += qty;
+        info!(
+            signal_id = %signal_id,
+            amount = %amount,
+            reserved = %reservation.amount,
+            "Cash reserved for working order"
+        );
+    }
+
+    /// Release the reservation for `qty` of the signal's order quantity, or all
+    /// of it when `qty` is None (cancel / reject).
+    pub fn release_cash(&mut self, signal_id: &str, qty: Option<Decimal>) {
+        let Some(reservation) = self.cash_reservations.get_mut(signal_id) else {
+            return;
+        };
+        match qty {
+            Some(qty) if qty < reservation.open_qty => {
+                let released = reservation.amount * qty / reservation.open_qty;
+                reservation.amount -= released;
+                reservation.open_qty -= qty;
+            }
+            _ => {
+                self.cash_reservations.remove(signal_id);
+            }
+        }
+    }
     pub fn has_position(&self, symbol: &str) -> bool {
         self.positions.contains_key(symbol)
     }
 
+    pub fn has_pending_intent(&self, signal_id: &str) -> bool {
+        self.pending_intents.contains_key(signal_id)
+    }
+
     pub fn get_position(&self, symbol: &str) -> Option<&Position> {
         self.positions.get(symbol)
     }
@@ -796,7 +1553,22 @@ impl ShadowState {
         ticker: &crate::market_data::types::BookTicker,
     ) -> Option<ExecutionEvent> {
         let symbol = &ticker.symbol;
+        if self.positions.contains_key(symbol) {
+            self.roll_daily_open();
+        }
         if let Some(position) = self.positions.get_mut(symbol) {
+            if let Some(max_age) = self.max_valuation_tick_age_ms {
+                let age = self.ctx.time.now_millis() - ticker.transaction_time;
+                if age > max_age {
+                    if self.stale_valuations.insert(symbol.clone()) {
+                        warn!(symbol = %symbol, age_ms = age, max_age_ms = max_age, "Stale tick - valuation frozen");
+                    }
+                    return None;
+                }
+                if self.stale_valuations.remove(symbol) {
+                    info!(symbol = %symbol, "Valuation fresh again");
+                }
+            }
             let mid_price = (ticker.best_bid + ticker.best_ask) / Decimal::from(2);
             let pnl = match position.side {
                 Side::Long => (mid_price - position.entry_price) * position.size,
@@ -807,37 +1579,111 @@ impl ShadowState {
             position.unrealized_pnl = pnl;
             position.last_mark_price = Some(mid_price);
             position.last_update_ts = ticker.transaction_time;
+            let event = ExecutionEvent::Updated(position.clone());
 
-            return Some(ExecutionEvent::Updated(position.clone()));
+            self.refresh_equity_hwm();
+            return Some(event);
         }
         None
     }
 
+    /// Apply the funding payment for `symbol` on `exchange` at `funding_time`
+    /// (ms). Epochs at or before the last one applied, including any applied
+    /// before a restart, are ignored.
     pub fn apply_funding(
         &mut self,
+        exchange: &str,
         symbol: &str,
         amount: Decimal,
+        rate: Option<Decimal>,
         asset: String,
+        funding_time: i64,
     ) -> Option<ExecutionEvent> {
-        if let Some(position) = self.positions.get_mut(symbol) {
-            position.funding_paid += amount;
-            position.last_update_ts = self.ctx.time.now_millis();
-
-            // Persist
-            if let Err(e) = self.persistence.save_position(position) {
-                error!("Failed to persist funding update {}: {}", symbol, e);
+        match self.persistence.last_funding_applied(exchange, symbol) {
+            Ok(Some(last)) if funding_time <= last => {
+                warn!(exchange = %exchange, symbol = %symbol, funding_time, last, "Funding epoch already applied - ignoring");
+                return None;
             }
+            Ok(_) => {}
+            Err(e) => {
+                error!("Funding dedup lookup failed for {}: {}", symbol, e);
+                return None;
+            }
+        }
+
+        if !self.positions.contains_key(symbol) {
+            return None;
+        }
+        self.roll_daily_open();
+        let position = self.positions.get_mut(symbol)?;
+        position.funding_paid += amount;
+        position.last_update_ts = self.ctx.time.now_millis();
+
+        // Deduct funding from cash
+        self.cash_balance -= amount;
+        let record = FundingRecord {
+            exchange: exchange.to_lowercase(),
+            symbol: symbol.to_string(),
+            position_signal_id: position.signal_id.clone(),
+            amount,
+            asset: asset.clone(),
+            rate,
+            funding_time,
+        };
+        if let Err(e) = self
+            .persistence
+            .commit_funding(&record, position, self.cash_balance)
+        {
+            error!("Failed to persist funding update {}: {}", symbol, e);
+        }
+        self.refresh_equity_hwm();
 
-            // Deduct funding from cash
-            self.update_cash_balance(-amount);
+        Some(ExecutionEvent::FundingPaid(
+            symbol.to_string(),
+            amount,
+            asset,
+        ))
+    }
 
-            return Some(ExecutionEvent::FundingPaid(
-                symbol.to_string(),
-                amount,
-                asset,
-            ));
+    /// Align an existing position with the venue's signed size (long positive).
+    /// Returns the updated position, or None if there is nothing to correct.
+    pub fn sync_position_size(&mut self, symbol: &str, venue_size: Decimal) -> Option<Position> {
+        if venue_size.is_zero() {
+            return None;
         }
-        None
+        let position = self.positions.get_mut(symbol)?;
+        let side = if venue_size > Decimal::ZERO {
+            Side::Long
+        } else {
+            Side::Short
+        };
+        if position.side != side || position.size != venue_size.abs() {
+            // The venue reports no lots, so the local history no longer adds up
+            position.entry_lots.clear();
+        }
+        position.side = side;
+        position.size = venue_size.abs();
+        position.last_update_ts = self.ctx.time.now_millis();
+        let position = position.clone();
+
+        if let Err(e) = self.persistence.save_position(&position) {
+            error!("Failed to persist synced position {}: {}", symbol, e);
+        }
+        Some(position)
+    }
+
+    /// Record that `symbol`'s position now lives on `venue`, after it was
+    /// moved there. Returns the updated position, or None if none is held.
+    pub fn set_position_venue(&mut self, symbol: &str, venue: &str) -> Option<Position> {
+        let position = self.positions.get_mut(symbol)?;
+        position.exchange = Some(venue.to_string());
+        position.last_update_ts = self.ctx.time.now_millis();
+        let position = position.clone();
+
+        if let Err(e) = self.persistence.save_position(&position) {
+            error!("Failed to persist moved position {}: {}", symbol, e);
+        }
+        Some(position)
     }
 
     pub fn get_all_positions(&self) -> HashMap<String, Position> {
@@ -866,7 +1712,8 @@ impl ShadowState {
             execution_order_id: execution_order_id.clone(),
             size,
             created_at: self.ctx.time.now_millis(),
-            status: "PENDING".to_string(), // Default status
+            status: OrderStatus::Pending,
+            filled: Decimal::ZERO,
         });
 
         // Persist "Order Placed" event to WAL
@@ -890,8 +1737,51 @@ impl ShadowState {
         self.order_children.get(signal_id)
     }
 
+    /// Child orders of active intents that can still fill, as
+    /// (signal_id, symbol, child).
+    pub fn working_orders(&self) -> Vec<(String, String, OrderChild)> {
+        self.pending_intents
+            .values()
+            .filter(|i| i.status.is_active())
+            .flat_map(|i| {
+                self.order_children
+                    .get(&i.signal_id)
+                    .into_iter()
+                    .flatten()
+                    .filter(|c| !c.status.is_terminal())
+                    .map(|c| (i.signal_id.clone(), i.symbol.clone(), c.clone()))
+            })
+            .collect()
+    }
+
     pub fn calculate_exposure(&self) -> ExposureMetrics {
-        ExposureCalculator::calculate(&self.positions)
+        let mut metrics = ExposureCalculator::calculate(&self.positions);
+        let now = self.ctx.time.now_millis();
+        metrics.stale_symbols = self
+            .positions
+            .iter()
+            .filter(|(symbol, pos)| self.marked_stale(symbol, pos, now))
+            .map(|(symbol, _)| symbol.clone())
+            .collect();
+        metrics.stale_symbols.sort();
+        metrics
+    }
+
+    /// Whether the position in `symbol` is still marked at an old price,
+    /// because recent ticks were too old to use or none arrived in time.
+    pub fn is_valuation_stale(&self, symbol: &str) -> bool {
+        self.positions
+            .get(symbol)
+            .is_some_and(|pos| self.marked_stale(symbol, pos, self.ctx.time.now_millis()))
+    }
+
+    /// Staleness as of `now`: a mark older than the tick age limit is stale
+    /// even when the feed simply went quiet.
+    fn marked_stale(&self, symbol: &str, pos: &Position, now: i64) -> bool {
+        self.stale_valuations.contains(symbol)
+            || self
+                .max_valuation_tick_age_ms
+                .is_some_and(|max_age| now - pos.last_update_ts > max_age)
     }
 
     pub fn count_open_intents_for_symbol(&self, symbol: &str) -> usize {
@@ -901,6 +1791,26 @@ impl ShadowState {
             .count()
     }
 
+    /// Symbols carrying risk: a nonzero position or an active intent that opens one.
+    pub fn exposed_symbols(&self) -> HashSet<String> {
+        let opening = self.pending_intents.values().filter(|i| {
+            i.status.is_active()
+                && !matches!(
+                    i.intent_type,
+                    IntentType::Close
+                        | IntentType::CloseLong
+                        | IntentType::CloseShort
+                        | IntentType::ForceSync
+                )
+        });
+        self.positions
+            .values()
+            .filter(|p| !p.size.is_zero())
+            .map(|p| p.symbol.clone())
+            .chain(opening.map(|i| i.symbol.clone()))
+            .collect()
+    }
+
     /// Persist an OrderFsm to Redb (delegates to PersistenceStore)
     pub fn save_fsm(&self, fsm: &crate::order_fsm::OrderFsm) {
         if let Err(e) = self.persistence.save_fsm(fsm) {
@@ -911,9 +1821,9 @@ impl ShadowState {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::context::ExecutionContext;
     use crate::persistence::redb_store::RedbStore;
     use crate::persistence::wal::WalManager;
+    use chrono::Utc;
     use rust_decimal_macros::dec;
     use uuid::Uuid;
 
@@ -951,6 +1861,7 @@ mod tests {
             funding_paid: dec!(0),
             last_mark_price: None,
             last_update_ts: 0,
+            entry_lots: Vec::new(),
         };
         store
             .save_position(&position)
@@ -973,4 +1884,290 @@ mod tests {
         // Cleanup
         std::fs::remove_file(path).unwrap_or(());
     }
+
+    #[test]
+    fn test_funding_epoch_not_reapplied_after_restart() {
+        let (store, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let position = Position {
+            symbol: "BTC/USDT".to_string(),
+            side: Side::Long,
+            size: dec!(1.0),
+            entry_price: dec!(50000.0),
+            stop_loss: dec!(45000.0),
+            take_profits: vec![],
+            signal_id: "funding-signal".to_string(),
+            opened_at: Utc::now(),
+            regime_state: None,
+            phase: None,
+            metadata: None,
+            exchange: Some("bybit".to_string()),
+            position_mode: None,
+            realized_pnl: dec!(0),
+            unrealized_pnl: dec!(0),
+            fees_paid: dec!(0),
+            funding_paid: dec!(0),
+            last_mark_price: None,
+            last_update_ts: 0,
+            entry_lots: Vec::new(),
+        };
+        store.save_position(&position).unwrap();
+
+        let epoch = 1_700_000_000_000;
+        {
+            let mut state = ShadowState::new(store.clone(), ctx.clone(), Some(10_000.0));
+            let applied = state.apply_funding(
+                "bybit",
+                "BTC/USDT",
+                dec!(5),
+                None,
+                "USDT".to_string(),
+                epoch,
+            );
+            assert!(applied.is_some());
+            // Redelivery within the same run
+            assert!(state
+                .apply_funding(
+                    "bybit",
+                    "BTC/USDT",
+                    dec!(5),
+                    None,
+                    "USDT".to_string(),
+                    epoch
+                )
+                .is_none());
+        }
+
+        // Restart: the epoch stays applied, the next one goes through
+        let mut state = ShadowState::new(store.clone(), ctx, Some(10_000.0));
+        assert_eq!(state.get_cash_balance(), dec!(9995));
+        assert!(state
+            .apply_funding(
+                "BYBIT",
+                "BTC/USDT",
+                dec!(5),
+                None,
+                "USDT".to_string(),
+                epoch
+            )
+            .is_none());
+        assert_eq!(
+            state.get_position("BTC/USDT").unwrap().funding_paid,
+            dec!(5)
+        );
+
+        assert!(state
+            .apply_funding(
+                "bybit",
+                "BTC/USDT",
+                dec!(3),
+                None,
+                "USDT".to_string(),
+                epoch + 28_800_000
+            )
+            .is_some());
+        assert_eq!(state.get_cash_balance(), dec!(9992));
+        assert_eq!(
+            state.get_position("BTC/USDT").unwrap().funding_paid,
+            dec!(8)
+        );
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[test]
+    fn test_intraday_pnl_against_opening_snapshot() {
+        use crate::context::{DeterministicIdProvider, SimulatedTimeProvider};
+        use crate::market_data::types::BookTicker;
+
+        const HOUR_MS: i64 = 3_600_000;
+        let (store, path) = create_test_persistence();
+        let day = 20_000 * 24 * HOUR_MS;
+        let time = Arc::new(SimulatedTimeProvider::new(day + 10 * HOUR_MS));
+        let ctx = Arc::new(ExecutionContext::from_providers(
+            time.clone(),
+            Arc::new(DeterministicIdProvider::new()),
+        ));
+        let position = Position {
+            symbol: "BTC/USDT".to_string(),
+            side: Side::Long,
+            size: dec!(1.0),
+            entry_price: dec!(50000.0),
+            stop_loss: dec!(45000.0),
+            take_profits: vec![],
+            signal_id: "seed-signal".to_string(),
+            opened_at: Utc::now(),
+            regime_state: None,
+            phase: None,
+            metadata: None,
+            exchange: Some("BYBIT".to_string()),
+            position_mode: None,
+            realized_pnl: dec!(0),
+            unrealized_pnl: dec!(0),
+            fees_paid: dec!(0),
+            funding_paid: dec!(0),
+            last_mark_price: None,
+            last_update_ts: 0,
+            entry_lots: Vec::new(),
+        };
+        store.save_position(&position).unwrap();
+        let mark = |state: &mut ShadowState, mid: Decimal| {
+            state.update_valuation(&BookTicker {
+                symbol: "BTC/USDT".to_string(),
+                best_bid: mid,
+                best_bid_qty: dec!(1),
+                best_ask: mid,
+                best_ask_qty: dec!(1),
+                transaction_time: 0,
+                event_time: 0,
+            });
+        };
+
+        let mut state = ShadowState::new(store.clone(), ctx.clone(), Some(10000.0));
+        state.set_daily_reset_hour_utc(8);
+        state.set_daily_open(dec!(9500), day + 8 * HOUR_MS);
+
+        // Unrealized moves count against the day's open, not just closed trades
+        mark(&mut state, dec!(50500));
+        assert_eq!(state.intraday_pnl(), dec!(1000));
+        mark(&mut state, dec!(49000));
+        assert_eq!(state.intraday_pnl(), dec!(-500));
+        assert_eq!(
+            state.intraday_drawdown_pct(),
+            dec!(500) / dec!(9500) * dec!(100)
+        );
+
+        // The snapshot survives a restart within the same trading day
+        let mut restored = ShadowState::new(store.clone(), ctx.clone(), Some(10000.0));
+        restored.set_daily_reset_hour_utc(8);
+        assert_eq!(restored.get_daily_open().unwrap().equity, dec!(9500));
+
+        mark(&mut restored, dec!(49000));
+        assert_eq!(restored.intraday_pnl(), dec!(-500));
+
+        // Next day's first update re-anchors at the equity carried over the
+        // reset, so the move it brings already counts against the new day
+        time.set_time(day + 24 * HOUR_MS + 9 * HOUR_MS);
+        mark(&mut restored, dec!(48000));
+        let open = restored.get_daily_open().unwrap();
+        assert_eq!(open.day_start, day + 32 * HOUR_MS);
+        assert_eq!(open.equity, dec!(9000));
+        assert_eq!(restored.intraday_pnl(), dec!(-1000));
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[test]
+    fn test_stale_tick_does_not_move_valuation() {
+        use crate::market_data::types::BookTicker;
+
+        let (store, path) = create_test_persistence();
+        let clock = Arc::new(crate::context::MockClock::new(1_000_000));
+        let ctx = Arc::new(ExecutionContext::new_test(clock.clone()));
+        store
+            .save_position(&Position {
+                symbol: "BTC/USDT".to_string(),
+                side: Side::Long,
+                size: dec!(1.0),
+                entry_price: dec!(50000.0),
+                stop_loss: dec!(45000.0),
+                take_profits: vec![],
+                signal_id: "seed-signal".to_string(),
+                opened_at: Utc::now(),
+                regime_state: None,
+                phase: None,
+                metadata: None,
+                exchange: Some("BYBIT".to_string()),
+                position_mode: None,
+                realized_pnl: dec!(0),
+                unrealized_pnl: dec!(0),
+                fees_paid: dec!(0),
+                funding_paid: dec!(0),
+                last_mark_price: None,
+                last_update_ts: 0,
+                entry_lots: Vec::new(),
+            })
+            .unwrap();
+        let tick = |mid: Decimal, ts: i64| BookTicker {
+            symbol: "BTC/USDT".to_string(),
+            best_bid: mid,
+            best_bid_qty: dec!(1),
+            best_ask: mid,
+            best_ask_qty: dec!(1),
+            transaction_time: ts,
+            event_time: ts,
+        };
+
+        let mut state = ShadowState::new(store, ctx, Some(10000.0));
+        state.set_max_valuation_tick_age_ms(5_000);
+
+        assert!(state
+            .update_valuation(&tick(dec!(51000), 999_000))
+            .is_some());
+        assert_eq!(state.get_equity(), dec!(11000));
+        assert!(!state.is_valuation_stale("BTC/USDT"));
+
+        // 10s old: ignored, the last fresh mark stands
+        assert!(state
+            .update_valuation(&tick(dec!(40000), 990_000))
+            .is_none());
+        assert_eq!(state.get_equity(), dec!(11000));
+        let position = state.get_position("BTC/USDT").unwrap();
+        assert_eq!(position.last_mark_price, Some(dec!(51000)));
+        assert!(state.is_valuation_stale("BTC/USDT"));
+        assert_eq!(state.calculate_exposure().stale_symbols, vec!["BTC/USDT"]);
+
+        // A fresh tick clears the flag
+        assert!(state
+            .update_valuation(&tick(dec!(50500), 1_000_000))
+            .is_some());
+        assert_eq!(state.get_equity(), dec!(10500));
+        assert!(state.calculate_exposure().stale_symbols.is_empty());
+
+        // The feed goes quiet: the mark ages out without another tick
+        clock.advance(5_001);
+        assert!(state.is_valuation_stale("BTC/USDT"));
+        assert_eq!(state.calculate_exposure().stale_symbols, vec!["BTC/USDT"]);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[test]
+    fn test_cash_balance_round_trip_preserves_precision() {
+        let (store, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+
+        let balance = dec!(12345.678901234567890123);
+        {
+            let mut state = ShadowState::new(store.clone(), ctx.clone(), None);
+            state.update_cash_balance(balance);
+        }
+
+        let stored = store.load_metadata("cash_balance").unwrap().unwrap();
+        assert_eq!(stored, serde_json::json!("12345.678901234567890123"));
+
+        let state = ShadowState::new(store.clone(), ctx, None);
+        assert_eq!(state.get_cash_balance(), balance);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[test]
+    fn test_cash_balance_migrates_legacy_f64() {
+        let (store, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+
+        store
+            .save_metadata("cash_balance", serde_json::json!(1234.5678))
+            .unwrap();
+
+        let state = ShadowState::new(store.clone(), ctx, None);
+        assert_eq!(state.get_cash_balance(), dec!(1234.5678));
+
+        // Rewritten in the decimal representation
+        let stored = store.load_metadata("cash_balance").unwrap().unwrap();
+        assert_eq!(stored, serde_json::json!("1234.5678"));
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
 }
@@ -0,0 +1,481 @@
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, warn};
+
+use crate::client_order_id::DEFAULT_MAX_LEN;
+use crate::context::ExecutionContext;
+use crate::exchange::adapter::{OrderRequest, OrderStatus, SwapMode};
+use crate::exchange::router::ExecutionRouter;
+use crate::model::{Intent, IntentStatus, IntentType, OrderType, Position, Side};
+use crate::persistence::redb_store::StoreError;
+use crate::persistence::store::PersistenceStore;
+use crate::shadow_state::ShadowState;
+use crate::subjects;
+
+/// Metadata key of the most recent report; each flatten overwrites it
+const SHUTDOWN_REPORT_KEY: &str = "last_shutdown_report";
+
+/// A position the flatten tried to close.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FlattenedPosition {
+    pub symbol: String,
+    pub side: Side,
+    pub size: Decimal,
+    /// Venues that filled (all or part of) the closing order
+    pub venues: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+/// A working order the flatten tried to cancel.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelledOrder {
+    pub signal_id: String,
+    pub symbol: String,
+    pub exchange: String,
+    pub order_id: String,
+    pub error: Option<String>,
+}
+
+/// What the service did while flattening on its way down, kept for incident
+/// review after the restart.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ShutdownReport {
+    pub reason: String,
+    pub started_at: i64,
+    pub completed_at: i64,
+    pub orders_cancelled: Vec<CancelledOrder>,
+    pub positions_closed: Vec<FlattenedPosition>,
+    /// Shadow equity once the closing orders were sent; fills still in
+    /// flight are not included
+    pub final_equity: Decimal,
+}
+
+impl ShutdownReport {
+    pub fn save(&self, persistence: &PersistenceStore) -> Result<(), StoreError> {
+        persistence.save_metadata(SHUTDOWN_REPORT_KEY, serde_json::to_value(self)?)
+    }
+
+    /// The report of the last flatten, if the service ever flattened.
+    pub fn load_last(persistence: &PersistenceStore) -> Result<Option<Self>, StoreError> {
+        persistence
+            .load_metadata(SHUTDOWN_REPORT_KEY)?
+            .map(|value| serde_json::from_value(value).map_err(StoreError::from))
+            .transpose()
+    }
+}
+
+/// Outcome of an operator emergency exit from a single symbol.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EmergencyExitReport {
+    pub symbol: String,
+    pub orders_cancelled: Vec<CancelledOrder>,
+    pub position_closed: FlattenedPosition,
+}
+
+/// Cancels working orders and closes every position with reduce-only market
+/// orders, recording the outcome as a `ShutdownReport`.
+pub struct Flattener {
+    router: Arc<ExecutionRouter>,
+    shadow_state: Arc<RwLock<ShadowState>>,
+    persistence: Arc<PersistenceStore>,
+    ctx: Arc<ExecutionContext>,
+    /// Reports are also published here, when set
+    publisher: Option<async_nats::Client>,
+}
+
+impl Flattener {
+    pub fn new(
+        router: Arc<ExecutionRouter>,
+        shadow_state: Arc<RwLock<ShadowState>>,
+        persistence: Arc<PersistenceStore>,
+        ctx: Arc<ExecutionContext>,
+    ) -> Self {
+        Self {
+            router,
+            shadow_state,
+            persistence,
+            ctx,
+            publisher: None,
+        }
+    }
+
+    /// Publish every flatten report on the shutdown report subject
+    pub fn with_publisher(mut self, client: async_nats::Client) -> Self {
+        self.publisher = Some(client);
+        self
+    }
+
+    /// Cancel working orders and close every position, whatever asked for it
+    /// (shutdown, operator command, panic halt). The report is persisted and,
+    /// with a publisher, published.
+    pub async fn flatten(&self, reason: &str) -> ShutdownReport {
+        warn!("🚨 Flattening all positions: {}", reason);
+        let started_at = self.ctx.time.now_millis();
+
+        // Cancel first so nothing opens behind the closing orders
+        let orders_cancelled = self.cancel_working(None).await;
+
+        let positions = self.shadow_state.read().get_all_positions();
+        let mut positions_closed = Vec::with_capacity(positions.len());
+        for pos in positions.into_values() {
+            positions_closed.push(self.close(&pos, "ShutdownFlatten").await);
+        }
+
+        let report = ShutdownReport {
+            reason: reason.to_string(),
+            started_at,
+            completed_at: self.ctx.time.now_millis(),
+            orders_cancelled,
+            positions_closed,
+            final_equity: self.shadow_state.read().get_equity(),
+        };
+        match report.save(&self.persistence) {
+            Ok(()) => info!(
+                "📝 Shutdown report saved: {} orders cancelled, {} positions closed",
+                report.orders_cancelled.len(),
+                report.positions_closed.len()
+            ),
+            Err(e) => error!("❌ Failed to persist shutdown report: {}", e),
+        }
+        if let Some(client) = &self.publisher {
+            match serde_json::to_vec(&report) {
+                Ok(payload) => {
+                    if let Err(e) = client
+                        .publish(subjects::EVT_EXECUTION_SHUTDOWN_REPORT, payload.into())
+                        .await
+                    {
+                        error!("Failed to publish shutdown report: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to encode shutdown report: {}", e),
+            }
+        }
+        report
+    }
+
+    /// Cancel working orders and close the position in one symbol. Orders go
+    /// straight to the router, so the whitelist and pre-trade risk checks do
+    /// not apply: this is how a delisted or policy-excluded holding is exited.
+    /// `None` if no position is held in `symbol`.
+    pub async fn flatten_symbol(&self, symbol: &str) -> Option<EmergencyExitReport> {
+        let key = normalize_symbol(symbol);
+        let pos = self
+            .shadow_state
+            .read()
+            .get_all_positions()
+            .into_values()
+            .find(|p| normalize_symbol(&p.symbol) == key)?;
+        warn!("🚨 Emergency exit from {}", pos.symbol);
+
+        let orders_cancelled = self.cancel_working(Some(&key)).await;
+        let position_closed = self.close(&pos, "EmergencyExit").await;
+        Some(EmergencyExitReport {
+            symbol: pos.symbol,
+            orders_cancelled,
+            position_closed,
+        })
+    }
+
+    /// Cancel working orders, only those in `symbol` (normalized) if given.
+    async fn cancel_working(&self, symbol: Option<&str>) -> Vec<CancelledOrder> {
+        let working: Vec<_> = self
+            .shadow_state
+            .read()
+            .working_orders()
+            .into_iter()
+            .filter(|(_, s, _)| symbol.is_none_or(|key| normalize_symbol(s) == key))
+            .collect();
+        let mut orders_cancelled = Vec::with_capacity(working.len());
+        for (signal_id, symbol, child) in working {
+            let error = match self.router.get_adapter(&child.exchange) {
+                Some(adapter) => adapter
+                    .cancel_order(&symbol.replace("/", ""), &child.execution_order_id)
+                    .await
+                    .err()
+                    .map(|e| e.to_string()),
+                None => Some(format!("adapter '{}' not registered", child.exchange)),
+            };
+            if let Some(e) = &error {
+                error!(
+                    "❌ Failed to cancel {} on {}: {}",
+                    child.execution_order_id, child.exchange, e
+                );
+            }
+            orders_cancelled.push(CancelledOrder {
+                signal_id,
+                symbol,
+                exchange: child.exchange,
+                order_id: child.execution_order_id,
+                error,
+            });
+        }
+        orders_cancelled
+    }
+
+    async fn close(&self, pos: &Position, source: &str) -> FlattenedPosition {
+        let side = match pos.side {
+            Side::Buy | Side::Long => Side::Sell,
+            Side::Sell | Side::Short => Side::Buy,
+        };
+        info!("🚨 Flattening {} ({:?} {})", pos.symbol, pos.side, pos.size);
+
+        let client_order_id = match self.ctx.client_order_ids.generate("fl", DEFAULT_MAX_LEN) {
+            Ok(id) => id,
+            Err(e) => {
+                error!("❌ Failed to flatten {}: {}", pos.symbol, e);
+                return FlattenedPosition {
+                    symbol: pos.symbol.clone(),
+                    side: pos.side.clone(),
+                    size: pos.size,
+                    venues: Vec::new(),
+                    errors: vec![e.to_string()],
+                };
+            }
+        };
+        let order_req = OrderRequest {
+            symbol: pos.symbol.replace("/", ""),
+            side,
+            order_type: OrderType::Market,
+            quantity: pos.size,
+            price: None,
+            stop_price: None,
+            client_order_id: client_order_id.clone(),
+            reduce_only: true,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
+        };
+        let intent = Intent {
+            // Unique per attempt: a terminal flatten intent must not block the next one
+            signal_id: format!("flatten-{}-{}", pos.symbol, client_order_id),
+            source: Some(source.to_string()),
+            symbol: pos.symbol.clone(),
+            direction: 0,
+            intent_type: IntentType::Close,
+            entry_zone: vec![],
+            stop_loss: Decimal::ZERO,
+            take_profits: vec![],
+            size: pos.size,
+            status: IntentStatus::Validated,
+            filled_size: Decimal::ZERO,
+            child_fills: vec![],
+            ttl_ms: None,
+            partition_key: None,
+            causation_id: None,
+            env: None,
+            subject: None,
+            t_signal: self.ctx.time.now_millis(),
+            t_analysis: None,
+            t_decision: None,
+            t_ingress: None,
+            t_exchange: None,
+            max_slippage_bps: None,
+            rejection_reason: None,
+            regime_state: None,
+            phase: None,
+            metadata: None,
+            // Close where the position lives
+            exchange: pos.exchange.clone(),
+            policy_hash: None,
+            position_mode: None,
+        };
+
+        let signal_id = intent.signal_id.clone();
+        self.shadow_state.write().process_intent(intent.clone());
+
+        let mut venues = Vec::new();
+        let mut errors = Vec::new();
+        let mut working = false;
+        for (venue, request, result) in self.router.execute(&intent, order_req).await {
+            let response = match result {
+                Ok(response) => response,
+                Err(e) => {
+                    error!("❌ Failed to flatten {} on {}: {}", pos.symbol, venue, e);
+                    errors.push(format!("{}: {}", venue, e));
+                    continue;
+                }
+            };
+            let mut state = self.shadow_state.write();
+            state.record_child_order(
+                &signal_id,
+                venue.clone(),
+                request.client_order_id.clone(),
+                response.order_id.clone(),
+                request.quantity,
+            );
+            let filled = matches!(
+                response.status,
+                OrderStatus::Filled | OrderStatus::PartiallyFilled
+            ) && response.executed_qty > Decimal::ZERO;
+            if !filled {
+                if !response.status.is_terminal() {
+                    // Still working: the execution stream applies it when it fills
+                    working = true;
+                }
+                warn!(
+                    "⚠️ Flatten order {} for {} on {} is {}",
+                    response.order_id, pos.symbol, venue, response.status
+                );
+                errors.push(format!(
+                    "{}: order {} {}",
+                    venue, response.order_id, response.status
+                ));
+                continue;
+            }
+            // Market orders may come back without an average price
+            let fill_price = response
+                .avg_price
+                .or(pos.last_mark_price)
+                .unwrap_or(pos.entry_price);
+            state.confirm_execution(
+                &signal_id,
+                &response.order_id,
+                fill_price,
+                response.executed_qty,
+                true,
+                response.fee.unwrap_or(Decimal::ZERO),
+                response.fee_asset.clone().unwrap_or("USDT".to_string()),
+                &venue,
+            );
+            venues.push(venue);
+        }
+        if venues.is_empty() && errors.is_empty() {
+            errors.push("no route".to_string());
+        }
+        if venues.is_empty() && !working {
+            self.shadow_state
+                .write()
+                .reject_intent(&signal_id, errors.join("; "));
+        }
+
+        FlattenedPosition {
+            symbol: pos.symbol.clone(),
+            side: pos.side.clone(),
+            size: pos.size,
+            venues,
+            errors,
+        }
+    }
+}
+
+/// `BTC/USDT`, `BTC_USDT` and `btcusdt` all compare equal
+pub(crate) fn normalize_symbol(symbol: &str) -> String {
+    symbol.replace("/", "").replace("_", "").to_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::redb_store::RedbStore;
+    use crate::persistence::wal::WalManager;
+    use crate::test_support::MockAdapter;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn open_store(path: &str) -> Arc<PersistenceStore> {
+        let redb = Arc::new(RedbStore::new(path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        Arc::new(PersistenceStore::new(redb, wal))
+    }
+
+    #[tokio::test]
+    async fn test_flatten_on_shutdown_persists_report_for_next_boot() {
+        let path = format!("/tmp/test_shutdown_{}.redb", uuid::Uuid::new_v4());
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let adapter = Arc::new(MockAdapter::new("binance"));
+
+        {
+            let persistence = open_store(&path);
+            persistence
+                .save_position(&Position {
+                    symbol: "BTC/USDT".to_string(),
+                    side: Side::Long,
+                    size: dec!(0.5),
+                    entry_price: dec!(50000),
+                    stop_loss: Decimal::ZERO,
+                    take_profits: vec![],
+                    signal_id: "sig-open".to_string(),
+                    opened_at: Utc::now(),
+                    regime_state: None,
+                    phase: None,
+                    metadata: None,
+                    exchange: Some("binance".to_string()),
+                    position_mode: None,
+                    realized_pnl: Decimal::ZERO,
+                    unrealized_pnl: Decimal::ZERO,
+                    fees_paid: Decimal::ZERO,
+                    funding_paid: Decimal::ZERO,
+                    last_mark_price: None,
+                    last_update_ts: 0,
+                    entry_lots: Vec::new(),
+                })
+                .unwrap();
+            let mut state = ShadowState::new(persistence.clone(), ctx.clone(), Some(10_000.0));
+
+            // A resting entry that must not fill after we go down
+            let intent: Intent = serde_json::from_value(serde_json::json!({
+                "signal_id": "sig-resting",
+                "symbol": "ETH/USDT",
+                "direction": 1,
+                "type": "BUY_SETUP",
+                "entry_zone": [2000.0],
+                "size": 1.0,
+                "status": "PENDING",
+                "t_signal": Utc::now().timestamp_millis(),
+            }))
+            .unwrap();
+            state.process_intent(intent);
+            state.record_child_order(
+                "sig-resting",
+                "binance".to_string(),
+                "coid-1".to_string(),
+                "oid-resting".to_string(),
+                dec!(1.0),
+            );
+
+            let router = Arc::new(ExecutionRouter::new());
+            router.register("binance", adapter.clone());
+            let state = Arc::new(RwLock::new(state));
+            let flattener = Flattener::new(router, state.clone(), persistence, ctx.clone());
+
+            let report = flattener.flatten("shutdown").await;
+            assert_eq!(report.reason, "shutdown");
+            assert_eq!(report.orders_cancelled.len(), 1);
+            assert_eq!(report.orders_cancelled[0].order_id, "oid-resting");
+            assert_eq!(report.orders_cancelled[0].error, None);
+            assert_eq!(report.positions_closed.len(), 1);
+            assert_eq!(report.positions_closed[0].venues, vec!["binance"]);
+            assert!(report.positions_closed[0].errors.is_empty());
+            assert_eq!(report.final_equity, dec!(10000));
+            // The closing fill is applied to the shadow book
+            assert!(!state.read().has_position("BTC/USDT"));
+        }
+
+        assert_eq!(adapter.cancelled_ids(), vec!["oid-resting".to_string()]);
+        let placed = adapter.placed.lock().clone();
+        assert_eq!(placed.len(), 1);
+        assert_eq!(placed[0].side, Side::Sell);
+        assert_eq!(placed[0].quantity, dec!(0.5));
+        assert!(placed[0].reduce_only);
+
+        // Next boot: the report is read back from the same database
+        let persistence = open_store(&path);
+        let report = ShutdownReport::load_last(&persistence)
+            .unwrap()
+            .expect("report persisted");
+        assert_eq!(report.reason, "shutdown");
+        assert_eq!(report.positions_closed[0].symbol, "BTC/USDT");
+        assert_eq!(report.positions_closed[0].size, dec!(0.5));
+        assert_eq!(report.orders_cancelled[0].signal_id, "sig-resting");
+        assert!(persistence.load_positions().unwrap().is_empty());
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::market_data::engine::MarketDataEngine;
-use crate::model::{FillReport, Intent};
+use crate::model::{FillReport, Intent, Side};
 
 use rust_decimal::Decimal;
 use std::sync::Arc;
@@ -33,32 +33,62 @@ impl SimulationEngine {
 
         // 2. Determine execution price based on side and aggressive/passive
         // For now, assume TAKING liquidity (crossing spread) for immediate fill simulation
-        let (fill_price, _liquidity) = {
-            let is_buy = match intent.direction {
-                1 => true,
-                _ => false, // Simplification, need robust mapping
-            };
-
-            // If Buy, we pay Best Ask. If Sell, we take Best Bid.
-            if is_buy {
-                (ticker.best_ask, ticker.best_ask_qty)
-            } else {
-                (ticker.best_bid, ticker.best_bid_qty)
-            }
+        // If Buy, we pay Best Ask. If Sell, we take Best Bid.
+        let side_enum = intent.get_side();
+        let is_buy = side_enum == Side::Buy;
+        let (touch_price, touch_qty) = if is_buy {
+            (ticker.best_ask, ticker.best_ask_qty)
+        } else {
+            (ticker.best_bid, ticker.best_bid_qty)
+        };
+        // The part not shown at the touch is assumed to fill a spread away
+        let spread = (ticker.best_ask - ticker.best_bid).abs();
+        let walk = if intent.size > touch_qty && intent.size > Decimal::ZERO {
+            spread * (intent.size - touch_qty) / intent.size
+        } else {
+            Decimal::ZERO
+        };
+        let avg_price = if is_buy {
+            touch_price + walk
+        } else {
+            touch_price - walk
         };
 
-        // Determine correct Side enum
-        let side_enum = intent.get_side();
+        // 3. Respect the intent's slippage cap the way a capped live order would:
+        // fill what the touch allows, or nothing if even the touch is too far
+        let limit = slippage_limit(intent);
+        let allowed = |price: Decimal| {
+            limit.is_none_or(|limit| {
+                if is_buy {
+                    price <= limit
+                } else {
+                    price >= limit
+                }
+            })
+        };
+        let (fill_price, fill_qty, status) = if allowed(avg_price) {
+            (avg_price, intent.size, "FILLED")
+        } else if allowed(touch_price) && touch_qty > Decimal::ZERO {
+            (touch_price, touch_qty.min(intent.size), "PARTIALLY_FILLED")
+        } else {
+            (touch_price, Decimal::ZERO, "REJECTED")
+        };
+        if status != "FILLED" {
+            warn!(
+                "👻 Shadow fill {} for {}: modeled {} beyond slippage limit {:?}",
+                status, intent.symbol, avg_price, limit
+            );
+        }
 
-        // 3. Create Shadow/Simulated Fill
+        // 4. Create Shadow/Simulated Fill
         let fill = FillReport {
             fill_id: format!("sim-{}", self.ctx.id.new_id()),
             signal_id: intent.signal_id.clone(),
             symbol: intent.symbol.clone(),
             side: side_enum,
             price: fill_price,
-            qty: intent.size,
-            fee: fill_price * intent.size * Decimal::from_f64_retain(0.0005).unwrap(), // 0.05% Taker
+            qty: fill_qty,
+            fee: fill_price * fill_qty * Decimal::from_f64_retain(0.0005).unwrap(), // 0.05% Taker
             fee_currency: "USDT".to_string(),
             t_signal: intent.t_signal,
             t_ingress: self.ctx.time.now_millis(), // Approx
@@ -67,9 +97,10 @@ impl SimulationEngine {
             t_exchange: ticker.transaction_time, // Use market data time as "exchange" time
             client_order_id: format!("sim-oid-{}", self.ctx.id.new_id()),
             execution_id: format!("sim-exec-{}", self.ctx.id.new_id()),
-            status: "FILLED".to_string(),
+            status: status.to_string(),
             timestamp: ticker.transaction_time,
             dex_proof: None,
+            correlation_id: None,
         };
 
         info!("👻 Shadow Fill: {} @ {}", fill.symbol, fill.price);
@@ -77,3 +108,100 @@ impl SimulationEngine {
         Some(fill)
     }
 }
+
+/// Worst price the intent's `max_slippage_bps` allows, measured from its first
+/// entry price. None when the intent has no cap or no reference price.
+fn slippage_limit(intent: &Intent) -> Option<Decimal> {
+    let reference = intent
+        .entry_zone
+        .first()
+        .copied()
+        .filter(|p| *p > Decimal::ZERO)?;
+    let cap = Decimal::from(intent.max_slippage_bps?.max(0)) / Decimal::from(10_000);
+    Some(if intent.get_side() == Side::Buy {
+        reference * (Decimal::ONE + cap)
+    } else {
+        reference * (Decimal::ONE - cap)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market_data::types::BookTicker;
+    use rust_decimal_macros::dec;
+
+    fn engine(ask: Decimal, ask_qty: Decimal) -> SimulationEngine {
+        let market_data = Arc::new(MarketDataEngine::new(None));
+        market_data.apply_tick(
+            "binance",
+            BookTicker {
+                symbol: "BTCUSDT".to_string(),
+                best_bid: ask - dec!(10),
+                best_bid_qty: dec!(5),
+                best_ask: ask,
+                best_ask_qty: ask_qty,
+                transaction_time: 0,
+                event_time: 0,
+            },
+        );
+        SimulationEngine::new(market_data, Arc::new(ExecutionContext::new_system()))
+    }
+
+    fn buy_intent(size: Decimal, max_slippage_bps: Option<i32>) -> Intent {
+        let mut intent: Intent = serde_json::from_value(serde_json::json!({
+            "signal_id": "sig-sim",
+            "symbol": "BTC/USDT",
+            "direction": 1,
+            "type": "BUY_SETUP",
+            "entry_zone": [50000.0],
+            "size": size,
+            "status": "VALIDATED",
+            "t_signal": 0,
+        }))
+        .unwrap();
+        intent.max_slippage_bps = max_slippage_bps;
+        intent
+    }
+
+    #[test]
+    fn test_shadow_fill_within_cap_fills_in_full() {
+        // Ask 50,020 is 4bps over the 50,000 entry
+        let fill = engine(dec!(50020), dec!(1))
+            .simulate_execution(&buy_intent(dec!(0.5), Some(5)))
+            .unwrap();
+        assert_eq!(fill.status, "FILLED");
+        assert_eq!(fill.price, dec!(50020));
+        assert_eq!(fill.qty, dec!(0.5));
+    }
+
+    #[test]
+    fn test_high_slippage_shadow_fill_rejected() {
+        // Ask 50,100 is 20bps over entry, the intent allows 10
+        let fill = engine(dec!(50100), dec!(1))
+            .simulate_execution(&buy_intent(dec!(0.5), Some(10)))
+            .unwrap();
+        assert_eq!(fill.status, "REJECTED");
+        assert_eq!(fill.qty, Decimal::ZERO);
+        assert_eq!(fill.fee, Decimal::ZERO);
+
+        // Without a cap the same fill goes through
+        let uncapped = engine(dec!(50100), dec!(1))
+            .simulate_execution(&buy_intent(dec!(0.5), None))
+            .unwrap();
+        assert_eq!(uncapped.status, "FILLED");
+        assert_eq!(uncapped.qty, dec!(0.5));
+    }
+
+    #[test]
+    fn test_size_beyond_touch_partially_fills_at_cap() {
+        // 1 BTC at the touch, 3 more modeled a 10 spread away: avg 50,007.5
+        // is 1.5bps over entry against a 1bp cap, so only the touch fills
+        let fill = engine(dec!(50000), dec!(1))
+            .simulate_execution(&buy_intent(dec!(4), Some(1)))
+            .unwrap();
+        assert_eq!(fill.status, "PARTIALLY_FILLED");
+        assert_eq!(fill.price, dec!(50000));
+        assert_eq!(fill.qty, dec!(1));
+    }
+}
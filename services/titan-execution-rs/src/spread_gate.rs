@@ -0,0 +1,155 @@
+use std::sync::Arc;
+
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use tracing::warn;
+
+use crate::config::SpreadGateConfig;
+use crate::market_data::engine::MarketDataEngine;
+use crate::market_data::types::BookTicker;
+use crate::risk_guard::RiskRejectionReason;
+
+/// Blocks market orders while the bid-ask spread is blown out, when crossing
+/// it would cost more than `max_spread_bps` (or the symbol's override); a
+/// limit order should be used instead. Symbols without a quote are not gated.
+pub struct SpreadGate {
+    config: SpreadGateConfig,
+    market_data: Arc<MarketDataEngine>,
+}
+
+impl SpreadGate {
+    pub fn new(config: SpreadGateConfig, market_data: Arc<MarketDataEngine>) -> Self {
+        Self {
+            config,
+            market_data,
+        }
+    }
+
+    fn key(symbol: &str) -> String {
+        symbol.replace("/", "").replace("_", "").to_uppercase()
+    }
+
+    /// Threshold for `symbol`: its override if configured, else the default
+    pub fn max_spread_bps(&self, symbol: &str) -> f64 {
+        let key = Self::key(symbol);
+        self.config
+            .symbols
+            .iter()
+            .find(|(s, _)| Self::key(s) == key)
+            .map(|(_, bps)| *bps)
+            .unwrap_or(self.config.max_spread_bps)
+    }
+
+    /// Spread in bps of mid, or None for a missing or crossed quote
+    pub fn spread_bps(ticker: &BookTicker) -> Option<Decimal> {
+        let mid = (ticker.best_bid + ticker.best_ask) / Decimal::TWO;
+        if mid <= Decimal::ZERO || ticker.best_ask < ticker.best_bid {
+            return None;
+        }
+        Some((ticker.best_ask - ticker.best_bid) / mid * Decimal::from(10_000))
+    }
+
+    /// Check a market order before it is sent, against the target venue's
+    /// quote if it has one, else the consolidated quote.
+    pub fn check(
+        &self,
+        venue: Option<&str>,
+        symbol: &str,
+        reduce_only: bool,
+    ) -> Result<(), RiskRejectionReason> {
+        if !self.config.enabled || reduce_only {
+            return Ok(());
+        }
+
+        let ticker = venue
+            .and_then(|v| self.market_data.get_venue_ticker(v, symbol))
+            .or_else(|| self.market_data.get_ticker(symbol));
+        let Some(spread) = ticker.as_ref().and_then(Self::spread_bps) else {
+            return Ok(());
+        };
+        let max_bps = Decimal::from_f64(self.max_spread_bps(symbol)).unwrap_or(Decimal::ZERO);
+        if spread > max_bps {
+            let spread_bps = spread.round_dp(2);
+            warn!(
+                symbol = %symbol,
+                %spread_bps,
+                %max_bps,
+                "Risk Reject: spread too wide for market order"
+            );
+            return Err(RiskRejectionReason::SpreadTooWide {
+                symbol: symbol.to_string(),
+                spread_bps,
+                max_bps,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use std::collections::HashMap;
+
+    fn quote(bid: Decimal, ask: Decimal) -> BookTicker {
+        BookTicker {
+            symbol: "BTCUSDT".to_string(),
+            best_bid: bid,
+            best_bid_qty: dec!(1),
+            best_ask: ask,
+            best_ask_qty: dec!(1),
+            transaction_time: 0,
+            event_time: 0,
+        }
+    }
+
+    fn gate(symbols: HashMap<String, f64>) -> (SpreadGate, Arc<MarketDataEngine>) {
+        let market_data = Arc::new(MarketDataEngine::new(None));
+        let gate = SpreadGate::new(
+            SpreadGateConfig {
+                enabled: true,
+                max_spread_bps: 10.0,
+                symbols,
+            },
+            market_data.clone(),
+        );
+        (gate, market_data)
+    }
+
+    #[test]
+    fn test_tight_spread_passes_wide_spread_rejects() {
+        let (gate, market_data) = gate(HashMap::new());
+
+        // 1bps: fine for a market order
+        market_data.apply_tick("binance", quote(dec!(49997.5), dec!(50002.5)));
+        assert!(gate.check(None, "BTC/USDT", false).is_ok());
+
+        // 50bps: the same market order is refused
+        market_data.apply_tick("binance", quote(dec!(49875), dec!(50125)));
+        match gate.check(None, "BTC/USDT", false) {
+            Err(RiskRejectionReason::SpreadTooWide {
+                spread_bps,
+                max_bps,
+                ..
+            }) => {
+                assert_eq!(spread_bps, dec!(50));
+                assert_eq!(max_bps, dec!(10));
+            }
+            other => panic!("Expected SpreadTooWide, got {:?}", other),
+        }
+
+        // Exits are never blocked
+        assert!(gate.check(None, "BTC/USDT", true).is_ok());
+    }
+
+    #[test]
+    fn test_per_symbol_override() {
+        let (gate, market_data) = gate(HashMap::from([("BTC/USDT".to_string(), 60.0)]));
+        market_data.apply_tick("binance", quote(dec!(49875), dec!(50125)));
+
+        assert_eq!(gate.max_spread_bps("BTCUSDT"), 60.0);
+        assert_eq!(gate.max_spread_bps("ETHUSDT"), 10.0);
+        assert!(gate.check(None, "BTC/USDT", false).is_ok());
+    }
+}
@@ -1,41 +1,110 @@
+use crate::context::{SystemTimeProvider, TimeProvider};
 use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{info, warn};
 
-#[derive(Debug, Clone)]
+/// Ticks required after a reconnect before a symbol is trusted again.
+pub const DEFAULT_RECONNECT_WARMUP_TICKS: u32 = 5;
+
+#[derive(Clone)]
 pub struct StalenessMonitor {
     // Map (Exchange, Symbol) -> Last Update Timestamp (ms)
     last_updates: Arc<RwLock<HashMap<(String, String), i64>>>,
+    // Symbols seen across a reconnect gap -> ticks still needed before trusting them
+    suspect: Arc<RwLock<HashMap<(String, String), u32>>>,
+    warmup_ticks: u32,
+    /// Clock ticks are stamped and aged with, so replay and tests see the same staleness
+    time: Arc<dyn TimeProvider>,
 }
 
 impl Default for StalenessMonitor {
     fn default() -> Self {
-        Self::new()
+        Self::new(Arc::new(SystemTimeProvider))
     }
 }
 
 impl StalenessMonitor {
-    pub fn new() -> Self {
+    pub fn new(time: Arc<dyn TimeProvider>) -> Self {
         Self {
             last_updates: Arc::new(RwLock::new(HashMap::new())),
+            suspect: Arc::new(RwLock::new(HashMap::new())),
+            warmup_ticks: DEFAULT_RECONNECT_WARMUP_TICKS,
+            time,
         }
     }
 
+    pub fn set_warmup_ticks(&mut self, ticks: u32) {
+        self.warmup_ticks = ticks;
+    }
+
+    fn suspect_key(exchange: &str, symbol: &str) -> (String, String) {
+        (
+            exchange.to_lowercase(),
+            symbol.replace("/", "").replace("_", "").to_uppercase(),
+        )
+    }
+
     pub fn update(&self, exchange: &str, symbol: &str) {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_millis() as i64;
+        let now = self.time.now_millis();
         let key = (exchange.to_string(), symbol.to_string());
         self.last_updates.write().insert(key, now);
+
+        let key = Self::suspect_key(exchange, symbol);
+        let mut suspect = self.suspect.write();
+        if let Some(remaining) = suspect.get_mut(&key) {
+            *remaining = remaining.saturating_sub(1);
+            if *remaining == 0 {
+                suspect.remove(&key);
+                info!("✅ {} {} warmed up after reconnect", exchange, symbol);
+            }
+        }
+    }
+
+    /// A connector re-established its stream: the first ticks after the gap say nothing
+    /// about what was missed, so `symbols` stay stale until `warmup_ticks` updates arrive.
+    /// Empty `symbols` marks every symbol seen on `exchange`.
+    pub fn mark_reconnect(&self, exchange: &str, symbols: &[String]) {
+        if self.warmup_ticks == 0 {
+            return;
+        }
+        let mut keys: Vec<(String, String)> = symbols
+            .iter()
+            .map(|symbol| Self::suspect_key(exchange, symbol))
+            .collect();
+        if keys.is_empty() {
+            keys = self
+                .last_updates
+                .read()
+                .keys()
+                .filter(|(ex, _)| ex.eq_ignore_ascii_case(exchange))
+                .map(|(ex, symbol)| Self::suspect_key(ex, symbol))
+                .collect();
+        }
+        warn!(
+            "⚠️ {} reconnected: {} symbols suspect for {} ticks",
+            exchange,
+            keys.len(),
+            self.warmup_ticks
+        );
+        let mut suspect = self.suspect.write();
+        for key in keys {
+            suspect.insert(key, self.warmup_ticks);
+        }
+    }
+
+    /// True while the symbol is still warming up after a reconnect.
+    pub fn is_suspect(&self, exchange: &str, symbol: &str) -> bool {
+        self.suspect
+            .read()
+            .contains_key(&Self::suspect_key(exchange, symbol))
     }
 
     pub fn is_stale(&self, exchange: &str, symbol: &str, threshold_ms: i64) -> bool {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_millis() as i64;
+        if self.is_suspect(exchange, symbol) {
+            return true;
+        }
+        let now = self.time.now_millis();
         let key = (exchange.to_string(), symbol.to_string());
 
         if let Some(last_ts) = self.last_updates.read().get(&key) {
@@ -47,12 +116,29 @@ impl StalenessMonitor {
     }
 
     pub fn get_age(&self, exchange: &str, symbol: &str) -> Option<i64> {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_millis() as i64;
+        let now = self.time.now_millis();
         let key = (exchange.to_string(), symbol.to_string());
 
         self.last_updates.read().get(&key).map(|ts| now - ts)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::MockClock;
+
+    #[test]
+    fn test_age_follows_injected_clock() {
+        let clock = Arc::new(MockClock::new(1_700_000_000_000));
+        let mut monitor = StalenessMonitor::new(clock.clone());
+        monitor.set_warmup_ticks(0);
+
+        monitor.update("binance", "BTC/USDT");
+        assert!(!monitor.is_stale("binance", "BTC/USDT", 5_000));
+
+        clock.advance(5_001);
+        assert_eq!(monitor.get_age("binance", "BTC/USDT"), Some(5_001));
+        assert!(monitor.is_stale("binance", "BTC/USDT", 5_000));
+    }
+}
@@ -0,0 +1,596 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use tracing::{error, info, warn};
+
+use crate::armed_state::ArmedState;
+use crate::config::{ReconciliationToleranceConfig, StartupReconciliationConfig};
+use crate::context::ExecutionContext;
+use crate::exchange::adapter::ExchangeError;
+use crate::exchange::router::ExecutionRouter;
+use crate::model::{Position, Side};
+use crate::shadow_state::ShadowState;
+
+/// A symbol whose hydrated size disagrees with what the venues report.
+/// Sizes are signed: long positive, short negative.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PositionDivergence {
+    pub symbol: String,
+    pub local_size: Decimal,
+    pub venue_size: Decimal,
+    /// Difference as a percentage of the larger of the two sizes
+    pub divergence_pct: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReconciliationReport {
+    pub passed: bool,
+    pub checked_at: i64,
+    pub tolerance_pct: Decimal,
+    pub venues: Vec<String>,
+    pub divergences: Vec<PositionDivergence>,
+    /// Venues whose positions could not be fetched
+    pub errors: Vec<String>,
+}
+
+pub(crate) fn symbol_key(symbol: &str) -> String {
+    symbol.replace(['/', '_', '-'], "").to_uppercase()
+}
+
+fn signed_size(position: &Position) -> Decimal {
+    match position.side {
+        Side::Buy | Side::Long => position.size,
+        Side::Sell | Side::Short => -position.size,
+    }
+}
+
+/// Bounds below which a size difference is noise rather than drift: a
+/// difference within the absolute or the relative bound is ignored.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DriftTolerance {
+    pct: Decimal,
+    abs: Decimal,
+    /// Symbol key -> (abs, pct) overrides
+    symbols: HashMap<String, (Option<Decimal>, Option<Decimal>)>,
+}
+
+impl DriftTolerance {
+    pub fn new(pct: Decimal) -> Self {
+        Self {
+            pct,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_symbols(mut self, config: &ReconciliationToleranceConfig) -> Self {
+        self.abs = Decimal::from_f64(config.default_abs).unwrap_or(Decimal::ZERO);
+        self.symbols = config
+            .symbols
+            .iter()
+            .map(|(symbol, t)| {
+                (
+                    symbol_key(symbol),
+                    (
+                        t.abs.and_then(Decimal::from_f64),
+                        t.pct.and_then(Decimal::from_f64),
+                    ),
+                )
+            })
+            .collect();
+        self
+    }
+
+    fn ignores(&self, symbol: &str, diff: Decimal, divergence_pct: Decimal) -> bool {
+        let (abs, pct) = self.symbols.get(symbol).copied().unwrap_or_default();
+        diff <= abs.unwrap_or(self.abs) || divergence_pct <= pct.unwrap_or(self.pct)
+    }
+}
+
+/// Live positions from every registered venue. Venues without position
+/// support are skipped; venues that fail are reported in the error list.
+pub async fn fetch_live_positions(
+    router: &ExecutionRouter,
+) -> (Vec<String>, Vec<Position>, Vec<String>) {
+    let mut venues = Vec::new();
+    let mut live = Vec::new();
+    let mut errors = Vec::new();
+    for (venue, adapter) in router.adapters() {
+        match adapter.get_positions().await {
+            Ok(positions) => {
+                venues.push(venue);
+                live.extend(positions);
+            }
+            Err(ExchangeError::NotImplemented(msg)) => {
+                info!("Position fetch skipped {}: {}", venue, msg);
+            }
+            Err(e) => {
+                error!("❌ Could not fetch positions from {}: {}", venue, e);
+                errors.push(format!("{}: {}", venue, e));
+            }
+        }
+    }
+    (venues, live, errors)
+}
+
+/// Net signed size per symbol, compared across both sides. Symbols held on
+/// only one side diverge by 100%.
+pub fn diff_positions(
+    local: &[Position],
+    live: &[Position],
+    tolerance: &DriftTolerance,
+) -> Vec<PositionDivergence> {
+    let mut sizes: BTreeMap<String, (Decimal, Decimal)> = BTreeMap::new();
+    for position in local {
+        sizes.entry(symbol_key(&position.symbol)).or_default().0 += signed_size(position);
+    }
+    for position in live {
+        sizes.entry(symbol_key(&position.symbol)).or_default().1 += signed_size(position);
+    }
+
+    sizes
+        .into_iter()
+        .filter_map(|(symbol, (local_size, venue_size))| {
+            let scale = local_size.abs().max(venue_size.abs());
+            if scale.is_zero() {
+                return None;
+            }
+            let diff = (local_size - venue_size).abs();
+            let divergence_pct = (diff / scale * Decimal::from(100)).round_dp(4);
+            (!tolerance.ignores(&symbol, diff, divergence_pct)).then_some(PositionDivergence {
+                symbol,
+                local_size,
+                venue_size,
+                divergence_pct,
+            })
+        })
+        .collect()
+}
+
+/// Result of cancelling orders that rest on a venue without a tracked intent.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct OrphanSweepReport {
+    /// `venue:order_id` of every order cancelled
+    pub cancelled: Vec<String>,
+    /// `venue:order_id` of untracked orders left resting because the symbol
+    /// still has an open position
+    pub kept: Vec<String>,
+    /// Venues whose orders could not be listed, and cancels that failed
+    pub errors: Vec<String>,
+}
+
+/// Cancel every open order no active hydrated intent accounts for: after a
+/// crash such orders have no in-memory state and would fill unnoticed. An
+/// order is tracked when its venue or client order id matches a working
+/// child order. Orders placed outside the pipeline are cancelled too.
+///
+/// Untracked orders on a symbol with an open position are left alone: TP
+/// ladder rungs and protective stops are not persisted as child orders, and
+/// pulling them would leave the position unprotected after a restart.
+pub async fn cancel_orphan_orders(
+    router: &ExecutionRouter,
+    shadow_state: &RwLock<ShadowState>,
+) -> OrphanSweepReport {
+    let tracked: HashSet<String> = shadow_state
+        .read()
+        .working_orders()
+        .into_iter()
+        .flat_map(|(_, _, child)| [child.client_order_id, child.execution_order_id])
+        .filter(|id| !id.is_empty())
+        .collect();
+    let open_symbols: HashSet<String> = shadow_state
+        .read()
+        .get_all_positions()
+        .keys()
+        .map(|symbol| normalize_symbol(symbol))
+        .collect();
+
+    let mut report = OrphanSweepReport::default();
+    for (venue, adapter) in router.adapters() {
+        let orders = match adapter.get_open_orders().await {
+            Ok(orders) => orders,
+            Err(ExchangeError::NotImplemented(msg)) => {
+                info!("Orphan sweep skipped {}: {}", venue, msg);
+                continue;
+            }
+            Err(e) => {
+                error!("❌ Could not list open orders on {}: {}", venue, e);
+                report.errors.push(format!("{}: {}", venue, e));
+                continue;
+            }
+        };
+        for order in orders
+            .into_iter()
+            .filter(|o| !tracked.contains(&o.order_id) && !tracked.contains(&o.client_order_id))
+        {
+            if open_symbols.contains(&normalize_symbol(&order.symbol)) {
+                warn!(
+                    "🛡️ Keeping untracked order {} {} on {}: position open",
+                    order.symbol, order.order_id, venue
+                );
+                report.kept.push(format!("{}:{}", venue, order.order_id));
+                continue;
+            }
+            warn!(
+                "🧟 Cancelling orphan order {} {} on {} (client id {})",
+                order.symbol, order.order_id, venue, order.client_order_id
+            );
+            match adapter.cancel_order(&order.symbol, &order.order_id).await {
+                Ok(_) => report
+                    .cancelled
+                    .push(format!("{}:{}", venue, order.order_id)),
+                Err(e) => {
+                    error!(
+                        "❌ Failed to cancel orphan {} on {}: {}",
+                        order.order_id, venue, e
+                    );
+                    report
+                        .errors
+                        .push(format!("{}:{}: {}", venue, order.order_id, e));
+                }
+            }
+        }
+    }
+    info!(
+        "🧹 Orphan sweep: {} cancelled, {} kept, {} errors",
+        report.cancelled.len(),
+        report.kept.len(),
+        report.errors.len()
+    );
+    report
+}
+
+/// `BTC/USDT`, `BTC-USDT` and `BTCUSDT` all compare equal
+fn normalize_symbol(symbol: &str) -> String {
+    symbol
+        .chars()
+        .filter(|c| !matches!(c, '/' | '-' | '_'))
+        .collect::<String>()
+        .to_uppercase()
+}
+
+/// Compares the hydrated positions against every venue before trading is
+/// allowed. On divergence (or a venue that cannot be checked) arming stays
+/// blocked until an operator approves it.
+pub struct StartupReconciler {
+    router: Arc<ExecutionRouter>,
+    shadow_state: Arc<RwLock<ShadowState>>,
+    ctx: Arc<ExecutionContext>,
+    tolerance: DriftTolerance,
+    report: RwLock<Option<ReconciliationReport>>,
+}
+
+impl StartupReconciler {
+    pub fn new(
+        router: Arc<ExecutionRouter>,
+        shadow_state: Arc<RwLock<ShadowState>>,
+        ctx: Arc<ExecutionContext>,
+        config: &StartupReconciliationConfig,
+    ) -> Self {
+        Self {
+            router,
+            shadow_state,
+            ctx,
+            tolerance: DriftTolerance::new(
+                Decimal::from_f64(config.tolerance_pct).unwrap_or(Decimal::ZERO),
+            ),
+            report: RwLock::new(None),
+        }
+    }
+
+    /// Ignore dust-level differences per `config` as well as the percentage tolerance.
+    pub fn with_tolerances(mut self, config: &ReconciliationToleranceConfig) -> Self {
+        self.tolerance = self.tolerance.with_symbols(config);
+        self
+    }
+
+    /// Latest result, for the health RPC.
+    pub fn report(&self) -> Option<ReconciliationReport> {
+        self.report.read().clone()
+    }
+
+    pub async fn run(&self, armed_state: &ArmedState) -> ReconciliationReport {
+        let (venues, live, errors) = fetch_live_positions(&self.router).await;
+
+        let local: Vec<Position> = self
+            .shadow_state
+            .read()
+            .get_all_positions()
+            .into_values()
+            .collect();
+        let divergences = diff_positions(&local, &live, &self.tolerance);
+        let report = ReconciliationReport {
+            passed: divergences.is_empty() && errors.is_empty(),
+            checked_at: self.ctx.time.now_millis(),
+            tolerance_pct: self.tolerance.pct,
+            venues,
+            divergences,
+            errors,
+        };
+
+        if report.passed {
+            info!(
+                "✅ Startup reconciliation passed ({} positions, {} venues)",
+                local.len(),
+                report.venues.len()
+            );
+        } else {
+            for d in &report.divergences {
+                warn!(
+                    "🚨 Startup divergence {}: hydrated {} vs venues {} ({}%)",
+                    d.symbol, d.local_size, d.venue_size, d.divergence_pct
+                );
+            }
+            armed_state.block_arming(&format!(
+                "Startup reconciliation failed: {} divergent symbols, {} venue errors",
+                report.divergences.len(),
+                report.errors.len()
+            ));
+        }
+
+        *self.report.write() = Some(report.clone());
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::adapter::{OrderResponse, OrderStatus};
+    use crate::persistence::redb_store::RedbStore;
+    use crate::persistence::store::PersistenceStore;
+    use crate::persistence::wal::WalManager;
+    use crate::test_support::MockAdapter;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn open_order(symbol: &str, order_id: &str, client_order_id: &str) -> OrderResponse {
+        OrderResponse {
+            order_id: order_id.to_string(),
+            client_order_id: client_order_id.to_string(),
+            symbol: symbol.to_string(),
+            status: OrderStatus::New,
+            raw_status: "NEW".to_string(),
+            avg_price: None,
+            executed_qty: Decimal::ZERO,
+            t_exchange: None,
+            t_ack: 0,
+            fee: None,
+            fee_asset: None,
+        }
+    }
+
+    fn position(symbol: &str, side: Side, size: Decimal) -> Position {
+        Position {
+            symbol: symbol.to_string(),
+            side,
+            size,
+            entry_price: dec!(50000),
+            stop_loss: Decimal::ZERO,
+            take_profits: vec![],
+            signal_id: "sig-1".to_string(),
+            opened_at: Utc::now(),
+            regime_state: None,
+            phase: None,
+            metadata: None,
+            exchange: Some("binance".to_string()),
+            position_mode: None,
+            realized_pnl: Decimal::ZERO,
+            unrealized_pnl: Decimal::ZERO,
+            fees_paid: Decimal::ZERO,
+            funding_paid: Decimal::ZERO,
+            last_mark_price: None,
+            last_update_ts: 0,
+            entry_lots: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_diff_positions_tolerance_and_symbol_format() {
+        let local = vec![
+            position("BTC/USDT", Side::Long, dec!(1.0)),
+            position("ETH/USDT", Side::Short, dec!(10)),
+        ];
+        let live = vec![
+            position("BTCUSDT", Side::Long, dec!(0.995)),
+            position("ETHUSDT", Side::Long, dec!(10)),
+            position("SOLUSDT", Side::Long, dec!(5)),
+        ];
+
+        let divergences = diff_positions(&local, &live, &DriftTolerance::new(dec!(1)));
+        let symbols: Vec<&str> = divergences.iter().map(|d| d.symbol.as_str()).collect();
+        // 0.5% on BTC is within tolerance; a flipped ETH and an unknown SOL are not
+        assert_eq!(symbols, vec!["ETHUSDT", "SOLUSDT"]);
+        assert_eq!(divergences[0].local_size, dec!(-10));
+        assert_eq!(divergences[0].divergence_pct, dec!(200));
+        assert_eq!(divergences[1].divergence_pct, dec!(100));
+    }
+
+    #[test]
+    fn test_per_symbol_tolerance_ignores_dust_only() {
+        let config = ReconciliationToleranceConfig {
+            default_abs: 0.001,
+            symbols: HashMap::from([
+                (
+                    "ETH/USDT".to_string(),
+                    crate::config::SymbolTolerance {
+                        abs: Some(0.05),
+                        pct: None,
+                    },
+                ),
+                (
+                    "SOLUSDT".to_string(),
+                    crate::config::SymbolTolerance {
+                        abs: None,
+                        pct: Some(2.0),
+                    },
+                ),
+            ]),
+        };
+        let tolerance = DriftTolerance::new(dec!(0.1)).with_symbols(&config);
+        let diff = |symbol: &str, local: Decimal, live: Decimal| {
+            diff_positions(
+                &[position(symbol, Side::Long, local)],
+                &[position(symbol, Side::Long, live)],
+                &tolerance,
+            )
+        };
+
+        // Under the default dust allowance, though 0.5% is over tolerance_pct
+        assert!(diff("BTC/USDT", dec!(0.1), dec!(0.1005)).is_empty());
+        assert_eq!(diff("BTC/USDT", dec!(0.1), dec!(0.102)).len(), 1);
+
+        // ETH tolerates 0.05 absolute
+        assert!(diff("ETH/USDT", dec!(10), dec!(10.04)).is_empty());
+        let drift = diff("ETH/USDT", dec!(10), dec!(10.06));
+        assert_eq!(drift.len(), 1);
+        assert_eq!(drift[0].symbol, "ETHUSDT");
+        assert_eq!(drift[0].venue_size, dec!(10.06));
+
+        // SOL tolerates 2% instead of 0.1%
+        assert!(diff("SOL/USDT", dec!(100), dec!(101.5)).is_empty());
+        assert_eq!(diff("SOL/USDT", dec!(100), dec!(103)).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_divergent_hydrated_state_blocks_arming() {
+        let path = format!("/tmp/test_startup_recon_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let persistence = Arc::new(PersistenceStore::new(redb, wal));
+        // Persisted before the crash, while the venue has since been partly closed
+        persistence
+            .save_position(&position("BTC/USDT", Side::Long, dec!(1.0)))
+            .unwrap();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let shadow_state = Arc::new(RwLock::new(ShadowState::new(
+            persistence,
+            ctx.clone(),
+            Some(10_000.0),
+        )));
+
+        let router = Arc::new(ExecutionRouter::new());
+        router.register(
+            "binance",
+            Arc::new(
+                MockAdapter::new("binance")
+                    .with_positions(vec![position("BTCUSDT", Side::Long, dec!(0.4))])
+                    .with_open_orders(vec![]),
+            ),
+        );
+
+        let armed_path = std::env::temp_dir().join(format!("titan_armed_{}", uuid::Uuid::new_v4()));
+        let armed_state = ArmedState::with_file(&armed_path);
+        armed_state.set_armed(true, "restored from lockfile");
+
+        let reconciler = StartupReconciler::new(
+            router,
+            shadow_state,
+            ctx,
+            &StartupReconciliationConfig {
+                enabled: true,
+                tolerance_pct: 1.0,
+            },
+        );
+        let report = reconciler.run(&armed_state).await;
+
+        assert!(!report.passed);
+        assert_eq!(report.venues, vec!["binance".to_string()]);
+        assert_eq!(report.divergences.len(), 1);
+        assert_eq!(report.divergences[0].local_size, dec!(1.0));
+        assert_eq!(report.divergences[0].venue_size, dec!(0.4));
+        assert!(reconciler.report().is_some_and(|r| !r.passed));
+
+        assert!(!armed_state.is_armed());
+        armed_state.set_armed(true, "operator ARM");
+        assert!(!armed_state.is_armed(), "ARM must wait for approval");
+
+        armed_state.approve_arming("positions checked by hand");
+        armed_state.set_armed(true, "operator ARM");
+        assert!(armed_state.is_armed());
+
+        std::fs::remove_file(path).unwrap_or(());
+        std::fs::remove_file(armed_path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_orphan_orders_cancelled_on_startup() {
+        let path = format!("/tmp/test_orphan_sweep_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let persistence = Arc::new(PersistenceStore::new(redb, wal));
+        // Before the crash: one working intent with a resting child order
+        let intent: crate::model::Intent = serde_json::from_value(serde_json::json!({
+            "signal_id": "sig-live",
+            "source": "hunter",
+            "symbol": "BTC/USDT",
+            "direction": 1,
+            "type": "BUY_SETUP",
+            "entry_zone": [50000.0],
+            "size": 0.1,
+            "status": "VALIDATED",
+            "t_signal": 0,
+        }))
+        .unwrap();
+        persistence.save_intent(&intent).unwrap();
+        persistence
+            .log_order_placed(
+                "sig-live".to_string(),
+                "binance".to_string(),
+                "tx-live".to_string(),
+                serde_json::json!({ "size": "0.1", "execution_id": "1001", "created_at": 0 }),
+            )
+            .unwrap();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let shadow_state = RwLock::new(ShadowState::new(persistence, ctx, Some(10_000.0)));
+        assert_eq!(shadow_state.read().working_orders().len(), 1);
+
+        let adapter = Arc::new(MockAdapter::new("binance").with_open_orders(vec![
+            open_order("BTCUSDT", "1001", "tx-live"),
+            // Never made it into the hydrated state
+            open_order("BTCUSDT", "1002", "tx-zombie"),
+        ]));
+        let router = ExecutionRouter::new();
+        router.register("binance", adapter.clone());
+
+        let report = cancel_orphan_orders(&router, &shadow_state).await;
+
+        assert_eq!(report.cancelled, vec!["binance:1002".to_string()]);
+        assert!(report.errors.is_empty());
+        assert_eq!(adapter.cancelled_ids(), vec!["1002".to_string()]);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_orphan_sweep_keeps_orders_on_open_positions() {
+        let path = format!("/tmp/test_orphan_keep_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let persistence = Arc::new(PersistenceStore::new(redb, wal));
+        persistence
+            .save_position(&position("BTC/USDT", Side::Long, dec!(1.0)))
+            .unwrap();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let shadow_state = RwLock::new(ShadowState::new(persistence, ctx, Some(10_000.0)));
+
+        let adapter = Arc::new(MockAdapter::new("binance").with_open_orders(vec![
+            // A TP rung or stop protecting the hydrated long
+            open_order("BTCUSDT", "2001", "tx-tp1"),
+            open_order("ETHUSDT", "2002", "tx-zombie"),
+        ]));
+        let router = ExecutionRouter::new();
+        router.register("binance", adapter.clone());
+
+        let report = cancel_orphan_orders(&router, &shadow_state).await;
+
+        assert_eq!(report.kept, vec!["binance:2001".to_string()]);
+        assert_eq!(report.cancelled, vec!["binance:2002".to_string()]);
+        assert_eq!(adapter.cancelled_ids(), vec!["2002".to_string()]);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+}
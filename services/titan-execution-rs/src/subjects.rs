@@ -17,6 +17,9 @@ pub const CMD_RISK_POLICY: &str = "titan.cmd.risk.policy.v1";
 // Operator Control
 pub const CMD_OPERATOR_ARM: &str = "titan.cmd.operator.arm.v1";
 pub const CMD_OPERATOR_DISARM: &str = "titan.cmd.operator.disarm.v1";
+pub const CMD_OPERATOR_APPROVE_ARMING: &str = "titan.cmd.operator.approve_arming.v1";
+pub const CMD_OPERATOR_MAINTENANCE_ENTER: &str = "titan.cmd.operator.maintenance.enter.v1";
+pub const CMD_OPERATOR_MAINTENANCE_EXIT: &str = "titan.cmd.operator.maintenance.exit.v1";
 
 // Execution Intent
 pub const CMD_EXECUTION_PLACE_PREFIX: &str = "titan.cmd.execution.place.v1";
@@ -33,6 +36,8 @@ pub const EVT_EXECUTION_FUNDING: &str = "titan.evt.execution.funding.v1"; // Nee
 pub const EVT_EXECUTION_BALANCE: &str = "titan.evt.execution.balance";
 pub const EVT_EXECUTION_REJECT: &str = "titan.evt.execution.reject.v1";
 pub const EVT_EXECUTION_TRUTH: &str = "titan.evt.execution.truth.v1";
+pub const EVT_EXECUTION_CDC: &str = "titan.evt.execution.cdc.v1"; // Sequenced state-change feed
+pub const EVT_EXECUTION_SHUTDOWN_REPORT: &str = "titan.evt.execution.shutdown_report.v1";
 
 // -----------------------------------------------------------------------------
 // SUBSCRIPTION PATTERNS (WILDCARDS)
@@ -45,11 +50,20 @@ pub const EVT_WILDCARD: &str = "titan.evt.>";
 
 // DATA
 pub const DATA_MARKET_TICKER_PREFIX: &str = "titan.data.market.ticker.v1.>";
+pub const DATA_MARKET_FUNDING: &str = "titan.data.market.funding.v1";
+pub const DATA_MARKET_FUNDING_PREFIX: &str = "titan.data.market.funding.v1.>";
+pub const DATA_MARKET_RECONNECT: &str = "titan.data.market.reconnect.v1";
+pub const DATA_MARKET_RECONNECT_PREFIX: &str = "titan.data.market.reconnect.v1.>";
+pub const DATA_MARKET_BOOK: &str = "titan.data.market.book.v1";
+pub const DATA_MARKET_BOOK_PREFIX: &str = "titan.data.market.book.v1.>";
 
 // RPC / REQUESTS (canonical form — matches titan_subjects.ts)
 pub const RPC_GET_POSITIONS_PREFIX: &str = "titan.rpc.execution.get_positions.v1.>";
 pub const RPC_GET_BALANCES_PREFIX: &str = "titan.rpc.execution.get_balances.v1.>";
+pub const RPC_REPLAY_EVENTS: &str = "titan.rpc.execution.replay_events.v1";
+pub const RPC_GET_HEALTH: &str = "titan.rpc.execution.get_health.v1";
 pub const REQ_POLICY_HASH: &str = "titan.req.exec.policy_hash.v1";
+pub const RPC_RISK_PRECHECK: &str = "titan.execution.risk_precheck";
 
 // SYSTEM EVENTS
 pub const EVT_SYS_HEARTBEAT: &str = "titan.sys.heartbeat.v1";
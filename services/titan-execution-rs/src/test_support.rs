@@ -0,0 +1,412 @@
+//! Fixtures shared by the unit tests: one scriptable venue adapter and an
+//! alert sink that keeps everything it is sent.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::alerts::{Alert, AlertSink};
+use crate::exchange::adapter::{
+    ExchangeAdapter, ExchangeError, FundingPayment, OrderRequest, OrderResponse, OrderStatus,
+};
+use crate::model::Position;
+
+/// How `MockAdapter` answers `place_order`
+#[derive(Clone, Copy)]
+pub enum PlaceBehavior {
+    /// Filled in full at the order's limit price, or the fill price for market orders
+    Fill,
+    /// Acknowledged as New and left working on the book
+    Rest,
+    /// Executed up to this quantity, the remainder cancelled (IOC, expiry)
+    CancelAfter(Decimal),
+    /// Refused with the error this builds
+    Fail(fn() -> ExchangeError),
+}
+
+/// Venue double. Fills every order by default and records placements,
+/// cancels, amends and control calls; optional venue features (amend,
+/// cancel-on-disconnect, leverage, order lookup, open orders, funding) answer
+/// NotImplemented unless switched on, like an adapter that lacks them.
+pub struct MockAdapter {
+    name: String,
+    place: PlaceBehavior,
+    fill_price: Decimal,
+    cancel_fills: Decimal,
+    cancel_fails: bool,
+    lost_replies: usize,
+    failing_first: Option<(usize, fn() -> ExchangeError)>,
+    order_lookup: bool,
+    fill_after_polls: Option<usize>,
+    supports_amend: bool,
+    supports_dead_mans_switch: bool,
+    supports_leverage: bool,
+    balances: Option<HashMap<String, Decimal>>,
+    positions: Vec<Position>,
+    open_orders: Option<Vec<OrderResponse>>,
+    funding_payments: Option<Vec<FundingPayment>>,
+    orders: Mutex<HashMap<String, OrderResponse>>,
+    polls: AtomicUsize,
+    pub placed: Mutex<Vec<OrderRequest>>,
+    /// (symbol, order id) of every cancel request
+    pub cancelled: Mutex<Vec<(String, String)>>,
+    pub amended: Mutex<Vec<Decimal>>,
+    pub dead_mans_switch_calls: Mutex<Vec<u64>>,
+    pub leverage_calls: Mutex<Vec<(String, u32)>>,
+}
+
+impl MockAdapter {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            place: PlaceBehavior::Fill,
+            fill_price: dec!(50000),
+            cancel_fills: Decimal::ZERO,
+            cancel_fails: false,
+            lost_replies: 0,
+            failing_first: None,
+            order_lookup: false,
+            fill_after_polls: None,
+            supports_amend: false,
+            supports_dead_mans_switch: false,
+            supports_leverage: false,
+            balances: None,
+            positions: Vec::new(),
+            open_orders: None,
+            funding_payments: None,
+            orders: Mutex::new(HashMap::new()),
+            polls: AtomicUsize::new(0),
+            placed: Mutex::new(Vec::new()),
+            cancelled: Mutex::new(Vec::new()),
+            amended: Mutex::new(Vec::new()),
+            dead_mans_switch_calls: Mutex::new(Vec::new()),
+            leverage_calls: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Ack orders without filling them
+    pub fn resting(mut self) -> Self {
+        self.place = PlaceBehavior::Rest;
+        self
+    }
+
+    /// Execute at most `executed_qty` of each order and cancel the rest
+    pub fn cancelling_after(mut self, executed_qty: Decimal) -> Self {
+        self.place = PlaceBehavior::CancelAfter(executed_qty);
+        self
+    }
+
+    /// Refuse every order with `error()`
+    pub fn failing(mut self, error: fn() -> ExchangeError) -> Self {
+        self.place = PlaceBehavior::Fail(error);
+        self
+    }
+
+    /// Refuse the first `n` placements with `error()`, then behave as configured
+    pub fn failing_first(mut self, n: usize, error: fn() -> ExchangeError) -> Self {
+        self.failing_first = Some((n, error));
+        self
+    }
+
+    /// Price market orders fill at
+    pub fn with_fill_price(mut self, price: Decimal) -> Self {
+        self.fill_price = price;
+        self
+    }
+
+    /// Cancels report this much executed on the order before it was pulled
+    pub fn with_cancel_fills(mut self, executed_qty: Decimal) -> Self {
+        self.cancel_fills = executed_qty;
+        self
+    }
+
+    /// Cancels are refused
+    pub fn failing_cancels(mut self) -> Self {
+        self.cancel_fails = true;
+        self
+    }
+
+    /// The first `n` placements reach the venue but their replies are lost
+    pub fn losing_first_replies(mut self, n: usize) -> Self {
+        self.lost_replies = n;
+        self.order_lookup = true;
+        self
+    }
+
+    /// `get_order` finds placed orders, still working until the `n`th poll
+    /// and filled from then on
+    pub fn filling_after_polls(mut self, n: usize) -> Self {
+        self.fill_after_polls = Some(n);
+        self.order_lookup = true;
+        self
+    }
+
+    pub fn with_amend(mut self) -> Self {
+        self.supports_amend = true;
+        self
+    }
+
+    pub fn with_dead_mans_switch(mut self) -> Self {
+        self.supports_dead_mans_switch = true;
+        self
+    }
+
+    pub fn with_leverage(mut self) -> Self {
+        self.supports_leverage = true;
+        self
+    }
+
+    /// Wallet balances; also the venue's settlement assets. Unknown assets error.
+    pub fn with_balances(mut self, balances: &[(&str, Decimal)]) -> Self {
+        self.balances = Some(
+            balances
+                .iter()
+                .map(|(asset, amount)| (asset.to_string(), *amount))
+                .collect(),
+        );
+        self
+    }
+
+    pub fn with_positions(mut self, positions: Vec<Position>) -> Self {
+        self.positions = positions;
+        self
+    }
+
+    pub fn with_open_orders(mut self, orders: Vec<OrderResponse>) -> Self {
+        self.open_orders = Some(orders);
+        self
+    }
+
+    /// Funding settlements `get_funding_payments` reports
+    pub fn with_funding_payments(mut self, payments: Vec<FundingPayment>) -> Self {
+        self.funding_payments = Some(payments);
+        self
+    }
+
+    pub fn placed(&self) -> Vec<OrderRequest> {
+        self.placed.lock().clone()
+    }
+
+    /// Order ids of every cancel request, in order
+    pub fn cancelled_ids(&self) -> Vec<String> {
+        self.cancelled
+            .lock()
+            .iter()
+            .map(|(_, order_id)| order_id.clone())
+            .collect()
+    }
+
+    /// `get_order` calls so far
+    pub fn polls(&self) -> usize {
+        self.polls.load(Ordering::SeqCst)
+    }
+
+    fn not_implemented(&self, what: &str) -> ExchangeError {
+        ExchangeError::NotImplemented(format!("{} not supported by {}", what, self.name))
+    }
+}
+
+/// Venue reply for `order_id` in `status`, nothing executed
+pub fn order_response(symbol: &str, order_id: &str, status: OrderStatus) -> OrderResponse {
+    OrderResponse {
+        order_id: order_id.to_string(),
+        client_order_id: String::new(),
+        symbol: symbol.to_string(),
+        status,
+        raw_status: status.to_string(),
+        avg_price: None,
+        executed_qty: Decimal::ZERO,
+        t_exchange: None,
+        t_ack: 0,
+        fee: None,
+        fee_asset: None,
+    }
+}
+
+#[async_trait]
+impl ExchangeAdapter for MockAdapter {
+    async fn init(&self) -> Result<(), ExchangeError> {
+        Ok(())
+    }
+
+    async fn place_order(&self, order: OrderRequest) -> Result<OrderResponse, ExchangeError> {
+        let placements = {
+            let mut placed = self.placed.lock();
+            placed.push(order.clone());
+            placed.len()
+        };
+        let mut response = order_response(
+            &order.symbol,
+            &format!("order-{}", order.client_order_id),
+            OrderStatus::New,
+        );
+        response.client_order_id = order.client_order_id.clone();
+        if let Some((n, error)) = self.failing_first {
+            if placements <= n {
+                return Err(error());
+            }
+        }
+        match self.place {
+            PlaceBehavior::Fail(error) => return Err(error()),
+            PlaceBehavior::Rest => {}
+            PlaceBehavior::Fill => {
+                response.status = OrderStatus::Filled;
+                response.raw_status = OrderStatus::Filled.to_string();
+                response.avg_price = Some(order.price.unwrap_or(self.fill_price));
+                response.executed_qty = order.quantity;
+            }
+            PlaceBehavior::CancelAfter(executed_qty) => {
+                response.status = OrderStatus::Cancelled;
+                response.raw_status = "EXPIRED".to_string();
+                response.executed_qty = executed_qty.min(order.quantity);
+                if !response.executed_qty.is_zero() {
+                    response.avg_price = Some(order.price.unwrap_or(self.fill_price));
+                }
+            }
+        }
+        self.orders
+            .lock()
+            .insert(order.client_order_id, response.clone());
+        if placements <= self.lost_replies {
+            return Err(ExchangeError::Network("request timed out".to_string()));
+        }
+        Ok(response)
+    }
+
+    async fn cancel_order(
+        &self,
+        symbol: &str,
+        order_id: &str,
+    ) -> Result<OrderResponse, ExchangeError> {
+        self.cancelled
+            .lock()
+            .push((symbol.to_string(), order_id.to_string()));
+        if self.cancel_fails {
+            return Err(ExchangeError::Api("cancel refused".to_string()));
+        }
+        let mut response = order_response(symbol, order_id, OrderStatus::Cancelled);
+        response.executed_qty = self.cancel_fills;
+        Ok(response)
+    }
+
+    async fn amend_order(
+        &self,
+        _symbol: &str,
+        order_id: &str,
+        price: Decimal,
+    ) -> Result<OrderResponse, ExchangeError> {
+        if !self.supports_amend {
+            return Err(self.not_implemented("amend_order"));
+        }
+        self.amended.lock().push(price);
+        Ok(order_response("", order_id, OrderStatus::New))
+    }
+
+    async fn get_order(
+        &self,
+        _symbol: &str,
+        client_order_id: &str,
+    ) -> Result<Option<OrderResponse>, ExchangeError> {
+        if !self.order_lookup {
+            return Err(self.not_implemented("get_order"));
+        }
+        let polls = self.polls.fetch_add(1, Ordering::SeqCst) + 1;
+        let Some(mut response) = self.orders.lock().get(client_order_id).cloned() else {
+            return Ok(None);
+        };
+        if self.fill_after_polls.is_some_and(|n| polls >= n) {
+            let quantity = self
+                .placed
+                .lock()
+                .iter()
+                .find(|o| o.client_order_id == client_order_id)
+                .map(|o| o.quantity)
+                .unwrap_or_default();
+            response.status = OrderStatus::Filled;
+            response.raw_status = OrderStatus::Filled.to_string();
+            response.avg_price = Some(self.fill_price);
+            response.executed_qty = quantity;
+        }
+        Ok(Some(response))
+    }
+
+    async fn get_open_orders(&self) -> Result<Vec<OrderResponse>, ExchangeError> {
+        self.open_orders
+            .clone()
+            .ok_or_else(|| self.not_implemented("get_open_orders"))
+    }
+
+    async fn set_dead_mans_switch(&self, window_ms: u64) -> Result<(), ExchangeError> {
+        self.dead_mans_switch_calls.lock().push(window_ms);
+        if self.supports_dead_mans_switch {
+            Ok(())
+        } else {
+            Err(self.not_implemented("cancel-on-disconnect"))
+        }
+    }
+
+    async fn set_leverage(&self, symbol: &str, leverage: u32) -> Result<(), ExchangeError> {
+        if !self.supports_leverage {
+            return Err(self.not_implemented("set_leverage"));
+        }
+        self.leverage_calls
+            .lock()
+            .push((symbol.to_string(), leverage));
+        Ok(())
+    }
+
+    async fn get_funding_payments(&self, since: i64) -> Result<Vec<FundingPayment>, ExchangeError> {
+        let payments = self
+            .funding_payments
+            .as_ref()
+            .ok_or_else(|| self.not_implemented("funding history"))?;
+        Ok(payments
+            .iter()
+            .filter(|p| p.funding_time >= since)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_balance(&self, asset: &str) -> Result<Decimal, ExchangeError> {
+        match &self.balances {
+            None => Ok(Decimal::ZERO),
+            Some(balances) => balances
+                .get(asset)
+                .copied()
+                .ok_or_else(|| ExchangeError::Api(format!("unknown asset {}", asset))),
+        }
+    }
+
+    fn settlement_assets(&self) -> Vec<String> {
+        match &self.balances {
+            None => vec!["USDT".to_string()],
+            Some(balances) => {
+                let mut assets: Vec<String> = balances.keys().cloned().collect();
+                assets.sort();
+                assets
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn get_positions(&self) -> Result<Vec<Position>, ExchangeError> {
+        Ok(self.positions.clone())
+    }
+}
+
+/// Alert sink that keeps every alert for inspection
+#[derive(Default)]
+pub struct CapturingAlertSink(pub Mutex<Vec<Alert>>);
+
+impl AlertSink for CapturingAlertSink {
+    fn send(&self, alert: Alert) {
+        self.0.lock().push(alert);
+    }
+}
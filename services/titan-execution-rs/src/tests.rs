@@ -1,11 +1,15 @@
 #[cfg(test)]
 mod integration {
-    use crate::circuit_breaker::GlobalHalt;
+    use crate::alerts::{AlertSeverity, KIND_OVERFILL};
+    use crate::circuit_breaker::{DrawdownBreaker, GlobalHalt, HaltLevel};
+    use crate::config::{LotMethod, MarketType, OrderTypeConfig, OrderTypeMode};
     use crate::context::ExecutionContext;
-    use crate::exchange::adapter::OrderRequest;
-    use crate::exchange::binance::build_order_params;
+    use crate::exchange::adapter::{ExchangeError, OrderRequest, SwapMode};
+    use crate::exchange::binance::{build_order_params, parse_order_error, parse_position_risk};
     use crate::exchange::bybit::build_order_payload;
     use crate::exchange::mexc::mexc_side_code;
+    use crate::fill_sanity::FillPriceGuard;
+    use crate::maintenance_mode::MaintenanceMode;
     use crate::market_data::engine::MarketDataEngine;
     use crate::market_data::types::BookTicker;
     use crate::model::{Intent, IntentStatus, IntentType, OrderParams, OrderType, Side};
@@ -13,8 +17,12 @@ mod integration {
     use crate::persistence::redb_store::RedbStore;
     use crate::persistence::store::PersistenceStore;
     use crate::persistence::wal::WalManager;
-    use crate::shadow_state::ShadowState;
+    use crate::risk_guard::RiskGuard;
+    use crate::risk_policy::RiskPolicy;
+    use crate::shadow_state::{ExecutionEvent, ShadowState};
+    use crate::test_support::CapturingAlertSink;
     use chrono::Utc;
+    use parking_lot::RwLock;
     use rust_decimal_macros::dec;
     use std::fs;
     use std::sync::Arc;
@@ -93,12 +101,105 @@ mod integration {
             take_profits: None,
             signal_type: None,
             expected_profit_pct: None,
+            ttl_ms: None,
+            remaining_ttl_ms: None,
         };
 
         let decision = om.decide_order_type(&params);
         assert_eq!(decision.reason, "SYSTEM_HALTED");
     }
 
+    #[test]
+    fn test_soft_halt_allows_closes_only() {
+        let halt_path = format!("/tmp/test_halt_{}", uuid::Uuid::new_v4());
+        let halt = Arc::new(GlobalHalt::with_file(&halt_path));
+        let md = Arc::new(MarketDataEngine::new(None));
+        let om = OrderManager::new(Some(OrderManagerConfig::default()), md, halt.clone());
+
+        let params = |signal_type: &str| crate::model::OrderParams {
+            signal_id: "test".to_string(),
+            symbol: "BTC/USD".to_string(),
+            side: Side::Buy,
+            size: dec!(1.0),
+            limit_price: None,
+            stop_loss: None,
+            take_profits: None,
+            signal_type: Some(signal_type.to_string()),
+            expected_profit_pct: None,
+            ttl_ms: None,
+            remaining_ttl_ms: None,
+        };
+
+        halt.set_level(HaltLevel::Soft, "De-risk");
+        assert_eq!(
+            om.decide_order_type(&params("BUY_SETUP")).reason,
+            "SYSTEM_HALTED"
+        );
+        let close = om.decide_order_type(&params("CLOSE_LONG"));
+        assert_ne!(close.reason, "SYSTEM_HALTED");
+        assert!(close.reduce_only);
+
+        halt.set_level(HaltLevel::Hard, "Emergency");
+        assert_eq!(
+            om.decide_order_type(&params("BUY_SETUP")).reason,
+            "SYSTEM_HALTED"
+        );
+        assert_eq!(
+            om.decide_order_type(&params("CLOSE_LONG")).reason,
+            "SYSTEM_HALTED"
+        );
+
+        halt.set_level(HaltLevel::Open, "Resume");
+    }
+
+    #[test]
+    fn test_maintenance_mode_freezes_opens_keeps_exits() {
+        let tmp = std::env::temp_dir();
+        let halt = Arc::new(GlobalHalt::with_file(
+            tmp.join(format!("titan_halt_{}", uuid::Uuid::new_v4())),
+        ));
+        let maintenance = Arc::new(MaintenanceMode::with_file(
+            tmp.join(format!("titan_maint_{}", uuid::Uuid::new_v4())),
+        ));
+        let md = Arc::new(MarketDataEngine::new(None));
+        let om = OrderManager::new(Some(OrderManagerConfig::default()), md, halt.clone())
+            .with_maintenance_mode(maintenance.clone());
+
+        let params = |signal_type: &str| crate::model::OrderParams {
+            signal_id: "test".to_string(),
+            symbol: "BTC/USD".to_string(),
+            side: Side::Sell,
+            size: dec!(1.0),
+            limit_price: None,
+            stop_loss: None,
+            take_profits: None,
+            signal_type: Some(signal_type.to_string()),
+            expected_profit_pct: None,
+            ttl_ms: None,
+            remaining_ttl_ms: None,
+        };
+
+        maintenance.set_active(true, "venue upgrade");
+        assert_eq!(
+            om.decide_order_type(&params("BUY_SETUP")).reason,
+            "MAINTENANCE_MODE"
+        );
+        // Stops, take-profits and closes are still managed
+        for exit in ["STOP_LOSS", "TAKE_PROFIT", "CLOSE_LONG"] {
+            let decision = om.decide_order_type(&params(exit));
+            assert_ne!(decision.reason, "MAINTENANCE_MODE", "{}", exit);
+            assert!(decision.reduce_only, "{}", exit);
+        }
+        // Not a halt: nothing alerts or reports halted
+        assert!(!halt.is_halted());
+
+        maintenance.set_active(false, "upgrade done");
+        assert_ne!(
+            om.decide_order_type(&params("BUY_SETUP")).reason,
+            "MAINTENANCE_MODE"
+        );
+    }
+
     #[test]
     fn test_shadow_state_workflow() {
         let (persistence, path) = create_test_persistence();
@@ -271,6 +372,8 @@ mod integration {
             take_profits: None,
             signal_type: Some("SELL_SETUP".to_string()),
             expected_profit_pct: None,
+            ttl_ms: None,
+            remaining_ttl_ms: None,
         };
 
         let decision = om.decide_order_type(&params);
@@ -278,6 +381,117 @@ mod integration {
         assert!(decision.reason.starts_with("IMBALANCE_SNIPE_SELL"));
     }
 
+    #[test]
+    fn test_urgency_mode_grows_aggressive_as_ttl_runs_out() {
+        let config = OrderManagerConfig {
+            order_type: OrderTypeConfig {
+                mode: OrderTypeMode::Urgency,
+                ..Default::default()
+            },
+            tick_sizes: std::collections::HashMap::from([("URGUSDT".to_string(), dec!(0.01))]),
+            ..Default::default()
+        };
+        let md = Arc::new(MarketDataEngine::new(None));
+        let halt_path = format!("/tmp/test_halt_{}", uuid::Uuid::new_v4());
+        let om = OrderManager::new(
+            Some(config),
+            md.clone(),
+            Arc::new(GlobalHalt::with_file(&halt_path)),
+        );
+        md.tickers.write().unwrap().insert(
+            "URGUSDT".to_string(),
+            BookTicker {
+                symbol: "URGUSDT".to_string(),
+                best_bid: dec!(100.00),
+                best_bid_qty: dec!(5.0),
+                best_ask: dec!(100.08),
+                best_ask_qty: dec!(5.0),
+                transaction_time: 0,
+                event_time: 0,
+            },
+        );
+        let params = |remaining_ttl_ms: i64| OrderParams {
+            signal_id: "sig-urgency".to_string(),
+            symbol: "URGUSDT".to_string(),
+            side: Side::Buy,
+            // Large enough for the impact model to allow crossing
+            size: dec!(600),
+            limit_price: Some(dec!(100.05)),
+            stop_loss: None,
+            take_profits: None,
+            signal_type: Some("BUY_SETUP".to_string()),
+            expected_profit_pct: None,
+            ttl_ms: Some(10_000),
+            remaining_ttl_ms: Some(remaining_ttl_ms),
+        };
+
+        // Plenty of time: rest on the bid
+        let early = om.decide_order_type(&params(9_000));
+        assert_eq!(early.order_type, OrderType::Limit);
+        assert!(early.post_only);
+        assert_eq!(early.limit_price, Some(dec!(100.00)));
+
+        // Deadline approaching: cross a growing share of the spread, rounded
+        // down onto the tick and never past the intent's limit
+        let mid = om.decide_order_type(&params(4_000));
+        let rounded = om.decide_order_type(&params(3_800));
+        let late = om.decide_order_type(&params(2_000));
+        assert!(!mid.post_only && !late.post_only);
+        assert_eq!(mid.limit_price, Some(dec!(100.02)));
+        assert_eq!(rounded.limit_price, Some(dec!(100.02)));
+        assert_eq!(late.limit_price, Some(dec!(100.05)));
+
+        // An order small enough to rest is kept post-only by the impact model
+        let small = OrderParams {
+            size: dec!(0.01),
+            ..params(2_000)
+        };
+        let decision = om.decide_order_type(&small);
+        assert!(decision.post_only);
+        assert_eq!(decision.limit_price, Some(dec!(100.05)));
+
+        // Near expiry: take
+        let expiring = om.decide_order_type(&params(500));
+        assert_eq!(expiring.order_type, OrderType::Market);
+        assert!(expiring.reason.starts_with("URGENCY_TAKER"));
+
+        // A taker that fees make unprofitable backs off to a maker at the limit
+        let unprofitable = OrderParams {
+            expected_profit_pct: Some(dec!(0.01)),
+            ..params(500)
+        };
+        let decision = om.decide_order_type(&unprofitable);
+        assert!(decision.reason.starts_with("UNPROFITABLE_TAKER"));
+        assert_eq!(decision.order_type, OrderType::Limit);
+        assert!(decision.post_only);
+        assert_eq!(decision.limit_price, Some(dec!(100.05)));
+
+        // A wide spread is never crossed, however little TTL is left
+        md.tickers.write().unwrap().insert(
+            "URGUSDT".to_string(),
+            BookTicker {
+                symbol: "URGUSDT".to_string(),
+                best_bid: dec!(100.00),
+                best_bid_qty: dec!(5.0),
+                best_ask: dec!(100.50),
+                best_ask_qty: dec!(5.0),
+                transaction_time: 0,
+                event_time: 0,
+            },
+        );
+        let wide = om.decide_order_type(&params(2_000));
+        assert!(wide.reason.starts_with("WIDE_SPREAD_MAKER"));
+        assert!(wide.post_only);
+        assert_eq!(wide.limit_price, Some(dec!(100.05)));
+
+        // No TTL: falls back to the default maker decision
+        let no_ttl = OrderParams {
+            ttl_ms: None,
+            ..params(0)
+        };
+        assert_eq!(om.decide_order_type(&no_ttl).reason, "DEFAULT_MAKER");
+    }
+
     #[test]
     fn test_shadow_state_reduce_and_flip() {
         let (persistence, _path) = create_test_persistence();
@@ -442,6 +656,112 @@ mod integration {
         assert!(state.validate_intent("sig-short-2").is_none());
     }
 
+    #[test]
+    fn test_entry_lots_through_pyramiding_and_partial_close() {
+        let intent = |signal_id: &str, intent_type: &str, size: rust_decimal::Decimal| -> Intent {
+            serde_json::from_value(serde_json::json!({
+                "signal_id": signal_id,
+                "symbol": "SOL/USDT",
+                "direction": 1,
+                "type": intent_type,
+                "entry_zone": [100.0],
+                "size": size,
+                "status": "PENDING",
+                "t_signal": Utc::now().timestamp_millis(),
+            }))
+            .unwrap()
+        };
+        let fill = |state: &mut ShadowState, signal_id: &str, price, size| {
+            state.confirm_execution(
+                signal_id,
+                &format!("child-{}", signal_id),
+                price,
+                size,
+                true,
+                dec!(0),
+                "USDT".to_string(),
+                "binance",
+            )
+        };
+
+        // (method, realized lots, remaining lots, remaining entry price)
+        let cases = [
+            (
+                LotMethod::Fifo,
+                vec![
+                    ("binance:child-a", dec!(100), dec!(1)),
+                    ("binance:child-b", dec!(110), dec!(0.5)),
+                ],
+                vec![("binance:child-b", dec!(0.5)), ("binance:child-c", dec!(2))],
+                dec!(118),
+            ),
+            (
+                LotMethod::Lifo,
+                vec![("binance:child-c", dec!(120), dec!(1.5))],
+                vec![
+                    ("binance:child-a", dec!(1)),
+                    ("binance:child-b", dec!(1)),
+                    ("binance:child-c", dec!(0.5)),
+                ],
+                dec!(108),
+            ),
+        ];
+
+        for (method, closed, open, remaining_entry) in cases {
+            let (persistence, path) = create_test_persistence();
+            let ctx = Arc::new(ExecutionContext::new_system());
+            let mut state = ShadowState::new(persistence, ctx, Some(100_000.0));
+            state.set_lot_method(method);
+
+            // 1 @ 100, then pyramid 1 @ 110 and 2 @ 120: average entry 112.5
+            for (signal_id, price, size) in [
+                ("a", dec!(100), dec!(1)),
+                ("b", dec!(110), dec!(1)),
+                ("c", dec!(120), dec!(2)),
+            ] {
+                state.process_intent(intent(signal_id, "BUY_SETUP", size));
+                fill(&mut state, signal_id, price, size);
+            }
+            let pos = state.get_position("SOL/USDT").expect("position exists");
+            assert_eq!(pos.size, dec!(4));
+            assert_eq!(pos.entry_price, dec!(112.5));
+            assert_eq!(pos.entry_lots.len(), 3);
+
+            // Partial close of 1.5 @ 130
+            state.process_intent(intent("d", "CLOSE_LONG", dec!(1.5)));
+            fill(&mut state, "d", dec!(130), dec!(1.5));
+
+            let trade = state.get_trade_history().last().unwrap().clone();
+            let lots: Vec<_> = trade
+                .lots
+                .iter()
+                .map(|l| (l.fill_id.as_str(), l.entry_price, l.size))
+                .collect();
+            assert_eq!(lots, closed, "{:?}", method);
+            for lot in &trade.lots {
+                assert_eq!(lot.pnl, (dec!(130) - lot.entry_price) * lot.size);
+            }
+            let expected_pnl: rust_decimal::Decimal = closed
+                .iter()
+                .map(|(_, price, size)| (dec!(130) - price) * size)
+                .sum();
+            assert_eq!(trade.pnl, expected_pnl);
+            assert_eq!(trade.size, dec!(1.5));
+
+            let pos = state.get_position("SOL/USDT").expect("position exists");
+            assert_eq!(pos.size, dec!(2.5));
+            assert_eq!(pos.entry_price, remaining_entry);
+            let remaining: Vec<_> = pos
+                .entry_lots
+                .iter()
+                .map(|l| (l.fill_id.as_str(), l.size))
+                .collect();
+            assert_eq!(remaining, open, "{:?}", method);
+
+            defer_delete(&path);
+        }
+    }
+
     #[test]
     fn test_exchange_reduce_only_mappings() {
         assert_eq!(mexc_side_code(Side::Buy, true), 2);
@@ -456,9 +776,12 @@ mod integration {
             stop_price: None,
             client_order_id: "test".to_string(),
             reduce_only: true,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
         };
 
-        let params = build_order_params(&order, 123);
+        let params = build_order_params(&order, 123, MarketType::Usdm);
         assert!(params.contains("reduceOnly=true"));
 
         let payload = build_order_payload(&order);
@@ -468,6 +791,448 @@ mod integration {
         );
     }
 
+    #[test]
+    fn test_binance_position_risk_parsing() {
+        // Captured /fapi/v2/positionRisk response (hedge mode long + one-way short + flat)
+        let json: serde_json::Value = serde_json::from_str(
+            r#"[
+                {"symbol":"BTCUSDT","positionAmt":"0.250","entryPrice":"64210.5","breakEvenPrice":"64236.18",
+                 "markPrice":"64820.10000000","unRealizedProfit":"152.40000000","liquidationPrice":"52011.3",
+                 "leverage":"10","maxNotionalValue":"40000000","marginType":"cross","isolatedMargin":"0.00000000",
+                 "isAutoAddMargin":"false","positionSide":"LONG","notional":"16205.02500000",
+                 "isolatedWallet":"0","updateTime":1718000000123},
+                {"symbol":"ETHUSDT","positionAmt":"-3.000","entryPrice":"3510.00","breakEvenPrice":"3508.60",
+                 "markPrice":"3490.25000000","unRealizedProfit":"59.25000000","liquidationPrice":"4012.7",
+                 "leverage":"5","maxNotionalValue":"10000000","marginType":"isolated","isolatedMargin":"2101.5",
+                 "isAutoAddMargin":"false","positionSide":"BOTH","notional":"-10470.75000000",
+                 "isolatedWallet":"2042.25","updateTime":1718000000456},
+                {"symbol":"SOLUSDT","positionAmt":"0.000","entryPrice":"0.0","breakEvenPrice":"0.0",
+                 "markPrice":"145.10000000","unRealizedProfit":"0.00000000","liquidationPrice":"0",
+                 "leverage":"20","maxNotionalValue":"5000000","marginType":"cross","isolatedMargin":"0.00000000",
+                 "isAutoAddMargin":"false","positionSide":"BOTH","notional":"0","isolatedWallet":"0","updateTime":0}
+            ]"#,
+        )
+        .unwrap();
+
+        let positions = parse_position_risk(&json);
+        assert_eq!(positions.len(), 2, "Flat SOLUSDT entry must be filtered");
+
+        let btc = &positions[0];
+        assert_eq!(btc.symbol, "BTCUSDT");
+        assert_eq!(btc.side, Side::Long);
+        assert_eq!(btc.size, dec!(0.250));
+        assert_eq!(btc.entry_price, dec!(64210.5));
+        assert_eq!(btc.last_mark_price, Some(dec!(64820.1)));
+        assert_eq!(btc.unrealized_pnl, dec!(152.4));
+        assert_eq!(btc.last_update_ts, 1718000000123);
+        assert_eq!(
+            btc.metadata.as_ref().unwrap()["leverage"],
+            serde_json::json!("10")
+        );
+
+        let eth = &positions[1];
+        assert_eq!(eth.side, Side::Short);
+        assert_eq!(eth.size, dec!(3));
+        assert_eq!(eth.entry_price, dec!(3510));
+        assert_eq!(eth.last_mark_price, Some(dec!(3490.25)));
+        assert_eq!(eth.unrealized_pnl, dec!(59.25));
+        assert_eq!(eth.position_mode.as_deref(), Some("BOTH"));
+        assert_eq!(
+            eth.metadata.as_ref().unwrap()["margin_type"],
+            serde_json::json!("isolated")
+        );
+    }
+
+    #[test]
+    fn test_binance_order_error_keeps_native_code() {
+        let err = parse_order_error(
+            reqwest::StatusCode::BAD_REQUEST,
+            r#"{"code":-2019,"msg":"Margin is insufficient."}"#,
+        );
+        let rejection = crate::exchange::rejection::VenueRejection::from_error("binance", &err)
+            .expect("structured rejection");
+        assert_eq!(rejection.code, "-2019");
+        assert_eq!(rejection.message, "Margin is insufficient.");
+        assert!(!rejection.retryable);
+
+        // Gateway pages without a code stay plain API errors
+        let err = parse_order_error(reqwest::StatusCode::BAD_GATEWAY, "<html>502</html>");
+        assert!(matches!(err, ExchangeError::Api(ref m) if m.contains("502")));
+    }
+
+    #[test]
+    fn test_trailing_drawdown_halt_from_equity_hwm() {
+        let (persistence, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let mut state = ShadowState::new(persistence.clone(), ctx.clone(), Some(10000.0));
+        let halt_path = std::env::temp_dir().join(format!("titan_halt_{}", uuid::Uuid::new_v4()));
+        let halt = Arc::new(GlobalHalt::with_file(&halt_path));
+        let breaker = DrawdownBreaker::new(dec!(5), halt.clone());
+
+        let intent: Intent = serde_json::from_value(serde_json::json!({
+            "signal_id": "sig-dd",
+            "symbol": "ETH/USD",
+            "direction": 1,
+            "type": "BUY_SETUP",
+            "entry_zone": [2000.0],
+            "size": 1.0,
+            "status": "PENDING",
+            "t_signal": Utc::now().timestamp_millis(),
+        }))
+        .unwrap();
+        state.process_intent(intent);
+        state.validate_intent("sig-dd");
+        state.confirm_execution(
+            "sig-dd",
+            "child-dd",
+            dec!(2000),
+            dec!(1),
+            true,
+            dec!(0),
+            "USDT".to_string(),
+            "BYBIT",
+        );
+
+        let mark = |state: &mut ShadowState, price: rust_decimal::Decimal| {
+            state.update_valuation(&BookTicker {
+                symbol: "ETH/USD".to_string(),
+                best_bid: price,
+                best_bid_qty: dec!(1),
+                best_ask: price,
+                best_ask_qty: dec!(1),
+                transaction_time: 0,
+                event_time: 0,
+            });
+            breaker.check(state.get_equity(), state.get_equity_hwm())
+        };
+
+        // Profitable run: equity 11000 becomes the high-water mark
+        assert!(!mark(&mut state, dec!(3000)));
+        assert_eq!(state.get_equity_hwm(), dec!(11000));
+
+        // Give-back of 500 (4.5%) is tolerated, and still above the starting balance
+        assert!(!mark(&mut state, dec!(2500)));
+        assert!(!halt.is_halted());
+
+        // 600 below HWM (5.45%) trips the halt, though the day is still net positive
+        assert!(mark(&mut state, dec!(2400)));
+        assert_eq!(halt.level(), HaltLevel::Hard);
+        assert!(state.drawdown_pct() > dec!(5));
+
+        // HWM survives a restart
+        let restored = ShadowState::new(persistence, ctx, Some(10000.0));
+        assert_eq!(restored.get_equity_hwm(), dec!(11000));
+
+        halt.set_halt(false, "test cleanup");
+        defer_delete(&path);
+    }
+
+    fn fill_guarded_state(persistence: Arc<PersistenceStore>, signal_id: &str) -> ShadowState {
+        let md = Arc::new(MarketDataEngine::new(None));
+        md.update_venue_ticker(
+            "bybit",
+            BookTicker {
+                symbol: "ETH/USD".to_string(),
+                best_bid: dec!(1999),
+                best_bid_qty: dec!(1),
+                best_ask: dec!(2001),
+                best_ask_qty: dec!(1),
+                transaction_time: 0,
+                event_time: 0,
+            },
+        );
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let mut state = ShadowState::new(persistence, ctx, Some(10000.0));
+        state.set_fill_price_guard(FillPriceGuard::new(md, dec!(10)));
+
+        let intent: Intent = serde_json::from_value(serde_json::json!({
+            "signal_id": signal_id,
+            "symbol": "ETH/USD",
+            "direction": 1,
+            "type": "BUY_SETUP",
+            "entry_zone": [2000.0],
+            "size": 1.0,
+            "status": "PENDING",
+            "t_signal": Utc::now().timestamp_millis(),
+        }))
+        .unwrap();
+        state.process_intent(intent);
+        state.validate_intent(signal_id);
+        state
+    }
+
+    #[test]
+    fn test_plausible_fill_price_accepted() {
+        let (persistence, path) = create_test_persistence();
+        let mut state = fill_guarded_state(persistence, "sig-fill-ok");
+
+        let events = state.confirm_execution(
+            "sig-fill-ok",
+            "child-fill-ok",
+            dec!(2010),
+            dec!(1),
+            true,
+            dec!(0),
+            "USDT".to_string(),
+            "BYBIT",
+        );
+
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, ExecutionEvent::Opened(_))));
+        assert!(!events
+            .iter()
+            .any(|e| matches!(e, ExecutionEvent::FillAnomaly(_))));
+        assert_eq!(
+            state.get_position("ETH/USD").unwrap().entry_price,
+            dec!(2010)
+        );
+
+        defer_delete(&path);
+    }
+
+    #[test]
+    fn test_fill_price_far_from_mid_rejected_as_anomaly() {
+        let (persistence, path) = create_test_persistence();
+        let mut state = fill_guarded_state(persistence, "sig-fill-bad");
+
+        // Half the mid, e.g. a quantity field parsed as price
+        let events = state.confirm_execution(
+            "sig-fill-bad",
+            "child-fill-bad",
+            dec!(1000),
+            dec!(1),
+            true,
+            dec!(0),
+            "USDT".to_string(),
+            "BYBIT",
+        );
+
+        assert_eq!(events.len(), 1);
+        let ExecutionEvent::FillAnomaly(anomaly) = &events[0] else {
+            panic!("Expected FillAnomaly event, got {:?}", events[0]);
+        };
+        assert_eq!(anomaly.mid, dec!(2000));
+        assert_eq!(anomaly.deviation_pct, dec!(50));
+        assert_eq!(anomaly.child_order_id, "child-fill-bad");
+
+        // Position state untouched, intent still awaiting a real fill
+        assert!(!state.has_position("ETH/USD"));
+        assert_eq!(state.get_cash_balance(), dec!(10000));
+        assert_eq!(state.count_open_intents_for_symbol("ETH/USD"), 1);
+
+        defer_delete(&path);
+    }
+
+    #[test]
+    fn test_overfill_applied_up_to_intent_size_and_alerted() {
+        let (persistence, path) = create_test_persistence();
+        let mut state = fill_guarded_state(persistence, "sig-overfill");
+        let sink = Arc::new(CapturingAlertSink::default());
+        state.set_alert_sink(sink.clone());
+
+        let fill = |state: &mut ShadowState, child: &str, qty, fee| {
+            state.confirm_execution(
+                "sig-overfill",
+                child,
+                dec!(2000),
+                qty,
+                true,
+                fee,
+                "USDT".to_string(),
+                "BYBIT",
+            )
+        };
+
+        let events = fill(&mut state, "child-a", dec!(0.6), dec!(0));
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, ExecutionEvent::Opened(_))));
+
+        // Second child takes the 1.0 intent to 1.2, past the 1% tolerance
+        let events = fill(&mut state, "child-b", dec!(0.6), dec!(1.2));
+        let ExecutionEvent::Overfill(overfill) = &events[0] else {
+            panic!("Expected Overfill event, got {:?}", events[0]);
+        };
+        assert_eq!(overfill.intent_size, dec!(1));
+        assert_eq!(overfill.filled_size, dec!(0.6));
+        assert_eq!(overfill.applied, dec!(0.4));
+        assert_eq!(overfill.excess, dec!(0.2));
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, ExecutionEvent::Updated(_))));
+
+        // The venue filled up to the intent size: that much is applied, with
+        // its share of the fee, and the intent completes
+        let position = state.get_position("ETH/USD").unwrap();
+        assert_eq!(position.size, dec!(1));
+        assert_eq!(position.fees_paid, dec!(0.8));
+        assert_eq!(state.count_open_intents_for_symbol("ETH/USD"), 0);
+
+        let alerts = sink.0.lock();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].kind, KIND_OVERFILL);
+        assert_eq!(alerts[0].severity, AlertSeverity::Critical);
+        assert!(alerts[0].message.contains("quarantined 0.2"));
+
+        defer_delete(&path);
+    }
+
+    #[test]
+    fn test_overfill_within_tolerance_applied_in_full() {
+        let (persistence, path) = create_test_persistence();
+        let mut state = fill_guarded_state(persistence, "sig-overfill-tol");
+
+        let events = state.confirm_execution(
+            "sig-overfill-tol",
+            "child-a",
+            dec!(2000),
+            dec!(1.005),
+            true,
+            dec!(0),
+            "USDT".to_string(),
+            "BYBIT",
+        );
+        assert!(!events
+            .iter()
+            .any(|e| matches!(e, ExecutionEvent::Overfill(_))));
+        assert_eq!(state.get_position("ETH/USD").unwrap().size, dec!(1.005));
+
+        defer_delete(&path);
+    }
+
+    #[test]
+    fn test_causation_dedup_window_from_policy_and_metrics() {
+        let (persistence, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let state = Arc::new(RwLock::new(ShadowState::new(persistence, ctx, None)));
+        let guard = RiskGuard::new(RiskPolicy::default(), state.clone());
+        guard.update_policy(RiskPolicy {
+            dedup_ttl_ms: 200,
+            ..RiskPolicy::default()
+        });
+
+        let intent = |signal_id: &str| -> Intent {
+            serde_json::from_value(serde_json::json!({
+                "signal_id": signal_id,
+                "symbol": "BTC/USDT",
+                "direction": 1,
+                "type": "BUY_SETUP",
+                "entry_zone": [50000.0],
+                "size": 0.1,
+                "status": "PENDING",
+                "causation_id": "cause-retry",
+                "t_signal": Utc::now().timestamp_millis(),
+            }))
+            .unwrap()
+        };
+        let hits = || crate::metrics::INTENT_DEDUP_HITS.get();
+        let misses = || crate::metrics::INTENT_DEDUP_MISSES.get();
+        let (hits_0, misses_0) = (hits(), misses());
+
+        let first = state.write().process_intent(intent("sig-dedup-1"));
+        assert_eq!(first.status, IntentStatus::Pending);
+        assert_eq!(misses() - misses_0, 1);
+
+        // Upstream retry inside the window
+        let retry = state.write().process_intent(intent("sig-dedup-2"));
+        assert_eq!(retry.status, IntentStatus::Rejected);
+        assert_eq!(
+            retry.rejection_reason.as_deref(),
+            Some("Duplicate causation_id")
+        );
+        assert_eq!(hits() - hits_0, 1);
+
+        // Outside the policy window the causation id is accepted again
+        std::thread::sleep(std::time::Duration::from_millis(250));
+        let later = state.write().process_intent(intent("sig-dedup-3"));
+        assert_eq!(later.status, IntentStatus::Pending);
+        assert_eq!(hits() - hits_0, 1);
+        assert_eq!(misses() - misses_0, 2);
+
+        defer_delete(&path);
+    }
+
+    #[test]
+    fn test_realized_pnl_and_win_rate_metrics() {
+        let (persistence, path) = create_test_persistence();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let mut state = ShadowState::new(persistence, ctx, Some(100_000.0));
+
+        let intent = |signal_id: &str, intent_type: &str, price: f64| -> Intent {
+            serde_json::from_value(serde_json::json!({
+                "signal_id": signal_id,
+                "source": "pnl-metrics-test",
+                "symbol": "PNLTEST/USDT",
+                "direction": 1,
+                "type": intent_type,
+                "entry_zone": [price],
+                "size": 1.0,
+                "status": "PENDING",
+                "t_signal": Utc::now().timestamp_millis(),
+            }))
+            .unwrap()
+        };
+
+        // Round trips from 100: +10, -5, +20
+        for (i, exit) in [110.0, 95.0, 120.0].into_iter().enumerate() {
+            let open_id = format!("sig-pnl-open-{}", i);
+            let close_id = format!("sig-pnl-close-{}", i);
+            state.process_intent(intent(&open_id, "BUY_SETUP", 100.0));
+            state.confirm_execution(
+                &open_id,
+                &format!("child-open-{}", i),
+                dec!(100),
+                dec!(1),
+                true,
+                dec!(0),
+                "USDT".to_string(),
+                "binance",
+            );
+            state.process_intent(intent(&close_id, "CLOSE_LONG", exit));
+            state.confirm_execution(
+                &close_id,
+                &format!("child-close-{}", i),
+                rust_decimal::Decimal::from_f64_retain(exit).unwrap(),
+                dec!(1),
+                true,
+                dec!(0),
+                "USDT".to_string(),
+                "binance",
+            );
+        }
+
+        let by_symbol = crate::metrics::trade_stats("symbol", "PNLTEST/USDT").unwrap();
+        assert_eq!(by_symbol.trades, 3);
+        assert_eq!(by_symbol.wins, 2);
+        assert_eq!(by_symbol.losses, 1);
+        assert!((by_symbol.win_rate() - 2.0 / 3.0).abs() < 1e-9);
+        assert!((by_symbol.realized_pnl - 25.0).abs() < 1e-9);
+        assert!((by_symbol.avg_win() - 15.0).abs() < 1e-9);
+        assert!((by_symbol.avg_loss() + 5.0).abs() < 1e-9);
+
+        let by_source = crate::metrics::trade_stats("source", "pnl-metrics-test").unwrap();
+        assert_eq!(by_source, by_symbol);
+
+        let labels = ["symbol", "PNLTEST/USDT"];
+        assert_eq!(
+            crate::metrics::CLOSED_TRADES_TOTAL
+                .with_label_values(&labels)
+                .get(),
+            3
+        );
+        assert!(
+            (crate::metrics::REALIZED_PNL
+                .with_label_values(&labels)
+                .get()
+                - 25.0)
+                .abs()
+                < 1e-9
+        );
+
+        defer_delete(&path);
+    }
+
     fn defer_delete(path: &str) {
         // Simple best effort cleanup. ideally use Drop guard.
         let _ = fs::remove_file(path);
@@ -476,9 +1241,16 @@ mod integration {
 
 #[cfg(test)]
 mod adapter_contracts {
-    use crate::exchange::adapter::{OrderRequest, OrderResponse};
-    use crate::exchange::binance::build_order_params;
-    use crate::exchange::bybit::build_order_payload;
+    use crate::config::MarketType;
+    use crate::exchange::adapter::{Bracket, OrderRequest, OrderResponse, OrderStatus, SwapMode};
+    use crate::exchange::binance::{
+        build_leverage_params, build_order_params, endpoints, next_income_start, parse_balance,
+        parse_funding_income, INCOME_PAGE_LIMIT,
+    };
+    use crate::exchange::bybit::{
+        build_leverage_payload, build_order_payload, next_page_cursor, parse_funding_settlements,
+        parse_open_orders, parse_order_query,
+    };
     use crate::exchange::mexc::mexc_side_code;
     use crate::model::{OrderType, Side};
     use rust_decimal_macros::dec;
@@ -488,6 +1260,7 @@ mod adapter_contracts {
     fn test_adapter_names_distinct() {
         let known_names = vec![
             "Binance Futures",
+            "Binance Spot",
             "Bybit Perps",
             "MEXC Futures",
             "OKX Perps",
@@ -528,9 +1301,12 @@ mod adapter_contracts {
             stop_price: None,
             client_order_id: "test-123".to_string(),
             reduce_only: false,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
         };
 
-        let params = build_order_params(&order, 1707840000000);
+        let params = build_order_params(&order, 1707840000000, MarketType::Usdm);
         assert!(params.contains("symbol=BTCUSDT"));
         assert!(params.contains("side=BUY"));
         assert!(params.contains("type=MARKET"));
@@ -551,9 +1327,12 @@ mod adapter_contracts {
             stop_price: None,
             client_order_id: "test-456".to_string(),
             reduce_only: true,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
         };
 
-        let params = build_order_params(&order, 1707840000000);
+        let params = build_order_params(&order, 1707840000000, MarketType::Usdm);
         assert!(params.contains("symbol=ETHUSDT"));
         assert!(params.contains("side=SELL"));
         assert!(params.contains("type=LIMIT"));
@@ -562,6 +1341,67 @@ mod adapter_contracts {
         assert!(params.contains("timeInForce=GTC"));
     }
 
+    /// Spot orders drop reduce-only and go to the spot API; futures keep both
+    #[test]
+    fn test_binance_spot_vs_futures_orders() {
+        let order = OrderRequest {
+            symbol: "ETH/USDT".to_string(),
+            side: Side::Sell,
+            order_type: OrderType::Limit,
+            quantity: dec!(2.0),
+            price: Some(dec!(3500.5)),
+            stop_price: None,
+            client_order_id: "test-789".to_string(),
+            reduce_only: true,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
+        };
+
+        let spot = build_order_params(&order, 1707840000000, MarketType::Spot);
+        assert!(!spot.contains("reduceOnly"));
+        assert!(spot.contains("type=LIMIT"));
+        assert!(spot.contains("timeInForce=GTC"));
+        assert_eq!(endpoints(MarketType::Spot).order, "/api/v3/order");
+        assert_eq!(endpoints(MarketType::Spot).positions, None);
+
+        let usdm = build_order_params(&order, 1707840000000, MarketType::Usdm);
+        assert!(usdm.contains("reduceOnly=true"));
+        assert_eq!(endpoints(MarketType::Usdm).order, "/fapi/v1/order");
+
+        let coinm = build_order_params(&order, 1707840000000, MarketType::Coinm);
+        assert!(coinm.contains("reduceOnly=true"));
+        assert_eq!(endpoints(MarketType::Coinm).order, "/dapi/v1/order");
+    }
+
+    /// Spot balances come from the account's free amounts, futures from availableBalance
+    #[test]
+    fn test_binance_balance_semantics() {
+        let spot = serde_json::json!({
+            "balances": [
+                {"asset": "BTC", "free": "0.5", "locked": "0.1"},
+                {"asset": "USDT", "free": "1200.25", "locked": "300.00"}
+            ]
+        });
+        assert_eq!(
+            parse_balance(MarketType::Spot, &spot, "USDT").unwrap(),
+            dec!(1200.25)
+        );
+
+        let futures = serde_json::json!([
+            {"asset": "USDT", "balance": "5000.00", "availableBalance": "4100.50"}
+        ]);
+        assert_eq!(
+            parse_balance(MarketType::Usdm, &futures, "USDT").unwrap(),
+            dec!(4100.50)
+        );
+        assert_eq!(
+            parse_balance(MarketType::Usdm, &futures, "BTC").unwrap(),
+            dec!(0)
+        );
+        assert!(parse_balance(MarketType::Spot, &futures, "USDT").is_err());
+    }
+
     /// Verify Bybit order payload structure
     #[test]
     fn test_bybit_order_payload_market() {
@@ -574,6 +1414,9 @@ mod adapter_contracts {
             stop_price: None,
             client_order_id: "bybit-test".to_string(),
             reduce_only: false,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
         };
 
         let payload = build_order_payload(&order);
@@ -584,6 +1427,134 @@ mod adapter_contracts {
             "Market"
         );
         assert_eq!(payload.get("qty").unwrap().as_str().unwrap(), "1.0");
+        assert!(payload.get("takeProfit").is_none());
+        assert!(payload.get("stopLoss").is_none());
+    }
+
+    /// A bracket rides on the Bybit entry as native TP/SL params
+    #[test]
+    fn test_bybit_order_payload_bracket() {
+        let order = OrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            side: Side::Buy,
+            order_type: OrderType::Limit,
+            quantity: dec!(0.5),
+            price: Some(dec!(50000)),
+            stop_price: None,
+            client_order_id: "bybit-bracket".to_string(),
+            reduce_only: false,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: Some(Bracket {
+                take_profit: Some(dec!(52000)),
+                stop_loss: Some(dec!(49000)),
+            }),
+        };
+
+        let payload = build_order_payload(&order);
+        assert_eq!(payload["takeProfit"], "52000");
+        assert_eq!(payload["stopLoss"], "49000");
+        assert_eq!(payload["tpslMode"], "Full");
+
+        // Stop-only bracket
+        let stop_only = OrderRequest {
+            bracket: Some(Bracket {
+                take_profit: None,
+                stop_loss: Some(dec!(49000)),
+            }),
+            ..order
+        };
+        let payload = build_order_payload(&stop_only);
+        assert!(payload.get("takeProfit").is_none());
+        assert_eq!(payload["stopLoss"], "49000");
+    }
+
+    /// Leverage goes to the futures leverage endpoints with the configured value
+    #[test]
+    fn test_leverage_requests() {
+        let params = build_leverage_params("BTC/USDT", 5, 1707840000000);
+        assert_eq!(params, "symbol=BTCUSDT&leverage=5&timestamp=1707840000000");
+        assert_eq!(
+            endpoints(MarketType::Usdm).leverage,
+            Some("/fapi/v1/leverage")
+        );
+        assert_eq!(
+            endpoints(MarketType::Coinm).leverage,
+            Some("/dapi/v1/leverage")
+        );
+        assert_eq!(endpoints(MarketType::Spot).leverage, None);
+
+        let payload = build_leverage_payload("BTC/USDT", 5);
+        assert_eq!(payload["category"], "linear");
+        assert_eq!(payload["symbol"], "BTCUSDT");
+        assert_eq!(payload["buyLeverage"], "5");
+        assert_eq!(payload["sellLeverage"], "5");
+    }
+
+    /// Funding history comes back oldest first, signed as paid by us
+    #[test]
+    fn test_funding_history_parsing() {
+        let income = serde_json::json!([
+            {"symbol": "BTCUSDT", "incomeType": "FUNDING_FEE", "income": "0.50",
+             "asset": "USDT", "time": 1707868800000_i64},
+            {"symbol": "BTCUSDT", "incomeType": "FUNDING_FEE", "income": "-1.25",
+             "asset": "USDT", "time": 1707840000000_i64},
+            {"symbol": "BTCUSDT", "incomeType": "COMMISSION", "income": "-0.10",
+             "asset": "USDT", "time": 1707840000000_i64}
+        ]);
+        let payments = parse_funding_income(&income);
+        assert_eq!(payments.len(), 2);
+        assert_eq!(payments[0].amount, dec!(1.25));
+        assert_eq!(payments[0].funding_time, 1707840000000);
+        assert_eq!(payments[1].amount, dec!(-0.50));
+        assert_eq!(endpoints(MarketType::Usdm).income, Some("/fapi/v1/income"));
+        assert_eq!(endpoints(MarketType::Spot).income, None);
+
+        let settlements = serde_json::json!({
+            "list": [
+                {"symbol": "BTCUSDT", "type": "SETTLEMENT", "funding": "-0.3",
+                 "feeRate": "-0.0001", "currency": "USDT", "transactionTime": "1707868800000"},
+                {"symbol": "BTCUSDT", "type": "SETTLEMENT", "funding": "0.8",
+                 "feeRate": "0.0001", "currency": "USDT", "transactionTime": "1707840000000"}
+            ]
+        });
+        let payments = parse_funding_settlements(&settlements);
+        assert_eq!(payments.len(), 2);
+        assert_eq!(payments[0].amount, dec!(0.8));
+        assert_eq!(payments[0].rate, Some(dec!(0.0001)));
+        assert_eq!(payments[1].amount, dec!(-0.3));
+        assert_eq!(payments[1].funding_time, 1707868800000);
+    }
+
+    /// Verify funding history paging walks past the first page and stops on the last
+    #[test]
+    fn test_funding_history_paging() {
+        let first = serde_json::json!({
+            "list": [{"symbol": "BTCUSDT", "funding": "0.8", "currency": "USDT",
+                      "transactionTime": "1707840000000"}],
+            "nextPageCursor": "21963%3A1%2C14954%3A1"
+        });
+        let last = serde_json::json!({
+            "list": [{"symbol": "ETHUSDT", "funding": "0.2", "currency": "USDT",
+                      "transactionTime": "1707811200000"}],
+            "nextPageCursor": ""
+        });
+        assert_eq!(next_page_cursor(&first), Some("21963%3A1%2C14954%3A1"));
+        assert_eq!(next_page_cursor(&last), None);
+        assert_eq!(parse_funding_settlements(&first).len(), 1);
+        assert_eq!(parse_funding_settlements(&last)[0].symbol, "ETHUSDT");
+
+        let row = |time: i64| {
+            serde_json::json!({"symbol": "BTCUSDT", "incomeType": "FUNDING_FEE",
+                               "income": "-0.1", "asset": "USDT", "time": time})
+        };
+        let full = vec![row(1_000), row(3_000), row(2_000)];
+        assert_eq!(next_income_start(&full, 500, 3), Some(3_000));
+        assert_eq!(next_income_start(&full[..2], 500, 3), None);
+        // A full page stuck on the start millisecond still advances.
+        let stuck = vec![row(500), row(500), row(500)];
+        assert_eq!(next_income_start(&stuck, 500, 3), Some(501));
+        assert_eq!(INCOME_PAGE_LIMIT, 1000);
     }
 
     /// Verify MEXC side code mappings
@@ -610,6 +1581,9 @@ mod adapter_contracts {
             stop_price: Some(dec!(160.0)),
             client_order_id: "full-test".to_string(),
             reduce_only: true,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
         };
 
         assert_eq!(order.symbol, "SOL/USDT");
@@ -621,6 +1595,47 @@ mod adapter_contracts {
         assert!(order.reduce_only);
     }
 
+    /// Verify a Bybit order query maps cumulative fill fields
+    #[test]
+    fn test_bybit_order_query_parsing() {
+        let result = serde_json::json!({
+            "list": [{
+                "orderId": "1321003749386327552",
+                "orderLinkId": "tx-abc",
+                "symbol": "BTCUSDT",
+                "orderStatus": "Filled",
+                "avgPrice": "50010.5",
+                "cumExecQty": "0.01",
+                "cumExecFee": "0.3",
+                "updatedTime": "1707840000100"
+            }]
+        });
+        let order = parse_order_query(&result).unwrap();
+        assert_eq!(order.client_order_id, "tx-abc");
+        assert_eq!(order.status, OrderStatus::Filled);
+        assert_eq!(order.avg_price, Some(dec!(50010.5)));
+        assert_eq!(order.executed_qty, dec!(0.01));
+        assert_eq!(order.fee, Some(dec!(0.3)));
+        assert_eq!(order.t_exchange, Some(1707840000100));
+
+        // Resting order: no average price yet
+        let resting = serde_json::json!({
+            "list": [{ "orderId": "1", "orderLinkId": "tx-def", "symbol": "BTCUSDT",
+                       "orderStatus": "New", "avgPrice": "", "cumExecQty": "0" }]
+        });
+        let order = parse_order_query(&resting).unwrap();
+        assert_eq!(order.status, OrderStatus::New);
+        assert_eq!(order.avg_price, None);
+        assert_eq!(order.executed_qty, dec!(0));
+
+        assert!(parse_order_query(&serde_json::json!({ "list": [] })).is_none());
+
+        let open =
+            serde_json::json!({ "list": [resting["list"][0].clone(), resting["list"][0].clone()] });
+        assert_eq!(parse_open_orders(&open).len(), 2);
+        assert!(parse_open_orders(&serde_json::json!({})).is_empty());
+    }
+
     /// Verify OrderResponse can be constructed with all optional fields
     #[test]
     fn test_order_response_construction() {
@@ -628,7 +1643,8 @@ mod adapter_contracts {
             order_id: "12345".to_string(),
             client_order_id: "client-1".to_string(),
             symbol: "BTC/USDT".to_string(),
-            status: "FILLED".to_string(),
+            status: OrderStatus::Filled,
+            raw_status: "FILLED".to_string(),
             avg_price: Some(dec!(42000.0)),
             executed_qty: dec!(0.5),
             t_ack: 1707840000000,
@@ -638,10 +1654,52 @@ mod adapter_contracts {
         };
 
         assert_eq!(resp.order_id, "12345");
-        assert_eq!(resp.status, "FILLED");
+        assert_eq!(resp.status, OrderStatus::Filled);
+        assert_eq!(resp.raw_status, "FILLED");
         assert_eq!(resp.avg_price, Some(dec!(42000.0)));
         assert_eq!(resp.executed_qty, dec!(0.5));
         assert_eq!(resp.fee, Some(dec!(0.001)));
         assert_eq!(resp.fee_asset, Some("USDT".to_string()));
     }
+
+    /// Verify an adapter with proxy_url set tunnels its REST calls through the proxy
+    #[tokio::test]
+    async fn test_adapter_routes_through_proxy() {
+        use crate::config::ExchangeConfig;
+        use crate::exchange::adapter::ExchangeAdapter;
+        use crate::exchange::binance::BinanceAdapter;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let proxy = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 1024];
+            let n = socket.read(&mut buf).await.unwrap();
+            socket
+                .write_all(b"HTTP/1.1 403 Forbidden\r\n\r\n")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        let config: ExchangeConfig = serde_json::from_value(serde_json::json!({
+            "apiKey": "key",
+            "apiSecret": "secret",
+            "enabled": true,
+            "testnet": true,
+            "proxyUrl": format!("http://127.0.0.1:{}", port),
+        }))
+        .unwrap();
+        let adapter = BinanceAdapter::new(Some(&config)).unwrap();
+
+        // The proxy refuses the tunnel, so the ping itself fails
+        assert!(adapter.init().await.is_err());
+        let request = proxy.await.unwrap();
+        assert!(
+            request.starts_with("CONNECT testnet.binancefuture.com:443"),
+            "unexpected proxy request: {}",
+            request
+        );
+    }
 }
@@ -0,0 +1,835 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::RwLock;
+use rust_decimal::Decimal;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::client_order_id::max_len_for;
+use crate::config::{TpDistribution, TpLadderConfig};
+use crate::context::ExecutionContext;
+use crate::exchange::adapter::{
+    Bracket, ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, SwapMode,
+};
+use crate::exchange::router::ExecutionRouter;
+use crate::model::{Intent, IntentStatus, IntentType, OrderType, Position, Side};
+use crate::shadow_state::{ExecutionEvent, ShadowState};
+
+/// Precision used when splitting position size across levels.
+const SIZE_DP: u32 = 8;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RungStatus {
+    Open,
+    Filled,
+    Cancelled,
+    Rejected,
+}
+
+#[derive(Debug, Clone)]
+pub struct TpRung {
+    pub price: Decimal,
+    pub quantity: Decimal,
+    pub filled: Decimal,
+    pub client_order_id: String,
+    pub order_id: Option<String>,
+    pub status: RungStatus,
+}
+
+impl TpRung {
+    pub fn remaining(&self) -> Decimal {
+        (self.quantity - self.filled).max(Decimal::ZERO)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TpLadder {
+    pub symbol: String,
+    pub exchange: String,
+    /// Side of the reduce-only TP orders (opposite of the position)
+    pub side: Side,
+    pub rungs: Vec<TpRung>,
+}
+
+impl TpLadder {
+    /// Quantity still resting on the book across open rungs.
+    pub fn outstanding(&self) -> Decimal {
+        self.rungs
+            .iter()
+            .filter(|r| r.status == RungStatus::Open)
+            .map(|r| r.remaining())
+            .sum()
+    }
+}
+
+/// Split `total` across `levels` according to `distribution`.
+/// The last level absorbs rounding dust so the sizes always sum to `total`.
+pub fn ladder_sizes(total: Decimal, levels: usize, distribution: TpDistribution) -> Vec<Decimal> {
+    if levels == 0 || total <= Decimal::ZERO {
+        return Vec::new();
+    }
+
+    let weights: Vec<Decimal> = match distribution {
+        TpDistribution::Equal => vec![Decimal::ONE; levels],
+        TpDistribution::FrontLoaded => (0..levels)
+            .map(|i| Decimal::from((levels - i) as u64))
+            .collect(),
+    };
+    let weight_sum: Decimal = weights.iter().sum();
+
+    let mut sizes = Vec::with_capacity(levels);
+    let mut allocated = Decimal::ZERO;
+    for (idx, weight) in weights.iter().enumerate() {
+        let size = if idx + 1 == levels {
+            total - allocated
+        } else {
+            (total * weight / weight_sum).round_dp(SIZE_DP)
+        };
+        allocated += size;
+        sizes.push(size);
+    }
+    sizes
+}
+
+/// Places and maintains laddered reduce-only take-profit orders for open positions.
+pub struct TpLadderExecutor {
+    router: Arc<ExecutionRouter>,
+    ctx: Arc<ExecutionContext>,
+    config: TpLadderConfig,
+    ladders: Mutex<HashMap<String, TpLadder>>,
+    shadow_state: Option<Arc<RwLock<ShadowState>>>,
+}
+
+impl TpLadderExecutor {
+    pub fn new(
+        router: Arc<ExecutionRouter>,
+        ctx: Arc<ExecutionContext>,
+        config: TpLadderConfig,
+    ) -> Self {
+        Self {
+            router,
+            ctx,
+            config,
+            ladders: Mutex::new(HashMap::new()),
+            shadow_state: None,
+        }
+    }
+
+    /// Book rung fills found by `poll_fills` in the shadow state
+    pub fn with_shadow_state(mut self, shadow_state: Arc<RwLock<ShadowState>>) -> Self {
+        self.shadow_state = Some(shadow_state);
+        self
+    }
+
+    pub fn start(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_millis(self.config.poll_interval_ms));
+            loop {
+                interval.tick().await;
+                self.poll_fills().await;
+            }
+        })
+    }
+
+    /// Ask the venue about every resting rung and apply what executed since
+    /// the last poll: the rung is marked filled, the fill is booked as a
+    /// close in the shadow state, and the resulting position events trim or
+    /// cancel the rest of the ladder. Returns the rungs that filled.
+    pub async fn poll_fills(&self) -> usize {
+        let resting: Vec<(String, String, TpRung)> = {
+            let ladders = self.ladders.lock().await;
+            ladders
+                .values()
+                .flat_map(|ladder| {
+                    ladder
+                        .rungs
+                        .iter()
+                        .filter(|r| r.status == RungStatus::Open)
+                        .map(|r| (ladder.symbol.clone(), ladder.exchange.clone(), r.clone()))
+                })
+                .collect()
+        };
+
+        let mut filled = 0;
+        for (symbol, exchange, rung) in resting {
+            let Some(adapter) = self.router.get_adapter(&exchange) else {
+                continue;
+            };
+            let response = match adapter
+                .get_order(&symbol.replace("/", ""), &rung.client_order_id)
+                .await
+            {
+                Ok(Some(response)) => response,
+                Ok(None) => continue,
+                Err(e) => {
+                    warn!("TP rung {} lookup failed: {}", rung.client_order_id, e);
+                    continue;
+                }
+            };
+            let qty = response.executed_qty.min(rung.quantity) - rung.filled;
+            if qty <= Decimal::ZERO {
+                continue;
+            }
+            info!(
+                "🪜 TP rung {} @ {} filled {} on {}",
+                symbol, rung.price, qty, exchange
+            );
+            self.on_rung_filled(&symbol, &rung.client_order_id, qty)
+                .await;
+            let events = self.book_fill(&symbol, &exchange, &rung, &response, qty);
+            self.apply_events(&events).await;
+            filled += 1;
+        }
+        filled
+    }
+
+    /// Book a rung fill as a close of `qty` so the shadow position follows
+    /// the venue. Unique signal per fill: a rung can fill in several polls.
+    fn book_fill(
+        &self,
+        symbol: &str,
+        exchange: &str,
+        rung: &TpRung,
+        response: &OrderResponse,
+        qty: Decimal,
+    ) -> Vec<ExecutionEvent> {
+        let Some(shadow_state) = &self.shadow_state else {
+            return Vec::new();
+        };
+        let mut state = shadow_state.write();
+        let Some(pos) = state.get_position(symbol).cloned() else {
+            warn!(
+                "TP rung {} filled with no position in {} to book it against",
+                rung.client_order_id, symbol
+            );
+            return Vec::new();
+        };
+        let order_id = rung
+            .order_id
+            .clone()
+            .unwrap_or_else(|| response.order_id.clone());
+        let signal_id = format!(
+            "tp-{}-{}-{}",
+            symbol,
+            rung.client_order_id,
+            rung.filled + qty
+        );
+        state.process_intent(Intent {
+            signal_id: signal_id.clone(),
+            source: Some("tp_ladder".to_string()),
+            symbol: symbol.to_string(),
+            direction: 0,
+            intent_type: IntentType::Close,
+            entry_zone: vec![],
+            stop_loss: pos.stop_loss,
+            take_profits: pos.take_profits.clone(),
+            size: qty,
+            status: IntentStatus::Validated,
+            filled_size: Decimal::ZERO,
+            child_fills: vec![],
+            ttl_ms: None,
+            partition_key: None,
+            causation_id: Some(pos.signal_id.clone()),
+            env: None,
+            subject: None,
+            t_signal: self.ctx.time.now_millis(),
+            t_analysis: None,
+            t_decision: None,
+            t_ingress: None,
+            t_exchange: response.t_exchange,
+            max_slippage_bps: None,
+            rejection_reason: None,
+            regime_state: pos.regime_state,
+            phase: pos.phase,
+            metadata: None,
+            exchange: Some(exchange.to_string()),
+            policy_hash: None,
+            position_mode: None,
+        });
+        state.record_child_order(
+            &signal_id,
+            exchange.to_string(),
+            rung.client_order_id.clone(),
+            order_id.clone(),
+            qty,
+        );
+        state.confirm_execution(
+            &signal_id,
+            &order_id,
+            response.avg_price.unwrap_or(rung.price),
+            qty,
+            true,
+            response.fee.unwrap_or(Decimal::ZERO),
+            response.fee_asset.clone().unwrap_or("USDT".to_string()),
+            exchange,
+        )
+    }
+
+    pub async fn ladder(&self, symbol: &str) -> Option<TpLadder> {
+        self.ladders.lock().await.get(symbol).cloned()
+    }
+
+    /// Dispatch shadow state events to the ladder lifecycle, including those
+    /// from fills the lifecycle itself books.
+    pub async fn apply_events(&self, events: &[ExecutionEvent]) {
+        let mut pending: VecDeque<ExecutionEvent> = events.iter().cloned().collect();
+        while let Some(event) = pending.pop_front() {
+            match &event {
+                ExecutionEvent::Opened(pos) => self.on_position_opened(pos).await,
+                ExecutionEvent::Updated(pos) => pending.extend(self.on_position_resized(pos).await),
+                ExecutionEvent::Closed(trade) => self.on_position_closed(&trade.symbol).await,
+                _ => {}
+            }
+        }
+    }
+
+    pub async fn on_position_opened(&self, pos: &Position) {
+        if !self.config.enabled || pos.take_profits.is_empty() {
+            return;
+        }
+        let Some(exchange) = pos.exchange.as_ref().map(|e| e.to_lowercase()) else {
+            warn!(
+                "TP ladder skipped for {}: position has no exchange",
+                pos.symbol
+            );
+            return;
+        };
+        let Some(adapter) = self.router.get_adapter(&exchange) else {
+            warn!(
+                "TP ladder skipped for {}: adapter '{}' not registered",
+                pos.symbol, exchange
+            );
+            return;
+        };
+        // The entry already carried this take-profit to the venue
+        if adapter.supports_native_brackets()
+            && Bracket::from_levels(pos.stop_loss, &pos.take_profits).is_some()
+        {
+            info!(
+                "🪜 TP ladder skipped for {}: bracket held natively by {}",
+                pos.symbol, exchange
+            );
+            return;
+        }
+
+        let side = close_side(&pos.side);
+        let sizes = ladder_sizes(pos.size, pos.take_profits.len(), self.config.distribution);
+
+        let mut rungs = Vec::with_capacity(sizes.len());
+        for (price, quantity) in pos.take_profits.iter().zip(sizes) {
+            if quantity <= Decimal::ZERO {
+                continue;
+            }
+            let rung = self
+                .place_rung(
+                    adapter.as_ref(),
+                    &exchange,
+                    &pos.symbol,
+                    &side,
+                    *price,
+                    quantity,
+                )
+                .await;
+            rungs.push(rung);
+        }
+
+        info!(
+            "🪜 TP ladder placed for {}: {} rungs on {}",
+            pos.symbol,
+            rungs.len(),
+            exchange
+        );
+        self.ladders.lock().await.insert(
+            pos.symbol.clone(),
+            TpLadder {
+                symbol: pos.symbol.clone(),
+                exchange,
+                side,
+                rungs,
+            },
+        );
+    }
+
+    /// Record a fill against a TP rung.
+    pub async fn on_rung_filled(&self, symbol: &str, client_order_id: &str, qty: Decimal) {
+        let mut ladders = self.ladders.lock().await;
+        let Some(ladder) = ladders.get_mut(symbol) else {
+            return;
+        };
+        if let Some(rung) = ladder
+            .rungs
+            .iter_mut()
+            .find(|r| r.client_order_id == client_order_id)
+        {
+            rung.filled = (rung.filled + qty).min(rung.quantity);
+            if rung.remaining() <= Decimal::ZERO {
+                rung.status = RungStatus::Filled;
+            }
+        }
+        if ladder.outstanding() <= Decimal::ZERO {
+            ladders.remove(symbol);
+        }
+    }
+
+    /// Shrink the ladder when the position was partially closed elsewhere, trimming the
+    /// furthest levels first so resting TPs never exceed the remaining position.
+    /// Returns the events of rung fills the cancels reported.
+    pub async fn on_position_resized(&self, pos: &Position) -> Vec<ExecutionEvent> {
+        let mut events = Vec::new();
+        let mut ladders = self.ladders.lock().await;
+        let Some(ladder) = ladders.get_mut(&pos.symbol) else {
+            return events;
+        };
+        let mut excess = ladder.outstanding() - pos.size;
+        if excess <= Decimal::ZERO {
+            return events;
+        }
+        let Some(adapter) = self.router.get_adapter(&ladder.exchange) else {
+            return events;
+        };
+
+        info!(
+            "🪜 Position {} resized to {}: trimming {} from TP ladder",
+            pos.symbol, pos.size, excess
+        );
+
+        for idx in (0..ladder.rungs.len()).rev() {
+            if excess <= Decimal::ZERO {
+                break;
+            }
+            if ladder.rungs[idx].status != RungStatus::Open {
+                continue;
+            }
+            // A rung whose cancel failed may still be working: re-placing it
+            // would rest two reduce orders, so a nearer rung is trimmed instead
+            let Ok(cancelled) =
+                cancel_rung(adapter.as_ref(), &ladder.symbol, &mut ladder.rungs[idx]).await
+            else {
+                continue;
+            };
+            if let Some(response) = cancelled {
+                let rung = &ladder.rungs[idx];
+                let late_fill = response.executed_qty.min(rung.quantity) - rung.filled;
+                if late_fill > Decimal::ZERO {
+                    info!(
+                        "🪜 TP rung {} @ {} filled {} before its cancel",
+                        ladder.symbol, rung.price, late_fill
+                    );
+                    events.extend(self.book_fill(
+                        &ladder.symbol,
+                        &ladder.exchange,
+                        rung,
+                        &response,
+                        late_fill,
+                    ));
+                    ladder.rungs[idx].filled += late_fill;
+                }
+            }
+            let remaining = ladder.rungs[idx].remaining();
+            let trim = excess.min(remaining);
+            excess -= trim;
+
+            let keep = remaining - trim;
+            if keep > Decimal::ZERO {
+                // No amend in the adapter API: re-place the reduced rung
+                let price = ladder.rungs[idx].price;
+                let rung = self
+                    .place_rung(
+                        adapter.as_ref(),
+                        &ladder.exchange,
+                        &ladder.symbol,
+                        &ladder.side,
+                        price,
+                        keep,
+                    )
+                    .await;
+                ladder.rungs[idx] = rung;
+            }
+        }
+        events
+    }
+
+    /// Cancel every resting rung once the position is gone.
+    pub async fn on_position_closed(&self, symbol: &str) {
+        let Some(mut ladder) = self.ladders.lock().await.remove(symbol) else {
+            return;
+        };
+        let Some(adapter) = self.router.get_adapter(&ladder.exchange) else {
+            return;
+        };
+        for rung in ladder.rungs.iter_mut() {
+            if rung.status == RungStatus::Open {
+                // A refused cancel is logged; the ladder goes with the position
+                let _ = cancel_rung(adapter.as_ref(), &ladder.symbol, rung).await;
+            }
+        }
+        info!("🪜 TP ladder cancelled for closed position {}", symbol);
+    }
+
+    async fn place_rung(
+        &self,
+        adapter: &(dyn ExchangeAdapter + Send + Sync),
+        exchange: &str,
+        symbol: &str,
+        side: &Side,
+        price: Decimal,
+        quantity: Decimal,
+    ) -> TpRung {
+        let client_order_id = match self
+            .ctx
+            .client_order_ids
+            .generate("tp", max_len_for(exchange))
+        {
+            Ok(id) => id,
+            Err(e) => {
+                error!("❌ TP rung {} @ {} not placed: {}", symbol, price, e);
+                return TpRung {
+                    price,
+                    quantity,
+                    filled: Decimal::ZERO,
+                    client_order_id: String::new(),
+                    order_id: None,
+                    status: RungStatus::Rejected,
+                };
+            }
+        };
+        let req = OrderRequest {
+            symbol: symbol.replace("/", ""),
+            side: side.clone(),
+            order_type: OrderType::Limit,
+            quantity,
+            price: Some(price),
+            stop_price: None,
+            client_order_id: client_order_id.clone(),
+            reduce_only: true,
+            correlation_id: None,
+            swap_mode: SwapMode::ExactIn,
+            bracket: None,
+        };
+
+        let (order_id, status) = match adapter.place_order(req).await {
+            Ok(resp) => (Some(resp.order_id), RungStatus::Open),
+            Err(e) => {
+                error!("❌ TP rung {} @ {} rejected: {}", symbol, price, e);
+                (None, RungStatus::Rejected)
+            }
+        };
+
+        TpRung {
+            price,
+            quantity,
+            filled: Decimal::ZERO,
+            client_order_id,
+            order_id,
+            status,
+        }
+    }
+}
+
+/// Cancel a resting rung, returning the venue's cancel response (None if the
+/// rung never reached the venue). A refused cancel leaves the rung Open.
+async fn cancel_rung(
+    adapter: &(dyn ExchangeAdapter + Send + Sync),
+    symbol: &str,
+    rung: &mut TpRung,
+) -> Result<Option<OrderResponse>, ExchangeError> {
+    let response = match &rung.order_id {
+        Some(order_id) => match adapter
+            .cancel_order(&symbol.replace("/", ""), order_id)
+            .await
+        {
+            Ok(response) => Some(response),
+            Err(e) => {
+                error!("❌ Failed to cancel TP rung {}: {}", order_id, e);
+                return Err(e);
+            }
+        },
+        None => None,
+    };
+    rung.status = RungStatus::Cancelled;
+    Ok(response)
+}
+
+fn close_side(side: &Side) -> Side {
+    match side {
+        Side::Buy | Side::Long => Side::Sell,
+        Side::Sell | Side::Short => Side::Buy,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::MockAdapter;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn position(size: Decimal) -> Position {
+        Position {
+            symbol: "BTC/USDT".to_string(),
+            side: Side::Long,
+            size,
+            entry_price: dec!(50000),
+            stop_loss: dec!(49000),
+            take_profits: vec![dec!(51000), dec!(52000), dec!(53000)],
+            signal_id: "sig-1".to_string(),
+            opened_at: Utc::now(),
+            regime_state: None,
+            phase: None,
+            metadata: None,
+            exchange: Some("binance".to_string()),
+            position_mode: None,
+            realized_pnl: Decimal::ZERO,
+            unrealized_pnl: Decimal::ZERO,
+            fees_paid: Decimal::ZERO,
+            funding_paid: Decimal::ZERO,
+            last_mark_price: None,
+            last_update_ts: 0,
+            entry_lots: Vec::new(),
+        }
+    }
+
+    fn executor(distribution: TpDistribution) -> (TpLadderExecutor, Arc<MockAdapter>) {
+        let adapter = Arc::new(MockAdapter::new("binance").resting());
+        let router = Arc::new(ExecutionRouter::new());
+        router.register("binance", adapter.clone());
+        let executor = TpLadderExecutor::new(
+            router,
+            Arc::new(ExecutionContext::new_simulated(0)),
+            TpLadderConfig {
+                enabled: true,
+                distribution,
+                ..Default::default()
+            },
+        );
+        (executor, adapter)
+    }
+
+    #[test]
+    fn test_ladder_sizing() {
+        let equal = ladder_sizes(dec!(1.0), 3, TpDistribution::Equal);
+        assert_eq!(equal.iter().sum::<Decimal>(), dec!(1.0));
+        assert_eq!(equal[0], dec!(0.33333333));
+        assert_eq!(equal[2], dec!(0.33333334));
+
+        // Weights 3:2:1
+        let front = ladder_sizes(dec!(6.0), 3, TpDistribution::FrontLoaded);
+        assert_eq!(front, vec![dec!(3.0), dec!(2.0), dec!(1.0)]);
+
+        assert!(ladder_sizes(dec!(1.0), 0, TpDistribution::Equal).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_ladder_placed_on_open() {
+        let (executor, adapter) = executor(TpDistribution::FrontLoaded);
+        executor.on_position_opened(&position(dec!(6.0))).await;
+
+        let placed = adapter.placed.lock().clone();
+        assert_eq!(placed.len(), 3);
+        assert!(placed.iter().all(|o| o.reduce_only
+            && o.side == Side::Sell
+            && o.order_type == OrderType::Limit
+            && o.symbol == "BTCUSDT"));
+        assert_eq!(placed[0].price, Some(dec!(51000)));
+        assert_eq!(placed[0].quantity, dec!(3.0));
+        assert_eq!(placed[2].quantity, dec!(1.0));
+
+        let ladder = executor.ladder("BTC/USDT").await.unwrap();
+        assert_eq!(ladder.outstanding(), dec!(6.0));
+    }
+
+    #[tokio::test]
+    async fn test_partial_fill_and_external_reduce_trim_far_rungs() {
+        let (executor, adapter) = executor(TpDistribution::Equal);
+        executor.on_position_opened(&position(dec!(3.0))).await;
+
+        // First TP fills; the position shrinks accordingly, ladder unchanged
+        let first = executor.ladder("BTC/USDT").await.unwrap().rungs[0].clone();
+        executor
+            .on_rung_filled("BTC/USDT", &first.client_order_id, dec!(1.0))
+            .await;
+        executor.on_position_resized(&position(dec!(2.0))).await;
+        assert_eq!(adapter.cancelled.lock().len(), 0);
+
+        // Position partially closed elsewhere: 2.0 -> 1.5, trim the furthest rung
+        executor.on_position_resized(&position(dec!(1.5))).await;
+        let ladder = executor.ladder("BTC/USDT").await.unwrap();
+        assert_eq!(ladder.rungs[0].status, RungStatus::Filled);
+        assert_eq!(ladder.rungs[1].remaining(), dec!(1.0));
+        assert_eq!(ladder.rungs[2].remaining(), dec!(0.5));
+        assert_eq!(ladder.rungs[2].price, dec!(53000));
+        assert_eq!(ladder.outstanding(), dec!(1.5));
+        assert_eq!(adapter.cancelled.lock().len(), 1);
+
+        let replaced = adapter.placed.lock().last().cloned().unwrap();
+        assert_eq!(replaced.quantity, dec!(0.5));
+        assert!(replaced.reduce_only);
+    }
+
+    #[tokio::test]
+    async fn test_failed_trim_cancel_keeps_rung_and_trims_nearer_one() {
+        let adapter = Arc::new(MockAdapter::new("binance").resting().failing_cancels());
+        let router = Arc::new(ExecutionRouter::new());
+        router.register("binance", adapter.clone());
+        let executor = TpLadderExecutor::new(
+            router,
+            Arc::new(ExecutionContext::new_simulated(0)),
+            TpLadderConfig {
+                enabled: true,
+                distribution: TpDistribution::Equal,
+                ..Default::default()
+            },
+        );
+        executor.on_position_opened(&position(dec!(3.0))).await;
+
+        executor.on_position_resized(&position(dec!(2.5))).await;
+        // Every cancel was refused: nothing is marked cancelled or re-placed
+        let ladder = executor.ladder("BTC/USDT").await.unwrap();
+        assert!(ladder.rungs.iter().all(|r| r.status == RungStatus::Open));
+        assert_eq!(adapter.cancelled.lock().len(), 3);
+        assert_eq!(adapter.placed.lock().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_fill_reported_by_trim_cancel_is_booked() {
+        use crate::persistence::redb_store::RedbStore;
+        use crate::persistence::store::PersistenceStore;
+        use crate::persistence::wal::WalManager;
+
+        let path = format!("/tmp/test_tp_ladder_trim_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let persistence = Arc::new(PersistenceStore::new(redb, wal));
+        persistence.save_position(&position(dec!(2.5))).unwrap();
+        let ctx = Arc::new(ExecutionContext::new_simulated(0));
+        let state = Arc::new(RwLock::new(ShadowState::new(
+            persistence,
+            ctx.clone(),
+            Some(10_000.0),
+        )));
+
+        // The far rung executed 0.2 before the trim's cancel landed
+        let adapter = Arc::new(
+            MockAdapter::new("binance")
+                .resting()
+                .with_cancel_fills(dec!(0.2)),
+        );
+        let router = Arc::new(ExecutionRouter::new());
+        router.register("binance", adapter.clone());
+        let executor = TpLadderExecutor::new(
+            router,
+            ctx,
+            TpLadderConfig {
+                enabled: true,
+                distribution: TpDistribution::Equal,
+                ..Default::default()
+            },
+        )
+        .with_shadow_state(state.clone());
+        executor.on_position_opened(&position(dec!(3.0))).await;
+
+        let events = executor.on_position_resized(&position(dec!(2.5))).await;
+        assert!(!events.is_empty());
+        assert_eq!(
+            state.read().get_position("BTC/USDT").unwrap().size,
+            dec!(2.3)
+        );
+        // Only the unexecuted part of the trimmed rung is re-placed
+        let ladder = executor.ladder("BTC/USDT").await.unwrap();
+        assert_eq!(ladder.rungs[2].quantity, dec!(0.3));
+        assert_eq!(ladder.outstanding(), dec!(2.3));
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_on_close() {
+        let (executor, adapter) = executor(TpDistribution::Equal);
+        executor.on_position_opened(&position(dec!(3.0))).await;
+
+        let first = executor.ladder("BTC/USDT").await.unwrap().rungs[0].clone();
+        executor
+            .on_rung_filled("BTC/USDT", &first.client_order_id, dec!(1.0))
+            .await;
+        executor.on_position_closed("BTC/USDT").await;
+
+        // Only the two resting rungs are cancelled
+        assert_eq!(adapter.cancelled.lock().len(), 2);
+        assert!(executor.ladder("BTC/USDT").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_polled_rung_fills_are_booked_and_close_the_position() {
+        use crate::persistence::redb_store::RedbStore;
+        use crate::persistence::store::PersistenceStore;
+        use crate::persistence::wal::WalManager;
+
+        let path = format!("/tmp/test_tp_ladder_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let persistence = Arc::new(PersistenceStore::new(redb, wal));
+        persistence.save_position(&position(dec!(3.0))).unwrap();
+        let ctx = Arc::new(ExecutionContext::new_simulated(0));
+        let state = Arc::new(RwLock::new(ShadowState::new(
+            persistence,
+            ctx.clone(),
+            Some(10_000.0),
+        )));
+
+        // Lookups report nothing executed until the third one
+        let adapter = Arc::new(
+            MockAdapter::new("binance")
+                .resting()
+                .filling_after_polls(3)
+                .with_fill_price(dec!(52000)),
+        );
+        let router = Arc::new(ExecutionRouter::new());
+        router.register("binance", adapter.clone());
+        let executor = TpLadderExecutor::new(
+            router,
+            ctx,
+            TpLadderConfig {
+                enabled: true,
+                distribution: TpDistribution::Equal,
+                ..Default::default()
+            },
+        )
+        .with_shadow_state(state.clone());
+        executor.on_position_opened(&position(dec!(3.0))).await;
+
+        assert_eq!(executor.poll_fills().await, 1);
+        let ladder = executor.ladder("BTC/USDT").await.unwrap();
+        assert_eq!(ladder.rungs[2].status, RungStatus::Filled);
+        assert_eq!(ladder.outstanding(), dec!(2.0));
+        let pos = state.read().get_position("BTC/USDT").cloned().unwrap();
+        assert_eq!(pos.size, dec!(2.0));
+
+        // The nearer rungs fill next and take the position flat
+        assert_eq!(executor.poll_fills().await, 2);
+        assert!(state.read().get_position("BTC/USDT").is_none());
+        assert!(executor.ladder("BTC/USDT").await.is_none());
+        let trades = state.read().get_trade_history().clone();
+        assert_eq!(trades.iter().map(|t| t.size).sum::<Decimal>(), dec!(3.0));
+        assert!(trades.iter().all(|t| t.exit_price == dec!(52000)));
+        assert!(adapter.cancelled.lock().is_empty());
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    #[tokio::test]
+    async fn test_disabled_places_nothing() {
+        let adapter = Arc::new(MockAdapter::new("binance").resting());
+        let router = Arc::new(ExecutionRouter::new());
+        router.register("binance", adapter.clone());
+        let executor = TpLadderExecutor::new(
+            router,
+            Arc::new(ExecutionContext::new_simulated(0)),
+            TpLadderConfig::default(),
+        );
+        executor.on_position_opened(&position(dec!(3.0))).await;
+        assert!(adapter.placed.lock().is_empty());
+    }
+}
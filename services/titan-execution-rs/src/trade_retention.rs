@@ -0,0 +1,231 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+use crate::config::TradeRetentionConfig;
+use crate::context::TimeProvider;
+use crate::persistence::store::PersistenceStore;
+
+/// Periodically deletes persisted trades that fall outside the configured
+/// retention (by count and/or age), and terminal intents past their age
+/// limit. The in-memory history is capped separately by `ShadowState`.
+pub struct TradeRetentionPruner {
+    persistence: Arc<PersistenceStore>,
+    config: TradeRetentionConfig,
+    time: Arc<dyn TimeProvider>,
+}
+
+impl TradeRetentionPruner {
+    pub fn new(
+        persistence: Arc<PersistenceStore>,
+        config: TradeRetentionConfig,
+        time: Arc<dyn TimeProvider>,
+    ) -> Self {
+        Self {
+            persistence,
+            config,
+            time,
+        }
+    }
+
+    pub fn start(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_millis(self.config.prune_interval_ms));
+            loop {
+                interval.tick().await;
+                self.prune();
+            }
+        })
+    }
+
+    /// Prune once. Returns the number of trades and intents removed.
+    pub fn prune(&self) -> usize {
+        self.prune_trades() + self.prune_intents()
+    }
+
+    fn prune_trades(&self) -> usize {
+        if self.config.max_count.is_none() && self.config.max_age_ms.is_none() {
+            return 0;
+        }
+        let closed_before = self
+            .config
+            .max_age_ms
+            .map(|age| self.time.now_millis() - age);
+        match self
+            .persistence
+            .prune_trades(self.config.max_count, closed_before)
+        {
+            Ok(0) => 0,
+            Ok(removed) => {
+                info!("🧹 Pruned {} persisted trades past retention", removed);
+                removed
+            }
+            Err(e) => {
+                error!("Failed to prune trade history: {}", e);
+                0
+            }
+        }
+    }
+
+    fn prune_intents(&self) -> usize {
+        let Some(max_age_ms) = self.config.intent_max_age_ms else {
+            return 0;
+        };
+        match self
+            .persistence
+            .prune_terminal_intents(self.time.now_millis() - max_age_ms)
+        {
+            Ok(0) => 0,
+            Ok(removed) => {
+                info!("🧹 Pruned {} terminal intents past retention", removed);
+                removed
+            }
+            Err(e) => {
+                error!("Failed to prune terminal intents: {}", e);
+                0
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::SimulatedTimeProvider;
+    use crate::model::{Intent, IntentStatus, Side, TradeRecord};
+    use crate::persistence::redb_store::RedbStore;
+    use crate::persistence::wal::WalManager;
+    use chrono::TimeZone;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    const NOW_MS: i64 = 1_700_000_000_000;
+    const HOUR_MS: i64 = 3_600_000;
+
+    fn trade(id: &str, closed_at_ms: i64) -> TradeRecord {
+        let closed_at = chrono::Utc.timestamp_millis_opt(closed_at_ms).unwrap();
+        TradeRecord {
+            signal_id: id.to_string(),
+            symbol: "BTC/USDT".to_string(),
+            side: Side::Buy,
+            entry_price: dec!(50000),
+            exit_price: dec!(50100),
+            size: dec!(0.01),
+            pnl: dec!(1),
+            pnl_pct: dec!(0.2),
+            fee: Decimal::ZERO,
+            fee_asset: "USDT".to_string(),
+            opened_at: closed_at,
+            closed_at,
+            close_reason: "test".to_string(),
+            metadata: None,
+            lots: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_prunes_trades_past_count_and_age() {
+        let path = format!("/tmp/test_trade_retention_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let persistence = Arc::new(PersistenceStore::new(redb, wal));
+
+        // One trade closed per hour over the last 10 hours, plus two from days ago
+        for i in 0..10 {
+            persistence
+                .save_trade(&trade(&format!("recent-{}", i), NOW_MS - i * HOUR_MS))
+                .unwrap();
+        }
+        persistence
+            .save_trade(&trade("old-1", NOW_MS - 72 * HOUR_MS))
+            .unwrap();
+        persistence
+            .save_trade(&trade("old-2", NOW_MS - 96 * HOUR_MS))
+            .unwrap();
+
+        let pruner = TradeRetentionPruner::new(
+            persistence.clone(),
+            TradeRetentionConfig {
+                max_count: Some(8),
+                max_age_ms: Some(48 * HOUR_MS),
+                intent_max_age_ms: None,
+                ..Default::default()
+            },
+            Arc::new(SimulatedTimeProvider::new(NOW_MS)),
+        );
+
+        // Both old trades by age, then the two oldest recent ones by count
+        assert_eq!(pruner.prune(), 4);
+        let mut kept: Vec<String> = persistence
+            .load_trades()
+            .unwrap()
+            .into_iter()
+            .map(|t| t.signal_id)
+            .collect();
+        kept.sort();
+        let expected: Vec<String> = (0..8).map(|i| format!("recent-{}", i)).collect();
+        assert_eq!(kept, expected);
+
+        // Nothing left to prune
+        assert_eq!(pruner.prune(), 0);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+
+    fn intent(signal_id: &str, status: IntentStatus, t_signal: i64) -> Intent {
+        serde_json::from_value(serde_json::json!({
+            "signal_id": signal_id,
+            "symbol": "BTC/USDT",
+            "direction": 1,
+            "type": "BUY_SETUP",
+            "entry_zone": [50000.0],
+            "size": 0.1,
+            "status": status,
+            "t_signal": t_signal,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_prunes_terminal_intents_past_age() {
+        let path = format!("/tmp/test_intent_retention_{}.redb", uuid::Uuid::new_v4());
+        let redb = Arc::new(RedbStore::new(&path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        let persistence = Arc::new(PersistenceStore::new(redb, wal));
+
+        let old = NOW_MS - 72 * HOUR_MS;
+        for intent in [
+            intent("old-executed", IntentStatus::Executed, old),
+            intent("old-rejected", IntentStatus::Rejected, old),
+            intent("old-working", IntentStatus::PartiallyFilled, old),
+            intent("recent-executed", IntentStatus::Executed, NOW_MS - HOUR_MS),
+        ] {
+            persistence.save_intent(&intent).unwrap();
+        }
+
+        let pruner = TradeRetentionPruner::new(
+            persistence.clone(),
+            TradeRetentionConfig {
+                intent_max_age_ms: Some(48 * HOUR_MS),
+                ..Default::default()
+            },
+            Arc::new(SimulatedTimeProvider::new(NOW_MS)),
+        );
+
+        // Only old terminal intents go; a working one is never pruned
+        assert_eq!(pruner.prune(), 2);
+        let mut kept: Vec<String> = persistence
+            .load_intents()
+            .unwrap()
+            .into_iter()
+            .map(|i| i.signal_id)
+            .collect();
+        kept.sort();
+        assert_eq!(kept, vec!["old-working", "recent-executed"]);
+
+        std::fs::remove_file(path).unwrap_or(());
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use tracing::warn;
+
+use crate::config::VolatilityGateConfig;
+use crate::context::{SystemTimeProvider, TimeProvider};
+use crate::market_data::engine::MarketDataEngine;
+use crate::model::Intent;
+use crate::risk_guard::RiskRejectionReason;
+
+/// Realized volatility per symbol from the market data tick history, and the
+/// per-symbol reduce-only mode it drives. A symbol enters reduce-only when its
+/// volatility reaches `enter_vol_bps` and leaves once it falls below
+/// `exit_vol_bps`, so a symbol hovering at the threshold does not flap.
+pub struct VolatilityTracker {
+    config: VolatilityGateConfig,
+    market_data: Arc<MarketDataEngine>,
+    time: Arc<dyn TimeProvider>,
+    reduce_only: Mutex<HashSet<String>>,
+}
+
+impl VolatilityTracker {
+    pub fn new(config: VolatilityGateConfig, market_data: Arc<MarketDataEngine>) -> Self {
+        Self::with_time(config, market_data, Arc::new(SystemTimeProvider))
+    }
+
+    pub fn with_time(
+        config: VolatilityGateConfig,
+        market_data: Arc<MarketDataEngine>,
+        time: Arc<dyn TimeProvider>,
+    ) -> Self {
+        Self {
+            config,
+            market_data,
+            time,
+            reduce_only: Mutex::new(HashSet::new()),
+        }
+    }
+
+    fn key(symbol: &str) -> String {
+        symbol.replace("/", "").replace("_", "").to_uppercase()
+    }
+
+    /// Realized volatility (bps) of the mid over the last `window_ms`: the root
+    /// of summed squared tick-to-tick log returns. None with too few ticks.
+    pub fn realized_vol_bps(&self, symbol: &str) -> Option<f64> {
+        let now = self.time.now_millis();
+        let mids: Vec<f64> = self
+            .market_data
+            .ticks_between(symbol, now - self.config.window_ms, now)
+            .iter()
+            .filter_map(|t| ((t.best_bid + t.best_ask) / Decimal::TWO).to_f64())
+            .filter(|mid| *mid > 0.0)
+            .collect();
+        if mids.len() < self.config.min_ticks.max(2) {
+            return None;
+        }
+        let variance: f64 = mids.windows(2).map(|w| (w[1] / w[0]).ln().powi(2)).sum();
+        Some(variance.sqrt() * 10_000.0)
+    }
+
+    /// Whether `symbol` is in volatility reduce-only mode, updating the mode
+    /// from the current volatility.
+    pub fn is_reduce_only(&self, symbol: &str) -> bool {
+        let key = Self::key(symbol);
+        let vol = self.realized_vol_bps(symbol);
+        let mut reduce_only = self.reduce_only.lock();
+        let active = reduce_only.contains(&key);
+        match vol {
+            Some(vol) if !active && vol >= self.config.enter_vol_bps => {
+                warn!(symbol = %symbol, vol_bps = vol, "Volatility reduce-only engaged");
+                reduce_only.insert(key);
+                true
+            }
+            Some(vol) if active && vol < self.config.exit_vol_bps => {
+                warn!(symbol = %symbol, vol_bps = vol, "Volatility reduce-only released");
+                reduce_only.remove(&key);
+                false
+            }
+            _ => active,
+        }
+    }
+
+    pub fn check(&self, intent: &Intent, reduces_risk: bool) -> Result<(), RiskRejectionReason> {
+        if !self.config.enabled || reduces_risk || !self.is_reduce_only(&intent.symbol) {
+            return Ok(());
+        }
+        warn!(
+            signal_id = %intent.signal_id,
+            symbol = %intent.symbol,
+            "Risk Reject: symbol is reduce-only under high volatility"
+        );
+        Err(RiskRejectionReason::VolatilityReduceOnly {
+            symbol: intent.symbol.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::SimulatedTimeProvider;
+    use crate::market_data::types::BookTicker;
+    use rust_decimal_macros::dec;
+
+    const NOW_MS: i64 = 1_700_000_000_000;
+
+    fn tick(symbol: &str, mid: Decimal, at: i64) -> BookTicker {
+        BookTicker {
+            symbol: symbol.to_string(),
+            best_bid: mid - dec!(0.5),
+            best_bid_qty: dec!(1),
+            best_ask: mid + dec!(0.5),
+            best_ask_qty: dec!(1),
+            transaction_time: at,
+            event_time: at,
+        }
+    }
+
+    #[test]
+    fn test_hysteresis_between_enter_and_exit() {
+        let engine = Arc::new(MarketDataEngine::new(None));
+        let time = Arc::new(SimulatedTimeProvider::new(NOW_MS));
+        let tracker = VolatilityTracker::with_time(
+            VolatilityGateConfig {
+                enabled: true,
+                window_ms: 10_000,
+                enter_vol_bps: 100.0,
+                exit_vol_bps: 50.0,
+                min_ticks: 3,
+            },
+            engine.clone(),
+            time.clone(),
+        );
+
+        // Too few ticks: no reading, no mode
+        engine.record_tick(tick("BTCUSDT", dec!(50000), NOW_MS - 9_000));
+        assert_eq!(tracker.realized_vol_bps("BTCUSDT"), None);
+        assert!(!tracker.is_reduce_only("BTC/USDT"));
+
+        // Two ~1.5% swings: well above the entry threshold
+        engine.record_tick(tick("BTCUSDT", dec!(50750), NOW_MS - 8_000));
+        engine.record_tick(tick("BTCUSDT", dec!(50000), NOW_MS - 7_000));
+        assert!(tracker.realized_vol_bps("BTCUSDT").unwrap() > 100.0);
+        assert!(tracker.is_reduce_only("BTC/USDT"));
+
+        // Swings age out, leaving ~0.6% of movement: between exit and entry, stays engaged
+        engine.record_tick(tick("BTCUSDT", dec!(50000), NOW_MS + 1_000));
+        engine.record_tick(tick("BTCUSDT", dec!(50300), NOW_MS + 2_000));
+        engine.record_tick(tick("BTCUSDT", dec!(50000), NOW_MS + 3_000));
+        time.advance(9_500);
+        let vol = tracker.realized_vol_bps("BTCUSDT").unwrap();
+        assert!(vol > 50.0 && vol < 100.0, "vol {}", vol);
+        assert!(tracker.is_reduce_only("BTC/USDT"));
+
+        // Calm again: released
+        engine.record_tick(tick("BTCUSDT", dec!(50010), NOW_MS + 10_000));
+        engine.record_tick(tick("BTCUSDT", dec!(50000), NOW_MS + 11_000));
+        engine.record_tick(tick("BTCUSDT", dec!(50005), NOW_MS + 12_000));
+        time.advance(8_000);
+        assert!(!tracker.is_reduce_only("BTC/USDT"));
+    }
+}
@@ -5,27 +5,34 @@ use hmac::{Hmac, Mac};
 use parking_lot::RwLock;
 use serde_json::Value;
 use sha2::Sha256;
+use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 use std::time::Duration;
 use titan_execution_rs::armed_state::ArmedState;
 use titan_execution_rs::circuit_breaker::GlobalHalt;
 use titan_execution_rs::context::ExecutionContext;
 use titan_execution_rs::drift_detector::DriftDetector;
+use titan_execution_rs::event_log::EventLog;
 use titan_execution_rs::exchange::adapter::{
-    ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse,
+    ExchangeAdapter, ExchangeError, OrderRequest, OrderResponse, OrderStatus,
 };
 use titan_execution_rs::exchange::router::ExecutionRouter;
 use titan_execution_rs::execution_constraints::ConstraintsStore;
+use titan_execution_rs::execution_report::ExecutionReportStore;
+use titan_execution_rs::maintenance_mode::MaintenanceMode;
 use titan_execution_rs::market_data::engine::MarketDataEngine;
 use titan_execution_rs::model::Position;
 use titan_execution_rs::nats_engine;
 use titan_execution_rs::order_manager::OrderManager;
+use titan_execution_rs::panic_watchdog::PanicWatchdog;
 use titan_execution_rs::persistence::redb_store::RedbStore;
 use titan_execution_rs::persistence::store::PersistenceStore;
 use titan_execution_rs::persistence::wal::WalManager;
+use titan_execution_rs::rejection_breaker::RejectionRateBreaker;
 use titan_execution_rs::risk_guard::RiskGuard;
 use titan_execution_rs::risk_policy::RiskPolicy;
 use titan_execution_rs::shadow_state::ShadowState;
+use titan_execution_rs::shutdown::Flattener;
 use titan_execution_rs::simulation_engine::SimulationEngine;
 use titan_execution_rs::subjects;
 
@@ -55,7 +62,7 @@ async fn test_full_execution_flow() {
     let (persistence, _db_path) = create_test_persistence();
     let ctx = Arc::new(ExecutionContext::new_system());
     let shadow_state = Arc::new(RwLock::new(ShadowState::new(
-        persistence,
+        persistence.clone(),
         ctx.clone(),
         Some(10000.0),
     )));
@@ -77,7 +84,8 @@ async fn test_full_execution_flow() {
                 order_id: format!("mock-{}", order.client_order_id),
                 client_order_id: order.client_order_id,
                 symbol: order.symbol,
-                status: "FILLED".to_string(),
+                status: OrderStatus::Filled,
+                raw_status: "FILLED".to_string(),
                 avg_price: None,
                 executed_qty: order.quantity,
                 t_exchange: None,
@@ -123,8 +131,11 @@ async fn test_full_execution_flow() {
 
     // 3. Start Engine
     let drift_detector = Arc::new(DriftDetector::new(50.0, 1000, 100.0));
-    let constraints_store = Arc::new(ConstraintsStore::new());
+    let constraints_store = Arc::new(ConstraintsStore::default());
     let armed_state = Arc::new(ArmedState::new()); // Test state, no persistence
+    let maintenance = Arc::new(MaintenanceMode::with_file(
+        std::env::temp_dir().join(format!("titan_maint_{}", uuid::Uuid::new_v4())),
+    ));
 
     let _handle = nats_engine::start_nats_engine(
         client.clone(),
@@ -134,11 +145,29 @@ async fn test_full_execution_flow() {
         sim_engine.clone(),
         halt.clone(),
         armed_state.clone(),
+        maintenance,
         risk_guard.clone(),
         ctx.clone(),
-        5000, // freshness threshold
+        Arc::new(AtomicU64::new(5000)), // freshness threshold
         drift_detector,
         constraints_store,
+        None,
+        None,
+        None,
+        None,
+        Arc::new(ExecutionReportStore::default()),
+        Arc::new(EventLog::new(persistence.clone())),
+        None,
+        Default::default(),
+        Vec::new(),
+        Arc::new(PanicWatchdog::new(halt.clone(), &Default::default())),
+        Arc::new(RejectionRateBreaker::new(Default::default())),
+        Arc::new(Flattener::new(
+            router.clone(),
+            shadow_state.clone(),
+            persistence,
+            ctx.clone(),
+        )),
     )
     .await
     .expect("Failed to start engine");
@@ -168,3 +168,76 @@ fn test_state_recovery() {
     // Cleanup
     let _ = std::fs::remove_file(db_path);
 }
+
+#[test]
+fn test_redelivered_fill_after_crash_is_not_double_applied() {
+    let db_path = format!("/tmp/recovery_test_{}.redb", uuid::Uuid::new_v4());
+    let open = || {
+        let redb = Arc::new(RedbStore::new(&db_path).unwrap());
+        let wal = Arc::new(WalManager::new(redb.clone()));
+        Arc::new(PersistenceStore::new(redb, wal))
+    };
+
+    let mut intent = create_test_intent("sig-redeliver");
+    intent.size = dec!(0.2);
+
+    // 1. Engine A applies a partial fill, then crashes
+    {
+        let persistence = open();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let mut state = ShadowState::new(persistence.clone(), ctx, Some(10000.0));
+        let pending = state.process_intent(intent.clone());
+
+        let events = state.confirm_execution(
+            "sig-redeliver",
+            "fill-1",
+            dec!(50000.0),
+            dec!(0.1),
+            true,
+            dec!(0),
+            "USDT".to_string(),
+            "MOCK",
+        );
+        assert_eq!(events.len(), 1);
+        assert_eq!(state.get_position("BTC/USDT").unwrap().size, dec!(0.1));
+
+        // The stored intent lags the fill: it no longer remembers fill-1
+        persistence.save_intent(&pending).unwrap();
+    }
+
+    // 2. Engine B recovers and the broker redelivers the same fill
+    {
+        let persistence = open();
+        let ctx = Arc::new(ExecutionContext::new_system());
+        let mut state = ShadowState::new(persistence, ctx, Some(10000.0));
+        assert_eq!(state.get_position("BTC/USDT").unwrap().size, dec!(0.1));
+
+        let events = state.confirm_execution(
+            "sig-redeliver",
+            "fill-1",
+            dec!(50000.0),
+            dec!(0.1),
+            true,
+            dec!(0),
+            "USDT".to_string(),
+            "MOCK",
+        );
+        assert!(events.is_empty(), "Redelivered fill must be ignored");
+        assert_eq!(state.get_position("BTC/USDT").unwrap().size, dec!(0.1));
+
+        // A genuinely new fill still applies
+        state.confirm_execution(
+            "sig-redeliver",
+            "fill-2",
+            dec!(50000.0),
+            dec!(0.1),
+            true,
+            dec!(0),
+            "USDT".to_string(),
+            "MOCK",
+        );
+        assert_eq!(state.get_position("BTC/USDT").unwrap().size, dec!(0.2));
+    }
+
+    let _ = std::fs::remove_file(db_path);
+}
@@ -2,6 +2,7 @@ use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use std::sync::Arc;
 use titan_execution_rs::context::ExecutionContext;
+use titan_execution_rs::exchange::adapter::OrderStatus;
 use titan_execution_rs::model::{Intent, IntentStatus, IntentType};
 use titan_execution_rs::persistence::redb_store::RedbStore;
 use titan_execution_rs::persistence::store::PersistenceStore;
@@ -101,9 +102,9 @@ fn test_aggregation_partial_fill() {
         .iter()
         .find(|c| c.execution_order_id == "fill-1")
         .unwrap();
-    assert_eq!(child_1.status, "FILLED"); // Or FILLED logic depending on EXACT match of size
-                                          // In my logic: if fill_size < child.size -> PARTIALLY_FILLED.
-                                          // Here record_child_order size is 0.4, fill_size is 0.4. So FILLED.
+    assert_eq!(child_1.status, OrderStatus::Filled); // Or FILLED logic depending on EXACT match of size
+                                                     // In my logic: if fill_size < child.size -> PARTIALLY_FILLED.
+                                                     // Here record_child_order size is 0.4, fill_size is 0.4. So FILLED.
 
     // 2. Duplicate Fill (Idempotency)
     let events_dup = state.confirm_execution(
@@ -137,7 +138,7 @@ fn test_aggregation_partial_fill() {
         .iter()
         .find(|c| c.execution_order_id == "fill-2")
         .unwrap();
-    assert_eq!(child_2.status, "FILLED");
+    assert_eq!(child_2.status, OrderStatus::Filled);
 
     // 4. Verify Intent Removed (Executed)
     // Try to fill again -> should warn "Intent not found" and return empty